@@ -135,7 +135,7 @@ impl Dimension2 {
 
     /// Decodes an index value and returns a coordinate, unchecked.
     pub fn decode_point_unchecked(&self, index: usize) -> Point2 {
-        let y = index as i32 / self.height as i32;
+        let y = index as i32 / self.width as i32;
         let x = index as i32 % self.width as i32;
         Point2::new(x, y)
     }
@@ -163,6 +163,7 @@ impl From<Dimension2> for Vec2 {
     }
 }
 
+#[cfg(feature = "render")]
 impl From<Extent3d> for Dimension2 {
     fn from(ext: Extent3d) -> Dimension2 {
         Dimension2::new(ext.width, ext.height)
@@ -471,12 +472,14 @@ impl From<Dimension3> for Dimension2 {
     }
 }
 
+#[cfg(feature = "render")]
 impl From<Extent3d> for Dimension3 {
     fn from(ext: Extent3d) -> Dimension3 {
         Dimension3::new(ext.width, ext.height, ext.depth)
     }
 }
 
+#[cfg(feature = "render")]
 impl From<Dimension3> for Extent3d {
     fn from(dim: Dimension3) -> Extent3d {
         Extent3d::new(dim.width, dim.height, dim.depth)
@@ -40,15 +40,20 @@ pub mod prelude;
 #[no_implicit_prelude]
 mod lib {
     extern crate bevy_math;
+    #[cfg(feature = "render")]
     extern crate bevy_render;
+    #[cfg(feature = "glam")]
+    extern crate glam;
     #[cfg(feature = "serde")]
     extern crate serde;
     extern crate std;
 
-    pub(crate) use self::{
-        bevy_math::{Vec2, Vec3},
-        bevy_render::texture::Extent3d,
-    };
+    pub(crate) use bevy_math::{Vec2, Vec3};
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_render::texture::Extent3d;
+
+    #[cfg(feature = "glam")]
+    pub(crate) use glam::{IVec2, IVec3, UVec2, UVec3};
 
     #[cfg(feature = "serde")]
     pub(crate) use serde::{Deserialize, Serialize};
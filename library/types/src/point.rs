@@ -67,6 +67,28 @@ macro_rules! point2_glam_impl {
 
 point2_glam_impl!(Vec2);
 point2_glam_impl!(Vec3);
+#[cfg(feature = "glam")]
+point2_glam_impl!(IVec2);
+#[cfg(feature = "glam")]
+point2_glam_impl!(IVec3);
+#[cfg(feature = "glam")]
+point2_glam_impl!(UVec2);
+#[cfg(feature = "glam")]
+point2_glam_impl!(UVec3);
+
+#[cfg(feature = "glam")]
+impl From<Point2> for IVec2 {
+    fn from(point: Point2) -> IVec2 {
+        IVec2::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<&Point2> for IVec2 {
+    fn from(point: &Point2) -> IVec2 {
+        IVec2::new(point.x, point.y)
+    }
+}
 
 macro_rules! point2_arr_impl {
     ($arr: ty) => {
@@ -332,6 +354,24 @@ macro_rules! point3_glam_impl {
 }
 
 point3_glam_impl!(Vec3);
+#[cfg(feature = "glam")]
+point3_glam_impl!(IVec3);
+#[cfg(feature = "glam")]
+point3_glam_impl!(UVec3);
+
+#[cfg(feature = "glam")]
+impl From<Point3> for IVec3 {
+    fn from(point: Point3) -> IVec3 {
+        IVec3::new(point.x, point.y, point.z)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<&Point3> for IVec3 {
+    fn from(point: &Point3) -> IVec3 {
+        IVec3::new(point.x, point.y, point.z)
+    }
+}
 
 macro_rules! point3_arr_impl {
     ($vec: ty) => {
@@ -0,0 +1,342 @@
+//! Animated tiles.
+//!
+//! An [`AnimationClip`] is a sequence of sprite indexes, each shown for its
+//! own duration before the next one, which loops forever. Clips are added to
+//! a tilemap by name with [`Tilemap::add_animation_clip`] and any tile can
+//! then play one with [`Tilemap::set_animated_tile`]. Only the tiles whose
+//! sprite changed are redrawn each frame.
+//!
+//! Clips can also be defined in the metadata of a texture packer, see the
+//! `atlas` module.
+//!
+//! A clip can also be played only once with [`Tilemap::play_once`], after
+//! which the tile reverts, changes to another sprite or is removed, as chests
+//! that open or explosions do.
+//!
+//! [`Tilemap::play_once`]: crate::tilemap::Tilemap::play_once
+//!
+//! Every tile starts its clip from the beginning by default, so a field of
+//! the same animated tile plays in sync. A clip with another
+//! [`AnimationPhase`] starts each tile at its own point of the clip instead.
+//!
+//! [`Tilemap::add_animation_clip`]: crate::tilemap::Tilemap::add_animation_clip
+//! [`Tilemap::set_animated_tile`]: crate::tilemap::Tilemap::set_animated_tile
+//!
+//! Layers which are animated as a whole, such as water or lava, can instead
+//! be played entirely by the shader with a [`ShaderAnimation`] on the layer,
+//! see [`TilemapLayer::shader_animation`]. Their sprites scroll or cycle
+//! through frames without any work on the CPU and without the chunk meshes
+//! ever being redrawn.
+//!
+//! [`TilemapLayer::shader_animation`]: crate::tilemap::TilemapLayer::shader_animation
+//!
+//! # Animating water
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{animation::AnimationClip, prelude::*};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! tilemap.add_animation_clip("water_flow", AnimationClip::new(vec![4, 5, 6, 7], 0.2));
+//! tilemap.set_animated_tile((1, 1), 0, "water_flow").unwrap();
+//!
+//! assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 4);
+//! ```
+//!
+//! # Flowing water in the shader
+//! ```
+//! use bevy_tilemap::{animation::ShaderAnimation, prelude::*, testing::tilemap_test_harness};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! // The water scrolls down and the lava cycles through the four sprites
+//! // from its own.
+//! let water = ShaderAnimation::Scroll { x: 0.0, y: -8.0 };
+//! let lava = ShaderAnimation::Frames { count: 4, fps: 6.0 };
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .add_layer(TilemapLayer { shader_animation: Some(water), ..Default::default() }, 0)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, shader_animation: Some(lava), ..Default::default() }, 1)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+//! tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 4, z_order: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! // The tiles are left as they are, as the shader animates them.
+//! assert_eq!(harness.tilemap(entity).unwrap().get_tile((1, 0), 1).unwrap().index, 4);
+//! ```
+
+use crate::lib::*;
+
+/// The duration of a frame of a clip, if none is given.
+pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+/// A frame of an animation clip.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AnimationFrame {
+    /// The sprite index of the frame.
+    pub sprite_index: usize,
+    /// How long the frame is shown, in seconds.
+    pub duration: f32,
+}
+
+/// Where in its clip an animated tile starts playing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum AnimationPhase {
+    /// Every tile starts at the beginning of the clip.
+    #[default]
+    Synced,
+    /// Each tile starts at a point of the clip that is derived from its
+    /// point, which is the same every time it is animated.
+    Position,
+    /// Each tile starts at a random point of the clip.
+    Random,
+}
+
+impl AnimationPhase {
+    /// Returns how far into a clip of the duration, in seconds, the tile at the
+    /// point starts playing.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::animation::AnimationPhase;
+    ///
+    /// let point = (3, -2);
+    ///
+    /// assert_eq!(AnimationPhase::Synced.start_time(point, 1.0), 0.0);
+    /// assert_eq!(
+    ///     AnimationPhase::Position.start_time(point, 1.0),
+    ///     AnimationPhase::Position.start_time(point, 1.0)
+    /// );
+    /// assert!(AnimationPhase::Random.start_time(point, 1.0) < 1.0);
+    /// ```
+    pub fn start_time<P: Into<Point2>>(self, point: P, duration: f32) -> f32 {
+        let point: Point2 = point.into();
+        let hash = match self {
+            AnimationPhase::Synced => return 0.0,
+            AnimationPhase::Position => {
+                // A small integer hash, so that neighbouring points are far
+                // apart in the clip.
+                let mut hash = (point.x as u32).wrapping_mul(0x9e37_79b1)
+                    ^ (point.y as u32).wrapping_mul(0x85eb_ca77);
+                hash ^= hash >> 16;
+                hash = hash.wrapping_mul(0x7feb_352d);
+                hash ^ (hash >> 15)
+            }
+            AnimationPhase::Random => RandomState::new().hash_one(point) as u32,
+        };
+        // The top 24 bits fit in a float exactly, which keeps the fraction
+        // below one.
+        let fraction = (hash >> 8) as f32 / (1 << 24) as f32;
+        fraction * duration.max(0.0)
+    }
+}
+
+/// A looping sequence of sprites.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AnimationClip {
+    /// The frames of the clip, in the order they are shown.
+    pub frames: Vec<AnimationFrame>,
+    /// Where in the clip each tile starts playing.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub phase: AnimationPhase,
+}
+
+impl AnimationClip {
+    /// Constructs a new clip of sprite indexes which are each shown for the
+    /// same duration, in seconds.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::animation::AnimationClip;
+    ///
+    /// let clip = AnimationClip::new(vec![0, 1, 2], 0.25);
+    ///
+    /// assert_eq!(clip.frames.len(), 3);
+    /// assert_eq!(clip.duration(), 0.75);
+    /// ```
+    pub fn new(sprite_indexes: Vec<usize>, frame_duration: f32) -> AnimationClip {
+        AnimationClip {
+            frames: sprite_indexes
+                .into_iter()
+                .map(|sprite_index| AnimationFrame {
+                    sprite_index,
+                    duration: frame_duration,
+                })
+                .collect(),
+            phase: AnimationPhase::Synced,
+        }
+    }
+
+    /// Sets where in the clip each tile starts playing.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::animation::{AnimationClip, AnimationPhase};
+    ///
+    /// let clip = AnimationClip::new(vec![0, 1, 2, 3], 0.2).with_phase(AnimationPhase::Position);
+    ///
+    /// assert_eq!(clip.phase, AnimationPhase::Position);
+    /// ```
+    pub fn with_phase(mut self, phase: AnimationPhase) -> AnimationClip {
+        self.phase = phase;
+        self
+    }
+
+    /// Returns how long the clip takes to play once, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+
+    /// Returns the frame that is shown after playing for the time, in
+    /// seconds, or `None` if the clip has no frames.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::animation::AnimationClip;
+    ///
+    /// let clip = AnimationClip::new(vec![3, 4], 0.5);
+    ///
+    /// assert_eq!(clip.frame_at(0.2).unwrap().sprite_index, 3);
+    /// assert_eq!(clip.frame_at(0.7).unwrap().sprite_index, 4);
+    /// assert_eq!(clip.frame_at(1.2).unwrap().sprite_index, 3);
+    /// assert!(AnimationClip::default().frame_at(1.0).is_none());
+    /// ```
+    pub fn frame_at(&self, time: f32) -> Option<&AnimationFrame> {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return self.frames.first();
+        }
+        let mut time = time % duration;
+        for frame in self.frames.iter() {
+            if time < frame.duration {
+                return Some(frame);
+            }
+            time -= frame.duration;
+        }
+        self.frames.last()
+    }
+}
+
+/// What happens to a tile once it played a clip once.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TileAfter {
+    /// The tile goes back to the sprite it had before the clip, or is removed
+    /// if there was no tile.
+    Revert,
+    /// The tile is set to the sprite index.
+    Sprite(usize),
+    /// The tile is removed.
+    Remove,
+}
+
+/// A tile which is playing an animation clip.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct TileAnimation {
+    /// The name of the clip.
+    pub(crate) clip: String,
+    /// How long the clip has been playing, in seconds.
+    pub(crate) time: f32,
+    /// What happens to the tile after playing the clip once, or `None` if it
+    /// loops. This is never [`TileAfter::Revert`] as the sprite to revert to
+    /// is known when it starts.
+    pub(crate) then: Option<TileAfter>,
+}
+
+/// An animation of every tile of a layer which is played by the shader.
+///
+/// Sprites which are drawn with their own rect, see [`sprite`], are not
+/// animated.
+///
+/// [`sprite`]: crate::sprite
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ShaderAnimation {
+    /// The sprite of every tile scrolls within its own rect and wraps around,
+    /// at a speed in pixels per second with x to the right and y up.
+    Scroll {
+        /// The speed to the right, in pixels per second.
+        x: f32,
+        /// The speed upwards, in pixels per second.
+        y: f32,
+    },
+    /// Every tile cycles through the frames which follow its sprite index in
+    /// the texture atlas, starting at its own sprite.
+    Frames {
+        /// How many frames there are, including the sprite of the tile.
+        count: u32,
+        /// How many frames are shown per second.
+        fps: f32,
+    },
+}
+
+impl ShaderAnimation {
+    /// Returns the animation at a time in seconds as the animation uniform of
+    /// the shader: its kind, its two parameters and the time.
+    #[cfg(feature = "render")]
+    pub(crate) fn to_uniform(self, time: f32) -> Vec4 {
+        match self {
+            ShaderAnimation::Scroll { x, y } => Vec4::new(1.0, x, y, time),
+            ShaderAnimation::Frames { count, fps } => Vec4::new(2.0, count as f32, fps, time),
+        }
+    }
+}
+
+/// A fade of the opacity of a tilemap or of a layer over time.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Fade {
+    /// The opacity which the fade started at.
+    from: f32,
+    /// The opacity which the fade ends at.
+    to: f32,
+    /// How long the fade lasts, in seconds.
+    duration: f32,
+    /// How long the fade has been going, in seconds.
+    elapsed: f32,
+}
+
+impl Fade {
+    /// Constructs a new fade from an opacity to another over a duration in
+    /// seconds.
+    pub(crate) fn new(from: f32, to: f32, duration: f32) -> Fade {
+        Fade {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Moves the fade on by the time in seconds, returning the opacity and
+    /// `true` if the fade is done.
+    pub(crate) fn advance(&mut self, delta: f32) -> (f32, bool) {
+        self.elapsed += delta;
+        if self.elapsed >= self.duration {
+            return (self.to, true);
+        }
+        let t = self.elapsed / self.duration;
+        (self.from + (self.to - self.from) * t, false)
+    }
+}
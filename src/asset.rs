@@ -0,0 +1,291 @@
+//! Loading tilemaps from RON files.
+//!
+//! The [`TilemapRonFormat`] loads a `.tilemap.ron` file as a
+//! [`TilemapData`] asset, along with the `TextureAtlas` of the sprite sheet it
+//! names, so that maps are made in a file instead of in code. It is a map
+//! format of the [`format`] module. A loaded tilemap is rendered with a
+//! [`TilemapInstanceBundle`].
+//! With the hot reloading of the `AssetServer`, changes to the file are
+//! shown as soon as it is saved.
+//!
+//! This is behind the `ron-asset` feature. Bevy picks loaders by the last
+//! extension alone, so the format is added for every `.ron` file.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["ron-asset"] }
+//! ```
+//!
+//! Only the tile dimensions and the atlas must be set, the rest can be left
+//! out. The texture of the atlas is relative to the map file and is split
+//! into a grid of sprites of the tile dimensions, unless sprite dimensions
+//! are set. Tiles are on layer 0 and white unless told otherwise, and their
+//! chunks are made when they are inserted.
+//!
+//! ```ron
+//! (
+//!     name: Some("overworld"),
+//!     topology: Square,
+//!     chunk_dimensions: Some((8, 8)),
+//!     tile_dimensions: (32, 32),
+//!     atlas: (texture: "tiles.png", columns: 4, rows: 4),
+//!     layers: [(z_order: 0), (z_order: 1, kind: Sparse)],
+//!     chunks: [(1, 1)],
+//!     tiles: [
+//!         (point: (0, 0), sprite_index: 1),
+//!         (point: (1, 0), sprite_index: 2, z_order: 1, tint: (red: 1.0, green: 0.5, blue: 0.5, alpha: 1.0)),
+//!     ],
+//! )
+//! ```
+//!
+//! [`TilemapData`]: crate::instance::TilemapData
+//! [`format`]: crate::format
+//! [`TilemapInstanceBundle`]: crate::entity::TilemapInstanceBundle
+//!
+//! # Loading a map
+//! ```
+//! use bevy_asset::prelude::*;
+//! use bevy_ecs::prelude::*;
+//! use bevy_tilemap::{entity::TilemapInstanceBundle, prelude::*};
+//!
+//! fn setup(commands: &mut Commands, asset_server: Res<AssetServer>) {
+//!     asset_server.watch_for_changes().unwrap();
+//!
+//!     commands.spawn(TilemapInstanceBundle {
+//!         tilemap: asset_server.load("maps/overworld.tilemap.ron"),
+//!         ..Default::default()
+//!     });
+//! }
+//! ```
+
+use crate::{
+    animation::ShaderAnimation,
+    chunk::{render::GridTopology, LayerKind},
+    format::{AtlasGrid, TilemapFormat, TilemapPrototype},
+    lib::*,
+    tilemap::{ErrorKind, TilemapResult},
+    Tile, Tilemap, TilemapLayer,
+};
+
+/// A tilemap file.
+#[derive(Deserialize)]
+struct TilemapFile {
+    /// The name of the tilemap.
+    #[serde(default)]
+    name: Option<String>,
+    /// The topology of the tilemap, square if it is not set.
+    #[serde(default = "square")]
+    topology: GridTopology,
+    /// The dimensions of the tilemap in chunks, if it has any.
+    #[serde(default)]
+    dimensions: Option<(u32, u32)>,
+    /// The dimensions of the chunks in tiles, if they are not the default.
+    #[serde(default)]
+    chunk_dimensions: Option<(u32, u32)>,
+    /// The dimensions of the tiles in pixels.
+    tile_dimensions: (u32, u32),
+    /// The sprite sheet of the tiles.
+    atlas: AtlasFile,
+    /// The layers of the tilemap.
+    #[serde(default)]
+    layers: Vec<LayerFile>,
+    /// The points of the chunks to make, even if they have no tiles.
+    #[serde(default)]
+    chunks: Vec<(i32, i32)>,
+    /// The tiles of the tilemap.
+    #[serde(default)]
+    tiles: Vec<TileFile>,
+}
+
+/// A sprite sheet which is split into a grid of sprites.
+#[derive(Deserialize)]
+struct AtlasFile {
+    /// The path of the texture, relative to the tilemap file.
+    texture: String,
+    /// The number of columns of sprites.
+    columns: usize,
+    /// The number of rows of sprites.
+    rows: usize,
+    /// The dimensions of the sprites in pixels, if they are not those of the
+    /// tiles.
+    #[serde(default)]
+    sprite_dimensions: Option<(u32, u32)>,
+}
+
+/// A layer of a tilemap file.
+#[derive(Deserialize)]
+struct LayerFile {
+    /// The z order of the layer.
+    z_order: usize,
+    /// The kind of the layer, dense if it is not set.
+    #[serde(default = "dense")]
+    kind: LayerKind,
+    /// If the layer is also drawn in the mask pass.
+    #[serde(default)]
+    mask: bool,
+    /// If the layer is also drawn in the picking pass.
+    #[serde(default)]
+    picking: bool,
+    /// If the tiles of the layer are one-way platforms.
+    #[serde(default)]
+    one_way: bool,
+    /// If the tiles of the layer are sensors.
+    #[serde(default)]
+    sensor: bool,
+    /// If the tiles of the layer are sorted by their rows.
+    #[serde(default)]
+    y_sort: bool,
+    /// If the tiles of the layer whose sprites are walls are extruded.
+    #[serde(default)]
+    walls: bool,
+    /// The color the layer is multiplied by, white if it is not set.
+    #[serde(default = "white")]
+    color: Color,
+    /// The animation which the shader plays on the layer, if any.
+    #[serde(default)]
+    shader_animation: Option<ShaderAnimation>,
+}
+
+/// A tile of a tilemap file.
+#[derive(Deserialize)]
+struct TileFile {
+    /// The point of the tile.
+    point: (i32, i32),
+    /// The sprite index of the tile.
+    sprite_index: usize,
+    /// The z order of the tile.
+    #[serde(default)]
+    z_order: usize,
+    /// The tint of the tile, white if it is not set.
+    #[serde(default = "white")]
+    tint: Color,
+    /// The reserved data of the tile for custom shaders.
+    #[serde(default)]
+    extra: u16,
+    /// The offset of the tile from its point in pixels.
+    #[serde(default)]
+    offset: (f32, f32),
+}
+
+/// The topology of tilemap files which do not set one.
+fn square() -> GridTopology {
+    GridTopology::Square
+}
+
+/// The kind of the layers of tilemap files which do not set one.
+fn dense() -> LayerKind {
+    LayerKind::Dense
+}
+
+/// The tint of the tiles of tilemap files which do not set one.
+fn white() -> Color {
+    Color::WHITE
+}
+
+impl From<TilemapFile> for TilemapPrototype {
+    fn from(file: TilemapFile) -> TilemapPrototype {
+        let dimension = |(width, height): (u32, u32)| Dimension2::new(width, height);
+        TilemapPrototype {
+            name: file.name,
+            topology: file.topology,
+            dimensions: file.dimensions.map(dimension),
+            chunk_dimensions: file.chunk_dimensions.map(dimension),
+            tile_dimensions: dimension(file.tile_dimensions),
+            atlas: Some(AtlasGrid {
+                texture: file.atlas.texture,
+                columns: file.atlas.columns,
+                rows: file.atlas.rows,
+                sprite_dimensions: file.atlas.sprite_dimensions.map(dimension),
+            }),
+            layers: file
+                .layers
+                .into_iter()
+                .map(|layer| {
+                    let tilemap_layer = TilemapLayer {
+                        kind: layer.kind,
+                        mask: layer.mask,
+                        picking: layer.picking,
+                        one_way: layer.one_way,
+                        sensor: layer.sensor,
+                        y_sort: layer.y_sort,
+                        walls: layer.walls,
+                        color: layer.color,
+                        shader_animation: layer.shader_animation,
+                        ..Default::default()
+                    };
+                    (layer.z_order, tilemap_layer)
+                })
+                .collect(),
+            chunks: file.chunks.into_iter().map(Point2::from).collect(),
+            tiles: file
+                .tiles
+                .into_iter()
+                .map(|tile| Tile {
+                    point: tile.point.into(),
+                    z_order: tile.z_order,
+                    sprite_index: tile.sprite_index,
+                    tint: tile.tint,
+                    extra: tile.extra,
+                    offset: Vec2::new(tile.offset.0, tile.offset.1),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The format of `.tilemap.ron` files, which is added by the
+/// `Tilemap2DPlugin`.
+#[derive(Default)]
+pub struct TilemapRonFormat;
+
+impl TilemapFormat for TilemapRonFormat {
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+
+    fn parse(&self, bytes: &[u8]) -> TilemapResult<TilemapPrototype> {
+        let file: TilemapFile =
+            ron::de::from_bytes(bytes).map_err(|e| ErrorKind::InvalidMapFile(e.to_string()))?;
+        Ok(file.into())
+    }
+}
+
+/// Reads a tilemap from the contents of a `.tilemap.ron` file, drawn with
+/// the texture atlas instead of the atlas of the file. Every chunk of the
+/// tilemap is spawned.
+///
+/// # Examples
+/// ```
+/// use bevy_asset::{prelude::*, HandleId};
+/// use bevy_sprite::prelude::*;
+/// use bevy_tilemap::asset::tilemap_from_ron;
+///
+/// // In production use a strong handle from an actual source.
+/// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+///
+/// let ron = r#"(
+///     chunk_dimensions: Some((4, 4)),
+///     tile_dimensions: (32, 32),
+///     atlas: (texture: "tiles.png", columns: 4, rows: 4),
+///     layers: [(z_order: 0), (z_order: 1, kind: Sparse)],
+///     tiles: [(point: (0, 0), sprite_index: 1), (point: (5, 0), sprite_index: 2, z_order: 1)],
+/// )"#;
+/// let mut tilemap = tilemap_from_ron(ron.as_bytes(), texture_atlas_handle).unwrap();
+///
+/// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 1);
+/// assert_eq!(tilemap.get_tile((5, 0), 1).unwrap().index, 2);
+/// assert!(tilemap.contains_chunk((1, 0)));
+///
+/// assert!(tilemap_from_ron(b"(tile_dimensions: (32, 32))", Handle::default()).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file is not a valid tilemap file or its tiles can
+/// not be inserted.
+pub fn tilemap_from_ron(
+    bytes: &[u8],
+    texture_atlas: Handle<TextureAtlas>,
+) -> TilemapResult<Tilemap> {
+    TilemapRonFormat.parse(bytes)?.into_tilemap(texture_atlas)
+}
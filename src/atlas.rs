@@ -0,0 +1,699 @@
+//! Loading texture atlases from the metadata of texture packers.
+//!
+//! Texture packers export an image of the packed sprites along with a file
+//! which describes where each sprite is. The [`TilemapAtlasLoader`] loads such
+//! a file as a [`TilemapAtlas`], which holds the `TextureAtlas` of the image,
+//! the sprite index of every sprite name and the [`SpriteRect`]s of the
+//! sprites that were trimmed or rotated.
+//!
+//! The following formats are supported, and they are told apart by their
+//! contents. Each must have the `.json` extension.
+//!
+//! * TexturePacker JSON, both the hash and the array flavour, as well as the
+//!   JSON of Aseprite which is the same. The sprites are indexed in the order
+//!   that they are in the file.
+//! * crunch JSON, of which only the first texture is used. crunch does not
+//!   store the size of its textures, so it is read from the PNG it exported.
+//!
+//! This is behind the `atlas` feature.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["atlas"] }
+//! ```
+//!
+//! Animations in the metadata become [`AnimationClip`]s of the same name.
+//! These are the `animations` of TexturePacker, whose frames are shown for
+//! the [`DEFAULT_FRAME_DURATION`], and the `frameTags` of Aseprite, whose
+//! frames are shown for their `duration`.
+//!
+//! [`SpriteRect`]: crate::sprite::SpriteRect
+//! [`AnimationClip`]: crate::animation::AnimationClip
+//! [`DEFAULT_FRAME_DURATION`]: crate::animation::DEFAULT_FRAME_DURATION
+//!
+//! # Using a loaded atlas
+//! ```
+//! use bevy_asset::prelude::*;
+//! use bevy_ecs::prelude::*;
+//! use bevy_tilemap::{atlas::TilemapAtlas, prelude::*};
+//!
+//! struct SpriteSheet(Handle<TilemapAtlas>);
+//!
+//! fn setup(asset_server: Res<AssetServer>, commands: &mut Commands) {
+//!     commands.insert_resource(SpriteSheet(asset_server.load("tiles.json")));
+//! }
+//!
+//! fn build_tilemap(sprite_sheet: Res<SpriteSheet>, atlases: Res<Assets<TilemapAtlas>>) {
+//!     let atlas = if let Some(atlas) = atlases.get(&sprite_sheet.0) {
+//!         atlas
+//!     } else {
+//!         return;
+//!     };
+//!
+//!     let mut tilemap = TilemapBuilder::new()
+//!         .texture_atlas(atlas.texture_atlas.clone())
+//!         .tile_dimensions(32, 32)
+//!         .finish()
+//!         .unwrap();
+//!     atlas.apply_sprite_rects(&mut tilemap);
+//!     atlas.apply_animation_clips(&mut tilemap);
+//!
+//!     let grass = atlas.sprite_index("grass.png").unwrap();
+//!     tilemap
+//!         .insert_tile(Tile { point: (0, 0), sprite_index: grass, ..Default::default() })
+//!         .unwrap();
+//!     tilemap.set_animated_tile((1, 0), 0, "water_flow").unwrap();
+//! }
+//! ```
+
+use crate::{
+    animation::{AnimationClip, AnimationFrame, DEFAULT_FRAME_DURATION},
+    lib::*,
+    sprite::SpriteRect,
+    tilemap::{ErrorKind, TilemapResult},
+    Tilemap,
+};
+
+/// The label of the `TextureAtlas` of a loaded tilemap atlas.
+pub const TEXTURE_ATLAS_LABEL: &str = "texture_atlas";
+
+/// A texture atlas along with the names and rects of its sprites.
+#[derive(Debug)]
+pub struct TilemapAtlas {
+    /// The handle of the texture atlas.
+    pub texture_atlas: Handle<TextureAtlas>,
+    /// The sprite index of each sprite name.
+    pub names: HashMap<String, usize>,
+    /// The rects of the sprites that were trimmed or rotated in the atlas.
+    pub sprite_rects: HashMap<usize, SpriteRect>,
+    /// The animation clips of the atlas by their names.
+    pub animation_clips: HashMap<String, AnimationClip>,
+}
+
+impl TypeUuid for TilemapAtlas {
+    const TYPE_UUID: Uuid = Uuid::from_u128(184013360879561446949908423149562742448);
+}
+
+impl TilemapAtlas {
+    /// Returns the sprite index of a sprite name, if it is in the atlas.
+    pub fn sprite_index(&self, name: &str) -> Option<usize> {
+        self.names.get(name).cloned()
+    }
+
+    /// Sets the sprite rects of the atlas on a tilemap, so that its trimmed
+    /// and rotated sprites are drawn as they were before they were packed.
+    pub fn apply_sprite_rects(&self, tilemap: &mut Tilemap) {
+        for (index, sprite_rect) in self.sprite_rects.iter() {
+            tilemap.set_sprite_rect(*index, *sprite_rect);
+        }
+    }
+
+    /// Adds the animation clips of the atlas to a tilemap, replacing those
+    /// with the same names.
+    pub fn apply_animation_clips(&self, tilemap: &mut Tilemap) {
+        for (name, clip) in self.animation_clips.iter() {
+            tilemap.add_animation_clip(name.clone(), clip.clone());
+        }
+    }
+}
+
+/// A sprite in the metadata of a texture packer.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AtlasFrame {
+    /// The name of the sprite, which is usually the file it was packed from.
+    pub name: String,
+    /// Where the sprite is in the atlas and how it is drawn.
+    pub sprite_rect: SpriteRect,
+}
+
+impl AtlasFrame {
+    /// Returns if the sprite needs its sprite rect to be drawn correctly.
+    fn is_packed(&self) -> bool {
+        self.sprite_rect.rotated || self.sprite_rect.trimmed_size.is_some()
+    }
+}
+
+/// The metadata of a texture packer.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AtlasMetadata {
+    /// The path of the image, relative to the metadata.
+    pub image: String,
+    /// The size of the image in pixels, if it is known.
+    pub size: Option<Vec2>,
+    /// The sprites in the order of their sprite indexes.
+    pub frames: Vec<AtlasFrame>,
+    /// The animation clips by their names.
+    pub animation_clips: HashMap<String, AnimationClip>,
+}
+
+impl AtlasMetadata {
+    /// Parses the metadata of any of the supported texture packers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata is not of a supported format, or if
+    /// it is missing any of the values that are needed.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::atlas::AtlasMetadata;
+    ///
+    /// let json = br#"{
+    ///     "frames": {
+    ///         "wall.png": {
+    ///             "frame": { "x": 32, "y": 0, "w": 32, "h": 32 },
+    ///             "rotated": false,
+    ///             "trimmed": false,
+    ///             "spriteSourceSize": { "x": 0, "y": 0, "w": 32, "h": 32 },
+    ///             "sourceSize": { "w": 32, "h": 32 }
+    ///         },
+    ///         "grass.png": {
+    ///             "frame": { "x": 0, "y": 0, "w": 28, "h": 30 },
+    ///             "rotated": true,
+    ///             "trimmed": true,
+    ///             "spriteSourceSize": { "x": 2, "y": 1, "w": 28, "h": 30 },
+    ///             "sourceSize": { "w": 32, "h": 32 }
+    ///         }
+    ///     },
+    ///     "meta": { "image": "tiles.png", "size": { "w": 64, "h": 32 } }
+    /// }"#;
+    ///
+    /// let metadata = AtlasMetadata::from_json(json).unwrap();
+    ///
+    /// assert_eq!(metadata.image, "tiles.png");
+    /// assert_eq!(metadata.size, Some(Vec2::new(64.0, 32.0)));
+    /// assert_eq!(metadata.frames[1].name, "grass.png");
+    ///
+    /// let grass = metadata.frames[1].sprite_rect;
+    /// assert!(grass.rotated);
+    /// assert_eq!(grass.max, Vec2::new(30.0, 28.0));
+    /// assert_eq!(grass.offset, Vec2::new(2.0, 1.0));
+    /// assert_eq!(grass.source_size(), Vec2::new(32.0, 32.0));
+    ///
+    /// // The sprites of crunch are in the order they are in the file.
+    /// let json = br#"{
+    ///     "textures": [{
+    ///         "name": "tiles",
+    ///         "images": [
+    ///             { "n": "wall", "x": 0, "y": 0, "w": 32, "h": 32, "fx": 0, "fy": 0, "fw": 32, "fh": 32 },
+    ///             { "n": "grass", "x": 32, "y": 0, "w": 28, "h": 30, "fx": -2, "fy": -1, "fw": 32, "fh": 32 }
+    ///         ]
+    ///     }]
+    /// }"#;
+    ///
+    /// let metadata = AtlasMetadata::from_json(json).unwrap();
+    ///
+    /// assert_eq!(metadata.image, "tiles.png");
+    /// assert_eq!(metadata.size, None);
+    /// assert_eq!(metadata.frames[1].name, "grass");
+    ///
+    /// let grass = metadata.frames[1].sprite_rect;
+    /// assert!(!grass.rotated);
+    /// assert_eq!(grass.offset, Vec2::new(2.0, 1.0));
+    /// assert_eq!(metadata.frames[0].sprite_rect.trimmed_size, None);
+    ///
+    /// // The frame tags of Aseprite are animation clips.
+    /// let json = br#"{
+    ///     "frames": [
+    ///         { "filename": "water 0", "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+    ///         { "filename": "water 1", "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "duration": 100 },
+    ///         { "filename": "water 2", "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "duration": 300 }
+    ///     ],
+    ///     "meta": {
+    ///         "image": "water.png",
+    ///         "size": { "w": 48, "h": 16 },
+    ///         "frameTags": [{ "name": "water_flow", "from": 0, "to": 2, "direction": "pingpong" }]
+    ///     }
+    /// }"#;
+    ///
+    /// let metadata = AtlasMetadata::from_json(json).unwrap();
+    /// let water_flow = &metadata.animation_clips["water_flow"];
+    ///
+    /// let sprite_indexes: Vec<usize> = water_flow.frames.iter().map(|frame| frame.sprite_index).collect();
+    /// assert_eq!(sprite_indexes, vec![0, 1, 2, 1]);
+    /// assert!((water_flow.duration() - 0.6).abs() < 0.001);
+    /// ```
+    pub fn from_json(bytes: &[u8]) -> TilemapResult<AtlasMetadata> {
+        let value: JsonValue = serde_json::from_slice(bytes).map_err(invalid_metadata)?;
+        if value.get("frames").is_some() {
+            // The order of the sprites is lost in a `JsonValue`.
+            AtlasMetadata::from_texture_packer(bytes)
+        } else if value.get("textures").is_some() {
+            AtlasMetadata::from_crunch(value)
+        } else {
+            Err(ErrorKind::InvalidAtlasMetadata(String::from(
+                "expected the `frames` of TexturePacker or the `textures` of crunch",
+            ))
+            .into())
+        }
+    }
+
+    /// Parses the metadata of TexturePacker, in the hash or array flavour.
+    fn from_texture_packer(bytes: &[u8]) -> TilemapResult<AtlasMetadata> {
+        let sheet: TexturePackerSheet = serde_json::from_slice(bytes).map_err(invalid_metadata)?;
+        let texture_packer_frames = match sheet.frames {
+            TexturePackerFrames::Hash(OrderedFrames(frames)) => frames,
+            TexturePackerFrames::Array(frames) => frames
+                .into_iter()
+                .map(|frame| (frame.filename.clone().unwrap_or_default(), frame))
+                .collect(),
+        };
+        let durations: Vec<f32> = texture_packer_frames
+            .iter()
+            .map(|(_, frame)| {
+                frame
+                    .duration
+                    .map(|milliseconds| milliseconds / 1000.0)
+                    .unwrap_or(DEFAULT_FRAME_DURATION)
+            })
+            .collect();
+        let frames: Vec<AtlasFrame> = texture_packer_frames
+            .iter()
+            .map(|(name, frame)| frame.to_atlas_frame(name.clone()))
+            .collect();
+
+        let clip = |name: &str, sprite_indexes: Vec<usize>| {
+            let mut clip_frames = Vec::new();
+            for sprite_index in sprite_indexes.into_iter() {
+                if let Some(duration) = durations.get(sprite_index) {
+                    clip_frames.push(AnimationFrame {
+                        sprite_index,
+                        duration: *duration,
+                    });
+                } else {
+                    warn!(
+                        "frame {} of animation \"{}\" does not exist, skipping",
+                        sprite_index, name
+                    );
+                }
+            }
+            AnimationClip {
+                frames: clip_frames,
+                ..Default::default()
+            }
+        };
+        let mut animation_clips = HashMap::default();
+        for (name, frame_names) in sheet.animations.iter() {
+            let sprite_indexes = frame_names
+                .iter()
+                .filter_map(|frame_name| frames.iter().position(|frame| &frame.name == frame_name))
+                .collect();
+            animation_clips.insert(name.clone(), clip(name, sprite_indexes));
+        }
+        for frame_tag in sheet.meta.frame_tags.iter() {
+            let mut sprite_indexes: Vec<usize> = (frame_tag.from..=frame_tag.to).collect();
+            match frame_tag.direction.as_str() {
+                "reverse" => sprite_indexes.reverse(),
+                "pingpong" if frame_tag.to > frame_tag.from + 1 => {
+                    sprite_indexes.extend((frame_tag.from + 1..frame_tag.to).rev())
+                }
+                _ => {}
+            }
+            animation_clips.insert(
+                frame_tag.name.clone(),
+                clip(&frame_tag.name, sprite_indexes),
+            );
+        }
+
+        Ok(AtlasMetadata {
+            image: sheet.meta.image,
+            size: Some(Vec2::new(sheet.meta.size.w, sheet.meta.size.h)),
+            frames,
+            animation_clips,
+        })
+    }
+
+    /// Parses the metadata of crunch, using only its first texture.
+    fn from_crunch(value: JsonValue) -> TilemapResult<AtlasMetadata> {
+        let crunch: CrunchAtlas = serde_json::from_value(value).map_err(invalid_metadata)?;
+        let mut textures = crunch.textures.into_iter();
+        let texture = if let Some(texture) = textures.next() {
+            texture
+        } else {
+            return Err(
+                ErrorKind::InvalidAtlasMetadata(String::from("there are no textures")).into(),
+            );
+        };
+        if textures.next().is_some() {
+            warn!(
+                "only the first texture \"{}\" of the crunch atlas is used",
+                texture.name
+            );
+        }
+
+        Ok(AtlasMetadata {
+            image: ::std::format!("{}.png", texture.name),
+            size: None,
+            frames: texture
+                .images
+                .into_iter()
+                .map(CrunchImage::into_atlas_frame)
+                .collect(),
+            animation_clips: HashMap::default(),
+        })
+    }
+
+    /// Returns the size of the image from the sprites, which is exact only if
+    /// the texture packer did not pad it.
+    fn frames_size(&self) -> Vec2 {
+        self.frames
+            .iter()
+            .fold(Vec2::zero(), |size, frame| size.max(frame.sprite_rect.max))
+    }
+
+    /// Constructs a texture atlas of the sprites, in the order of their sprite
+    /// indexes.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_render::texture::Texture;
+    /// use bevy_tilemap::atlas::AtlasMetadata;
+    ///
+    /// let json = br#"{
+    ///     "frames": [
+    ///         { "filename": "water", "frame": { "x": 0, "y": 0, "w": 16, "h": 16 } },
+    ///         { "filename": "sand", "frame": { "x": 16, "y": 0, "w": 16, "h": 16 } }
+    ///     ],
+    ///     "meta": { "image": "tiles.png", "size": { "w": 32, "h": 16 } }
+    /// }"#;
+    /// let metadata = AtlasMetadata::from_json(json).unwrap();
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_handle = Handle::weak(HandleId::random::<Texture>());
+    ///
+    /// let texture_atlas = metadata.texture_atlas(texture_handle, Vec2::new(32.0, 16.0));
+    ///
+    /// assert_eq!(texture_atlas.len(), 2);
+    /// assert_eq!(texture_atlas.textures[1].min, Vec2::new(16.0, 0.0));
+    /// ```
+    pub fn texture_atlas(&self, texture: Handle<Texture>, size: Vec2) -> TextureAtlas {
+        let mut texture_atlas = TextureAtlas::new_empty(texture, size);
+        for frame in self.frames.iter() {
+            texture_atlas.add_texture(Rect {
+                min: frame.sprite_rect.min,
+                max: frame.sprite_rect.max,
+            });
+        }
+        texture_atlas
+    }
+}
+
+/// Turns a JSON error into an invalid metadata error.
+fn invalid_metadata(err: serde_json::Error) -> ErrorKind {
+    ErrorKind::InvalidAtlasMetadata(err.to_string())
+}
+
+/// Returns the size of a PNG image from its header.
+fn png_size(bytes: &[u8]) -> Option<Vec2> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    if bytes.get(..8)? != SIGNATURE || bytes.get(12..16)? != *b"IHDR" {
+        return None;
+    }
+    let read_u32 = |offset: usize| {
+        let mut value = 0u32;
+        for byte in bytes.get(offset..offset + 4)?.iter() {
+            value = value << 8 | u32::from(*byte);
+        }
+        Some(value)
+    };
+    Some(Vec2::new(read_u32(16)? as f32, read_u32(20)? as f32))
+}
+
+/// A rect of TexturePacker, in pixels.
+#[derive(Deserialize)]
+struct TexturePackerRect {
+    /// The left of the rect.
+    x: f32,
+    /// The top of the rect.
+    y: f32,
+    /// The width of the rect.
+    w: f32,
+    /// The height of the rect.
+    h: f32,
+}
+
+/// A size of TexturePacker, in pixels.
+#[derive(Deserialize)]
+struct TexturePackerSize {
+    /// The width.
+    w: f32,
+    /// The height.
+    h: f32,
+}
+
+/// A sprite of TexturePacker.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TexturePackerFrame {
+    /// The name of the sprite, only in the array flavour.
+    filename: Option<String>,
+    /// The rect in the atlas, with the size before it was rotated.
+    frame: TexturePackerRect,
+    /// If the sprite was rotated clockwise in the atlas.
+    #[serde(default)]
+    rotated: bool,
+    /// If the transparent borders of the sprite were trimmed.
+    #[serde(default)]
+    trimmed: bool,
+    /// The rect of the trimmed sprite in its source, from the top left.
+    sprite_source_size: Option<TexturePackerRect>,
+    /// The size of the source of the sprite.
+    source_size: Option<TexturePackerSize>,
+    /// How long the sprite is shown in an animation, in milliseconds. This
+    /// is only written by Aseprite.
+    duration: Option<f32>,
+}
+
+impl TexturePackerFrame {
+    /// Converts the sprite into a sprite with the name.
+    fn to_atlas_frame(&self, name: String) -> AtlasFrame {
+        let frame = &self.frame;
+        let min = Vec2::new(frame.x, frame.y);
+        let mut sprite_rect = if self.rotated {
+            SpriteRect::new(min, min + Vec2::new(frame.h, frame.w)).rotated()
+        } else {
+            SpriteRect::new(min, min + Vec2::new(frame.w, frame.h))
+        };
+        if let (true, Some(trim), Some(source)) =
+            (self.trimmed, &self.sprite_source_size, &self.source_size)
+        {
+            sprite_rect = sprite_rect.trimmed(
+                Vec2::new(trim.x, source.h - trim.y - trim.h),
+                Vec2::new(source.w, source.h),
+            );
+        }
+
+        AtlasFrame { name, sprite_rect }
+    }
+}
+
+/// The sprites of the hash flavour of TexturePacker, in the order that they
+/// are in the file.
+struct OrderedFrames(Vec<(String, TexturePackerFrame)>);
+
+impl<'de> Deserialize<'de> for OrderedFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<OrderedFrames, D::Error> {
+        deserializer.deserialize_map(OrderedFramesVisitor)
+    }
+}
+
+/// Visits the sprites of the hash flavour of TexturePacker in order.
+struct OrderedFramesVisitor;
+
+impl<'de> Visitor<'de> for OrderedFramesVisitor {
+    type Value = OrderedFrames;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "a map of sprite names to sprites")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<OrderedFrames, A::Error> {
+        let mut frames = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            frames.push(entry);
+        }
+        Ok(OrderedFrames(frames))
+    }
+}
+
+/// The sprites of TexturePacker in either flavour.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TexturePackerFrames {
+    /// The sprites by their names.
+    Hash(OrderedFrames),
+    /// The sprites with their names.
+    Array(Vec<TexturePackerFrame>),
+}
+
+/// The image of TexturePacker.
+#[derive(Deserialize)]
+struct TexturePackerMeta {
+    /// The path of the image.
+    image: String,
+    /// The size of the image.
+    size: TexturePackerSize,
+    /// The animations of Aseprite.
+    #[serde(default, rename = "frameTags")]
+    frame_tags: Vec<FrameTag>,
+}
+
+/// An animation of Aseprite.
+#[derive(Deserialize)]
+struct FrameTag {
+    /// The name of the animation.
+    name: String,
+    /// The index of the first frame.
+    from: usize,
+    /// The index of the last frame.
+    to: usize,
+    /// Either `forward`, `reverse` or `pingpong`.
+    #[serde(default)]
+    direction: String,
+}
+
+/// The metadata of TexturePacker.
+#[derive(Deserialize)]
+struct TexturePackerSheet {
+    /// The sprites.
+    frames: TexturePackerFrames,
+    /// The image.
+    meta: TexturePackerMeta,
+    /// The animations of TexturePacker, as the names of their sprites.
+    #[serde(default)]
+    animations: BTreeMap<String, Vec<String>>,
+}
+
+/// A sprite of crunch.
+#[derive(Deserialize)]
+struct CrunchImage {
+    /// The name of the sprite.
+    n: String,
+    /// The left of the sprite in the atlas.
+    x: f32,
+    /// The top of the sprite in the atlas.
+    y: f32,
+    /// The width of the sprite before it was rotated.
+    w: f32,
+    /// The height of the sprite before it was rotated.
+    h: f32,
+    /// The negated offset of the trimmed sprite from the left of its source.
+    fx: Option<f32>,
+    /// The negated offset of the trimmed sprite from the top of its source.
+    fy: Option<f32>,
+    /// The width of the source of the sprite.
+    fw: Option<f32>,
+    /// The height of the source of the sprite.
+    fh: Option<f32>,
+    /// If the sprite was rotated, which is written as a number or a bool.
+    r: Option<JsonValue>,
+}
+
+impl CrunchImage {
+    /// Converts the sprite into a sprite of the atlas.
+    fn into_atlas_frame(self) -> AtlasFrame {
+        let min = Vec2::new(self.x, self.y);
+        let rotated = match self.r {
+            Some(JsonValue::Bool(rotated)) => rotated,
+            Some(JsonValue::Number(rotated)) => rotated.as_u64() != Some(0),
+            _ => false,
+        };
+        let mut sprite_rect = if rotated {
+            SpriteRect::new(min, min + Vec2::new(self.h, self.w)).rotated()
+        } else {
+            SpriteRect::new(min, min + Vec2::new(self.w, self.h))
+        };
+        if let (Some(fx), Some(fy), Some(fw), Some(fh)) = (self.fx, self.fy, self.fw, self.fh) {
+            let is_trimmed = fx != 0.0 || fy != 0.0 || fw != self.w || fh != self.h;
+            if is_trimmed {
+                sprite_rect =
+                    sprite_rect.trimmed(Vec2::new(-fx, fh + fy - self.h), Vec2::new(fw, fh));
+            }
+        }
+
+        AtlasFrame {
+            name: self.n,
+            sprite_rect,
+        }
+    }
+}
+
+/// A texture of crunch.
+#[derive(Deserialize)]
+struct CrunchTexture {
+    /// The name of the image, without its extension.
+    name: String,
+    /// The sprites in the image.
+    images: Vec<CrunchImage>,
+}
+
+/// The metadata of crunch.
+#[derive(Deserialize)]
+struct CrunchAtlas {
+    /// The images of the atlas.
+    textures: Vec<CrunchTexture>,
+}
+
+/// The asset loader of tilemap atlases, which is added by the
+/// `Tilemap2DPlugin`.
+#[derive(Default)]
+pub struct TilemapAtlasLoader;
+
+impl AssetLoader for TilemapAtlasLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let metadata = AtlasMetadata::from_json(bytes)?;
+            let image_path = match load_context.path().parent() {
+                Some(parent) => parent.join(&metadata.image),
+                None => PathBuf::from(&metadata.image),
+            };
+            let size = match metadata.size {
+                Some(size) => size,
+                None => {
+                    let image = load_context.read_asset_bytes(&image_path).await?;
+                    png_size(&image).unwrap_or_else(|| metadata.frames_size())
+                }
+            };
+
+            let texture = load_context.get_handle(AssetPath::new_ref(&image_path, None));
+            let texture_atlas = metadata.texture_atlas(texture, size);
+            load_context.set_labeled_asset(
+                TEXTURE_ATLAS_LABEL,
+                LoadedAsset::new(texture_atlas)
+                    .with_dependency(AssetPath::new(image_path.clone(), None)),
+            );
+
+            let texture_atlas = load_context.get_handle(AssetPath::new_ref(
+                load_context.path(),
+                Some(TEXTURE_ATLAS_LABEL),
+            ));
+            let mut names = HashMap::default();
+            let mut sprite_rects = HashMap::default();
+            for (index, frame) in metadata.frames.into_iter().enumerate() {
+                if frame.is_packed() {
+                    sprite_rects.insert(index, frame.sprite_rect);
+                }
+                names.insert(frame.name, index);
+            }
+            load_context.set_default_asset(LoadedAsset::new(TilemapAtlas {
+                texture_atlas,
+                names,
+                sprite_rects,
+                animation_clips: metadata.animation_clips,
+            }));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
@@ -0,0 +1,149 @@
+//! Custom per-tile vertex attributes of a layer.
+//!
+//! A channel is added to a layer with [`Tilemap::add_channel`] and holds a
+//! value for every tile of the layer, such as its wetness, growth stage or
+//! team color index. The values are put in the chunk meshes of the layer as a
+//! vertex attribute named after the channel, which a custom shader declares
+//! to read them. Tiles which were never given a value have zeros.
+//!
+//! [`Tilemap::add_channel`]: crate::tilemap::Tilemap::add_channel
+//!
+//! # Adding a channel
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{
+//!     channel::{ChannelKind, ChannelValue},
+//!     prelude::*,
+//! };
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Read in the vertex shader as `in float Vertex_Wetness;`.
+//! tilemap.add_channel(0, "Vertex_Wetness", ChannelKind::Float).unwrap();
+//! tilemap.insert_tile(Tile { point: (1, 1), ..Default::default() }).unwrap();
+//! tilemap.set_tile_channel((1, 1), 0, "Vertex_Wetness", 0.5).unwrap();
+//!
+//! assert_eq!(
+//!     tilemap.tile_channel((1, 1), 0, "Vertex_Wetness"),
+//!     Some(ChannelValue::Float(0.5))
+//! );
+//! ```
+
+use crate::lib::*;
+
+/// The type of the values of a channel in the shader.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ChannelKind {
+    /// A `float` for every tile.
+    Float,
+    /// A `vec4` for every tile.
+    Vec4,
+}
+
+/// The value of a tile in a channel.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ChannelValue {
+    /// The value of a [`ChannelKind::Float`] channel.
+    Float(f32),
+    /// The value of a [`ChannelKind::Vec4`] channel.
+    Vec4([f32; 4]),
+}
+
+impl ChannelValue {
+    /// Returns the kind of channel which holds the value.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::channel::{ChannelKind, ChannelValue};
+    ///
+    /// assert_eq!(ChannelValue::from(1.0).kind(), ChannelKind::Float);
+    /// assert_eq!(ChannelValue::from([1.0; 4]).kind(), ChannelKind::Vec4);
+    /// ```
+    pub fn kind(&self) -> ChannelKind {
+        match self {
+            ChannelValue::Float(_) => ChannelKind::Float,
+            ChannelValue::Vec4(_) => ChannelKind::Vec4,
+        }
+    }
+
+    /// Returns the four components the value is stored as.
+    pub(crate) fn to_components(self) -> [f32; 4] {
+        match self {
+            ChannelValue::Float(value) => [value, 0.0, 0.0, 0.0],
+            ChannelValue::Vec4(value) => value,
+        }
+    }
+
+    /// Constructs the value of a channel of the kind from its components.
+    pub(crate) fn from_components(kind: ChannelKind, components: [f32; 4]) -> ChannelValue {
+        match kind {
+            ChannelKind::Float => ChannelValue::Float(components[0]),
+            ChannelKind::Vec4 => ChannelValue::Vec4(components),
+        }
+    }
+}
+
+impl From<f32> for ChannelValue {
+    fn from(value: f32) -> ChannelValue {
+        ChannelValue::Float(value)
+    }
+}
+
+impl From<[f32; 4]> for ChannelValue {
+    fn from(value: [f32; 4]) -> ChannelValue {
+        ChannelValue::Vec4(value)
+    }
+}
+
+/// A vertex attribute channel of a layer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TileChannel {
+    /// The name of the vertex attribute in the shader.
+    pub name: String,
+    /// The type of the values.
+    pub kind: ChannelKind,
+}
+
+/// Changes the values of the tiles of a chunk layer in the channel into a
+/// vertex attribute, with a value for each of the four vertices of a tile.
+#[cfg(feature = "render")]
+pub(crate) fn channel_to_attribute(
+    channel: &TileChannel,
+    area: usize,
+    values: Option<&Vec<[f32; 4]>>,
+) -> VertexAttributeValues {
+    let value_at = |index: usize| {
+        values
+            .and_then(|values| values.get(index))
+            .copied()
+            .unwrap_or([0.0; 4])
+    };
+    match channel.kind {
+        ChannelKind::Float => {
+            let mut attribute = Vec::with_capacity(area * 4);
+            for index in 0..area {
+                attribute.extend([value_at(index)[0]; 4].iter());
+            }
+            VertexAttributeValues::Float(attribute)
+        }
+        ChannelKind::Vec4 => {
+            let mut attribute = Vec::with_capacity(area * 4);
+            for index in 0..area {
+                attribute.extend([value_at(index); 4].iter());
+            }
+            VertexAttributeValues::Float4(attribute)
+        }
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(feature = "compress")]
+use crate::chunk::{
+    layer::{DecorationLayer, DenseLayer, LayerKindInner, SparseLayer, SpriteLayer},
+    raw_tile::RawTile,
+    ChunkActivity,
+};
+#[cfg(feature = "compress")]
+use crate::tilemap::ErrorKind;
+use crate::{chunk::Chunk, lib::*, save, store::ChunkEncoding, tilemap::TilemapResult};
+
+/// The magic number which zstd frames start with.
+#[cfg(feature = "compress")]
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// A chunk whose dense layers are run-length encoded.
+#[cfg(feature = "compress")]
+#[derive(Serialize, Deserialize)]
+struct PackedChunk {
+    /// The point of the chunk.
+    point: Point2,
+    /// The sprite layers of the chunk.
+    sprite_layers: Vec<Option<PackedLayer>>,
+    /// The user data of the chunk.
+    user_data: u128,
+    /// The activity of the chunk.
+    activity: ChunkActivity,
+    /// The elevation of the tiles which are raised, in pixels.
+    #[serde(default)]
+    heights: HashMap<usize, f32>,
+}
+
+/// A sprite layer whose dense tiles are run-length encoded.
+#[cfg(feature = "compress")]
+#[derive(Serialize, Deserialize)]
+struct PackedLayer {
+    /// The tiles of the layer.
+    tiles: PackedTiles,
+    /// The values of the tiles in the channels of the layer.
+    channels: HashMap<String, Vec<[f32; 4]>>,
+}
+
+/// The tiles of a packed sprite layer.
+#[cfg(feature = "compress")]
+#[derive(Serialize, Deserialize)]
+enum PackedTiles {
+    /// The runs of equal tiles of a dense layer, with their lengths.
+    Dense(Vec<(u32, RawTile)>),
+    /// A sparse layer, which is stored as it is.
+    Sparse(SparseLayer),
+    /// A decoration layer, which is stored as it is.
+    Decoration(DecorationLayer),
+}
+
+#[cfg(feature = "compress")]
+impl From<&Chunk> for PackedChunk {
+    fn from(chunk: &Chunk) -> PackedChunk {
+        let sprite_layers = chunk
+            .sprite_layers
+            .iter()
+            .map(|layer| {
+                layer.as_ref().map(|layer| {
+                    let tiles = match &layer.inner {
+                        LayerKindInner::Dense(dense) => PackedTiles::Dense(runs(dense.tiles())),
+                        LayerKindInner::Sparse(sparse) => PackedTiles::Sparse(sparse.clone()),
+                        LayerKindInner::Decoration(decoration) => {
+                            PackedTiles::Decoration(decoration.clone())
+                        }
+                    };
+                    PackedLayer {
+                        tiles,
+                        channels: layer.channels.clone(),
+                    }
+                })
+            })
+            .collect();
+        PackedChunk {
+            point: chunk.point,
+            sprite_layers,
+            user_data: chunk.user_data,
+            activity: chunk.activity,
+            heights: chunk.heights.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "compress")]
+impl From<PackedChunk> for Chunk {
+    fn from(packed: PackedChunk) -> Chunk {
+        let sprite_layers = packed
+            .sprite_layers
+            .into_iter()
+            .map(|layer| {
+                layer.map(|layer| {
+                    let inner = match layer.tiles {
+                        PackedTiles::Dense(runs) => {
+                            let mut tiles = Vec::new();
+                            for (length, tile) in runs.into_iter() {
+                                tiles.resize(tiles.len() + length as usize, tile);
+                            }
+                            LayerKindInner::Dense(DenseLayer::new(tiles))
+                        }
+                        PackedTiles::Sparse(sparse) => LayerKindInner::Sparse(sparse),
+                        PackedTiles::Decoration(decoration) => {
+                            LayerKindInner::Decoration(decoration)
+                        }
+                    };
+                    SpriteLayer {
+                        inner,
+                        entity: None,
+                        channels: layer.channels,
+                    }
+                })
+            })
+            .collect();
+        Chunk {
+            point: packed.point,
+            sprite_layers,
+            user_data: packed.user_data,
+            colliders: HashMap::default(),
+            activity: packed.activity,
+            heights: packed.heights,
+        }
+    }
+}
+
+/// Returns the runs of equal tiles, with their lengths.
+#[cfg(feature = "compress")]
+fn runs(tiles: &[RawTile]) -> Vec<(u32, RawTile)> {
+    let mut runs: Vec<(u32, RawTile)> = Vec::new();
+    for tile in tiles.iter() {
+        match runs.last_mut() {
+            Some((length, last)) if last == tile => *length += 1,
+            _ => runs.push((1, *tile)),
+        }
+    }
+    runs
+}
+
+/// Encodes a chunk which is not spawned, so it has no colliders.
+///
+/// Chunks are encoded as a save of JSON, or when they are compressed as the
+/// save of their dense layers in runs of equal tiles compressed with zstd.
+pub(crate) fn encode(chunk: &Chunk, encoding: ChunkEncoding) -> TilemapResult<Vec<u8>> {
+    match encoding {
+        ChunkEncoding::Json => save::write("chunk", chunk),
+        #[cfg(feature = "compress")]
+        ChunkEncoding::Compressed(level) => {
+            let json = save::write("chunk", &PackedChunk::from(chunk))?;
+            zstd::encode_all(&json[..], level)
+                .map_err(|e| ErrorKind::ChunkStorage(e.to_string()).into())
+        }
+    }
+}
+
+/// Decodes a chunk of any encoding and save version, where the encodings are
+/// told apart by the magic number of zstd.
+pub(crate) fn decode(bytes: &[u8]) -> TilemapResult<Chunk> {
+    #[cfg(feature = "compress")]
+    if bytes.starts_with(&ZSTD_MAGIC) {
+        let json = zstd::decode_all(bytes).map_err(|e| ErrorKind::ChunkStorage(e.to_string()))?;
+        let (_, value) = save::read(&json, "chunk", &save::PACKED_CHUNK_MIGRATIONS)?;
+        let packed: PackedChunk = save::from_json(value)?;
+        return Ok(packed.into());
+    }
+    let (_, value) = save::read(bytes, "chunk", &save::CHUNK_MIGRATIONS)?;
+    save::from_json(value)
+}
@@ -1,13 +1,108 @@
 use crate::lib::*;
+#[cfg(feature = "render")]
+use crate::{
+    mask::TilemapMaskPass, palette::ChunkPalette, picking::TilemapPickingPass,
+    render_layers::RenderLayers,
+};
 
 /// A component that is used as a flag for dirty chunks that need updating.
+#[cfg(feature = "render")]
 #[derive(Default)]
 pub(crate) struct ModifiedLayer(pub usize);
 
 /// The Z Order of a layer in a chunk.
+#[cfg(feature = "render")]
 pub(crate) struct ZOrder(pub usize);
 
+/// The color which the tiles of a chunk layer are multiplied by, which is
+/// bound to its pipeline.
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ChunkColor(pub Vec4);
+
+#[cfg(feature = "render")]
+impl Default for ChunkColor {
+    fn default() -> Self {
+        ChunkColor(Vec4::one())
+    }
+}
+
+#[cfg(feature = "render")]
+impl RenderResources for ChunkColor {
+    fn render_resources_len(&self) -> usize {
+        1
+    }
+
+    fn get_render_resource(&self, index: usize) -> Option<&dyn RenderResource> {
+        if index == 0 {
+            Some(&self.0)
+        } else {
+            None
+        }
+    }
+
+    fn get_render_resource_name(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            Some("ChunkColor_color")
+        } else {
+            None
+        }
+    }
+
+    fn iter(&self) -> RenderResourceIterator<'_> {
+        RenderResourceIterator::new(self)
+    }
+}
+
+/// The shader animation of a chunk layer as its kind, its two parameters and
+/// the time in seconds, which is bound to its pipeline. The kind is zero if
+/// the layer is not animated.
+#[cfg(feature = "render")]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ChunkAnimation(pub Vec4);
+
+#[cfg(feature = "render")]
+impl RenderResources for ChunkAnimation {
+    fn render_resources_len(&self) -> usize {
+        1
+    }
+
+    fn get_render_resource(&self, index: usize) -> Option<&dyn RenderResource> {
+        if index == 0 {
+            Some(&self.0)
+        } else {
+            None
+        }
+    }
+
+    fn get_render_resource_name(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            Some("ChunkAnimation_animation")
+        } else {
+            None
+        }
+    }
+
+    fn iter(&self) -> RenderResourceIterator<'_> {
+        RenderResourceIterator::new(self)
+    }
+}
+
+/// A component bundle for `Chunk` entities without the `render` feature,
+/// which have no mesh.
+#[cfg(not(feature = "render"))]
+#[derive(Bundle)]
+pub(crate) struct ChunkLayerBundle {
+    /// The point of the chunk.
+    pub point: Point2,
+    /// The transform location in a space for a component.
+    pub transform: Transform,
+    /// The global transform location in a space for a component.
+    pub global_transform: GlobalTransform,
+}
+
 /// A component bundle for `Chunk` entities.
+#[cfg(feature = "render")]
 #[derive(Bundle)]
 pub(crate) struct ChunkBundle {
     /// The point of the chunk.
@@ -33,4 +128,101 @@ pub(crate) struct ChunkBundle {
     pub global_transform: GlobalTransform,
     /// If a layer has been modified, all are set here.
     pub modified_layer: ModifiedLayer,
+    /// The color which the tiles are multiplied by.
+    pub color: ChunkColor,
+    /// The animation which the shader plays on the tiles.
+    pub animation: ChunkAnimation,
+    /// The palettes which the tiles are colored from.
+    pub palette: ChunkPalette,
+    /// The render layers of the layer, which decide the cameras it is drawn
+    /// by.
+    pub render_layers: RenderLayers,
+}
+
+/// A component bundle for the chunks of a `TilemapInstance`.
+///
+/// This has no `ModifiedLayer` as the meshes are updated with the asset.
+#[cfg(feature = "render")]
+#[derive(Bundle)]
+pub(crate) struct ChunkInstanceBundle {
+    /// The point of the chunk.
+    pub point: Point2,
+    /// The z order of the layer.
+    pub z_order: ZOrder,
+    /// The handle for a TextureAtlas which contains multiple textures.
+    pub texture_atlas: Handle<TextureAtlas>,
+    /// A component that indicates how to draw a component.
+    pub draw: Draw,
+    /// A component that indicates if the component is visible.
+    pub visible: Visible,
+    /// The pipeline for the renderer.
+    pub render_pipelines: RenderPipelines,
+    /// A component that indicates that an entity should be drawn in the
+    /// "main pass"
+    pub main_pass: MainPass,
+    /// A mesh of vertices for a component.
+    pub mesh: Handle<Mesh>,
+    /// The transform location in a space for a component.
+    pub transform: Transform,
+    /// The global transform location in a space for a component.
+    pub global_transform: GlobalTransform,
+    /// The color which the tiles are multiplied by.
+    pub color: ChunkColor,
+    /// The animation which the shader plays on the tiles.
+    pub animation: ChunkAnimation,
+    /// The palettes which the tiles are colored from.
+    pub palette: ChunkPalette,
+    /// The render layers of the layer, which decide the cameras it is drawn
+    /// by.
+    pub render_layers: RenderLayers,
+}
+
+/// A component bundle for drawing a chunk layer in the tilemap mask pass.
+///
+/// This is spawned as a child of the chunk layer entity and shares its mesh.
+#[cfg(feature = "render")]
+#[derive(Bundle)]
+pub(crate) struct ChunkMaskBundle {
+    /// The handle for a TextureAtlas which contains multiple textures.
+    pub texture_atlas: Handle<TextureAtlas>,
+    /// A component that indicates how to draw a component.
+    pub draw: Draw,
+    /// A component that indicates if the component is visible.
+    pub visible: Visible,
+    /// The mask pipeline for the renderer.
+    pub render_pipelines: RenderPipelines,
+    /// A component that indicates that an entity should be drawn in the
+    /// "tilemap mask pass"
+    pub mask_pass: TilemapMaskPass,
+    /// A mesh of vertices for a component.
+    pub mesh: Handle<Mesh>,
+    /// The transform location in a space for a component.
+    pub transform: Transform,
+    /// The global transform location in a space for a component.
+    pub global_transform: GlobalTransform,
+}
+
+/// A component bundle for drawing a chunk layer in the tilemap picking pass.
+///
+/// This is spawned as a child of the chunk layer entity and shares its mesh.
+#[cfg(feature = "render")]
+#[derive(Bundle)]
+pub(crate) struct ChunkPickingBundle {
+    /// The handle for a TextureAtlas which contains multiple textures.
+    pub texture_atlas: Handle<TextureAtlas>,
+    /// A component that indicates how to draw a component.
+    pub draw: Draw,
+    /// A component that indicates if the component is visible.
+    pub visible: Visible,
+    /// The picking pipeline for the renderer.
+    pub render_pipelines: RenderPipelines,
+    /// A component that indicates that an entity should be drawn in the
+    /// "tilemap picking pass"
+    pub picking_pass: TilemapPickingPass,
+    /// A mesh of vertices for a component.
+    pub mesh: Handle<Mesh>,
+    /// The transform location in a space for a component.
+    pub transform: Transform,
+    /// The global transform location in a space for a component.
+    pub global_transform: GlobalTransform,
 }
@@ -1,11 +1,15 @@
-use crate::{chunk::raw_tile::RawTile, lib::*};
+#[cfg(feature = "render")]
+use crate::chunk::raw_tile::TileAttributes;
+use crate::{chunk::raw_tile::RawTile, lib::*, tile::TileTransform};
 
 /// Common methods for layers in a chunk.
 pub(super) trait Layer: 'static {
     /// Returns the handle of the mesh.
+    #[cfg(feature = "render")]
     fn mesh(&self) -> &Handle<Mesh>;
 
     /// Sets the mesh for the layer.
+    #[cfg(feature = "render")]
     fn set_mesh(&mut self, mesh: Handle<Mesh>);
 
     /// Sets a raw tile for a layer at an index.
@@ -14,6 +18,9 @@ pub(super) trait Layer: 'static {
     /// Removes a tile for a layer at an index.
     fn remove_tile(&mut self, index: usize);
 
+    /// Removes every tile of the layer at once.
+    fn clear(&mut self);
+
     /// Gets a tile by an index.
     fn get_tile(&self, index: usize) -> Option<&RawTile>;
 
@@ -23,8 +30,30 @@ pub(super) trait Layer: 'static {
     /// Gets all the tile indices in the layer that exist.
     fn get_tile_indices(&self) -> Vec<usize>;
 
-    /// Takes all the tiles in the layer and returns attributes for the renderer.
-    fn tiles_to_attributes(&self, area: usize) -> (Vec<f32>, Vec<[f32; 4]>);
+    /// Sets the transform of the tile at an index, returning `false` if the
+    /// layer has no transforms or no tile there.
+    fn set_transform(&mut self, index: usize, transform: TileTransform) -> bool;
+
+    /// Gets the transform of the tile at an index, if the layer has
+    /// transforms and it is not the default.
+    fn get_transform(&self, index: usize) -> Option<&TileTransform>;
+
+    /// Takes all the tiles in the layer and returns attributes for the
+    /// renderer, with the tile dimensions that transforms are baked with.
+    #[cfg(feature = "render")]
+    fn tiles_to_attributes(&self, area: usize, tile_dimensions: Vec2) -> TileAttributes;
+
+    /// Returns about how many bytes the tiles of the layer take on the heap,
+    /// counting the capacity which is not used.
+    fn heap_size(&self) -> usize;
+
+    /// Frees the capacity of the layer which is not used by its tiles.
+    fn shrink_to_fit(&mut self);
+}
+
+/// Returns about how many bytes a map takes on the heap with its capacity.
+fn map_heap_size<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (::std::mem::size_of::<K>() + ::std::mem::size_of::<V>())
 }
 
 /// A layer with dense sprite tiles.
@@ -32,20 +61,44 @@ pub(super) trait Layer: 'static {
 /// The difference between a dense layer and a sparse layer is simply the
 /// storage types.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "DenseTiles"))]
 #[derive(Clone, Debug, PartialEq)]
 pub(super) struct DenseLayer {
     /// A mesh handle.
+    #[cfg(feature = "render")]
     #[cfg_attr(feature = "serde", serde(skip))]
     mesh: Handle<Mesh>,
     /// A vector of all the tiles in the chunk.
     tiles: Vec<RawTile>,
+    /// A bit for every tile, which is set if the tile is not empty, so runs
+    /// of empty tiles are skipped 64 at a time.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    occupancy: Vec<u64>,
+}
+
+/// The tiles of a dense layer as they are serialized, which the occupancy of
+/// the layer is built from when it is deserialized.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct DenseTiles {
+    /// A vector of all the tiles in the chunk.
+    tiles: Vec<RawTile>,
+}
+
+#[cfg(feature = "serde")]
+impl From<DenseTiles> for DenseLayer {
+    fn from(dense_tiles: DenseTiles) -> DenseLayer {
+        DenseLayer::new(dense_tiles.tiles)
+    }
 }
 
 impl Layer for DenseLayer {
+    #[cfg(feature = "render")]
     fn mesh(&self) -> &Handle<Mesh> {
         &self.mesh
     }
 
+    #[cfg(feature = "render")]
     fn set_mesh(&mut self, mesh: Handle<Mesh>) {
         self.mesh = mesh;
     }
@@ -53,6 +106,7 @@ impl Layer for DenseLayer {
     fn set_tile(&mut self, index: usize, tile: RawTile) {
         if let Some(inner_tile) = self.tiles.get_mut(index) {
             *inner_tile = tile;
+            self.set_occupied(index, !tile.is_empty());
         } else {
             warn!(
                 "tile is out of bounds at index {} and can not be set",
@@ -63,54 +117,116 @@ impl Layer for DenseLayer {
 
     fn remove_tile(&mut self, index: usize) {
         if let Some(tile) = self.tiles.get_mut(index) {
-            tile.color.set_a(0.0);
+            *tile = RawTile::EMPTY;
+            self.set_occupied(index, false);
+        }
+    }
+
+    fn clear(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            *tile = RawTile::EMPTY;
+        }
+        for word in self.occupancy.iter_mut() {
+            *word = 0;
         }
     }
 
     fn get_tile(&self, index: usize) -> Option<&RawTile> {
-        self.tiles.get(index).and_then(|tile| {
-            if tile.color.a() == 0.0 {
-                None
-            } else {
-                Some(tile)
-            }
-        })
+        self.tiles.get(index).filter(|tile| !tile.is_empty())
     }
 
     fn get_tile_mut(&mut self, index: usize) -> Option<&mut RawTile> {
-        self.tiles.get_mut(index).and_then(|tile| {
-            if tile.color.a() == 0.0 {
-                None
-            } else {
-                Some(tile)
-            }
-        })
+        self.tiles.get_mut(index).filter(|tile| !tile.is_empty())
     }
 
     fn get_tile_indices(&self) -> Vec<usize> {
-        let mut indices = Vec::with_capacity(self.tiles.len());
-        for (index, tile) in self.tiles.iter().enumerate() {
-            if tile.color.a() != 0.0 {
-                indices.push(index);
-            }
-        }
-        indices.shrink_to_fit();
-        indices
+        self.occupied_indices().collect()
+    }
+
+    fn set_transform(&mut self, _index: usize, _transform: TileTransform) -> bool {
+        false
+    }
+
+    fn get_transform(&self, _index: usize) -> Option<&TileTransform> {
+        None
+    }
+
+    #[cfg(feature = "render")]
+    fn tiles_to_attributes(&self, _area: usize, _tile_dimensions: Vec2) -> TileAttributes {
+        crate::chunk::raw_tile::dense_tiles_to_attributes(&self.tiles, self.occupied_indices())
+    }
+
+    fn heap_size(&self) -> usize {
+        self.tiles.capacity() * ::std::mem::size_of::<RawTile>()
+            + self.occupancy.capacity() * ::std::mem::size_of::<u64>()
     }
 
-    fn tiles_to_attributes(&self, _area: usize) -> (Vec<f32>, Vec<[f32; 4]>) {
-        crate::chunk::raw_tile::dense_tiles_to_attributes(&self.tiles)
+    fn shrink_to_fit(&mut self) {
+        self.tiles.shrink_to_fit();
+        self.occupancy.shrink_to_fit();
     }
 }
 
 impl DenseLayer {
     /// Constructs a new dense layer with tiles.
     pub fn new(tiles: Vec<RawTile>) -> DenseLayer {
+        let mut occupancy = vec![0; tiles.len() / 64 + 1];
+        for (index, tile) in tiles.iter().enumerate() {
+            if !tile.is_empty() {
+                if let Some(word) = occupancy.get_mut(index / 64) {
+                    *word |= 1 << (index % 64);
+                }
+            }
+        }
         DenseLayer {
+            #[cfg(feature = "render")]
             mesh: Default::default(),
             tiles,
+            occupancy,
+        }
+    }
+
+    /// Constructs a new dense layer of empty tiles with the area.
+    pub fn empty(area: usize) -> DenseLayer {
+        DenseLayer::new(vec![RawTile::EMPTY; area])
+    }
+
+    /// Sets or clears the occupancy bit of the tile at an index.
+    fn set_occupied(&mut self, index: usize, occupied: bool) {
+        if let Some(word) = self.occupancy.get_mut(index / 64) {
+            if occupied {
+                *word |= 1 << (index % 64);
+            } else {
+                *word &= !(1 << (index % 64));
+            }
         }
     }
+
+    /// Returns the indexes of the tiles whose occupancy bit is set, skipping
+    /// every word of empty tiles at once.
+    fn occupied_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.occupancy
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word != 0)
+            .flat_map(|(word_index, word)| {
+                let mut bits = *word;
+                ::std::iter::from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+                    let bit = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some(word_index * 64 + bit)
+                })
+            })
+    }
+
+    /// Returns every tile of the layer, including those which were removed.
+    #[cfg(feature = "compress")]
+    pub fn tiles(&self) -> &[RawTile] {
+        &self.tiles
+    }
 }
 
 /// A layer with sparse sprite tiles.
@@ -118,6 +234,7 @@ impl DenseLayer {
 #[derive(Clone, PartialEq, Debug)]
 pub(super) struct SparseLayer {
     /// A mesh handle.
+    #[cfg(feature = "render")]
     #[cfg_attr(feature = "serde", serde(skip))]
     mesh: Handle<Mesh>,
     /// A map of all the tiles in the chunk.
@@ -125,25 +242,32 @@ pub(super) struct SparseLayer {
 }
 
 impl Layer for SparseLayer {
+    #[cfg(feature = "render")]
     fn mesh(&self) -> &Handle<Mesh> {
         &self.mesh
     }
 
+    #[cfg(feature = "render")]
     fn set_mesh(&mut self, mesh: Handle<Mesh>) {
         self.mesh = mesh;
     }
 
     fn set_tile(&mut self, index: usize, tile: RawTile) {
-        if tile.color.a() == 0.0 {
+        if tile.is_empty() {
             self.tiles.remove(&index);
+        } else {
+            self.tiles.insert(index, tile);
         }
-        self.tiles.insert(index, tile);
     }
 
     fn remove_tile(&mut self, index: usize) {
         self.tiles.remove(&index);
     }
 
+    fn clear(&mut self) {
+        self.tiles = HashMap::default();
+    }
+
     fn get_tile(&self, index: usize) -> Option<&RawTile> {
         self.tiles.get(&index)
     }
@@ -160,21 +284,149 @@ impl Layer for SparseLayer {
         indices
     }
 
-    fn tiles_to_attributes(&self, area: usize) -> (Vec<f32>, Vec<[f32; 4]>) {
+    fn set_transform(&mut self, _index: usize, _transform: TileTransform) -> bool {
+        false
+    }
+
+    fn get_transform(&self, _index: usize) -> Option<&TileTransform> {
+        None
+    }
+
+    #[cfg(feature = "render")]
+    fn tiles_to_attributes(&self, area: usize, _tile_dimensions: Vec2) -> TileAttributes {
         crate::chunk::raw_tile::sparse_tiles_to_attributes(area, &self.tiles)
     }
+
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.tiles)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.tiles.shrink_to_fit();
+    }
 }
 
 impl SparseLayer {
     /// Constructs a new sparse layer with a tile hashmap.
     pub fn new(tiles: HashMap<usize, RawTile>) -> SparseLayer {
         SparseLayer {
+            #[cfg(feature = "render")]
             mesh: Default::default(),
             tiles,
         }
     }
 }
 
+/// A layer with sparse sprite tiles which can be rotated and scaled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub(super) struct DecorationLayer {
+    /// A mesh handle.
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    mesh: Handle<Mesh>,
+    /// A map of all the tiles in the chunk.
+    tiles: HashMap<usize, RawTile>,
+    /// A map of the transforms of the tiles which have one.
+    transforms: HashMap<usize, TileTransform>,
+}
+
+impl Layer for DecorationLayer {
+    #[cfg(feature = "render")]
+    fn mesh(&self) -> &Handle<Mesh> {
+        &self.mesh
+    }
+
+    #[cfg(feature = "render")]
+    fn set_mesh(&mut self, mesh: Handle<Mesh>) {
+        self.mesh = mesh;
+    }
+
+    fn set_tile(&mut self, index: usize, tile: RawTile) {
+        if tile.is_empty() {
+            self.tiles.remove(&index);
+            self.transforms.remove(&index);
+        } else {
+            self.tiles.insert(index, tile);
+        }
+    }
+
+    fn remove_tile(&mut self, index: usize) {
+        self.tiles.remove(&index);
+        self.transforms.remove(&index);
+    }
+
+    fn clear(&mut self) {
+        self.tiles = HashMap::default();
+        self.transforms = HashMap::default();
+    }
+
+    fn get_tile(&self, index: usize) -> Option<&RawTile> {
+        self.tiles.get(&index)
+    }
+
+    fn get_tile_mut(&mut self, index: usize) -> Option<&mut RawTile> {
+        self.tiles.get_mut(&index)
+    }
+
+    fn get_tile_indices(&self) -> Vec<usize> {
+        self.tiles.keys().copied().collect()
+    }
+
+    fn set_transform(&mut self, index: usize, transform: TileTransform) -> bool {
+        if !self.tiles.contains_key(&index) {
+            return false;
+        }
+        if transform == TileTransform::default() {
+            self.transforms.remove(&index);
+        } else {
+            self.transforms.insert(index, transform);
+        }
+        true
+    }
+
+    fn get_transform(&self, index: usize) -> Option<&TileTransform> {
+        self.transforms.get(&index)
+    }
+
+    #[cfg(feature = "render")]
+    fn tiles_to_attributes(&self, area: usize, tile_dimensions: Vec2) -> TileAttributes {
+        let (indexes, colors, extras, mut offsets) =
+            crate::chunk::raw_tile::sparse_tiles_to_attributes(area, &self.tiles);
+        for (index, transform) in self.transforms.iter() {
+            let corners = transform.corner_offsets(tile_dimensions);
+            for (corner, corner_offset) in corners.iter().enumerate() {
+                if let Some(offset) = offsets.get_mut(index * 4 + corner) {
+                    offset[0] += corner_offset.x;
+                    offset[1] += corner_offset.y;
+                }
+            }
+        }
+        (indexes, colors, extras, offsets)
+    }
+
+    fn heap_size(&self) -> usize {
+        map_heap_size(&self.tiles) + map_heap_size(&self.transforms)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.tiles.shrink_to_fit();
+        self.transforms.shrink_to_fit();
+    }
+}
+
+impl DecorationLayer {
+    /// Constructs a new decoration layer without any tiles.
+    pub fn new() -> DecorationLayer {
+        DecorationLayer {
+            #[cfg(feature = "render")]
+            mesh: Default::default(),
+            tiles: HashMap::default(),
+            transforms: HashMap::default(),
+        }
+    }
+}
+
 /// Specifies which kind of layer to construct, either a dense or a sparse
 /// sprite layer.
 ///
@@ -185,6 +437,18 @@ impl SparseLayer {
 ///
 /// It is highly recommended to adhere to the above principles to get the lowest
 /// amount of byte usage.
+///
+/// A decoration layer is a sparse layer whose tiles can also be rotated and
+/// scaled, which is ideal for clutter such as grass tufts, pebbles and
+/// debris, without an entity for each of them.
+///
+/// A y sorted layer is a sparse layer whose tiles are sorted by their rows on
+/// square tilemaps, so tall tiles such as trees and walls are drawn in front
+/// of the tiles above them. Sprites which are sorted the same way, such as
+/// players, walk in front of and behind them, see
+/// [`Tilemap::y_sort_depth`].
+///
+/// [`Tilemap::y_sort_depth`]: crate::tilemap::Tilemap::y_sort_depth
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum LayerKind {
@@ -192,6 +456,23 @@ pub enum LayerKind {
     Dense,
     /// Specifies the tilemap to add a sparse sprite layer.
     Sparse,
+    /// Specifies the tilemap to add a sparse sprite layer whose tiles can be
+    /// rotated and scaled.
+    Decoration,
+    /// Specifies the tilemap to add a sparse sprite layer whose tiles are
+    /// sorted by their rows.
+    YSorted,
+}
+
+impl Display for LayerKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            LayerKind::Dense => write!(f, "dense"),
+            LayerKind::Sparse => write!(f, "sparse"),
+            LayerKind::Decoration => write!(f, "decoration"),
+            LayerKind::YSorted => write!(f, "y sorted"),
+        }
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -202,6 +483,8 @@ pub(super) enum LayerKindInner {
     Dense(DenseLayer),
     /// Inner sparse layer storage.
     Sparse(SparseLayer),
+    /// Inner decoration layer storage.
+    Decoration(DecorationLayer),
 }
 
 impl AsRef<dyn Layer> for LayerKindInner {
@@ -209,6 +492,7 @@ impl AsRef<dyn Layer> for LayerKindInner {
         match self {
             LayerKindInner::Dense(s) => s,
             LayerKindInner::Sparse(s) => s,
+            LayerKindInner::Decoration(s) => s,
         }
     }
 }
@@ -218,6 +502,7 @@ impl AsMut<dyn Layer> for LayerKindInner {
         match self {
             LayerKindInner::Dense(s) => s,
             LayerKindInner::Sparse(s) => s,
+            LayerKindInner::Decoration(s) => s,
         }
     }
 }
@@ -231,4 +516,8 @@ pub(super) struct SpriteLayer {
     #[cfg_attr(feature = "serde", serde(skip))]
     /// Contains an entity if the layer had been spawned.
     pub entity: Option<Entity>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The values of the tiles in the channels of the layer, by the names of
+    /// the channels.
+    pub channels: HashMap<String, Vec<[f32; 4]>>,
 }
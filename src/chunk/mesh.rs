@@ -1,10 +1,26 @@
-use crate::lib::*;
+use crate::{
+    chunk::raw_tile::{self, TileAttributes},
+    iso,
+    lib::*,
+    sprite::SpriteRect,
+    wall::WallSprite,
+};
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 /// The mesh of a chunk layer.
 pub struct ChunkMesh {
+    /// The point of the chunk.
+    chunk_point: Point2,
     /// The dimensions of the chunk in pixels.
     dimensions: Dimension2,
+    /// The z order of the layer.
+    z_order: usize,
+    /// If the tiles need an iso depth.
+    iso_depth: bool,
+    /// If the tiles need the depth of their row.
+    row_depth: bool,
+    /// If the mesh has a second quad for the side face of every tile.
+    walls: bool,
 }
 
 impl ChunkMesh {
@@ -12,10 +28,205 @@ impl ChunkMesh {
     pub(crate) const ATTRIBUTE_TILE_INDEX: &'static str = "Vertex_Tile_Index";
     /// Vertex attribute of the tile's color.
     pub(crate) const ATTRIBUTE_TILE_COLOR: &'static str = "Vertex_Tile_Color";
+    /// Vertex attribute of the tile's point and z order, used for picking.
+    pub(crate) const ATTRIBUTE_TILE_POINT: &'static str = "Vertex_Tile_Point";
+    /// Vertex attribute of the tile's source size and corner offset, which is
+    /// zero unless the sprite has a `SpriteRect`.
+    pub(crate) const ATTRIBUTE_TILE_SOURCE: &'static str = "Vertex_Tile_Source";
+    /// Vertex attribute of the tile's position in the atlas, if the sprite
+    /// has a `SpriteRect`.
+    pub(crate) const ATTRIBUTE_TILE_UV: &'static str = "Vertex_Tile_Uv";
+    /// Vertex attribute of the tile's reserved data, which only custom shaders
+    /// use.
+    pub(crate) const ATTRIBUTE_TILE_EXTRA: &'static str = "Vertex_Tile_Extra";
+    /// Vertex attribute of the tile's offset in pixels.
+    pub(crate) const ATTRIBUTE_TILE_OFFSET: &'static str = "Vertex_Tile_Offset";
 
-    /// Constructs a new chunk mesh.
-    pub(crate) fn new(dimensions: Dimension2) -> ChunkMesh {
-        ChunkMesh { dimensions }
+    /// Returns about how many bytes the vertices and indices of the mesh of
+    /// a chunk layer with a number of tiles take, without its channels.
+    pub(crate) fn approximate_size(tiles: usize) -> usize {
+        // The position, index, color, point, source, uv, extra and offset of
+        // a vertex.
+        let vertex = 12 + 4 + 4 + 12 + 16 + 8 + 4 + 8;
+        tiles * (4 * vertex + 6 * ::std::mem::size_of::<u32>())
+    }
+
+    /// Constructs a new mesh of the layer at the z order in the chunk at the
+    /// chunk point.
+    pub(crate) fn new(chunk_point: Point2, dimensions: Dimension2, z_order: usize) -> ChunkMesh {
+        ChunkMesh {
+            chunk_point,
+            dimensions,
+            z_order,
+            iso_depth: false,
+            row_depth: false,
+            walls: false,
+        }
+    }
+
+    /// Gives every tile of the mesh the iso depth of its point.
+    pub(crate) fn with_iso_depth(mut self) -> ChunkMesh {
+        self.iso_depth = true;
+        self
+    }
+
+    /// Gives every tile of the mesh the depth of its row.
+    pub(crate) fn with_row_depth(mut self) -> ChunkMesh {
+        self.row_depth = true;
+        self
+    }
+
+    /// Gives every tile of the mesh a second quad for the side face of a
+    /// wall, after the quads of all of the tiles.
+    pub(crate) fn with_walls(mut self) -> ChunkMesh {
+        self.walls = true;
+        self
+    }
+
+    /// Extrudes the tiles which are walls in the renderer parts of a chunk
+    /// layer and its channels, for a mesh with walls.
+    ///
+    /// The top faces of the walls are raised by their height and the
+    /// attributes of the side faces are added after those of all of the
+    /// tiles. Tiles which are not walls have no side face.
+    pub(crate) fn extrude_walls(
+        parts: TileAttributes,
+        channels: Vec<(String, VertexAttributeValues)>,
+        walls: &HashMap<usize, WallSprite>,
+        tile_height: f32,
+    ) -> (TileAttributes, Vec<(String, VertexAttributeValues)>) {
+        let (mut indexes, mut colors, mut extras, mut offsets) = parts;
+        let vertices = indexes.len();
+        let mut side_indexes = vec![0; vertices];
+        let mut side_colors = vec![0; vertices];
+        let mut side_extras = vec![0.0; vertices];
+        let mut side_offsets = vec![[0.0; 2]; vertices];
+        for vertex in (0..vertices).step_by(4) {
+            let index = indexes.get(vertex).copied().unwrap_or_default() as usize;
+            let color = colors.get(vertex).copied().unwrap_or_default();
+            // Empty tiles of sparse layers are transparent.
+            if raw_tile::packed_alpha(color) == 0 {
+                continue;
+            }
+            let wall = match walls.get(&index) {
+                Some(wall) => wall,
+                None => continue,
+            };
+            let (top, side) = wall.corner_offsets(tile_height);
+            for corner in 0..4 {
+                let vertex = vertex + corner;
+                let offset = offsets.get(vertex).copied().unwrap_or_default();
+                if let Some(side_index) = side_indexes.get_mut(vertex) {
+                    *side_index = wall.side_index as u32;
+                }
+                if let Some(side_color) = side_colors.get_mut(vertex) {
+                    *side_color = color;
+                }
+                if let Some(side_extra) = side_extras.get_mut(vertex) {
+                    *side_extra = extras.get(vertex).copied().unwrap_or_default();
+                }
+                if let Some(side_offset) = side_offsets.get_mut(vertex) {
+                    *side_offset = [offset[0], offset[1] + side.get(corner).unwrap_or(&0.0)];
+                }
+                if let Some(top_offset) = offsets.get_mut(vertex) {
+                    top_offset[1] += top.get(corner).unwrap_or(&0.0);
+                }
+            }
+        }
+        indexes.extend(side_indexes);
+        colors.extend(side_colors);
+        extras.extend(side_extras);
+        offsets.extend(side_offsets);
+        // The side faces share the channel values of their tiles.
+        let channels = channels
+            .into_iter()
+            .map(|(name, values)| {
+                let values = match values {
+                    VertexAttributeValues::Float(mut values) => {
+                        values.extend(values.clone());
+                        VertexAttributeValues::Float(values)
+                    }
+                    VertexAttributeValues::Float4(mut values) => {
+                        values.extend(values.clone());
+                        VertexAttributeValues::Float4(values)
+                    }
+                    values => values,
+                };
+                (name, values)
+            })
+            .collect();
+        ((indexes, colors, extras, offsets), channels)
+    }
+
+    /// Sets the tile attributes of a mesh from the renderer parts of a chunk
+    /// layer and its channels, using the sprite rects for the sprites that
+    /// have one.
+    pub(crate) fn set_tiles(
+        mesh: &mut Mesh,
+        indexes: Vec<u32>,
+        colors: Vec<u32>,
+        extras: Vec<f32>,
+        offsets: Vec<[f32; 2]>,
+        channels: Vec<(String, VertexAttributeValues)>,
+        sprite_rects: &HashMap<usize, SpriteRect>,
+    ) {
+        let mut sources = vec![[0.0; 4]; indexes.len()];
+        let mut uvs = vec![[0.0; 2]; indexes.len()];
+        if !sprite_rects.is_empty() {
+            for (vertex, index) in indexes.iter().enumerate().step_by(4) {
+                let sprite_rect = if let Some(sprite_rect) = sprite_rects.get(&(*index as usize)) {
+                    sprite_rect
+                } else {
+                    continue;
+                };
+                let corner_sources = sprite_rect.corner_sources();
+                let corner_uvs = sprite_rect.corner_uvs();
+                let corners = corner_sources.iter().zip(corner_uvs.iter());
+                for (corner, (source, uv)) in corners.enumerate() {
+                    if let Some(vertex_source) = sources.get_mut(vertex + corner) {
+                        *vertex_source = *source;
+                    }
+                    if let Some(vertex_uv) = uvs.get_mut(vertex + corner) {
+                        *vertex_uv = *uv;
+                    }
+                }
+            }
+        }
+        mesh.set_indices(Some(ChunkMesh::visible_indices(&colors)));
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_INDEX, indexes);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_COLOR, colors);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_SOURCE, sources);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_UV, uvs);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_EXTRA, extras);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_OFFSET, offsets);
+        for (name, values) in channels.into_iter() {
+            mesh.set_attribute(name, values);
+        }
+    }
+
+    /// Returns the indices of the quads which have a color that is not fully
+    /// transparent, so the empty tiles of sparse layers and the tiles which
+    /// are hidden are left out of the mesh.
+    fn visible_indices(colors: &[u32]) -> Indices {
+        let mut indices: Vec<u32> = colors
+            .chunks(4)
+            .enumerate()
+            .filter(|(_, corners)| {
+                corners
+                    .iter()
+                    .any(|color| raw_tile::packed_alpha(*color) != 0)
+            })
+            .flat_map(|(quad, _)| {
+                let i = quad as u32 * 4;
+                vec![i, i + 2, i + 1, i, i + 3, i + 2]
+            })
+            .collect();
+        // Index buffers can not be empty, so an empty layer is drawn as one
+        // degenerate triangle.
+        if indices.is_empty() {
+            indices = vec![0, 0, 0];
+        }
+        Indices::U32(indices)
     }
 }
 
@@ -24,23 +235,48 @@ impl From<&ChunkMesh> for Mesh {
         let chunk_width = chunk_mesh.dimensions.width as i32;
         let chunk_height = chunk_mesh.dimensions.height as i32;
 
+        let chunk_point = chunk_mesh.chunk_point;
+        let z_order = chunk_mesh.z_order as i32;
+
         let mut vertices = Vec::with_capacity((chunk_width * chunk_height) as usize * 4);
+        let mut tile_points = Vec::with_capacity(vertices.capacity());
         for y in 0..chunk_height {
             for x in 0..chunk_width {
                 let y0 = y as f32 - chunk_height as f32 / 2.0;
                 let y1 = (y + 1) as f32 - chunk_height as f32 / 2.0;
                 let x0 = x as f32 - chunk_width as f32 / 2.0;
                 let x1 = (x + 1) as f32 - chunk_width as f32 / 2.0;
+                let point = Point2::new(
+                    x - chunk_width / 2 + chunk_point.x * chunk_width,
+                    y - chunk_height / 2 + chunk_point.y * chunk_height,
+                );
+                let z = if chunk_mesh.iso_depth {
+                    iso::tile_depth(point)
+                } else if chunk_mesh.row_depth {
+                    iso::row_depth(point.y as f32 + 0.5)
+                } else {
+                    0.0
+                };
 
-                vertices.push([x0, y0, 0.0]);
-                vertices.push([x0, y1, 0.0]);
-                vertices.push([x1, y1, 0.0]);
-                vertices.push([x1, y0, 0.0]);
+                vertices.push([x0, y0, z]);
+                vertices.push([x0, y1, z]);
+                vertices.push([x1, y1, z]);
+                vertices.push([x1, y0, z]);
+                for _ in 0..4 {
+                    tile_points.push([point.x, point.y, z_order]);
+                }
             }
         }
 
+        let mut quads = (chunk_width * chunk_height) as u32;
+        if chunk_mesh.walls {
+            vertices.extend(vertices.clone());
+            tile_points.extend(tile_points.clone());
+            quads *= 2;
+        }
+
         let indices = Indices::U32(
-            (0..(chunk_width * chunk_height) as u32)
+            (0..quads)
                 .flat_map(|i| {
                     let i = i * 4;
                     vec![i, i + 2, i + 1, i, i + 3, i + 2]
@@ -48,14 +284,23 @@ impl From<&ChunkMesh> for Mesh {
                 .collect(),
         );
 
-        let tile_indexes = vec![0.; vertices.len()];
-        let tile_colors: Vec<[f32; 4]> = vec![Color::WHITE.into(); vertices.len()];
+        let tile_indexes = vec![0u32; vertices.len()];
+        let tile_colors = vec![raw_tile::pack_color(Color::WHITE); vertices.len()];
+        let tile_sources = vec![[0.0f32; 4]; vertices.len()];
+        let tile_uvs = vec![[0.0f32; 2]; vertices.len()];
+        let tile_extras = vec![0.0f32; vertices.len()];
+        let tile_offsets = vec![[0.0f32; 2]; vertices.len()];
 
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.set_indices(Some(indices));
         mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
         mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_INDEX, tile_indexes);
         mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_COLOR, tile_colors);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_POINT, tile_points);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_SOURCE, tile_sources);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_UV, tile_uvs);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_EXTRA, tile_extras);
+        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_OFFSET, tile_offsets);
 
         mesh
     }
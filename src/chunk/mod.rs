@@ -55,11 +55,15 @@
 //! tilemap.add_layer(TilemapLayer { kind: LayerKind::Dense, ..Default::default() }, 1);
 //! ```
 
+/// Encoding chunks for chunk stores.
+#[cfg(feature = "serialize")]
+pub(crate) mod codec;
 /// Chunk entity.
 pub(crate) mod entity;
 /// Sparse and dense chunk layers.
 mod layer;
 /// Meshes for rendering to vertices.
+#[cfg(feature = "render")]
 pub(crate) mod mesh;
 /// Raw tile that is stored in the chunks.
 pub mod raw_tile;
@@ -68,10 +72,452 @@ pub(crate) mod render;
 /// Systems for chunks.
 pub(crate) mod system;
 
-use crate::{lib::*, tile::Tile};
+#[cfg(feature = "render")]
+use crate::channel::{self, TileChannel};
+use crate::{
+    lib::*,
+    tile::{Tile, TileTransform},
+};
 pub use layer::LayerKind;
-use layer::{DenseLayer, LayerKindInner, SparseLayer, SpriteLayer};
+use layer::{DecorationLayer, DenseLayer, LayerKindInner, SparseLayer, SpriteLayer};
 pub use raw_tile::RawTile;
+#[cfg(feature = "render")]
+use raw_tile::TileAttributes;
+
+/// How much of a chunk is kept up to date.
+///
+/// Chunks far from the player can be put to sleep, either explicitly with
+/// [`Tilemap::set_chunk_activity`] or by the distance to the cameras with an
+/// [`ActivityPolicy`].
+///
+/// [`Tilemap::set_chunk_activity`]: crate::tilemap::Tilemap::set_chunk_activity
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum ChunkActivity {
+    /// The chunk is animated, rendered and has collisions.
+    #[default]
+    Active,
+    /// The chunk is animated and rendered, but has no collisions.
+    VisualOnly,
+    /// The chunk has no collisions, its tiles are not animated and its meshes
+    /// are not updated until it is woken up.
+    Frozen,
+}
+
+/// The activity of chunks by their distance in chunks to the nearest camera,
+/// or streaming anchor if there are any.
+///
+/// Chunks within the active radius are [`ChunkActivity::Active`], chunks
+/// within the visual radius are [`ChunkActivity::VisualOnly`] and every other
+/// chunk is [`ChunkActivity::Frozen`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ActivityPolicy {
+    /// The radius of the active chunks.
+    pub active: Dimension2,
+    /// The radius of the chunks which are at least visual-only.
+    pub visual: Dimension2,
+}
+
+impl ActivityPolicy {
+    /// Constructs a new policy from the radius of the active chunks and the
+    /// radius of the visual-only chunks.
+    pub fn new<A, V>(active: A, visual: V) -> ActivityPolicy
+    where
+        A: Into<Dimension2>,
+        V: Into<Dimension2>,
+    {
+        ActivityPolicy {
+            active: active.into(),
+            visual: visual.into(),
+        }
+    }
+
+    /// Returns the activity of a chunk at the offset, in chunks, from the
+    /// chunk of a camera.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::{ActivityPolicy, ChunkActivity};
+    ///
+    /// let policy = ActivityPolicy::new((1, 1), (3, 2));
+    ///
+    /// assert_eq!(policy.activity((-1, 1)), ChunkActivity::Active);
+    /// assert_eq!(policy.activity((3, 0)), ChunkActivity::VisualOnly);
+    /// assert_eq!(policy.activity((0, 3)), ChunkActivity::Frozen);
+    /// ```
+    pub fn activity<P: Into<Point2>>(&self, offset: P) -> ChunkActivity {
+        let offset: Point2 = offset.into();
+        if within(offset, self.active) {
+            ChunkActivity::Active
+        } else if within(offset, self.visual) {
+            ChunkActivity::VisualOnly
+        } else {
+            ChunkActivity::Frozen
+        }
+    }
+}
+
+/// A component of the entities around which tilemaps with
+/// [`auto_spawn`] spawn their chunks, such as the players of a split-screen
+/// game.
+///
+/// The chunks around every anchor are spawned, and only the chunks which
+/// are around none of them are despawned. Without any anchor, the chunks
+/// around every camera are spawned instead. Activity policies are measured
+/// from the anchors too.
+///
+/// [`auto_spawn`]: crate::tilemap::TilemapBuilder::auto_spawn
+///
+/// # Examples
+/// ```
+/// use bevy_math::Vec3;
+/// use bevy_tilemap::{chunk::StreamingAnchor, prelude::*, testing::tilemap_test_harness};
+/// use bevy_transform::prelude::*;
+///
+/// let mut harness = tilemap_test_harness();
+///
+/// let tilemap = TilemapBuilder::new()
+///     .texture_atlas(harness.texture_atlas().clone())
+///     .chunk_dimensions(4, 4)
+///     .tile_dimensions(32, 32)
+///     .auto_chunk()
+///     .auto_spawn(1, 1)
+///     .finish()
+///     .unwrap();
+/// let entity = harness.spawn_tilemap(tilemap);
+///
+/// // Two players at opposite ends of the world.
+/// for x in [-1280.0, 1280.0].iter() {
+///     harness.app().world.spawn((
+///         StreamingAnchor,
+///         Transform::from_translation(Vec3::new(*x, 0.0, 0.0)),
+///         GlobalTransform::default(),
+///     ));
+/// }
+/// harness.tick_frames(2);
+///
+/// let spawned = harness.spawned_chunks(entity);
+/// assert_eq!(spawned.len(), 18);
+/// assert!(spawned.contains(&(-10, 0).into()));
+/// assert!(spawned.contains(&(10, 0).into()));
+/// assert!(!spawned.contains(&(0, 0).into()));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct StreamingAnchor;
+
+/// How many chunks are spawned in a frame.
+///
+/// Without a budget every chunk around a camera which moved far is spawned in
+/// the same frame. With one, the chunks wait in a queue and those nearest to
+/// the camera are spawned first, spreading the work over the next frames.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SpawnBudget {
+    /// At most this many chunks are spawned in a frame.
+    Chunks(usize),
+    /// Chunks are spawned until the time is up, with at least one chunk in a
+    /// frame.
+    Duration(Duration),
+}
+
+impl SpawnBudget {
+    /// Returns `true` if another chunk can be spawned, after the chunks that
+    /// were spawned since the start.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::SpawnBudget;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let budget = SpawnBudget::Chunks(2);
+    ///
+    /// assert!(budget.allows(1, start));
+    /// assert!(!budget.allows(2, start));
+    ///
+    /// let budget = SpawnBudget::Duration(Duration::from_secs(0));
+    ///
+    /// assert!(budget.allows(0, start));
+    /// assert!(!budget.allows(1, start));
+    /// ```
+    pub fn allows(&self, spawned: usize, start: Instant) -> bool {
+        match self {
+            SpawnBudget::Chunks(max) => spawned < *max,
+            SpawnBudget::Duration(max) => spawned == 0 || start.elapsed() < *max,
+        }
+    }
+}
+
+/// How a tilemap with auto spawn spawns and despawns the chunks around its
+/// cameras or streaming anchors.
+///
+/// Chunks within the spawn radius of a camera are spawned, and only the
+/// chunks outside of the despawn radius of every camera are despawned, so a
+/// camera going back and forth over the edge of a chunk does not spawn and
+/// despawn the same chunks over and over. In between, chunks are kept until
+/// there are more than the most loaded chunks, and then the farthest are
+/// despawned first.
+///
+/// # Examples
+/// ```
+/// use bevy_math::Vec3;
+/// use bevy_tilemap::{
+///     chunk::{ChunkSpawnConfig, StreamingAnchor},
+///     prelude::*,
+///     testing::tilemap_test_harness,
+/// };
+/// use bevy_transform::prelude::*;
+///
+/// let mut harness = tilemap_test_harness();
+///
+/// let tilemap = TilemapBuilder::new()
+///     .texture_atlas(harness.texture_atlas().clone())
+///     .chunk_dimensions(4, 4)
+///     .tile_dimensions(32, 32)
+///     .auto_chunk()
+///     .spawn_config(ChunkSpawnConfig::new((1, 1), (2, 2)))
+///     .finish()
+///     .unwrap();
+/// let entity = harness.spawn_tilemap(tilemap);
+/// let player = harness
+///     .app()
+///     .world
+///     .spawn((StreamingAnchor, Transform::default(), GlobalTransform::default()));
+/// harness.tick_frames(2);
+///
+/// // The player walks two chunks to the right, which is where its global
+/// // transform is from the next frame.
+/// harness.app().world.get_mut::<Transform>(player).unwrap().translation = Vec3::new(256.0, 0.0, 0.0);
+/// harness.tick_frames(3);
+///
+/// let spawned = harness.spawned_chunks(entity);
+/// assert!(spawned.contains(&(3, 0).into()));
+/// // Two chunks away from the player, so it is kept.
+/// assert!(spawned.contains(&(0, 0).into()));
+/// // Three chunks away from the player, so it was despawned.
+/// assert!(!spawned.contains(&(-1, 0).into()));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ChunkSpawnConfig {
+    /// The radius in chunks around a camera of the chunks which are spawned.
+    pub spawn_radius: Dimension2,
+    /// The radius in chunks around a camera out of which chunks are
+    /// despawned. Radii smaller than the spawn radius are as large as it.
+    pub despawn_radius: Dimension2,
+    /// The most chunks which are spawned or queued at once, if there is a
+    /// limit. Chunks within the spawn radius are always kept.
+    pub max_loaded_chunks: Option<usize>,
+    /// How many chunks are spawned in a frame, if there is a budget.
+    pub budget_per_frame: Option<SpawnBudget>,
+}
+
+impl ChunkSpawnConfig {
+    /// Constructs a new config from the spawn and despawn radii, without a
+    /// limit of loaded chunks or a budget.
+    pub fn new<S, D>(spawn_radius: S, despawn_radius: D) -> ChunkSpawnConfig
+    where
+        S: Into<Dimension2>,
+        D: Into<Dimension2>,
+    {
+        ChunkSpawnConfig {
+            spawn_radius: spawn_radius.into(),
+            despawn_radius: despawn_radius.into(),
+            max_loaded_chunks: None,
+            budget_per_frame: None,
+        }
+    }
+
+    /// Returns `true` if a chunk at the offset, in chunks, from the chunk of a
+    /// camera is spawned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::ChunkSpawnConfig;
+    ///
+    /// let config = ChunkSpawnConfig::new((2, 1), (3, 3));
+    ///
+    /// assert!(config.spawns((-2, 1)));
+    /// assert!(!config.spawns((0, 2)));
+    /// assert!(config.keeps((0, 2)));
+    /// assert!(!config.keeps((4, 0)));
+    /// ```
+    pub fn spawns<P: Into<Point2>>(&self, offset: P) -> bool {
+        within(offset.into(), self.spawn_radius)
+    }
+
+    /// Returns `true` if a spawned chunk at the offset, in chunks, from the
+    /// chunk of a camera is kept.
+    pub fn keeps<P: Into<Point2>>(&self, offset: P) -> bool {
+        let offset: Point2 = offset.into();
+        within(offset, self.despawn_radius) || within(offset, self.spawn_radius)
+    }
+}
+
+/// Returns `true` if an offset in chunks is within a radius.
+fn within(offset: Point2, radius: Dimension2) -> bool {
+    offset.x.unsigned_abs() <= radius.width && offset.y.unsigned_abs() <= radius.height
+}
+
+/// The thresholds past which a tilemap sheds load, so that long sessions on
+/// low-end machines degrade instead of running out of memory.
+///
+/// Load is shed from the chunks farthest from the camera, in order: first
+/// their colliders, then their layer entities and meshes and last, with the
+/// `serialize` feature, the data of chunks which are not spawned, which is
+/// written to the chunk store. Each step only sheds as much as needed to get back under
+/// its threshold, and what was shed comes back once it fits again. A
+/// [`TilemapShedEvent`] is sent for everything that was shed.
+///
+/// [`TilemapShedEvent`]: crate::event::TilemapShedEvent
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ShedPolicy {
+    /// The most collider entities that are kept.
+    max_colliders: Option<usize>,
+    /// The most layer entities of spawned chunks that are kept.
+    max_layer_entities: Option<usize>,
+    /// The most chunks that are kept in memory before the others are written
+    /// to the chunk store.
+    #[cfg(feature = "serialize")]
+    max_chunks: Option<usize>,
+}
+
+impl ShedPolicy {
+    /// Constructs a new policy which never sheds anything.
+    pub fn new() -> ShedPolicy {
+        ShedPolicy::default()
+    }
+
+    /// Sets the most collider entities that are kept before the colliders of
+    /// the farthest chunks are despawned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::ShedPolicy;
+    ///
+    /// let policy = ShedPolicy::new().max_colliders(2_000);
+    ///
+    /// assert_eq!(policy.colliders(), Some(2_000));
+    /// ```
+    pub fn max_colliders(mut self, max: usize) -> ShedPolicy {
+        self.max_colliders = Some(max);
+        self
+    }
+
+    /// Sets the most layer entities that are kept before the farthest chunks
+    /// are despawned, dropping their meshes but keeping their tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::ShedPolicy;
+    ///
+    /// let policy = ShedPolicy::new().max_layer_entities(500);
+    ///
+    /// assert_eq!(policy.layer_entities(), Some(500));
+    /// ```
+    pub fn max_layer_entities(mut self, max: usize) -> ShedPolicy {
+        self.max_layer_entities = Some(max);
+        self
+    }
+
+    /// Sets the most chunks that are kept in memory before the farthest ones
+    /// which are not spawned are written to the chunk store of the tilemap
+    /// and dropped. They are read back when they are spawned or their tiles
+    /// are changed. Tilemaps without a chunk store keep every chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::ShedPolicy;
+    ///
+    /// let policy = ShedPolicy::new().max_chunks(10_000);
+    ///
+    /// assert_eq!(policy.chunks(), Some(10_000));
+    /// ```
+    #[cfg(feature = "serialize")]
+    pub fn max_chunks(mut self, max: usize) -> ShedPolicy {
+        self.max_chunks = Some(max);
+        self
+    }
+
+    /// Returns the most collider entities that are kept, if there is a limit.
+    pub fn colliders(&self) -> Option<usize> {
+        self.max_colliders
+    }
+
+    /// Returns the most layer entities that are kept, if there is a limit.
+    pub fn layer_entities(&self) -> Option<usize> {
+        self.max_layer_entities
+    }
+
+    /// Returns the most chunks that are kept in memory, if there is a limit.
+    #[cfg(feature = "serialize")]
+    pub fn chunks(&self) -> Option<usize> {
+        self.max_chunks
+    }
+}
+
+/// The fill ratios at which the sparse layers of a chunk are stored as dense
+/// layers and back, so the kinds of the layers of procedurally generated
+/// maps need not be guessed up front.
+///
+/// A sparse layer whose tiles fill at least the promote ratio of a chunk is
+/// stored as a dense layer in that chunk, which takes less memory and is
+/// faster to go through when it is mostly full. It is stored as a sparse
+/// layer again once its tiles fill no more than the demote ratio. The layer
+/// keeps behaving as a sparse layer either way, only its storage changes.
+/// Only layers of the [`LayerKind::Sparse`] kind are converted.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StoragePolicy {
+    /// The fill ratio at which sparse layers are stored dense.
+    promote_ratio: f32,
+    /// The fill ratio at which layers stored dense are stored sparse again.
+    demote_ratio: f32,
+}
+
+impl Default for StoragePolicy {
+    fn default() -> Self {
+        StoragePolicy {
+            promote_ratio: 0.75,
+            demote_ratio: 0.5,
+        }
+    }
+}
+
+impl StoragePolicy {
+    /// Constructs a new policy from the fill ratio at which sparse layers are
+    /// stored dense and the one at which they are stored sparse again, which
+    /// is kept no higher than the first.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::chunk::StoragePolicy;
+    ///
+    /// let policy = StoragePolicy::new(0.6, 0.8);
+    ///
+    /// assert_eq!(policy.promote_ratio(), 0.6);
+    /// assert_eq!(policy.demote_ratio(), 0.6);
+    /// ```
+    pub fn new(promote_ratio: f32, demote_ratio: f32) -> StoragePolicy {
+        StoragePolicy {
+            promote_ratio,
+            demote_ratio: demote_ratio.min(promote_ratio),
+        }
+    }
+
+    /// Returns the fill ratio at which sparse layers are stored dense.
+    pub fn promote_ratio(&self) -> f32 {
+        self.promote_ratio
+    }
+
+    /// Returns the fill ratio at which layers stored dense are stored sparse
+    /// again.
+    pub fn demote_ratio(&self) -> f32 {
+        self.demote_ratio
+    }
+}
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Debug)]
@@ -83,9 +529,58 @@ pub(crate) struct Chunk {
     sprite_layers: Vec<Option<SpriteLayer>>,
     /// Ephemeral user data that can be used for flags or other purposes.
     user_data: u128,
-    /// Contains a map of all collision entities.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub collision_entities: HashMap<usize, Entity>,
+    /// Contains the colliders of each layer, at the z order.
+    colliders: HashMap<usize, Vec<ChunkCollider>>,
+    /// How much of the chunk is kept up to date.
+    #[cfg_attr(feature = "serde", serde(default))]
+    activity: ChunkActivity,
+    /// The elevation of the tiles which are raised, in pixels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    heights: HashMap<usize, f32>,
+}
+
+/// The layers above a chunk layer whose opaque tiles hide the tiles of the
+/// layer, which are then not drawn.
+#[cfg(feature = "render")]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(crate) struct TileOcclusion {
+    /// The z orders of the layers above whose tiles can hide tiles.
+    pub(crate) layers: Vec<usize>,
+    /// The sprite indexes which are opaque and cover their whole tile.
+    pub(crate) sprites: HashSet<usize>,
+}
+
+/// A collider entity of a chunk layer and the rectangle of tiles it covers.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct ChunkCollider {
+    /// The entity of the collider.
+    pub(crate) entity: Entity,
+    /// The bottom left point of the rectangle in the chunk.
+    pub(crate) point: Point2,
+    /// The dimensions of the rectangle in tiles.
+    pub(crate) dimensions: Dimension2,
+}
+
+impl ChunkCollider {
+    /// Returns `true` if the rectangle covers the point in the chunk.
+    pub(crate) fn contains(&self, point: Point2) -> bool {
+        point.x >= self.point.x
+            && point.y >= self.point.y
+            && point.x < self.point.x + self.dimensions.width as i32
+            && point.y < self.point.y + self.dimensions.height as i32
+    }
+
+    /// Returns the points in the chunk that the rectangle covers.
+    pub(crate) fn points(&self) -> Vec<Point2> {
+        let mut points = Vec::new();
+        for y in self.point.y..self.point.y + self.dimensions.height as i32 {
+            for x in self.point.x..self.point.x + self.dimensions.width as i32 {
+                points.push(Point2::new(x, y));
+            }
+        }
+        points
+    }
 }
 
 impl Chunk {
@@ -99,8 +594,9 @@ impl Chunk {
             point,
             sprite_layers: vec![None; layers.len()],
             user_data: 0,
-            #[cfg(feature = "bevy_rapier2d")]
-            collision_entities: HashMap::default(),
+            colliders: HashMap::default(),
+            activity: ChunkActivity::Active,
+            heights: HashMap::default(),
         };
         for (z_order, kind) in layers.iter().enumerate() {
             if let Some(kind) = kind {
@@ -115,27 +611,34 @@ impl Chunk {
     pub(crate) fn add_layer(&mut self, kind: &LayerKind, z_order: usize, dimensions: Dimension2) {
         match kind {
             LayerKind::Dense => {
-                let tiles = vec![
-                    RawTile {
-                        index: 0,
-                        color: Color::rgba(0.0, 0.0, 0.0, 0.0)
-                    };
-                    dimensions.area() as usize
-                ];
+                let dense = DenseLayer::empty(dimensions.area() as usize);
                 if let Some(layer) = self.sprite_layers.get_mut(z_order) {
                     *layer = Some(SpriteLayer {
-                        inner: LayerKindInner::Dense(DenseLayer::new(tiles)),
+                        inner: LayerKindInner::Dense(dense),
                         entity: None,
+                        channels: HashMap::default(),
                     });
                 } else {
                     error!("sprite layer {} is out of bounds", z_order);
                 }
             }
-            LayerKind::Sparse => {
+            LayerKind::Sparse | LayerKind::YSorted => {
                 if let Some(layer) = self.sprite_layers.get_mut(z_order) {
                     *layer = Some(SpriteLayer {
                         inner: LayerKindInner::Sparse(SparseLayer::new(HashMap::default())),
                         entity: None,
+                        channels: HashMap::default(),
+                    });
+                } else {
+                    error!("sprite layer {} is out of bounds", z_order);
+                }
+            }
+            LayerKind::Decoration => {
+                if let Some(layer) = self.sprite_layers.get_mut(z_order) {
+                    *layer = Some(SpriteLayer {
+                        inner: LayerKindInner::Decoration(DecorationLayer::new()),
+                        entity: None,
+                        channels: HashMap::default(),
                     });
                 } else {
                     error!("sprite layer {} is out of bounds", z_order);
@@ -145,10 +648,16 @@ impl Chunk {
     }
 
     /// Returns the point of the location of the chunk.
+    #[cfg(feature = "render")]
     pub(crate) fn point(&self) -> Point2 {
         self.point
     }
 
+    /// Sets the point of the location of the chunk.
+    pub(crate) fn set_point(&mut self, point: Point2) {
+        self.point = point;
+    }
+
     // /// Returns a copy of the user data.
     // pub(crate) fn user_data(&self) -> u128 {
     //     self.user_data
@@ -159,18 +668,54 @@ impl Chunk {
     //     &mut self.user_data
     // }
 
-    /// Moves a layer from a z layer to another.
-    pub(crate) fn move_layer(&mut self, from_z: usize, to_z: usize) {
-        // TODO: rename to swap and include it in the greater api
-        if self.sprite_layers.get(to_z).is_some() {
-            error!(
-                "sprite layer {} unexpectedly exists and can not be moved",
-                to_z
-            );
+    /// Returns how many z orders the chunk has room for.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn z_layers(&self) -> usize {
+        self.sprite_layers.len()
+    }
+
+    /// Sets how many z orders the chunk has room for, dropping the layers
+    /// and colliders above them.
+    pub(crate) fn set_z_layers(&mut self, count: usize) {
+        self.sprite_layers.resize(count, None);
+        self.colliders.retain(|z_order, _| *z_order < count);
+    }
+
+    /// Swaps the layers at two z orders with their colliders. A layer is
+    /// moved by swapping it with a z order without a layer.
+    pub(crate) fn swap_layers(&mut self, a: usize, b: usize) {
+        if a >= self.sprite_layers.len() || b >= self.sprite_layers.len() {
+            error!("sprite layer {} or {} does not exist", a, b);
             return;
         }
+        self.sprite_layers.swap(a, b);
+        let colliders_a = self.colliders.remove(&a);
+        let colliders_b = self.colliders.remove(&b);
+        if let Some(colliders) = colliders_a {
+            self.colliders.insert(b, colliders);
+        }
+        if let Some(colliders) = colliders_b {
+            self.colliders.insert(a, colliders);
+        }
+    }
 
-        self.sprite_layers.swap(from_z, to_z);
+    /// Moves the layers at every z order with their colliders, so that the
+    /// layer at a z order is the one which was at the z order the
+    /// permutation has for it. Z orders past the permutation keep their
+    /// layers.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn permute_layers(&mut self, permutation: &[usize]) {
+        let count = self.sprite_layers.len().max(permutation.len());
+        let mut sprite_layers = ::std::mem::take(&mut self.sprite_layers);
+        let mut colliders = ::std::mem::take(&mut self.colliders);
+        for z_order in 0..count {
+            let from = permutation.get(z_order).copied().unwrap_or(z_order);
+            self.sprite_layers
+                .push(sprite_layers.get_mut(from).and_then(Option::take));
+            if let Some(layer_colliders) = colliders.remove(&from) {
+                self.colliders.insert(z_order, layer_colliders);
+            }
+        }
     }
 
     /// Removes a layer from the specified layer.
@@ -179,6 +724,7 @@ impl Chunk {
     }
 
     /// Sets the mesh for the chunk layer to use.
+    #[cfg(feature = "render")]
     pub(crate) fn set_mesh(&mut self, z_order: usize, mesh: Handle<Mesh>) {
         if let Some(layer) = self.sprite_layers.get_mut(z_order) {
             if let Some(layer) = layer.as_mut() {
@@ -198,6 +744,8 @@ impl Chunk {
                 let raw_tile = RawTile {
                     index: tile.sprite_index,
                     color: tile.tint,
+                    extra: tile.extra,
+                    offset: tile.offset,
                 };
                 layer.inner.as_mut().set_tile(index, raw_tile);
             } else {
@@ -221,6 +769,20 @@ impl Chunk {
         }
     }
 
+    /// Removes every tile of a sprite layer at once, and returns the indexes
+    /// of the tiles which were removed.
+    pub(crate) fn clear_layer(&mut self, z_order: usize) -> Vec<usize> {
+        let layer = match self.sprite_layers.get_mut(z_order) {
+            Some(Some(layer)) => layer,
+            _ => return Vec::new(),
+        };
+        let indices = layer.inner.as_ref().get_tile_indices();
+        if !indices.is_empty() {
+            layer.inner.as_mut().clear();
+        }
+        indices
+    }
+
     /// Adds an entity to a z layer, always when it is spawned.
     pub(crate) fn add_entity(&mut self, z_order: usize, entity: Entity) {
         if let Some(layer) = self.sprite_layers.get_mut(z_order) {
@@ -234,14 +796,202 @@ impl Chunk {
         }
     }
 
-    /// Adds an entity to a tile index in a layer.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub(crate) fn insert_collision_entity(
+    /// Returns the z order and collider of a collider entity, if it is one of
+    /// the chunk.
+    pub(crate) fn get_collider(&self, entity: Entity) -> Option<(usize, &ChunkCollider)> {
+        self.colliders.iter().find_map(|(z_order, colliders)| {
+            colliders
+                .iter()
+                .find(|collider| collider.entity == entity)
+                .map(|collider| (*z_order, collider))
+        })
+    }
+
+    /// Returns how much of the chunk is kept up to date.
+    pub(crate) fn activity(&self) -> ChunkActivity {
+        self.activity
+    }
+
+    /// Sets how much of the chunk is kept up to date.
+    pub(crate) fn set_activity(&mut self, activity: ChunkActivity) {
+        self.activity = activity;
+    }
+
+    /// Returns how many collider entities the chunk has.
+    pub(crate) fn collider_count(&self) -> usize {
+        self.colliders
+            .values()
+            .map(|colliders| colliders.len())
+            .sum()
+    }
+
+    /// Returns how many sprite layers the chunk has, which is how many layer
+    /// entities it is spawned with.
+    pub(crate) fn layer_count(&self) -> usize {
+        self.sprite_layers.iter().flatten().count()
+    }
+
+    /// Returns `true` if no layer of the chunk has a tile and no tile is
+    /// raised.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+            && self
+                .sprite_layers
+                .iter()
+                .flatten()
+                .all(|layer| layer.inner.as_ref().get_tile_indices().is_empty())
+    }
+
+    /// Returns about how many bytes the tiles, channel values and heights of
+    /// the chunk take on the heap, counting the capacity which is not used.
+    pub(crate) fn heap_size(&self) -> usize {
+        let layers: usize = (0..self.sprite_layers.len())
+            .filter_map(|z_order| self.layer_heap_size(z_order))
+            .sum();
+        let heights = self.heights.capacity()
+            * (::std::mem::size_of::<usize>() + ::std::mem::size_of::<f32>());
+        layers + heights
+    }
+
+    /// Returns the kind of storage of a layer, or `None` if the layer does
+    /// not exist. Y sorted layers are stored as sparse layers.
+    pub(crate) fn layer_storage(&self, z_order: usize) -> Option<LayerKind> {
+        let layer = self.sprite_layers.get(z_order)?.as_ref()?;
+        Some(match layer.inner {
+            LayerKindInner::Dense(_) => LayerKind::Dense,
+            LayerKindInner::Sparse(_) => LayerKind::Sparse,
+            LayerKindInner::Decoration(_) => LayerKind::Decoration,
+        })
+    }
+
+    /// Returns the kind of storage a sparse layer should have with the
+    /// policy, if it is not the one it has.
+    pub(crate) fn storage_change(
+        &self,
+        z_order: usize,
+        area: usize,
+        policy: &StoragePolicy,
+    ) -> Option<LayerKind> {
+        let storage = self.layer_storage(z_order)?;
+        let tiles = self.get_tile_indices(z_order)?.len();
+        let ratio = tiles as f32 / area.max(1) as f32;
+        match storage {
+            LayerKind::Sparse if ratio >= policy.promote_ratio() => Some(LayerKind::Dense),
+            LayerKind::Dense if ratio <= policy.demote_ratio() => Some(LayerKind::Sparse),
+            _ => None,
+        }
+    }
+
+    /// Stores a layer as a dense or a sparse layer with the area, keeping its
+    /// tiles, channel values and mesh.
+    pub(crate) fn set_layer_storage(&mut self, z_order: usize, storage: LayerKind, area: usize) {
+        let layer = match self.sprite_layers.get_mut(z_order) {
+            Some(Some(layer)) => layer,
+            _ => return,
+        };
+        let tiles: Vec<(usize, RawTile)> = layer
+            .inner
+            .as_ref()
+            .get_tile_indices()
+            .into_iter()
+            .filter_map(|index| Some((index, *layer.inner.as_ref().get_tile(index)?)))
+            .collect();
+        let mut inner = match storage {
+            LayerKind::Dense => LayerKindInner::Dense(DenseLayer::empty(area)),
+            LayerKind::Sparse => LayerKindInner::Sparse(SparseLayer::new(HashMap::default())),
+            _ => return,
+        };
+        for (index, tile) in tiles.into_iter() {
+            inner.as_mut().set_tile(index, tile);
+        }
+        #[cfg(feature = "render")]
+        inner.as_mut().set_mesh(layer.inner.as_ref().mesh().clone());
+        layer.inner = inner;
+    }
+
+    /// Sets a tile of a layer stored as a dense layer, such as a tile which
+    /// was cleared. Sparse and decoration layers are left as they are.
+    pub(crate) fn fill_tile(&mut self, index: usize, z_order: usize, tile: RawTile) {
+        if let Some(Some(layer)) = self.sprite_layers.get_mut(z_order) {
+            if let LayerKindInner::Dense(_) = layer.inner {
+                layer.inner.as_mut().set_tile(index, tile);
+            }
+        }
+    }
+
+    /// Sets every empty tile of a layer stored as a dense layer with the
+    /// area to a tile. Sparse and decoration layers are left as they are.
+    pub(crate) fn fill_empty_tiles(&mut self, z_order: usize, tile: RawTile, area: usize) {
+        let layer = match self.sprite_layers.get_mut(z_order) {
+            Some(Some(layer)) => layer,
+            _ => return,
+        };
+        if let LayerKindInner::Dense(_) = layer.inner {
+            let inner = layer.inner.as_mut();
+            for index in 0..area {
+                if inner.get_tile(index).is_none() {
+                    inner.set_tile(index, tile);
+                }
+            }
+        }
+    }
+
+    /// Returns about how many bytes the tiles and channel values of a layer
+    /// take on the heap, or `None` if the layer does not exist.
+    pub(crate) fn layer_heap_size(&self, z_order: usize) -> Option<usize> {
+        let layer = self.sprite_layers.get(z_order)?.as_ref()?;
+        let channels: usize = layer
+            .channels
+            .values()
+            .map(|values| values.capacity() * ::std::mem::size_of::<[f32; 4]>())
+            .sum();
+        Some(layer.inner.as_ref().heap_size() + channels)
+    }
+
+    /// Frees the capacity of the layers, channels and heights of the chunk
+    /// which is not used.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for layer in self.sprite_layers.iter_mut().flatten() {
+            layer.inner.as_mut().shrink_to_fit();
+            for values in layer.channels.values_mut() {
+                values.shrink_to_fit();
+            }
+        }
+        self.heights.shrink_to_fit();
+    }
+
+    /// Drops the mesh handles of every layer, so that the meshes of a
+    /// despawned chunk are freed.
+    #[cfg(feature = "render")]
+    pub(crate) fn clear_meshes(&mut self) {
+        for layer in self.sprite_layers.iter_mut().flatten() {
+            layer.inner.as_mut().set_mesh(Handle::default());
+        }
+    }
+
+    /// Removes the colliders of every layer, returning them.
+    pub(crate) fn take_colliders(&mut self) -> Vec<ChunkCollider> {
+        self.colliders
+            .drain()
+            .flat_map(|(_, colliders)| colliders)
+            .collect()
+    }
+
+    /// Sets the colliders of a layer, returning the previous ones.
+    pub(crate) fn set_colliders(
         &mut self,
-        index: usize,
-        entity: Entity,
-    ) -> Option<Entity> {
-        self.collision_entities.insert(index, entity)
+        z_order: usize,
+        colliders: Vec<ChunkCollider>,
+    ) -> Option<Vec<ChunkCollider>> {
+        self.colliders.insert(z_order, colliders)
+    }
+
+    /// Gets the mesh of a layer, if the layer exists.
+    #[cfg(feature = "render")]
+    pub(crate) fn get_mesh(&self, z_order: usize) -> Option<&Handle<Mesh>> {
+        self.sprite_layers
+            .get(z_order)
+            .and_then(|o| o.as_ref().map(|layer| layer.inner.as_ref().mesh()))
     }
 
     /// Gets the layers entity, if any. Useful for despawning.
@@ -251,12 +1001,6 @@ impl Chunk {
             .and_then(|o| o.as_ref().and_then(|layer| layer.entity))
     }
 
-    /// Gets the collision entity if any.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub(crate) fn get_collision_entity(&self, index: usize) -> Option<Entity> {
-        self.collision_entities.get(&index).cloned()
-    }
-
     /// Gets all the layers entities for use with bulk despawning.
     pub(crate) fn get_entities(&self) -> Vec<Entity> {
         let mut entities = Vec::new();
@@ -270,6 +1014,56 @@ impl Chunk {
         entities
     }
 
+    /// Copies the layer entities, meshes and colliders of the chunk which
+    /// this one replaces, or drops them if it replaces none, so a chunk which
+    /// is restored from a snapshot keeps what was spawned for its point.
+    pub(crate) fn copy_spawned_state(&mut self, other: Option<&Chunk>) {
+        for (z_order, layer) in self.sprite_layers.iter_mut().enumerate() {
+            let layer = match layer {
+                Some(layer) => layer,
+                None => continue,
+            };
+            let other = other
+                .and_then(|other| other.sprite_layers.get(z_order))
+                .and_then(Option::as_ref);
+            layer.entity = other.and_then(|other| other.entity);
+            #[cfg(feature = "render")]
+            {
+                let mesh = other
+                    .map(|other| other.inner.as_ref().mesh().clone())
+                    .unwrap_or_default();
+                layer.inner.as_mut().set_mesh(mesh);
+            }
+        }
+        self.colliders = other
+            .map(|other| other.colliders.clone())
+            .unwrap_or_default();
+    }
+
+    /// Returns every tile of every layer, at its point in the chunk.
+    pub(crate) fn tiles(&self, dimensions: Dimension2) -> Vec<Tile<Point2>> {
+        let mut tiles = Vec::new();
+        for z_order in 0..self.sprite_layers.len() {
+            for index in self.get_tile_indices(z_order).unwrap_or_default() {
+                match (
+                    self.get_tile(z_order, index),
+                    dimensions.decode_point(index),
+                ) {
+                    (Some(tile), Ok(point)) => tiles.push(Tile {
+                        point,
+                        z_order,
+                        sprite_index: tile.index,
+                        tint: tile.color,
+                        extra: tile.extra,
+                        offset: tile.offset,
+                    }),
+                    _ => continue,
+                }
+            }
+        }
+        tiles
+    }
+
     /// Gets a reference to a tile from a provided z order and index.
     pub(crate) fn get_tile(&self, z_order: usize, index: usize) -> Option<&RawTile> {
         self.sprite_layers.get(z_order).and_then(|layer| {
@@ -289,7 +1083,6 @@ impl Chunk {
     }
 
     /// Gets a vec of all the tiles in the layer, if any.
-    #[cfg(feature = "bevy_rapier2d")]
     pub(crate) fn get_tile_indices(&self, z_order: usize) -> Option<Vec<usize>> {
         self.sprite_layers.get(z_order).and_then(|layer| {
             layer
@@ -298,19 +1091,248 @@ impl Chunk {
         })
     }
 
+    /// Merges the tiles of a layer for which the filter, given the index and
+    /// the tile, returns `true` into as few rectangles as it can.
+    ///
+    /// Each row is scanned for the widest run of tiles which are not merged
+    /// yet, and the run is then grown upwards for as long as every row above
+    /// has the same run. Returns the bottom left point in the chunk and the
+    /// dimensions of each rectangle.
+    pub(crate) fn merge_tiles<F>(
+        &self,
+        z_order: usize,
+        dimensions: Dimension2,
+        mut filter: F,
+    ) -> Vec<(Point2, Dimension2)>
+    where
+        F: FnMut(usize, &RawTile) -> bool,
+    {
+        let width = dimensions.width as usize;
+        let height = dimensions.height as usize;
+        let mut solid = vec![false; width * height];
+        if let Some(indices) = self.get_tile_indices(z_order) {
+            for index in indices.into_iter() {
+                let is_included = match self.get_tile(z_order, index) {
+                    Some(raw_tile) => filter(index, raw_tile),
+                    None => false,
+                };
+                if let Some(tile) = solid.get_mut(index) {
+                    *tile = is_included;
+                }
+            }
+        }
+
+        let is_solid = |solid: &[bool], x: usize, y: usize| {
+            x < width && solid.get(y * width + x).cloned().unwrap_or(false)
+        };
+        let mut rects = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if !is_solid(&solid, x, y) {
+                    continue;
+                }
+                let mut rect_width = 1;
+                while is_solid(&solid, x + rect_width, y) {
+                    rect_width += 1;
+                }
+                let mut rect_height = 1;
+                while (x..x + rect_width).all(|rect_x| is_solid(&solid, rect_x, y + rect_height)) {
+                    rect_height += 1;
+                }
+                for rect_y in y..y + rect_height {
+                    for rect_x in x..x + rect_width {
+                        if let Some(tile) = solid.get_mut(rect_y * width + rect_x) {
+                            *tile = false;
+                        }
+                    }
+                }
+                rects.push((
+                    Point2::new(x as i32, y as i32),
+                    Dimension2::new(rect_width as u32, rect_height as u32),
+                ));
+            }
+        }
+        rects
+    }
+
+    /// Sets the value of a tile at an index in a channel of the z layer.
+    pub(crate) fn set_channel_value(
+        &mut self,
+        z_order: usize,
+        name: &str,
+        index: usize,
+        value: [f32; 4],
+        dimensions: Dimension2,
+    ) {
+        if let Some(Some(layer)) = self.sprite_layers.get_mut(z_order) {
+            let values = layer
+                .channels
+                .entry(name.to_string())
+                .or_insert_with(|| vec![[0.0; 4]; dimensions.area() as usize]);
+            if let Some(tile_value) = values.get_mut(index) {
+                *tile_value = value;
+            }
+        } else {
+            error!("sprite layer {} does not exist", z_order);
+        }
+    }
+
+    /// Sets the transform of a tile at an index of the z layer, returning
+    /// `false` if the layer is not a decoration layer or has no tile there.
+    pub(crate) fn set_tile_transform(
+        &mut self,
+        z_order: usize,
+        index: usize,
+        transform: TileTransform,
+    ) -> bool {
+        match self.sprite_layers.get_mut(z_order) {
+            Some(Some(layer)) => layer.inner.as_mut().set_transform(index, transform),
+            _ => false,
+        }
+    }
+
+    /// Returns the transform of a tile at an index of the z layer, or `None`
+    /// if it has the default transform.
+    pub(crate) fn tile_transform(&self, z_order: usize, index: usize) -> Option<TileTransform> {
+        self.sprite_layers
+            .get(z_order)
+            .and_then(|layer| layer.as_ref())
+            .and_then(|layer| layer.inner.as_ref().get_transform(index))
+            .copied()
+    }
+
+    /// Sets the elevation of the tiles at an index of every layer, in pixels.
+    pub(crate) fn set_height(&mut self, index: usize, height: f32) {
+        if height == 0.0 {
+            self.heights.remove(&index);
+        } else {
+            self.heights.insert(index, height);
+        }
+    }
+
+    /// Returns the elevation of the tiles at an index, in pixels.
+    pub(crate) fn height(&self, index: usize) -> f32 {
+        self.heights.get(&index).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the elevations of the raised tiles at their indexes, in pixels.
+    pub(crate) fn heights(&self) -> &HashMap<usize, f32> {
+        &self.heights
+    }
+
+    /// Returns the value of a tile at an index in a channel of the z layer,
+    /// or `None` if it was never set.
+    pub(crate) fn channel_value(
+        &self,
+        z_order: usize,
+        name: &str,
+        index: usize,
+    ) -> Option<[f32; 4]> {
+        self.sprite_layers
+            .get(z_order)
+            .and_then(|layer| layer.as_ref())
+            .and_then(|layer| layer.channels.get(name))
+            .and_then(|values| values.get(index))
+            .copied()
+    }
+
+    /// At the given z layer, changes the values of the tiles in the channels
+    /// of the layer into vertex attributes for use with the renderer.
+    #[cfg(feature = "render")]
+    pub(crate) fn channels_to_renderer_parts(
+        &self,
+        z: usize,
+        channels: &HashMap<usize, Vec<TileChannel>>,
+        dimensions: Dimension2,
+    ) -> Vec<(String, VertexAttributeValues)> {
+        let channels = match channels.get(&z) {
+            Some(channels) => channels,
+            None => return Vec::new(),
+        };
+        let area = dimensions.area() as usize;
+        let layer = self.sprite_layers.get(z).and_then(|layer| layer.as_ref());
+        channels
+            .iter()
+            .map(|channel| {
+                let values = layer.and_then(|layer| layer.channels.get(&channel.name));
+                (
+                    channel.name.clone(),
+                    channel::channel_to_attribute(channel, area, values),
+                )
+            })
+            .collect()
+    }
+
     /// At the given z layer, changes the tiles into attributes for use with
-    /// the renderer using the given dimensions.
+    /// the renderer using the given dimensions, with the tile dimensions that
+    /// tile transforms are baked with. Tiles which are hidden by the opaque
+    /// tiles of the occlusion are transparent.
     ///
     /// Easier to pass in the dimensions opposed to storing it everywhere.
+    #[cfg(feature = "render")]
     pub(crate) fn tiles_to_renderer_parts(
         &self,
         z: usize,
         dimensions: Dimension2,
-    ) -> Option<(Vec<f32>, Vec<[f32; 4]>)> {
+        tile_dimensions: Dimension2,
+        occlusion: Option<&TileOcclusion>,
+    ) -> Option<TileAttributes> {
         let area = dimensions.area() as usize;
-        self.sprite_layers.get(z).and_then(|o| {
-            o.as_ref()
-                .map(|layer| layer.inner.as_ref().tiles_to_attributes(area))
-        })
+        let tile_dimensions =
+            Vec2::new(tile_dimensions.width as f32, tile_dimensions.height as f32);
+        let (indexes, mut colors, extras, mut offsets) =
+            self.sprite_layers.get(z).and_then(|o| {
+                o.as_ref().map(|layer| {
+                    layer
+                        .inner
+                        .as_ref()
+                        .tiles_to_attributes(area, tile_dimensions)
+                })
+            })?;
+        // Hidden tiles are made transparent, which leaves them out of the
+        // mesh, unless they are moved out from under the tiles above.
+        for index in occlusion.map_or_else(Vec::new, |occlusion| self.hidden_tiles(occlusion)) {
+            let vertex = index * 4;
+            let moved = match offsets.get(vertex..vertex + 4) {
+                Some(corners) => corners.iter().any(|offset| *offset != [0.0, 0.0]),
+                None => true,
+            };
+            if moved {
+                continue;
+            }
+            for color in colors.iter_mut().skip(vertex).take(4) {
+                *color &= 0x00ff_ffff;
+            }
+        }
+        for (index, height) in self.heights.iter() {
+            for corner in 0..4 {
+                if let Some(offset) = offsets.get_mut(index * 4 + corner) {
+                    offset[1] += height;
+                }
+            }
+        }
+        Some((indexes, colors, extras, offsets))
+    }
+
+    /// Returns the indexes of the tiles which are covered by an opaque tile
+    /// in one of the layers of the occlusion.
+    #[cfg(feature = "render")]
+    fn hidden_tiles(&self, occlusion: &TileOcclusion) -> Vec<usize> {
+        let mut hidden = HashSet::default();
+        for z_order in occlusion.layers.iter() {
+            for index in self.get_tile_indices(*z_order).unwrap_or_default() {
+                let tile = match self.get_tile(*z_order, index) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                if occlusion.sprites.contains(&tile.index)
+                    && tile.color.a() >= 1.0
+                    && tile.offset == Vec2::zero()
+                {
+                    hidden.insert(index);
+                }
+            }
+        }
+        hidden.into_iter().collect()
     }
 }
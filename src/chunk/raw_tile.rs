@@ -2,12 +2,27 @@ use crate::lib::*;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialEq, Debug)]
-/// A raw tile composed of simply an index and a color.
+/// A raw tile composed of simply an index, a color, reserved data and an
+/// offset.
 pub struct RawTile {
     /// The index of the tile in the sprite sheet.
     pub index: usize,
     /// The color, or tint, of the tile.
     pub color: Color,
+    /// Reserved data for custom shaders, such as a variant or a damage stage.
+    ///
+    /// It is passed to the vertex shader as the optional `float`
+    /// `Vertex_Tile_Extra` attribute, which the built in shaders ignore.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: u16,
+    /// The offset of the tile from its point in pixels, such as for grass
+    /// tufts and pebbles of a decoration layer which are slightly off the
+    /// grid.
+    ///
+    /// It moves the corners of the tile in its chunk mesh, after the tile is
+    /// scaled to its sprite.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offset: Vec2,
 }
 
 impl Default for RawTile {
@@ -15,41 +30,144 @@ impl Default for RawTile {
         RawTile {
             index: 0,
             color: Color::WHITE,
+            extra: 0,
+            offset: Vec2::zero(),
         }
     }
 }
 
-/// A utility function that takes an array of `Tile`s and splits the indexes and
-/// colors and returns them as separate vectors for use in the renderer.
-pub(crate) fn dense_tiles_to_attributes(tiles: &[RawTile]) -> (Vec<f32>, Vec<[f32; 4]>) {
+impl RawTile {
+    /// The tile which stands for no tile in a dense layer, whose sprite index
+    /// is `usize::MAX` which no sprite has.
+    ///
+    /// Tiles of sprite 0 and tiles which are fully transparent are tiles like
+    /// any other, so an invisible tile still collides and is still matched
+    /// by tile rules. Setting this tile is the same as removing the tile.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::RawTile, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// // An invisible wall.
+    /// let tint = Color::rgba(1.0, 1.0, 1.0, 0.0);
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 0, tint, ..Default::default() }).unwrap();
+    /// assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 0);
+    ///
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: RawTile::EMPTY.index, ..Default::default() }).unwrap();
+    /// assert!(tilemap.get_tile((1, 1), 0).is_none());
+    /// ```
+    pub const EMPTY: RawTile = RawTile {
+        index: usize::MAX,
+        color: Color::NONE,
+        extra: 0,
+        offset: Vec2::zero(),
+    };
+
+    /// Returns `true` if the tile is [`RawTile::EMPTY`], which stands for no
+    /// tile.
+    pub fn is_empty(&self) -> bool {
+        self.index == RawTile::EMPTY.index
+    }
+}
+
+/// The vertex attributes of the tiles of a chunk layer, which are the
+/// indexes, packed colors, extras and offsets of each of their vertices.
+#[cfg(feature = "render")]
+pub(crate) type TileAttributes = (Vec<u32>, Vec<u32>, Vec<f32>, Vec<[f32; 2]>);
+
+/// Packs a color into 8 bits for each of its red, green, blue and alpha
+/// channels, from the lowest bits up, which the shaders unpack with
+/// `unpackUnorm4x8`.
+#[cfg(feature = "render")]
+pub(crate) fn pack_color(color: Color) -> u32 {
+    let channels: [f32; 4] = color.into();
+    channels.iter().rev().fold(0, |packed, channel| {
+        (packed << 8) | (channel.clamp(0.0, 1.0) * 255.0).round() as u32
+    })
+}
+
+/// Returns the alpha of a packed color, which is zero for the empty tiles of
+/// sparse layers and for tiles which are hidden.
+#[cfg(feature = "render")]
+pub(crate) fn packed_alpha(color: u32) -> u32 {
+    color >> 24
+}
+
+/// A utility function that takes an array of `Tile`s and the indexes of
+/// those which may not be empty, splits the indexes, colors, extras and
+/// offsets and returns them as separate vectors for use in the renderer.
+///
+/// The tiles which are not occupied are left empty without being read.
+#[cfg(feature = "render")]
+pub(crate) fn dense_tiles_to_attributes<I: Iterator<Item = usize>>(
+    tiles: &[RawTile],
+    occupied: I,
+) -> TileAttributes {
     let capacity = tiles.len() * 4;
-    let mut tile_indexes: Vec<f32> = Vec::with_capacity(capacity);
-    let mut tile_colors: Vec<[f32; 4]> = Vec::with_capacity(capacity);
-    for tile in tiles.iter() {
-        tile_indexes.extend([tile.index as f32; 4].iter());
-        tile_colors.extend([tile.color.into(); 4].iter());
+    let mut tile_indexes = vec![0; capacity];
+    // Empty tiles are transparent, which leaves them out of the mesh.
+    let mut tile_colors = vec![0; capacity];
+    let mut tile_extras = vec![0.; capacity];
+    let mut tile_offsets = vec![[0.0, 0.0]; capacity];
+    for index in occupied {
+        let tile = match tiles.get(index) {
+            Some(tile) => tile,
+            None => continue,
+        };
+        let vertices = index * 4..index * 4 + 4;
+        for tile_index in tile_indexes.get_mut(vertices.clone()).into_iter().flatten() {
+            *tile_index = tile.index as u32;
+        }
+        for tile_color in tile_colors.get_mut(vertices.clone()).into_iter().flatten() {
+            *tile_color = pack_color(tile.color);
+        }
+        for tile_extra in tile_extras.get_mut(vertices.clone()).into_iter().flatten() {
+            *tile_extra = f32::from(tile.extra);
+        }
+        for tile_offset in tile_offsets.get_mut(vertices).into_iter().flatten() {
+            *tile_offset = [tile.offset.x, tile.offset.y];
+        }
     }
-    (tile_indexes, tile_colors)
+    (tile_indexes, tile_colors, tile_extras, tile_offsets)
 }
 
-/// A utility function that takes a sparse map of `Tile`s and splits the indexes
-/// and colors and returns them as separate vectors for use in the renderer.
+/// A utility function that takes a sparse map of `Tile`s and splits the
+/// indexes, colors, extras and offsets and returns them as separate vectors
+/// for use in the renderer.
+#[cfg(feature = "render")]
 pub(crate) fn sparse_tiles_to_attributes(
     area: usize,
     tiles: &HashMap<usize, RawTile>,
-) -> (Vec<f32>, Vec<[f32; 4]>) {
-    let mut tile_indexes = vec![0.; area * 4];
+) -> TileAttributes {
+    let mut tile_indexes = vec![0; area * 4];
     // If tiles are set with an alpha of 0, they are discarded.
-    let mut tile_colors = vec![[0.0, 0.0, 0.0, 0.0]; area * 4];
+    let mut tile_colors = vec![0; area * 4];
+    let mut tile_extras = vec![0.; area * 4];
+    let mut tile_offsets = vec![[0.0, 0.0]; area * 4];
     for (index, tile) in tiles.iter() {
         for i in 0..4 {
             if let Some(index) = tile_indexes.get_mut(index * 4 + i) {
-                *index = tile.index as f32;
+                *index = tile.index as u32;
             }
             if let Some(index) = tile_colors.get_mut(index * 4 + i) {
-                *index = tile.color.into();
+                *index = pack_color(tile.color);
+            }
+            if let Some(index) = tile_extras.get_mut(index * 4 + i) {
+                *index = f32::from(tile.extra);
+            }
+            if let Some(index) = tile_offsets.get_mut(index * 4 + i) {
+                *index = [tile.offset.x, tile.offset.y];
             }
         }
     }
-    (tile_indexes, tile_colors)
+    (tile_indexes, tile_colors, tile_extras, tile_offsets)
 }
@@ -1,12 +1,130 @@
+#[cfg(feature = "render")]
+use crate::chunk::entity::{ChunkAnimation, ChunkColor};
 use crate::lib::*;
+#[cfg(feature = "lighting")]
+use crate::light::ChunkLighting;
+#[cfg(feature = "render")]
+use crate::palette::ChunkPalette;
+#[cfg(feature = "render")]
+use crate::{mask::MASK_TEXTURE_FORMAT, picking::PICKING_TEXTURE_FORMAT};
+
+/// Builds a chunk render pipeline from its shader stages, which blends the
+/// tiles over what is behind them.
+#[cfg(feature = "render")]
+pub(crate) fn chunk_pipeline_descriptor(stages: ShaderStages) -> PipelineDescriptor {
+    PipelineDescriptor {
+        rasterization_state: Some(RasterizationStateDescriptor {
+            front_face: FrontFace::Ccw,
+            cull_mode: CullMode::None,
+            depth_bias: 0,
+            depth_bias_slope_scale: 0.0,
+            depth_bias_clamp: 0.0,
+            clamp_depth: false,
+        }),
+        color_states: vec![ColorStateDescriptor {
+            format: TextureFormat::default(),
+            color_blend: BlendDescriptor {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            },
+            alpha_blend: BlendDescriptor {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            write_mask: ColorWrite::ALL,
+        }],
+        depth_stencil_state: Some(DepthStencilStateDescriptor {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::LessEqual,
+            stencil: StencilStateDescriptor {
+                front: StencilStateFaceDescriptor::IGNORE,
+                back: StencilStateFaceDescriptor::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+        }),
+        ..PipelineDescriptor::new(stages)
+    }
+}
 
 macro_rules! build_chunk_pipeline {
     ($handle: ident, $id: expr, $name: ident, $file: expr) => {
         /// The constant render pipeline for a chunk.
+        #[cfg(feature = "render")]
         pub(crate) const $handle: HandleUntyped =
             HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, $id);
 
         /// Builds the chunk render pipeline.
+        #[cfg(feature = "render")]
+        fn $name(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+            chunk_pipeline_descriptor(ShaderStages {
+                vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, include_str!($file))),
+                fragment: Some(shaders.add(Shader::from_glsl(
+                    ShaderStage::Fragment,
+                    include_str!("tilemap.frag"),
+                ))),
+            })
+        }
+    };
+}
+
+/// Builds a chunk pipeline which shades the tiles with their normal maps.
+macro_rules! build_chunk_lit_pipeline {
+    ($handle: ident, $id: expr, $name: ident, $file: expr) => {
+        /// The constant lit render pipeline for a chunk.
+        #[cfg(feature = "lighting")]
+        pub(crate) const $handle: HandleUntyped =
+            HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, $id);
+
+        /// Builds the chunk lit render pipeline.
+        #[cfg(feature = "lighting")]
+        fn $name(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+            chunk_pipeline_descriptor(ShaderStages {
+                vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, include_str!($file))),
+                fragment: Some(shaders.add(Shader::from_glsl(
+                    ShaderStage::Fragment,
+                    include_str!("tilemap-lit.frag"),
+                ))),
+            })
+        }
+    };
+}
+
+/// Builds a chunk pipeline which colors the tiles from a palette.
+macro_rules! build_chunk_palette_pipeline {
+    ($handle: ident, $id: expr, $name: ident, $file: expr) => {
+        /// The constant palette render pipeline for a chunk.
+        #[cfg(feature = "render")]
+        pub(crate) const $handle: HandleUntyped =
+            HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, $id);
+
+        /// Builds the chunk palette render pipeline.
+        #[cfg(feature = "render")]
+        fn $name(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+            chunk_pipeline_descriptor(ShaderStages {
+                vertex: shaders.add(Shader::from_glsl(ShaderStage::Vertex, include_str!($file))),
+                fragment: Some(shaders.add(Shader::from_glsl(
+                    ShaderStage::Fragment,
+                    include_str!("tilemap-palette.frag"),
+                ))),
+            })
+        }
+    };
+}
+
+/// Builds a chunk pipeline which draws to the tilemap mask pass.
+macro_rules! build_chunk_mask_pipeline {
+    ($handle: ident, $id: expr, $name: ident, $file: expr) => {
+        /// The constant mask render pipeline for a chunk.
+        #[cfg(feature = "render")]
+        pub(crate) const $handle: HandleUntyped =
+            HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, $id);
+
+        /// Builds the chunk mask render pipeline.
+        #[cfg(feature = "render")]
         fn $name(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
             PipelineDescriptor {
                 rasterization_state: Some(RasterizationStateDescriptor {
@@ -18,17 +136,49 @@ macro_rules! build_chunk_pipeline {
                     clamp_depth: false,
                 }),
                 color_states: vec![ColorStateDescriptor {
-                    format: TextureFormat::default(),
-                    color_blend: BlendDescriptor {
-                        src_factor: BlendFactor::SrcAlpha,
-                        dst_factor: BlendFactor::OneMinusSrcAlpha,
-                        operation: BlendOperation::Add,
-                    },
-                    alpha_blend: BlendDescriptor {
-                        src_factor: BlendFactor::One,
-                        dst_factor: BlendFactor::One,
-                        operation: BlendOperation::Add,
-                    },
+                    format: MASK_TEXTURE_FORMAT,
+                    color_blend: BlendDescriptor::REPLACE,
+                    alpha_blend: BlendDescriptor::REPLACE,
+                    write_mask: ColorWrite::ALL,
+                }],
+                depth_stencil_state: None,
+                ..PipelineDescriptor::new(ShaderStages {
+                    vertex: shaders
+                        .add(Shader::from_glsl(ShaderStage::Vertex, include_str!($file))),
+                    fragment: Some(shaders.add(Shader::from_glsl(
+                        ShaderStage::Fragment,
+                        include_str!("tilemap-mask.frag"),
+                    ))),
+                })
+            }
+        }
+    };
+}
+
+/// Builds a chunk pipeline which draws to the tilemap picking pass.
+macro_rules! build_chunk_picking_pipeline {
+    ($handle: ident, $id: expr, $name: ident, $file: expr) => {
+        /// The constant picking render pipeline for a chunk.
+        #[cfg(feature = "render")]
+        pub(crate) const $handle: HandleUntyped =
+            HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, $id);
+
+        /// Builds the chunk picking render pipeline.
+        #[cfg(feature = "render")]
+        fn $name(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+            PipelineDescriptor {
+                rasterization_state: Some(RasterizationStateDescriptor {
+                    front_face: FrontFace::Ccw,
+                    cull_mode: CullMode::None,
+                    depth_bias: 0,
+                    depth_bias_slope_scale: 0.0,
+                    depth_bias_clamp: 0.0,
+                    clamp_depth: false,
+                }),
+                color_states: vec![ColorStateDescriptor {
+                    format: PICKING_TEXTURE_FORMAT,
+                    color_blend: BlendDescriptor::REPLACE,
+                    alpha_blend: BlendDescriptor::REPLACE,
                     write_mask: ColorWrite::ALL,
                 }],
                 depth_stencil_state: Some(DepthStencilStateDescriptor {
@@ -47,7 +197,7 @@ macro_rules! build_chunk_pipeline {
                         .add(Shader::from_glsl(ShaderStage::Vertex, include_str!($file))),
                     fragment: Some(shaders.add(Shader::from_glsl(
                         ShaderStage::Fragment,
-                        include_str!("tilemap.frag"),
+                        include_str!("tilemap-picking.frag"),
                     ))),
                 })
             }
@@ -97,10 +247,212 @@ build_chunk_pipeline!(
     build_chunk_hexrows_odd,
     "tilemap-hexrows-odd.vert"
 );
+build_chunk_pipeline!(
+    CHUNK_ISO_DIAMOND_PIPELINE,
+    5731918364528257131,
+    build_chunk_iso_diamond,
+    "tilemap-iso-diamond.vert"
+);
+
+build_chunk_mask_pipeline!(
+    CHUNK_SQUARE_MASK_PIPELINE,
+    6793994132578096183,
+    build_chunk_square_mask,
+    "tilemap-square.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEX_X_MASK_PIPELINE,
+    7629834366591971685,
+    build_chunk_hex_x_mask,
+    "tilemap-hex-x.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEX_Y_MASK_PIPELINE,
+    7195257399323155458,
+    build_chunk_hex_y_mask,
+    "tilemap-hex-y.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEXCOLS_EVEN_MASK_PIPELINE,
+    7448320487722020989,
+    build_chunk_hexcols_even_mask,
+    "tilemap-hexcols-even.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEXCOLS_ODD_MASK_PIPELINE,
+    1506407383268791202,
+    build_chunk_hexcols_odd_mask,
+    "tilemap-hexcols-odd.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEXROWS_EVEN_MASK_PIPELINE,
+    5604598026303743228,
+    build_chunk_hexrows_even_mask,
+    "tilemap-hexrows-even.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_HEXROWS_ODD_MASK_PIPELINE,
+    4537544724149101149,
+    build_chunk_hexrows_odd_mask,
+    "tilemap-hexrows-odd.vert"
+);
+build_chunk_mask_pipeline!(
+    CHUNK_ISO_DIAMOND_MASK_PIPELINE,
+    4124937572384801041,
+    build_chunk_iso_diamond_mask,
+    "tilemap-iso-diamond.vert"
+);
+
+build_chunk_palette_pipeline!(
+    CHUNK_SQUARE_PALETTE_PIPELINE,
+    5319701231285543929,
+    build_chunk_square_palette,
+    "tilemap-square.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEX_X_PALETTE_PIPELINE,
+    5705688691792924229,
+    build_chunk_hex_x_palette,
+    "tilemap-hex-x.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEX_Y_PALETTE_PIPELINE,
+    1610228142360775723,
+    build_chunk_hex_y_palette,
+    "tilemap-hex-y.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEXCOLS_EVEN_PALETTE_PIPELINE,
+    2923067563986907690,
+    build_chunk_hexcols_even_palette,
+    "tilemap-hexcols-even.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEXCOLS_ODD_PALETTE_PIPELINE,
+    3983574798309728921,
+    build_chunk_hexcols_odd_palette,
+    "tilemap-hexcols-odd.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEXROWS_EVEN_PALETTE_PIPELINE,
+    3033508211462817347,
+    build_chunk_hexrows_even_palette,
+    "tilemap-hexrows-even.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_HEXROWS_ODD_PALETTE_PIPELINE,
+    4163750932666821231,
+    build_chunk_hexrows_odd_palette,
+    "tilemap-hexrows-odd.vert"
+);
+build_chunk_palette_pipeline!(
+    CHUNK_ISO_DIAMOND_PALETTE_PIPELINE,
+    8056209198110744737,
+    build_chunk_iso_diamond_palette,
+    "tilemap-iso-diamond.vert"
+);
+
+build_chunk_lit_pipeline!(
+    CHUNK_SQUARE_LIT_PIPELINE,
+    2847561093846271953,
+    build_chunk_square_lit,
+    "tilemap-square.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEX_X_LIT_PIPELINE,
+    6410298375610284719,
+    build_chunk_hex_x_lit,
+    "tilemap-hex-x.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEX_Y_LIT_PIPELINE,
+    8372619450271836104,
+    build_chunk_hex_y_lit,
+    "tilemap-hex-y.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEXCOLS_EVEN_LIT_PIPELINE,
+    1937462810573920468,
+    build_chunk_hexcols_even_lit,
+    "tilemap-hexcols-even.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEXCOLS_ODD_LIT_PIPELINE,
+    5583920176452019387,
+    build_chunk_hexcols_odd_lit,
+    "tilemap-hexcols-odd.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEXROWS_EVEN_LIT_PIPELINE,
+    7720194836501928374,
+    build_chunk_hexrows_even_lit,
+    "tilemap-hexrows-even.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_HEXROWS_ODD_LIT_PIPELINE,
+    3068472915063728195,
+    build_chunk_hexrows_odd_lit,
+    "tilemap-hexrows-odd.vert"
+);
+build_chunk_lit_pipeline!(
+    CHUNK_ISO_DIAMOND_LIT_PIPELINE,
+    9148305726193048571,
+    build_chunk_iso_diamond_lit,
+    "tilemap-iso-diamond.vert"
+);
+
+build_chunk_picking_pipeline!(
+    CHUNK_SQUARE_PICKING_PIPELINE,
+    3549069068831355575,
+    build_chunk_square_picking,
+    "tilemap-square.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEX_X_PICKING_PIPELINE,
+    4643535997168155365,
+    build_chunk_hex_x_picking,
+    "tilemap-hex-x.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEX_Y_PICKING_PIPELINE,
+    1420384663967596990,
+    build_chunk_hex_y_picking,
+    "tilemap-hex-y.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEXCOLS_EVEN_PICKING_PIPELINE,
+    4401617786710636147,
+    build_chunk_hexcols_even_picking,
+    "tilemap-hexcols-even.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEXCOLS_ODD_PICKING_PIPELINE,
+    7791924664923549836,
+    build_chunk_hexcols_odd_picking,
+    "tilemap-hexcols-odd.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEXROWS_EVEN_PICKING_PIPELINE,
+    4009355186901248145,
+    build_chunk_hexrows_even_picking,
+    "tilemap-hexrows-even.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_HEXROWS_ODD_PICKING_PIPELINE,
+    2930887739149733563,
+    build_chunk_hexrows_odd_picking,
+    "tilemap-hexrows-odd.vert"
+);
+build_chunk_picking_pipeline!(
+    CHUNK_ISO_DIAMOND_PICKING_PIPELINE,
+    4268480956920965422,
+    build_chunk_iso_diamond_picking,
+    "tilemap-iso-diamond.vert"
+);
 
-/// Topology of the tilemap grid (square or hex)
+/// Topology of the tilemap grid (square, hex or iso)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum GridTopology {
     /// Square grid
     Square,
@@ -116,8 +468,31 @@ pub enum GridTopology {
     HexEvenCols,
     /// Hex grid with offset on odd columns (hexes with flat top).
     HexOddCols,
+    /// Isometric grid of diamonds, the x axis going up and right and the y
+    /// axis going up and left. Tiles are depth sorted, see [`iso`].
+    ///
+    /// [`iso`]: crate::iso
+    IsoDiamond,
 }
 
+impl Display for GridTopology {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use GridTopology::*;
+        let name = match self {
+            Square => "square",
+            HexY => "hex y",
+            HexX => "hex x",
+            HexEvenRows => "hex even rows",
+            HexOddRows => "hex odd rows",
+            HexEvenCols => "hex even columns",
+            HexOddCols => "hex odd columns",
+            IsoDiamond => "iso diamond",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(feature = "render")]
 impl GridTopology {
     /// Takes a grid topology and returns a handle.
     pub(crate) fn to_pipeline_handle(&self) -> HandleUntyped {
@@ -130,16 +505,193 @@ impl GridTopology {
             HexOddRows => CHUNK_HEXROWS_ODD_PIPELINE,
             HexEvenCols => CHUNK_HEXCOLS_EVEN_PIPELINE,
             HexOddCols => CHUNK_HEXCOLS_ODD_PIPELINE,
+            IsoDiamond => CHUNK_ISO_DIAMOND_PIPELINE,
+        }
+    }
+
+    /// Takes a grid topology and returns the handle of its palette pipeline.
+    pub(crate) fn to_palette_pipeline_handle(self) -> HandleUntyped {
+        use GridTopology::*;
+        match self {
+            Square => CHUNK_SQUARE_PALETTE_PIPELINE,
+            HexX => CHUNK_HEX_X_PALETTE_PIPELINE,
+            HexY => CHUNK_HEX_Y_PALETTE_PIPELINE,
+            HexEvenCols => CHUNK_HEXCOLS_EVEN_PALETTE_PIPELINE,
+            HexOddCols => CHUNK_HEXCOLS_ODD_PALETTE_PIPELINE,
+            HexEvenRows => CHUNK_HEXROWS_EVEN_PALETTE_PIPELINE,
+            HexOddRows => CHUNK_HEXROWS_ODD_PALETTE_PIPELINE,
+            IsoDiamond => CHUNK_ISO_DIAMOND_PALETTE_PIPELINE,
+        }
+    }
+
+    /// Takes a grid topology and returns the handle of its lit pipeline.
+    #[cfg(feature = "lighting")]
+    pub(crate) fn to_lit_pipeline_handle(self) -> HandleUntyped {
+        use GridTopology::*;
+        match self {
+            Square => CHUNK_SQUARE_LIT_PIPELINE,
+            HexX => CHUNK_HEX_X_LIT_PIPELINE,
+            HexY => CHUNK_HEX_Y_LIT_PIPELINE,
+            HexEvenCols => CHUNK_HEXCOLS_EVEN_LIT_PIPELINE,
+            HexOddCols => CHUNK_HEXCOLS_ODD_LIT_PIPELINE,
+            HexEvenRows => CHUNK_HEXROWS_EVEN_LIT_PIPELINE,
+            HexOddRows => CHUNK_HEXROWS_ODD_LIT_PIPELINE,
+            IsoDiamond => CHUNK_ISO_DIAMOND_LIT_PIPELINE,
+        }
+    }
+
+    /// Returns the source of the vertex shader of the grid topology.
+    fn vertex_shader_source(self) -> &'static str {
+        use GridTopology::*;
+        match self {
+            Square => include_str!("tilemap-square.vert"),
+            HexY => include_str!("tilemap-hex-y.vert"),
+            HexX => include_str!("tilemap-hex-x.vert"),
+            HexEvenRows => include_str!("tilemap-hexrows-even.vert"),
+            HexOddRows => include_str!("tilemap-hexrows-odd.vert"),
+            HexEvenCols => include_str!("tilemap-hexcols-even.vert"),
+            HexOddCols => include_str!("tilemap-hexcols-odd.vert"),
+            IsoDiamond => include_str!("tilemap-iso-diamond.vert"),
+        }
+    }
+
+    /// Builds a chunk render pipeline of the grid topology which draws with
+    /// a fragment shader of your own, for a tilemap built with
+    /// [`TilemapBuilder::pipeline`].
+    ///
+    /// The vertex shader of the topology is kept, so the fragment shader gets
+    /// the same inputs as the one of the crate: the atlas coordinates of the
+    /// tile at location 0, its color at location 1, its point as a flat
+    /// `ivec3` at location 2, its world position at location 3, its sprite
+    /// index as a flat `int` at location 4, which is `-1` for sprites with
+    /// their own rect, and its corner within the sprite at location 5. The texture atlas is bound to set 1 at bindings
+    /// 2 and 3 as `TextureAtlas_texture` and `TextureAtlas_texture_sampler`,
+    /// and the color of the tilemap and the layer to set 3 at binding 0 as
+    /// the `ChunkColor_color` uniform, as is the shader animation of the
+    /// layer at binding 5 as the `ChunkAnimation_animation` uniform. Uniforms of your own, such as the time of a dissolve, are bound from
+    /// components of the chunk entities, which are the children of the
+    /// tilemap entity.
+    ///
+    /// This is behind the `render` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::prelude::*;
+    /// use bevy_render::{
+    ///     pipeline::{PipelineDescriptor, RenderPipelines},
+    ///     shader::{Shader, ShaderStage},
+    /// };
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    ///
+    /// const DISSOLVE: &str = r#"
+    /// #version 450
+    /// layout(location = 0) in vec2 v_Uv;
+    /// layout(location = 1) in vec4 v_Color;
+    /// layout(location = 2) flat in ivec3 v_TilePoint;
+    /// layout(location = 0) out vec4 o_Target;
+    /// layout(set = 1, binding = 2) uniform texture2D TextureAtlas_texture;
+    /// layout(set = 1, binding = 3) uniform sampler TextureAtlas_texture_sampler;
+    /// void main() {
+    ///     if ((v_TilePoint.x + v_TilePoint.y) % 2 == 0) {
+    ///         discard;
+    ///     }
+    ///     o_Target = v_Color * texture(
+    ///         sampler2D(TextureAtlas_texture, TextureAtlas_texture_sampler),
+    ///         v_Uv
+    ///     );
+    /// }
+    /// "#;
+    ///
+    /// let mut harness = tilemap_test_harness();
+    /// let pipeline = {
+    ///     let resources = &harness.app().resources;
+    ///     let mut shaders = resources.get_mut::<Assets<Shader>>().unwrap();
+    ///     let mut pipelines = resources.get_mut::<Assets<PipelineDescriptor>>().unwrap();
+    ///     let fragment = shaders.add(Shader::from_glsl(ShaderStage::Fragment, DISSOLVE));
+    ///     pipelines.add(GridTopology::Square.chunk_pipeline(&mut shaders, fragment))
+    /// };
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .pipeline(pipeline.clone())
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// let chunk = harness.chunk_entities(entity, (0, 0))[0];
+    /// let render_pipelines = harness.app().world.get::<RenderPipelines>(chunk).unwrap();
+    /// assert_eq!(render_pipelines.pipelines[0].pipeline, pipeline);
+    /// ```
+    ///
+    /// [`TilemapBuilder::pipeline`]: crate::tilemap::TilemapBuilder::pipeline
+    pub fn chunk_pipeline(
+        self,
+        shaders: &mut Assets<Shader>,
+        fragment: Handle<Shader>,
+    ) -> PipelineDescriptor {
+        chunk_pipeline_descriptor(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                self.vertex_shader_source(),
+            )),
+            fragment: Some(fragment),
+        })
+    }
+
+    /// Takes a grid topology and returns the handle of its mask pipeline.
+    pub(crate) fn to_mask_pipeline_handle(self) -> HandleUntyped {
+        use GridTopology::*;
+        match self {
+            Square => CHUNK_SQUARE_MASK_PIPELINE,
+            HexX => CHUNK_HEX_X_MASK_PIPELINE,
+            HexY => CHUNK_HEX_Y_MASK_PIPELINE,
+            HexEvenCols => CHUNK_HEXCOLS_EVEN_MASK_PIPELINE,
+            HexOddCols => CHUNK_HEXCOLS_ODD_MASK_PIPELINE,
+            HexEvenRows => CHUNK_HEXROWS_EVEN_MASK_PIPELINE,
+            HexOddRows => CHUNK_HEXROWS_ODD_MASK_PIPELINE,
+            IsoDiamond => CHUNK_ISO_DIAMOND_MASK_PIPELINE,
+        }
+    }
+
+    /// Takes a grid topology and returns the handle of its picking pipeline.
+    pub(crate) fn to_picking_pipeline_handle(self) -> HandleUntyped {
+        use GridTopology::*;
+        match self {
+            Square => CHUNK_SQUARE_PICKING_PIPELINE,
+            HexX => CHUNK_HEX_X_PICKING_PIPELINE,
+            HexY => CHUNK_HEX_Y_PICKING_PIPELINE,
+            HexEvenCols => CHUNK_HEXCOLS_EVEN_PICKING_PIPELINE,
+            HexOddCols => CHUNK_HEXCOLS_ODD_PICKING_PIPELINE,
+            HexEvenRows => CHUNK_HEXROWS_EVEN_PICKING_PIPELINE,
+            HexOddRows => CHUNK_HEXROWS_ODD_PICKING_PIPELINE,
+            IsoDiamond => CHUNK_ISO_DIAMOND_PICKING_PIPELINE,
         }
     }
 }
 
+/// The names of the tilemap nodes in the render graph.
+#[cfg(feature = "render")]
+pub mod node {
+    /// The node which binds the colors of the chunks.
+    pub const CHUNK_COLOR: &str = "chunk_color";
+    /// The node which binds the shader animations of the chunks.
+    pub const CHUNK_ANIMATION: &str = "chunk_animation";
+}
+
 /// A trait which implements the tilemap graph to a render graph.
+#[cfg(feature = "render")]
 pub trait TilemapRenderGraphBuilder {
     /// Adds the tilemaps render graph.
     fn add_tilemap_graph(&mut self, resources: &Resources) -> &mut Self;
 }
 
+#[cfg(feature = "render")]
 impl TilemapRenderGraphBuilder for RenderGraph {
     fn add_tilemap_graph(&mut self, resources: &Resources) -> &mut Self {
         let mut pipelines = resources
@@ -171,17 +723,185 @@ impl TilemapRenderGraphBuilder for RenderGraph {
             CHUNK_HEXROWS_ODD_PIPELINE,
             build_chunk_hexrows_odd(&mut shaders),
         );
+        pipelines.set_untracked(
+            CHUNK_ISO_DIAMOND_PIPELINE,
+            build_chunk_iso_diamond(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_SQUARE_MASK_PIPELINE,
+            build_chunk_square_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_X_MASK_PIPELINE,
+            build_chunk_hex_x_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_Y_MASK_PIPELINE,
+            build_chunk_hex_y_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_EVEN_MASK_PIPELINE,
+            build_chunk_hexcols_even_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_ODD_MASK_PIPELINE,
+            build_chunk_hexcols_odd_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_EVEN_MASK_PIPELINE,
+            build_chunk_hexrows_even_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_ODD_MASK_PIPELINE,
+            build_chunk_hexrows_odd_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_ISO_DIAMOND_MASK_PIPELINE,
+            build_chunk_iso_diamond_mask(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_SQUARE_PALETTE_PIPELINE,
+            build_chunk_square_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_X_PALETTE_PIPELINE,
+            build_chunk_hex_x_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_Y_PALETTE_PIPELINE,
+            build_chunk_hex_y_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_EVEN_PALETTE_PIPELINE,
+            build_chunk_hexcols_even_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_ODD_PALETTE_PIPELINE,
+            build_chunk_hexcols_odd_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_EVEN_PALETTE_PIPELINE,
+            build_chunk_hexrows_even_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_ODD_PALETTE_PIPELINE,
+            build_chunk_hexrows_odd_palette(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_ISO_DIAMOND_PALETTE_PIPELINE,
+            build_chunk_iso_diamond_palette(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_SQUARE_LIT_PIPELINE,
+            build_chunk_square_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEX_X_LIT_PIPELINE,
+            build_chunk_hex_x_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEX_Y_LIT_PIPELINE,
+            build_chunk_hex_y_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_EVEN_LIT_PIPELINE,
+            build_chunk_hexcols_even_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_ODD_LIT_PIPELINE,
+            build_chunk_hexcols_odd_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_EVEN_LIT_PIPELINE,
+            build_chunk_hexrows_even_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_ODD_LIT_PIPELINE,
+            build_chunk_hexrows_odd_lit(&mut shaders),
+        );
+        #[cfg(feature = "lighting")]
+        pipelines.set_untracked(
+            CHUNK_ISO_DIAMOND_LIT_PIPELINE,
+            build_chunk_iso_diamond_lit(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_SQUARE_PICKING_PIPELINE,
+            build_chunk_square_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_X_PICKING_PIPELINE,
+            build_chunk_hex_x_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEX_Y_PICKING_PIPELINE,
+            build_chunk_hex_y_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_EVEN_PICKING_PIPELINE,
+            build_chunk_hexcols_even_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXCOLS_ODD_PICKING_PIPELINE,
+            build_chunk_hexcols_odd_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_EVEN_PICKING_PIPELINE,
+            build_chunk_hexrows_even_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_HEXROWS_ODD_PICKING_PIPELINE,
+            build_chunk_hexrows_odd_picking(&mut shaders),
+        );
+        pipelines.set_untracked(
+            CHUNK_ISO_DIAMOND_PICKING_PIPELINE,
+            build_chunk_iso_diamond_picking(&mut shaders),
+        );
 
-        self
-    }
-}
-
-/// Prevents the traits in this module from being implemented outside the crate.
-mod private {
-    use super::RenderGraph;
+        self.add_system_node(
+            node::CHUNK_COLOR,
+            RenderResourcesNode::<ChunkColor>::new(true),
+        );
+        // Headless apps have no main pass for the colors to be bound before.
+        if self.get_node_state(base::node::MAIN_PASS).is_ok() {
+            self.add_node_edge(node::CHUNK_COLOR, base::node::MAIN_PASS)
+                .expect("The main pass is missing from the render graph.");
+        }
+        self.add_system_node(
+            node::CHUNK_ANIMATION,
+            RenderResourcesNode::<ChunkAnimation>::new(true),
+        );
+        if self.get_node_state(base::node::MAIN_PASS).is_ok() {
+            self.add_node_edge(node::CHUNK_ANIMATION, base::node::MAIN_PASS)
+                .expect("The main pass is missing from the render graph.");
+        }
+        self.add_system_node(
+            crate::palette::node::CHUNK_PALETTE,
+            RenderResourcesNode::<ChunkPalette>::new(true),
+        );
+        if self.get_node_state(base::node::MAIN_PASS).is_ok() {
+            self.add_node_edge(crate::palette::node::CHUNK_PALETTE, base::node::MAIN_PASS)
+                .expect("The main pass is missing from the render graph.");
+        }
 
-    /// Seals the type.
-    pub trait Sealed {}
+        #[cfg(feature = "lighting")]
+        {
+            self.add_system_node(
+                crate::light::node::CHUNK_LIGHTING,
+                RenderResourcesNode::<ChunkLighting>::new(true),
+            );
+            if self.get_node_state(base::node::MAIN_PASS).is_ok() {
+                self.add_node_edge(crate::light::node::CHUNK_LIGHTING, base::node::MAIN_PASS)
+                    .expect("The main pass is missing from the render graph.");
+            }
+        }
 
-    impl Sealed for RenderGraph {}
+        self
+    }
 }
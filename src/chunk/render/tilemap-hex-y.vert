@@ -1,11 +1,20 @@
 #version 450
 
 layout(location = 0) in vec3 Vertex_Position;
-layout(location = 1) in float Vertex_Tile_Index;
-layout(location = 2) in vec4 Vertex_Tile_Color;
+layout(location = 1) in uint Vertex_Tile_Index;
+// the color is packed as 8 bits for each of red, green, blue and alpha
+layout(location = 2) in uint Vertex_Tile_Color;
+layout(location = 3) in ivec3 Vertex_Tile_Point;
+layout(location = 4) in vec4 Vertex_Tile_Source;
+layout(location = 5) in vec2 Vertex_Tile_Uv;
+layout(location = 6) in vec2 Vertex_Tile_Offset;
 
 layout(location = 0) out vec2 v_Uv;
 layout(location = 1) out vec4 v_Color;
+layout(location = 2) flat out ivec3 v_TilePoint;
+layout(location = 3) out vec3 v_WorldPosition;
+layout(location = 4) flat out int v_TileIndex;
+layout(location = 5) out vec2 v_TileUv;
 
 layout(set = 0, binding = 0) uniform Camera {
     mat4 ViewProj;
@@ -32,8 +41,13 @@ layout(set = 2, binding = 0) uniform Transform {
 };
 
 void main() {
-    Rect sprite_rect = Textures[int(Vertex_Tile_Index)];
+    Rect sprite_rect = Textures[Vertex_Tile_Index];
     vec2 sprite_dimensions = sprite_rect.end - sprite_rect.begin;
+    // a sprite with its own rect is laid out with the size of its source
+    bool has_source = Vertex_Tile_Source.x > 0.0;
+    if (has_source) {
+        sprite_dimensions = Vertex_Tile_Source.xy;
+    }
 
     int local_index = gl_VertexIndex % 4;
 
@@ -62,6 +76,21 @@ void main() {
         sprite_rect.end
     );
     v_Uv = floor(atlas_positions[local_index]) / AtlasSize;
-    v_Color = Vertex_Tile_Color;
-    gl_Position = ViewProj * ChunkTransform * vec4(ceil(vertex_position), 1.0);
+    if (has_source) {
+        // move the corner in to the trimmed sprite, and sample its own rect
+        vertex_position.xy += Vertex_Tile_Source.zw;
+        v_Uv = Vertex_Tile_Uv / AtlasSize;
+    }
+    // move the tile off the grid by its offset in pixels
+    vertex_position.xy += Vertex_Tile_Offset;
+    // the sprite index and the corner within the sprite, which the fragment
+    // shader animates layers with, see the `animation` module
+    vec2 tile_uvs[4] = vec2[](vec2(0.0, 1.0), vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(1.0, 1.0));
+    v_TileIndex = has_source ? -1 : int(Vertex_Tile_Index);
+    v_TileUv = tile_uvs[local_index];
+    v_Color = unpackUnorm4x8(Vertex_Tile_Color);
+    v_TilePoint = Vertex_Tile_Point;
+    vec4 world_position = ChunkTransform * vec4(ceil(vertex_position), 1.0);
+    v_WorldPosition = world_position.xyz;
+    gl_Position = ViewProj * world_position;
 }
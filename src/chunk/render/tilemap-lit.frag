@@ -0,0 +1,101 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec4 v_Color;
+layout(location = 3) in vec3 v_WorldPosition;
+layout(location = 4) flat in int v_TileIndex;
+layout(location = 5) in vec2 v_TileUv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 0) uniform TextureAtlas_size {
+    vec2 AtlasSize;
+};
+
+struct Rect {
+    // Upper-left coordinate
+    vec2 begin;
+    // Bottom-right coordinate
+    vec2 end;
+};
+
+layout(set = 1, binding = 1) buffer TextureAtlas_textures {
+    Rect[] Textures;
+};
+
+layout(set = 1, binding = 2) uniform texture2D TextureAtlas_texture;
+layout(set = 1, binding = 3) uniform sampler TextureAtlas_texture_sampler;
+
+layout(set = 3, binding = 0) uniform ChunkColor_color {
+    vec4 ChunkColor;
+};
+
+layout(set = 3, binding = 1) uniform texture2D ChunkLighting_normal_map;
+layout(set = 3, binding = 2) uniform sampler ChunkLighting_normal_map_sampler;
+layout(set = 3, binding = 3) uniform ChunkLighting_ambient {
+    vec4 Ambient;
+};
+// every light is its world position in xy, its height above the tiles in z
+// and its radius in w, followed by its color multiplied by its intensity
+layout(set = 3, binding = 4) uniform ChunkLighting_lights {
+    vec4 Lights[32];
+};
+
+// the kind of the animation of the layer in x, which is none, a scroll or a
+// cycle of frames, its two parameters in y and z and the time in seconds in w
+layout(set = 3, binding = 5) uniform ChunkAnimation_animation {
+    vec4 Animation;
+};
+
+// returns the atlas coordinates of the tile with the animation of its layer
+vec2 animated_uv() {
+    // sprites with their own rect are not animated
+    if (v_TileIndex < 0 || Animation.x == 0.0) {
+        return v_Uv;
+    }
+    int index = v_TileIndex;
+    vec2 uv = v_TileUv;
+    if (Animation.x == 1.0) {
+        // scrolled in pixels per second with y up, wrapped around the sprite
+        Rect sprite_rect = Textures[index];
+        vec2 sprite_dimensions = sprite_rect.end - sprite_rect.begin;
+        uv = fract(uv + vec2(-Animation.y, Animation.z) * Animation.w / sprite_dimensions);
+    } else {
+        // the frames are the sprites which follow the sprite index of the tile
+        index += int(mod(floor(Animation.w * Animation.z), Animation.y));
+    }
+    Rect rect = Textures[index];
+    return (rect.begin + uv * (rect.end - rect.begin)) / AtlasSize;
+}
+
+void main() {
+    vec2 uv = animated_uv();
+    vec4 color = ChunkColor * v_Color * texture(
+        sampler2D(TextureAtlas_texture, TextureAtlas_texture_sampler),
+        uv
+    );
+    // fully transparent pixels must not write depth, or they would hide the
+    // tiles behind them
+    if (color.a == 0.0) {
+        discard;
+    }
+    // the normal map is laid out like the atlas, with its normals facing the
+    // camera along z
+    vec3 normal = normalize(texture(
+        sampler2D(ChunkLighting_normal_map, ChunkLighting_normal_map_sampler),
+        uv
+    ).rgb * 2.0 - 1.0);
+    vec3 light = Ambient.rgb;
+    for (int i = 0; i < 16; i++) {
+        vec4 position = Lights[i * 2];
+        // lights without a radius are the padding of the array
+        if (position.w <= 0.0) {
+            continue;
+        }
+        vec3 to_light = vec3(position.xy - v_WorldPosition.xy, position.z);
+        float falloff = clamp(1.0 - length(to_light.xy) / position.w, 0.0, 1.0);
+        float diffuse = max(dot(normal, normalize(to_light)), 0.0);
+        light += Lights[i * 2 + 1].rgb * diffuse * falloff * falloff;
+    }
+    o_Target = vec4(color.rgb * light, color.a);
+}
@@ -0,0 +1,21 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec4 v_Color;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 2) uniform texture2D TextureAtlas_texture;
+layout(set = 1, binding = 3) uniform sampler TextureAtlas_texture_sampler;
+
+void main() {
+    float alpha = v_Color.a * texture(
+        sampler2D(TextureAtlas_texture, TextureAtlas_texture_sampler),
+        v_Uv
+    ).a;
+    // only the pixels which would be drawn in the main pass are masked
+    if (alpha == 0.0) {
+        discard;
+    }
+    o_Target = vec4(1.0);
+}
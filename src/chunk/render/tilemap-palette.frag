@@ -0,0 +1,98 @@
+#version 450
+
+layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec4 v_Color;
+layout(location = 4) flat in int v_TileIndex;
+layout(location = 5) in vec2 v_TileUv;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 0) uniform TextureAtlas_size {
+    vec2 AtlasSize;
+};
+
+struct Rect {
+    // Upper-left coordinate
+    vec2 begin;
+    // Bottom-right coordinate
+    vec2 end;
+};
+
+layout(set = 1, binding = 1) buffer TextureAtlas_textures {
+    Rect[] Textures;
+};
+
+layout(set = 1, binding = 2) uniform texture2D TextureAtlas_texture;
+layout(set = 1, binding = 3) uniform sampler TextureAtlas_texture_sampler;
+
+layout(set = 3, binding = 0) uniform ChunkColor_color {
+    vec4 ChunkColor;
+};
+
+// the kind of the animation of the layer in x, which is none, a scroll or a
+// cycle of frames, its two parameters in y and z and the time in seconds in w
+layout(set = 3, binding = 5) uniform ChunkAnimation_animation {
+    vec4 Animation;
+};
+
+// returns the atlas coordinates of the tile with the animation of its layer
+vec2 animated_uv() {
+    // sprites with their own rect are not animated
+    if (v_TileIndex < 0 || Animation.x == 0.0) {
+        return v_Uv;
+    }
+    int index = v_TileIndex;
+    vec2 uv = v_TileUv;
+    if (Animation.x == 1.0) {
+        // scrolled in pixels per second with y up, wrapped around the sprite
+        Rect sprite_rect = Textures[index];
+        vec2 sprite_dimensions = sprite_rect.end - sprite_rect.begin;
+        uv = fract(uv + vec2(-Animation.y, Animation.z) * Animation.w / sprite_dimensions);
+    } else {
+        // the frames are the sprites which follow the sprite index of the tile
+        index += int(mod(floor(Animation.w * Animation.z), Animation.y));
+    }
+    Rect rect = Textures[index];
+    return (rect.begin + uv * (rect.end - rect.begin)) / AtlasSize;
+}
+
+// the palette colors are a row of the palette texture, one pixel per index
+layout(set = 3, binding = 6) uniform texture2D ChunkPalette_palette;
+layout(set = 3, binding = 7) uniform sampler ChunkPalette_palette_sampler;
+layout(set = 3, binding = 8) uniform texture2D ChunkPalette_blend_palette;
+layout(set = 3, binding = 9) uniform sampler ChunkPalette_blend_palette_sampler;
+// how far the colors are blended from the palette to the blend palette
+layout(set = 3, binding = 10) uniform ChunkPalette_blend {
+    float PaletteBlend;
+};
+
+// the atlas is an sRGB texture, so the index is encoded again to get the
+// byte which was stored
+float srgb_byte(float linear) {
+    float srgb = linear <= 0.0031308
+        ? linear * 12.92
+        : 1.055 * pow(linear, 1.0 / 2.4) - 0.055;
+    return round(srgb * 255.0);
+}
+
+void main() {
+    vec2 uv = animated_uv();
+    vec4 indexed = texture(
+        sampler2D(TextureAtlas_texture, TextureAtlas_texture_sampler),
+        uv
+    );
+    // the red channel of the atlas is the index of the color in the palette
+    ivec2 index = ivec2(int(srgb_byte(indexed.r)), 0);
+    vec4 paletted = mix(
+        texelFetch(sampler2D(ChunkPalette_palette, ChunkPalette_palette_sampler), index, 0),
+        texelFetch(sampler2D(ChunkPalette_blend_palette, ChunkPalette_blend_palette_sampler), index, 0),
+        PaletteBlend
+    );
+    vec4 color = ChunkColor * v_Color * vec4(paletted.rgb, paletted.a * indexed.a);
+    // fully transparent pixels must not write depth, or they would hide the
+    // tiles behind them
+    if (color.a == 0.0) {
+        discard;
+    }
+    o_Target = color;
+}
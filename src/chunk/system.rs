@@ -1,19 +1,131 @@
+#[cfg(feature = "render")]
 use crate::{
     chunk::{
-        entity::{ModifiedLayer, ZOrder},
+        entity::{ChunkAnimation, ChunkColor, ModifiedLayer, ZOrder},
         mesh::ChunkMesh,
     },
+    instance::TilemapData,
+    metrics::{FeatureMetrics, TilemapMetrics},
+    system::{layer_renderer_parts, layer_walls},
+};
+use crate::{
+    chunk::{ChunkActivity, ChunkSpawnConfig, StreamingAnchor},
     lib::*,
+    tilemap::in_order,
     Tilemap,
 };
 
+/// Sets the color of every chunk layer to the color of its tilemap times the
+/// color of its layer, for chunks of tilemap entities and of instances.
+#[cfg(feature = "render")]
+pub(crate) fn chunk_colors(
+    tilemaps: Res<Assets<TilemapData>>,
+    map_query: Query<&Tilemap>,
+    instance_query: Query<&Handle<TilemapData>>,
+    mut chunk_query: Query<(&Parent, &ZOrder, &mut ChunkColor)>,
+) {
+    for (parent, z_order, mut color) in chunk_query.iter_mut() {
+        let tint = if let Ok(tilemap) = map_query.get(**parent) {
+            tilemap.layer_tint(z_order.0)
+        } else if let Some(data) = instance_query
+            .get(**parent)
+            .ok()
+            .and_then(|handle| tilemaps.get(handle))
+        {
+            data.layer_tint(z_order.0)
+        } else {
+            continue;
+        };
+        // Only write changes, which would otherwise upload every chunk again
+        // every frame.
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+}
+
+/// Sets the z translation and visibility of every chunk layer to the render
+/// order of its tilemap plus the depth of its layer and the visibility of
+/// its layer, for chunks of tilemap entities and of
+/// instances.
+#[cfg(feature = "render")]
+pub(crate) fn chunk_depths(
+    tilemaps: Res<Assets<TilemapData>>,
+    map_query: Query<&Tilemap>,
+    instance_query: Query<&Handle<TilemapData>>,
+    mut chunk_query: Query<(&Parent, &ZOrder, &mut Transform, &mut Visible)>,
+) {
+    for (parent, z_order, mut transform, mut visible) in chunk_query.iter_mut() {
+        let (depth, is_visible) = if let Ok(tilemap) = map_query.get(**parent) {
+            (
+                tilemap.render_order() + tilemap.layer_depth(z_order.0),
+                tilemap.is_layer_visible(z_order.0),
+            )
+        } else if let Some(data) = instance_query
+            .get(**parent)
+            .ok()
+            .and_then(|handle| tilemaps.get(handle))
+        {
+            (
+                data.render_order() + data.layer_depth(z_order.0),
+                data.is_layer_visible(z_order.0),
+            )
+        } else {
+            continue;
+        };
+        if transform.translation.z != depth {
+            transform.translation.z = depth;
+        }
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+    }
+}
+
+/// Sets the shader animation of every chunk layer to that of its layer at the
+/// time since startup, for chunks of tilemap entities and of instances.
+#[cfg(feature = "render")]
+pub(crate) fn chunk_animations(
+    time: Res<Time>,
+    tilemaps: Res<Assets<TilemapData>>,
+    map_query: Query<&Tilemap>,
+    instance_query: Query<&Handle<TilemapData>>,
+    mut chunk_query: Query<(&Parent, &ZOrder, &mut ChunkAnimation)>,
+) {
+    let seconds = time.seconds_since_startup() as f32;
+    for (parent, z_order, mut animation) in chunk_query.iter_mut() {
+        let shader_animation = if let Ok(tilemap) = map_query.get(**parent) {
+            tilemap.layer_shader_animation(z_order.0)
+        } else if let Some(data) = instance_query
+            .get(**parent)
+            .ok()
+            .and_then(|handle| tilemaps.get(handle))
+        {
+            data.layer_shader_animation(z_order.0)
+        } else {
+            continue;
+        };
+        let uniform = shader_animation.map_or(Vec4::zero(), |shader_animation| {
+            shader_animation.to_uniform(seconds)
+        });
+        // Only animated layers are uploaded again every frame.
+        if animation.0 != uniform {
+            animation.0 = uniform;
+        }
+    }
+}
+
 /// The chunk update system that is used to set attributes of the tiles and
 /// tints if they need updating.
+#[cfg(feature = "render")]
 pub(crate) fn chunk_update(
     mut meshes: ResMut<Assets<Mesh>>,
+    mut metrics: ResMut<TilemapMetrics>,
     map_query: Query<&Tilemap>,
     mut chunk_query: Query<(&Parent, &Point2, &ZOrder, &Handle<Mesh>), Changed<ModifiedLayer>>,
 ) {
+    let start = Instant::now();
+    let mut updated = 0;
     for (parent, point, z_order, mesh_handle) in chunk_query.iter_mut() {
         let tilemap = if let Ok(tilemap) = map_query.get(**parent) {
             tilemap
@@ -33,115 +145,275 @@ pub(crate) fn chunk_update(
             error!("`Mesh` is missing, can not update chunk");
             return;
         };
-        let (indexes, colors) = if let Some((index, colors)) =
-            chunk.tiles_to_renderer_parts(z_order.0, tilemap.chunk_dimensions())
-        {
-            (index, colors)
+        let walls = layer_walls(
+            &tilemap.layers(),
+            z_order.0,
+            tilemap.topology(),
+            tilemap.wall_sprites(),
+        );
+        let ((indexes, colors, extras, offsets), channel_parts) = if let Some(parts) =
+            layer_renderer_parts(
+                chunk,
+                z_order.0,
+                tilemap.chunk_dimensions(),
+                tilemap.tile_dimensions(),
+                tilemap.layer_channels(),
+                walls,
+                tilemap.layer_occlusion(z_order.0).as_ref(),
+            ) {
+            parts
         } else {
             error!("Tiles are missing, can not update chunk");
             return;
         };
-        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_INDEX, indexes);
-        mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_COLOR, colors);
+        ChunkMesh::set_tiles(
+            mesh,
+            indexes,
+            colors,
+            extras,
+            offsets,
+            channel_parts,
+            tilemap.sprite_rects(),
+        );
+        updated += 1;
     }
+    metrics.mesh = FeatureMetrics::since(start, updated);
 }
 
-/// Actual method used to spawn chunks.
+/// Returns the translations of the streaming anchors, or of every camera if
+/// there are no anchors. Without the `render` feature there are no cameras to
+/// fall back on.
+fn anchor_translations(
+    anchor_query: &Query<&GlobalTransform, With<StreamingAnchor>>,
+    #[cfg(feature = "render")] camera_query: &Query<&GlobalTransform, With<Camera>>,
+) -> Vec<Vec3> {
+    let anchors: Vec<Vec3> = anchor_query
+        .iter()
+        .map(|transform| transform.translation)
+        .collect();
+    #[cfg(feature = "render")]
+    if anchors.is_empty() {
+        return camera_query
+            .iter()
+            .map(|transform| transform.translation)
+            .collect();
+    }
+    anchors
+}
+
+/// Returns the chunks of the translations of cameras or anchors.
+fn anchor_chunks(
+    anchors: &[Vec3],
+    tilemap_transform: &GlobalTransform,
+    tilemap: &Tilemap,
+) -> Vec<Point2> {
+    anchors
+        .iter()
+        .map(|translation| {
+            let point = tilemap.global_translation_to_point(tilemap_transform, *translation);
+            tilemap.point_to_chunk_point(point).into()
+        })
+        .collect()
+}
+
+/// Actual method used to spawn chunks, around every camera or anchor.
 fn auto_spawn(
-    camera_transform: &Transform,
-    tilemap_transform: &Transform,
+    anchors: &[Vec3],
+    tilemap_transform: &GlobalTransform,
     tilemap: &mut Tilemap,
-    spawn_dimensions: Dimension2,
+    config: ChunkSpawnConfig,
 ) {
-    let translation = camera_transform.translation - tilemap_transform.translation;
-    let point_x = translation.x / tilemap.tile_width() as f32;
-    let point_y = translation.y / tilemap.tile_height() as f32;
-    let (chunk_x, chunk_y) = tilemap.point_to_chunk_point((point_x as i32, point_y as i32));
-    let mut new_spawned: Vec<Point2> = Vec::new();
-    let spawn_width = spawn_dimensions.width as i32;
-    let spawn_height = spawn_dimensions.height as i32;
-    for y in -spawn_width as i32..spawn_width + 1 {
-        for x in -spawn_height..spawn_height + 1 {
-            let chunk_x = x + chunk_x;
-            let chunk_y = y + chunk_y;
-            if let Some(width) = tilemap.width() {
-                let width = (width / tilemap.chunk_width()) as i32 / 2;
-                if chunk_x < -width || chunk_x > width {
-                    continue;
+    if anchors.is_empty() {
+        return;
+    }
+    let anchor_chunks = anchor_chunks(anchors, tilemap_transform, tilemap);
+    tilemap.set_spawn_focus(anchor_chunks.clone());
+    let mut new_spawned: HashSet<Point2> = HashSet::default();
+    let spawn_width = config.spawn_radius.width as i32;
+    let spawn_height = config.spawn_radius.height as i32;
+    for anchor_chunk in anchor_chunks.iter() {
+        for y in -spawn_height..spawn_height + 1 {
+            for x in -spawn_width..spawn_width + 1 {
+                let chunk_x = x + anchor_chunk.x;
+                let chunk_y = y + anchor_chunk.y;
+                if let Some(width) = tilemap.width() {
+                    let width = (width / tilemap.chunk_width()) as i32 / 2;
+                    if chunk_x < -width || chunk_x > width {
+                        continue;
+                    }
                 }
-            }
-            if let Some(height) = tilemap.height() {
-                let height = (height / tilemap.chunk_height()) as i32 / 2;
-                if chunk_y < -height || chunk_y > height {
+                if let Some(height) = tilemap.height() {
+                    let height = (height / tilemap.chunk_height()) as i32 / 2;
+                    if chunk_y < -height || chunk_y > height {
+                        continue;
+                    }
+                }
+                // Chunks around more than one anchor are spawned once.
+                if !new_spawned.insert(Point2::new(chunk_x, chunk_y)) {
                     continue;
                 }
+
+                if let Err(e) = tilemap.spawn_chunk(Point2::new(chunk_x, chunk_y)) {
+                    warn!("{}", e);
+                }
             }
+        }
+    }
 
-            if let Err(e) = tilemap.spawn_chunk(Point2::new(chunk_x, chunk_y)) {
+    // Chunks between the spawn and despawn radii are kept, and queued chunks
+    // which are out of range are never spawned.
+    let keeps = |point: Point2| {
+        anchor_chunks
+            .iter()
+            .any(|anchor_chunk| config.keeps(point - *anchor_chunk))
+    };
+    let deterministic = tilemap.is_deterministic();
+    let loaded_list = in_order(
+        tilemap
+            .spawned_chunks()
+            .iter()
+            .map(Point2::from)
+            .chain(tilemap.queued_chunks().iter().copied()),
+        deterministic,
+        |point| *point,
+    );
+    let mut kept = Vec::new();
+    let mut despawned = Vec::new();
+    for point in loaded_list.into_iter() {
+        if new_spawned.contains(&point) {
+            continue;
+        }
+        if keeps(point) {
+            kept.push(point);
+        } else {
+            despawned.push(point);
+        }
+    }
+    // Past the most loaded chunks, the farthest kept chunks are despawned.
+    if let Some(max) = config.max_loaded_chunks {
+        let loaded = tilemap.spawned_chunks().len() + tilemap.queued_chunks().len();
+        let excess = loaded.saturating_sub(despawned.len()).saturating_sub(max);
+        kept.sort_by_key(|point| Reverse((tilemap.chunk_distance(*point), *point)));
+        despawned.extend(kept.into_iter().take(excess));
+    }
+    for point in despawned.iter() {
+        if let Err(e) = tilemap.despawn_chunk(*point) {
+            warn!("{}", e);
+        }
+    }
+    // Chunks which leave the radius are kept in the chunk store, if there is
+    // one, until they come back.
+    #[cfg(feature = "serialize")]
+    if tilemap.chunk_store().is_some() {
+        let unloadable: HashSet<Point2> = tilemap.unloadable_chunks().into_iter().collect();
+        for point in despawned
+            .into_iter()
+            .filter(|point| unloadable.contains(point))
+        {
+            if let Err(e) = tilemap.unload_chunk(point) {
                 warn!("{}", e);
             }
-            new_spawned.push(Point2::new(chunk_x, chunk_y));
         }
     }
+}
 
-    let spawned_list = tilemap.spawned_chunks_mut().clone();
-    for point in spawned_list.iter() {
-        if !new_spawned.contains(&point.into()) {
-            if let Err(e) = tilemap.despawn_chunk(point) {
+/// Sets the activity of the spawned chunks from their distance to the nearest
+/// camera or streaming anchor, if the tilemap has an activity policy.
+pub(crate) fn chunk_auto_activity(
+    mut tilemap_query: Query<(&mut Tilemap, &GlobalTransform)>,
+    anchor_query: Query<&GlobalTransform, With<StreamingAnchor>>,
+    #[cfg(feature = "render")] camera_query: Query<&GlobalTransform, With<Camera>>,
+) {
+    #[cfg(feature = "render")]
+    let anchors = anchor_translations(&anchor_query, &camera_query);
+    #[cfg(not(feature = "render"))]
+    let anchors = anchor_translations(&anchor_query);
+    if anchors.is_empty() {
+        return;
+    }
+    for (mut tilemap, tilemap_transform) in tilemap_query.iter_mut() {
+        let policy = if let Some(policy) = tilemap.activity_policy() {
+            policy
+        } else {
+            continue;
+        };
+        let anchor_chunks = anchor_chunks(&anchors, tilemap_transform, &tilemap);
+        let spawned = in_order(
+            tilemap.spawned_chunks().iter().map(Point2::from),
+            tilemap.is_deterministic(),
+            |point| *point,
+        );
+        for point in spawned.into_iter() {
+            let activity = anchor_chunks
+                .iter()
+                .map(|anchor_chunk| policy.activity(point - *anchor_chunk))
+                .min()
+                .unwrap_or(ChunkActivity::Frozen);
+            if tilemap.chunk_activity(point) == Some(activity) {
+                continue;
+            }
+            if let Err(e) = tilemap.set_chunk_activity(point, activity) {
                 warn!("{}", e);
             }
         }
     }
 }
 
-/// On window size change, the radius of chunks changes if needed.
+/// On window size change, the radius of chunks changes if needed, unless the
+/// tilemap was given a spawn config.
 pub(crate) fn chunk_auto_radius(
     window_resized_events: Res<Events<WindowResized>>,
-    mut tilemap_query: Query<(&mut Tilemap, &Transform)>,
-    camera_query: Query<(&Camera, &Transform)>,
+    mut tilemap_query: Query<(&mut Tilemap, &GlobalTransform)>,
+    anchor_query: Query<&GlobalTransform, With<StreamingAnchor>>,
+    #[cfg(feature = "render")] camera_query: Query<&GlobalTransform, With<Camera>>,
 ) {
     let mut window_reader = window_resized_events.get_reader();
     for event in window_reader.iter(&window_resized_events) {
+        #[cfg(feature = "render")]
+        let anchors = anchor_translations(&anchor_query, &camera_query);
+        #[cfg(not(feature = "render"))]
+        let anchors = anchor_translations(&anchor_query);
         for (mut tilemap, tilemap_transform) in tilemap_query.iter_mut() {
+            if !tilemap.fits_spawn_radius_to_window() {
+                continue;
+            }
             let window_width = event.width as u32;
             let window_height = event.height as u32;
             let chunk_px_width = tilemap.chunk_width() * tilemap.tile_width();
             let chunk_px_height = tilemap.chunk_height() * tilemap.tile_height();
             let chunks_wide = (window_width as f32 / chunk_px_width as f32).ceil() as u32 + 1;
             let chunks_high = (window_height as f32 / chunk_px_height as f32).ceil() as u32 + 1;
-            let spawn_dimensions = Dimension2::new(chunks_wide, chunks_high);
-            tilemap.set_auto_spawn(spawn_dimensions);
-            for (_camera, camera_transform) in camera_query.iter() {
-                auto_spawn(
-                    camera_transform,
-                    &tilemap_transform,
-                    &mut tilemap,
-                    spawn_dimensions,
-                );
+            tilemap.set_auto_spawn(Dimension2::new(chunks_wide, chunks_high));
+            if let Some(config) = tilemap.spawn_config() {
+                auto_spawn(&anchors, tilemap_transform, &mut tilemap, config);
             }
         }
     }
 }
 
-/// Spawns and despawns chunks automatically based on a camera's position.
+/// Spawns and despawns chunks automatically based on the positions of the
+/// streaming anchors, or of the cameras without any, when one of them moved
+/// to another chunk.
 pub(crate) fn chunk_auto_spawn(
-    mut tilemap_query: Query<(&mut Tilemap, &Transform)>,
-    camera_query: Query<(&Camera, &Transform), Changed<Transform>>,
+    mut tilemap_query: Query<(&mut Tilemap, &GlobalTransform)>,
+    anchor_query: Query<&GlobalTransform, With<StreamingAnchor>>,
+    #[cfg(feature = "render")] camera_query: Query<&GlobalTransform, With<Camera>>,
 ) {
-    // For the transform, get chunk coord.
+    #[cfg(feature = "render")]
+    let anchors = anchor_translations(&anchor_query, &camera_query);
+    #[cfg(not(feature = "render"))]
+    let anchors = anchor_translations(&anchor_query);
     for (mut tilemap, tilemap_transform) in tilemap_query.iter_mut() {
-        for (_camera, camera_transform) in camera_query.iter() {
-            let spawn_dimensions = if let Some(dimensions) = tilemap.auto_spawn() {
-                dimensions
-            } else {
-                continue;
-            };
-            auto_spawn(
-                camera_transform,
-                &tilemap_transform,
-                &mut tilemap,
-                spawn_dimensions,
-            );
+        let config = if let Some(config) = tilemap.spawn_config() {
+            config
+        } else {
+            continue;
+        };
+        // The global transforms are those of the last frame, so the chunks
+        // of the anchors are compared instead of whether they moved.
+        if anchor_chunks(&anchors, tilemap_transform, &tilemap) == tilemap.spawn_focus() {
+            continue;
         }
+        auto_spawn(&anchors, tilemap_transform, &mut tilemap, config);
     }
 }
@@ -0,0 +1,141 @@
+//! The color of tiles and layers when built without the `render` feature.
+//!
+//! Headless builds leave out `bevy_render` and with it its `Color`. This is a
+//! stand in with the same storage and sRGB conversions, so that tints are kept
+//! and saves read and write the same either way.
+
+use crate::lib::*;
+
+/// RGBA color in the linear sRGB colorspace.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color {
+    /// The linear red.
+    red: f32,
+    /// The linear green.
+    green: f32,
+    /// The linear blue.
+    blue: f32,
+    /// The alpha.
+    alpha: f32,
+}
+
+impl Color {
+    /// Opaque black.
+    pub const BLACK: Color = Color::rgb_linear(0.0, 0.0, 0.0);
+    /// Opaque blue.
+    pub const BLUE: Color = Color::rgb_linear(0.0, 0.0, 1.0);
+    /// Opaque green.
+    pub const GREEN: Color = Color::rgb_linear(0.0, 1.0, 0.0);
+    /// Fully transparent.
+    pub const NONE: Color = Color::rgba_linear(0.0, 0.0, 0.0, 0.0);
+    /// Opaque red.
+    pub const RED: Color = Color::rgb_linear(1.0, 0.0, 0.0);
+    /// Opaque white.
+    pub const WHITE: Color = Color::rgb_linear(1.0, 1.0, 1.0);
+
+    /// New `Color` from sRGB colorspace.
+    pub fn rgb(r: f32, g: f32, b: f32) -> Color {
+        Color::rgba(r, g, b, 1.0)
+    }
+
+    /// New `Color` from sRGB colorspace.
+    pub fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color {
+            red: nonlinear_to_linear(r),
+            green: nonlinear_to_linear(g),
+            blue: nonlinear_to_linear(b),
+            alpha: a,
+        }
+    }
+
+    /// New `Color` from linear colorspace.
+    pub const fn rgb_linear(r: f32, g: f32, b: f32) -> Color {
+        Color::rgba_linear(r, g, b, 1.0)
+    }
+
+    /// New `Color` from linear colorspace.
+    pub const fn rgba_linear(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color {
+            red: r,
+            green: g,
+            blue: b,
+            alpha: a,
+        }
+    }
+
+    /// Get red in sRGB colorspace.
+    pub fn r(&self) -> f32 {
+        linear_to_nonlinear(self.red)
+    }
+
+    /// Get green in sRGB colorspace.
+    pub fn g(&self) -> f32 {
+        linear_to_nonlinear(self.green)
+    }
+
+    /// Get blue in sRGB colorspace.
+    pub fn b(&self) -> f32 {
+        linear_to_nonlinear(self.blue)
+    }
+
+    /// Get red in linear colorspace.
+    pub fn r_linear(&self) -> f32 {
+        self.red
+    }
+
+    /// Get green in linear colorspace.
+    pub fn g_linear(&self) -> f32 {
+        self.green
+    }
+
+    /// Get blue in linear colorspace.
+    pub fn b_linear(&self) -> f32 {
+        self.blue
+    }
+
+    /// Get alpha.
+    pub fn a(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Set alpha.
+    pub fn set_a(&mut self, a: f32) -> &mut Self {
+        self.alpha = a;
+        self
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Color::WHITE
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        [color.red, color.green, color.blue, color.alpha]
+    }
+}
+
+/// Converts an sRGB component to linear.
+fn nonlinear_to_linear(value: f32) -> f32 {
+    if value <= 0.0 {
+        value
+    } else if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear component to sRGB.
+fn linear_to_nonlinear(value: f32) -> f32 {
+    if value <= 0.0 {
+        value
+    } else if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        (1.055 * value.powf(1.0 / 2.4)) - 0.055
+    }
+}
@@ -0,0 +1,330 @@
+//! Deferred tilemap commands which can be queued from parallel systems.
+//!
+//! Mutating a [`Tilemap`] requires mutable access to it which means that all
+//! systems that edit a tilemap can not run in parallel. Instead, the
+//! [`TilemapCommands`] resource can be used to queue edits with only shared
+//! access. All queued commands are then applied once per frame, in order, in
+//! the tilemap commands stage before the tilemap stage spawns or modifies any
+//! chunks.
+//!
+//! A `TilemapCommands` can also be cloned and sent elsewhere, every clone
+//! pushes to the same queue.
+//!
+//! [`Tilemap`]: crate::tilemap::Tilemap
+//!
+//! # Queueing commands from a system
+//! ```
+//! use bevy_ecs::prelude::*;
+//! use bevy_tilemap::{command::TilemapCommands, prelude::*};
+//!
+//! fn place_tiles(tilemap_commands: Res<TilemapCommands>, query: Query<(Entity, &Tilemap)>) {
+//!     for (entity, _tilemap) in query.iter() {
+//!         tilemap_commands.insert_tile(entity, Tile { point: (1, 1), ..Default::default() });
+//!     }
+//! }
+//! ```
+
+use crate::{animation::TileAfter, lib::*, tile::Tile, tilemap::TilemapResult, Tilemap};
+
+/// A single deferred edit to a tilemap.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TilemapCommand {
+    /// Inserts the tiles, see [`Tilemap::insert_tiles`].
+    ///
+    /// [`Tilemap::insert_tiles`]: crate::tilemap::Tilemap::insert_tiles
+    InsertTiles(Vec<Tile<Point2>>),
+    /// Clears the tiles at the points and z orders, see
+    /// [`Tilemap::clear_tiles`].
+    ///
+    /// [`Tilemap::clear_tiles`]: crate::tilemap::Tilemap::clear_tiles
+    ClearTiles(Vec<(Point2, usize)>),
+    /// Inserts a new chunk, see [`Tilemap::insert_chunk`].
+    ///
+    /// [`Tilemap::insert_chunk`]: crate::tilemap::Tilemap::insert_chunk
+    InsertChunk(Point2),
+    /// Removes a chunk, see [`Tilemap::remove_chunk`].
+    ///
+    /// [`Tilemap::remove_chunk`]: crate::tilemap::Tilemap::remove_chunk
+    RemoveChunk(Point2),
+    /// Spawns a chunk, see [`Tilemap::spawn_chunk`].
+    ///
+    /// [`Tilemap::spawn_chunk`]: crate::tilemap::Tilemap::spawn_chunk
+    SpawnChunk(Point2),
+    /// Despawns a chunk, see [`Tilemap::despawn_chunk`].
+    ///
+    /// [`Tilemap::despawn_chunk`]: crate::tilemap::Tilemap::despawn_chunk
+    DespawnChunk(Point2),
+    /// Plays an animation clip once on the tile at the point and z order, see
+    /// [`Tilemap::play_once`].
+    ///
+    /// [`Tilemap::play_once`]: crate::tilemap::Tilemap::play_once
+    PlayOnce(Point2, usize, String, TileAfter),
+    /// Deals damage to the tile at the point and z order, see
+    /// [`Tilemap::damage_tile`].
+    ///
+    /// [`Tilemap::damage_tile`]: crate::tilemap::Tilemap::damage_tile
+    DamageTile(Point2, usize, u32),
+}
+
+impl TilemapCommand {
+    /// Applies the command to a tilemap.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as the tilemap method that the command maps to.
+    pub fn apply(self, tilemap: &mut Tilemap) -> TilemapResult<()> {
+        use TilemapCommand::*;
+        match self {
+            InsertTiles(tiles) => tilemap.insert_tiles(tiles),
+            ClearTiles(points) => tilemap.clear_tiles(points),
+            InsertChunk(point) => tilemap.insert_chunk(point),
+            RemoveChunk(point) => tilemap.remove_chunk(point),
+            SpawnChunk(point) => tilemap.spawn_chunk(point),
+            DespawnChunk(point) => tilemap.despawn_chunk(point),
+            PlayOnce(point, z_order, clip, then) => tilemap.play_once(point, z_order, clip, then),
+            DamageTile(point, z_order, amount) => {
+                tilemap.damage_tile(point, z_order, amount).map(|_| ())
+            }
+        }
+    }
+}
+
+/// A cloneable queue of commands for tilemap entities.
+///
+/// This is added as a resource by the [`Tilemap2DPlugin`]. Every clone shares
+/// the same queue.
+///
+/// [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+#[derive(Clone, Default, Debug)]
+pub struct TilemapCommands {
+    /// The queued commands with the tilemap entity that they are meant for.
+    queue: Arc<Mutex<Vec<(Entity, TilemapCommand)>>>,
+}
+
+impl TilemapCommands {
+    /// Constructs a new and empty command queue.
+    pub fn new() -> TilemapCommands {
+        TilemapCommands::default()
+    }
+
+    /// Queues a command for a tilemap entity.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_tilemap::command::{TilemapCommand, TilemapCommands};
+    ///
+    /// let tilemap_commands = TilemapCommands::new();
+    ///
+    /// tilemap_commands.push(Entity::new(0), TilemapCommand::SpawnChunk((0, 0).into()));
+    ///
+    /// assert_eq!(tilemap_commands.len(), 1);
+    /// ```
+    pub fn push(&self, entity: Entity, command: TilemapCommand) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.push((entity, command));
+        } else {
+            error!("tilemap command queue is poisoned, dropping command");
+        }
+    }
+
+    /// Queues many tiles to be inserted into the tilemap entity.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_tilemap::{command::TilemapCommands, prelude::*};
+    ///
+    /// let tilemap_commands = TilemapCommands::new();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (1, 1), ..Default::default() },
+    ///     Tile { point: (2, 2), ..Default::default() },
+    /// ];
+    ///
+    /// tilemap_commands.insert_tiles(Entity::new(0), tiles);
+    /// ```
+    pub fn insert_tiles<P, I>(&self, entity: Entity, tiles: I)
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = Tile<P>>,
+    {
+        let tiles = tiles
+            .into_iter()
+            .map(|tile| Tile {
+                point: tile.point.into(),
+                z_order: tile.z_order,
+                sprite_index: tile.sprite_index,
+                tint: tile.tint,
+                extra: tile.extra,
+                offset: tile.offset,
+            })
+            .collect();
+        self.push(entity, TilemapCommand::InsertTiles(tiles));
+    }
+
+    /// Queues a single tile to be inserted into the tilemap entity.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{command::TilemapCommands, prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// let tilemap_commands = TilemapCommands::clone(&harness.app().resources.get().unwrap());
+    /// tilemap_commands.insert_tile(entity, Tile { point: (1, 0), sprite_index: 2, ..Default::default() });
+    /// harness.tick();
+    ///
+    /// // The tile is applied and in the mesh of its chunk in the same frame.
+    /// assert_eq!(harness.chunk_tile_indexes(entity, (0, 0), 0).unwrap()[11], 2);
+    /// ```
+    pub fn insert_tile<P: Into<Point2>>(&self, entity: Entity, tile: Tile<P>) {
+        self.insert_tiles(entity, vec![tile]);
+    }
+
+    /// Queues many tiles to be cleared from the tilemap entity.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_tilemap::command::TilemapCommands;
+    ///
+    /// let tilemap_commands = TilemapCommands::new();
+    ///
+    /// tilemap_commands.clear_tiles(Entity::new(0), vec![((1, 1), 0), ((2, 2), 0)]);
+    /// ```
+    pub fn clear_tiles<P, I>(&self, entity: Entity, points: I)
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = (P, usize)>,
+    {
+        let points = points
+            .into_iter()
+            .map(|(point, z_order)| (point.into(), z_order))
+            .collect();
+        self.push(entity, TilemapCommand::ClearTiles(points));
+    }
+
+    /// Queues a single tile to be cleared from the tilemap entity.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_tilemap::command::TilemapCommands;
+    ///
+    /// let tilemap_commands = TilemapCommands::new();
+    ///
+    /// tilemap_commands.clear_tile(Entity::new(0), (1, 1), 0);
+    /// ```
+    pub fn clear_tile<P: Into<Point2>>(&self, entity: Entity, point: P, z_order: usize) {
+        self.clear_tiles(entity, vec![(point, z_order)]);
+    }
+
+    /// Queues a chunk to be inserted into the tilemap entity.
+    pub fn insert_chunk<P: Into<Point2>>(&self, entity: Entity, point: P) {
+        self.push(entity, TilemapCommand::InsertChunk(point.into()));
+    }
+
+    /// Queues a chunk to be removed from the tilemap entity.
+    pub fn remove_chunk<P: Into<Point2>>(&self, entity: Entity, point: P) {
+        self.push(entity, TilemapCommand::RemoveChunk(point.into()));
+    }
+
+    /// Queues a chunk of the tilemap entity to be spawned.
+    pub fn spawn_chunk<P: Into<Point2>>(&self, entity: Entity, point: P) {
+        self.push(entity, TilemapCommand::SpawnChunk(point.into()));
+    }
+
+    /// Queues a chunk of the tilemap entity to be despawned.
+    pub fn despawn_chunk<P: Into<Point2>>(&self, entity: Entity, point: P) {
+        self.push(entity, TilemapCommand::DespawnChunk(point.into()));
+    }
+
+    /// Queues an animation clip to be played once on a tile of the tilemap
+    /// entity, see [`Tilemap::play_once`].
+    ///
+    /// [`Tilemap::play_once`]: crate::tilemap::Tilemap::play_once
+    pub fn play_once<P, S>(
+        &self,
+        entity: Entity,
+        point: P,
+        z_order: usize,
+        clip: S,
+        then: TileAfter,
+    ) where
+        P: Into<Point2>,
+        S: Into<String>,
+    {
+        self.push(
+            entity,
+            TilemapCommand::PlayOnce(point.into(), z_order, clip.into(), then),
+        );
+    }
+
+    /// Queues damage to a tile of the tilemap entity, see
+    /// [`Tilemap::damage_tile`].
+    ///
+    /// [`Tilemap::damage_tile`]: crate::tilemap::Tilemap::damage_tile
+    pub fn damage_tile<P: Into<Point2>>(
+        &self,
+        entity: Entity,
+        point: P,
+        z_order: usize,
+        amount: u32,
+    ) {
+        self.push(
+            entity,
+            TilemapCommand::DamageTile(point.into(), z_order, amount),
+        );
+    }
+
+    /// Takes all the queued commands out of the queue, in the order that they
+    /// were queued.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_tilemap::command::{TilemapCommand, TilemapCommands};
+    ///
+    /// let tilemap_commands = TilemapCommands::new();
+    /// let sender = tilemap_commands.clone();
+    ///
+    /// sender.spawn_chunk(Entity::new(0), (0, 0));
+    /// sender.despawn_chunk(Entity::new(0), (1, 1));
+    ///
+    /// let drained = tilemap_commands.drain();
+    /// assert_eq!(drained.len(), 2);
+    /// assert_eq!(drained[0].1, TilemapCommand::SpawnChunk((0, 0).into()));
+    /// assert!(tilemap_commands.is_empty());
+    /// ```
+    pub fn drain(&self) -> Vec<(Entity, TilemapCommand)> {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.drain(..).collect()
+        } else {
+            error!("tilemap command queue is poisoned, can not drain commands");
+            Vec::new()
+        }
+    }
+
+    /// Returns the amount of queued commands.
+    pub fn len(&self) -> usize {
+        self.queue.lock().map(|queue| queue.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no queued commands.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,214 @@
+//! Tinting tilemaps through a cycle of day and night.
+//!
+//! A [`DaylightCycle`] added to a tilemap entity sets the color of the
+//! tilemap, see [`Tilemap::set_color`], every frame. The cycle is a list of
+//! keyed colors at times of the day, from `0.0` at midnight to `1.0` at the
+//! next midnight, and the color between two keys is blended from both, so
+//! the map fades from night into dawn and day and back without any code of
+//! your own.
+//!
+//! With a day length, the time of day of the cycle moves on by itself as the
+//! game runs. Without one, it follows the [`TimeOfDay`] resource instead, for
+//! games whose clock is kept elsewhere, such as in a save or by a server.
+//!
+//! [`Tilemap::set_color`]: crate::tilemap::Tilemap::set_color
+//!
+//! # A day of ten minutes
+//! ```
+//! use bevy_render::color::Color;
+//! use bevy_tilemap::{
+//!     daylight::{DaylightCycle, TimeOfDay},
+//!     prelude::*,
+//!     testing::tilemap_test_harness,
+//! };
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .tile_dimensions(32, 32)
+//!     .finish()
+//!     .unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//!
+//! let cycle = DaylightCycle::new(600.0)
+//!     .with_key(0.0, Color::rgb(0.2, 0.2, 0.5))
+//!     .with_key(0.25, Color::rgb(1.0, 0.7, 0.6))
+//!     .with_key(0.5, Color::WHITE)
+//!     .with_key(0.75, Color::rgb(0.9, 0.5, 0.4));
+//! harness.app().world.insert_one(entity, cycle).unwrap();
+//! harness.tick();
+//!
+//! // The day starts at midnight.
+//! assert_eq!(harness.tilemap(entity).unwrap().color(), Color::rgb(0.2, 0.2, 0.5));
+//!
+//! // Driven by a clock which is kept elsewhere instead.
+//! let cycle = DaylightCycle::driven().with_key(0.0, Color::BLACK).with_key(0.5, Color::WHITE);
+//! harness.app().world.insert_one(entity, cycle).unwrap();
+//! harness.app().resources.insert(TimeOfDay(0.5));
+//! harness.tick();
+//!
+//! assert_eq!(harness.tilemap(entity).unwrap().color(), Color::WHITE);
+//! ```
+
+use crate::{lib::*, Tilemap};
+
+/// The time of day which [`DaylightCycle`]s without a day length follow, from
+/// `0.0` at midnight to `1.0` at the next midnight.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TimeOfDay(pub f32);
+
+/// A cycle of colors through a day, which tints the tilemap of its entity.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DaylightCycle {
+    /// The colors keyed at times of day, ordered by their times.
+    keys: Vec<(f32, Color)>,
+    /// How many seconds a whole day lasts, or `None` if the time of day
+    /// follows the [`TimeOfDay`] resource.
+    pub day_length: Option<f32>,
+    /// The time of day, from `0.0` at midnight to `1.0` at the next midnight.
+    time_of_day: f32,
+}
+
+impl DaylightCycle {
+    /// Constructs a new cycle without any keys, whose day lasts as many
+    /// seconds as the day length and starts at midnight.
+    pub fn new(day_length: f32) -> DaylightCycle {
+        DaylightCycle {
+            day_length: Some(day_length),
+            ..Default::default()
+        }
+    }
+
+    /// Constructs a new cycle without any keys, whose time of day follows the
+    /// [`TimeOfDay`] resource.
+    pub fn driven() -> DaylightCycle {
+        DaylightCycle::default()
+    }
+
+    /// Adds the color at a time of day, replacing the one which was keyed at
+    /// that time. Times past a day wrap around.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_render::color::Color;
+    /// use bevy_tilemap::daylight::DaylightCycle;
+    ///
+    /// let cycle = DaylightCycle::new(600.0)
+    ///     .with_key(0.5, Color::WHITE)
+    ///     .with_key(1.25, Color::BLACK);
+    ///
+    /// assert_eq!(cycle.keys(), &[(0.25, Color::BLACK), (0.5, Color::WHITE)]);
+    /// ```
+    pub fn with_key(mut self, time_of_day: f32, color: Color) -> DaylightCycle {
+        let time_of_day = time_of_day.rem_euclid(1.0);
+        self.keys.retain(|(time, _)| *time != time_of_day);
+        let index = self
+            .keys
+            .iter()
+            .position(|(time, _)| *time > time_of_day)
+            .unwrap_or(self.keys.len());
+        self.keys.insert(index, (time_of_day, color));
+        self
+    }
+
+    /// Returns the colors keyed at times of day, ordered by their times.
+    pub fn keys(&self) -> &[(f32, Color)] {
+        &self.keys
+    }
+
+    /// Returns the time of day, from `0.0` at midnight to `1.0` at the next
+    /// midnight.
+    pub fn time_of_day(&self) -> f32 {
+        self.time_of_day
+    }
+
+    /// Sets the time of day, which wraps around past a day.
+    pub fn set_time_of_day(&mut self, time_of_day: f32) {
+        self.time_of_day = time_of_day.rem_euclid(1.0);
+    }
+
+    /// Moves the time of day on by a number of seconds, if the cycle has a day
+    /// length.
+    pub fn advance(&mut self, seconds: f32) {
+        if let Some(day_length) = self.day_length {
+            if day_length > 0.0 {
+                self.set_time_of_day(self.time_of_day + seconds / day_length);
+            }
+        }
+    }
+
+    /// Returns the color at the time of day, blended from the keys before and
+    /// after it, which is white if there are no keys.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_render::color::Color;
+    /// use bevy_tilemap::daylight::DaylightCycle;
+    ///
+    /// let mut cycle = DaylightCycle::new(600.0)
+    ///     .with_key(0.0, Color::BLACK)
+    ///     .with_key(0.5, Color::WHITE);
+    ///
+    /// // Half way from midnight to noon.
+    /// cycle.advance(150.0);
+    /// assert_eq!(cycle.time_of_day(), 0.25);
+    /// assert_eq!(cycle.color(), Color::rgb_linear(0.5, 0.5, 0.5));
+    ///
+    /// // Half way from noon back to midnight.
+    /// cycle.set_time_of_day(0.75);
+    /// assert_eq!(cycle.color(), Color::rgb_linear(0.5, 0.5, 0.5));
+    /// ```
+    pub fn color(&self) -> Color {
+        let time = self.time_of_day;
+        // The keys on either side of the time, wrapping around midnight.
+        let next = self
+            .keys
+            .iter()
+            .position(|(key_time, _)| *key_time > time)
+            .unwrap_or(0);
+        let previous = if next == 0 {
+            self.keys.len().saturating_sub(1)
+        } else {
+            next - 1
+        };
+        let ((previous_time, from), (next_time, to)) =
+            match (self.keys.get(previous), self.keys.get(next)) {
+                (Some(previous), Some(next)) => (*previous, *next),
+                _ => return Color::WHITE,
+            };
+        let span = (next_time - previous_time).rem_euclid(1.0);
+        if span == 0.0 {
+            return from;
+        }
+        let t = (time - previous_time).rem_euclid(1.0) / span;
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        Color::rgba_linear(
+            lerp(from.r_linear(), to.r_linear()),
+            lerp(from.g_linear(), to.g_linear()),
+            lerp(from.b_linear(), to.b_linear()),
+            lerp(from.a(), to.a()),
+        )
+    }
+}
+
+/// Moves the daylight cycles on and tints their tilemaps.
+pub(crate) fn tilemap_daylight(
+    time: Res<Time>,
+    time_of_day: Res<TimeOfDay>,
+    mut tilemap_query: Query<(&mut DaylightCycle, &mut Tilemap)>,
+) {
+    let delta = time.delta_seconds();
+    for (mut cycle, mut tilemap) in tilemap_query.iter_mut() {
+        if cycle.day_length.is_some() {
+            cycle.advance(delta);
+        } else {
+            cycle.set_time_of_day(time_of_day.0);
+        }
+        let color = cycle.color();
+        // The tilemap is only changed when its color is.
+        if tilemap.color() != color {
+            tilemap.set_color(color);
+        }
+    }
+}
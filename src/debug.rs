@@ -0,0 +1,429 @@
+//! Debug overlays which draw the grid and the chunks of tilemaps.
+//!
+//! With the [`TilemapDebugPlugin`] added after the tilemap plugin, every
+//! tilemap entity is given an overlay which is drawn in lines above its
+//! layers: the outline of every tile of its spawned chunks, the bounds of each
+//! chunk, the point of each chunk written at its center and, for a moment
+//! after they are built again, the chunks whose meshes were updated. The last
+//! is the quickest way to see why a frame was slow.
+//!
+//! What is drawn is set in the [`TilemapDebug`] resource, which can be
+//! changed at any time, such as from a key binding. Nothing is built while it
+//! is disabled.
+//!
+//! # Toggled at runtime
+//! ```
+//! use bevy_tilemap::{
+//!     debug::{TilemapDebug, TilemapDebugOverlay, TilemapDebugPlugin},
+//!     prelude::*,
+//!     testing::tilemap_test_harness_with,
+//! };
+//! use bevy_tilemap_types::point::Point2;
+//!
+//! let mut harness = tilemap_test_harness_with(TilemapDebugPlugin);
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! // The chunk was just built, so it is highlighted.
+//! let world = &harness.app().world;
+//! let overlay = world.query::<&TilemapDebugOverlay>().next().unwrap();
+//! assert_eq!(overlay.highlighted_chunks().collect::<Vec<_>>(), vec![&Point2::new(0, 0)]);
+//!
+//! // Only the bounds of the chunks from now on.
+//! let mut debug = harness.app().resources.get_mut::<TilemapDebug>().unwrap();
+//! debug.grid = false;
+//! debug.chunk_points = false;
+//! ```
+
+use crate::{
+    chunk::{entity::ModifiedLayer, render::chunk_pipeline_descriptor},
+    geometry,
+    lib::*,
+    Tilemap,
+};
+
+/// The render pipeline of the debug overlays.
+pub(crate) const TILEMAP_DEBUG_PIPELINE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 6164807612469350722);
+
+/// The name of the vertex attribute of the colors of the overlay lines.
+const ATTRIBUTE_COLOR: &str = "Vertex_Color";
+
+/// How far the overlay is drawn above its tilemap, which is above every layer
+/// with a lower z order.
+const OVERLAY_Z: f32 = 100.0;
+
+/// How many seconds a chunk is highlighted for after its mesh was updated.
+pub const DIRTY_HIGHLIGHT_SECONDS: f32 = 0.5;
+
+/// The color of the outlines of the tiles.
+const GRID_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.25];
+/// The color of the bounds of the chunks.
+const CHUNK_BOUNDS_COLOR: [f32; 4] = [1.0, 0.8, 0.0, 1.0];
+/// The color of the points of the chunks.
+const CHUNK_POINT_COLOR: [f32; 4] = [0.2, 1.0, 1.0, 1.0];
+/// The color of the chunks whose meshes were updated.
+const DIRTY_CHUNK_COLOR: [f32; 4] = [1.0, 0.2, 0.2, 1.0];
+
+/// Adds a debug overlay to every tilemap entity.
+///
+/// This must be added after [`Tilemap2DPlugin`].
+///
+/// [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+#[derive(Default)]
+pub struct TilemapDebugPlugin;
+
+impl Plugin for TilemapDebugPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<TilemapDebug>()
+            .add_system_to_stage(app_stage::POST_UPDATE, tilemap_debug_overlays.system());
+
+        let resources = app.resources_mut();
+        let mut shaders = resources
+            .get_mut::<Assets<Shader>>()
+            .expect("`Shader` is missing.");
+        let mut pipelines = resources
+            .get_mut::<Assets<PipelineDescriptor>>()
+            .expect("`PipelineDescriptor` is missing.");
+        pipelines.set_untracked(
+            TILEMAP_DEBUG_PIPELINE,
+            chunk_pipeline_descriptor(ShaderStages {
+                vertex: shaders.add(Shader::from_glsl(
+                    ShaderStage::Vertex,
+                    include_str!("chunk/render/tilemap-debug.vert"),
+                )),
+                fragment: Some(shaders.add(Shader::from_glsl(
+                    ShaderStage::Fragment,
+                    include_str!("chunk/render/tilemap-debug.frag"),
+                ))),
+            }),
+        );
+    }
+}
+
+/// What the debug overlays draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilemapDebug {
+    /// If the overlays are drawn at all.
+    pub enabled: bool,
+    /// If the outline of every tile is drawn.
+    pub grid: bool,
+    /// If the bounds of the chunks are drawn.
+    pub chunk_bounds: bool,
+    /// If the point of each chunk is written at its center.
+    pub chunk_points: bool,
+    /// If the chunks whose meshes were just updated are highlighted.
+    pub dirty_chunks: bool,
+}
+
+impl Default for TilemapDebug {
+    fn default() -> Self {
+        TilemapDebug {
+            enabled: true,
+            grid: true,
+            chunk_bounds: true,
+            chunk_points: true,
+            dirty_chunks: true,
+        }
+    }
+}
+
+impl TilemapDebug {
+    /// Turns the overlays on if they are off and off if they are on.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::debug::TilemapDebug;
+    ///
+    /// let mut debug = TilemapDebug::default();
+    /// debug.toggle();
+    ///
+    /// assert!(!debug.enabled);
+    /// ```
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
+
+/// The debug overlay of a tilemap, which is a child of its entity.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TilemapDebugOverlay {
+    /// The chunks whose meshes were updated, with how many seconds they stay
+    /// highlighted for.
+    highlights: HashMap<Point2, f32>,
+}
+
+impl TilemapDebugOverlay {
+    /// Returns the points of the chunks which are highlighted as their meshes
+    /// were just updated.
+    pub fn highlighted_chunks(&self) -> impl Iterator<Item = &Point2> {
+        self.highlights.keys()
+    }
+
+    /// Counts down the highlights and highlights the chunks which were just
+    /// updated.
+    fn update_highlights(&mut self, delta: f32, dirty_chunks: &[Point2]) {
+        for seconds in self.highlights.values_mut() {
+            *seconds -= delta;
+        }
+        self.highlights.retain(|_, seconds| *seconds > 0.0);
+        for point in dirty_chunks {
+            self.highlights.insert(*point, DIRTY_HIGHLIGHT_SECONDS);
+        }
+    }
+}
+
+/// The lines of an overlay, as pairs of their ends and their colors.
+#[derive(Default)]
+struct OverlayLines {
+    /// The ends of the lines, two for every line.
+    positions: Vec<[f32; 3]>,
+    /// The color of each end.
+    colors: Vec<[f32; 4]>,
+}
+
+impl OverlayLines {
+    /// Adds a line between two ends.
+    fn push(&mut self, start: Vec2, end: Vec2, color: [f32; 4]) {
+        self.positions.push([start.x, start.y, 0.0]);
+        self.positions.push([end.x, end.y, 0.0]);
+        self.colors.push(color);
+        self.colors.push(color);
+    }
+
+    /// Sets the lines as the vertices of a line list mesh.
+    fn set_mesh(self, mesh: &mut Mesh) {
+        let indices = (0..self.positions.len() as u32).collect();
+        mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.set_attribute(ATTRIBUTE_COLOR, self.colors);
+        mesh.set_indices(Some(Indices::U32(indices)));
+    }
+}
+
+/// Returns the key of an edge between two corners, which is the same for the
+/// edge shared by the tiles on either side of it.
+fn edge_key(a: Vec2, b: Vec2) -> ((i32, i32), (i32, i32)) {
+    // Corners are on half pixels at most.
+    let a = ((a.x * 2.0).round() as i32, (a.y * 2.0).round() as i32);
+    let b = ((b.x * 2.0).round() as i32, (b.y * 2.0).round() as i32);
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// The segments of a seven segment glyph, as the ends of each line in a glyph
+/// one unit wide and two units tall.
+const SEGMENTS: [((f32, f32), (f32, f32)); 7] = [
+    ((0.0, 2.0), (1.0, 2.0)),
+    ((1.0, 2.0), (1.0, 1.0)),
+    ((1.0, 1.0), (1.0, 0.0)),
+    ((0.0, 0.0), (1.0, 0.0)),
+    ((0.0, 0.0), (0.0, 1.0)),
+    ((0.0, 1.0), (0.0, 2.0)),
+    ((0.0, 1.0), (1.0, 1.0)),
+];
+
+/// Returns the lines of a label of digits, minuses and commas, centered on a
+/// point and as tall as the height.
+fn label_lines(label: &str, center: Vec2, height: f32) -> Vec<(Vec2, Vec2)> {
+    let unit = height / 2.0;
+    let advance = 1.5;
+    let width = label.len() as f32 * advance - 0.5;
+    let origin = center - Vec2::new(width, 2.0) * unit / 2.0;
+    let mut lines = Vec::new();
+    for (index, character) in label.chars().enumerate() {
+        let left = origin + Vec2::new(index as f32 * advance * unit, 0.0);
+        let point = |(x, y): (f32, f32)| left + Vec2::new(x, y) * unit;
+        let segments = match character {
+            '0' => 0b011_1111,
+            '1' => 0b000_0110,
+            '2' => 0b101_1011,
+            '3' => 0b100_1111,
+            '4' => 0b110_0110,
+            '5' => 0b110_1101,
+            '6' => 0b111_1101,
+            '7' => 0b000_0111,
+            '8' => 0b111_1111,
+            '9' => 0b110_1111,
+            '-' => 0b100_0000,
+            ',' => {
+                lines.push((point((0.5, 0.0)), point((0.25, -0.5))));
+                continue;
+            }
+            _ => continue,
+        };
+        for (bit, (start, end)) in SEGMENTS.iter().enumerate() {
+            if segments & (1 << bit) != 0 {
+                lines.push((point(*start), point(*end)));
+            }
+        }
+    }
+    lines
+}
+
+/// Returns the lines of the overlay of a tilemap.
+fn overlay_lines(
+    tilemap: &Tilemap,
+    debug: &TilemapDebug,
+    overlay: &TilemapDebugOverlay,
+) -> OverlayLines {
+    let topology = tilemap.topology();
+    let tile_dimensions = tilemap.tile_dimensions();
+    let width = tilemap.chunk_width() as i32;
+    let height = tilemap.chunk_height() as i32;
+    let mut lines = OverlayLines::default();
+    let mut grid = HashSet::default();
+    for (x, y) in tilemap.spawned_chunks().iter() {
+        let chunk_point = Point2::new(*x, *y);
+        // The edges of the tiles of the chunk, counted by how many tiles they
+        // are shared by.
+        let mut edges: HashMap<_, (Vec2, Vec2, u32)> = HashMap::default();
+        let start = Point2::new(x * width - width / 2, y * height - height / 2);
+        for tile_y in start.y..start.y + height {
+            for tile_x in start.x..start.x + width {
+                let center = tilemap.point_to_translation((tile_x, tile_y));
+                let corners = geometry::tile_outline(topology, center, tile_dimensions);
+                for (a, b) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+                    let edge = edges.entry(edge_key(*a, *b)).or_insert((*a, *b, 0));
+                    edge.2 += 1;
+                }
+            }
+        }
+
+        if debug.grid {
+            for (key, (a, b, _)) in edges.iter() {
+                if grid.insert(*key) {
+                    lines.push(*a, *b, GRID_COLOR);
+                }
+            }
+        }
+        let dirty = debug.dirty_chunks && overlay.highlights.contains_key(&chunk_point);
+        if debug.chunk_bounds || dirty {
+            let color = if dirty {
+                DIRTY_CHUNK_COLOR
+            } else {
+                CHUNK_BOUNDS_COLOR
+            };
+            for (a, b, _) in edges.values().filter(|(_, _, count)| *count == 1) {
+                lines.push(*a, *b, color);
+            }
+        }
+        let mut min = Vec2::splat(f32::INFINITY);
+        let mut max = Vec2::splat(f32::NEG_INFINITY);
+        for (a, b, _) in edges.values() {
+            min = min.min(a.min(*b));
+            max = max.max(a.max(*b));
+        }
+        if dirty {
+            lines.push(min, max, DIRTY_CHUNK_COLOR);
+            lines.push(
+                Vec2::new(min.x, max.y),
+                Vec2::new(max.x, min.y),
+                DIRTY_CHUNK_COLOR,
+            );
+        }
+        if debug.chunk_points {
+            let label = ::std::format!("{},{}", x, y);
+            let center = (min + max) / 2.0;
+            let label_height = tile_dimensions.height as f32;
+            for (a, b) in label_lines(&label, center, label_height) {
+                lines.push(a, b, CHUNK_POINT_COLOR);
+            }
+        }
+    }
+    lines
+}
+
+/// Spawns the debug overlays of new tilemaps and builds the lines of every
+/// overlay.
+pub(crate) fn tilemap_debug_overlays(
+    commands: &mut Commands,
+    time: Res<Time>,
+    debug: Res<TilemapDebug>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    map_query: Query<(Entity, &Tilemap)>,
+    dirty_query: Query<(&Parent, &Point2), Changed<ModifiedLayer>>,
+    mut overlay_query: Query<(
+        &Parent,
+        &mut TilemapDebugOverlay,
+        &Handle<Mesh>,
+        &mut Visible,
+    )>,
+) {
+    let mut dirty_chunks: HashMap<Entity, Vec<Point2>> = HashMap::default();
+    for (parent, point) in dirty_query.iter() {
+        dirty_chunks.entry(**parent).or_default().push(*point);
+    }
+    let no_chunks = Vec::new();
+
+    let mut with_overlay = HashSet::default();
+    for (parent, mut overlay, mesh_handle, mut visible) in overlay_query.iter_mut() {
+        let tilemap = if let Ok((entity, tilemap)) = map_query.get(**parent) {
+            with_overlay.insert(entity);
+            tilemap
+        } else {
+            continue;
+        };
+        let dirty = dirty_chunks.get(&**parent).unwrap_or(&no_chunks);
+        overlay.update_highlights(time.delta_seconds(), dirty);
+        if !debug.enabled {
+            visible.is_visible = false;
+            continue;
+        }
+        let lines = overlay_lines(tilemap, &debug, &overlay);
+        // Empty meshes are not drawn, so the last lines are hidden instead.
+        visible.is_visible = !lines.positions.is_empty();
+        if visible.is_visible {
+            if let Some(mesh) = meshes.get_mut(mesh_handle) {
+                lines.set_mesh(mesh);
+            }
+        }
+    }
+
+    for (entity, _) in map_query.iter() {
+        if with_overlay.contains(&entity) {
+            continue;
+        }
+        let mut overlay = TilemapDebugOverlay::default();
+        overlay.update_highlights(0.0, dirty_chunks.get(&entity).unwrap_or(&no_chunks));
+        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+        let mut lines = OverlayLines::default();
+        lines.push(Vec2::zero(), Vec2::zero(), GRID_COLOR);
+        lines.set_mesh(&mut mesh);
+        let pipeline = RenderPipeline::new(TILEMAP_DEBUG_PIPELINE.typed());
+        let overlay_entity = if let Some(overlay_entity) = commands
+            .spawn((
+                overlay,
+                meshes.add(mesh),
+                RenderPipelines::from_pipelines(vec![pipeline]),
+                Draw::default(),
+                Visible {
+                    is_visible: false,
+                    is_transparent: true,
+                },
+                MainPass,
+                Transform::from_translation(Vec3::new(0.0, 0.0, OVERLAY_Z)),
+                GlobalTransform::default(),
+            ))
+            .current_entity()
+        {
+            overlay_entity
+        } else {
+            error!("Debug overlay entity does not exist unexpectedly");
+            continue;
+        };
+        commands.push_children(entity, &[overlay_entity]);
+    }
+}
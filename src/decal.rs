@@ -0,0 +1,171 @@
+//! Decals layered over the tiles.
+//!
+//! Decals are sprites such as blood splatters, cracks or footprints which are
+//! drawn over the tiles without replacing them. A tilemap keeps them in
+//! dedicated sparse layers which are added with [`Tilemap::add_decal_layers`],
+//! one for each decal that a tile can have at once. When a tile has as many
+//! decals as its [`DecalPolicy`] allows, adding another removes the oldest.
+//!
+//! Decals can also age, fading out before they are removed at the end of
+//! their lifetime.
+//!
+//! The rotation of each decal, in radians, is put in the meshes of the decal
+//! layers as the [`DECAL_ROTATION_CHANNEL`] vertex attribute, see the
+//! `channel` module. The built in shaders ignore it, so a custom shader reads
+//! it to draw the decals rotated.
+//!
+//! [`Tilemap::add_decal_layers`]: crate::tilemap::Tilemap::add_decal_layers
+//!
+//! # Adding decals
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_render::prelude::*;
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{decal::DecalPolicy, prelude::*};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Up to 2 decals on each tile, in the layers 2 and 3, which fade out over
+//! // the last second of the 10 seconds they last.
+//! tilemap
+//!     .add_decal_layers(DecalPolicy::new(2, 2).lifetime(10.0, 1.0))
+//!     .unwrap();
+//!
+//! tilemap.add_decal((1, 1), 7, 0.0, Color::RED).unwrap();
+//! tilemap.add_decal((1, 1), 8, 1.5, Color::WHITE).unwrap();
+//! tilemap.add_decal((1, 1), 9, 3.0, Color::WHITE).unwrap();
+//!
+//! // The oldest decal was removed to make room.
+//! let decals: Vec<usize> = tilemap.decals((1, 1)).iter().map(|decal| decal.sprite_index).collect();
+//! assert_eq!(decals, vec![8, 9]);
+//! assert_eq!(tilemap.get_tile((1, 1), 2).unwrap().index, 8);
+//! assert_eq!(tilemap.get_tile((1, 1), 3).unwrap().index, 9);
+//! ```
+
+use crate::lib::*;
+
+/// The vertex attribute of the decal layers with the rotation of each decal,
+/// in radians.
+pub const DECAL_ROTATION_CHANNEL: &str = "Vertex_Decal_Rotation";
+
+/// How many decals a tile can have and how long they last.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DecalPolicy {
+    /// The z order of the lowest decal layer.
+    pub z_order: usize,
+    /// The most decals a tile can have, which is also how many decal layers
+    /// there are.
+    pub max_per_tile: usize,
+    /// How long a decal lasts in seconds before it is removed, or `None` if it
+    /// lasts until it is cleared.
+    pub lifetime: Option<f32>,
+    /// How long before the end of its lifetime a decal starts to fade out, in
+    /// seconds.
+    pub fade_out: f32,
+}
+
+impl DecalPolicy {
+    /// Constructs a new policy of decal layers from the z order up, with
+    /// decals that last until they are cleared.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::decal::DecalPolicy;
+    ///
+    /// let policy = DecalPolicy::new(3, 4);
+    ///
+    /// assert_eq!(policy.z_orders(), 3..7);
+    /// assert_eq!(policy.lifetime, None);
+    /// ```
+    pub fn new(z_order: usize, max_per_tile: usize) -> DecalPolicy {
+        DecalPolicy {
+            z_order,
+            max_per_tile,
+            lifetime: None,
+            fade_out: 0.0,
+        }
+    }
+
+    /// Sets how long decals last and how long before the end of it they start
+    /// to fade out, in seconds.
+    pub fn lifetime(mut self, lifetime: f32, fade_out: f32) -> DecalPolicy {
+        self.lifetime = Some(lifetime);
+        self.fade_out = fade_out.min(lifetime).max(0.0);
+        self
+    }
+
+    /// Returns the z orders of the decal layers.
+    pub fn z_orders(&self) -> Range<usize> {
+        self.z_order..self.z_order + self.max_per_tile
+    }
+}
+
+/// A decal on a tile.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Decal {
+    /// The sprite index of the decal in the texture atlas.
+    pub sprite_index: usize,
+    /// The rotation of the decal in radians, see [`DECAL_ROTATION_CHANNEL`].
+    pub rotation: f32,
+    /// The tint and alpha of the decal before it fades out.
+    pub tint: Color,
+    /// How long the decal has been on its tile, in seconds.
+    pub(crate) age: f32,
+}
+
+impl Decal {
+    /// Constructs a new decal which was just added.
+    pub(crate) fn new(sprite_index: usize, rotation: f32, tint: Color) -> Decal {
+        Decal {
+            sprite_index,
+            rotation,
+            tint,
+            age: 0.0,
+        }
+    }
+
+    /// Returns how long the decal has been on its tile, in seconds.
+    pub fn age(&self) -> f32 {
+        self.age
+    }
+
+    /// Returns `true` if the decal outlived the lifetime of the policy.
+    pub(crate) fn is_expired(&self, policy: &DecalPolicy) -> bool {
+        match policy.lifetime {
+            Some(lifetime) => self.age >= lifetime,
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the decal is fading out under the policy.
+    pub(crate) fn is_fading(&self, policy: &DecalPolicy) -> bool {
+        match policy.lifetime {
+            Some(lifetime) => self.age > lifetime - policy.fade_out,
+            None => false,
+        }
+    }
+
+    /// Returns the color the decal is drawn with at its age, which is its
+    /// tint faded out towards the end of its lifetime.
+    pub(crate) fn color(&self, policy: &DecalPolicy) -> Color {
+        let lifetime = match policy.lifetime {
+            Some(lifetime) if self.is_fading(policy) && policy.fade_out > 0.0 => lifetime,
+            _ => return self.tint,
+        };
+        let remaining = ((lifetime - self.age) / policy.fade_out).clamp(0.0, 1.0);
+        let mut color = self.tint;
+        color.set_a(self.tint.a() * remaining);
+        color
+    }
+}
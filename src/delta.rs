@@ -0,0 +1,176 @@
+//! Changes to tilemaps which are sent over the network.
+//!
+//! A tilemap which tracks its changes, set with
+//! [`Tilemap::set_track_changes`], records the chunks which are inserted or
+//! removed and the tiles which are inserted, cleared or changed.
+//! [`Tilemap::drain_changes`] returns them as a [`TilemapDelta`] which a
+//! server broadcasts to its clients each tick, instead of the whole map, and
+//! which the clients apply to their copy of the tilemap with
+//! [`Tilemap::apply_delta`].
+//!
+//! A delta has the last state of each tile which changed, so a tile that
+//! changed many times in a tick is only sent once and the tiles can be
+//! applied in any order. With the `serde` feature, deltas can be serialized.
+//!
+//! Only chunks and tiles are tracked. The layers, sprites and other settings
+//! of the tilemaps of the server and its clients need to be the same.
+//! Shifting the origin of a tilemap moves its tracked changes along with it.
+//!
+//! [`Tilemap::set_track_changes`]: crate::tilemap::Tilemap::set_track_changes
+//! [`Tilemap::drain_changes`]: crate::tilemap::Tilemap::drain_changes
+//! [`Tilemap::apply_delta`]: crate::tilemap::Tilemap::apply_delta
+//!
+//! # Sending changes
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let builder = || {
+//!     TilemapBuilder::new()
+//!         .texture_atlas(texture_atlas_handle.clone())
+//!         .chunk_dimensions(4, 4)
+//!         .tile_dimensions(32, 32)
+//!         .auto_chunk()
+//! };
+//! let mut server = builder().finish().unwrap();
+//! let mut client = builder().finish().unwrap();
+//! server.set_track_changes(true);
+//!
+//! server.insert_tile(Tile { point: (1, 1), sprite_index: 3, ..Default::default() }).unwrap();
+//! server.insert_tile(Tile { point: (1, 1), sprite_index: 4, ..Default::default() }).unwrap();
+//! server.insert_tile(Tile { point: (6, 2), sprite_index: 5, ..Default::default() }).unwrap();
+//!
+//! let delta = server.drain_changes();
+//! assert_eq!(delta.tiles().len(), 2);
+//! assert!(server.drain_changes().is_empty());
+//!
+//! client.apply_delta(&delta).unwrap();
+//! assert_eq!(client.get_tile((1, 1), 0).unwrap().index, 4);
+//! assert_eq!(client.get_tile((6, 2), 0).unwrap().index, 5);
+//! ```
+
+use crate::{
+    chunk::RawTile,
+    lib::*,
+    tile::Tile,
+    tilemap::{Tilemap, TilemapResult},
+};
+
+/// The changes to the chunks and tiles of a tilemap since they were last
+/// drained.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TilemapDelta {
+    /// The chunks which were removed.
+    removed_chunks: HashSet<Point2>,
+    /// The chunks which were inserted and were not removed after.
+    inserted_chunks: HashSet<Point2>,
+    /// The last state of the tiles which changed at their points and z
+    /// orders, or `None` if they were cleared.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    tiles: HashMap<(Point2, usize), Option<RawTile>>,
+}
+
+impl TilemapDelta {
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.removed_chunks.is_empty() && self.inserted_chunks.is_empty() && self.tiles.is_empty()
+    }
+
+    /// Returns the points of the chunks which were removed. Those which were
+    /// inserted again after are also inserted chunks.
+    pub fn removed_chunks(&self) -> &HashSet<Point2> {
+        &self.removed_chunks
+    }
+
+    /// Returns the points of the chunks which were inserted.
+    pub fn inserted_chunks(&self) -> &HashSet<Point2> {
+        &self.inserted_chunks
+    }
+
+    /// Returns the last state of the tiles which changed at their points and
+    /// z orders, or `None` if they were cleared.
+    pub fn tiles(&self) -> &HashMap<(Point2, usize), Option<RawTile>> {
+        &self.tiles
+    }
+
+    /// Records that a chunk was inserted.
+    pub(crate) fn insert_chunk(&mut self, point: Point2) {
+        self.inserted_chunks.insert(point);
+    }
+
+    /// Records that a chunk was removed, dropping the changes to its tiles
+    /// which the chunk at its chunk point has.
+    pub(crate) fn remove_chunk<F>(&mut self, point: Point2, mut chunk_point: F)
+    where
+        F: FnMut(Point2) -> Point2,
+    {
+        self.inserted_chunks.remove(&point);
+        self.removed_chunks.insert(point);
+        self.tiles
+            .retain(|(tile_point, _), _| chunk_point(*tile_point) != point);
+    }
+
+    /// Records the last state of a tile.
+    pub(crate) fn set_tile(&mut self, point: Point2, z_order: usize, tile: Option<RawTile>) {
+        self.tiles.insert((point, z_order), tile);
+    }
+
+    /// Moves the changes along with the origin of the tilemap, by the chunks
+    /// and the tiles of the delta.
+    pub(crate) fn shift(&mut self, delta_chunks: Point2, delta_tiles: Point2) {
+        for points in vec![&mut self.removed_chunks, &mut self.inserted_chunks].into_iter() {
+            *points = points.drain().map(|point| point - delta_chunks).collect();
+        }
+        self.tiles = self
+            .tiles
+            .drain()
+            .map(|((point, z_order), tile)| ((point - delta_tiles, z_order), tile))
+            .collect();
+    }
+}
+
+/// Applies the changes of a delta to a tilemap.
+///
+/// The removed chunks are removed first and the inserted ones inserted after,
+/// so chunks which were removed and inserted again are empty before their
+/// tiles are set. Tiles which were cleared in chunks that the tilemap does
+/// not have are skipped.
+pub(crate) fn apply(tilemap: &mut Tilemap, delta: &TilemapDelta) -> TilemapResult<()> {
+    for point in delta.removed_chunks.iter() {
+        if tilemap.contains_chunk(*point) {
+            tilemap.remove_chunk(*point)?;
+        }
+    }
+    for point in delta.inserted_chunks.iter() {
+        if !tilemap.contains_chunk(*point) {
+            tilemap.insert_chunk(*point)?;
+        }
+    }
+
+    let mut inserted = Vec::new();
+    let mut cleared = Vec::new();
+    for ((point, z_order), tile) in delta.tiles.iter() {
+        match tile {
+            Some(tile) => inserted.push(Tile {
+                point: *point,
+                z_order: *z_order,
+                sprite_index: tile.index,
+                tint: tile.color,
+                extra: tile.extra,
+                offset: tile.offset,
+            }),
+            None => {
+                if tilemap.contains_chunk(tilemap.point_to_chunk_point(*point)) {
+                    cleared.push((*point, *z_order));
+                }
+            }
+        }
+    }
+    tilemap.clear_tiles(cleared)?;
+    tilemap.insert_tiles(inserted)
+}
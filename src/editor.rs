@@ -0,0 +1,518 @@
+//! Tools for editing tilemaps in game.
+//!
+//! A [`TilemapEditor`] added to a tilemap entity edits its tilemap with the
+//! [`EditorTool`] it holds: a brush which paints the sprite index, an eraser
+//! which clears tiles, a rectangle which is dragged out and painted when it is
+//! released, and a flood fill of the tiles which are connected to the one
+//! that was clicked and have the same sprite index.
+//!
+//! The editor does not read the mouse itself. The tile under the cursor is
+//! found with picking, see the `picking` module, or with
+//! [`Tilemap::translation_to_point`], and sent as [`TilemapEditorEvent`]s as
+//! the button is pressed, dragged and released. Every stroke can be undone
+//! and redone: before it starts a snapshot of the tilemap is taken, see the
+//! [`snapshot`] module, which shares its chunks with the tilemap so that a
+//! long history only keeps the chunks which were edited. While a rectangle is
+//! dragged out, tilemaps with a highlight layer, see the [`highlight`]
+//! module, show where it will be painted.
+//!
+//! This is behind the `editor` feature.
+//!
+//! [`Tilemap::translation_to_point`]: crate::tilemap::Tilemap::translation_to_point
+//! [`snapshot`]: crate::snapshot
+//! [`highlight`]: crate::highlight
+//!
+//! # Painting and undoing
+//! ```
+//! use bevy_app::Events;
+//! use bevy_tilemap::{
+//!     editor::{EditorInput, EditorTool, TilemapEditor, TilemapEditorEvent},
+//!     prelude::*,
+//!     testing::tilemap_test_harness,
+//! };
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.app().world.insert_one(entity, TilemapEditor::new(EditorTool::Rectangle, 3, 0)).unwrap();
+//!
+//! let inputs = vec![
+//!     EditorInput::Press((0, 0).into()),
+//!     EditorInput::Drag((1, 0).into()),
+//!     EditorInput::Release((1, 1).into()),
+//! ];
+//! let mut events = harness.app().resources.get_mut::<Events<TilemapEditorEvent>>().unwrap();
+//! for input in inputs {
+//!     events.send(TilemapEditorEvent { entity, input });
+//! }
+//! drop(events);
+//! harness.tick();
+//!
+//! assert_eq!(harness.tilemap(entity).unwrap().get_tile((1, 1), 0).unwrap().index, 3);
+//!
+//! let mut events = harness.app().resources.get_mut::<Events<TilemapEditorEvent>>().unwrap();
+//! events.send(TilemapEditorEvent { entity, input: EditorInput::Undo });
+//! drop(events);
+//! harness.tick();
+//!
+//! assert!(harness.tilemap(entity).unwrap().get_tile((1, 1), 0).is_none());
+//! ```
+
+use crate::{
+    lib::*,
+    prelude::GridTopology,
+    snapshot::TilemapSnapshot,
+    tile::Tile,
+    tilemap::{Neighborhood, Tilemap, TilemapResult},
+};
+
+/// What an editor does to the tiles it is used on.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EditorTool {
+    /// Paints the sprite index on every tile it is dragged over.
+    Brush,
+    /// Paints the sprite index on the rectangle of tiles from where it was
+    /// pressed to where it was released.
+    Rectangle,
+    /// Paints the sprite index on the tile which is pressed and every tile
+    /// connected to it which has the same sprite index, or which is empty
+    /// if it is.
+    Fill,
+    /// Clears every tile it is dragged over.
+    Eraser,
+}
+
+/// The input of the pointer of an editor, at the points of the tiles under
+/// it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EditorInput {
+    /// The pointer was pressed, which starts a stroke.
+    Press(Point2),
+    /// The pointer moved while it was pressed.
+    Drag(Point2),
+    /// The pointer was released, which ends the stroke.
+    Release(Point2),
+    /// The last stroke is undone.
+    Undo,
+    /// The last stroke which was undone is done again.
+    Redo,
+}
+
+/// An event of the input of the editor of a tilemap entity.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TilemapEditorEvent {
+    /// The tilemap entity with the editor.
+    pub entity: Entity,
+    /// The input of the editor.
+    pub input: EditorInput,
+}
+
+/// The stroke of an editor while its pointer is pressed.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Stroke {
+    /// The point where the pointer was pressed.
+    start: Point2,
+    /// The point where the pointer was last.
+    last: Point2,
+}
+
+/// An editor of the tilemap of its entity.
+#[derive(Clone, Debug)]
+pub struct TilemapEditor {
+    /// The tool which is used.
+    pub tool: EditorTool,
+    /// The sprite index which is painted.
+    pub sprite_index: usize,
+    /// The z order of the layer which is edited.
+    pub z_order: usize,
+    /// How many strokes can be undone.
+    pub history_limit: usize,
+    /// The most tiles which a fill paints, so that filling the empty tiles
+    /// of a large map does not stall the game.
+    pub fill_limit: usize,
+    /// The color of the highlights of the rectangle while it is dragged out.
+    pub preview_color: Color,
+    /// The stroke while the pointer is pressed.
+    stroke: Option<Stroke>,
+    /// The snapshots from before each stroke which can be undone, from the
+    /// oldest to the newest.
+    undo: Vec<TilemapSnapshot>,
+    /// The snapshots from before each undo which can be redone.
+    redo: Vec<TilemapSnapshot>,
+}
+
+impl Default for TilemapEditor {
+    fn default() -> Self {
+        TilemapEditor {
+            tool: EditorTool::Brush,
+            sprite_index: 0,
+            z_order: 0,
+            history_limit: 64,
+            fill_limit: 4096,
+            preview_color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+            stroke: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl TilemapEditor {
+    /// Constructs a new editor which uses the tool to edit the layer at the
+    /// z order with the sprite index.
+    pub fn new(tool: EditorTool, sprite_index: usize, z_order: usize) -> TilemapEditor {
+        TilemapEditor {
+            tool,
+            sprite_index,
+            z_order,
+            ..Default::default()
+        }
+    }
+
+    /// Starts a stroke at a point, which can be undone as a whole.
+    ///
+    /// # Errors
+    ///
+    /// If a tile can not be painted or cleared, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{editor::{EditorTool, TilemapEditor}, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A stroke of the brush, dragged quickly enough to skip a tile.
+    /// let mut editor = TilemapEditor::new(EditorTool::Brush, 3, 0);
+    /// editor.press(&mut tilemap, (0, 0)).unwrap();
+    /// editor.drag(&mut tilemap, (2, 0)).unwrap();
+    /// editor.release(&mut tilemap, (2, 0)).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 3);
+    ///
+    /// assert!(editor.undo(&mut tilemap).unwrap());
+    /// assert!(tilemap.get_tile((1, 0), 0).is_none());
+    ///
+    /// assert!(editor.redo(&mut tilemap).unwrap());
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 3);
+    /// ```
+    pub fn press<P: Into<Point2>>(&mut self, tilemap: &mut Tilemap, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        self.undo.push(tilemap.snapshot());
+        if self.undo.len() > self.history_limit {
+            let excess = self.undo.len() - self.history_limit;
+            self.undo.drain(..excess);
+        }
+        self.redo.clear();
+        self.stroke = Some(Stroke {
+            start: point,
+            last: point,
+        });
+        match self.tool {
+            EditorTool::Brush | EditorTool::Eraser => self.paint(tilemap, &[point]),
+            EditorTool::Fill => self.fill(tilemap, point),
+            EditorTool::Rectangle => Ok(()),
+        }
+    }
+
+    /// Moves the stroke on to a point, painting or clearing the tiles on the
+    /// way there.
+    ///
+    /// # Errors
+    ///
+    /// If a tile can not be painted or cleared, an error is returned.
+    pub fn drag<P: Into<Point2>>(&mut self, tilemap: &mut Tilemap, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        let stroke = match &mut self.stroke {
+            Some(stroke) => stroke,
+            None => return Ok(()),
+        };
+        let last = stroke.last;
+        stroke.last = point;
+        match self.tool {
+            EditorTool::Brush | EditorTool::Eraser => {
+                let points = line_points(last, point);
+                self.paint(tilemap, &points)
+            }
+            EditorTool::Fill | EditorTool::Rectangle => Ok(()),
+        }
+    }
+
+    /// Ends the stroke at a point, which paints the rectangle of the
+    /// rectangle tool.
+    ///
+    /// # Errors
+    ///
+    /// If a tile can not be painted or cleared, an error is returned.
+    pub fn release<P: Into<Point2>>(
+        &mut self,
+        tilemap: &mut Tilemap,
+        point: P,
+    ) -> TilemapResult<()> {
+        self.drag(tilemap, point)?;
+        if self.tool == EditorTool::Rectangle {
+            let points = self.preview();
+            self.paint(tilemap, &points)?;
+        }
+        self.stroke = None;
+        Ok(())
+    }
+
+    /// Returns `true` while the pointer is pressed.
+    pub fn is_stroking(&self) -> bool {
+        self.stroke.is_some()
+    }
+
+    /// Returns the points of the rectangle which is being dragged out, or
+    /// nothing if there is none.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{editor::{EditorTool, TilemapEditor}, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let mut editor = TilemapEditor::new(EditorTool::Rectangle, 3, 0);
+    /// editor.press(&mut tilemap, (2, 2)).unwrap();
+    /// editor.drag(&mut tilemap, (1, 3)).unwrap();
+    ///
+    /// assert_eq!(editor.preview().len(), 4);
+    /// ```
+    pub fn preview(&self) -> Vec<Point2> {
+        let stroke = match (&self.stroke, self.tool) {
+            (Some(stroke), EditorTool::Rectangle) => stroke,
+            _ => return Vec::new(),
+        };
+        let min_x = stroke.start.x.min(stroke.last.x);
+        let max_x = stroke.start.x.max(stroke.last.x);
+        let min_y = stroke.start.y.min(stroke.last.y);
+        let max_y = stroke.start.y.max(stroke.last.y);
+        let mut points = Vec::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                points.push(Point2::new(x, y));
+            }
+        }
+        points
+    }
+
+    /// Puts the tilemap back to how it was before the last stroke.
+    ///
+    /// Returns `false` if there is no stroke to undo.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap can not be restored, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{editor::{EditorTool, TilemapEditor}, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// // Fills the 16 empty tiles of the chunk.
+    /// let mut editor = TilemapEditor::new(EditorTool::Fill, 5, 0);
+    /// editor.press(&mut tilemap, (0, 0)).unwrap();
+    /// editor.release(&mut tilemap, (0, 0)).unwrap();
+    /// assert_eq!(tilemap.get_tile((-2, -2), 0).unwrap().index, 5);
+    /// assert!(tilemap.get_tile((2, 0), 0).is_none());
+    ///
+    /// assert!(editor.undo(&mut tilemap).unwrap());
+    /// assert!(tilemap.get_tile((-2, -2), 0).is_none());
+    /// assert!(!editor.undo(&mut tilemap).unwrap());
+    /// ```
+    pub fn undo(&mut self, tilemap: &mut Tilemap) -> TilemapResult<bool> {
+        self.stroke = None;
+        let snapshot = match self.undo.pop() {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
+        };
+        self.redo.push(tilemap.snapshot());
+        tilemap.restore(&snapshot)?;
+        Ok(true)
+    }
+
+    /// Does the last stroke which was undone again.
+    ///
+    /// Returns `false` if there is no stroke to redo.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap can not be restored, an error is returned.
+    pub fn redo(&mut self, tilemap: &mut Tilemap) -> TilemapResult<bool> {
+        self.stroke = None;
+        let snapshot = match self.redo.pop() {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
+        };
+        self.undo.push(tilemap.snapshot());
+        tilemap.restore(&snapshot)?;
+        Ok(true)
+    }
+
+    /// Returns `true` if there is a stroke which can be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is a stroke which can be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Paints the sprite index on the tiles at the points, or clears them
+    /// with the eraser.
+    fn paint(&self, tilemap: &mut Tilemap, points: &[Point2]) -> TilemapResult<()> {
+        for point in points {
+            if self.tool == EditorTool::Eraser {
+                if tilemap.get_tile(*point, self.z_order).is_some() {
+                    tilemap.clear_tile(*point, self.z_order)?;
+                }
+            } else {
+                tilemap.insert_tile(Tile {
+                    point: *point,
+                    z_order: self.z_order,
+                    sprite_index: self.sprite_index,
+                    ..Default::default()
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Paints the sprite index on the tile at the point and the tiles
+    /// connected to it with the same sprite index, within the chunks of the
+    /// tilemap.
+    fn fill(&self, tilemap: &mut Tilemap, point: Point2) -> TilemapResult<()> {
+        let target = tilemap.get_tile(point, self.z_order).map(|tile| tile.index);
+        if target == Some(self.sprite_index) {
+            return Ok(());
+        }
+        let neighborhood = match tilemap.topology() {
+            GridTopology::Square | GridTopology::IsoDiamond => Neighborhood::Four,
+            _ => Neighborhood::Hex,
+        };
+        let mut filled = HashSet::default();
+        let mut open = vec![point];
+        filled.insert(point);
+        let mut points = Vec::new();
+        while let Some(point) = open.pop() {
+            if points.len() >= self.fill_limit {
+                break;
+            }
+            points.push(point);
+            let neighbors: Vec<Point2> = tilemap
+                .neighbors(point, self.z_order, neighborhood)
+                .filter(|(neighbor, tile)| {
+                    tile.map(|tile| tile.index) == target && !filled.contains(neighbor)
+                })
+                .map(|(neighbor, _)| neighbor)
+                .collect();
+            for neighbor in neighbors {
+                let chunk_point = tilemap.point_to_chunk_point(neighbor);
+                if tilemap.contains_chunk(chunk_point) {
+                    filled.insert(neighbor);
+                    open.push(neighbor);
+                }
+            }
+        }
+        self.paint(tilemap, &points)
+    }
+}
+
+/// Returns the points on the line from one point to another, without the
+/// first.
+fn line_points(from: Point2, to: Point2) -> Vec<Point2> {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let step_x = if from.x < to.x { 1 } else { -1 };
+    let step_y = if from.y < to.y { 1 } else { -1 };
+    let mut error = dx + dy;
+    let mut point = from;
+    let mut points = Vec::new();
+    while point != to {
+        let doubled = error * 2;
+        if doubled >= dy {
+            error += dy;
+            point.x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            point.y += step_y;
+        }
+        points.push(point);
+    }
+    points
+}
+
+/// Passes the editor events on to the editors of their tilemaps, and shows
+/// the rectangles which are being dragged out.
+pub(crate) fn tilemap_editors(
+    mut reader: Local<EventReader<TilemapEditorEvent>>,
+    events: Res<Events<TilemapEditorEvent>>,
+    mut editor_query: Query<(&mut TilemapEditor, &mut Tilemap)>,
+) {
+    for event in reader.iter(&events) {
+        let (mut editor, mut tilemap) = if let Ok(editor) = editor_query.get_mut(event.entity) {
+            editor
+        } else {
+            warn!("{:?} has no tilemap editor, skipping", event.entity);
+            continue;
+        };
+        let result = match event.input {
+            EditorInput::Press(point) => editor.press(&mut tilemap, point),
+            EditorInput::Drag(point) => editor.drag(&mut tilemap, point),
+            EditorInput::Release(point) => editor.release(&mut tilemap, point),
+            EditorInput::Undo => editor.undo(&mut tilemap).map(|_| ()),
+            EditorInput::Redo => editor.redo(&mut tilemap).map(|_| ()),
+        };
+        if let Err(e) = result {
+            warn!("{}", e);
+        }
+    }
+
+    for (editor, mut tilemap) in editor_query.iter_mut() {
+        let preview = editor.preview();
+        if preview.is_empty() || tilemap.highlight_layer().is_none() {
+            continue;
+        }
+        if let Err(e) = tilemap.highlight_tiles(preview, editor.preview_color) {
+            warn!("{}", e);
+        }
+    }
+}
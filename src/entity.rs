@@ -1,5 +1,7 @@
 //! Bundles of components for spawning entities.
 
+#[cfg(feature = "render")]
+use crate::instance::{TilemapData, TilemapInstance};
 use crate::{
     lib::{Bundle, *},
     Tilemap,
@@ -10,6 +12,27 @@ use crate::{
 pub struct TilemapBundle {
     /// A `Tilemap` which maintains chunks and its tiles.
     pub tilemap: Tilemap,
+    /// The transform of the tilemap, which moves, rotates and scales the
+    /// chunks, their colliders and the chunks that are spawned around the
+    /// cameras along with it. The chunks are its children, so it may be the
+    /// child of another entity itself.
+    pub transform: Transform,
+    /// The global transform location in a space for a component.
+    pub global_transform: GlobalTransform,
+}
+
+/// A component bundle for entities that render a `TilemapData` asset.
+///
+/// See the [`instance`] module for more information.
+///
+/// [`instance`]: crate::instance
+#[cfg(feature = "render")]
+#[derive(Debug, Default, Bundle)]
+pub struct TilemapInstanceBundle {
+    /// The handle of the `TilemapData` asset to render.
+    pub tilemap: Handle<TilemapData>,
+    /// The chunks which the instance has spawned.
+    pub instance: TilemapInstance,
     /// The transform location in a space for a component.
     pub transform: Transform,
     /// The global transform location in a space for a component.
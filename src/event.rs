@@ -1,7 +1,6 @@
 //! The tilemap events.
 
 use crate::lib::*;
-#[cfg(feature = "bevy_rapier2d")]
 use crate::Tile;
 
 #[derive(Debug)]
@@ -24,9 +23,27 @@ pub enum TilemapChunkEvent {
         /// The point of the chunk to despawn.
         point: Point2,
     },
+    /// An event when the origin or the translation origin of the tilemap
+    /// moved and the spawned chunks need to move to their new translations.
+    Rehomed {
+        /// The offset in chunks that the origin or translation origin moved
+        /// by.
+        delta: Point2,
+    },
+}
+
+impl Display for TilemapChunkEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use TilemapChunkEvent::*;
+        match self {
+            Spawned { point } => write!(f, "chunk {} spawned", point),
+            Modified { layers } => write!(f, "{} chunk layer(s) modified", layers.len()),
+            Despawned { point, .. } => write!(f, "chunk {} despawned", point),
+            Rehomed { delta } => write!(f, "chunks rehomed by {}", delta),
+        }
+    }
 }
 
-#[cfg(feature = "bevy_rapier2d")]
 #[derive(Debug)]
 /// Events that can happen to collisions.
 pub enum TilemapCollisionEvent {
@@ -45,3 +62,177 @@ pub enum TilemapCollisionEvent {
         tiles: Vec<Tile<Point2>>,
     },
 }
+
+impl Display for TilemapCollisionEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use TilemapCollisionEvent::*;
+        match self {
+            Spawned { chunk_point, tiles } => write!(
+                f,
+                "{} collision(s) spawned in chunk {}",
+                tiles.len(),
+                chunk_point
+            ),
+            Despawned { chunk_point, tiles } => write!(
+                f,
+                "{} collision(s) despawned in chunk {}",
+                tiles.len(),
+                chunk_point
+            ),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// An event when a body starts to overlap a sensor tile.
+pub struct TileTriggerEvent {
+    /// The global point of the sensor tile.
+    pub point: Point2,
+    /// The z order of the sensor tile.
+    pub z_order: usize,
+    /// The entity of the body that overlaps the sensor tile.
+    pub entity: Entity,
+}
+
+impl Display for TileTriggerEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "sensor tile {} on z order {} triggered by {:?}",
+            self.point, self.z_order, self.entity
+        )
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// An event when a tile was destroyed by damage.
+pub struct TileDestroyedEvent {
+    /// The global point of the tile.
+    pub point: Point2,
+    /// The z order of the tile.
+    pub z_order: usize,
+    /// The sprite index the tile had.
+    pub sprite_index: usize,
+    /// The sprite index of the rubble which replaced the tile, or `None` if
+    /// it was removed.
+    pub rubble: Option<usize>,
+}
+
+impl Display for TileDestroyedEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "tile {} on z order {} destroyed",
+            self.point, self.z_order
+        )
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// Events of the load that a tilemap shed, see [`ShedPolicy`].
+///
+/// [`ShedPolicy`]: crate::chunk::ShedPolicy
+pub enum TilemapShedEvent {
+    /// An event when the colliders of a chunk were despawned.
+    Colliders {
+        /// The point of the chunk.
+        chunk_point: Point2,
+        /// How many collider entities were despawned.
+        colliders: usize,
+    },
+    /// An event when a chunk was despawned and its meshes dropped.
+    Meshes {
+        /// The point of the chunk.
+        chunk_point: Point2,
+    },
+    /// An event when the data of a chunk was written to the chunk store and
+    /// dropped from memory.
+    Unloaded {
+        /// The point of the chunk.
+        chunk_point: Point2,
+    },
+}
+
+impl Display for TilemapShedEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use TilemapShedEvent::*;
+        match self {
+            Colliders {
+                chunk_point,
+                colliders,
+            } => write!(f, "{} collider(s) of chunk {} shed", colliders, chunk_point),
+            Meshes { chunk_point } => write!(f, "meshes of chunk {} shed", chunk_point),
+            Unloaded { chunk_point } => write!(f, "chunk {} unloaded", chunk_point),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// An event when a chunk whose tiles were all removed was despawned and
+/// removed, see [`Tilemap::set_empty_chunk_frames`].
+///
+/// [`Tilemap::set_empty_chunk_frames`]: crate::tilemap::Tilemap::set_empty_chunk_frames
+pub struct ChunkDespawnedEvent {
+    /// The point of the chunk.
+    pub point: Point2,
+}
+
+impl Display for ChunkDespawnedEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "empty chunk {} despawned", self.point)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+/// An event when a gameplay entity was unbound from its tile, see
+/// [`Tilemap::set_tile_entity`]. The entity is not despawned, so that the game
+/// can despawn it, keep it or save it with its chunk.
+///
+/// [`Tilemap::set_tile_entity`]: crate::tilemap::Tilemap::set_tile_entity
+pub enum TileEntityEvent {
+    /// The chunk of the tile was despawned, such as when it left the radius of
+    /// the cameras.
+    ChunkDespawned {
+        /// The point of the tile.
+        point: Point2,
+        /// The z order of the tile.
+        z_order: usize,
+        /// The entity which was bound to the tile.
+        entity: Entity,
+    },
+    /// The tile was cleared, or its chunk or layer was removed.
+    TileRemoved {
+        /// The point of the tile.
+        point: Point2,
+        /// The z order of the tile.
+        z_order: usize,
+        /// The entity which was bound to the tile.
+        entity: Entity,
+    },
+}
+
+impl Display for TileEntityEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use TileEntityEvent::*;
+        match self {
+            ChunkDespawned {
+                point,
+                z_order,
+                entity,
+            } => write!(
+                f,
+                "entity {:?} of tile {} on layer {} unbound, its chunk was despawned",
+                entity, point, z_order
+            ),
+            TileRemoved {
+                point,
+                z_order,
+                entity,
+            } => write!(
+                f,
+                "entity {:?} of tile {} on layer {} unbound, the tile was removed",
+                entity, point, z_order
+            ),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Exporting the tiles of a tilemap to an image.
+//!
+//! [`Tilemap::render_to_texture`] draws a region of tiles of a range of layers
+//! on the CPU, from the pixels of the texture of the texture atlas, into a new
+//! [`Texture`]. Every tile is drawn where it is rendered, so this works for
+//! every topology, and with the sprite rects and tints of the tiles. With the
+//! `png` feature the texture can then be written to a file with [`save_png`],
+//! for debugging or screenshots of whole maps.
+//!
+//! Only textures with 8 bit RGBA pixels can be read from, which is what PNG
+//! textures are loaded as.
+//!
+//! [`Tilemap::render_to_texture`]: crate::tilemap::Tilemap::render_to_texture
+//! [`Texture`]: bevy_render::texture::Texture
+//!
+//! # Exporting a region
+//! ```no_run
+//! use bevy_asset::prelude::*;
+//! use bevy_render::prelude::*;
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{export::save_png, prelude::*};
+//!
+//! fn export(
+//!     tilemap: &Tilemap,
+//!     texture_atlases: &Assets<TextureAtlas>,
+//!     textures: &Assets<Texture>,
+//! ) {
+//!     let texture_atlas = texture_atlases.get(tilemap.texture_atlas()).unwrap();
+//!     let texture = tilemap
+//!         .render_to_texture((-16, -16), (15, 15), 0..2, texture_atlas, textures)
+//!         .unwrap();
+//!     save_png(&texture, "map.png").unwrap();
+//! }
+//! ```
+
+use crate::{
+    lib::*,
+    sprite::SpriteRect,
+    tilemap::{ErrorKind, Tilemap, TilemapResult},
+};
+
+/// The size in bytes of a pixel of the textures that can be drawn from.
+const PIXEL_SIZE: usize = 4;
+
+/// A tile to be drawn, with the rect of its tile in pixels.
+struct DrawnTile {
+    /// The z order of the tile.
+    z_order: usize,
+    /// The bottom left of the tile in the image.
+    min: Vec2,
+    /// The sprite of the tile.
+    sprite_rect: SpriteRect,
+    /// The tint of the tile.
+    color: Color,
+}
+
+/// Draws the tiles between two points of the layers of a z order range into
+/// a new texture, see [`Tilemap::render_to_texture`].
+///
+/// [`Tilemap::render_to_texture`]: crate::tilemap::Tilemap::render_to_texture
+pub(crate) fn render_to_texture(
+    tilemap: &Tilemap,
+    min: Point2,
+    max: Point2,
+    layers: Range<usize>,
+    texture_atlas: &TextureAtlas,
+    textures: &Assets<Texture>,
+) -> TilemapResult<Texture> {
+    let atlas_texture = textures
+        .get(&texture_atlas.texture)
+        .ok_or_else(|| ErrorKind::ImageExport("the atlas texture is not loaded".to_string()))?;
+    match atlas_texture.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {}
+        format => {
+            return Err(ErrorKind::ImageExport(::std::format!(
+                "the atlas texture format {:?} is not 8 bit RGBA",
+                format
+            ))
+            .into())
+        }
+    }
+
+    let tile_size = Vec2::new(tilemap.tile_width() as f32, tilemap.tile_height() as f32);
+    let (min_x, max_x) = (min.x.min(max.x), min.x.max(max.x));
+    let (min_y, max_y) = (min.y.min(max.y), min.y.max(max.y));
+
+    // The image covers the whole region, not just the tiles in it.
+    let mut lower = Vec2::new(f32::MAX, f32::MAX);
+    let mut upper = Vec2::new(f32::MIN, f32::MIN);
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let center = tilemap.point_to_translation((x, y));
+            lower = lower.min(center - tile_size / 2.0);
+            upper = upper.max(center + tile_size / 2.0);
+        }
+    }
+    let lower = Vec2::new(lower.x.floor(), lower.y.floor());
+    let width = (upper.x.ceil() - lower.x) as usize;
+    let height = (upper.y.ceil() - lower.y) as usize;
+
+    let mut tiles = Vec::new();
+    for z_order in layers {
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let tile = match tilemap.get_tile((x, y), z_order) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                let sprite_rect = match tilemap.sprite_rect(tile.index) {
+                    Some(sprite_rect) => *sprite_rect,
+                    None => match texture_atlas.textures.get(tile.index) {
+                        Some(rect) => SpriteRect::new(rect.min, rect.max),
+                        None => continue,
+                    },
+                };
+                let center = tilemap.point_to_translation((x, y));
+                tiles.push(DrawnTile {
+                    z_order,
+                    min: center - tile_size / 2.0 - lower,
+                    sprite_rect,
+                    color: tile.color,
+                });
+            }
+        }
+    }
+    // Lower tiles overlap the ones above them, as they do when rendered.
+    tiles.sort_by(|a, b| {
+        a.z_order
+            .cmp(&b.z_order)
+            .then(b.min.y.partial_cmp(&a.min.y).unwrap_or(Ordering::Equal))
+    });
+
+    let mut data = vec![0; width * height * PIXEL_SIZE];
+    for tile in tiles.iter() {
+        draw_tile(&mut data, width, height, tile, tile_size, atlas_texture);
+    }
+
+    Ok(Texture::new(
+        Extent3d::new(width as u32, height as u32, 1),
+        TextureDimension::D2,
+        data,
+        atlas_texture.format,
+    ))
+}
+
+/// Draws a tile over the pixels of an image, blending it by its alpha.
+fn draw_tile(
+    data: &mut [u8],
+    width: usize,
+    height: usize,
+    tile: &DrawnTile,
+    tile_size: Vec2,
+    atlas_texture: &Texture,
+) {
+    let sprite_rect = &tile.sprite_rect;
+    let grid_size = sprite_rect.grid_size();
+    if grid_size.x <= 0.0 || grid_size.y <= 0.0 {
+        return;
+    }
+    // The part of the image that the trimmed or anchored sprite is drawn in,
+    // which may overhang the tile.
+    let scale = tile_size / grid_size;
+    let min = tile.min + sprite_rect.sprite_offset() * scale;
+    let size = sprite_rect.size() * scale;
+
+    let atlas_width = atlas_texture.size.width as usize;
+    let atlas_height = atlas_texture.size.height as usize;
+    let tint = [
+        tile.color.r(),
+        tile.color.g(),
+        tile.color.b(),
+        tile.color.a(),
+    ];
+    let start_x = min.x.floor().max(0.0) as usize;
+    let end_x = ((min.x + size.x).ceil().max(0.0) as usize).min(width);
+    let start_y = min.y.floor().max(0.0) as usize;
+    let end_y = ((min.y + size.y).ceil().max(0.0) as usize).min(height);
+    for y in start_y..end_y {
+        for x in start_x..end_x {
+            let u = (x as f32 + 0.5 - min.x) / size.x;
+            let v = (y as f32 + 0.5 - min.y) / size.y;
+            if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) {
+                continue;
+            }
+            let (sample_x, sample_y) = if sprite_rect.rotated {
+                (
+                    sprite_rect.min.x + v * (sprite_rect.max.x - sprite_rect.min.x),
+                    sprite_rect.min.y + u * (sprite_rect.max.y - sprite_rect.min.y),
+                )
+            } else {
+                (
+                    sprite_rect.min.x + u * (sprite_rect.max.x - sprite_rect.min.x),
+                    sprite_rect.max.y - v * (sprite_rect.max.y - sprite_rect.min.y),
+                )
+            };
+            let sample_x = (sample_x.max(0.0) as usize).min(atlas_width.saturating_sub(1));
+            let sample_y = (sample_y.max(0.0) as usize).min(atlas_height.saturating_sub(1));
+            let source = (sample_y * atlas_width + sample_x) * PIXEL_SIZE;
+            let source = match atlas_texture.data.get(source..source + PIXEL_SIZE) {
+                Some(source) => source,
+                None => continue,
+            };
+            // Images are stored from the top row down.
+            let target = ((height - 1 - y) * width + x) * PIXEL_SIZE;
+            if let Some(target) = data.get_mut(target..target + PIXEL_SIZE) {
+                blend(target, source, tint);
+            }
+        }
+    }
+}
+
+/// Blends a tinted source pixel over a target pixel.
+fn blend(target: &mut [u8], source: &[u8], tint: [f32; 4]) {
+    let channel = |i: usize| -> f32 {
+        let value = source.get(i).copied().map_or(0.0, f32::from) / 255.0;
+        value * tint.get(i).copied().unwrap_or(1.0)
+    };
+    let alpha = channel(3);
+    if alpha <= 0.0 {
+        return;
+    }
+    let target_alpha = target.get(3).copied().map_or(0.0, f32::from) / 255.0;
+    let out_alpha = alpha + target_alpha * (1.0 - alpha);
+    for (i, value) in target.iter_mut().enumerate().take(3) {
+        let previous = f32::from(*value) / 255.0;
+        let blended = (channel(i) * alpha + previous * target_alpha * (1.0 - alpha)) / out_alpha;
+        *value = (blended * 255.0).round() as u8;
+    }
+    if let Some(value) = target.get_mut(3) {
+        *value = (out_alpha * 255.0).round() as u8;
+    }
+}
+
+/// Writes an 8 bit RGBA texture to a PNG file.
+///
+/// # Errors
+///
+/// If the texture is of another format or the file can not be written, an
+/// error is returned.
+#[cfg(feature = "png")]
+pub fn save_png<P: AsRef<Path>>(texture: &Texture, path: P) -> TilemapResult<()> {
+    match texture.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {}
+        format => {
+            return Err(ErrorKind::ImageExport(::std::format!(
+                "the texture format {:?} is not 8 bit RGBA",
+                format
+            ))
+            .into())
+        }
+    }
+    image::save_buffer(
+        path,
+        &texture.data,
+        texture.size.width,
+        texture.size.height,
+        image::ColorType::Rgba8,
+    )
+    .map_err(|e| ErrorKind::ImageExport(e.to_string()).into())
+}
@@ -0,0 +1,323 @@
+//! Map formats which tilemaps can be loaded from.
+//!
+//! A [`TilemapFormat`] parses the bytes of a map file into a
+//! [`TilemapPrototype`], which describes the tilemap, its layers and its tiles
+//! along with the sprite sheet they are drawn from. Every format builds its
+//! tilemaps from a prototype with [`TilemapPrototype::into_tilemap`], so maps
+//! of every format are made the same way.
+//!
+//! Formats are added to an app with [`AddTilemapFormat::add_tilemap_format`],
+//! which adds an asset loader for the extensions of the format that loads
+//! files as `TilemapData` assets, and keeps the format in the [`TilemapFormats`]
+//! resource to parse maps which are not assets. The `ron-asset` feature adds
+//! the format of the [`asset`] module.
+//!
+//! This is behind the `format` feature.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["format"] }
+//! ```
+//!
+//! [`asset`]: crate::asset
+//!
+//! # Adding a format
+//! ```
+//! use bevy_app::prelude::*;
+//! use bevy_tilemap::{
+//!     format::{AddTilemapFormat, TilemapFormat, TilemapPrototype},
+//!     prelude::*,
+//!     tilemap::TilemapResult,
+//! };
+//!
+//! /// Maps of one row of sprite indexes, such as `1 2 3`.
+//! struct RowFormat;
+//!
+//! impl TilemapFormat for RowFormat {
+//!     fn extensions(&self) -> &[&str] {
+//!         &["row"]
+//!     }
+//!
+//!     fn parse(&self, bytes: &[u8]) -> TilemapResult<TilemapPrototype> {
+//!         let mut prototype = TilemapPrototype::new(16, 16).atlas("tiles.png", 4, 4);
+//!         let text = String::from_utf8_lossy(bytes);
+//!         for (x, index) in text.split_whitespace().enumerate() {
+//!             prototype.tiles.push(Tile {
+//!                 point: (x as i32, 0).into(),
+//!                 sprite_index: index.parse().unwrap_or(0),
+//!                 ..Default::default()
+//!             });
+//!         }
+//!         Ok(prototype)
+//!     }
+//! }
+//!
+//! fn build(app: &mut AppBuilder) {
+//!     app.add_tilemap_format(RowFormat);
+//! }
+//! ```
+
+use crate::{
+    chunk::render::GridTopology,
+    lib::*,
+    tilemap::{ErrorKind, TilemapBuilder, TilemapResult},
+    Tile, Tilemap, TilemapLayer,
+};
+
+/// The label of the `TextureAtlas` of a loaded tilemap.
+pub const TEXTURE_ATLAS_LABEL: &str = "texture_atlas";
+
+/// A sprite sheet which is split into a grid of sprites.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AtlasGrid {
+    /// The path of the texture, relative to the map file.
+    pub texture: String,
+    /// The number of columns of sprites.
+    pub columns: usize,
+    /// The number of rows of sprites.
+    pub rows: usize,
+    /// The dimensions of the sprites in pixels, or `None` if they are those
+    /// of the tiles.
+    pub sprite_dimensions: Option<Dimension2>,
+}
+
+/// A description of a tilemap which a map format parsed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TilemapPrototype {
+    /// The name of the tilemap.
+    pub name: Option<String>,
+    /// The topology of the tilemap.
+    pub topology: GridTopology,
+    /// The dimensions of the tilemap in chunks, if it has any.
+    pub dimensions: Option<Dimension2>,
+    /// The dimensions of the chunks in tiles, or `None` for the default.
+    pub chunk_dimensions: Option<Dimension2>,
+    /// The dimensions of the tiles in pixels.
+    pub tile_dimensions: Dimension2,
+    /// The sprite sheet of the tiles, which a loaded map must have.
+    pub atlas: Option<AtlasGrid>,
+    /// The layers of the tilemap at their z order.
+    pub layers: Vec<(usize, TilemapLayer)>,
+    /// The points of the chunks to make, even if they have no tiles.
+    pub chunks: Vec<Point2>,
+    /// The tiles of the tilemap.
+    pub tiles: Vec<Tile<Point2>>,
+}
+
+impl TilemapPrototype {
+    /// Constructs a prototype of an empty square tilemap with the dimensions
+    /// of its tiles in pixels.
+    pub fn new(tile_width: u32, tile_height: u32) -> TilemapPrototype {
+        TilemapPrototype {
+            name: None,
+            topology: GridTopology::Square,
+            dimensions: None,
+            chunk_dimensions: None,
+            tile_dimensions: Dimension2::new(tile_width, tile_height),
+            atlas: None,
+            layers: Vec::new(),
+            chunks: Vec::new(),
+            tiles: Vec::new(),
+        }
+    }
+
+    /// Sets the sprite sheet of the tiles, split into a grid of sprites of the
+    /// tile dimensions.
+    pub fn atlas<S: Into<String>>(
+        mut self,
+        texture: S,
+        columns: usize,
+        rows: usize,
+    ) -> TilemapPrototype {
+        self.atlas = Some(AtlasGrid {
+            texture: texture.into(),
+            columns,
+            rows,
+            sprite_dimensions: None,
+        });
+        self
+    }
+
+    /// Builds the tilemap of the prototype, drawn with the texture atlas, and
+    /// spawns every chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{format::TilemapPrototype, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut prototype = TilemapPrototype::new(32, 32);
+    /// prototype.tiles.push(Tile { point: (3, 3).into(), sprite_index: 2, ..Default::default() });
+    ///
+    /// let tilemap = prototype.into_tilemap(texture_atlas_handle).unwrap();
+    /// assert_eq!(tilemap.get_tile((3, 3), 0).unwrap().index, 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tilemap can not be built or its tiles can not
+    /// be inserted.
+    pub fn into_tilemap(self, texture_atlas: Handle<TextureAtlas>) -> TilemapResult<Tilemap> {
+        let mut builder = TilemapBuilder::new()
+            .texture_atlas(texture_atlas)
+            .topology(self.topology)
+            .tile_dimensions(self.tile_dimensions.width, self.tile_dimensions.height)
+            .auto_chunk();
+        if let Some(name) = self.name {
+            builder = builder.name(name);
+        }
+        if let Some(dimensions) = self.dimensions {
+            builder = builder.dimensions(dimensions.width, dimensions.height);
+        }
+        if let Some(dimensions) = self.chunk_dimensions {
+            builder = builder.chunk_dimensions(dimensions.width, dimensions.height);
+        }
+        if let Some(z_layers) = self.layers.iter().map(|(z_order, _)| z_order + 1).max() {
+            builder = builder.z_layers(z_layers);
+        }
+        for (z_order, layer) in self.layers.into_iter() {
+            builder = builder.add_layer(layer, z_order);
+        }
+        let mut tilemap = builder.finish()?;
+
+        for point in self.chunks.into_iter() {
+            if !tilemap.contains_chunk(point) {
+                tilemap.insert_chunk(point)?;
+            }
+        }
+        tilemap.insert_tiles(self.tiles)?;
+        let points: Vec<Point2> = tilemap.chunk_points().collect();
+        for point in points.into_iter() {
+            tilemap.spawn_chunk(point)?;
+        }
+
+        Ok(tilemap)
+    }
+}
+
+/// A format of map files which tilemaps are loaded from.
+pub trait TilemapFormat: Send + Sync + 'static {
+    /// Returns the extensions of the files of the format, without the dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Parses the bytes of a map file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid map of the format.
+    fn parse(&self, bytes: &[u8]) -> TilemapResult<TilemapPrototype>;
+}
+
+/// The map formats which were added to the app.
+#[derive(Default)]
+pub struct TilemapFormats {
+    /// The formats, from the first added.
+    formats: Vec<Arc<dyn TilemapFormat>>,
+}
+
+impl TilemapFormats {
+    /// Returns the format of an extension, which is the last added one if
+    /// more than one has it.
+    pub fn get(&self, extension: &str) -> Option<&dyn TilemapFormat> {
+        self.formats
+            .iter()
+            .rev()
+            .find(|format| format.extensions().contains(&extension))
+            .map(|format| &**format)
+    }
+
+    /// Parses the bytes of a map file with the format of its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no format has the extension or the bytes are not a
+    /// valid map of the format.
+    pub fn parse(&self, extension: &str, bytes: &[u8]) -> TilemapResult<TilemapPrototype> {
+        match self.get(extension) {
+            Some(format) => format.parse(bytes),
+            None => Err(ErrorKind::InvalidMapFile(::std::format!(
+                "no format has the extension \"{}\"",
+                extension
+            ))
+            .into()),
+        }
+    }
+}
+
+/// The asset loader of the files of a map format.
+struct TilemapFormatLoader {
+    /// The format of the files.
+    format: Arc<dyn TilemapFormat>,
+}
+
+impl AssetLoader for TilemapFormatLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let prototype = self.format.parse(bytes)?;
+            let atlas = prototype.atlas.clone().ok_or_else(|| {
+                ErrorKind::InvalidMapFile("the map has no sprite sheet".to_string())
+            })?;
+            let texture_path = match load_context.path().parent() {
+                Some(parent) => parent.join(&atlas.texture),
+                None => PathBuf::from(&atlas.texture),
+            };
+            let sprite_dimensions = atlas.sprite_dimensions.unwrap_or(prototype.tile_dimensions);
+            let texture = load_context.get_handle(AssetPath::new_ref(&texture_path, None));
+            let texture_atlas = TextureAtlas::from_grid(
+                texture,
+                Vec2::new(
+                    sprite_dimensions.width as f32,
+                    sprite_dimensions.height as f32,
+                ),
+                atlas.columns,
+                atlas.rows,
+            );
+            load_context.set_labeled_asset(
+                TEXTURE_ATLAS_LABEL,
+                LoadedAsset::new(texture_atlas)
+                    .with_dependency(AssetPath::new(texture_path.clone(), None)),
+            );
+
+            let texture_atlas = load_context.get_handle(AssetPath::new_ref(
+                load_context.path(),
+                Some(TEXTURE_ATLAS_LABEL),
+            ));
+            let tilemap = prototype.into_tilemap(texture_atlas)?;
+            load_context.set_default_asset(LoadedAsset::new(tilemap.to_data()));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.format.extensions()
+    }
+}
+
+/// Adds map formats to an app.
+pub trait AddTilemapFormat {
+    /// Adds a map format, whose files are then loaded as `TilemapData` assets
+    /// by the `AssetServer`, to the [`TilemapFormats`].
+    fn add_tilemap_format<F: TilemapFormat>(&mut self, format: F) -> &mut Self;
+}
+
+impl AddTilemapFormat for AppBuilder {
+    fn add_tilemap_format<F: TilemapFormat>(&mut self, format: F) -> &mut Self {
+        let format: Arc<dyn TilemapFormat> = Arc::new(format);
+        if !self.resources().contains::<TilemapFormats>() {
+            self.resources_mut().insert(TilemapFormats::default());
+        }
+        if let Some(mut formats) = self.resources().get_mut::<TilemapFormats>() {
+            formats.formats.push(format.clone());
+        }
+        self.add_asset_loader(TilemapFormatLoader { format })
+    }
+}
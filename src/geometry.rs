@@ -0,0 +1,262 @@
+//! Conversions between tile points and translations for every grid topology.
+//!
+//! The renderer offsets every tile inside of the vertex shaders, these
+//! functions mirror the exact same math so that what is picked is what is
+//! seen. All translations are relative to the tilemap's transform.
+
+use crate::{chunk::render::GridTopology, lib::*};
+
+/// The square root of 3, used to normalize hex grids into regular hexagons.
+const SQRT_3: f32 = 1.732_050_8;
+
+/// How far around an estimated point to search for the real tile.
+const SEARCH_RADIUS: i32 = 2;
+
+/// Returns the rotation of a transform around the z axis in radians.
+pub(crate) fn z_rotation(transform: &GlobalTransform) -> f32 {
+    2.0 * transform.rotation.z.atan2(transform.rotation.w)
+}
+
+/// Returns the chunk point of a tile point.
+pub(crate) fn point_to_chunk_point(point: Point2, chunk_dimensions: Dimension2) -> Point2 {
+    let width = chunk_dimensions.width as f32;
+    let height = chunk_dimensions.height as f32;
+    Point2::new(
+        ((point.x as f32 + width / 2.0) / width).floor() as i32,
+        ((point.y as f32 + height / 2.0) / height).floor() as i32,
+    )
+}
+
+/// Returns the translation of a chunk for the topology.
+pub(crate) fn chunk_translation(
+    topology: GridTopology,
+    chunk_point: Point2,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+) -> Vec2 {
+    use GridTopology::*;
+    let chunk_width = chunk_dimensions.width as i32;
+    let chunk_height = chunk_dimensions.height as i32;
+    let tile_width = tile_dimensions.width as i32;
+    let tile_height = tile_dimensions.height as i32;
+    let x = match topology {
+        HexX | HexEvenCols | HexOddCols => {
+            (((chunk_point.x * tile_width) as f32 * 0.75) as i32 * chunk_width) as f32
+        }
+        HexY => {
+            (chunk_point.x * tile_width * chunk_width) as f32
+                + (chunk_point.y as f32 * chunk_height as f32 * 0.5) * tile_width as f32
+        }
+        Square | HexEvenRows | HexOddRows => (chunk_point.x * tile_width * chunk_width) as f32,
+        IsoDiamond => {
+            (chunk_point.x * chunk_width - chunk_point.y * chunk_height) as f32
+                * tile_width as f32
+                * 0.5
+        }
+    };
+    let y = match topology {
+        HexX => {
+            (chunk_point.y * tile_height * chunk_height) as f32
+                + (chunk_point.x as f32 * chunk_width as f32 * 0.5) * tile_height as f32
+        }
+        HexY | HexEvenRows | HexOddRows => {
+            (((chunk_point.y * tile_height) as f32 * 0.75) as i32 * chunk_height) as f32
+        }
+        Square | HexEvenCols | HexOddCols => (chunk_point.y * tile_height * chunk_height) as f32,
+        IsoDiamond => {
+            (chunk_point.x * chunk_width + chunk_point.y * chunk_height) as f32
+                * tile_height as f32
+                * 0.5
+        }
+    };
+    Vec2::new(x, y)
+}
+
+/// Returns the center of a tile exactly as it is rendered.
+pub(crate) fn tile_center(
+    topology: GridTopology,
+    point: Point2,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+) -> Vec2 {
+    use GridTopology::*;
+    let chunk_point = point_to_chunk_point(point, chunk_dimensions);
+    let chunk_width = chunk_dimensions.width as i32;
+    let chunk_height = chunk_dimensions.height as i32;
+    let tile_width = tile_dimensions.width as f32;
+    let tile_height = tile_dimensions.height as f32;
+
+    // The bottom left corner of the tile in the chunk mesh, see `ChunkMesh`.
+    let local_x = point.x - chunk_width * chunk_point.x + chunk_width / 2;
+    let local_y = point.y - chunk_height * chunk_point.y + chunk_height / 2;
+    let x0 = local_x as f32 - chunk_dimensions.width as f32 / 2.0;
+    let y0 = local_y as f32 - chunk_dimensions.height as f32 / 2.0;
+    let col = (x0 + 0.01).floor() as i32 + 1;
+    let row = (y0 + 0.01).floor() as i32 + 1;
+
+    let mut x = (x0 + 0.5) * tile_width;
+    let mut y = (y0 + 0.5) * tile_height;
+    match topology {
+        Square => {}
+        HexX => {
+            y += (0.5 * tile_height).floor() * col as f32;
+            x -= col as f32 * (0.25 * tile_width).ceil();
+        }
+        HexY => {
+            x += (0.5 * tile_width).floor() * row as f32;
+            y -= row as f32 * (0.25 * tile_height).ceil();
+        }
+        HexEvenCols | HexOddCols => {
+            let offset = (0.25 * tile_height).floor();
+            if (col.rem_euclid(2) == 0) == (topology == HexEvenCols) {
+                y -= offset;
+            } else {
+                y += offset;
+            }
+            x -= col as f32 * (0.25 * tile_width).ceil();
+        }
+        HexEvenRows | HexOddRows => {
+            let offset = (0.25 * tile_width).floor();
+            if (row.rem_euclid(2) == 0) == (topology == HexEvenRows) {
+                x -= offset;
+            } else {
+                x += offset;
+            }
+            y -= row as f32 * (0.25 * tile_height).ceil();
+        }
+        IsoDiamond => {
+            let cell_x = x0 + 0.5;
+            let cell_y = y0 + 0.5;
+            x = (cell_x - cell_y) * 0.5 * tile_width;
+            y = (cell_x + cell_y) * 0.5 * tile_height;
+        }
+    }
+
+    chunk_translation(topology, chunk_point, chunk_dimensions, tile_dimensions) + Vec2::new(x, y)
+}
+
+/// Returns the corners of the shape of a tile around its center, in order
+/// around it: rects for squares, diamonds for iso tiles and regular hexagons
+/// for hexes.
+#[cfg(feature = "render")]
+pub(crate) fn tile_outline(
+    topology: GridTopology,
+    center: Vec2,
+    tile_dimensions: Dimension2,
+) -> Vec<Vec2> {
+    use GridTopology::*;
+    let half_width = tile_dimensions.width as f32 / 2.0;
+    let half_height = tile_dimensions.height as f32 / 2.0;
+    let corners = match topology {
+        Square => vec![
+            (-half_width, -half_height),
+            (half_width, -half_height),
+            (half_width, half_height),
+            (-half_width, half_height),
+        ],
+        IsoDiamond => vec![
+            (0.0, -half_height),
+            (half_width, 0.0),
+            (0.0, half_height),
+            (-half_width, 0.0),
+        ],
+        HexY | HexEvenRows | HexOddRows => vec![
+            (0.0, -half_height),
+            (half_width, -half_height / 2.0),
+            (half_width, half_height / 2.0),
+            (0.0, half_height),
+            (-half_width, half_height / 2.0),
+            (-half_width, -half_height / 2.0),
+        ],
+        HexX | HexEvenCols | HexOddCols => vec![
+            (-half_width, 0.0),
+            (-half_width / 2.0, -half_height),
+            (half_width / 2.0, -half_height),
+            (half_width, 0.0),
+            (half_width / 2.0, half_height),
+            (-half_width / 2.0, half_height),
+        ],
+    };
+    corners
+        .into_iter()
+        .map(|(x, y)| center + Vec2::new(x, y))
+        .collect()
+}
+
+/// Returns the tile point which contains the translation.
+///
+/// An estimate is made first from the regular lattice of the topology, then
+/// the neighbourhood is searched for the tile whose shape contains the
+/// translation. Translations that lie exactly on a shared edge or corner
+/// always resolve to the same tile, the one with the greatest point.
+pub(crate) fn translation_to_point(
+    topology: GridTopology,
+    translation: Vec2,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+) -> Point2 {
+    use GridTopology::*;
+    let tile_width = tile_dimensions.width as f32;
+    let tile_height = tile_dimensions.height as f32;
+    let (x, y) = (translation.x, translation.y);
+    let (estimate_x, estimate_y) = match topology {
+        Square => (x / tile_width, y / tile_height),
+        HexY => {
+            let estimate_y = y / (tile_height * 0.75);
+            (x / tile_width - estimate_y * 0.5, estimate_y)
+        }
+        HexX => {
+            let estimate_x = x / (tile_width * 0.75);
+            (estimate_x, y / tile_height - estimate_x * 0.5)
+        }
+        HexEvenRows | HexOddRows => (x / tile_width, y / (tile_height * 0.75)),
+        HexEvenCols | HexOddCols => (x / (tile_width * 0.75), y / tile_height),
+        IsoDiamond => (
+            x / tile_width + y / tile_height,
+            y / tile_height - x / tile_width,
+        ),
+    };
+    let estimate = Point2::new(estimate_x.round() as i32, estimate_y.round() as i32);
+
+    let mut closest = estimate;
+    let mut closest_distance = f32::INFINITY;
+    for offset_y in -SEARCH_RADIUS..=SEARCH_RADIUS {
+        for offset_x in -SEARCH_RADIUS..=SEARCH_RADIUS {
+            let point = Point2::new(estimate.x + offset_x, estimate.y + offset_y);
+            let center = tile_center(topology, point, chunk_dimensions, tile_dimensions);
+            let distance = distance(topology, translation - center, tile_dimensions);
+            let is_closer = distance < closest_distance
+                || (distance == closest_distance && (point.x, point.y) > (closest.x, closest.y));
+            if is_closer {
+                closest = point;
+                closest_distance = distance;
+            }
+        }
+    }
+    closest
+}
+
+/// Distance from the center of a tile in units where the tile is regular.
+///
+/// The nearest center with this distance is the tile which contains a
+/// translation: squares and diamonds use the Chebyshev distance and hexes use
+/// the euclidean distance of regular hexagons.
+fn distance(topology: GridTopology, delta: Vec2, tile_dimensions: Dimension2) -> f32 {
+    use GridTopology::*;
+    let x = delta.x / tile_dimensions.width as f32;
+    let y = delta.y / tile_dimensions.height as f32;
+    match topology {
+        Square => x.abs().max(y.abs()),
+        IsoDiamond => (x + y).abs().max((y - x).abs()),
+        HexY | HexEvenRows | HexOddRows => {
+            let x = x * SQRT_3;
+            let y = y * 2.0;
+            (x * x + y * y).sqrt()
+        }
+        HexX | HexEvenCols | HexOddCols => {
+            let x = x * 2.0;
+            let y = y * SQRT_3;
+            (x * x + y * y).sqrt()
+        }
+    }
+}
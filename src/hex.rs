@@ -0,0 +1,248 @@
+//! Utilities for working with hex grids.
+//!
+//! Most of these work with axial coordinates, where the `x` of a point is the
+//! column and `y` is the row along a slanted axis. The points of a
+//! [`GridTopology::HexX`] or [`GridTopology::HexY`] tilemap already are axial
+//! coordinates. The other hex topologies use offset coordinates which can be
+//! converted with [`offset_to_axial`] and back with [`axial_to_offset`].
+//!
+//! [`GridTopology::HexX`]: crate::chunk::render::GridTopology::HexX
+//! [`GridTopology::HexY`]: crate::chunk::render::GridTopology::HexY
+//!
+//! # Finding the tiles in range of a unit
+//! ```
+//! use bevy_tilemap::{hex, prelude::*};
+//!
+//! let topology = GridTopology::HexOddRows;
+//! let unit = hex::offset_to_axial((3, 3), topology);
+//!
+//! let in_range: Vec<_> = hex::hex_range(unit, 2)
+//!     .into_iter()
+//!     .map(|point| hex::axial_to_offset(point, topology))
+//!     .collect();
+//!
+//! assert_eq!(in_range.len(), 19);
+//! assert!(in_range.contains(&(3, 3).into()));
+//! ```
+
+use crate::{chunk::render::GridTopology, lib::*};
+
+/// The axial directions to each neighbour, counter clockwise starting from
+/// the positive x axis.
+const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+/// Returns the distance in steps between two axial points.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::hex;
+///
+/// assert_eq!(hex::hex_distance((0, 0), (0, 0)), 0);
+/// assert_eq!(hex::hex_distance((0, 0), (2, -1)), 2);
+/// assert_eq!(hex::hex_distance((-1, 3), (2, -1)), 4);
+/// ```
+pub fn hex_distance<P: Into<Point2>>(from: P, to: P) -> u32 {
+    let from: Point2 = from.into();
+    let to: Point2 = to.into();
+    let x = to.x - from.x;
+    let y = to.y - from.y;
+    let z = -x - y;
+    x.abs().max(y.abs()).max(z.abs()) as u32
+}
+
+/// Returns the 6 neighbours of an axial point, counter clockwise starting
+/// from the positive x axis.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::hex;
+///
+/// let neighbours = hex::hex_neighbours((2, 2));
+///
+/// assert_eq!(neighbours[0], (3, 2).into());
+/// assert!(neighbours.iter().all(|n| hex::hex_distance(*n, (2, 2).into()) == 1));
+/// ```
+pub fn hex_neighbours<P: Into<Point2>>(point: P) -> [Point2; 6] {
+    let point: Point2 = point.into();
+    let mut neighbours = [point; 6];
+    for (neighbour, (x, y)) in neighbours.iter_mut().zip(DIRECTIONS.iter()) {
+        *neighbour = Point2::new(point.x + x, point.y + y);
+    }
+    neighbours
+}
+
+/// Returns all the axial points which are exactly `radius` steps away from
+/// the center.
+///
+/// The ring is walked counter clockwise. A radius of 0 returns only the
+/// center.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::hex;
+///
+/// assert_eq!(hex::hex_ring((0, 0), 0), vec![(0, 0).into()]);
+///
+/// let ring = hex::hex_ring((1, 1), 3);
+/// assert_eq!(ring.len(), 18);
+/// assert!(ring.iter().all(|p| hex::hex_distance(*p, (1, 1).into()) == 3));
+/// ```
+pub fn hex_ring<P: Into<Point2>>(center: P, radius: u32) -> Vec<Point2> {
+    let center: Point2 = center.into();
+    if radius == 0 {
+        return vec![center];
+    }
+    let radius = radius as i32;
+    let (start_x, start_y) = DIRECTIONS[4];
+    let mut point = Point2::new(center.x + start_x * radius, center.y + start_y * radius);
+    let mut ring = Vec::with_capacity(6 * radius as usize);
+    for (x, y) in DIRECTIONS.iter() {
+        for _ in 0..radius {
+            ring.push(point);
+            point = Point2::new(point.x + x, point.y + y);
+        }
+    }
+    ring
+}
+
+/// Returns all the axial points which are `radius` steps or less away from
+/// the center, including the center.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::hex;
+///
+/// let range = hex::hex_range((0, 0), 1);
+/// assert_eq!(range.len(), 7);
+///
+/// let range = hex::hex_range((4, -2), 3);
+/// assert_eq!(range.len(), 37);
+/// assert!(range.iter().all(|p| hex::hex_distance(*p, (4, -2).into()) <= 3));
+/// ```
+pub fn hex_range<P: Into<Point2>>(center: P, radius: u32) -> Vec<Point2> {
+    let center: Point2 = center.into();
+    let radius = radius as i32;
+    let mut range = Vec::with_capacity((3 * radius * (radius + 1) + 1) as usize);
+    for x in -radius..=radius {
+        let min_y = (-radius).max(-x - radius);
+        let max_y = radius.min(-x + radius);
+        for y in min_y..=max_y {
+            range.push(Point2::new(center.x + x, center.y + y));
+        }
+    }
+    range
+}
+
+/// Rotates an axial point around a center in steps of 60 degrees.
+///
+/// Positive steps are counter clockwise and negative steps are clockwise.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::hex;
+///
+/// assert_eq!(hex::hex_rotate((1, 0), (0, 0), 1), (0, 1).into());
+/// assert_eq!(hex::hex_rotate((1, 0), (0, 0), -1), (1, -1).into());
+/// assert_eq!(hex::hex_rotate((3, 2), (1, 1), 6), (3, 2).into());
+/// ```
+pub fn hex_rotate<P: Into<Point2>>(point: P, center: P, steps: i32) -> Point2 {
+    let point: Point2 = point.into();
+    let center: Point2 = center.into();
+    let mut x = point.x - center.x;
+    let mut y = point.y - center.y;
+    for _ in 0..steps.rem_euclid(6) {
+        let z = -x - y;
+        x = -y;
+        y = -z;
+    }
+    Point2::new(center.x + x, center.y + y)
+}
+
+/// Rounds fractional axial coordinates to the axial point of the hex that
+/// contains them.
+///
+/// # Examples
+/// ```
+/// use bevy_math::Vec2;
+/// use bevy_tilemap::hex;
+///
+/// assert_eq!(hex::hex_round(Vec2::new(0.2, 0.1)), (0, 0).into());
+/// assert_eq!(hex::hex_round(Vec2::new(0.6, 0.1)), (1, 0).into());
+/// assert_eq!(hex::hex_round(Vec2::new(-0.6, 0.9)), (-1, 1).into());
+/// ```
+pub fn hex_round(axial: Vec2) -> Point2 {
+    let z = -axial.x - axial.y;
+    let mut x = axial.x.round();
+    let mut y = axial.y.round();
+    let rounded_z = z.round();
+    let x_diff = (x - axial.x).abs();
+    let y_diff = (y - axial.y).abs();
+    let z_diff = (rounded_z - z).abs();
+    if x_diff > y_diff && x_diff > z_diff {
+        x = -y - rounded_z;
+    } else if y_diff > z_diff {
+        y = -x - rounded_z;
+    } else {
+        // Only x and y make up the axial point, so z does not need fixing.
+    }
+    Point2::new(x as i32, y as i32)
+}
+
+/// Converts the point of a tile in a tilemap with the topology to an axial
+/// point.
+///
+/// [`GridTopology::HexEvenRows`], [`GridTopology::HexOddRows`],
+/// [`GridTopology::HexEvenCols`] and [`GridTopology::HexOddCols`] are
+/// converted from offset coordinates. Points of every other topology are
+/// returned as they are.
+///
+/// [`GridTopology::HexEvenRows`]: crate::chunk::render::GridTopology::HexEvenRows
+/// [`GridTopology::HexOddRows`]: crate::chunk::render::GridTopology::HexOddRows
+/// [`GridTopology::HexEvenCols`]: crate::chunk::render::GridTopology::HexEvenCols
+/// [`GridTopology::HexOddCols`]: crate::chunk::render::GridTopology::HexOddCols
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::{hex, prelude::*};
+///
+/// assert_eq!(hex::offset_to_axial((2, 3), GridTopology::HexOddRows), (1, 3).into());
+/// assert_eq!(hex::offset_to_axial((2, 3), GridTopology::HexEvenCols), (2, 2).into());
+/// assert_eq!(hex::offset_to_axial((2, 3), GridTopology::HexY), (2, 3).into());
+/// ```
+pub fn offset_to_axial<P: Into<Point2>>(point: P, topology: GridTopology) -> Point2 {
+    use GridTopology::*;
+    let point: Point2 = point.into();
+    match topology {
+        HexEvenRows => Point2::new(point.x - (point.y + (point.y & 1)) / 2, point.y),
+        HexOddRows => Point2::new(point.x - (point.y - (point.y & 1)) / 2, point.y),
+        HexEvenCols => Point2::new(point.x, point.y - (point.x + (point.x & 1)) / 2),
+        HexOddCols => Point2::new(point.x, point.y - (point.x - (point.x & 1)) / 2),
+        Square | HexX | HexY | IsoDiamond => point,
+    }
+}
+
+/// Converts an axial point to the point of a tile in a tilemap with the
+/// topology.
+///
+/// This is the inverse of [`offset_to_axial`].
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::{hex, prelude::*};
+///
+/// let topology = GridTopology::HexEvenRows;
+/// let axial = hex::offset_to_axial((-3, -5), topology);
+///
+/// assert_eq!(hex::axial_to_offset(axial, topology), (-3, -5).into());
+/// ```
+pub fn axial_to_offset<P: Into<Point2>>(point: P, topology: GridTopology) -> Point2 {
+    use GridTopology::*;
+    let point: Point2 = point.into();
+    match topology {
+        HexEvenRows => Point2::new(point.x + (point.y + (point.y & 1)) / 2, point.y),
+        HexOddRows => Point2::new(point.x + (point.y - (point.y & 1)) / 2, point.y),
+        HexEvenCols => Point2::new(point.x, point.y + (point.x + (point.x & 1)) / 2),
+        HexOddCols => Point2::new(point.x, point.y + (point.x - (point.x & 1)) / 2),
+        Square | HexX | HexY | IsoDiamond => point,
+    }
+}
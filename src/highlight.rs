@@ -0,0 +1,81 @@
+//! Highlighting tiles, such as movement ranges and build previews.
+//!
+//! Strategy games highlight tiles all the time: the tiles a unit can move to,
+//! the tiles a building would cover or the tile under the cursor. A tilemap
+//! draws its highlights in a dedicated sparse layer which is added with
+//! [`Tilemap::add_highlight_layer`], as a tinted [`HighlightLayer`] sprite over
+//! every highlighted tile.
+//!
+//! Highlights last only as long as they are asked for.
+//! [`Tilemap::highlight_tiles`] is called every frame with the tiles to
+//! highlight, and the tiles which were not highlighted again are cleared in
+//! the tilemap stage of the next frame. Only the tiles which change are
+//! written, so only the chunks where the highlights moved are built again.
+//!
+//! [`Tilemap::add_highlight_layer`]: crate::tilemap::Tilemap::add_highlight_layer
+//! [`Tilemap::highlight_tiles`]: crate::tilemap::Tilemap::highlight_tiles
+//!
+//! # A movement range
+//! ```
+//! use bevy_render::color::Color;
+//! use bevy_tilemap::{highlight::HighlightLayer, prelude::*, testing::tilemap_test_harness};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Sprite 3 is a white square which is tinted by the highlights.
+//! tilemap.add_highlight_layer(HighlightLayer::new(1, 3)).unwrap();
+//! tilemap.highlight_tiles(vec![(0, 0), (1, 0), (0, 1)], Color::rgba(0.2, 0.4, 1.0, 0.5)).unwrap();
+//! tilemap.highlight_tiles(vec![(1, 1)], Color::rgba(1.0, 0.2, 0.2, 0.5)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! assert_eq!(harness.tilemap(entity).unwrap().get_tile((1, 1), 1).unwrap().index, 3);
+//!
+//! // Nothing is highlighted on the next frame, so the highlights are cleared.
+//! harness.tick();
+//!
+//! let tilemap = harness.tilemap(entity).unwrap();
+//! assert!(tilemap.get_tile((1, 1), 1).is_none());
+//! assert_eq!(tilemap.highlighted_tiles().count(), 0);
+//! ```
+
+use crate::lib::*;
+
+/// The sparse layer which tiles are highlighted in.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HighlightLayer {
+    /// The z order of the layer.
+    pub z_order: usize,
+    /// The sprite index of the highlights, which is tinted by their colors.
+    pub sprite_index: usize,
+}
+
+impl HighlightLayer {
+    /// Constructs a new highlight layer at the z order, whose highlights are
+    /// drawn with the sprite index.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::highlight::HighlightLayer;
+    ///
+    /// let layer = HighlightLayer::new(2, 7);
+    ///
+    /// assert_eq!(layer.z_order, 2);
+    /// assert_eq!(layer.sprite_index, 7);
+    /// ```
+    pub fn new(z_order: usize, sprite_index: usize) -> HighlightLayer {
+        HighlightLayer {
+            z_order,
+            sprite_index,
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! Rendering the tiles of a tilemap at several places.
+//!
+//! The tiles of a [`Tilemap`] can be added to `Assets<TilemapData>` as a
+//! [`TilemapData`], which is taken with [`Tilemap::to_data`]. Every entity
+//! with a [`TilemapInstanceBundle`] that holds a handle to it then renders
+//! all of its chunks at its own transform. The tiles and the chunk meshes are
+//! stored only once, which makes minimaps and split screens of the same map
+//! cheap. The data shares its chunks with the tilemap it was taken from,
+//! which copies a chunk only the first time it changes after.
+//!
+//! The data has none of the state of a spawned tilemap, such as its spawned
+//! chunks, events or entities, so instances do not depend on each other or
+//! on the tilemap. Edits are made to the asset, for example with
+//! `Assets::get_mut` and [`TilemapData::insert_tile`], or by taking the data
+//! of the tilemap again, and the meshes of the chunks which changed are
+//! rebuilt for every instance. When the asset is replaced, such as when a map
+//! file is loaded again, the instances spawn its chunks again. Collisions are
+//! not spawned for instances.
+//!
+//! [`Tilemap`]: crate::tilemap::Tilemap
+//! [`Tilemap::to_data`]: crate::tilemap::Tilemap::to_data
+//! [`TilemapInstanceBundle`]: crate::entity::TilemapInstanceBundle
+//!
+//! # Spawning two instances of a tilemap
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_ecs::prelude::*;
+//! use bevy_math::Vec3;
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{entity::TilemapInstanceBundle, instance::TilemapData, prelude::*};
+//! use bevy_transform::prelude::*;
+//!
+//! fn setup(commands: &mut Commands, mut tilemaps: ResMut<Assets<TilemapData>>) {
+//!     // In production use a strong handle from an actual source.
+//!     let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//!     let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+//!     tilemap.insert_chunk((0, 0)).unwrap();
+//!     tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+//!     let tilemap_handle = tilemaps.add(tilemap.to_data());
+//!
+//!     commands
+//!         .spawn(TilemapInstanceBundle {
+//!             tilemap: tilemap_handle.clone(),
+//!             ..Default::default()
+//!         })
+//!         .spawn(TilemapInstanceBundle {
+//!             tilemap: tilemap_handle,
+//!             transform: Transform::from_scale(Vec3::splat(0.25)),
+//!             ..Default::default()
+//!         });
+//! }
+//! ```
+//!
+//! # Editing the data of two instances
+//! ```
+//! use bevy_app::App;
+//! use bevy_asset::prelude::*;
+//! use bevy_ecs::Entity;
+//! use bevy_render::prelude::*;
+//! use bevy_tilemap::{entity::TilemapInstanceBundle, prelude::*, testing::tilemap_test_harness};
+//! use bevy_transform::prelude::*;
+//!
+//! fn chunk_meshes(app: &App, instance: Entity) -> Vec<Handle<Mesh>> {
+//!     let children = app.world.get::<Children>(instance).unwrap();
+//!     children
+//!         .iter()
+//!         .map(|child| app.world.get::<Handle<Mesh>>(*child).unwrap().clone())
+//!         .collect()
+//! }
+//!
+//! let mut harness = tilemap_test_harness();
+//! let mut tilemap = Tilemap::new(harness.texture_atlas().clone(), 32, 32);
+//! tilemap.insert_chunk((0, 0)).unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+//!
+//! let app = harness.app();
+//! let handle = app.resources.get_mut::<Assets<TilemapData>>().unwrap().add(tilemap.to_data());
+//! let map = app.world.spawn(TilemapInstanceBundle { tilemap: handle.clone(), ..Default::default() });
+//! let minimap = app.world.spawn(TilemapInstanceBundle { tilemap: handle.clone(), ..Default::default() });
+//! harness.tick();
+//!
+//! // Both instances draw the one mesh of the chunk.
+//! let meshes = chunk_meshes(harness.app(), map);
+//! assert_eq!(meshes.len(), 1);
+//! assert_eq!(chunk_meshes(harness.app(), minimap), meshes);
+//!
+//! let app = harness.app();
+//! let mut tilemaps = app.resources.get_mut::<Assets<TilemapData>>().unwrap();
+//! let data = tilemaps.get_mut(&handle).unwrap();
+//! data.insert_tile(Tile { point: (1, 1), sprite_index: 2, ..Default::default() }).unwrap();
+//! data.insert_tile(Tile { point: (100, 100), ..Default::default() }).unwrap();
+//! drop(tilemaps);
+//! harness.tick();
+//!
+//! // The mesh of the changed chunk is updated in place and the new chunk is
+//! // drawn by both instances.
+//! let map_meshes = chunk_meshes(harness.app(), map);
+//! assert_eq!(map_meshes.len(), 2);
+//! assert!(map_meshes.contains(&meshes[0]));
+//! assert_eq!(chunk_meshes(harness.app(), minimap).len(), 2);
+//! ```
+
+use crate::{
+    animation::ShaderAnimation,
+    channel::TileChannel,
+    chunk::{raw_tile::RawTile, render::GridTopology, Chunk, LayerKind, TileOcclusion},
+    geometry,
+    lib::*,
+    palette::ChunkPalette,
+    render_layers::RenderLayers,
+    sprite::SpriteRect,
+    tilemap::{chunk_point_of, ErrorKind, TilemapResult},
+    wall::WallSprite,
+    Tile, TilemapLayer,
+};
+
+/// The tiles of a tilemap and how they are drawn, without any of the state
+/// of a spawned tilemap, as an asset which instances render.
+///
+/// See the [`instance`] module for more information.
+///
+/// [`instance`]: crate::instance
+#[derive(Clone, Debug)]
+pub struct TilemapData {
+    /// The name of the tilemap.
+    pub(crate) name: Option<String>,
+    /// The topology of the tilemap.
+    pub(crate) topology: GridTopology,
+    /// The dimensions of the chunks in tiles.
+    pub(crate) chunk_dimensions: Dimension2,
+    /// The dimensions of the tiles in pixels.
+    pub(crate) tile_dimensions: Dimension2,
+    /// The chunk point which is drawn at the center of an instance.
+    pub(crate) translation_origin: Point2,
+    /// The handle of the texture atlas.
+    pub(crate) texture_atlas: Handle<TextureAtlas>,
+    /// The layers of the tilemap at their z order.
+    pub(crate) layers: Vec<Option<TilemapLayer>>,
+    /// The chunks, which may be shared with the tilemap.
+    pub(crate) chunks: HashMap<Point2, Arc<Chunk>>,
+    /// The color which every tile is multiplied by.
+    pub(crate) color: Color,
+    /// The opacity of every tile.
+    pub(crate) opacity: f32,
+    /// The z translation which the depths of the layers are added to.
+    pub(crate) render_order: f32,
+    /// The render layers of the layers which have none of their own.
+    pub(crate) render_layers: RenderLayers,
+    /// The pipeline which the chunks are drawn with.
+    pub(crate) pipeline: Handle<PipelineDescriptor>,
+    /// The palettes which the chunks are drawn with.
+    pub(crate) palette: ChunkPalette,
+    /// The normal map which the chunks are lit with.
+    #[cfg(feature = "lighting")]
+    pub(crate) normal_map: Option<Handle<Texture>>,
+    /// The sprite rects of the sprite indexes.
+    pub(crate) sprite_rects: HashMap<usize, SpriteRect>,
+    /// The walls of the sprite indexes which have one.
+    pub(crate) wall_sprites: HashMap<usize, WallSprite>,
+    /// The channels of the layers.
+    pub(crate) channels: HashMap<usize, Vec<TileChannel>>,
+    /// The layers above every layer whose opaque tiles hide its tiles, by z
+    /// order.
+    pub(crate) occlusions: Vec<Option<TileOcclusion>>,
+}
+
+impl TypeUuid for TilemapData {
+    const TYPE_UUID: Uuid = Uuid::from_u128(170520854237151199000156106980081190710);
+}
+
+impl Display for TilemapData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(name) = &self.name {
+            write!(f, "tilemap data \"{}\"", name)
+        } else {
+            write!(f, "unnamed tilemap data")
+        }
+    }
+}
+
+impl TilemapData {
+    /// Returns the chunk point and the point in the chunk of a tile point.
+    fn chunk_tile_point(&self, point: Point2) -> (Point2, Point2) {
+        let chunk_point = chunk_point_of(self.chunk_dimensions, point);
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+        let tile_point = Point2::new(
+            point.x - (width * chunk_point.x) + (width / 2),
+            point.y - (height * chunk_point.y) + (height / 2),
+        );
+        (chunk_point, tile_point)
+    }
+
+    /// Sets a tile, making its chunk if it does not exist yet.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 1, ..Default::default() }).unwrap();
+    ///
+    /// let mut data = tilemap.to_data();
+    /// data.insert_tile(Tile { point: (1, 1), sprite_index: 2, ..Default::default() }).unwrap();
+    /// data.insert_tile(Tile { point: (100, 100), sprite_index: 3, ..Default::default() }).unwrap();
+    ///
+    /// // The tilemap keeps its own tiles.
+    /// assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 1);
+    /// assert_eq!(data.get_tile((1, 1), 0).unwrap().index, 2);
+    /// assert_eq!(data.get_tile((100, 100), 0).unwrap().index, 3);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the layer of the tile does not exist, an error is returned.
+    pub fn insert_tile<P: Into<Point2>>(&mut self, tile: Tile<P>) -> TilemapResult<()> {
+        self.insert_tiles(vec![tile])
+    }
+
+    /// Sets many tiles, making their chunks if they do not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// If the layer of a tile does not exist, an error is returned and none of
+    /// the tiles after it are set.
+    pub fn insert_tiles<P, I>(&mut self, tiles: I) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = Tile<P>>,
+    {
+        let layer_kinds: Vec<Option<LayerKind>> = self
+            .layers
+            .iter()
+            .map(|layer| layer.as_ref().map(|layer| layer.kind))
+            .collect();
+        for tile in tiles.into_iter() {
+            if self
+                .layers
+                .get(tile.z_order)
+                .and_then(Option::as_ref)
+                .is_none()
+            {
+                return Err(ErrorKind::LayerDoesNotExist(tile.z_order).into());
+            }
+            let point: Point2 = tile.point.into();
+            let (chunk_point, tile_point) = self.chunk_tile_point(point);
+            let chunk_dimensions = self.chunk_dimensions;
+            let layers = &self.layers;
+            let chunk = Arc::make_mut(self.chunks.entry(chunk_point).or_insert_with(|| {
+                let mut chunk = Chunk::new(chunk_point, &layer_kinds, chunk_dimensions);
+                let area = chunk_dimensions.area() as usize;
+                for (z_order, layer) in layers.iter().enumerate() {
+                    if let Some(empty_tile) = layer
+                        .as_ref()
+                        .filter(|layer| layer.kind == LayerKind::Dense)
+                        .and_then(|layer| layer.empty_tile)
+                    {
+                        chunk.fill_empty_tiles(z_order, empty_tile, area);
+                    }
+                }
+                Arc::new(chunk)
+            }));
+            let index = chunk_dimensions.encode_point_unchecked(tile_point);
+            chunk.set_tile(
+                index,
+                Tile {
+                    point: tile_point,
+                    sprite_index: tile.sprite_index,
+                    z_order: tile.z_order,
+                    tint: tile.tint,
+                    extra: tile.extra,
+                    offset: tile.offset,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Clears the tile at a point and z order, if there is one.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 1, ..Default::default() }).unwrap();
+    ///
+    /// let mut data = tilemap.to_data();
+    /// data.clear_tile((1, 1), 0);
+    ///
+    /// assert_eq!(data.get_tile((1, 1), 0), None);
+    /// assert!(tilemap.get_tile((1, 1), 0).is_some());
+    /// ```
+    pub fn clear_tile<P: Into<Point2>>(&mut self, point: P, z_order: usize) {
+        let (chunk_point, tile_point) = self.chunk_tile_point(point.into());
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let has_tile = self
+            .chunks
+            .get(&chunk_point)
+            .is_some_and(|chunk| chunk.get_tile(z_order, index).is_some());
+        if !has_tile {
+            return;
+        }
+        if let Some(chunk) = self.chunks.get_mut(&chunk_point) {
+            Arc::make_mut(chunk).remove_tile(index, z_order);
+        }
+    }
+
+    /// Returns the tile at a point and z order, if there is one.
+    pub fn get_tile<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<&RawTile> {
+        let (chunk_point, tile_point) = self.chunk_tile_point(point.into());
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        self.chunks.get(&chunk_point)?.get_tile(z_order, index)
+    }
+
+    /// Returns `true` if the data has the chunk.
+    pub fn contains_chunk<P: Into<Point2>>(&self, point: P) -> bool {
+        self.chunks.contains_key(&point.into())
+    }
+
+    /// Returns the topology of the tilemap.
+    pub fn topology(&self) -> GridTopology {
+        self.topology
+    }
+
+    /// Returns the handle of the texture atlas.
+    pub fn texture_atlas(&self) -> &Handle<TextureAtlas> {
+        &self.texture_atlas
+    }
+
+    /// Returns the layers of the tilemap at their z order.
+    pub(crate) fn layers(&self) -> &[Option<TilemapLayer>] {
+        &self.layers
+    }
+
+    /// Returns the render order of the tilemap.
+    pub(crate) fn render_order(&self) -> f32 {
+        self.render_order
+    }
+
+    /// Returns the z translation of a layer, relative to the render order.
+    pub(crate) fn layer_depth(&self, z_order: usize) -> f32 {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.depth.unwrap_or(z_order as f32),
+            _ => z_order as f32,
+        }
+    }
+
+    /// Returns `true` if a layer is visible.
+    pub(crate) fn is_layer_visible(&self, z_order: usize) -> bool {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => !layer.hidden,
+            _ => true,
+        }
+    }
+
+    /// Returns the color of the tilemap times the color of a layer, with the
+    /// opacity of the tilemap.
+    pub(crate) fn layer_tint(&self, z_order: usize) -> Vec4 {
+        let layer_color = match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.color,
+            _ => Color::WHITE,
+        };
+        Vec4::from(self.color) * Vec4::from(layer_color) * Vec4::new(1.0, 1.0, 1.0, self.opacity)
+    }
+
+    /// Returns the shader animation of a layer, if it has one.
+    pub(crate) fn layer_shader_animation(&self, z_order: usize) -> Option<ShaderAnimation> {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.shader_animation,
+            _ => None,
+        }
+    }
+
+    /// Returns the render layers of a layer.
+    pub(crate) fn layer_render_layers(&self, z_order: usize) -> RenderLayers {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.render_layers.unwrap_or(self.render_layers),
+            _ => self.render_layers,
+        }
+    }
+
+    /// Returns the pipeline which the chunks are drawn with.
+    pub(crate) fn chunk_pipeline_handle(&self) -> Handle<PipelineDescriptor> {
+        self.pipeline.clone_weak()
+    }
+
+    /// Returns the palettes which the chunks are drawn with.
+    pub(crate) fn palette(&self) -> &ChunkPalette {
+        &self.palette
+    }
+
+    /// Returns the normal map which the chunks are lit with, if any.
+    #[cfg(feature = "lighting")]
+    pub(crate) fn normal_map(&self) -> Option<&Handle<Texture>> {
+        self.normal_map.as_ref()
+    }
+
+    /// Returns the translation of a chunk in an instance.
+    pub(crate) fn chunk_translation(&self, chunk_point: Point2) -> Vec2 {
+        geometry::chunk_translation(
+            self.topology,
+            chunk_point - self.translation_origin,
+            self.chunk_dimensions,
+            self.tile_dimensions,
+        )
+    }
+}
+
+/// The chunk meshes of a `TilemapData` asset, which all of its instances
+/// share.
+#[derive(Default)]
+pub(crate) struct DataMeshes {
+    /// The chunk which the meshes were built from and the meshes of its
+    /// layers by z order, at the chunk point.
+    chunks: HashMap<Point2, (Weak<Chunk>, Vec<Option<Handle<Mesh>>>)>,
+}
+
+impl DataMeshes {
+    /// Builds the meshes of the chunks which were added or changed since they
+    /// were last built and drops those of the chunks which were removed.
+    ///
+    /// Chunks are copied when they are changed while they are shared, and
+    /// moved when the meshes are the only other thing which knows of them, so
+    /// a chunk changed if it is no longer the one the meshes were built from.
+    pub(crate) fn update(&mut self, meshes: &mut Assets<Mesh>, data: &TilemapData) {
+        self.chunks
+            .retain(|point, _| data.chunks.contains_key(point));
+        for (point, chunk) in data.chunks.iter() {
+            let (built_from, layer_meshes) = self
+                .chunks
+                .entry(*point)
+                .or_insert_with(|| (Weak::new(), Vec::new()));
+            if built_from.as_ptr() == Arc::as_ptr(chunk) {
+                continue;
+            }
+            *built_from = Arc::downgrade(chunk);
+            layer_meshes.resize(data.layers.len(), None);
+            for (z_order, mesh) in layer_meshes.iter_mut().enumerate() {
+                let layer = data.layers.get(z_order).and_then(Option::as_ref);
+                *mesh = crate::system::update_layer_mesh(
+                    meshes,
+                    chunk,
+                    mesh.take(),
+                    z_order,
+                    data.chunk_dimensions,
+                    data.tile_dimensions,
+                    data.topology,
+                    layer.is_some_and(TilemapLayer::is_y_sorted),
+                    crate::system::layer_walls(
+                        &data.layers,
+                        z_order,
+                        data.topology,
+                        &data.wall_sprites,
+                    ),
+                    data.occlusions.get(z_order).and_then(Option::as_ref),
+                    &data.sprite_rects,
+                    &data.channels,
+                );
+            }
+        }
+    }
+
+    /// Returns the meshes of the layers of a chunk by z order, if it has been
+    /// built.
+    pub(crate) fn get(&self, point: &Point2) -> Option<&[Option<Handle<Mesh>>]> {
+        self.chunks.get(point).map(|(_, meshes)| &meshes[..])
+    }
+
+    /// Returns an iterator of the points of the built chunks and the meshes of
+    /// their layers by z order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Point2, &[Option<Handle<Mesh>>])> + '_ {
+        self.chunks
+            .iter()
+            .map(|(point, (_, meshes))| (point, &meshes[..]))
+    }
+}
+
+/// A component which keeps track of the chunks an instance has spawned.
+#[derive(Clone, Default, Debug)]
+pub struct TilemapInstance {
+    /// The entities of the spawned chunk layers, at the chunk point.
+    chunks: HashMap<Point2, Vec<Entity>>,
+    /// The meshes that the spawned chunk layers share, at the chunk point.
+    meshes: HashMap<Point2, Vec<Handle<Mesh>>>,
+}
+
+impl TilemapInstance {
+    /// Returns `true` if the instance has spawned the chunk.
+    pub(crate) fn contains_chunk(&self, point: &Point2) -> bool {
+        self.chunks.contains_key(point)
+    }
+
+    /// Adds the entities of a spawned chunk and the meshes they share.
+    pub(crate) fn insert_chunk(
+        &mut self,
+        point: Point2,
+        entities: Vec<Entity>,
+        meshes: Vec<Handle<Mesh>>,
+    ) {
+        self.chunks.insert(point, entities);
+        self.meshes.insert(point, meshes);
+    }
+
+    /// Removes and returns the entities of every chunk for which the predicate
+    /// returns `false`, given the point and the meshes of the chunk.
+    pub(crate) fn remove_chunks_where<F>(&mut self, mut keep: F) -> Vec<Entity>
+    where
+        F: FnMut(&Point2, &[Handle<Mesh>]) -> bool,
+    {
+        let meshes = &mut self.meshes;
+        let mut removed = Vec::new();
+        self.chunks.retain(|point, entities| {
+            let chunk_meshes = meshes.get(point).map_or(&[][..], |meshes| &meshes[..]);
+            if keep(point, chunk_meshes) {
+                true
+            } else {
+                removed.append(entities);
+                meshes.remove(point);
+                false
+            }
+        });
+        removed
+    }
+}
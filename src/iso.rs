@@ -0,0 +1,152 @@
+//! Depth sorting for iso tilemaps and y sorted layers.
+//!
+//! On an iso grid the x axis goes up and right and the y axis goes up and
+//! left, so the further a cell is up the screen the further back it is. The
+//! depth of anything standing on the grid is
+//!
+//! ```text
+//! depth = 0.5 - (center.x + center.y) / 8192
+//! ```
+//!
+//! where `center` is the center of its footprint in grid units. The center of
+//! the cell at point `(x, y)` is at `(x + 0.5, y + 0.5)`. The depth is always
+//! between 0 and 1 for points whose `x + y` is within ±4095, which keeps
+//! everything on a layer below the next layer. Further out it is clamped to
+//! stay within the layer, so nothing is drawn over the neighbouring layers,
+//! but things out there are no longer sorted against each other. Endless
+//! maps should keep what is drawn within this range by moving the origin
+//! with [`Tilemap::shift_origin`] as the camera travels.
+//!
+//! Tiles of a [`GridTopology::IsoDiamond`] tilemap are rendered with this
+//! depth added to their layer's z order. Sprites that are drawn over the
+//! tilemap, such as units, should use the same depth added to the z order of
+//! the layer they stand on. They are then hidden by the taller tiles in front
+//! of them and drawn over the tiles behind them. Sprites which are taller than
+//! one cell only need the depth of the cell that they stand on.
+//!
+//! Using the center of the footprint, instead of a row or corner, is what
+//! makes objects that cover several cells sort correctly against everything
+//! around them. This holds for footprints that are square or at most one
+//! cell longer on one side, such as 2×2 buildings or 2×3 carts. Longer
+//! footprints, such as a 1×4 wall, should be split into pieces that are.
+//!
+//! Layers of square tilemaps which are [`y_sort`]ed are sorted the same way
+//! by rows alone, with [`row_depth`], so that sprites which overhang the tile
+//! above, such as tall trees, are drawn in front of it and behind the sprites
+//! standing below them.
+//!
+//! The ground of iso maps is raised into cliffs and plateaus with
+//! [`Tilemap::set_height`], which draws the tiles of every layer at a point
+//! higher up without changing their depth. A cliff is then the tiles of a
+//! plateau drawn over the ground behind it, and a ramp a sprite which slopes
+//! up from the ground in front of it to the plateau. Units standing on raised
+//! ground are drawn the [`Tilemap::height_at`] their point higher up too.
+//!
+//! [`GridTopology::IsoDiamond`]: crate::chunk::render::GridTopology::IsoDiamond
+//! [`y_sort`]: crate::tilemap::TilemapLayer::y_sort
+//! [`Tilemap::set_height`]: crate::tilemap::Tilemap::set_height
+//! [`Tilemap::height_at`]: crate::tilemap::Tilemap::height_at
+//! [`Tilemap::shift_origin`]: crate::tilemap::Tilemap::shift_origin
+//!
+//! # Sorting a unit and a building
+//! ```
+//! use bevy_math::Vec2;
+//! use bevy_tilemap::iso;
+//!
+//! // A 2x2 building with its lowest cell at (4, 4).
+//! let building = iso::footprint_depth((4, 4), 2, 2);
+//!
+//! // A unit to the front left and one to the back right of the building.
+//! let front = iso::tile_depth((3, 5));
+//! let back = iso::tile_depth((6, 4));
+//! assert!(front > building);
+//! assert!(back < building);
+//!
+//! // A unit half way between two cells.
+//! let moving = iso::depth(Vec2::new(3.5, 6.0));
+//! assert!(moving > building);
+//!
+//! // Place the unit on layer 1.
+//! let z = 1.0 + front;
+//! ```
+
+use crate::lib::*;
+
+/// How much the depth changes per step along either axis.
+const DEPTH_STEP: f32 = 1.0 / 8192.0;
+
+/// The greatest depth, one step below the next layer.
+const MAX_DEPTH: f32 = 1.0 - DEPTH_STEP;
+
+/// Returns the depth of something whose footprint is centered at a point in
+/// grid units.
+///
+/// # Examples
+/// ```
+/// use bevy_math::Vec2;
+/// use bevy_tilemap::iso;
+///
+/// assert!(iso::depth(Vec2::new(1.5, 1.5)) > iso::depth(Vec2::new(2.5, 1.5)));
+/// assert_eq!(iso::depth(Vec2::new(2.5, 0.5)), iso::depth(Vec2::new(0.5, 2.5)));
+///
+/// // The last cells which are sorted, and those past them which are clamped
+/// // to the layer.
+/// assert!(iso::tile_depth((2047, 2047)) > iso::tile_depth((2048, 2047)));
+/// assert_eq!(iso::tile_depth((2048, 2047)), 0.0);
+/// assert_eq!(iso::tile_depth((5000, 5000)), 0.0);
+/// assert!(iso::tile_depth((-2048, -2048)) > iso::tile_depth((-2048, -2047)));
+/// assert!(iso::tile_depth((-5000, -5000)) < 1.0);
+/// ```
+pub fn depth(center: Vec2) -> f32 {
+    (0.5 - (center.x + center.y) * DEPTH_STEP).clamp(0.0, MAX_DEPTH)
+}
+
+/// Returns the depth of a tile, or anything standing on a single cell.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::iso;
+///
+/// assert!(iso::tile_depth((0, 0)) > iso::tile_depth((0, 1)));
+/// assert!(iso::tile_depth((0, 0)) < iso::tile_depth((-1, 0)));
+/// ```
+pub fn tile_depth<P: Into<Point2>>(point: P) -> f32 {
+    footprint_depth(point, 1, 1)
+}
+
+/// Returns the depth of an object covering `width` by `height` cells, where
+/// `point` is the cell with the lowest x and y.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::iso;
+///
+/// // A 2x2 building is sorted in front of what is right behind it.
+/// let building = iso::footprint_depth((0, 0), 2, 2);
+/// assert!(building > iso::tile_depth((2, 0)));
+/// assert!(building < iso::tile_depth((1, -1)));
+/// ```
+pub fn footprint_depth<P: Into<Point2>>(point: P, width: u32, height: u32) -> f32 {
+    let point: Point2 = point.into();
+    depth(Vec2::new(
+        point.x as f32 + width as f32 / 2.0,
+        point.y as f32 + height as f32 / 2.0,
+    ))
+}
+
+/// Returns the depth of something standing at a height in grid units on a y
+/// sorted layer of a square tilemap, where the row at `y` is centered at
+/// `y + 0.5`.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::iso;
+///
+/// // A unit standing in the row below a tree is drawn in front of it.
+/// let tree = iso::row_depth(3.5);
+/// assert!(iso::row_depth(2.5) > tree);
+/// assert!(iso::row_depth(4.5) < tree);
+/// ```
+pub fn row_depth(y: f32) -> f32 {
+    (0.5 - y * DEPTH_STEP).clamp(0.0, MAX_DEPTH)
+}
@@ -14,7 +14,7 @@
 //! * Easy to use and stable API with thorough documentation.
 //! * Endless or constrained tilemaps.
 //! * Batched rendering of many tiles.
-//! * Square and hex tiles.
+//! * Square, hex and iso tiles.
 //!
 //! ## Design
 //! This is not intended to be just another Tilemap. It is meant to be a
@@ -34,6 +34,21 @@
 //! bevy_tilemap = { version = "0.3", features = ["serialize"] }
 //! ```
 //!
+//! # Headless
+//!
+//! Dedicated servers which do not draw their tilemaps can leave out the
+//! default `render` feature, and with it `bevy_render` and `bevy_sprite`.
+//! Tilemaps are then built without a texture atlas, such as with
+//! `Tilemap::new(32, 32)`, and their tints use `color::Color`. Their chunks
+//! are spawned without meshes and the render graph, the tilemap instances and
+//! the mask and picking passes are left out. Tiles, queries, events,
+//! collisions and pathfinding all work the same.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", default-features = false }
+//! ```
+//!
 //! # Extra types feature
 //!
 //! Internally, the library uses Point2, Point3, Dimension2 and Dimension3
@@ -52,6 +67,62 @@
 //! ```
 //!
 //! See the library `bevy_tilemap_types` for more information.
+//!
+//! # Glam integer vectors
+//!
+//! The glam of Bevy 0.4 has no integer vectors. With the `glam` feature, the
+//! `IVec2`, `IVec3`, `UVec2` and `UVec3` of glam 0.15 convert into points, so
+//! they can be passed to every method which takes `Into<Point2>` or
+//! `Into<Point3>` just like tuples and arrays, and points convert back into
+//! `IVec2` and `IVec3`.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["glam"] }
+//! ```
+//!
+//! # Procedural generation
+//!
+//! The `procgen` feature adds ready-made generators of endless worlds from
+//! Perlin and simplex noise, see the `procgen` module. It also turns on the
+//! `types` feature, which generators are written with.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["procgen"] }
+//! ```
+//!
+//! # Map editor
+//!
+//! The `editor` feature adds brush, rectangle, fill and eraser tools which
+//! edit tilemaps in game with undo and redo, see the `editor` module.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["editor"] }
+//! ```
+//!
+//! # Lighting
+//!
+//! The `lighting` feature draws tilemaps which have a normal map with lit
+//! pipelines, which are shaded by 2D point lights, see the `light` module.
+//! Tilemaps without a normal map are drawn unlit as before.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["lighting"] }
+//! ```
+//!
+//! # Testing
+//!
+//! The `testing` feature adds a headless app which runs the plugin frame by
+//! frame for integration tests, see the `testing` module. It is meant for the
+//! dev dependencies, so that it is not built into games.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["testing"] }
+//! ```
 
 #![doc(html_root_url = "https://docs.rs/bevy_tilemap/0.3.1")]
 // This was broken even further and no longer will work at all with the previous
@@ -89,11 +160,38 @@ pub use bevy_tilemap_types::dimension;
 #[doc(inline)]
 pub use bevy_tilemap_types::point;
 
+#[no_implicit_prelude]
+pub mod animation;
+#[cfg(feature = "ron-asset")]
+#[no_implicit_prelude]
+pub mod asset;
+#[cfg(feature = "atlas")]
+#[no_implicit_prelude]
+pub mod atlas;
+#[no_implicit_prelude]
+pub mod channel;
 #[no_implicit_prelude]
 pub mod chunk;
+#[cfg(not(feature = "render"))]
+#[no_implicit_prelude]
+pub mod color;
+#[no_implicit_prelude]
+pub mod command;
+#[no_implicit_prelude]
+pub mod daylight;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod debug;
+#[no_implicit_prelude]
+pub mod decal;
 #[no_implicit_prelude]
 pub mod default_plugin;
 #[no_implicit_prelude]
+pub mod delta;
+#[cfg(feature = "editor")]
+#[no_implicit_prelude]
+pub mod editor;
+#[no_implicit_prelude]
 pub mod entity;
 #[no_implicit_prelude]
 pub mod prelude;
@@ -103,17 +201,101 @@ pub mod stage {
 
     /// The tilemap stage, set to run before `POST_UPDATE` stage.
     pub const TILEMAP: &str = "tilemap";
+    /// The stage which applies the queued tilemap commands, set to run before
+    /// the `TILEMAP` stage.
+    pub const TILEMAP_COMMANDS: &str = "tilemap_commands";
 }
 #[no_implicit_prelude]
 pub mod event;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod export;
+#[cfg(feature = "format")]
+#[no_implicit_prelude]
+pub mod format;
+#[no_implicit_prelude]
+mod geometry;
+#[no_implicit_prelude]
+pub mod hex;
+#[no_implicit_prelude]
+pub mod highlight;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod instance;
+#[no_implicit_prelude]
+pub mod iso;
+#[cfg(feature = "lighting")]
+#[no_implicit_prelude]
+pub mod light;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod mask;
+#[no_implicit_prelude]
+pub mod metrics;
+#[no_implicit_prelude]
+pub mod navmesh;
+#[cfg(feature = "serde")]
+#[no_implicit_prelude]
+mod pairs;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod palette;
+#[no_implicit_prelude]
+pub mod physics;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod picking;
+#[cfg(feature = "bevy_rapier2d")]
+#[no_implicit_prelude]
+pub mod platform;
+#[cfg(feature = "procgen")]
+#[no_implicit_prelude]
+pub mod procgen;
+#[cfg(feature = "bevy_rapier2d")]
+#[no_implicit_prelude]
+pub mod rapier;
+#[no_implicit_prelude]
+pub mod region;
+#[cfg(feature = "render")]
+#[no_implicit_prelude]
+pub mod render_layers;
+#[no_implicit_prelude]
+pub mod rule;
+#[cfg(feature = "serialize")]
+#[no_implicit_prelude]
+pub mod save;
+#[no_implicit_prelude]
+pub mod snapshot;
+#[no_implicit_prelude]
+pub mod sprite;
+#[cfg(feature = "serialize")]
+#[no_implicit_prelude]
+pub mod store;
 #[no_implicit_prelude]
 mod system;
 #[no_implicit_prelude]
+pub mod template;
+#[cfg(feature = "testing")]
+#[no_implicit_prelude]
+pub mod testing;
+#[no_implicit_prelude]
 pub mod tile;
 #[no_implicit_prelude]
 pub mod tilemap;
+#[no_implicit_prelude]
+pub mod tmx;
+#[no_implicit_prelude]
+pub mod variant;
+#[no_implicit_prelude]
+pub mod wall;
+#[no_implicit_prelude]
+pub mod wfc;
+#[no_implicit_prelude]
+pub mod world;
 
-use crate::{chunk::render::TilemapRenderGraphBuilder, event::TilemapChunkEvent, lib::*};
+#[cfg(feature = "render")]
+use crate::chunk::render::TilemapRenderGraphBuilder;
+use crate::{command::TilemapCommands, event::TilemapChunkEvent, lib::*, metrics::TilemapMetrics};
 pub use crate::{
     tile::Tile,
     tilemap::{Tilemap, TilemapLayer},
@@ -126,13 +308,33 @@ pub struct Tilemap2DPlugin;
 impl Plugin for Tilemap2DPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<Tilemap>()
+            .init_resource::<TilemapCommands>()
+            .init_resource::<TilemapMetrics>()
             .add_stage_before(
                 app_stage::POST_UPDATE,
                 stage::TILEMAP,
                 SystemStage::parallel(),
             )
+            .add_stage_before(
+                stage::TILEMAP,
+                stage::TILEMAP_COMMANDS,
+                SystemStage::serial(),
+            )
+            .add_system_to_stage(
+                stage::TILEMAP_COMMANDS,
+                crate::system::tilemap_commands.system(),
+            )
             .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_events.system())
-            .add_system_to_stage(stage::TILEMAP, crate::chunk::system::chunk_update.system())
+            .init_resource::<crate::daylight::TimeOfDay>()
+            .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_animations.system())
+            .add_system_to_stage(stage::TILEMAP, crate::daylight::tilemap_daylight.system())
+            .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_fades.system())
+            .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_decals.system())
+            .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_highlights.system())
+            .add_system_to_stage(
+                stage::TILEMAP,
+                crate::system::tilemap_tile_entities.system(),
+            )
             .add_system_to_stage(
                 stage::TILEMAP,
                 crate::chunk::system::chunk_auto_radius.system(),
@@ -140,101 +342,246 @@ impl Plugin for Tilemap2DPlugin {
             .add_system_to_stage(
                 stage::TILEMAP,
                 crate::chunk::system::chunk_auto_spawn.system(),
+            )
+            .add_system_to_stage(
+                stage::TILEMAP,
+                crate::chunk::system::chunk_auto_activity.system(),
+            )
+            .add_system_to_stage(stage::TILEMAP, crate::system::tilemap_navmeshes.system())
+            .add_system_to_stage(
+                stage::TILEMAP,
+                crate::system::tilemap_load_shedding.system(),
             );
         #[cfg(feature = "bevy_rapier2d")]
-        app.add_system_to_stage(
-            stage::TILEMAP,
-            crate::system::tilemap_collision_events.system(),
-        );
-
-        let resources = app.resources_mut();
-        let mut render_graph = resources
-            .get_mut::<RenderGraph>()
-            .expect("`RenderGraph` is missing.");
-        render_graph.add_tilemap_graph(resources);
+        {
+            // The rapier plugin replaces this with its own narrow phase, which
+            // the trigger events are read from.
+            if app.resources().get::<NarrowPhase>().is_none() {
+                app.add_resource(NarrowPhase::new());
+            }
+            app.add_plugin(crate::physics::TilemapPhysicsPlugin::<
+                crate::rapier::RapierPhysicsBackend,
+            >::default())
+                .add_system_to_stage(
+                    stage::TILEMAP,
+                    crate::system::tilemap_trigger_events.system(),
+                );
+        }
+        #[cfg(feature = "editor")]
+        app.add_event::<crate::editor::TilemapEditorEvent>()
+            .add_system_to_stage(stage::TILEMAP, crate::editor::tilemap_editors.system());
+        #[cfg(feature = "render")]
+        app.add_asset::<crate::instance::TilemapData>();
+        #[cfg(feature = "atlas")]
+        app.add_asset::<crate::atlas::TilemapAtlas>()
+            .init_asset_loader::<crate::atlas::TilemapAtlasLoader>();
+        #[cfg(feature = "format")]
+        {
+            #[cfg(feature = "ron-asset")]
+            use crate::format::AddTilemapFormat;
+            app.init_resource::<crate::format::TilemapFormats>();
+            #[cfg(feature = "ron-asset")]
+            app.add_tilemap_format(crate::asset::TilemapRonFormat);
+        }
+
+        #[cfg(feature = "render")]
+        {
+            app.add_system_to_stage(stage::TILEMAP, crate::system::tilemap_instances.system())
+                .add_system_to_stage(stage::TILEMAP, crate::chunk::system::chunk_update.system())
+                .add_system_to_stage(stage::TILEMAP, crate::chunk::system::chunk_colors.system())
+                .add_system_to_stage(stage::TILEMAP, crate::chunk::system::chunk_depths.system())
+                .add_system_to_stage(
+                    stage::TILEMAP,
+                    crate::chunk::system::chunk_animations.system(),
+                )
+                .add_system_to_stage(stage::TILEMAP, crate::palette::chunk_palettes.system())
+                .add_system_to_stage(
+                    stage::TILEMAP,
+                    crate::render_layers::chunk_render_layers.system(),
+                )
+                .add_system_to_stage(
+                    app_stage::LAST,
+                    crate::render_layers::camera_render_layers.system(),
+                );
+
+            let resources = app.resources_mut();
+            let mut render_graph = resources
+                .get_mut::<RenderGraph>()
+                .expect("`RenderGraph` is missing.");
+            render_graph.add_tilemap_graph(resources);
+        }
+        #[cfg(feature = "lighting")]
+        app.init_resource::<crate::light::TilemapAmbientLight>()
+            .add_system_to_stage(stage::TILEMAP, crate::light::chunk_lighting.system());
     }
 }
 
 /// A custom prelude around everything that we only need to use.
 #[no_implicit_prelude]
 mod lib {
+    #[cfg(any(feature = "atlas", feature = "format"))]
+    pub(crate) extern crate anyhow;
     extern crate bevy_app;
     extern crate bevy_asset;
+    extern crate bevy_core;
     extern crate bevy_ecs;
     extern crate bevy_log;
     extern crate bevy_math;
     #[cfg(feature = "bevy_rapier2d")]
     extern crate bevy_rapier2d;
     extern crate bevy_reflect;
+    #[cfg(feature = "render")]
     extern crate bevy_render;
+    #[cfg(feature = "render")]
     extern crate bevy_sprite;
     extern crate bevy_tilemap_types;
     extern crate bevy_transform;
     extern crate bevy_utils;
     extern crate bevy_window;
     pub extern crate bitflags;
+    #[cfg(feature = "png")]
+    pub(crate) extern crate image;
+    #[cfg(feature = "ron-asset")]
+    pub(crate) extern crate ron;
     #[cfg(feature = "serde")]
     extern crate serde;
+    #[cfg(feature = "serialize")]
+    pub(crate) extern crate serde_json;
     extern crate std;
+    #[cfg(feature = "compress")]
+    pub(crate) extern crate zstd;
 
+    #[cfg(any(feature = "editor", feature = "render"))]
+    pub(crate) use bevy_app::EventReader;
     pub(crate) use bevy_app::{
-        stage as app_stage, AppBuilder, Events, Plugin, PluginGroup, PluginGroupBuilder,
+        stage as app_stage, App, AppBuilder, Events, Plugin, PluginGroup, PluginGroupBuilder,
     };
-    pub(crate) use bevy_asset::{AddAsset, Assets, Handle, HandleUntyped};
+    pub(crate) use bevy_asset::{AddAsset, AssetPlugin};
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_asset::{AssetEvent, Assets, Handle, HandleUntyped};
+    #[cfg(any(feature = "atlas", feature = "format"))]
+    pub(crate) use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
+    pub(crate) use bevy_core::{CorePlugin, Time};
+    #[cfg(any(feature = "editor", feature = "render"))]
+    pub(crate) use bevy_ecs::Local;
     pub(crate) use bevy_ecs::{
-        Bundle, Changed, Commands, Entity, IntoSystem, Query, Res, ResMut, Resources, SystemStage,
+        Bundle, Commands, Entity, IntoSystem, Mut, Query, Res, ResMut, SystemStage, With,
     };
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_ecs::{Changed, Resources};
     pub(crate) use bevy_log::{error, info, warn};
-    pub(crate) use bevy_math::Vec3;
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_math::Vec4;
+    pub(crate) use bevy_math::{Vec2, Vec3};
     #[cfg(feature = "bevy_rapier2d")]
-    pub(crate) use bevy_rapier2d::rapier::{
-        dynamics::RigidBodyBuilder,
-        geometry::{ColliderBuilder, InteractionGroups},
+    pub(crate) use bevy_rapier2d::{
+        physics::ColliderHandleComponent,
+        rapier::{
+            dynamics::{RigidBody, RigidBodyBuilder},
+            geometry::{
+                Collider, ColliderBuilder, ColliderHandle, ContactPairFilter, InteractionGroups,
+                NarrowPhase, PairFilterContext, Proximity, SolverFlags,
+            },
+        },
     };
-    pub(crate) use bevy_reflect::{TypeUuid, Uuid};
+    pub(crate) use bevy_reflect::{ReflectPlugin, TypeUuid, Uuid};
+    #[cfg(feature = "render")]
     pub(crate) use bevy_render::{
         camera::Camera,
         color::Color,
+        mesh::{Indices, Mesh, VertexAttributeValues},
+        pipeline::{PipelineDescriptor, PrimitiveTopology},
+        render_graph::RenderGraph,
+        shader::Shader,
+        texture::{Extent3d, Texture, TextureDimension, TextureFormat},
+    };
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_render::{
+        camera::VisibleEntities,
         draw::{Draw, Visible},
-        mesh::{Indices, Mesh},
+        pass::{
+            LoadOp, Operations, PassDescriptor, RenderPassColorAttachmentDescriptor,
+            RenderPassDepthStencilAttachmentDescriptor, TextureAttachment,
+        },
         pipeline::{
             BlendDescriptor, BlendFactor, BlendOperation, ColorStateDescriptor, ColorWrite,
-            CompareFunction, CullMode, DepthStencilStateDescriptor, FrontFace, PipelineDescriptor,
-            PrimitiveTopology, RasterizationStateDescriptor, RenderPipeline, RenderPipelines,
-            StencilStateDescriptor, StencilStateFaceDescriptor,
+            CompareFunction, CullMode, DepthStencilStateDescriptor, FrontFace,
+            RasterizationStateDescriptor, RenderPipeline, RenderPipelines, StencilStateDescriptor,
+            StencilStateFaceDescriptor,
         },
-        render_graph::{base::MainPass, RenderGraph},
-        shader::{Shader, ShaderStage, ShaderStages},
-        texture::TextureFormat,
+        render_graph::{
+            base::{self, MainPass, Msaa},
+            PassNode,
+        },
+        shader::{ShaderStage, ShaderStages},
+    };
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_render::{
+        render_graph::RenderResourcesNode,
+        renderer::{RenderResource, RenderResourceIterator, RenderResources},
     };
+    #[cfg(feature = "atlas")]
+    pub(crate) use bevy_sprite::Rect;
+    #[cfg(feature = "render")]
     pub(crate) use bevy_sprite::TextureAtlas;
     pub(crate) use bevy_tilemap_types::{
         dimension::{Dimension2, DimensionError},
         point::Point2,
     };
+    #[cfg(feature = "render")]
+    pub(crate) use bevy_transform::components::{Children, Parent};
     pub(crate) use bevy_transform::{
-        components::{GlobalTransform, Parent, Transform},
+        components::{GlobalTransform, Transform},
         hierarchy::{BuildChildren, DespawnRecursiveExt},
+        TransformPlugin,
     };
-    pub(crate) use bevy_utils::{HashMap, HashSet};
+    #[cfg(any(feature = "atlas", feature = "format"))]
+    pub(crate) use bevy_utils::BoxedFuture;
+    pub(crate) use bevy_utils::{Duration, HashMap, HashSet, Instant};
     pub(crate) use bevy_window::WindowResized;
 
     pub(crate) use crate::bitflags::*;
+    #[cfg(not(feature = "render"))]
+    pub(crate) use crate::color::Color;
 
+    #[cfg(feature = "atlas")]
+    pub(crate) use serde::de::{Deserializer, MapAccess, Visitor};
     #[cfg(feature = "serde")]
     pub(crate) use serde::{Deserialize, Serialize};
+    #[cfg(feature = "serialize")]
+    pub(crate) use serde_json::Value as JsonValue;
+    #[cfg(feature = "atlas")]
+    pub(crate) use std::collections::BTreeMap;
+    #[cfg(feature = "procgen")]
+    pub(crate) use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+    #[cfg(feature = "png")]
+    pub(crate) use std::path::Path;
+    #[cfg(any(feature = "serialize", feature = "format"))]
+    pub(crate) use std::path::PathBuf;
+    #[cfg(feature = "serialize")]
+    pub(crate) use std::{fs, io, ops::FnOnce};
 
+    #[cfg(any(feature = "procgen", feature = "render"))]
+    pub(crate) use std::cmp::Ordering;
+    #[cfg(feature = "render")]
+    pub(crate) use std::sync::Weak;
     pub(crate) use std::{
         boxed::Box,
         clone::Clone,
-        cmp::Ord,
-        convert::{AsMut, AsRef, From, Into},
+        cmp::{Ord, PartialOrd, Reverse},
+        collections::{hash_map::RandomState, BinaryHeap},
+        convert::{AsMut, AsRef, From, Into, TryFrom},
         default::Default,
         error::Error,
         fmt::{Debug, Display, Formatter, Result as FmtResult},
+        hash::BuildHasher,
         iter::{Extend, IntoIterator, Iterator},
+        marker::{PhantomData, Send, Sync},
+        ops::{Fn, FnMut, Range},
         option::Option::{self, *},
         result::Result::{self, *},
+        string::{String, ToString},
+        sync::{Arc, Mutex},
         vec::Vec,
     };
 
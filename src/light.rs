@@ -0,0 +1,234 @@
+//! Normal mapped tilemaps which are shaded by 2D point lights.
+//!
+//! A tilemap built with [`TilemapBuilder::normal_map`] is drawn with the lit
+//! pipeline of its topology instead of the unlit one. The normal map is a
+//! second texture laid out exactly like the texture of the texture atlas, so
+//! every sprite has its normals at the same rect. Normals face the camera
+//! along z, with x to the right and y up, as most 2D tools export them.
+//!
+//! Entities with a [`TilemapLight`] and a transform are point lights in world
+//! space. Every chunk is lit by the [`MAX_TILEMAP_LIGHTS`] lights which are
+//! closest to it, on top of the [`TilemapAmbientLight`] resource which lights
+//! every tile where no point light reaches.
+//!
+//! This is behind the `lighting` feature, which leaves the unlit pipelines
+//! as they are.
+//!
+//! [`TilemapBuilder::normal_map`]: crate::tilemap::TilemapBuilder::normal_map
+//!
+//! # A torch
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_math::Vec3;
+//! use bevy_render::{color::Color, texture::Texture};
+//! use bevy_tilemap::{
+//!     light::{TilemapAmbientLight, TilemapLight},
+//!     prelude::*,
+//!     testing::tilemap_test_harness,
+//! };
+//! use bevy_transform::prelude::*;
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! // In production use a strong handle from an actual source.
+//! let normal_map = Handle::weak(HandleId::random::<Texture>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .normal_map(normal_map.clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! assert_eq!(tilemap.normal_map(), Some(&normal_map));
+//!
+//! harness.app().resources.insert(TilemapAmbientLight { color: Color::rgb(0.1, 0.1, 0.2) });
+//! harness.app().world.spawn((
+//!     TilemapLight::new(Color::rgb(1.0, 0.6, 0.2), 96.0),
+//!     Transform::from_translation(Vec3::new(16.0, 16.0, 0.0)),
+//!     GlobalTransform::default(),
+//! ));
+//! harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//! ```
+
+use crate::lib::*;
+
+/// The most point lights which light a chunk.
+pub const MAX_TILEMAP_LIGHTS: usize = 16;
+
+/// The names of the tilemap lighting nodes in the render graph.
+pub mod node {
+    /// The node which binds the normal maps and lights of the chunks.
+    pub const CHUNK_LIGHTING: &str = "chunk_lighting";
+}
+
+/// A point light in world space which shades normal mapped tilemaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilemapLight {
+    /// The color of the light.
+    pub color: Color,
+    /// How bright the light is, which its color is multiplied by.
+    pub intensity: f32,
+    /// How far the light reaches, in pixels.
+    pub radius: f32,
+    /// How high above the tiles the light is, in pixels. Lower lights graze
+    /// the tiles and bring out their bumps more.
+    pub height: f32,
+}
+
+impl Default for TilemapLight {
+    fn default() -> Self {
+        TilemapLight {
+            color: Color::WHITE,
+            intensity: 1.0,
+            radius: 256.0,
+            height: 64.0,
+        }
+    }
+}
+
+impl TilemapLight {
+    /// Constructs a new light of a color which reaches as far as the radius.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_render::color::Color;
+    /// use bevy_tilemap::light::TilemapLight;
+    ///
+    /// let light = TilemapLight::new(Color::rgb(1.0, 0.6, 0.2), 96.0);
+    ///
+    /// assert_eq!(light.radius, 96.0);
+    /// assert_eq!(light.intensity, 1.0);
+    /// ```
+    pub fn new(color: Color, radius: f32) -> TilemapLight {
+        TilemapLight {
+            color,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the light at a position as the two vectors of the lights
+    /// uniform of the lit shader.
+    fn to_uniform(self, position: Vec2) -> [[f32; 4]; 2] {
+        [
+            [position.x, position.y, self.height, self.radius],
+            [
+                self.color.r() * self.intensity,
+                self.color.g() * self.intensity,
+                self.color.b() * self.intensity,
+                1.0,
+            ],
+        ]
+    }
+}
+
+/// The light which every tile of normal mapped tilemaps is lit by, even where
+/// no point light reaches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilemapAmbientLight {
+    /// The color of the light.
+    pub color: Color,
+}
+
+impl Default for TilemapAmbientLight {
+    fn default() -> Self {
+        TilemapAmbientLight {
+            color: Color::rgb(0.2, 0.2, 0.2),
+        }
+    }
+}
+
+/// The normal map and lights of the layer of a chunk, which are bound to its
+/// lit pipeline.
+#[derive(Debug, Clone)]
+pub(crate) struct ChunkLighting {
+    /// The handle of the normal map of the tilemap.
+    normal_map: Handle<Texture>,
+    /// The color of the ambient light.
+    ambient: Color,
+    /// The positions and colors of the closest lights, padded with lights
+    /// without a radius.
+    lights: Vec<[f32; 4]>,
+}
+
+impl ChunkLighting {
+    /// Constructs the lighting of a chunk with a normal map, which is not lit
+    /// until the lights are set.
+    pub(crate) fn new(normal_map: Handle<Texture>) -> ChunkLighting {
+        ChunkLighting {
+            normal_map,
+            ambient: Color::BLACK,
+            lights: vec![[0.0; 4]; MAX_TILEMAP_LIGHTS * 2],
+        }
+    }
+}
+
+impl RenderResources for ChunkLighting {
+    fn render_resources_len(&self) -> usize {
+        3
+    }
+
+    fn get_render_resource(&self, index: usize) -> Option<&dyn RenderResource> {
+        match index {
+            0 => Some(&self.normal_map),
+            1 => Some(&self.ambient),
+            2 => Some(&self.lights),
+            _ => None,
+        }
+    }
+
+    fn get_render_resource_name(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("ChunkLighting_normal_map"),
+            1 => Some("ChunkLighting_ambient"),
+            2 => Some("ChunkLighting_lights"),
+            _ => None,
+        }
+    }
+
+    fn iter(&self) -> RenderResourceIterator<'_> {
+        RenderResourceIterator::new(self)
+    }
+}
+
+/// Sets the ambient light and the closest lights of every lit chunk.
+pub(crate) fn chunk_lighting(
+    ambient: Res<TilemapAmbientLight>,
+    light_query: Query<(&TilemapLight, &GlobalTransform)>,
+    mut chunk_query: Query<(&GlobalTransform, &mut ChunkLighting)>,
+) {
+    for (transform, mut lighting) in chunk_query.iter_mut() {
+        let center = transform.translation.truncate();
+        let mut closest: Vec<(f32, [[f32; 4]; 2])> = light_query
+            .iter()
+            .map(|(light, light_transform)| {
+                let position = light_transform.translation.truncate();
+                (
+                    (position - center).length_squared(),
+                    light.to_uniform(position),
+                )
+            })
+            .collect();
+        closest.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let mut lights: Vec<[f32; 4]> = closest
+            .into_iter()
+            .take(MAX_TILEMAP_LIGHTS)
+            .flat_map(|(_, uniform)| uniform.to_vec())
+            .collect();
+        lights.resize(MAX_TILEMAP_LIGHTS * 2, [0.0; 4]);
+
+        // Only write changes, which would otherwise upload every chunk again
+        // every frame.
+        if lighting.ambient != ambient.color {
+            lighting.ambient = ambient.color;
+        }
+        if lighting.lights != lights {
+            lighting.lights = lights;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Drawing tilemap layers into a mask texture.
+//!
+//! Layers which are added with [`TilemapLayer::mask`] set have their chunks
+//! drawn a second time, in the tilemap mask pass. Every pixel of a tile which
+//! is visible in the main pass is written as white to the mask texture, and
+//! everything else is left black. The mask can then be sampled by screen
+//! space effects, for example to occlude lights with walls or to outline the
+//! tiles of a layer.
+//!
+//! The mask pass is added to the render graph with
+//! [`TilemapMaskGraphBuilder::add_tilemap_mask_pass`]. It is drawn with the 2D
+//! camera right after the main pass and needs a texture of the
+//! [`MASK_TEXTURE_FORMAT`] connected to its [`MASK_TEXTURE`] input slot. The
+//! texture has to have as many samples as the `Msaa` resource when the pass
+//! is added. If that is more than 1, the mask is resolved into the texture
+//! connected to the [`MASK_RESOLVE_TARGET`] slot, which has only 1 sample.
+//!
+//! [`TilemapLayer::mask`]: crate::tilemap::TilemapLayer::mask
+//!
+//! # Adding a mask pass
+//! ```
+//! use bevy_ecs::Resources;
+//! use bevy_render::render_graph::RenderGraph;
+//! use bevy_tilemap::{mask::TilemapMaskGraphBuilder, prelude::*};
+//!
+//! // Only the walls are drawn to the mask.
+//! let builder = TilemapBuilder::new()
+//!     .add_layer(TilemapLayer { kind: LayerKind::Dense, ..Default::default() }, 0)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, mask: true, ..Default::default() }, 1);
+//!
+//! fn build_graph(render_graph: &mut RenderGraph, resources: &Resources) {
+//!     render_graph.add_tilemap_mask_pass(resources);
+//!     // A texture still needs to be connected to the `MASK_TEXTURE` slot of
+//!     // `node::TILEMAP_MASK_PASS` with `RenderGraph::add_slot_edge`.
+//! }
+//! ```
+
+use crate::lib::*;
+
+/// The format of the mask texture.
+pub const MASK_TEXTURE_FORMAT: TextureFormat = TextureFormat::R8Unorm;
+
+/// The name of the input slot of the texture that the mask is drawn to.
+pub const MASK_TEXTURE: &str = "mask_texture";
+
+/// The name of the input slot of the texture that a multisampled mask is
+/// resolved to.
+pub const MASK_RESOLVE_TARGET: &str = "mask_resolve_target";
+
+/// The names of the tilemap mask nodes in the render graph.
+pub mod node {
+    /// The tilemap mask pass which draws the masked layers.
+    pub const TILEMAP_MASK_PASS: &str = "tilemap_mask_pass";
+}
+
+/// A component that indicates that an entity should be drawn in the tilemap
+/// mask pass.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TilemapMaskPass;
+
+/// A trait which adds the tilemap mask pass to a render graph.
+pub trait TilemapMaskGraphBuilder: private::Sealed {
+    /// Adds the tilemap mask pass, which is drawn after the main pass.
+    fn add_tilemap_mask_pass(&mut self, resources: &Resources) -> &mut Self;
+}
+
+impl TilemapMaskGraphBuilder for RenderGraph {
+    fn add_tilemap_mask_pass(&mut self, resources: &Resources) -> &mut Self {
+        let msaa = resources.get::<Msaa>().expect("`Msaa` is missing.");
+        let resolve_target = if msaa.samples > 1 {
+            Some(TextureAttachment::Input(MASK_RESOLVE_TARGET.to_string()))
+        } else {
+            None
+        };
+
+        let mut mask_pass_node = PassNode::<&TilemapMaskPass>::new(PassDescriptor {
+            color_attachments: vec![RenderPassColorAttachmentDescriptor {
+                attachment: TextureAttachment::Input(MASK_TEXTURE.to_string()),
+                resolve_target,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+            sample_count: msaa.samples,
+        });
+        mask_pass_node.add_camera(base::camera::CAMERA_2D);
+
+        self.add_node(node::TILEMAP_MASK_PASS, mask_pass_node);
+        self.add_node_edge(base::node::MAIN_PASS, node::TILEMAP_MASK_PASS)
+            .expect("The main pass is missing from the render graph.");
+
+        self
+    }
+}
+
+/// Prevents the traits in this module from being implemented outside the crate.
+mod private {
+    use super::RenderGraph;
+
+    /// Seals the type.
+    pub trait Sealed {}
+
+    impl Sealed for RenderGraph {}
+}
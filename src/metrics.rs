@@ -0,0 +1,129 @@
+//! Work and timing counters of the optional tilemap features, and the
+//! memory that tilemaps use and free.
+//!
+//! The [`TilemapMetrics`] resource is added by the [`Tilemap2DPlugin`]. Each
+//! tilemap system records how much work it did in the last frame and how long
+//! it took, so that it is easy to find the feature which is eating the frame
+//! budget.
+//!
+//! [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+//!
+//! # Logging the metrics
+//! ```
+//! use bevy_ecs::prelude::*;
+//! use bevy_tilemap::metrics::TilemapMetrics;
+//!
+//! fn log_metrics(metrics: Res<TilemapMetrics>) {
+//!     println!(
+//!         "{} colliders spawned in {:?}",
+//!         metrics.collision.work, metrics.collision.duration
+//!     );
+//! }
+//! ```
+
+use crate::{chunk::LayerKind, lib::*};
+
+/// How much work a feature did in a frame and how long it took.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct FeatureMetrics {
+    /// The amount of work done, whose unit depends on the feature.
+    pub work: usize,
+    /// How long the work took.
+    pub duration: Duration,
+}
+
+impl FeatureMetrics {
+    /// Constructs new metrics of work which started at the instant.
+    pub(crate) fn since(start: Instant, work: usize) -> FeatureMetrics {
+        FeatureMetrics {
+            work,
+            duration: start.elapsed(),
+        }
+    }
+}
+
+/// The metrics of the last frame of every tilemap feature, summed over all
+/// the tilemaps.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TilemapMetrics {
+    /// The animated tiles that were advanced.
+    pub animation: FeatureMetrics,
+    /// The tile colliders that were spawned by the physics backend.
+    pub collision: FeatureMetrics,
+    /// The chunks whose navigation mesh regions were merged again.
+    pub navmesh: FeatureMetrics,
+    /// The chunk layers whose meshes were updated.
+    pub mesh: FeatureMetrics,
+}
+
+impl TilemapMetrics {
+    /// Returns how long all the features took together.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::metrics::TilemapMetrics;
+    ///
+    /// let metrics = TilemapMetrics::default();
+    ///
+    /// assert_eq!(metrics.total_duration().as_secs(), 0);
+    /// ```
+    pub fn total_duration(&self) -> Duration {
+        self.animation.duration
+            + self.collision.duration
+            + self.navmesh.duration
+            + self.mesh.duration
+    }
+}
+
+/// What compacting a tilemap freed, see [`Tilemap::compact`].
+///
+/// [`Tilemap::compact`]: crate::tilemap::Tilemap::compact
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct CompactReport {
+    /// The chunks without any tiles which were removed.
+    pub removed_chunks: usize,
+    /// About how many bytes of tiles were freed, from the capacity of the
+    /// layers and from the removed chunks.
+    pub freed_bytes: usize,
+}
+
+/// How many tiles the layers of a kind have and about how many bytes they
+/// take.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct LayerStats {
+    /// The layers of the kind in every chunk in memory.
+    pub layers: usize,
+    /// The tiles of the layers.
+    pub tiles: usize,
+    /// About how many bytes the tiles and channel values of the layers take,
+    /// counting the capacity which is not used.
+    pub bytes: usize,
+}
+
+/// The chunks, tiles and memory of a tilemap, see [`Tilemap::stats`].
+///
+/// [`Tilemap::stats`]: crate::tilemap::Tilemap::stats
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TilemapStats {
+    /// The chunks in memory.
+    pub chunks: usize,
+    /// The chunks which are spawned.
+    pub spawned_chunks: usize,
+    /// The layers of the chunks by their kinds.
+    pub layers: HashMap<LayerKind, LayerStats>,
+    /// About how many bytes the meshes of the spawned chunks take.
+    pub mesh_bytes: usize,
+}
+
+impl TilemapStats {
+    /// Returns how many tiles the layers of every kind have together.
+    pub fn tiles(&self) -> usize {
+        self.layers.values().map(|layer| layer.tiles).sum()
+    }
+
+    /// Returns about how many bytes the layers of every kind and the meshes
+    /// take together.
+    pub fn bytes(&self) -> usize {
+        self.layers.values().map(|layer| layer.bytes).sum::<usize>() + self.mesh_bytes
+    }
+}
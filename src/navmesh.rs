@@ -0,0 +1,403 @@
+//! Navigation meshes of walkable tiles.
+//!
+//! A [`NavMesh`] is added to a layer of a tilemap with
+//! [`Tilemap::add_navmesh`]. The walkable tiles of each chunk are merged into
+//! as few rectangular [`NavRegion`]s as possible, the same way tile colliders
+//! are, and regions which share an edge are linked by a [`NavPortal`]. Agents
+//! can then move straight across a region and only steer at the portals,
+//! which is smoother and cheaper than A* over every tile.
+//!
+//! When tiles change only the regions of their chunks are merged again, once
+//! per frame by the tilemap systems or sooner with
+//! [`Tilemap::update_navmeshes`].
+//!
+//! Points and positions are in tiles, where the tile at the point `(x, y)`
+//! covers `x..x + 1` and `y..y + 1`.
+//!
+//! [`Tilemap::add_navmesh`]: crate::tilemap::Tilemap::add_navmesh
+//! [`Tilemap::update_navmeshes`]: crate::tilemap::Tilemap::update_navmeshes
+//!
+//! # Finding a path
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // A floor with a wall, sprite index 1, that has a gap at the top.
+//! let mut tiles = Vec::new();
+//! for y in -2..2 {
+//!     for x in -2..2 {
+//!         let sprite_index = if x == 0 && y < 1 { 1 } else { 0 };
+//!         tiles.push(Tile { point: (x, y), sprite_index, ..Default::default() });
+//!     }
+//! }
+//! tilemap.insert_tiles(tiles).unwrap();
+//! tilemap.add_navmesh(0, vec![1]).unwrap();
+//!
+//! let navmesh = tilemap.navmesh(0).unwrap();
+//! let path = navmesh.find_path((-2, -2), (1, -2)).unwrap();
+//!
+//! // The path goes around the wall.
+//! assert!(path.len() > 2);
+//! assert!(navmesh.region_at((0, 0)).is_none());
+//! ```
+
+use crate::{chunk::Chunk, lib::*};
+
+/// A rectangle of walkable tiles.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct NavRegion {
+    /// The point of the chunk the region is in.
+    pub chunk_point: Point2,
+    /// The global point of the bottom left tile of the region.
+    pub point: Point2,
+    /// The dimensions of the region in tiles.
+    pub dimensions: Dimension2,
+}
+
+impl NavRegion {
+    /// Returns `true` if the region covers the global tile point.
+    pub fn contains<P: Into<Point2>>(&self, point: P) -> bool {
+        let point: Point2 = point.into();
+        point.x >= self.point.x
+            && point.y >= self.point.y
+            && point.x < self.point.x + self.dimensions.width as i32
+            && point.y < self.point.y + self.dimensions.height as i32
+    }
+
+    /// Returns the center of the region, in tiles.
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(
+            self.point.x as f32 + self.dimensions.width as f32 / 2.0,
+            self.point.y as f32 + self.dimensions.height as f32 / 2.0,
+        )
+    }
+
+    /// Returns twice the center of the region, which is a whole number.
+    fn double_center(&self) -> (i64, i64) {
+        (
+            2 * i64::from(self.point.x) + i64::from(self.dimensions.width),
+            2 * i64::from(self.point.y) + i64::from(self.dimensions.height),
+        )
+    }
+
+    /// Returns the edge that the region shares with another, if they are next
+    /// to each other.
+    fn shared_edge(&self, other: &NavRegion) -> Option<(Vec2, Vec2)> {
+        let (left, bottom) = (self.point.x, self.point.y);
+        let right = left + self.dimensions.width as i32;
+        let top = bottom + self.dimensions.height as i32;
+        let (other_left, other_bottom) = (other.point.x, other.point.y);
+        let other_right = other_left + other.dimensions.width as i32;
+        let other_top = other_bottom + other.dimensions.height as i32;
+
+        let vertical = match () {
+            _ if right == other_left => Some(right),
+            _ if left == other_right => Some(left),
+            _ => None,
+        };
+        if let Some(x) = vertical {
+            let start = bottom.max(other_bottom);
+            let end = top.min(other_top);
+            if start < end {
+                return Some((
+                    Vec2::new(x as f32, start as f32),
+                    Vec2::new(x as f32, end as f32),
+                ));
+            }
+        }
+        let horizontal = match () {
+            _ if top == other_bottom => Some(top),
+            _ if bottom == other_top => Some(bottom),
+            _ => None,
+        };
+        if let Some(y) = horizontal {
+            let start = left.max(other_left);
+            let end = right.min(other_right);
+            if start < end {
+                return Some((
+                    Vec2::new(start as f32, y as f32),
+                    Vec2::new(end as f32, y as f32),
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// The edge that a region shares with a neighbouring region.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct NavPortal {
+    /// The ID of the neighbouring region.
+    pub region: usize,
+    /// The start of the edge, in tiles.
+    pub start: Vec2,
+    /// The end of the edge, in tiles.
+    pub end: Vec2,
+}
+
+/// The walkable regions of a layer and how they connect.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct NavMesh {
+    /// The z order of the layer.
+    z_order: usize,
+    /// The dimensions of the chunks in tiles.
+    chunk_dimensions: Dimension2,
+    /// The sprite indexes whose tiles are not walkable.
+    blocked_sprites: HashSet<usize>,
+    /// The regions by their IDs.
+    regions: HashMap<usize, NavRegion>,
+    /// The portals of each region, by its ID.
+    portals: HashMap<usize, Vec<NavPortal>>,
+    /// The IDs of the regions of each chunk.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    chunk_regions: HashMap<Point2, Vec<usize>>,
+    /// The chunks whose tiles changed since the regions were merged.
+    dirty_chunks: HashSet<Point2>,
+    /// The ID of the next region.
+    next_region: usize,
+}
+
+impl NavMesh {
+    /// Constructs a new and empty navigation mesh of a layer.
+    pub(crate) fn new(
+        z_order: usize,
+        chunk_dimensions: Dimension2,
+        blocked_sprites: HashSet<usize>,
+    ) -> NavMesh {
+        NavMesh {
+            z_order,
+            chunk_dimensions,
+            blocked_sprites,
+            regions: HashMap::default(),
+            portals: HashMap::default(),
+            chunk_regions: HashMap::default(),
+            dirty_chunks: HashSet::default(),
+            next_region: 0,
+        }
+    }
+
+    /// Returns the z order of the layer.
+    pub fn z_order(&self) -> usize {
+        self.z_order
+    }
+
+    /// Sets the z order of the layer, once the layer has moved.
+    pub(crate) fn set_z_order(&mut self, z_order: usize) {
+        self.z_order = z_order;
+    }
+
+    /// Returns a reference to the region with the ID, if it exists.
+    pub fn region(&self, id: usize) -> Option<&NavRegion> {
+        self.regions.get(&id)
+    }
+
+    /// Returns an iterator of every region with its ID.
+    pub fn regions(&self) -> impl Iterator<Item = (usize, &NavRegion)> {
+        self.regions.iter().map(|(id, region)| (*id, region))
+    }
+
+    /// Returns the ID and region that covers the global tile point, if the
+    /// tile is walkable.
+    pub fn region_at<P: Into<Point2>>(&self, point: P) -> Option<(usize, &NavRegion)> {
+        let point: Point2 = point.into();
+        let width = self.chunk_dimensions.width as f32;
+        let height = self.chunk_dimensions.height as f32;
+        let chunk_point = Point2::new(
+            ((point.x as f32 + width / 2.0) / width).floor() as i32,
+            ((point.y as f32 + height / 2.0) / height).floor() as i32,
+        );
+        self.chunk_regions
+            .get(&chunk_point)?
+            .iter()
+            .filter_map(|id| self.regions.get(id).map(|region| (*id, region)))
+            .find(|(_, region)| region.contains(point))
+    }
+
+    /// Returns the portals to the neighbours of the region with the ID.
+    pub fn portals(&self, id: usize) -> &[NavPortal] {
+        match self.portals.get(&id) {
+            Some(portals) => portals,
+            None => &[],
+        }
+    }
+
+    /// Finds the shortest path of regions from the tile at one global point
+    /// to the tile at another with A*, returning the IDs of the regions in
+    /// order.
+    ///
+    /// Returns `None` if either tile is not walkable or there is no path.
+    pub fn find_path<F, T>(&self, from: F, to: T) -> Option<Vec<usize>>
+    where
+        F: Into<Point2>,
+        T: Into<Point2>,
+    {
+        let (start, _) = self.region_at(from)?;
+        let (goal, goal_region) = self.region_at(to)?;
+        let goal_center = goal_region.double_center();
+        let distance =
+            |a: (i64, i64), b: (i64, i64)| ((a.0 - b.0).abs() + (a.1 - b.1).abs()) as u64;
+
+        let mut open = BinaryHeap::new();
+        let mut costs: HashMap<usize, u64> = HashMap::default();
+        let mut came_from: HashMap<usize, usize> = HashMap::default();
+        costs.insert(start, 0);
+        open.push(Reverse((0, start)));
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                let mut path = vec![goal];
+                let mut current = goal;
+                while let Some(previous) = came_from.get(&current) {
+                    path.push(*previous);
+                    current = *previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            let (cost, center) = match (costs.get(&current), self.regions.get(&current)) {
+                (Some(cost), Some(region)) => (*cost, region.double_center()),
+                _ => continue,
+            };
+            for portal in self.portals(current) {
+                let next = match self.regions.get(&portal.region) {
+                    Some(region) => region.double_center(),
+                    None => continue,
+                };
+                let next_cost = cost + distance(center, next);
+                match costs.get(&portal.region) {
+                    Some(known) if *known <= next_cost => continue,
+                    _ => {}
+                }
+                costs.insert(portal.region, next_cost);
+                came_from.insert(portal.region, current);
+                open.push(Reverse((
+                    next_cost + distance(next, goal_center),
+                    portal.region,
+                )));
+            }
+        }
+        None
+    }
+
+    /// Marks a chunk whose tiles changed, so that its regions are merged
+    /// again.
+    pub(crate) fn mark_dirty(&mut self, chunk_point: Point2) {
+        self.dirty_chunks.insert(chunk_point);
+    }
+
+    /// Removes every region and portal, leaving the chunks to be merged again
+    /// once they are marked as dirty.
+    pub(crate) fn clear(&mut self) {
+        self.regions.clear();
+        self.portals.clear();
+        self.chunk_regions.clear();
+        self.dirty_chunks.clear();
+    }
+
+    /// Takes the chunks whose tiles changed.
+    pub(crate) fn take_dirty(&mut self) -> Vec<Point2> {
+        self.dirty_chunks.drain().collect()
+    }
+
+    /// Merges the walkable tiles of a chunk into regions again, replacing the
+    /// previous regions of the chunk and their portals. The chunk is `None`
+    /// if it was removed.
+    pub(crate) fn rebuild_chunk(&mut self, chunk_point: Point2, chunk: Option<&Chunk>) {
+        for id in self
+            .chunk_regions
+            .remove(&chunk_point)
+            .unwrap_or_default()
+            .into_iter()
+        {
+            self.regions.remove(&id);
+            for portal in self.portals.remove(&id).unwrap_or_default().into_iter() {
+                if let Some(portals) = self.portals.get_mut(&portal.region) {
+                    portals.retain(|portal| portal.region != id);
+                }
+            }
+        }
+        let chunk = if let Some(chunk) = chunk {
+            chunk
+        } else {
+            return;
+        };
+
+        let blocked_sprites = &self.blocked_sprites;
+        let rects = chunk.merge_tiles(self.z_order, self.chunk_dimensions, |_, raw_tile| {
+            !blocked_sprites.contains(&raw_tile.index)
+        });
+        let chunk_width = self.chunk_dimensions.width as i32;
+        let chunk_height = self.chunk_dimensions.height as i32;
+        let mut ids = Vec::with_capacity(rects.len());
+        for (point, dimensions) in rects.into_iter() {
+            let id = self.next_region;
+            self.next_region += 1;
+            self.regions.insert(
+                id,
+                NavRegion {
+                    chunk_point,
+                    point: Point2::new(
+                        point.x + chunk_width * chunk_point.x - chunk_width / 2,
+                        point.y + chunk_height * chunk_point.y - chunk_height / 2,
+                    ),
+                    dimensions,
+                },
+            );
+            ids.push(id);
+        }
+
+        let mut neighbours = ids.clone();
+        for (x, y) in [(1, 0), (-1, 0), (0, 1), (0, -1)].iter() {
+            if let Some(regions) = self.chunk_regions.get(&(chunk_point + Point2::new(*x, *y))) {
+                neighbours.extend(regions.iter().cloned());
+            }
+        }
+        for id in ids.iter() {
+            let region = match self.regions.get(id) {
+                Some(region) => *region,
+                None => continue,
+            };
+            let mut portals = Vec::new();
+            for other_id in neighbours.iter() {
+                let other = match self.regions.get(other_id) {
+                    Some(other) if other_id != id => *other,
+                    _ => continue,
+                };
+                let (start, end) = if let Some(edge) = region.shared_edge(&other) {
+                    edge
+                } else {
+                    continue;
+                };
+                portals.push(NavPortal {
+                    region: *other_id,
+                    start,
+                    end,
+                });
+                // The regions of this chunk add their own portals.
+                if other.chunk_point != chunk_point {
+                    self.portals.entry(*other_id).or_default().push(NavPortal {
+                        region: *id,
+                        start,
+                        end,
+                    });
+                }
+            }
+            self.portals.insert(*id, portals);
+        }
+        self.chunk_regions.insert(chunk_point, ids);
+    }
+}
@@ -0,0 +1,31 @@
+//! Serializing maps whose keys are not strings as sequences of key value
+//! pairs, so that tilemaps can be written to formats such as JSON.
+
+use crate::lib::*;
+
+/// Serializes a map as a sequence of its key value pairs.
+pub(crate) fn serialize<K, V, H, S>(
+    map: &::std::collections::HashMap<K, V, H>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    V: Serialize,
+    S: ::serde::Serializer,
+{
+    serializer.collect_seq(map.iter())
+}
+
+/// Deserializes a map from a sequence of its key value pairs.
+pub(crate) fn deserialize<'de, K, V, H, D>(
+    deserializer: D,
+) -> Result<::std::collections::HashMap<K, V, H>, D::Error>
+where
+    K: Deserialize<'de> + ::std::cmp::Eq + ::std::hash::Hash,
+    V: Deserialize<'de>,
+    H: BuildHasher + Default,
+    D: ::serde::Deserializer<'de>,
+{
+    let pairs: Vec<(K, V)> = Deserialize::deserialize(deserializer)?;
+    Ok(pairs.into_iter().collect())
+}
@@ -0,0 +1,162 @@
+//! Indexed color tilemaps which are colored from a palette.
+//!
+//! A tilemap with a palette, see [`TilemapBuilder::palette`], is drawn with
+//! the palette pipeline of its topology. The red channel of every pixel of the
+//! texture atlas is then the index of its color in the palette, from `0` to
+//! `255`, and its alpha is kept. The palette is a texture whose first row has
+//! the color of each index at that pixel.
+//!
+//! Palettes are swapped at runtime with [`Tilemap::set_palette`] without ever
+//! touching the chunk meshes, so the same tileset can be drawn in the colors
+//! of each team or of each season. [`Tilemap::blend_palette`] blends the
+//! palette into another one by an amount, for retro palette cycling and
+//! fades.
+//!
+//! Palettes take the place of the lit pipeline, so tilemaps with a palette
+//! are not lit by the lights of the `light` module.
+//!
+//! [`TilemapBuilder::palette`]: crate::tilemap::TilemapBuilder::palette
+//! [`Tilemap::set_palette`]: crate::tilemap::Tilemap::set_palette
+//! [`Tilemap::blend_palette`]: crate::tilemap::Tilemap::blend_palette
+//!
+//! # Team colors
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_render::texture::Texture;
+//! use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! // In production use strong handles from actual sources.
+//! let red_team = Handle::weak(HandleId::random::<Texture>());
+//! let blue_team = Handle::weak(HandleId::random::<Texture>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .palette(red_team.clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! // Half way through the fade to the colors of the blue team.
+//! harness.app().world.get_mut::<Tilemap>(entity).unwrap().blend_palette(blue_team.clone(), 0.5);
+//! harness.tick();
+//!
+//! let tilemap = harness.tilemap(entity).unwrap();
+//! assert_eq!(tilemap.palette(), Some(&red_team));
+//! assert_eq!(tilemap.palette_blend(), Some((&blue_team, 0.5)));
+//! ```
+
+use crate::{instance::TilemapData, lib::*, Tilemap};
+
+/// The names of the tilemap palette nodes in the render graph.
+pub mod node {
+    /// The node which binds the palettes of the chunks.
+    pub const CHUNK_PALETTE: &str = "chunk_palette";
+}
+
+/// The palettes of a chunk layer and how far they are blended, which are
+/// bound to its palette pipeline.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct ChunkPalette {
+    /// The handle of the palette of the tilemap.
+    palette: Handle<Texture>,
+    /// The handle of the palette which the colors are blended to, which is
+    /// the palette itself if there is no blend.
+    blend_palette: Handle<Texture>,
+    /// How far the colors are blended to the blend palette.
+    blend: f32,
+}
+
+impl ChunkPalette {
+    /// Constructs the palette of the chunks of a tilemap, which binds nothing
+    /// if it has no palette.
+    pub(crate) fn from_tilemap(tilemap: &Tilemap) -> ChunkPalette {
+        let palette = if let Some(palette) = tilemap.palette() {
+            palette.clone_weak()
+        } else {
+            return ChunkPalette::default();
+        };
+        let (blend_palette, blend) = match tilemap.palette_blend() {
+            Some((blend_palette, blend)) => (blend_palette.clone_weak(), blend),
+            None => (palette.clone_weak(), 0.0),
+        };
+        ChunkPalette {
+            palette,
+            blend_palette,
+            blend,
+        }
+    }
+}
+
+impl RenderResources for ChunkPalette {
+    fn render_resources_len(&self) -> usize {
+        3
+    }
+
+    fn get_render_resource(&self, index: usize) -> Option<&dyn RenderResource> {
+        match index {
+            0 => Some(&self.palette),
+            1 => Some(&self.blend_palette),
+            2 => Some(&self.blend),
+            _ => None,
+        }
+    }
+
+    fn get_render_resource_name(&self, index: usize) -> Option<&str> {
+        match index {
+            0 => Some("ChunkPalette_palette"),
+            1 => Some("ChunkPalette_blend_palette"),
+            2 => Some("ChunkPalette_blend"),
+            _ => None,
+        }
+    }
+
+    fn iter(&self) -> RenderResourceIterator<'_> {
+        RenderResourceIterator::new(self)
+    }
+}
+
+/// Sets the palettes of every chunk layer to those of its tilemap, and draws
+/// it with the pipeline of its tilemap as palettes are set or cleared.
+pub(crate) fn chunk_palettes(
+    tilemaps: Res<Assets<TilemapData>>,
+    map_query: Query<&Tilemap>,
+    instance_query: Query<&Handle<TilemapData>>,
+    mut chunk_query: Query<(&Parent, &mut RenderPipelines, &mut ChunkPalette)>,
+) {
+    for (parent, mut render_pipelines, mut chunk_palette) in chunk_query.iter_mut() {
+        let (pipeline_handle, palette) = if let Ok(tilemap) = map_query.get(**parent) {
+            (
+                tilemap.chunk_pipeline_handle(),
+                ChunkPalette::from_tilemap(tilemap),
+            )
+        } else if let Some(data) = instance_query
+            .get(**parent)
+            .ok()
+            .and_then(|handle| tilemaps.get(handle))
+        {
+            (data.chunk_pipeline_handle(), data.palette().clone())
+        } else {
+            continue;
+        };
+        let drawn_with = render_pipelines
+            .pipelines
+            .first()
+            .map(|render_pipeline| &render_pipeline.pipeline);
+        if drawn_with != Some(&pipeline_handle) {
+            render_pipelines.pipelines = vec![RenderPipeline::new(pipeline_handle)];
+        }
+        // Only write changes, which would otherwise upload every chunk again
+        // every frame.
+        if *chunk_palette != palette {
+            *chunk_palette = palette;
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Physics backends for the collisions of tiles.
+//!
+//! The tiles of a chunk layer are merged into as few rectangular
+//! [`TileCollider`]s as possible whenever a chunk is spawned or its tiles
+//! change. A [`TilemapPhysicsBackend`] turns those into whatever colliders a
+//! physics engine needs, and is added with the [`TilemapPhysicsPlugin`]. Only
+//! the colliders which cover changed tiles are replaced.
+//!
+//! With the `bevy_rapier2d` feature the `RapierPhysicsBackend` is added by
+//! the [`Tilemap2DPlugin`]. Other engines, or a simple AABB system, can
+//! implement the trait themselves.
+//!
+//! [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+//!
+//! A tilemap with a collision radius only keeps the colliders of the chunks
+//! near an entity with a [`CollisionTracker`], such as the player or an
+//! important NPC, and drops the others until one comes back into range.
+//!
+//! # Implementing a backend
+//! ```
+//! use bevy_ecs::prelude::*;
+//! use bevy_tilemap::{
+//!     physics::{TileCollider, TilemapPhysicsBackend, TilemapPhysicsPlugin},
+//!     prelude::*,
+//! };
+//!
+//! /// An axis aligned box that a custom system collides with.
+//! struct Aabb {
+//!     center: (f32, f32),
+//!     half_extents: (f32, f32),
+//! }
+//!
+//! #[derive(Default)]
+//! struct AabbBackend;
+//!
+//! impl TilemapPhysicsBackend for AabbBackend {
+//!     fn spawn_collider(
+//!         &self,
+//!         commands: &mut Commands,
+//!         _tilemap: &Tilemap,
+//!         _layer: &TilemapLayer,
+//!         collider: &TileCollider,
+//!     ) -> Option<Entity> {
+//!         commands
+//!             .spawn((Aabb {
+//!                 center: (collider.translation.x, collider.translation.y),
+//!                 half_extents: (collider.size.x / 2.0, collider.size.y / 2.0),
+//!             },))
+//!             .current_entity()
+//!     }
+//! }
+//!
+//! // Added to the app after the `Tilemap2DPlugin`.
+//! let plugin = TilemapPhysicsPlugin::<AabbBackend>::default();
+//! ```
+
+use crate::{lib::*, metrics::TilemapMetrics, stage, Tilemap, TilemapLayer};
+
+/// The kinds of colliders that are spawned for tiles.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColliderKind {
+    /// A collider which bodies can not pass through.
+    Solid,
+    /// A collider of one-way platforms, see [`TilemapLayer::one_way`].
+    OneWay,
+    /// A collider of a single sensor tile, which bodies pass through, see
+    /// [`TilemapLayer::sensor`].
+    Sensor,
+}
+
+/// A rectangle of tiles of a chunk layer which needs a collider.
+///
+/// It is also inserted as a component of the collider entity, so that a hit
+/// can be mapped back to the tiles, see [`Tilemap::collider_to_tile`].
+///
+/// [`Tilemap::collider_to_tile`]: crate::tilemap::Tilemap::collider_to_tile
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TileCollider {
+    /// The entity of the tilemap.
+    pub tilemap: Entity,
+    /// The point of the chunk.
+    pub chunk_point: Point2,
+    /// The z order of the layer.
+    pub z_order: usize,
+    /// The global point of the bottom left tile of the rectangle.
+    pub point: Point2,
+    /// The dimensions of the rectangle in tiles.
+    pub dimensions: Dimension2,
+    /// The translation of the center of the rectangle in pixels, with the
+    /// transform of the tilemap.
+    pub translation: Vec2,
+    /// The size of the rectangle in pixels, with the scale of the tilemap.
+    pub size: Vec2,
+    /// The rotation of the rectangle around its center in radians, which is
+    /// the rotation of the tilemap around the z axis.
+    pub rotation: f32,
+    /// The kind of the collider.
+    pub kind: ColliderKind,
+}
+
+impl TileCollider {
+    /// Returns `true` if the rectangle covers the global tile point.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_ecs::Entity;
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::physics::{ColliderKind, TileCollider};
+    /// use bevy_tilemap_types::{dimension::Dimension2, point::Point2};
+    ///
+    /// let collider = TileCollider {
+    ///     tilemap: Entity::new(0),
+    ///     chunk_point: Point2::new(0, 0),
+    ///     z_order: 0,
+    ///     point: Point2::new(-2, 1),
+    ///     dimensions: Dimension2::new(3, 1),
+    ///     translation: Vec2::new(-16.0, 48.0),
+    ///     size: Vec2::new(96.0, 32.0),
+    ///     rotation: 0.0,
+    ///     kind: ColliderKind::Solid,
+    /// };
+    ///
+    /// assert!(collider.contains((0, 1)));
+    /// assert!(!collider.contains((1, 1)));
+    /// ```
+    pub fn contains<P: Into<Point2>>(&self, point: P) -> bool {
+        let point: Point2 = point.into();
+        point.x >= self.point.x
+            && point.y >= self.point.y
+            && point.x < self.point.x + self.dimensions.width as i32
+            && point.y < self.point.y + self.dimensions.height as i32
+    }
+}
+
+/// A component of the entities around which tilemaps with a collision radius
+/// keep the colliders of their chunks.
+///
+/// # Examples
+/// ```
+/// use bevy_asset::{prelude::*, HandleId};
+/// use bevy_sprite::prelude::*;
+/// use bevy_tilemap::{physics::CollisionTracker, prelude::*};
+///
+/// // In production use a strong handle from an actual source.
+/// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+///
+/// // Only the chunks next to the chunk of a tracker get colliders.
+/// let tilemap = TilemapBuilder::new()
+///     .texture_atlas(texture_atlas_handle)
+///     .chunk_dimensions(4, 4)
+///     .tile_dimensions(32, 32)
+///     .collision_radius(1, 1)
+///     .auto_chunk()
+///     .finish()
+///     .unwrap();
+///
+/// // Spawned with the transform and global transform of the player.
+/// let tracker = CollisionTracker;
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct CollisionTracker;
+
+/// A physics engine integration, which spawns the colliders of tiles.
+///
+/// The backend is a resource, which the [`TilemapPhysicsPlugin`] adds if it
+/// was not added yet.
+pub trait TilemapPhysicsBackend: Send + Sync + 'static {
+    /// Returns if the tiles of the layer get colliders. Every layer does by
+    /// default.
+    fn has_colliders(&self, layer: &TilemapLayer) -> bool {
+        let _ = layer;
+        true
+    }
+
+    /// Spawns the collider entity of the rectangle of tiles, returning it.
+    ///
+    /// The entity is made a child of the chunk layer, so that it is despawned
+    /// with the chunk, and the collider is inserted as its component.
+    fn spawn_collider(
+        &self,
+        commands: &mut Commands,
+        tilemap: &Tilemap,
+        layer: &TilemapLayer,
+        collider: &TileCollider,
+    ) -> Option<Entity>;
+
+    /// Despawns a collider entity whose tiles changed.
+    fn despawn_collider(&self, commands: &mut Commands, entity: Entity) {
+        commands.despawn(entity);
+    }
+}
+
+/// The plugin which spawns the colliders of tiles with a physics backend.
+///
+/// It must be added after the [`Tilemap2DPlugin`], which adds the tilemap
+/// stage.
+///
+/// [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+pub struct TilemapPhysicsPlugin<B> {
+    /// The backend of the plugin, which is only a type.
+    backend: PhantomData<B>,
+}
+
+impl<B> Default for TilemapPhysicsPlugin<B> {
+    fn default() -> TilemapPhysicsPlugin<B> {
+        TilemapPhysicsPlugin {
+            backend: PhantomData,
+        }
+    }
+}
+
+impl<B> Plugin for TilemapPhysicsPlugin<B>
+where
+    B: TilemapPhysicsBackend + Default,
+{
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<B>()
+            .init_resource::<TilemapMetrics>()
+            .add_system_to_stage(
+                stage::TILEMAP,
+                crate::system::tilemap_collision_events::<B>.system(),
+            );
+    }
+}
@@ -0,0 +1,137 @@
+//! Picking tiles by reading a texture.
+//!
+//! Layers which are added with [`TilemapLayer::picking`] set have their chunks
+//! drawn a second time, in the tilemap picking pass. Instead of a color, every
+//! pixel of a tile which is visible in the main pass is written as the point
+//! and z order of the tile. Reading the pixel under the cursor from the
+//! picking texture then gives exactly the tile that is seen there, even for
+//! overlapping layers, iso tiles and tile art that does not fill its cell.
+//!
+//! The picking pass is added to the render graph with
+//! [`TilemapPickingGraphBuilder::add_tilemap_picking_pass`]. It is drawn with
+//! the 2D camera right after the main pass and needs a texture of the
+//! [`PICKING_TEXTURE_FORMAT`] connected to its [`PICKING_TEXTURE`] input slot,
+//! as well as a `Depth32Float` texture of the same size connected to its
+//! [`PICKING_DEPTH_TEXTURE`] input slot. Integer textures can not be
+//! multisampled, so the `Msaa` resource must have only 1 sample.
+//!
+//! The pixels which are read back can be turned into a tile with
+//! [`decode_pixel`].
+//!
+//! [`TilemapLayer::picking`]: crate::tilemap::TilemapLayer::picking
+//!
+//! # Adding a picking pass
+//! ```
+//! use bevy_ecs::Resources;
+//! use bevy_render::render_graph::RenderGraph;
+//! use bevy_tilemap::{picking::TilemapPickingGraphBuilder, prelude::*};
+//!
+//! let builder = TilemapBuilder::new()
+//!     .add_layer(TilemapLayer { kind: LayerKind::Dense, picking: true, ..Default::default() }, 0)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, picking: true, ..Default::default() }, 1);
+//!
+//! fn build_graph(render_graph: &mut RenderGraph, resources: &Resources) {
+//!     render_graph.add_tilemap_picking_pass(resources);
+//!     // The picking and depth textures still need to be connected to
+//!     // `node::TILEMAP_PICKING_PASS` with `RenderGraph::add_slot_edge`.
+//! }
+//! ```
+
+use crate::lib::*;
+
+/// The format of the picking texture.
+pub const PICKING_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba32Sint;
+
+/// The name of the input slot of the texture that the tiles are drawn to.
+pub const PICKING_TEXTURE: &str = "picking_texture";
+
+/// The name of the input slot of the depth texture of the picking pass.
+pub const PICKING_DEPTH_TEXTURE: &str = "picking_depth_texture";
+
+/// The names of the tilemap picking nodes in the render graph.
+pub mod node {
+    /// The tilemap picking pass which draws the pickable layers.
+    pub const TILEMAP_PICKING_PASS: &str = "tilemap_picking_pass";
+}
+
+/// A component that indicates that an entity should be drawn in the tilemap
+/// picking pass.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TilemapPickingPass;
+
+/// Returns the point and z order of the tile in a pixel of the picking
+/// texture, or `None` if there is no tile in it.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::{picking, prelude::*};
+///
+/// assert_eq!(picking::decode_pixel([0, 0, 0, 0]), None);
+/// assert_eq!(picking::decode_pixel([-3, 7, 1, 1]), Some(((-3, 7).into(), 1)));
+/// ```
+pub fn decode_pixel(pixel: [i32; 4]) -> Option<(Point2, usize)> {
+    let [x, y, z_order, has_tile] = pixel;
+    if has_tile == 0 {
+        None
+    } else {
+        Some((Point2::new(x, y), z_order as usize))
+    }
+}
+
+/// A trait which adds the tilemap picking pass to a render graph.
+pub trait TilemapPickingGraphBuilder: private::Sealed {
+    /// Adds the tilemap picking pass, which is drawn after the main pass.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Msaa` resource has more than 1 sample.
+    fn add_tilemap_picking_pass(&mut self, resources: &Resources) -> &mut Self;
+}
+
+impl TilemapPickingGraphBuilder for RenderGraph {
+    fn add_tilemap_picking_pass(&mut self, resources: &Resources) -> &mut Self {
+        let msaa = resources.get::<Msaa>().expect("`Msaa` is missing.");
+        ::std::assert_eq!(
+            msaa.samples,
+            1,
+            "The tilemap picking pass needs `Msaa` to have only 1 sample."
+        );
+
+        let mut picking_pass_node = PassNode::<&TilemapPickingPass>::new(PassDescriptor {
+            color_attachments: vec![RenderPassColorAttachmentDescriptor {
+                attachment: TextureAttachment::Input(PICKING_TEXTURE.to_string()),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::NONE),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                attachment: TextureAttachment::Input(PICKING_DEPTH_TEXTURE.to_string()),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            sample_count: 1,
+        });
+        picking_pass_node.add_camera(base::camera::CAMERA_2D);
+
+        self.add_node(node::TILEMAP_PICKING_PASS, picking_pass_node);
+        self.add_node_edge(base::node::MAIN_PASS, node::TILEMAP_PICKING_PASS)
+            .expect("The main pass is missing from the render graph.");
+
+        self
+    }
+}
+
+/// Prevents the traits in this module from being implemented outside the crate.
+mod private {
+    use super::RenderGraph;
+
+    /// Seals the type.
+    pub trait Sealed {}
+
+    impl Sealed for RenderGraph {}
+}
@@ -0,0 +1,99 @@
+//! One-way platform collisions for platformers.
+//!
+//! The tiles of a layer which is added with [`TilemapLayer::one_way`] set, or
+//! the tiles of a sprite index which is set with
+//! [`Tilemap::set_one_way_sprite`], get one-way platform colliders. Bodies can
+//! jump up through them and walk along them, but land on them when falling
+//! from above.
+//!
+//! Rapier can only do this with a contact pair filter, which must be set in
+//! its `InteractionPairFilters` resource after the `RapierPhysicsPlugin` is
+//! added. The [`OneWayPlatformFilter`] only changes pairs with a one-way
+//! platform and otherwise acts the same as rapier does without a filter.
+//!
+//! [`TilemapLayer::one_way`]: crate::tilemap::TilemapLayer::one_way
+//! [`Tilemap::set_one_way_sprite`]: crate::tilemap::Tilemap::set_one_way_sprite
+//!
+//! # Setting up the filter
+//! ```
+//! use bevy_app::prelude::*;
+//! use bevy_rapier2d::physics::{InteractionPairFilters, RapierPhysicsPlugin};
+//! use bevy_tilemap::platform::OneWayPlatformFilter;
+//!
+//! let mut app = App::build();
+//! app.add_plugin(RapierPhysicsPlugin)
+//!     .add_resource(InteractionPairFilters::new().contact_filter(OneWayPlatformFilter::default()));
+//! ```
+
+use crate::lib::*;
+
+/// The user data of the colliders of one-way platforms.
+pub const ONE_WAY_PLATFORM_USER_DATA: u128 = 0x6f6e_655f_7761_795f_706c_6174_666f_726d;
+
+/// A contact pair filter which lets bodies pass through one-way platforms
+/// unless they are falling onto them from above.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OneWayPlatformFilter {
+    /// How far a body may have sunk into the top of a platform and still
+    /// stand on it, in physics units.
+    pub tolerance: f32,
+}
+
+impl Default for OneWayPlatformFilter {
+    fn default() -> OneWayPlatformFilter {
+        OneWayPlatformFilter { tolerance: 0.1 }
+    }
+}
+
+impl OneWayPlatformFilter {
+    /// Returns if a body, which has a collider with the bounding box, is
+    /// standing on or falling onto the platform.
+    fn is_above(&self, platform: &Collider, body: &RigidBody, collider: &Collider) -> bool {
+        let platform_top = platform.compute_aabb().maxs.y;
+        let body_bottom = collider.compute_aabb().mins.y;
+        body.linvel().y <= 0.0 && body_bottom >= platform_top - self.tolerance
+    }
+}
+
+impl ContactPairFilter for OneWayPlatformFilter {
+    fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+        let is_platform1 = is_one_way_platform(context.collider1);
+        let is_platform2 = is_one_way_platform(context.collider2);
+        let has_contact = if is_platform1 && !is_platform2 {
+            context.rigid_body2.is_dynamic()
+                && self.is_above(context.collider1, context.rigid_body2, context.collider2)
+        } else if is_platform2 && !is_platform1 {
+            context.rigid_body1.is_dynamic()
+                && self.is_above(context.collider2, context.rigid_body1, context.collider1)
+        } else {
+            // The default rule of rapier, no contact between two non-dynamic
+            // bodies.
+            context.rigid_body1.is_dynamic() || context.rigid_body2.is_dynamic()
+        };
+
+        if has_contact {
+            Some(SolverFlags::COMPUTE_IMPULSES)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns if a collider is the collider of a one-way platform.
+///
+/// # Examples
+/// ```
+/// use bevy_rapier2d::rapier::geometry::ColliderBuilder;
+/// use bevy_tilemap::platform;
+///
+/// let platform = ColliderBuilder::cuboid(1.0, 0.5)
+///     .user_data(platform::ONE_WAY_PLATFORM_USER_DATA)
+///     .build();
+/// let wall = ColliderBuilder::cuboid(1.0, 0.5).build();
+///
+/// assert!(platform::is_one_way_platform(&platform));
+/// assert!(!platform::is_one_way_platform(&wall));
+/// ```
+pub fn is_one_way_platform(collider: &Collider) -> bool {
+    collider.user_data == ONE_WAY_PLATFORM_USER_DATA
+}
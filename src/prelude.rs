@@ -7,13 +7,16 @@
 //!
 //! * [`bevy_tilemap::chunk`]::[`LayerKind`], the only public part
 //! of `chunk` module is the kind of layer you need to specify to create.
+//! * `bevy_tilemap::color::Color`, tints without the `render` feature.
+//! * [`bevy_tilemap::command`]::[`TilemapCommands`], deferred tilemap edits.
 //! * [`bevy_tilemap::default_plugin`]::[`TilemapDefaultPlugins`], the
 //! default plugins for the library.
-//! * [`bevy_tilemap::entity`]::[`TilemapBundle`], the component bundle
-//! for spawning with a Tilemap.
+//! * [`bevy_tilemap::entity`]::{[`TilemapBundle`], [`TilemapInstanceBundle`]},
+//! the component bundles for spawning with a Tilemap or a TilemapData asset.
+//! * [`bevy_tilemap::instance`]::[`TilemapData`], tiles for instances.
 //! * [`bevy_tilemap::tile`]::[`Tile`], a sprite tile which
 //! holds minimal amount of data for small data sizes.
-//! * [`bevy_tilemap::tilemap`]::{[`Tilemap`], [`TilemapBuilder`]},
+//! * [`bevy_tilemap::tilemap`]::{[`Neighborhood`], [`Tilemap`], [`TilemapBuilder`]},
 //! the core object that is used for virtually everything in this library.
 //! * [`bevy_tilemap`]::[`Tilemap2DPlugin`], the main plugin with
 //! a collection of systems, components and assets to be used in a Bevy app.
@@ -21,6 +24,8 @@
 //! [`bevy_tilemap::prelude::v0`]: crate::prelude::v0
 //! [`bevy_tilemap::default_plugin`]: crate::default_plugin
 //! [`bevy_tilemap::chunk`]: crate::chunk
+//! [`bevy_tilemap::command`]: crate::command
+//! [`bevy_tilemap::instance`]: crate::instance
 //! [`bevy_tilemap::entity`]: crate::entity
 //! [`bevy_tilemap::tile`]: crate::tile
 //! [`bevy_tilemap::tilemap`]: crate::tilemap
@@ -28,14 +33,19 @@
 
 /// Version 0 prelude.
 pub mod v0 {
+    #[cfg(not(feature = "render"))]
+    pub use crate::color::Color;
     pub use crate::{
         chunk::{render::GridTopology, LayerKind},
+        command::TilemapCommands,
         default_plugin::TilemapDefaultPlugins,
         entity::TilemapBundle,
         tile::Tile,
-        tilemap::{Tilemap, TilemapBuilder, TilemapLayer},
+        tilemap::{Neighborhood, Tilemap, TilemapBuilder, TilemapLayer},
         Tilemap2DPlugin,
     };
+    #[cfg(feature = "render")]
+    pub use crate::{entity::TilemapInstanceBundle, instance::TilemapData};
 }
 
 pub use v0::*;
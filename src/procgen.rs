@@ -0,0 +1,439 @@
+//! Ready-made generators of endless worlds from noise.
+//!
+//! A [`ChunkGenerator`] fills the tiles of a region, such as those of a
+//! chunk. Once one is set with [`Tilemap::set_chunk_generator`], chunks which
+//! are spawned but do not exist yet are generated first, so a tilemap which
+//! auto spawns scrolls through an endless world.
+//!
+//! [`HeightmapGenerator`] turns [`Noise`] into a heightmap and picks the tile
+//! of each point from the thresholds its height is below, such as water,
+//! sand, grass and then mountains. [`BiomeGenerator`] picks one of a few
+//! heightmaps from a second noise of the climate, and blends the tiles of
+//! biomes next to each other along their borders.
+//!
+//! The noise is picked from a seed, so the same seed always generates the
+//! same world, on every platform. This is behind the `procgen` feature.
+//!
+//! [`Tilemap::set_chunk_generator`]: crate::tilemap::Tilemap::set_chunk_generator
+//!
+//! # An island world
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{
+//!     prelude::*,
+//!     procgen::{HeightmapGenerator, Noise},
+//! };
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(16, 16)
+//!     .tile_dimensions(32, 32)
+//!     .auto_spawn(2, 2)
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Water, sand, grass and then mountains.
+//! let generator = HeightmapGenerator::new(Noise::simplex(42).with_frequency(0.05).with_octaves(3))
+//!     .with_threshold(0.4, 1)
+//!     .with_threshold(0.45, 2)
+//!     .with_threshold(0.7, 3)
+//!     .with_threshold(1.0, 4);
+//! tilemap.set_chunk_generator(generator);
+//!
+//! // The chunk does not exist until it is spawned.
+//! assert!(tilemap.get_tile((1600, -48), 0).is_none());
+//! tilemap.spawn_chunk((100, -3)).unwrap();
+//!
+//! let index = tilemap.get_tile((1600, -48), 0).unwrap().index;
+//! assert!((1..=4).contains(&index));
+//! ```
+
+use crate::{lib::*, tile::Tile, variant::position_hash};
+
+/// Generates the tiles of regions, such as those of chunks.
+pub trait ChunkGenerator: Debug + Send + Sync {
+    /// Returns the tiles of the region of the dimensions from its bottom left
+    /// point.
+    fn generate(&self, from: Point2, dimensions: (u32, u32)) -> Vec<Tile<Point2>>;
+}
+
+/// The algorithms which noise can be made with.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NoiseKind {
+    /// Perlin noise, on a square grid.
+    Perlin,
+    /// Simplex noise, on a grid of triangles, which has fewer artifacts along
+    /// the axes.
+    Simplex,
+}
+
+/// Smooth noise from a seed, with octaves of finer detail on top.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Noise {
+    /// The algorithm of the noise.
+    pub kind: NoiseKind,
+    /// The seed which the noise is picked from.
+    pub seed: u64,
+    /// How many features of the first octave there are per tile, usually
+    /// much less than 1.
+    pub frequency: f64,
+    /// How many octaves of noise there are, each at twice the frequency and
+    /// half the amplitude of the one before it.
+    pub octaves: u32,
+}
+
+impl Noise {
+    /// Constructs new Perlin noise with the seed, with one octave at a
+    /// frequency of 0.1.
+    pub fn perlin(seed: u64) -> Noise {
+        Noise {
+            kind: NoiseKind::Perlin,
+            seed,
+            frequency: 0.1,
+            octaves: 1,
+        }
+    }
+
+    /// Constructs new simplex noise with the seed, with one octave at a
+    /// frequency of 0.1.
+    pub fn simplex(seed: u64) -> Noise {
+        Noise {
+            kind: NoiseKind::Simplex,
+            ..Noise::perlin(seed)
+        }
+    }
+
+    /// Sets how many features of the first octave there are per tile.
+    pub fn with_frequency(mut self, frequency: f64) -> Noise {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Sets how many octaves of noise there are.
+    pub fn with_octaves(mut self, octaves: u32) -> Noise {
+        self.octaves = octaves;
+        self
+    }
+
+    /// Returns the noise at the point, between 0 and 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::procgen::Noise;
+    ///
+    /// let noise = Noise::perlin(7).with_octaves(4);
+    ///
+    /// let value = noise.get(3.5, -12.25);
+    /// assert!((0.0..=1.0).contains(&value));
+    /// assert_eq!(noise.get(3.5, -12.25), value);
+    /// assert_ne!(Noise::perlin(8).with_octaves(4).get(3.5, -12.25), value);
+    /// ```
+    pub fn get(&self, x: f64, y: f64) -> f64 {
+        let mut total = 0.0;
+        let mut amplitudes = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = self.frequency;
+        for octave in 0..self.octaves.max(1) {
+            let seed = self.seed.wrapping_add(u64::from(octave));
+            let value = match self.kind {
+                NoiseKind::Perlin => perlin(seed, x * frequency, y * frequency),
+                NoiseKind::Simplex => simplex(seed, x * frequency, y * frequency),
+            };
+            total += value * amplitude;
+            amplitudes += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        ((total / amplitudes + 1.0) / 2.0).clamp(0.0, 1.0)
+    }
+
+    /// Returns the noise at the middle of the tile at the point, between 0
+    /// and 1.
+    pub fn get_point<P: Into<Point2>>(&self, point: P) -> f64 {
+        let point: Point2 = point.into();
+        self.get(f64::from(point.x) + 0.5, f64::from(point.y) + 0.5)
+    }
+}
+
+/// A generator which picks the tile of each point from the height of noise.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct HeightmapGenerator {
+    /// The noise of the heights.
+    pub noise: Noise,
+    /// The heights which the tiles are below, from the lowest, with their
+    /// sprite indexes. Points at or above the highest have no tile.
+    pub thresholds: Vec<(f64, usize)>,
+    /// The layer of the tiles.
+    pub z_order: usize,
+}
+
+impl HeightmapGenerator {
+    /// Constructs a new generator of the noise without any thresholds, whose
+    /// tiles are on the layer at 0.
+    pub fn new(noise: Noise) -> HeightmapGenerator {
+        HeightmapGenerator {
+            noise,
+            thresholds: Vec::new(),
+            z_order: 0,
+        }
+    }
+
+    /// Adds the sprite index of the tiles whose height is below the height
+    /// and at or above the thresholds below it.
+    pub fn with_threshold(mut self, height: f64, sprite_index: usize) -> HeightmapGenerator {
+        self.thresholds.push((height, sprite_index));
+        self.thresholds
+            .sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        self
+    }
+
+    /// Sets the layer of the tiles.
+    pub fn with_z_order(mut self, z_order: usize) -> HeightmapGenerator {
+        self.z_order = z_order;
+        self
+    }
+
+    /// Returns the sprite index of the tile at the point, or `None` if its
+    /// height is at or above the highest threshold.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::procgen::{HeightmapGenerator, Noise};
+    ///
+    /// let generator = HeightmapGenerator::new(Noise::perlin(3))
+    ///     .with_threshold(1.0, 5)
+    ///     .with_threshold(0.5, 4);
+    ///
+    /// let height = generator.noise.get_point((2, 9));
+    /// let expected = if height < 0.5 { 4 } else { 5 };
+    /// assert_eq!(generator.sprite_index_at((2, 9)), Some(expected));
+    /// ```
+    pub fn sprite_index_at<P: Into<Point2>>(&self, point: P) -> Option<usize> {
+        let height = self.noise.get_point(point);
+        self.thresholds
+            .iter()
+            .find(|(threshold, _)| height < *threshold)
+            .map(|(_, sprite_index)| *sprite_index)
+    }
+}
+
+impl ChunkGenerator for HeightmapGenerator {
+    fn generate(&self, from: Point2, dimensions: (u32, u32)) -> Vec<Tile<Point2>> {
+        region(from, dimensions)
+            .filter_map(|point| {
+                Some(Tile {
+                    point,
+                    sprite_index: self.sprite_index_at(point)?,
+                    z_order: self.z_order,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+}
+
+/// A heightmap which is used where the climate is near its own.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Biome {
+    /// The climate of the biome, between 0 and 1.
+    pub climate: f64,
+    /// The heightmap of the biome.
+    pub heightmap: HeightmapGenerator,
+}
+
+/// A generator which picks one of a few biomes at each point from the noise
+/// of the climate.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct BiomeGenerator {
+    /// The noise of the climate, such as from cold to hot.
+    pub climate: Noise,
+    /// The biomes.
+    pub biomes: Vec<Biome>,
+    /// How close the climate must be to halfway between two biomes for their
+    /// tiles to be mixed.
+    pub blend: f64,
+}
+
+impl BiomeGenerator {
+    /// Constructs a new generator of the noise of the climate without any
+    /// biomes, which blends them by 0.05.
+    pub fn new(climate: Noise) -> BiomeGenerator {
+        BiomeGenerator {
+            climate,
+            biomes: Vec::new(),
+            blend: 0.05,
+        }
+    }
+
+    /// Adds a biome with its climate.
+    pub fn with_biome(mut self, climate: f64, heightmap: HeightmapGenerator) -> BiomeGenerator {
+        self.biomes.push(Biome { climate, heightmap });
+        self
+    }
+
+    /// Sets how close the climate must be to halfway between two biomes for
+    /// their tiles to be mixed.
+    pub fn with_blend(mut self, blend: f64) -> BiomeGenerator {
+        self.blend = blend;
+        self
+    }
+
+    /// Returns the biome at the point, or `None` if there are no biomes.
+    ///
+    /// The biome of the nearest climate is picked. Near halfway between two
+    /// biomes, the second nearest is picked more often the nearer the point
+    /// is to halfway, so that their tiles are mixed instead of meeting in a
+    /// hard line.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::procgen::{BiomeGenerator, HeightmapGenerator, Noise};
+    ///
+    /// let generator = BiomeGenerator::new(Noise::perlin(1))
+    ///     .with_biome(0.0, HeightmapGenerator::new(Noise::perlin(2)).with_threshold(1.0, 7))
+    ///     .with_biome(1.0, HeightmapGenerator::new(Noise::perlin(3)).with_threshold(1.0, 8))
+    ///     .with_blend(0.0);
+    ///
+    /// let climate = generator.climate.get_point((4, 4));
+    /// let expected = if climate < 0.5 { 0.0 } else { 1.0 };
+    /// assert_eq!(generator.biome_at((4, 4)).unwrap().climate, expected);
+    /// ```
+    pub fn biome_at<P: Into<Point2>>(&self, point: P) -> Option<&Biome> {
+        let point: Point2 = point.into();
+        let climate = self.climate.get_point(point);
+        let distance = |biome: &Biome| (biome.climate - climate).abs();
+        let mut biomes: Vec<&Biome> = self.biomes.iter().collect();
+        biomes.sort_by(|a, b| {
+            distance(a)
+                .partial_cmp(&distance(b))
+                .unwrap_or(Ordering::Equal)
+        });
+        let (nearest, second) = match (biomes.first(), biomes.get(1)) {
+            (Some(nearest), Some(second)) => (*nearest, *second),
+            (nearest, _) => return nearest.copied(),
+        };
+        let gap = distance(second) - distance(nearest);
+        if gap >= self.blend {
+            return Some(nearest);
+        }
+        // Halfway between the biomes, either is as likely.
+        let chance = 0.5 * (1.0 - gap / self.blend);
+        let roll = position_hash(self.climate.seed ^ BLEND_SEED, point) as f64 / u64::MAX as f64;
+        if roll < chance {
+            Some(second)
+        } else {
+            Some(nearest)
+        }
+    }
+}
+
+impl ChunkGenerator for BiomeGenerator {
+    fn generate(&self, from: Point2, dimensions: (u32, u32)) -> Vec<Tile<Point2>> {
+        region(from, dimensions)
+            .filter_map(|point| {
+                let heightmap = &self.biome_at(point)?.heightmap;
+                Some(Tile {
+                    point,
+                    sprite_index: heightmap.sprite_index_at(point)?,
+                    z_order: heightmap.z_order,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+}
+
+/// Mixed into the seed of the climate for the rolls of blending, so that they
+/// do not follow the noise.
+const BLEND_SEED: u64 = 0x5851_f42d_4c95_7f2d;
+
+/// The gradients of the noise, picked by the hash of a point of its grid.
+const GRADIENTS: [(f64, f64); 8] = [
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+/// Returns the points of the region of the dimensions from its bottom left
+/// point.
+fn region(from: Point2, dimensions: (u32, u32)) -> impl Iterator<Item = Point2> {
+    let (width, height) = (dimensions.0 as i32, dimensions.1 as i32);
+    (0..height).flat_map(move |y| (0..width).map(move |x| Point2::new(from.x + x, from.y + y)))
+}
+
+/// Returns the dot product of the gradient of the point of the grid with the
+/// offset from it.
+fn gradient(seed: u64, x: i32, y: i32, dx: f64, dy: f64) -> f64 {
+    let (gx, gy) = GRADIENTS
+        .get((position_hash(seed, Point2::new(x, y)) & 7) as usize)
+        .copied()
+        .unwrap_or((1.0, 0.0));
+    gx * dx + gy * dy
+}
+
+/// Returns Perlin noise at the point, between about -1 and 1.
+fn perlin(seed: u64, x: f64, y: f64) -> f64 {
+    let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let lerp = |t: f64, a: f64, b: f64| a + t * (b - a);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (xi, yi) = (x0 as i32, y0 as i32);
+    let (dx, dy) = (x - x0, y - y0);
+    let (u, v) = (fade(dx), fade(dy));
+    let bottom = lerp(
+        u,
+        gradient(seed, xi, yi, dx, dy),
+        gradient(seed, xi + 1, yi, dx - 1.0, dy),
+    );
+    let top = lerp(
+        u,
+        gradient(seed, xi, yi + 1, dx, dy - 1.0),
+        gradient(seed, xi + 1, yi + 1, dx - 1.0, dy - 1.0),
+    );
+    // The gradients are of unit length, which reaches at most half of the
+    // square root of 2.
+    lerp(v, bottom, top) * SQRT_2
+}
+
+/// Returns simplex noise at the point, between about -1 and 1.
+fn simplex(seed: u64, x: f64, y: f64) -> f64 {
+    // Skews the square grid into triangles and back.
+    const SKEW: f64 = 0.366_025_403_784_438_6;
+    const UNSKEW: f64 = 0.211_324_865_405_187_1;
+    let s = (x + y) * SKEW;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * UNSKEW;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let (i, j) = (i as i32, j as i32);
+    let corner = |ci: i32, cj: i32, dx: f64, dy: f64| {
+        let t = 0.5 - dx * dx - dy * dy;
+        if t < 0.0 {
+            0.0
+        } else {
+            t * t * t * t * gradient(seed, i + ci, j + cj, dx, dy)
+        }
+    };
+    let sum = corner(0, 0, x0, y0)
+        + corner(
+            i1,
+            j1,
+            x0 - f64::from(i1) + UNSKEW,
+            y0 - f64::from(j1) + UNSKEW,
+        )
+        + corner(1, 1, x0 - 1.0 + 2.0 * UNSKEW, y0 - 1.0 + 2.0 * UNSKEW);
+    sum * 99.0
+}
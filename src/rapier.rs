@@ -0,0 +1,84 @@
+//! The rapier physics backend.
+//!
+//! Each tile collider is a cuboid with its own static rigid body, in the
+//! interaction and solver groups of its layer. One-way platforms get the user
+//! data of the [`platform`] module and sensor tiles are rapier sensors, whose
+//! overlaps are sent as trigger events of the tilemap.
+//!
+//! This backend is added by the [`Tilemap2DPlugin`] when the `bevy_rapier2d`
+//! feature is enabled.
+//!
+//! [`platform`]: crate::platform
+//! [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+
+use crate::{
+    lib::*,
+    physics::{ColliderKind, TileCollider, TilemapPhysicsBackend},
+    platform::ONE_WAY_PLATFORM_USER_DATA,
+    Tilemap, TilemapLayer,
+};
+
+/// The physics backend for `bevy_rapier2d`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct RapierPhysicsBackend;
+
+impl TilemapPhysicsBackend for RapierPhysicsBackend {
+    fn has_colliders(&self, layer: &TilemapLayer) -> bool {
+        layer.interaction_groups.with_mask(0).0 != 0
+    }
+
+    fn spawn_collider(
+        &self,
+        commands: &mut Commands,
+        tilemap: &Tilemap,
+        layer: &TilemapLayer,
+        collider: &TileCollider,
+    ) -> Option<Entity> {
+        let physics_scale = tilemap.physics_scale();
+        let mut collider_builder = ColliderBuilder::cuboid(
+            collider.size.x / 2.0 / physics_scale,
+            collider.size.y / 2.0 / physics_scale,
+        )
+        .collision_groups(layer.interaction_groups)
+        .solver_groups(layer.solver_groups);
+        match collider.kind {
+            ColliderKind::Solid => {}
+            ColliderKind::OneWay => {
+                collider_builder = collider_builder.user_data(ONE_WAY_PLATFORM_USER_DATA)
+            }
+            ColliderKind::Sensor => collider_builder = collider_builder.sensor(true),
+        }
+
+        commands.spawn((
+            RigidBodyBuilder::new_static()
+                .translation(
+                    collider.translation.x / physics_scale,
+                    collider.translation.y / physics_scale,
+                )
+                .rotation(collider.rotation),
+            collider_builder,
+        ));
+        if collider.kind == ColliderKind::Sensor {
+            commands.with(TileSensor {
+                point: collider.point,
+                z_order: collider.z_order,
+                tilemap: collider.tilemap,
+                overlapping: HashSet::default(),
+            });
+        }
+        commands.current_entity()
+    }
+}
+
+/// The collider of a sensor tile.
+#[derive(Debug)]
+pub(crate) struct TileSensor {
+    /// The global point of the tile.
+    pub(crate) point: Point2,
+    /// The z order of the tile.
+    pub(crate) z_order: usize,
+    /// The entity of the tilemap the tile is in.
+    pub(crate) tilemap: Entity,
+    /// The colliders which overlapped the sensor in the last frame.
+    pub(crate) overlapping: HashSet<ColliderHandle>,
+}
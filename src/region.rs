@@ -0,0 +1,213 @@
+//! Regions of tiles which are copied and pasted.
+//!
+//! [`Tilemap::copy_region`] copies the tiles of every layer in a rectangle of
+//! points into a [`TileRegion`], with their z orders, colors, offsets and
+//! transforms, and [`Tilemap::paste_region`] pastes them back at another
+//! point, or into another tilemap with the same layers. Regions are how an
+//! editor copies a part of a map, and how prefabs such as rooms or houses are
+//! stitched into generated maps. With the `serde` feature, regions can be
+//! saved as prefabs.
+//!
+//! A region can be turned and flipped before it is pasted. Only where the
+//! tiles are is turned, their sprites are drawn as they are, and on hex and
+//! iso tilemaps the tiles are moved within the rectangle of the region like
+//! on square tilemaps.
+//!
+//! [`Tilemap::copy_region`]: crate::tilemap::Tilemap::copy_region
+//! [`Tilemap::paste_region`]: crate::tilemap::Tilemap::paste_region
+//!
+//! # Stamping a prefab
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(8, 8)
+//!     .tile_dimensions(16, 16)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // A hut of 3 by 2 tiles, with a door on the layer above the floor.
+//! for x in 0..3 {
+//!     for y in 0..2 {
+//!         tilemap.insert_tile(Tile { point: (x, y), sprite_index: 1, ..Default::default() }).unwrap();
+//!     }
+//! }
+//! tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 2, z_order: 1, ..Default::default() }).unwrap();
+//!
+//! let hut = tilemap.copy_region((0, 0), (2, 1));
+//! assert_eq!(hut.dimensions(), (3, 2));
+//! assert_eq!(hut.len(), 7);
+//!
+//! // Turned a quarter counterclockwise, so the door faces east.
+//! tilemap.paste_region((10, 10), &hut.rotated(1)).unwrap();
+//! assert_eq!(tilemap.get_tile((11, 11), 1).unwrap().index, 2);
+//! assert_eq!(tilemap.get_tile((10, 12), 0).unwrap().index, 1);
+//! assert!(tilemap.get_tile((12, 10), 0).is_none());
+//! ```
+
+use crate::{chunk::RawTile, lib::*, tile::TileTransform};
+
+/// A tile of a region.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RegionTile {
+    /// The point of the tile from the lower left corner of the region.
+    pub point: Point2,
+    /// The z order of the layer of the tile.
+    pub z_order: usize,
+    /// The sprite index, color, extra data and offset of the tile.
+    pub tile: RawTile,
+    /// The rotation and scale of the tile, if it is on a decoration layer and
+    /// has one.
+    pub transform: Option<TileTransform>,
+}
+
+/// The tiles of every layer in a rectangle of points.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TileRegion {
+    /// The width of the region in tiles.
+    width: u32,
+    /// The height of the region in tiles.
+    height: u32,
+    /// The tiles of the region, ordered by their points and z orders.
+    tiles: Vec<RegionTile>,
+}
+
+impl TileRegion {
+    /// Constructs a new region of the dimensions from its tiles.
+    pub(crate) fn new(width: u32, height: u32, tiles: Vec<RegionTile>) -> TileRegion {
+        TileRegion {
+            width,
+            height,
+            tiles,
+        }
+    }
+
+    /// Returns the width and height of the region in tiles.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the tiles of the region.
+    pub fn tiles(&self) -> &[RegionTile] {
+        &self.tiles
+    }
+
+    /// Returns how many tiles the region has, counting every layer.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns `true` if the region has no tiles.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Returns the tile at a point of the region and a z order.
+    pub fn get<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<&RawTile> {
+        let point: Point2 = point.into();
+        self.tiles
+            .iter()
+            .find(|tile| tile.point == point && tile.z_order == z_order)
+            .map(|tile| &tile.tile)
+    }
+
+    /// Returns the region turned counterclockwise by a number of quarter
+    /// turns.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (2, 0), sprite_index: 4, ..Default::default() }).unwrap();
+    ///
+    /// let region = tilemap.copy_region((0, 0), (2, 0));
+    /// assert_eq!(region.rotated(1).dimensions(), (1, 3));
+    /// assert_eq!(region.rotated(1).get((0, 2), 0).unwrap().index, 4);
+    /// assert_eq!(region.rotated(2).get((0, 0), 0).unwrap().index, 4);
+    /// assert_eq!(region.rotated(4), region);
+    /// ```
+    pub fn rotated(&self, quarter_turns: u32) -> TileRegion {
+        let mut region = self.clone();
+        for _ in 0..quarter_turns % 4 {
+            let height = region.height as i32;
+            for tile in region.tiles.iter_mut() {
+                tile.point = Point2::new(height - 1 - tile.point.y, tile.point.x);
+            }
+            ::std::mem::swap(&mut region.width, &mut region.height);
+        }
+        region.sort();
+        region
+    }
+
+    /// Returns the region mirrored from left to right.
+    pub fn flipped_x(&self) -> TileRegion {
+        let mut region = self.clone();
+        let width = region.width as i32;
+        for tile in region.tiles.iter_mut() {
+            tile.point.x = width - 1 - tile.point.x;
+        }
+        region.sort();
+        region
+    }
+
+    /// Returns the region mirrored from top to bottom.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 2), sprite_index: 4, ..Default::default() }).unwrap();
+    ///
+    /// let region = tilemap.copy_region((0, 0), (1, 2));
+    /// assert_eq!(region.flipped_y().get((1, 0), 0).unwrap().index, 4);
+    /// assert_eq!(region.flipped_x().get((0, 2), 0).unwrap().index, 4);
+    /// ```
+    pub fn flipped_y(&self) -> TileRegion {
+        let mut region = self.clone();
+        let height = region.height as i32;
+        for tile in region.tiles.iter_mut() {
+            tile.point.y = height - 1 - tile.point.y;
+        }
+        region.sort();
+        region
+    }
+
+    /// Orders the tiles by their points and z orders, so that regions with
+    /// the same tiles are equal.
+    fn sort(&mut self) {
+        self.tiles
+            .sort_by_key(|tile| (tile.point.y, tile.point.x, tile.z_order));
+    }
+}
@@ -0,0 +1,177 @@
+//! Render layers which decide which cameras draw which tilemaps and layers.
+//!
+//! A minimap camera which only draws the ground of the world, or a camera of
+//! the UI which draws a tilemap of its own, puts [`RenderLayers`] on its
+//! entity. Tilemaps are given render layers with
+//! [`TilemapBuilder::render_layers`] or [`Tilemap::set_render_layers`], and
+//! each of their layers may have its own with [`TilemapLayer::render_layers`].
+//! The chunks of a layer are then only drawn by the cameras which draw any of
+//! its render layers.
+//!
+//! Cameras and tilemaps without render layers are in the first one, so every
+//! camera draws every tilemap until render layers are set. Other entities
+//! with render layers, such as sprites, are drawn the same way.
+//!
+//! [`TilemapBuilder::render_layers`]: crate::tilemap::TilemapBuilder::render_layers
+//! [`Tilemap::set_render_layers`]: crate::tilemap::Tilemap::set_render_layers
+//! [`TilemapLayer::render_layers`]: crate::tilemap::TilemapLayer::render_layers
+//!
+//! # A minimap of the ground
+//! ```
+//! use bevy_tilemap::{prelude::*, render_layers::RenderLayers, testing::tilemap_test_harness};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+//!     .render_layers(RenderLayers::layer(0).with(1))
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // The trees are left out of the minimap, which draws render layer 1.
+//! tilemap.set_layer_render_layers(1, Some(RenderLayers::layer(0))).unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 2, z_order: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! let chunks = harness.chunk_entities(entity, (0, 0));
+//! let world = &harness.app().world;
+//! let layers: Vec<RenderLayers> = chunks
+//!     .iter()
+//!     .map(|chunk| *world.get::<RenderLayers>(*chunk).unwrap())
+//!     .collect();
+//! assert_eq!(layers, vec![RenderLayers::layer(0).with(1), RenderLayers::layer(0)]);
+//! ```
+
+use crate::{chunk::entity::ZOrder, instance::TilemapData, lib::*, Tilemap};
+
+/// The number of render layers.
+pub const TOTAL_LAYERS: u8 = 32;
+
+/// The render layers which a camera draws, or which an entity is drawn in.
+///
+/// Entities with render layers are only drawn by the cameras which draw any
+/// of them. Cameras without render layers draw the first one.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RenderLayers(u32);
+
+impl Default for RenderLayers {
+    fn default() -> RenderLayers {
+        RenderLayers::layer(0)
+    }
+}
+
+impl RenderLayers {
+    /// Constructs the render layers of only one layer. Layers from
+    /// [`TOTAL_LAYERS`] up are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::render_layers::RenderLayers;
+    ///
+    /// let minimap = RenderLayers::layer(1);
+    ///
+    /// assert!(minimap.contains(1));
+    /// assert!(!minimap.contains(0));
+    /// ```
+    pub fn layer(layer: u8) -> RenderLayers {
+        RenderLayers::none().with(layer)
+    }
+
+    /// Constructs the render layers of every layer.
+    pub fn all() -> RenderLayers {
+        RenderLayers(u32::MAX)
+    }
+
+    /// Constructs the render layers of no layer, which no camera draws.
+    pub fn none() -> RenderLayers {
+        RenderLayers(0)
+    }
+
+    /// Returns the render layers with another layer.
+    pub fn with(self, layer: u8) -> RenderLayers {
+        RenderLayers(self.0 | bit(layer))
+    }
+
+    /// Returns the render layers without a layer.
+    pub fn without(self, layer: u8) -> RenderLayers {
+        RenderLayers(self.0 & !bit(layer))
+    }
+
+    /// Returns `true` if the layer is one of the render layers.
+    pub fn contains(&self, layer: u8) -> bool {
+        self.0 & bit(layer) != 0
+    }
+
+    /// Returns `true` if any layer is in both render layers.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::render_layers::RenderLayers;
+    ///
+    /// let world = RenderLayers::layer(0).with(1);
+    ///
+    /// assert!(world.intersects(&RenderLayers::layer(1)));
+    /// assert!(!world.intersects(&RenderLayers::layer(2)));
+    /// ```
+    pub fn intersects(&self, other: &RenderLayers) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+/// Returns the bit of a render layer, which is `0` from [`TOTAL_LAYERS`] up.
+fn bit(layer: u8) -> u32 {
+    1u32.checked_shl(u32::from(layer)).unwrap_or(0)
+}
+
+/// Sets the render layers of every chunk layer to those of its layer, for
+/// chunks of tilemap entities and of instances.
+pub(crate) fn chunk_render_layers(
+    tilemaps: Res<Assets<TilemapData>>,
+    map_query: Query<&Tilemap>,
+    instance_query: Query<&Handle<TilemapData>>,
+    mut chunk_query: Query<(&Parent, &ZOrder, &mut RenderLayers)>,
+) {
+    for (parent, z_order, mut render_layers) in chunk_query.iter_mut() {
+        let layers = if let Ok(tilemap) = map_query.get(**parent) {
+            tilemap.layer_render_layers(z_order.0)
+        } else if let Some(data) = instance_query
+            .get(**parent)
+            .ok()
+            .and_then(|handle| tilemaps.get(handle))
+        {
+            data.layer_render_layers(z_order.0)
+        } else {
+            continue;
+        };
+        if *render_layers != layers {
+            *render_layers = layers;
+        }
+    }
+}
+
+/// Leaves the entities whose render layers a camera does not draw out of
+/// the visible entities of the camera, once they were found in the
+/// `POST_UPDATE` stage and before they are drawn.
+pub(crate) fn camera_render_layers(
+    mut camera_query: Query<(Option<&RenderLayers>, &mut VisibleEntities), With<Camera>>,
+    layers_query: Query<&RenderLayers>,
+) {
+    for (camera_layers, mut visible_entities) in camera_query.iter_mut() {
+        let camera_layers = camera_layers.copied().unwrap_or_default();
+        visible_entities.value.retain(|visible| {
+            layers_query
+                .get(visible.entity)
+                .ok()
+                .filter(|layers| !layers.intersects(&camera_layers))
+                .is_none()
+        });
+    }
+}
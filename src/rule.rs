@@ -0,0 +1,286 @@
+//! Rules which pick the sprites of tiles from their neighbours.
+//!
+//! [`TileRules`] are the transition and corner tiles of terrains described in
+//! data instead of in hand-written match statements, like the automapping of
+//! Tiled or the terrain sets of Godot. A terrain is a set of sprite indexes
+//! which are the same kind of tile, such as water with all of its shores.
+//! Each [`TileRule`] of a terrain matches the neighbours of a tile at their
+//! offsets, and the first rule which matches sets the sprite of the tile.
+//! The sprites which rules set must be part of the terrain themselves, so
+//! that the tiles they change still match the same rules after.
+//!
+//! Rules are added to a layer with [`Tilemap::add_tile_rules`] and applied to
+//! every tile of the layer with [`Tilemap::apply_tile_rules`]. Rules which
+//! are automatic are also applied to the tiles around those which are
+//! inserted or cleared, as they change. With the `ron-asset` feature, rules
+//! are read from RON with [`TileRules::from_ron`].
+//!
+//! Rules see across the edges of chunks, so a tile which changes at the edge
+//! of a chunk also changes the tiles of the chunk next to it which match it,
+//! and both chunks are modified. Tiles whose neighbours are in a chunk which
+//! is unloaded to a chunk store are left as they are until it is loaded
+//! again, when the tiles along its edges and those facing them are ruled
+//! again. Tiles of static chunks and layers are never changed by rules.
+//!
+//! ```ron
+//! (
+//!     terrains: { "water": [10, 11, 12] },
+//!     rules: [
+//!         (terrain: "water", neighbors: [((0, 1), NotTerrain("water"))], sprite_index: 11),
+//!         (terrain: "water", neighbors: [((0, -1), NotTerrain("water"))], sprite_index: 12),
+//!         (terrain: "water", neighbors: [], sprite_index: 10),
+//!     ],
+//!     auto: true,
+//! )
+//! ```
+//!
+//! [`Tilemap::add_tile_rules`]: crate::tilemap::Tilemap::add_tile_rules
+//! [`Tilemap::apply_tile_rules`]: crate::tilemap::Tilemap::apply_tile_rules
+//!
+//! # Shores
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{
+//!     prelude::*,
+//!     rule::{TileMatch, TileRule, TileRules},
+//! };
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! let water = || TileMatch::Terrain("water".to_string());
+//! let rules = TileRules::new()
+//!     .with_terrain("water", vec![10, 11])
+//!     .with_rule(TileRule::new("water", 11).with_neighbor((0, 1), TileMatch::NotTerrain("water".to_string())))
+//!     .with_rule(TileRule::new("water", 10).with_neighbor((0, 1), water()))
+//!     .with_auto(true);
+//! tilemap.add_tile_rules(0, rules);
+//!
+//! tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 10, ..Default::default() }).unwrap();
+//! assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 11);
+//!
+//! // The water below is no longer the shore once there is water above it.
+//! tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 10, ..Default::default() }).unwrap();
+//! assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 10);
+//! assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 11);
+//!
+//! // The top row of the chunk at (0, 0) is next to the chunk at (0, 1).
+//! tilemap.insert_tile(Tile { point: (1, 2), sprite_index: 10, ..Default::default() }).unwrap();
+//! assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 10);
+//! assert_eq!(tilemap.get_tile((1, 2), 0).unwrap().index, 11);
+//! ```
+
+use crate::lib::*;
+#[cfg(feature = "ron-asset")]
+use crate::tilemap::{ErrorKind, TilemapResult};
+
+/// What a neighbour of a tile must be for a rule to match.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TileMatch {
+    /// Any tile or no tile at all.
+    Any,
+    /// No tile.
+    Empty,
+    /// A tile of the terrain.
+    Terrain(String),
+    /// No tile, or a tile which is not of the terrain.
+    NotTerrain(String),
+}
+
+/// A rule which sets the sprite of a tile of a terrain when its neighbours
+/// match.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TileRule {
+    /// The terrain of the tiles which the rule is for.
+    pub terrain: String,
+    /// What the neighbours must be, at their offsets from the tile.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub neighbors: Vec<((i32, i32), TileMatch)>,
+    /// The sprite index which the tile is set to, which must be of the
+    /// terrain.
+    pub sprite_index: usize,
+}
+
+impl TileRule {
+    /// Constructs a new rule for the tiles of a terrain without any
+    /// neighbours, which always matches.
+    pub fn new<S: Into<String>>(terrain: S, sprite_index: usize) -> TileRule {
+        TileRule {
+            terrain: terrain.into(),
+            neighbors: Vec::new(),
+            sprite_index,
+        }
+    }
+
+    /// Adds what the neighbour at the offset from the tile must be.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::rule::{TileMatch, TileRule};
+    ///
+    /// let rule = TileRule::new("water", 11).with_neighbor((0, 1), TileMatch::Empty);
+    ///
+    /// assert_eq!(rule.neighbors, vec![((0, 1), TileMatch::Empty)]);
+    /// ```
+    pub fn with_neighbor(mut self, offset: (i32, i32), tile_match: TileMatch) -> TileRule {
+        self.neighbors.push((offset, tile_match));
+        self
+    }
+}
+
+/// The terrains and rules of a layer.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TileRules {
+    /// The sprite indexes of the terrains by their names.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub terrains: HashMap<String, Vec<usize>>,
+    /// The rules, in the order they are tried.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Vec<TileRule>,
+    /// If the rules are applied as tiles are inserted or cleared.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto: bool,
+}
+
+impl TileRules {
+    /// Constructs new rules without any terrains or rules, which are not
+    /// automatic.
+    pub fn new() -> TileRules {
+        TileRules::default()
+    }
+
+    /// Reads rules from RON.
+    ///
+    /// This is behind the `ron-asset` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RON is not valid rules.
+    #[cfg(feature = "ron-asset")]
+    pub fn from_ron(ron: &str) -> TilemapResult<TileRules> {
+        ron::de::from_str(ron).map_err(|e| ErrorKind::InvalidMapFile(e.to_string()).into())
+    }
+
+    /// Adds a terrain with its sprite indexes, replacing the terrain which
+    /// had the name.
+    pub fn with_terrain<S: Into<String>>(
+        mut self,
+        name: S,
+        sprite_indexes: Vec<usize>,
+    ) -> TileRules {
+        self.terrains.insert(name.into(), sprite_indexes);
+        self
+    }
+
+    /// Adds a rule, which is tried after the rules before it.
+    pub fn with_rule(mut self, rule: TileRule) -> TileRules {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Sets if the rules are applied as tiles are inserted or cleared.
+    pub fn with_auto(mut self, auto: bool) -> TileRules {
+        self.auto = auto;
+        self
+    }
+
+    /// Returns `true` if the sprite index is of the terrain.
+    pub fn is_terrain(&self, terrain: &str, sprite_index: usize) -> bool {
+        self.terrains
+            .get(terrain)
+            .is_some_and(|sprites| sprites.contains(&sprite_index))
+    }
+
+    /// Returns the sprite index which the first rule that matches sets the
+    /// tile at the point to, where the sprite of a tile is given by the
+    /// closure, or `None` if no rule matches.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::rule::{TileMatch, TileRule, TileRules};
+    ///
+    /// let rules = TileRules::new()
+    ///     .with_terrain("water", vec![10, 11])
+    ///     .with_rule(TileRule::new("water", 11).with_neighbor((0, 1), TileMatch::Empty));
+    ///
+    /// // Water below the row at zero.
+    /// let water = |y: i32| if y <= 0 { Some(10) } else { None };
+    ///
+    /// assert_eq!(rules.resolve((0, 0), |point| water(point.y)), Some(11));
+    /// assert_eq!(rules.resolve((0, -1), |point| water(point.y)), None);
+    /// assert_eq!(rules.resolve((0, 1), |point| water(point.y)), None);
+    /// ```
+    pub fn resolve<P, F>(&self, point: P, sprite_at: F) -> Option<usize>
+    where
+        P: Into<Point2>,
+        F: Fn(Point2) -> Option<usize>,
+    {
+        let point: Point2 = point.into();
+        let sprite_index = sprite_at(point)?;
+        self.rules
+            .iter()
+            .filter(|rule| self.is_terrain(&rule.terrain, sprite_index))
+            .find(|rule| {
+                rule.neighbors.iter().all(|((x, y), tile_match)| {
+                    let neighbor = sprite_at(point + Point2::new(*x, *y));
+                    match (tile_match, neighbor) {
+                        (TileMatch::Any, _) => true,
+                        (TileMatch::Empty, neighbor) => neighbor.is_none(),
+                        (TileMatch::Terrain(terrain), Some(neighbor)) => {
+                            self.is_terrain(terrain, neighbor)
+                        }
+                        (TileMatch::Terrain(_), None) => false,
+                        (TileMatch::NotTerrain(terrain), Some(neighbor)) => {
+                            !self.is_terrain(terrain, neighbor)
+                        }
+                        (TileMatch::NotTerrain(_), None) => true,
+                    }
+                })
+            })
+            .map(|rule| rule.sprite_index)
+    }
+
+    /// Returns the offsets of the neighbours which the rules match.
+    pub(crate) fn offsets(&self) -> HashSet<Point2> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.neighbors.iter())
+            .map(|((x, y), _)| Point2::new(*x, *y))
+            .collect()
+    }
+
+    /// Returns the offsets of the tiles whose rules match the tile at `(0, 0)`
+    /// as a neighbour, along with `(0, 0)` itself.
+    pub(crate) fn dependents(&self) -> HashSet<Point2> {
+        let mut offsets: HashSet<Point2> = self
+            .offsets()
+            .into_iter()
+            .map(|offset| Point2::new(-offset.x, -offset.y))
+            .collect();
+        offsets.insert(Point2::new(0, 0));
+        offsets
+    }
+
+    /// Returns how far away the furthest neighbour which the rules match is
+    /// along either axis.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn reach(&self) -> i32 {
+        self.offsets()
+            .into_iter()
+            .map(|offset| offset.x.abs().max(offset.y.abs()))
+            .max()
+            .unwrap_or(0)
+    }
+}
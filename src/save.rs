@@ -0,0 +1,290 @@
+//! Versioned saves of tilemaps and their chunks.
+//!
+//! [`Tilemap::to_save`] writes a tilemap as JSON along with the version of
+//! the save format, [`SAVE_VERSION`], and [`Tilemap::from_save`] reads it
+//! back. The chunks which are written to a chunk store have the version too.
+//! Saves without a version, which were written before saves had one, are
+//! version 0.
+//!
+//! When the layout of tilemaps, chunks or their tiles changes, the save
+//! format gets a new version along with the migrations which upgrade the JSON
+//! of older saves to it. Saves of older versions of the crate are upgraded
+//! one version at a time as they are read, so they keep loading. Games can
+//! also fix up their own saves after they were upgraded with
+//! [`Tilemap::from_save_with`], such as when their sprite sheets changed.
+//!
+//! This is behind the `serialize` feature.
+//!
+//! [`Tilemap::to_save`]: crate::tilemap::Tilemap::to_save
+//! [`Tilemap::from_save`]: crate::tilemap::Tilemap::from_save
+//! [`Tilemap::from_save_with`]: crate::tilemap::Tilemap::from_save_with
+//!
+//! # Saving a map
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle.clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 3, ..Default::default() }).unwrap();
+//!
+//! let save = tilemap.to_save().unwrap();
+//! let tilemap = Tilemap::from_save(&save, texture_atlas_handle).unwrap();
+//!
+//! assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 3);
+//! ```
+//!
+//! # Upgrading a save
+//!
+//! The empty tiles of dense layers were fully transparent tiles until version
+//! 2, where they are [`RawTile::EMPTY`], so they are upgraded to it. The auto
+//! spawn radius of tilemaps is upgraded to a [`ChunkSpawnConfig`].
+//!
+//! [`RawTile::EMPTY`]: crate::chunk::RawTile::EMPTY
+//! [`ChunkSpawnConfig`]: crate::chunk::ChunkSpawnConfig
+//!
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle.clone())
+//!     .chunk_dimensions(2, 1)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // A chunk saved with version 1, whose first tile is empty.
+//! let chunk = r#"[{"x":0,"y":0},{
+//!     "point":{"x":0,"y":0},
+//!     "user_data":0,
+//!     "colliders":{},
+//!     "sprite_layers":[{"inner":{"Dense":{"tiles":[
+//!         {"index":0,"color":{"red":0.0,"green":0.0,"blue":0.0,"alpha":0.0}},
+//!         {"index":3,"color":{"red":1.0,"green":1.0,"blue":1.0,"alpha":1.0}}
+//!     ]}}}]
+//! }]"#;
+//! let save = String::from_utf8(tilemap.to_save().unwrap())
+//!     .unwrap()
+//!     .replace(r#""chunks":[]"#, &format!(r#""chunks":[{}]"#, chunk))
+//!     .replace(r#""spawn_config":null"#, r#""auto_spawn":{"width":2,"height":1}"#)
+//!     .replace(r#""version":2"#, r#""version":1"#);
+//!
+//! let tilemap = Tilemap::from_save(save.as_bytes(), texture_atlas_handle).unwrap();
+//!
+//! assert!(tilemap.get_tile((-1, 0), 0).is_none());
+//! assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 3);
+//! assert_eq!(tilemap.spawn_config().unwrap().spawn_radius, (2, 1).into());
+//! ```
+
+use crate::{
+    chunk::RawTile,
+    lib::*,
+    tilemap::{AutoFlags, ErrorKind, TilemapError, TilemapResult},
+};
+
+/// The version of the save format which is written.
+pub const SAVE_VERSION: u32 = 2;
+
+/// A migration which upgrades the JSON of a save to the next version.
+pub(crate) type Migration = fn(&mut JsonValue) -> TilemapResult<()>;
+
+/// The migrations of tilemap saves, where each upgrades the version of its
+/// index.
+pub(crate) const TILEMAP_MIGRATIONS: [Migration; SAVE_VERSION as usize] =
+    [unversioned, tilemap_version_1];
+
+/// The migrations of chunks written as JSON, where each upgrades the version
+/// of its index.
+pub(crate) const CHUNK_MIGRATIONS: [Migration; SAVE_VERSION as usize] =
+    [unversioned, chunk_empty_tiles];
+
+/// The migrations of compressed chunks, where each upgrades the version of
+/// its index.
+#[cfg(feature = "compress")]
+pub(crate) const PACKED_CHUNK_MIGRATIONS: [Migration; SAVE_VERSION as usize] =
+    [unversioned, packed_chunk_empty_tiles];
+
+/// Upgrades a save from before saves had a version, whose layout is the same
+/// as that of version 1.
+fn unversioned(_value: &mut JsonValue) -> TilemapResult<()> {
+    Ok(())
+}
+
+/// Upgrades a tilemap save of version 1, see [`tilemap_empty_tiles`] and
+/// [`tilemap_spawn_config`].
+fn tilemap_version_1(value: &mut JsonValue) -> TilemapResult<()> {
+    tilemap_empty_tiles(value)?;
+    tilemap_spawn_config(value)
+}
+
+/// Upgrades the auto spawn radius of a tilemap save of version 1 to a spawn
+/// config with the same spawn and despawn radii, which is fit to the window
+/// like the radius was.
+fn tilemap_spawn_config(value: &mut JsonValue) -> TilemapResult<()> {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return Ok(()),
+    };
+    let radius = match object.remove("auto_spawn") {
+        Some(radius) if !radius.is_null() => radius,
+        _ => return Ok(()),
+    };
+    let mut config = serde_json::Map::new();
+    config.insert("spawn_radius".to_string(), radius.clone());
+    config.insert("despawn_radius".to_string(), radius);
+    config.insert("max_loaded_chunks".to_string(), JsonValue::Null);
+    config.insert("budget_per_frame".to_string(), JsonValue::Null);
+    object.insert("spawn_config".to_string(), JsonValue::Object(config));
+    if let Some(bits) = object
+        .get_mut("auto_flags")
+        .and_then(|flags| flags.get_mut("bits"))
+    {
+        let flags = bits.as_u64().unwrap_or(0) | u64::from(AutoFlags::AUTO_SPAWN.bits());
+        *bits = JsonValue::from(flags);
+    }
+    Ok(())
+}
+
+/// Upgrades a tilemap save of version 1 by upgrading its chunks and chunk
+/// templates, see [`chunk_empty_tiles`].
+fn tilemap_empty_tiles(value: &mut JsonValue) -> TilemapResult<()> {
+    if let Some(chunks) = value.get_mut("chunks").and_then(JsonValue::as_array_mut) {
+        // The chunks are pairs of their points and themselves.
+        for pair in chunks.iter_mut() {
+            if let Some(chunk) = pair.get_mut(1) {
+                chunk_empty_tiles(chunk)?;
+            }
+        }
+    }
+    if let Some(templates) = value
+        .get_mut("chunk_templates")
+        .and_then(JsonValue::as_array_mut)
+    {
+        for chunk in templates.iter_mut() {
+            chunk_empty_tiles(chunk)?;
+        }
+    }
+    Ok(())
+}
+
+/// Upgrades a chunk of version 1, where the empty tiles of dense layers were
+/// those which were fully transparent, so that they are [`RawTile::EMPTY`].
+fn chunk_empty_tiles(value: &mut JsonValue) -> TilemapResult<()> {
+    let empty = to_json(&RawTile::EMPTY)?;
+    for layer in sprite_layers(value) {
+        let tiles = layer
+            .pointer_mut("/inner/Dense/tiles")
+            .and_then(JsonValue::as_array_mut);
+        for tile in tiles.into_iter().flatten() {
+            empty_if_transparent(tile, &empty);
+        }
+    }
+    Ok(())
+}
+
+/// Upgrades a compressed chunk of version 1, whose dense layers are runs of
+/// equal tiles, see [`chunk_empty_tiles`].
+#[cfg(feature = "compress")]
+fn packed_chunk_empty_tiles(value: &mut JsonValue) -> TilemapResult<()> {
+    let empty = to_json(&RawTile::EMPTY)?;
+    for layer in sprite_layers(value) {
+        let runs = layer
+            .pointer_mut("/tiles/Dense")
+            .and_then(JsonValue::as_array_mut);
+        // The runs are pairs of their lengths and tiles.
+        for run in runs.into_iter().flatten() {
+            if let Some(tile) = run.get_mut(1) {
+                empty_if_transparent(tile, &empty);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the sprite layers of the JSON of a chunk which are not `None`.
+fn sprite_layers(chunk: &mut JsonValue) -> impl Iterator<Item = &mut JsonValue> {
+    chunk
+        .get_mut("sprite_layers")
+        .and_then(JsonValue::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter(|layer| !layer.is_null())
+}
+
+/// Replaces the JSON of a tile with that of the empty tile if it is fully
+/// transparent.
+fn empty_if_transparent(tile: &mut JsonValue, empty: &JsonValue) {
+    let alpha = tile.pointer("/color/alpha").and_then(JsonValue::as_f64);
+    if alpha == Some(0.0) {
+        *tile = empty.clone();
+    }
+}
+
+/// Writes a value as the JSON of a save of the current version, under the
+/// key.
+pub(crate) fn write<T: Serialize>(key: &str, value: &T) -> TilemapResult<Vec<u8>> {
+    let mut save = serde_json::Map::new();
+    save.insert("version".to_string(), JsonValue::from(SAVE_VERSION));
+    save.insert(key.to_string(), to_json(value)?);
+    serde_json::to_vec(&JsonValue::Object(save)).map_err(invalid)
+}
+
+/// Reads the JSON of a save and upgrades it to the current version, returning
+/// the version it was written with and the value under the key.
+///
+/// A save without a version is the value itself.
+pub(crate) fn read(
+    bytes: &[u8],
+    key: &str,
+    migrations: &[Migration],
+) -> TilemapResult<(u32, JsonValue)> {
+    let mut save: JsonValue = serde_json::from_slice(bytes).map_err(invalid)?;
+    let (version, mut value) = match save.get("version").and_then(JsonValue::as_u64) {
+        Some(version) => {
+            let value = save
+                .get_mut(key)
+                .map(JsonValue::take)
+                .ok_or_else(|| ErrorKind::InvalidSave(::std::format!("\"{}\" is missing", key)))?;
+            (version as u32, value)
+        }
+        None => (0, save),
+    };
+    if version > SAVE_VERSION {
+        return Err(ErrorKind::UnsupportedSaveVersion(version).into());
+    }
+    for migration in migrations.iter().skip(version as usize) {
+        migration(&mut value)?;
+    }
+    Ok((version, value))
+}
+
+/// Serializes a value to JSON.
+pub(crate) fn to_json<T: Serialize>(value: &T) -> TilemapResult<JsonValue> {
+    serde_json::to_value(value).map_err(invalid)
+}
+
+/// Deserializes a value from JSON.
+pub(crate) fn from_json<T: ::serde::de::DeserializeOwned>(value: JsonValue) -> TilemapResult<T> {
+    serde_json::from_value(value).map_err(invalid)
+}
+
+/// Returns the error of a save which could not be read or written.
+fn invalid<E: Display>(e: E) -> TilemapError {
+    ErrorKind::InvalidSave(e.to_string()).into()
+}
@@ -0,0 +1,72 @@
+//! Snapshots of tilemaps for rollback.
+//!
+//! [`Tilemap::snapshot`] takes a [`TilemapSnapshot`] of the chunks of a
+//! tilemap and their tiles, and [`Tilemap::restore`] puts the tilemap back to
+//! it, such as when rollback netcode rewinds to the last confirmed tick to
+//! simulate it again with the inputs which arrived late.
+//!
+//! Snapshots share their chunks with the tilemap, which copies a chunk only
+//! the first time it changes after a snapshot was taken. Taking a snapshot of
+//! a mostly static map is then cheap no matter how large it is, and restoring
+//! one only replaces the chunks which changed since. The meshes and colliders
+//! of the restored chunks are rebuilt by the systems of the tilemap after.
+//!
+//! Only chunks and tiles are in snapshots, like deltas. Chunks which were
+//! written to the chunk store when the snapshot was taken are left as they
+//! are, and snapshots are of chunks at their points so they are no longer
+//! valid once the origin of the tilemap is shifted.
+//!
+//! [`Tilemap::snapshot`]: crate::tilemap::Tilemap::snapshot
+//! [`Tilemap::restore`]: crate::tilemap::Tilemap::restore
+//!
+//! # Rolling back
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 3, ..Default::default() }).unwrap();
+//! let confirmed = tilemap.snapshot();
+//!
+//! tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 4, ..Default::default() }).unwrap();
+//! tilemap.insert_tile(Tile { point: (9, 9), sprite_index: 5, ..Default::default() }).unwrap();
+//!
+//! tilemap.restore(&confirmed).unwrap();
+//! assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 3);
+//! assert_eq!(tilemap.get_tile((9, 9), 0), None);
+//! ```
+
+use crate::{chunk::Chunk, lib::*};
+
+/// The chunks of a tilemap and their tiles at the time it was taken.
+#[derive(Clone, Debug, Default)]
+pub struct TilemapSnapshot {
+    /// The chunks in memory, which are shared with the tilemap.
+    pub(crate) chunks: HashMap<Point2, Arc<Chunk>>,
+    /// The chunks which were written to the chunk store.
+    #[cfg(feature = "serialize")]
+    pub(crate) unloaded_chunks: HashSet<Point2>,
+}
+
+impl TilemapSnapshot {
+    /// Returns `true` if the snapshot has the chunk in memory.
+    pub fn contains_chunk<P: Into<Point2>>(&self, point: P) -> bool {
+        self.chunks.contains_key(&point.into())
+    }
+
+    /// Returns how many chunks the snapshot has in memory.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+}
@@ -0,0 +1,243 @@
+//! Sprites with their own rect in the texture atlas.
+//!
+//! Tiles are normally drawn with the rect of their sprite index in the
+//! texture atlas, and the size of that rect is the size of the tile. Texture
+//! packers however trim the transparent borders off of sprites and rotate
+//! some of them to fit more into an atlas. A [`SpriteRect`] describes where
+//! such a sprite is in the atlas and how it is drawn in its tile, so that the
+//! metadata of a packer can be used as it is.
+//!
+//! A sprite rect is set for a sprite index with [`Tilemap::set_sprite_rect`]
+//! and is then used by every tile with that sprite index.
+//!
+//! Sprites which are bigger than the tiles of the grid, such as trees which
+//! are two tiles tall, are [`anchored`] in their tile. The anchor of the
+//! sprite is put at the anchor of its tile and the rest of the sprite
+//! overhangs the tiles around it. Layers with such sprites are usually
+//! [`y_sort`]ed so that the part which overhangs the tile above is drawn in
+//! front of it.
+//!
+//! [`Tilemap::set_sprite_rect`]: crate::tilemap::Tilemap::set_sprite_rect
+//! [`anchored`]: SpriteRect::anchored
+//! [`y_sort`]: crate::tilemap::TilemapLayer::y_sort
+//!
+//! # Using the frame of a texture packer
+//! ```
+//! use bevy_math::Vec2;
+//! use bevy_tilemap::sprite::SpriteRect;
+//!
+//! // A 32x32 sprite that was trimmed to 20x28, is 2 pixels from the left and
+//! // 1 pixel from the top of its source, and was rotated in the atlas.
+//! let frame = SpriteRect::new(Vec2::new(64.0, 0.0), Vec2::new(92.0, 20.0))
+//!     .rotated()
+//!     .trimmed(Vec2::new(2.0, 32.0 - 28.0 - 1.0), Vec2::new(32.0, 32.0));
+//!
+//! assert_eq!(frame.size(), Vec2::new(20.0, 28.0));
+//! assert_eq!(frame.source_size(), Vec2::new(32.0, 32.0));
+//! ```
+//!
+//! # A tree two tiles tall
+//! ```
+//! use bevy_math::Vec2;
+//! use bevy_tilemap::sprite::SpriteRect;
+//!
+//! // A 16x32 tree standing on the middle of the bottom of a 16x16 tile.
+//! let tree = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(16.0, 32.0))
+//!     .anchored(Vec2::new(16.0, 16.0), Vec2::new(0.5, 0.0));
+//!
+//! assert_eq!(tree.grid_size(), Vec2::new(16.0, 16.0));
+//! assert_eq!(tree.sprite_offset(), Vec2::new(0.0, 0.0));
+//! ```
+
+use crate::lib::*;
+
+/// Where a sprite is in the texture atlas and how it is drawn in its tile.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SpriteRect {
+    /// The top left corner of the sprite in the atlas, in pixels.
+    pub min: Vec2,
+    /// The bottom right corner of the sprite in the atlas, in pixels.
+    pub max: Vec2,
+    /// If the sprite is rotated 90 degrees clockwise in the atlas.
+    pub rotated: bool,
+    /// The offset of the sprite from the bottom left of its source, in pixels.
+    pub offset: Vec2,
+    /// The size of the sprite before it was trimmed, if it was.
+    pub trimmed_size: Option<Vec2>,
+    /// The size of the tile the sprite is anchored in, if it is another size
+    /// than the source of the sprite.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tile_size: Option<Vec2>,
+    /// The point of the source of the sprite which is put at the same point
+    /// of its tile, from `(0, 0)` at the bottom left to `(1, 1)` at the top
+    /// right.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub anchor: Vec2,
+}
+
+impl SpriteRect {
+    /// Constructs a new sprite rect from its top left and bottom right corners
+    /// in the atlas.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::sprite::SpriteRect;
+    ///
+    /// let sprite_rect = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(16.0, 24.0));
+    ///
+    /// assert_eq!(sprite_rect.size(), Vec2::new(16.0, 24.0));
+    /// ```
+    pub fn new(min: Vec2, max: Vec2) -> SpriteRect {
+        SpriteRect {
+            min,
+            max,
+            rotated: false,
+            offset: Vec2::zero(),
+            trimmed_size: None,
+            tile_size: None,
+            anchor: Vec2::zero(),
+        }
+    }
+
+    /// Marks the sprite as rotated 90 degrees clockwise in the atlas.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::sprite::SpriteRect;
+    ///
+    /// let sprite_rect = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(16.0, 24.0)).rotated();
+    ///
+    /// assert_eq!(sprite_rect.size(), Vec2::new(24.0, 16.0));
+    /// ```
+    pub fn rotated(mut self) -> SpriteRect {
+        self.rotated = true;
+        self
+    }
+
+    /// Marks the sprite as trimmed from a source of the size, with the offset
+    /// from the bottom left of the source.
+    ///
+    /// Texture packers usually give the offset from the top left, which is
+    /// the height of the source minus the height and top offset of the
+    /// sprite from the bottom.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::sprite::SpriteRect;
+    ///
+    /// let sprite_rect = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(10.0, 10.0))
+    ///     .trimmed(Vec2::new(3.0, 3.0), Vec2::new(16.0, 16.0));
+    ///
+    /// assert_eq!(sprite_rect.size(), Vec2::new(10.0, 10.0));
+    /// assert_eq!(sprite_rect.source_size(), Vec2::new(16.0, 16.0));
+    /// ```
+    pub fn trimmed(mut self, offset: Vec2, source_size: Vec2) -> SpriteRect {
+        self.offset = offset;
+        self.trimmed_size = Some(source_size);
+        self
+    }
+
+    /// Anchors the sprite in a tile of the size, putting the anchor of its
+    /// source at the same point of the tile, so a sprite bigger than the tile
+    /// overhangs the tiles around it.
+    ///
+    /// The anchor goes from `(0, 0)` at the bottom left to `(1, 1)` at the
+    /// top right, so `(0.5, 0.0)` stands the sprite on the middle of the
+    /// bottom of its tile.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::sprite::SpriteRect;
+    ///
+    /// // A 32x32 bush centered on a 16x16 tile.
+    /// let bush = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(32.0, 32.0))
+    ///     .anchored(Vec2::new(16.0, 16.0), Vec2::new(0.5, 0.5));
+    ///
+    /// assert_eq!(bush.sprite_offset(), Vec2::new(-8.0, -8.0));
+    /// ```
+    pub fn anchored(mut self, tile_size: Vec2, anchor: Vec2) -> SpriteRect {
+        self.tile_size = Some(tile_size);
+        self.anchor = anchor;
+        self
+    }
+
+    /// Returns the size of the sprite as it is drawn, in pixels.
+    pub fn size(&self) -> Vec2 {
+        let size = self.max - self.min;
+        if self.rotated {
+            Vec2::new(size.y, size.x)
+        } else {
+            size
+        }
+    }
+
+    /// Returns the size of the sprite before it was trimmed, in pixels. This
+    /// is the size of the tile it is drawn in.
+    pub fn source_size(&self) -> Vec2 {
+        self.trimmed_size.unwrap_or_else(|| self.size())
+    }
+
+    /// Returns the size of the tile the sprite is drawn in, which lays out
+    /// the grid, in pixels. This is the source size unless the sprite is
+    /// anchored.
+    pub fn grid_size(&self) -> Vec2 {
+        self.tile_size.unwrap_or_else(|| self.source_size())
+    }
+
+    /// Returns the offset of the sprite as it is drawn from the bottom left
+    /// of its tile, in pixels, which is negative where it overhangs the tiles
+    /// to the left or below.
+    pub fn sprite_offset(&self) -> Vec2 {
+        self.anchor * (self.grid_size() - self.source_size()) + self.offset
+    }
+
+    /// Returns the source attribute of each corner of the tile, in the order
+    /// of the chunk mesh vertices.
+    ///
+    /// This is the grid size followed by how far the corner is moved to fit
+    /// the trimmed or anchored sprite.
+    #[cfg(feature = "render")]
+    pub(crate) fn corner_sources(&self) -> [[f32; 4]; 4] {
+        let grid_size = self.grid_size();
+        let size = self.size();
+        let offset = self.sprite_offset();
+        let left = offset.x;
+        let bottom = offset.y;
+        let right = offset.x + size.x - grid_size.x;
+        let top = offset.y + size.y - grid_size.y;
+        let source = |x: f32, y: f32| [grid_size.x, grid_size.y, x, y];
+        [
+            source(left, bottom),
+            source(left, top),
+            source(right, top),
+            source(right, bottom),
+        ]
+    }
+
+    /// Returns the position in the atlas of each corner of the tile, in the
+    /// order of the chunk mesh vertices.
+    #[cfg(feature = "render")]
+    pub(crate) fn corner_uvs(&self) -> [[f32; 2]; 4] {
+        let (min, max) = (self.min, self.max);
+        if self.rotated {
+            [
+                [min.x, min.y],
+                [max.x, min.y],
+                [max.x, max.y],
+                [min.x, max.y],
+            ]
+        } else {
+            [
+                [min.x, max.y],
+                [min.x, min.y],
+                [max.x, min.y],
+                [max.x, max.y],
+            ]
+        }
+    }
+}
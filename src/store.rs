@@ -0,0 +1,157 @@
+//! Persisting chunks which are not in memory.
+//!
+//! A [`ChunkStore`] keeps the bytes of chunks at their points. A tilemap with
+//! a chunk store writes the chunks which leave the radius of its auto spawn,
+//! or which are unloaded to shed load, to the store and drops them from
+//! memory. They are read back when they are spawned, their tiles are changed
+//! or they are loaded with [`Tilemap::load_chunk`], so the chunks of endless
+//! worlds keep their changes instead of being made again.
+//!
+//! The [`FileChunkStore`] keeps each chunk in a file of a directory.
+//!
+//! Chunks are written as JSON unless the tilemap has another
+//! [`ChunkEncoding`]. With the `compress` feature, the tiles of dense layers
+//! are written in runs of equal tiles and compressed with zstd, which makes
+//! the mostly uniform layers of large worlds a small fraction of their size.
+//! Chunks of every encoding are read back.
+//!
+//! This is behind the `serialize` feature.
+//!
+//! ```toml
+//! [dependencies]
+//! bevy_tilemap = { version = "0.3", features = ["compress"] }
+//! ```
+//!
+//! [`Tilemap::load_chunk`]: crate::tilemap::Tilemap::load_chunk
+//!
+//! # Storing chunks
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{prelude::*, store::FileChunkStore};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .auto_spawn(2, 2)
+//!     .finish()
+//!     .unwrap();
+//!
+//! tilemap.set_chunk_store(FileChunkStore::new("saves/overworld"));
+//! ```
+
+use crate::{
+    lib::*,
+    tilemap::{ErrorKind, TilemapResult},
+};
+
+/// How chunks are encoded when they are written to a chunk store.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ChunkEncoding {
+    /// The chunk is written as JSON.
+    #[default]
+    Json,
+    /// The dense layers of the chunk are written in runs of equal tiles and
+    /// the chunk is compressed with zstd at the level, from 1 for the fastest
+    /// to 21 for the smallest, or 0 for the default level of zstd.
+    #[cfg(feature = "compress")]
+    Compressed(i32),
+}
+
+/// A store of the bytes of chunks at their points.
+pub trait ChunkStore: Send + Sync + 'static {
+    /// Saves the bytes of a chunk, replacing those it had.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes could not be saved.
+    fn save(&self, point: Point2, bytes: &[u8]) -> TilemapResult<()>;
+
+    /// Returns the bytes of a chunk, or `None` if it was never saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes could not be read.
+    fn load(&self, point: Point2) -> TilemapResult<Option<Vec<u8>>>;
+
+    /// Removes the bytes of a chunk, if it has any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes could not be removed.
+    fn remove(&self, point: Point2) -> TilemapResult<()>;
+}
+
+impl Debug for dyn ChunkStore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "ChunkStore")
+    }
+}
+
+/// A chunk store which keeps each chunk in a file of a directory.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FileChunkStore {
+    /// The directory of the files.
+    directory: PathBuf,
+}
+
+impl FileChunkStore {
+    /// Constructs a new store of the chunks in the directory, which is made
+    /// when the first chunk is saved.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::store::FileChunkStore;
+    /// use std::path::PathBuf;
+    ///
+    /// let store = FileChunkStore::new("saves/chunks");
+    ///
+    /// assert_eq!(store.directory(), &PathBuf::from("saves/chunks"));
+    /// ```
+    pub fn new<D: Into<PathBuf>>(directory: D) -> FileChunkStore {
+        FileChunkStore {
+            directory: directory.into(),
+        }
+    }
+
+    /// Returns the directory of the files.
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Returns the path of the file of a chunk.
+    fn path(&self, point: Point2) -> PathBuf {
+        self.directory
+            .join(::std::format!("chunk_{}_{}.chunk", point.x, point.y))
+    }
+}
+
+impl ChunkStore for FileChunkStore {
+    fn save(&self, point: Point2, bytes: &[u8]) -> TilemapResult<()> {
+        fs::create_dir_all(&self.directory)
+            .and_then(|_| fs::write(self.path(point), bytes))
+            .map_err(|e| ErrorKind::ChunkStorage(e.to_string()).into())
+    }
+
+    fn load(&self, point: Point2) -> TilemapResult<Option<Vec<u8>>> {
+        match fs::read(self.path(point)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(ErrorKind::ChunkStorage(e.to_string()).into()),
+        }
+    }
+
+    fn remove(&self, point: Point2) -> TilemapResult<()> {
+        match fs::remove_file(self.path(point)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                Err(ErrorKind::ChunkStorage(e.to_string()).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,16 +1,248 @@
 //! The tilemap systems.
 
-#[cfg(feature = "bevy_rapier2d")]
-use crate::{chunk::Chunk, TilemapLayer};
+#[cfg(not(feature = "render"))]
+use crate::chunk::entity::ChunkLayerBundle;
+#[cfg(feature = "lighting")]
+use crate::light::ChunkLighting;
+#[cfg(feature = "render")]
 use crate::{
-    chunk::{
-        entity::{ChunkBundle, ModifiedLayer, ZOrder},
-        mesh::ChunkMesh,
-        render::GridTopology,
+    channel::TileChannel,
+    chunk::entity::{
+        ChunkBundle, ChunkInstanceBundle, ChunkMaskBundle, ChunkPickingBundle, ModifiedLayer,
+        ZOrder,
     },
+    chunk::mesh::ChunkMesh,
+    chunk::{raw_tile::TileAttributes, Chunk, TileOcclusion},
+    instance::{DataMeshes, TilemapData, TilemapInstance},
+    mask::TilemapMaskPass,
+    palette::ChunkPalette,
+    picking::TilemapPickingPass,
+    render_layers::RenderLayers,
+    sprite::SpriteRect,
+    wall::WallSprite,
+};
+use crate::{
+    chunk::{render::GridTopology, ChunkActivity, ChunkCollider, RawTile},
+    command::TilemapCommands,
+    event::TilemapChunkEvent,
+    geometry,
     lib::*,
-    Tilemap,
+    metrics::{FeatureMetrics, TilemapMetrics},
+    physics::{ColliderKind, CollisionTracker, TileCollider, TilemapPhysicsBackend},
+    tilemap::in_order,
+    Tilemap, TilemapLayer,
 };
+#[cfg(feature = "bevy_rapier2d")]
+use crate::{event::TileTriggerEvent, rapier::TileSensor};
+
+/// Advances the animated tiles of every tilemap.
+pub(crate) fn tilemap_animations(
+    time: Res<Time>,
+    mut metrics: ResMut<TilemapMetrics>,
+    mut tilemap_query: Query<&mut Tilemap>,
+) {
+    let start = Instant::now();
+    let delta = time.delta_seconds();
+    let mut advanced = 0;
+    for mut tilemap in tilemap_query.iter_mut() {
+        advanced += tilemap.update_animations(delta);
+    }
+    metrics.animation = FeatureMetrics::since(start, advanced);
+}
+
+/// Ages the decals of every tilemap.
+pub(crate) fn tilemap_decals(time: Res<Time>, mut tilemap_query: Query<&mut Tilemap>) {
+    let delta = time.delta_seconds();
+    for mut tilemap in tilemap_query.iter_mut() {
+        tilemap.update_decals(delta);
+    }
+}
+
+/// Clears the highlights of every tilemap which were not highlighted again
+/// since the last frame.
+pub(crate) fn tilemap_highlights(mut tilemap_query: Query<&mut Tilemap>) {
+    for mut tilemap in tilemap_query.iter_mut() {
+        if let Err(e) = tilemap.clear_stale_highlights() {
+            warn!("{}", e);
+        }
+    }
+}
+
+/// Forgets the gameplay entities bound to tiles which were despawned, for
+/// tilemaps which keep tile entities.
+pub(crate) fn tilemap_tile_entities(
+    mut tilemap_query: Query<&mut Tilemap>,
+    entity_query: Query<Entity>,
+) {
+    for mut tilemap in tilemap_query.iter_mut() {
+        if tilemap.has_tile_entities() {
+            tilemap.retain_tile_entities(|entity| entity_query.get(entity).is_ok());
+        }
+    }
+}
+
+/// Fades the tilemaps and layers which are fading.
+pub(crate) fn tilemap_fades(time: Res<Time>, mut tilemap_query: Query<&mut Tilemap>) {
+    let delta = time.delta_seconds();
+    for mut tilemap in tilemap_query.iter_mut() {
+        // Tilemaps which are not fading are left unchanged.
+        if tilemap.is_fading() {
+            tilemap.update_fades(delta);
+        }
+    }
+}
+
+/// Merges the walkable tiles of the changed chunks of every navigation mesh.
+pub(crate) fn tilemap_navmeshes(
+    mut metrics: ResMut<TilemapMetrics>,
+    mut tilemap_query: Query<&mut Tilemap>,
+) {
+    let start = Instant::now();
+    let mut merged = 0;
+    for mut tilemap in tilemap_query.iter_mut() {
+        merged += tilemap.update_navmeshes();
+    }
+    metrics.navmesh = FeatureMetrics::since(start, merged);
+}
+
+/// Sheds the load of every tilemap whose shed policy is past a threshold, from
+/// the chunks farthest from the camera.
+pub(crate) fn tilemap_load_shedding(mut tilemap_query: Query<&mut Tilemap>) {
+    for mut tilemap in tilemap_query.iter_mut() {
+        let policy = if let Some(policy) = tilemap.shed_policy() {
+            policy.clone()
+        } else {
+            continue;
+        };
+        tilemap.retain_shed_colliders();
+        if let Some(max) = policy.colliders() {
+            shed_colliders(&mut tilemap, max);
+        }
+        if let Some(max) = policy.layer_entities() {
+            shed_meshes(&mut tilemap, max);
+        }
+        #[cfg(feature = "serialize")]
+        if let Some(max) = policy.chunks() {
+            unload_chunks(&mut tilemap, max);
+        }
+    }
+}
+
+/// Keeps the colliders of the nearest spawned chunks which fit under the most
+/// colliders and sheds those of the chunks past them.
+///
+/// A chunk whose colliders were shed is counted with the colliders it had, so
+/// that it only comes back once they fit again.
+fn shed_colliders(tilemap: &mut Tilemap, max: usize) {
+    let mut chunks: Vec<(i64, Point2, usize)> = tilemap
+        .spawned_chunks()
+        .iter()
+        .map(|point| {
+            let point: Point2 = point.into();
+            let colliders = match tilemap.shed_colliders().get(&point) {
+                Some(colliders) => *colliders,
+                None => match tilemap.get_chunk(&point) {
+                    Some(chunk) => chunk.collider_count(),
+                    None => 0,
+                },
+            };
+            (tilemap.chunk_distance(point), point, colliders)
+        })
+        .collect();
+    chunks.sort_unstable();
+    let mut kept = 0;
+    let mut full = false;
+    for (_distance, point, colliders) in chunks.into_iter() {
+        full = full || kept + colliders > max;
+        if full {
+            if !tilemap.shed_colliders().contains_key(&point) {
+                tilemap.shed_chunk_colliders(point, colliders);
+            }
+            continue;
+        }
+        kept += colliders;
+        tilemap.readmit_chunk_colliders(point);
+    }
+}
+
+/// Keeps the nearest spawned chunks whose layer entities fit under the most
+/// layer entities and despawns those past them, which are spawned again once
+/// they fit.
+fn shed_meshes(tilemap: &mut Tilemap, max: usize) {
+    let spawned: HashSet<Point2> = tilemap
+        .spawned_chunks()
+        .iter()
+        .map(|point| point.into())
+        .collect();
+    let mut chunks: Vec<(i64, Point2, usize)> = spawned
+        .iter()
+        .chain(tilemap.shed_meshes().iter())
+        .filter_map(|point| {
+            let chunk = tilemap.get_chunk(point)?;
+            Some((tilemap.chunk_distance(*point), *point, chunk.layer_count()))
+        })
+        .collect();
+    chunks.sort_unstable();
+    let mut kept = 0;
+    let mut full = false;
+    for (_distance, point, entities) in chunks.into_iter() {
+        full = full || kept + entities > max;
+        if full {
+            if spawned.contains(&point) {
+                tilemap.shed_chunk_meshes(point);
+            }
+            continue;
+        }
+        kept += entities;
+        tilemap.readmit_chunk_meshes(point);
+    }
+}
+
+/// Writes the farthest chunks which are not in use to the chunk store until
+/// no more than the most chunks are in memory.
+#[cfg(feature = "serialize")]
+fn unload_chunks(tilemap: &mut Tilemap, max: usize) {
+    if tilemap.chunk_store().is_none() {
+        return;
+    }
+    let excess = match tilemap.chunk_count().checked_sub(max) {
+        Some(excess) if excess > 0 => excess,
+        _ => return,
+    };
+    let mut chunks: Vec<(i64, Point2)> = tilemap
+        .unloadable_chunks()
+        .into_iter()
+        .map(|point| (tilemap.chunk_distance(point), point))
+        .collect();
+    chunks.sort_unstable_by(|a, b| b.cmp(a));
+    for (_distance, point) in chunks.into_iter().take(excess) {
+        if let Err(e) = tilemap.shed_chunk(point) {
+            warn!("{}", e);
+            break;
+        }
+    }
+}
+
+/// Applies all the queued tilemap commands to their tilemaps.
+pub(crate) fn tilemap_commands(
+    tilemap_commands: Res<TilemapCommands>,
+    mut tilemap_query: Query<&mut Tilemap>,
+) {
+    for (entity, command) in tilemap_commands.drain() {
+        let mut tilemap = if let Ok(tilemap) = tilemap_query.get_mut(entity) {
+            tilemap
+        } else {
+            warn!(
+                "Tilemap entity {:?} does not exist, skipping command",
+                entity
+            );
+            continue;
+        };
+        if let Err(e) = command.apply(&mut tilemap) {
+            warn!("{}", e);
+        }
+    }
+}
 
 /// The event handling system for the tilemap.
 ///
@@ -18,20 +250,46 @@ use crate::{
 /// order of operation here. It was done in this order that made the most sense
 /// at the time of creation.
 ///
+/// 1. Move chunks to where the origin moved them
 /// 1. Spawn chunks
 /// 1. Modify chunks
 /// 1. Despawn chunks
 pub(crate) fn tilemap_events(
     commands: &mut Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
+    #[cfg(feature = "render")] mut meshes: ResMut<Assets<Mesh>>,
     mut tilemap_query: Query<(Entity, &mut Tilemap)>,
-    mut layer_query: Query<&mut ModifiedLayer>,
+    #[cfg(feature = "render")] mut layer_query: Query<&mut ModifiedLayer>,
+    #[cfg(feature = "render")] mut mesh_query: Query<&mut Handle<Mesh>>,
+    #[cfg(feature = "render")] children_query: Query<&Children>,
+    mut chunk_query: Query<(&mut Transform, &mut Point2)>,
 ) {
     for (map_entity, mut tilemap) in tilemap_query.iter_mut() {
         tilemap.chunk_events_update();
+        tilemap.collision_events_update();
+        tilemap.trigger_events_update();
+        tilemap.destroyed_events_update();
+        tilemap.shed_events_update();
+        tilemap.despawned_events_update();
+        tilemap.tile_entity_events_update();
+        // Only assets need these, chunk entities are updated from the events,
+        // but chunks which were changed are no longer copies of templates.
+        let modified_points = tilemap.take_modified_chunks();
+        for point in modified_points.iter() {
+            #[cfg(feature = "render")]
+            detach_chunk_instance(
+                &mut meshes,
+                &mut mesh_query,
+                &children_query,
+                &mut tilemap,
+                *point,
+            );
+            #[cfg(not(feature = "render"))]
+            tilemap.remove_chunk_instance(*point);
+        }
         let mut modified_chunks = Vec::new();
         let mut spawned_chunks = Vec::new();
         let mut despawned_chunks = Vec::new();
+        let mut rehomed = false;
         let mut reader = tilemap.chunk_events().get_reader();
         for event in reader.iter(&tilemap.chunk_events()) {
             use crate::TilemapChunkEvent::*;
@@ -48,105 +306,798 @@ pub(crate) fn tilemap_events(
                 } => {
                     despawned_chunks.push((entities.clone(), *point));
                 }
+                Rehomed { .. } => {
+                    rehomed = true;
+                }
+            }
+        }
+        // Opaque tiles hide the tiles of the layers below them, so every layer
+        // of a changed chunk is built again.
+        #[cfg(feature = "render")]
+        if tilemap.has_opaque_sprites() {
+            for point in modified_points.iter() {
+                if let Some(chunk) = tilemap.get_chunk(point) {
+                    modified_chunks.push(chunk.get_entities().into_iter().enumerate().collect());
+                }
             }
         }
 
-        let capacity = spawned_chunks.len();
+        if rehomed {
+            #[cfg(feature = "render")]
+            rehome_chunks(&mut meshes, &mut tilemap, &mut chunk_query);
+            #[cfg(not(feature = "render"))]
+            rehome_chunks(&mut tilemap, &mut chunk_query);
+        }
+
         for point in spawned_chunks.into_iter() {
-            if tilemap.spawned_chunks().contains(&(point.x, point.y)) {
-                continue;
-            } else {
-                tilemap.spawned_chunks_mut().insert((point.x, point.y));
+            #[cfg(feature = "render")]
+            spawn_chunk(commands, &mut meshes, map_entity, &mut tilemap, point);
+            #[cfg(not(feature = "render"))]
+            spawn_chunk(commands, map_entity, &mut tilemap, point);
+        }
+
+        if let Some(budget) = tilemap.spawn_budget() {
+            let start = Instant::now();
+            let mut queue = tilemap.spawn_queue_by_distance();
+            let mut spawned = 0;
+            while budget.allows(spawned, start) {
+                let point = match queue.pop() {
+                    Some(Reverse((_distance, point))) => point,
+                    None => break,
+                };
+                if tilemap.spawn_queued_chunk(point) {
+                    #[cfg(feature = "render")]
+                    spawn_chunk(commands, &mut meshes, map_entity, &mut tilemap, point);
+                    #[cfg(not(feature = "render"))]
+                    spawn_chunk(commands, map_entity, &mut tilemap, point);
+                    spawned += 1;
+                }
             }
+        }
 
-            let layers = tilemap.layers();
-            let layers_len = tilemap.layers().len();
-            let chunk_dimensions = tilemap.chunk_dimensions();
-            let tile_dimensions = tilemap.tile_dimensions();
-            let texture_atlas = tilemap.texture_atlas().clone_weak();
-            let pipeline_handle = tilemap.topology().to_pipeline_handle();
-            let topology = tilemap.topology();
-            let chunk = if let Some(chunk) = tilemap.chunks_mut().get_mut(&point) {
-                chunk
-            } else {
-                warn!("Can not get chunk at {}, skipping", &point);
-                continue;
-            };
-            let mut entities = Vec::with_capacity(capacity);
-            for z_order in 0..layers_len {
-                if layers.get(z_order).is_none() {
+        // Frozen chunks update their meshes once they are woken up.
+        #[cfg(feature = "render")]
+        let frozen_entities: HashSet<Entity> = tilemap
+            .chunks_with_activity(ChunkActivity::Frozen)
+            .iter()
+            .filter_map(|point| tilemap.get_chunk(point))
+            .flat_map(|chunk| chunk.get_entities())
+            .collect();
+        #[cfg(feature = "render")]
+        for layers in modified_chunks.into_iter() {
+            for (_layer, entity) in layers.into_iter() {
+                if frozen_entities.contains(&entity) {
                     continue;
                 }
-                let mut mesh = Mesh::from(&ChunkMesh::new(chunk_dimensions));
-                let (indexes, colors) =
-                    if let Some(parts) = chunk.tiles_to_renderer_parts(z_order, chunk_dimensions) {
+                let mut modified_layer = if let Ok(layer) = layer_query.get_mut(entity) {
+                    layer
+                } else {
+                    warn!("Chunk layer does not exist, skipping");
+                    continue;
+                };
+                modified_layer.0 += 1;
+            }
+        }
+
+        for (entities, point) in despawned_chunks.into_iter() {
+            for entity in entities.into_iter() {
+                commands.despawn_recursive(entity);
+            }
+            info!("Chunk {} despawned from {}", point, *tilemap);
+        }
+
+        tilemap.adapt_layer_storage(&modified_points);
+        // Chunks which are removed for being empty are despawned next frame.
+        tilemap.collect_empty_chunks(&modified_points);
+    }
+}
+
+/// Spawns the layer entities of a chunk, with their meshes, as children of
+/// the tilemap. Without the `render` feature they have no meshes.
+fn spawn_chunk(
+    commands: &mut Commands,
+    #[cfg(feature = "render")] meshes: &mut Assets<Mesh>,
+    map_entity: Entity,
+    tilemap: &mut Tilemap,
+    point: Point2,
+) {
+    if tilemap.spawned_chunks().contains(&(point.x, point.y)) {
+        return;
+    } else {
+        tilemap.spawned_chunks_mut().insert((point.x, point.y));
+    }
+
+    let layers = tilemap.layers();
+    let layers_len = tilemap.layers().len();
+    #[cfg(feature = "render")]
+    let chunk_dimensions = tilemap.chunk_dimensions();
+    #[cfg(feature = "render")]
+    let tile_dimensions = tilemap.tile_dimensions();
+    #[cfg(feature = "render")]
+    let texture_atlas = tilemap.texture_atlas().clone_weak();
+    #[cfg(feature = "render")]
+    let pipeline_handle = tilemap.chunk_pipeline_handle();
+    #[cfg(feature = "lighting")]
+    let normal_map = tilemap.normal_map().map(Handle::clone_weak);
+    #[cfg(feature = "render")]
+    let palette = ChunkPalette::from_tilemap(tilemap);
+    #[cfg(feature = "render")]
+    let layer_render_layers: Vec<RenderLayers> = (0..layers_len)
+        .map(|z_order| tilemap.layer_render_layers(z_order))
+        .collect();
+    let topology = tilemap.topology();
+    let chunk_translation = tilemap.chunk_translation(topology, point);
+    let render_order = tilemap.render_order();
+    let tilemap_name = tilemap.to_string();
+    #[cfg(feature = "render")]
+    let sprite_rects = tilemap.sprite_rects().clone();
+    #[cfg(feature = "render")]
+    let wall_sprites = tilemap.wall_sprites().clone();
+    #[cfg(feature = "render")]
+    let channels = tilemap.layer_channels().clone();
+    #[cfg(feature = "render")]
+    let occlusions = layer_occlusions(tilemap);
+    #[cfg(feature = "render")]
+    let template = tilemap.chunk_instance(point);
+    #[cfg(feature = "render")]
+    let mut template_meshes: HashMap<usize, Handle<Mesh>> = template
+        .map(|template| {
+            (0..layers_len)
+                .filter_map(|z_order| {
+                    let mesh = tilemap.template_mesh(template, z_order)?;
+                    Some((z_order, mesh.clone()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    #[cfg(feature = "render")]
+    let mut new_template_meshes = Vec::new();
+    let chunk = if let Some(chunk) = tilemap.chunk_mut(&point) {
+        chunk
+    } else {
+        warn!(
+            "Can not get chunk at {} on {}, skipping",
+            &point, tilemap_name
+        );
+        return;
+    };
+    let mut entities = Vec::with_capacity(layers_len);
+    for z_order in 0..layers_len {
+        if layers.get(z_order).is_none() {
+            continue;
+        }
+        let translation = Vec3::new(
+            chunk_translation.x,
+            chunk_translation.y,
+            render_order + layer_depth(&layers, z_order),
+        );
+
+        #[cfg(feature = "render")]
+        let entity = {
+            let y_sort = is_y_sorted(&layers, z_order);
+            let walls = layer_walls(&layers, z_order, topology, &wall_sprites);
+            let shared = template.filter(|_| is_shareable(&layers, z_order, topology));
+            let mesh_handle = match shared.and_then(|_| template_meshes.remove(&z_order)) {
+                Some(mesh_handle) => mesh_handle,
+                None => {
+                    let chunk_mesh = layer_chunk_mesh(
+                        point,
+                        chunk_dimensions,
+                        z_order,
+                        topology,
+                        y_sort,
+                        walls.is_some(),
+                    );
+                    let mut mesh = Mesh::from(&chunk_mesh);
+                    let ((indexes, colors, extras, offsets), channel_parts) = if let Some(parts) =
+                        layer_renderer_parts(
+                            chunk,
+                            z_order,
+                            chunk_dimensions,
+                            tile_dimensions,
+                            &channels,
+                            walls,
+                            occlusions.get(z_order).and_then(Option::as_ref),
+                        ) {
                         parts
                     } else {
                         warn!("Can not split tiles to data for the renderer");
                         continue;
                     };
-                mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_INDEX, indexes);
-                mesh.set_attribute(ChunkMesh::ATTRIBUTE_TILE_COLOR, colors);
-                let mesh_handle = meshes.add(mesh);
-                chunk.set_mesh(z_order, mesh_handle.clone());
-
-                use GridTopology::*;
-                let translation_x = match topology {
-                    HexX | HexEvenCols | HexOddCols => {
-                        (((chunk.point().x * tile_dimensions.width as i32) as f32 * 0.75) as i32
-                            * chunk_dimensions.width as i32) as f32
-                    }
-                    HexY => {
-                        (chunk.point().x
-                            * tile_dimensions.width as i32
-                            * chunk_dimensions.width as i32) as f32
-                            + (chunk.point().y as f32 * chunk_dimensions.height as f32 * 0.5)
-                                * tile_dimensions.width as f32
-                    }
-                    Square | HexEvenRows | HexOddRows => {
-                        (chunk.point().x
-                            * tile_dimensions.width as i32
-                            * chunk_dimensions.width as i32) as f32
-                    }
-                };
-                let translation_y = match topology {
-                    HexX => {
-                        (chunk.point().y
-                            * tile_dimensions.height as i32
-                            * chunk_dimensions.height as i32) as f32
-                            + (chunk.point().x as f32 * chunk_dimensions.width as f32 * 0.5)
-                                * tile_dimensions.height as f32
-                    }
-                    HexY | HexEvenRows | HexOddRows => {
-                        (((chunk.point().y * tile_dimensions.height as i32) as f32 * 0.75) as i32
-                            * chunk_dimensions.height as i32) as f32
+                    ChunkMesh::set_tiles(
+                        &mut mesh,
+                        indexes,
+                        colors,
+                        extras,
+                        offsets,
+                        channel_parts,
+                        &sprite_rects,
+                    );
+                    let mesh_handle = meshes.add(mesh);
+                    if let Some(template) = shared {
+                        new_template_meshes.push((template, z_order, mesh_handle.clone()));
                     }
-                    Square | HexEvenCols | HexOddCols => {
-                        (chunk.point().y
-                            * tile_dimensions.height as i32
-                            * chunk_dimensions.height as i32) as f32
+                    mesh_handle
+                }
+            };
+            chunk.set_mesh(z_order, mesh_handle.clone());
+
+            let pipeline = RenderPipeline::new(pipeline_handle.clone_weak());
+            let entity = if let Some(entity) = commands
+                .spawn(ChunkBundle {
+                    point,
+                    z_order: ZOrder(z_order),
+                    texture_atlas: texture_atlas.clone_weak(),
+                    mesh: mesh_handle.clone_weak(),
+                    transform: Transform::from_translation(translation),
+                    render_pipelines: RenderPipelines::from_pipelines(vec![pipeline]),
+                    draw: Default::default(),
+                    visible: Visible {
+                        // TODO: this would be nice as a config parameter to make
+                        // RapierRenderPlugin's output visible.
+                        is_visible: !is_hidden(&layers, z_order),
+                        is_transparent: true,
+                    },
+                    main_pass: MainPass,
+                    global_transform: Default::default(),
+                    modified_layer: Default::default(),
+                    color: Default::default(),
+                    animation: Default::default(),
+                    palette: palette.clone(),
+                    render_layers: layer_render_layers
+                        .get(z_order)
+                        .copied()
+                        .unwrap_or_default(),
+                })
+                .current_entity()
+            {
+                entity
+            } else {
+                error!("Chunk entity does not exist unexpectedly, can not run the tilemap system");
+                return;
+            };
+            #[cfg(feature = "lighting")]
+            if let Some(normal_map) = &normal_map {
+                commands.insert_one(entity, ChunkLighting::new(normal_map.clone_weak()));
+            }
+
+            if let Some(Some(layer)) = layers.get(z_order) {
+                spawn_chunk_passes(
+                    commands,
+                    layer,
+                    entity,
+                    &texture_atlas,
+                    &mesh_handle,
+                    topology,
+                );
+            }
+            entity
+        };
+        #[cfg(not(feature = "render"))]
+        let entity = if let Some(entity) = commands
+            .spawn(ChunkLayerBundle {
+                point,
+                transform: Transform::from_translation(translation),
+                global_transform: Default::default(),
+            })
+            .current_entity()
+        {
+            entity
+        } else {
+            error!("Chunk entity does not exist unexpectedly, can not run the tilemap system");
+            return;
+        };
+
+        info!("Chunk {} spawned on {}", point, tilemap_name);
+
+        chunk.add_entity(z_order, entity);
+        entities.push(entity);
+    }
+    commands.push_children(map_entity, &entities);
+    #[cfg(feature = "render")]
+    for (template, z_order, mesh_handle) in new_template_meshes.into_iter() {
+        tilemap.set_template_mesh(template, z_order, mesh_handle);
+    }
+}
+
+/// Gives the layers of a chunk which share the meshes of its template meshes
+/// of their own, once it was changed and is no longer a copy of it. The mask
+/// and picking passes of the layers draw the new meshes too.
+#[cfg(feature = "render")]
+fn detach_chunk_instance(
+    meshes: &mut Assets<Mesh>,
+    mesh_query: &mut Query<&mut Handle<Mesh>>,
+    children_query: &Query<&Children>,
+    tilemap: &mut Tilemap,
+    point: Point2,
+) {
+    let template = match tilemap.remove_chunk_instance(point) {
+        Some(template) => template,
+        None => return,
+    };
+    let layers = tilemap.layers();
+    let template_meshes: Vec<(usize, Handle<Mesh>)> = (0..layers.len())
+        .filter_map(|z_order| {
+            let mesh = tilemap.template_mesh(template, z_order)?;
+            Some((z_order, mesh.clone_weak()))
+        })
+        .collect();
+    let chunk_dimensions = tilemap.chunk_dimensions();
+    let tile_dimensions = tilemap.tile_dimensions();
+    let topology = tilemap.topology();
+    let sprite_rects = tilemap.sprite_rects().clone();
+    let wall_sprites = tilemap.wall_sprites().clone();
+    let channels = tilemap.layer_channels().clone();
+    let occlusions = layer_occlusions(tilemap);
+    let chunk = match tilemap.chunk_mut(&point) {
+        Some(chunk) => chunk,
+        None => return,
+    };
+    for (z_order, template_mesh) in template_meshes.into_iter() {
+        if chunk.get_mesh(z_order) != Some(&template_mesh) {
+            continue;
+        }
+        chunk.set_mesh(z_order, Handle::default());
+        build_layer_mesh(
+            meshes,
+            chunk,
+            z_order,
+            chunk_dimensions,
+            tile_dimensions,
+            topology,
+            is_y_sorted(&layers, z_order),
+            layer_walls(&layers, z_order, topology, &wall_sprites),
+            occlusions.get(z_order).and_then(Option::as_ref),
+            &sprite_rects,
+            &channels,
+        );
+        let entity = match chunk.get_entity(z_order) {
+            Some(entity) => entity,
+            None => continue,
+        };
+        let mesh = match chunk.get_mesh(z_order) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        if let Ok(mut mesh_handle) = mesh_query.get_mut(entity) {
+            *mesh_handle = mesh.clone_weak();
+        }
+        // The mask and picking passes of the layer draw its mesh too.
+        if let Ok(children) = children_query.get(entity) {
+            for child in children.iter() {
+                if let Ok(mut mesh_handle) = mesh_query.get_mut(*child) {
+                    if *mesh_handle == template_mesh {
+                        *mesh_handle = mesh.clone_weak();
                     }
+                }
+            }
+        }
+    }
+}
+
+/// Moves the layer entities of the spawned chunks to the translations of
+/// their points after the origin of the tilemap moved, and builds their
+/// meshes again for the new tile points.
+fn rehome_chunks(
+    #[cfg(feature = "render")] meshes: &mut Assets<Mesh>,
+    tilemap: &mut Tilemap,
+    chunk_query: &mut Query<(&mut Transform, &mut Point2)>,
+) {
+    #[cfg(feature = "render")]
+    let chunk_dimensions = tilemap.chunk_dimensions();
+    #[cfg(feature = "render")]
+    let tile_dimensions = tilemap.tile_dimensions();
+    let topology = tilemap.topology();
+    let layers_len = tilemap.layers().len();
+    #[cfg(feature = "render")]
+    let layers = tilemap.layers();
+    #[cfg(feature = "render")]
+    let sprite_rects = tilemap.sprite_rects().clone();
+    #[cfg(feature = "render")]
+    let wall_sprites = tilemap.wall_sprites().clone();
+    #[cfg(feature = "render")]
+    let channels = tilemap.layer_channels().clone();
+    #[cfg(feature = "render")]
+    let occlusions = layer_occlusions(tilemap);
+    let spawned: Vec<Point2> = tilemap
+        .spawned_chunks()
+        .iter()
+        .map(|point| point.into())
+        .collect();
+    for point in spawned.into_iter() {
+        let translation = tilemap.chunk_translation(topology, point);
+        let chunk = match tilemap.chunk_mut(&point) {
+            Some(chunk) => chunk,
+            None => continue,
+        };
+        for z_order in 0..layers_len {
+            #[cfg(feature = "render")]
+            rehome_layer_mesh(
+                meshes,
+                chunk,
+                z_order,
+                chunk_dimensions,
+                tile_dimensions,
+                topology,
+                is_y_sorted(&layers, z_order),
+                layer_walls(&layers, z_order, topology, &wall_sprites),
+                occlusions.get(z_order).and_then(Option::as_ref),
+                &sprite_rects,
+                &channels,
+            );
+            let entity = match chunk.get_entity(z_order) {
+                Some(entity) => entity,
+                None => continue,
+            };
+            if let Ok((mut transform, mut chunk_point)) = chunk_query.get_mut(entity) {
+                transform.translation.x = translation.x;
+                transform.translation.y = translation.y;
+                *chunk_point = point;
+            }
+        }
+    }
+}
+
+/// Builds the mesh of a chunk layer again at the point of its chunk, if it
+/// has one.
+#[cfg(feature = "render")]
+fn rehome_layer_mesh(
+    meshes: &mut Assets<Mesh>,
+    chunk: &mut Chunk,
+    z_order: usize,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+    topology: GridTopology,
+    y_sort: bool,
+    walls: Option<&HashMap<usize, WallSprite>>,
+    occlusion: Option<&TileOcclusion>,
+    sprite_rects: &HashMap<usize, SpriteRect>,
+    channels: &HashMap<usize, Vec<TileChannel>>,
+) {
+    let mesh = match chunk
+        .get_mesh(z_order)
+        .and_then(|handle| meshes.get_mut(handle))
+    {
+        Some(mesh) => mesh,
+        None => return,
+    };
+    let chunk_mesh = layer_chunk_mesh(
+        chunk.point(),
+        chunk_dimensions,
+        z_order,
+        topology,
+        y_sort,
+        walls.is_some(),
+    );
+    *mesh = Mesh::from(&chunk_mesh);
+    build_layer_mesh(
+        meshes,
+        chunk,
+        z_order,
+        chunk_dimensions,
+        tile_dimensions,
+        topology,
+        y_sort,
+        walls,
+        occlusion,
+        sprite_rects,
+        channels,
+    );
+}
+
+/// Returns the layers above every layer of a tilemap whose opaque tiles hide
+/// its tiles, by z order.
+#[cfg(feature = "render")]
+fn layer_occlusions(tilemap: &Tilemap) -> Vec<Option<TileOcclusion>> {
+    (0..tilemap.layers().len())
+        .map(|z_order| tilemap.layer_occlusion(z_order))
+        .collect()
+}
+
+/// Returns `true` if the layer at the z order is y sorted.
+#[cfg(feature = "render")]
+fn is_y_sorted(layers: &[Option<TilemapLayer>], z_order: usize) -> bool {
+    if let Some(Some(layer)) = layers.get(z_order) {
+        layer.is_y_sorted()
+    } else {
+        false
+    }
+}
+
+/// Returns the z translation of the layer at the z order.
+fn layer_depth(layers: &[Option<TilemapLayer>], z_order: usize) -> f32 {
+    if let Some(Some(layer)) = layers.get(z_order) {
+        layer.depth.unwrap_or(z_order as f32)
+    } else {
+        z_order as f32
+    }
+}
+
+/// Returns `true` if the layer at the z order is hidden.
+#[cfg(feature = "render")]
+fn is_hidden(layers: &[Option<TilemapLayer>], z_order: usize) -> bool {
+    if let Some(Some(layer)) = layers.get(z_order) {
+        layer.hidden
+    } else {
+        false
+    }
+}
+
+/// Returns `true` if the mesh of the layer at the z order does not depend on
+/// the point of its chunk, so the copies of a chunk template can share it.
+#[cfg(feature = "render")]
+fn is_shareable(layers: &[Option<TilemapLayer>], z_order: usize, topology: GridTopology) -> bool {
+    match layers.get(z_order) {
+        Some(Some(layer)) => {
+            !layer.mask
+                && !layer.picking
+                && !layer.is_y_sorted()
+                && topology != GridTopology::IsoDiamond
+        }
+        _ => false,
+    }
+}
+
+/// Returns the walls of the sprite indexes if the tiles of the layer at the z
+/// order are extruded into them, which they only are on square tilemaps.
+#[cfg(feature = "render")]
+pub(crate) fn layer_walls<'a>(
+    layers: &[Option<TilemapLayer>],
+    z_order: usize,
+    topology: GridTopology,
+    wall_sprites: &'a HashMap<usize, WallSprite>,
+) -> Option<&'a HashMap<usize, WallSprite>> {
+    match layers.get(z_order) {
+        Some(Some(layer)) if layer.walls && topology == GridTopology::Square => Some(wall_sprites),
+        _ => None,
+    }
+}
+
+/// Constructs the mesh of a chunk layer, with the depth which the topology or
+/// the layer sorts its tiles by and the side faces of its walls.
+#[cfg(feature = "render")]
+fn layer_chunk_mesh(
+    point: Point2,
+    chunk_dimensions: Dimension2,
+    z_order: usize,
+    topology: GridTopology,
+    y_sort: bool,
+    walls: bool,
+) -> ChunkMesh {
+    let mut chunk_mesh = ChunkMesh::new(point, chunk_dimensions, z_order);
+    if walls {
+        chunk_mesh = chunk_mesh.with_walls();
+    }
+    match topology {
+        GridTopology::IsoDiamond => chunk_mesh.with_iso_depth(),
+        GridTopology::Square if y_sort => chunk_mesh.with_row_depth(),
+        _ => chunk_mesh,
+    }
+}
+
+/// Changes the tiles and channels of a chunk layer into attributes for the
+/// renderer, extruding its walls if it has them and hiding the tiles under
+/// opaque tiles.
+#[cfg(feature = "render")]
+pub(crate) fn layer_renderer_parts(
+    chunk: &Chunk,
+    z_order: usize,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+    channels: &HashMap<usize, Vec<TileChannel>>,
+    walls: Option<&HashMap<usize, WallSprite>>,
+    occlusion: Option<&TileOcclusion>,
+) -> Option<(TileAttributes, Vec<(String, VertexAttributeValues)>)> {
+    let parts =
+        chunk.tiles_to_renderer_parts(z_order, chunk_dimensions, tile_dimensions, occlusion)?;
+    let channel_parts = chunk.channels_to_renderer_parts(z_order, channels, chunk_dimensions);
+    Some(match walls {
+        Some(walls) => {
+            ChunkMesh::extrude_walls(parts, channel_parts, walls, tile_dimensions.height as f32)
+        }
+        None => (parts, channel_parts),
+    })
+}
+
+/// Builds the mesh of a chunk layer, or updates it if it already exists.
+#[cfg(feature = "render")]
+pub(crate) fn build_layer_mesh(
+    meshes: &mut Assets<Mesh>,
+    chunk: &mut Chunk,
+    z_order: usize,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+    topology: GridTopology,
+    y_sort: bool,
+    walls: Option<&HashMap<usize, WallSprite>>,
+    occlusion: Option<&TileOcclusion>,
+    sprite_rects: &HashMap<usize, SpriteRect>,
+    channels: &HashMap<usize, Vec<TileChannel>>,
+) {
+    let mesh = chunk.get_mesh(z_order).cloned();
+    if let Some(mesh) = update_layer_mesh(
+        meshes,
+        chunk,
+        mesh,
+        z_order,
+        chunk_dimensions,
+        tile_dimensions,
+        topology,
+        y_sort,
+        walls,
+        occlusion,
+        sprite_rects,
+        channels,
+    ) {
+        chunk.set_mesh(z_order, mesh);
+    }
+}
+
+/// Updates the mesh of a chunk layer, or builds a new one if there is none,
+/// and returns its handle. If the chunk has no such layer, `None` is
+/// returned.
+#[cfg(feature = "render")]
+pub(crate) fn update_layer_mesh(
+    meshes: &mut Assets<Mesh>,
+    chunk: &Chunk,
+    mesh: Option<Handle<Mesh>>,
+    z_order: usize,
+    chunk_dimensions: Dimension2,
+    tile_dimensions: Dimension2,
+    topology: GridTopology,
+    y_sort: bool,
+    walls: Option<&HashMap<usize, WallSprite>>,
+    occlusion: Option<&TileOcclusion>,
+    sprite_rects: &HashMap<usize, SpriteRect>,
+    channels: &HashMap<usize, Vec<TileChannel>>,
+) -> Option<Handle<Mesh>> {
+    let ((indexes, colors, extras, offsets), channel_parts) = layer_renderer_parts(
+        chunk,
+        z_order,
+        chunk_dimensions,
+        tile_dimensions,
+        channels,
+        walls,
+        occlusion,
+    )?;
+    if let Some(handle) = mesh {
+        if let Some(mesh) = meshes.get_mut(&handle) {
+            ChunkMesh::set_tiles(
+                mesh,
+                indexes,
+                colors,
+                extras,
+                offsets,
+                channel_parts,
+                sprite_rects,
+            );
+            return Some(handle);
+        }
+    }
+    let chunk_mesh = layer_chunk_mesh(
+        chunk.point(),
+        chunk_dimensions,
+        z_order,
+        topology,
+        y_sort,
+        walls.is_some(),
+    );
+    let mut mesh = Mesh::from(&chunk_mesh);
+    ChunkMesh::set_tiles(
+        &mut mesh,
+        indexes,
+        colors,
+        extras,
+        offsets,
+        channel_parts,
+        sprite_rects,
+    );
+    Some(meshes.add(mesh))
+}
+
+/// The instance handling system for tilemap data.
+///
+/// The chunk meshes of every `TilemapData` asset that is used by an instance
+/// are built when it is first used and rebuilt for the chunks which changed
+/// whenever the asset is. Then each instance spawns the chunks, sharing the
+/// meshes, that it is missing and despawns the chunks which no longer are.
+#[cfg(feature = "render")]
+pub(crate) fn tilemap_instances(
+    commands: &mut Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut data_meshes: Local<HashMap<Handle<TilemapData>, DataMeshes>>,
+    mut asset_event_reader: Local<EventReader<AssetEvent<TilemapData>>>,
+    asset_events: Res<Events<AssetEvent<TilemapData>>>,
+    tilemaps: Res<Assets<TilemapData>>,
+    mut instance_query: Query<(Entity, &Handle<TilemapData>, &mut TilemapInstance)>,
+) {
+    let mut changed: HashSet<Handle<TilemapData>> = HashSet::default();
+    for event in asset_event_reader.iter(&asset_events) {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed.insert(handle.clone_weak());
+            }
+            AssetEvent::Removed { handle } => {
+                data_meshes.remove(handle);
+            }
+        }
+    }
+    let mut handles: Vec<Handle<TilemapData>> = Vec::new();
+    for (_entity, handle, _instance) in instance_query.iter_mut() {
+        if !handles.contains(handle) {
+            handles.push(handle.clone_weak());
+        }
+    }
+    // The meshes of data which is no longer rendered are dropped.
+    data_meshes.retain(|handle, _| handles.contains(handle));
+    for handle in handles.into_iter() {
+        // The asset may not have been added or loaded yet.
+        let data = if let Some(data) = tilemaps.get(&handle) {
+            data
+        } else {
+            continue;
+        };
+        if data_meshes.contains_key(&handle) && !changed.contains(&handle) {
+            continue;
+        }
+        data_meshes
+            .entry(handle)
+            .or_default()
+            .update(&mut meshes, data);
+    }
+
+    for (instance_entity, handle, mut instance) in instance_query.iter_mut() {
+        let (data, chunk_meshes) = match (tilemaps.get(handle), data_meshes.get(handle)) {
+            (Some(data), Some(chunk_meshes)) => (data, chunk_meshes),
+            _ => continue,
+        };
+        // Chunks which were removed or whose layers have new meshes are
+        // despawned, and spawned again below if they still exist.
+        let despawned = instance.remove_chunks_where(|point, meshes| {
+            chunk_meshes
+                .get(point)
+                .is_some_and(|chunk_meshes| chunk_meshes.iter().flatten().eq(meshes.iter()))
+        });
+        for entity in despawned.into_iter() {
+            commands.despawn_recursive(entity);
+        }
+
+        let pipeline_handle = data.chunk_pipeline_handle();
+        let mut spawned_entities = Vec::new();
+        for (point, layer_meshes) in chunk_meshes.iter() {
+            if instance.contains_chunk(point) {
+                continue;
+            }
+            let translation = data.chunk_translation(*point);
+            let mut entities = Vec::with_capacity(layer_meshes.len());
+            for (z_order, mesh) in layer_meshes.iter().enumerate() {
+                let mesh = if let Some(mesh) = mesh {
+                    mesh
+                } else {
+                    continue;
                 };
-                let translation = Vec3::new(translation_x, translation_y, z_order as f32);
-                let pipeline = RenderPipeline::new(pipeline_handle.clone_weak().typed());
+                let depth = data.render_order() + data.layer_depth(z_order);
+                let translation = Vec3::new(translation.x, translation.y, depth);
+                let pipeline = RenderPipeline::new(pipeline_handle.clone_weak());
                 let entity = if let Some(entity) = commands
-                    .spawn(ChunkBundle {
-                        point,
+                    .spawn(ChunkInstanceBundle {
+                        point: *point,
                         z_order: ZOrder(z_order),
-                        texture_atlas: texture_atlas.clone_weak(),
-                        mesh: mesh_handle.clone_weak(),
+                        texture_atlas: data.texture_atlas().clone_weak(),
+                        mesh: mesh.clone_weak(),
                         transform: Transform::from_translation(translation),
                         render_pipelines: RenderPipelines::from_pipelines(vec![pipeline]),
                         draw: Default::default(),
                         visible: Visible {
-                            // TODO: this would be nice as a config parameter to make
-                            // RapierRenderPlugin's output visible.
-                            is_visible: true,
+                            is_visible: data.is_layer_visible(z_order),
                             is_transparent: true,
                         },
                         main_pass: MainPass,
                         global_transform: Default::default(),
-                        modified_layer: Default::default(),
+                        color: Default::default(),
+                        animation: Default::default(),
+                        palette: data.palette().clone(),
+                        render_layers: data.layer_render_layers(z_order),
                     })
                     .current_entity()
                 {
@@ -157,266 +1108,498 @@ pub(crate) fn tilemap_events(
                     );
                     return;
                 };
-
-                info!("Chunk {} spawned", point);
-
-                chunk.add_entity(z_order, entity);
+                #[cfg(feature = "lighting")]
+                if let Some(normal_map) = data.normal_map() {
+                    commands.insert_one(entity, ChunkLighting::new(normal_map.clone_weak()));
+                }
+                if let Some(Some(layer)) = data.layers().get(z_order) {
+                    spawn_chunk_passes(
+                        commands,
+                        layer,
+                        entity,
+                        data.texture_atlas(),
+                        mesh,
+                        data.topology(),
+                    );
+                }
                 entities.push(entity);
             }
-            commands.push_children(map_entity, &entities);
+            info!("Chunk {} spawned on an instance of {}", point, data);
+            spawned_entities.extend(entities.iter().cloned());
+            let meshes = layer_meshes.iter().flatten().map(Handle::clone_weak);
+            instance.insert_chunk(*point, entities, meshes.collect());
         }
+        commands.push_children(instance_entity, &spawned_entities);
+    }
+}
 
-        for layers in modified_chunks.into_iter() {
-            for (_layer, entity) in layers.into_iter() {
-                let mut modified_layer = if let Ok(layer) = layer_query.get_mut(entity) {
-                    layer
-                } else {
-                    warn!("Chunk layer does not exist, skipping");
-                    continue;
-                };
-                modified_layer.0 += 1;
-            }
+/// Spawns the entities which draw a chunk layer in the other passes that the
+/// layer is drawn in, as children of the chunk layer entity.
+#[cfg(feature = "render")]
+fn spawn_chunk_passes(
+    commands: &mut Commands,
+    layer: &TilemapLayer,
+    layer_entity: Entity,
+    texture_atlas: &Handle<TextureAtlas>,
+    mesh: &Handle<Mesh>,
+    topology: GridTopology,
+) {
+    let visible = Visible {
+        is_visible: true,
+        is_transparent: true,
+    };
+    let mut pass_entities = Vec::new();
+    if layer.mask {
+        let pipeline = RenderPipeline::new(topology.to_mask_pipeline_handle().typed());
+        let mask_entity = commands
+            .spawn(ChunkMaskBundle {
+                texture_atlas: texture_atlas.clone_weak(),
+                mesh: mesh.clone_weak(),
+                transform: Default::default(),
+                render_pipelines: RenderPipelines::from_pipelines(vec![pipeline]),
+                draw: Default::default(),
+                visible: visible.clone(),
+                mask_pass: TilemapMaskPass,
+                global_transform: Default::default(),
+            })
+            .current_entity();
+        if let Some(entity) = mask_entity {
+            pass_entities.push(entity);
+        } else {
+            error!("Chunk mask entity does not exist unexpectedly, can not spawn the mask");
         }
-
-        for (entities, point) in despawned_chunks.into_iter() {
-            for entity in entities.into_iter() {
-                commands.despawn_recursive(entity);
-            }
-            info!("Chunk {} despawned", point);
+    }
+    if layer.picking {
+        let pipeline = RenderPipeline::new(topology.to_picking_pipeline_handle().typed());
+        let picking_entity = commands
+            .spawn(ChunkPickingBundle {
+                texture_atlas: texture_atlas.clone_weak(),
+                mesh: mesh.clone_weak(),
+                transform: Default::default(),
+                render_pipelines: RenderPipelines::from_pipelines(vec![pipeline]),
+                draw: Default::default(),
+                visible,
+                picking_pass: TilemapPickingPass,
+                global_transform: Default::default(),
+            })
+            .current_entity();
+        if let Some(entity) = picking_entity {
+            pass_entities.push(entity);
+        } else {
+            error!("Chunk picking entity does not exist unexpectedly, can not spawn the picking");
         }
     }
+    commands.push_children(layer_entity, &pass_entities);
 }
 
-/// Spawns collisions based on given arguments.
+/// Spawns the collisions of a chunk layer with the physics backend, replacing
+/// the ones it had.
 ///
-/// This is a bit messy and has quite a few inputs but, quite a few parts had
-/// to be cloned. There very likely is a better way to clean this up.
-#[cfg(feature = "bevy_rapier2d")]
-fn spawn_collisions(
+/// Contiguous tiles are merged into as few rectangles as possible, so that
+/// large maps need only a handful of colliders per chunk. If only some points
+/// of the layer changed, only the colliders which cover them are replaced and
+/// their tiles merged again, all the other colliders are kept. Returns the
+/// amount of colliders that were spawned.
+fn spawn_collisions<B: TilemapPhysicsBackend>(
     commands: &mut Commands,
+    backend: &B,
+    tilemap_entity: Entity,
+    tilemap: &mut Tilemap,
+    transform: &GlobalTransform,
     layers: &[Option<TilemapLayer>],
-    point: Point2,
+    chunk_point: Point2,
     z_order: usize,
-    chunk: &mut Chunk,
-    chunk_dimensions: Dimension2,
-    tile_dimensions: Dimension2,
-    transform: &Transform,
-    physics_tile_width: f32,
-    physics_tile_height: f32,
-) {
-    // Don't continue if there is no layer.
-    if let Some(layer_opt) = layers.get(z_order) {
-        match layer_opt {
-            Some(layer) => {
-                if layer.interaction_groups.0 == 0 {
-                    return;
-                }
-            }
-            None => return,
-        }
-    }
+    changed_points: Option<&HashSet<Point2>>,
+) -> usize {
+    let chunk_dimensions = tilemap.chunk_dimensions();
+    let tile_dimensions = tilemap.tile_dimensions();
+    let chunk = if let Some(chunk) = tilemap.chunk_mut(&chunk_point) {
+        chunk
+    } else {
+        warn!("Can not get chunk at {}, skipping", &chunk_point);
+        return 0;
+    };
     // Don't continue if there is no entity.
     let entity = match chunk.get_entity(z_order) {
         Some(e) => e,
-        None => return,
+        None => return 0,
     };
-    // Don't continue if there already is a collision there.
-    let index = chunk_dimensions.encode_point_unchecked(point);
-    if chunk.get_collision_entity(index).is_some() {
-        return;
+    let layer = match layers.get(z_order) {
+        Some(Some(layer)) if backend.has_colliders(layer) => Some(layer),
+        _ => None,
+    };
+    // Every collider is replaced if the whole layer changed or if it has no
+    // collisions now, otherwise only those that cover a changed point.
+    let old_colliders = chunk.set_colliders(z_order, Vec::new()).unwrap_or_default();
+    let (replaced, mut colliders): (Vec<_>, Vec<_>) = match (changed_points, layer) {
+        (Some(points), Some(_)) => old_colliders
+            .into_iter()
+            .partition(|collider| points.iter().any(|point| collider.contains(*point))),
+        _ => (old_colliders, Vec::new()),
+    };
+    for collider in replaced.iter() {
+        backend.despawn_collider(commands, collider.entity);
     }
+    // Don't continue if there is no layer or it does not collide.
+    let layer = match layer {
+        Some(layer) => layer,
+        None => {
+            chunk.set_colliders(z_order, colliders);
+            return 0;
+        }
+    };
+    // The indexes of the tiles which need new colliders, or `None` for all.
+    let region: Option<HashSet<usize>> = changed_points.map(|points| {
+        points
+            .iter()
+            .cloned()
+            .chain(replaced.iter().flat_map(|collider| collider.points()))
+            .filter_map(|point| chunk_dimensions.encode_point(point).ok())
+            .collect()
+    });
+    let in_region = |index: usize| match &region {
+        Some(region) => region.contains(&index),
+        None => true,
+    };
+
+    let chunk = if let Some(chunk) = tilemap.get_chunk(&chunk_point) {
+        chunk
+    } else {
+        return 0;
+    };
+    let one_way_sprites = tilemap.one_way_sprites();
+    let sensor_sprites = tilemap.sensor_sprites();
+    let is_sensor = |tile: &RawTile| layer.sensor || sensor_sprites.contains(&tile.index);
+    let is_one_way = |tile: &RawTile| {
+        !is_sensor(tile) && (layer.one_way || one_way_sprites.contains(&tile.index))
+    };
+    // Solid tiles and one-way platforms are never merged together, and each
+    // sensor tile gets its own collider so that it knows its point.
+    let solid_rects = chunk.merge_tiles(z_order, chunk_dimensions, |index, tile| {
+        in_region(index) && !is_sensor(tile) && !is_one_way(tile)
+    });
+    let one_way_rects = chunk.merge_tiles(z_order, chunk_dimensions, |index, tile| {
+        in_region(index) && is_one_way(tile)
+    });
+    let mut sensor_points = Vec::new();
+    for index in chunk.get_tile_indices(z_order).unwrap_or_default() {
+        if !in_region(index) {
+            continue;
+        }
+        match (
+            chunk.get_tile(z_order, index),
+            chunk_dimensions.decode_point(index),
+        ) {
+            (Some(tile), Ok(point)) if is_sensor(tile) => sensor_points.push(point),
+            _ => {}
+        }
+    }
+    let rects: Vec<_> = solid_rects
+        .into_iter()
+        .map(|rect| (rect, ColliderKind::Solid))
+        .chain(
+            one_way_rects
+                .into_iter()
+                .map(|rect| (rect, ColliderKind::OneWay)),
+        )
+        .chain(
+            sensor_points
+                .into_iter()
+                .map(|point| ((point, Dimension2::new(1, 1)), ColliderKind::Sensor)),
+        )
+        .collect();
+
+    let chunk_translation = tilemap.chunk_translation(GridTopology::Square, chunk_point);
+    let tile_width = tile_dimensions.width as f32;
+    let tile_height = tile_dimensions.height as f32;
+    let chunk_width = chunk_dimensions.width as i32;
+    let chunk_height = chunk_dimensions.height as i32;
     let mut collision_entities = Vec::new();
-    if let Some(indices) = chunk.get_tile_indices(z_order) {
-        for index in &indices {
-            let point = match chunk_dimensions.decode_point(*index) {
-                Ok(p) => p,
-                Err(e) => {
-                    error!("{}", e);
-                    continue;
-                }
-            };
-            // Adjust half a width and height back.
-            let mut x = (point.x - chunk_dimensions.width as i32 / 2) as f32;
-            let mut y = (point.y - chunk_dimensions.height as i32 / 2) as f32;
-            // Adjust by chunk position
-            x += chunk.point().x as f32
-                * chunk_dimensions.width as f32
-                * tile_dimensions.width as f32;
-            y += chunk.point().y as f32
-                * chunk_dimensions.height as f32
-                * tile_dimensions.height as f32;
-            // Add tilemap's translation
-            x += transform.translation.x;
-            y += transform.translation.y;
-
-            if chunk_dimensions.width % 2 == 0 {
-                x += 0.5;
-            }
-            if chunk_dimensions.height % 2 == 0 {
-                y += 0.5;
-            }
+    for ((point, dimensions), kind) in rects.into_iter() {
+        let width = dimensions.width as f32;
+        let height = dimensions.height as f32;
+        // The center of the rectangle, relative to the center of the chunk.
+        let x = (point.x as f32 + width / 2.0 - chunk_dimensions.width as f32 / 2.0) * tile_width;
+        let y =
+            (point.y as f32 + height / 2.0 - chunk_dimensions.height as f32 / 2.0) * tile_height;
+        let collider = TileCollider {
+            tilemap: tilemap_entity,
+            chunk_point,
+            z_order,
+            point: Point2::new(
+                point.x + chunk_width * chunk_point.x - chunk_width / 2,
+                point.y + chunk_height * chunk_point.y - chunk_height / 2,
+            ),
+            dimensions,
+            translation: transform
+                .mul_vec3(Vec3::new(
+                    x + chunk_translation.x,
+                    y + chunk_translation.y,
+                    0.0,
+                ))
+                .truncate(),
+            size: Vec2::new(
+                width * tile_width * transform.scale.x.abs(),
+                height * tile_height * transform.scale.y.abs(),
+            ),
+            rotation: geometry::z_rotation(transform),
+            kind,
+        };
 
-            let collision_groups = layers
-                .get(z_order)
-                .and_then(|layer_opt| layer_opt.and_then(|layer| Some(layer.interaction_groups)));
-            if let Some(collision_groups) = collision_groups {
-                if collision_groups.with_mask(0).0 != 0 {
-                    let mut collider = ColliderBuilder::cuboid(
-                        physics_tile_width / 2.0,
-                        physics_tile_height / 2.0,
-                    );
+        let collision_entity = if let Some(entity) =
+            backend.spawn_collider(commands, tilemap, layer, &collider)
+        {
+            entity
+        } else {
+            error!("Collider entity does not exist unexpectedly, can not run the tilemap system");
+            break;
+        };
 
-                    collider = collider.collision_groups(collision_groups);
-
-                    let entity = if let Some(entity) = commands
-                        .spawn((
-                            RigidBodyBuilder::new_static()
-                                .translation(x * physics_tile_width, y * physics_tile_height),
-                            collider,
-                        ))
-                        .current_entity()
-                    {
-                        entity
-                    } else {
-                        error!("Collider entity does not exist unexpectedly, can not run the tilemap system");
-                        return;
-                    };
+        commands.insert_one(collision_entity, collider);
+        collision_entities.push(collision_entity);
+        colliders.push(ChunkCollider {
+            entity: collision_entity,
+            point,
+            dimensions,
+        });
+    }
+    commands.push_children(entity, &collision_entities);
+    if let Some(chunk) = tilemap.chunk_mut(&chunk_point) {
+        chunk.set_colliders(z_order, colliders);
+    }
+    collision_entities.len()
+}
 
-                    collision_entities.push(entity);
+/// Drops the colliders of the chunks which left the collision radius of every
+/// tracker or are no longer active, and rebuilds the ones which came back.
+/// Static chunks keep their colliders outside of the collision radius.
+///
+/// Changes to chunks without colliders are ignored. Without a collision radius
+/// every spawned and active chunk has colliders.
+fn update_collision_chunks<B: TilemapPhysicsBackend>(
+    commands: &mut Commands,
+    backend: &B,
+    tilemap: &mut Tilemap,
+    transform: &GlobalTransform,
+    tracker_query: &Query<&GlobalTransform, With<CollisionTracker>>,
+    modified_layers: &mut HashMap<(Point2, usize), Option<HashSet<Point2>>>,
+) {
+    let spawned: HashSet<Point2> = tilemap
+        .spawned_chunks()
+        .iter()
+        .map(|point| point.into())
+        .collect();
+    let mut active = match tilemap.collision_radius() {
+        Some(radius) => {
+            let radius_width = radius.width as i32;
+            let radius_height = radius.height as i32;
+            let mut active = HashSet::default();
+            for tracker_transform in tracker_query.iter() {
+                let point =
+                    tilemap.global_translation_to_point(transform, tracker_transform.translation);
+                let (chunk_x, chunk_y) = tilemap.point_to_chunk_point(point);
+                for y in -radius_height..radius_height + 1 {
+                    for x in -radius_width..radius_width + 1 {
+                        let point = Point2::new(chunk_x + x, chunk_y + y);
+                        if spawned.contains(&point) {
+                            active.insert(point);
+                        }
+                    }
                 }
             }
+            active
+        }
+        None => spawned.clone(),
+    };
+    // Until now every spawned chunk had colliders.
+    let previous = tilemap
+        .collision_chunks_mut()
+        .take()
+        .unwrap_or_else(|| spawned.clone());
+    // Static chunks keep the colliders they were built with.
+    for point in previous.intersection(&spawned) {
+        if tilemap.static_chunks().contains(point) {
+            active.insert(*point);
         }
-        for (index, entity) in indices.iter().zip(&collision_entities) {
-            chunk.insert_collision_entity(*index, *entity);
+    }
+    active.retain(|point| tilemap.chunk_activity(*point) == Some(ChunkActivity::Active));
+    active.retain(|point| !tilemap.shed_colliders().contains_key(point));
+
+    let deterministic = tilemap.is_deterministic();
+    for point in in_order(previous.difference(&active), deterministic, |point| **point) {
+        let colliders = match tilemap.chunk_mut(point) {
+            Some(chunk) => chunk.take_colliders(),
+            None => continue,
+        };
+        for collider in colliders {
+            backend.despawn_collider(commands, collider.entity);
         }
-        commands.push_children(entity, &collision_entities);
+        info!("Chunk {} collisions dropped", point);
+    }
+    for point in active.difference(&previous) {
+        for z_order in 0..tilemap.layers().len() {
+            modified_layers.insert((*point, z_order), None);
+        }
+    }
+    modified_layers.retain(|(point, _), _| active.contains(point));
+    if tilemap.collision_radius().is_some() || active != spawned {
+        *tilemap.collision_chunks_mut() = Some(active);
     }
 }
 
 /// The event handling system for collisions. Namely spawning and despawning.
 ///
-/// Depending on if a collision needs to be created or not, given a variety of
-/// conditions, collisions are spawned or despawned accordingly.
-#[cfg(feature = "bevy_rapier2d")]
-pub(crate) fn tilemap_collision_events(
+/// The collisions of a chunk layer are spawned with the chunk. When its tiles
+/// are changed, only the collisions of that chunk layer which cover the
+/// changed tiles are spawned again. With a collision radius, only the chunks
+/// near a [`CollisionTracker`] have collisions, and chunks which are not
+/// active never do.
+pub(crate) fn tilemap_collision_events<B: TilemapPhysicsBackend>(
     commands: &mut Commands,
-    mut tilemap_query: Query<(&mut Tilemap, &Transform)>,
+    backend: Res<B>,
+    mut tilemap_query: Query<(Entity, &mut Tilemap, &GlobalTransform)>,
+    tracker_query: Query<&GlobalTransform, With<CollisionTracker>>,
+    mut metrics: ResMut<TilemapMetrics>,
 ) {
-    for (mut tilemap, transform) in tilemap_query.iter_mut() {
+    let start = Instant::now();
+    let mut spawned = 0;
+    for (tilemap_entity, mut tilemap, transform) in tilemap_query.iter_mut() {
         if tilemap.topology() != GridTopology::Square {
-            error!("collision physics are not supported on hex tiles yet");
+            error!("collision physics are only supported on square tiles");
             continue;
         }
-        tilemap.collision_events_update();
-        let mut spawned_chunks = Vec::new();
+        let layers = tilemap.layers();
+        // The changed points of each chunk layer, or `None` if all of them
+        // changed.
+        let mut modified_layers: HashMap<(Point2, usize), Option<HashSet<Point2>>> =
+            HashMap::default();
         let mut reader = tilemap.chunk_events().get_reader();
         for event in reader.iter(&tilemap.chunk_events()) {
-            use crate::TilemapChunkEvent::*;
             match event {
-                Spawned { ref point } => {
-                    spawned_chunks.push(*point);
+                TilemapChunkEvent::Spawned { point } => {
+                    for z_order in 0..layers.len() {
+                        modified_layers.insert((*point, z_order), None);
+                    }
                 }
-                _ => continue,
-            };
-        }
-
-        for point in spawned_chunks.into_iter() {
-            let layers = tilemap.layers();
-            let layers_len = tilemap.layers().len();
-            let chunk_dimensions = tilemap.chunk_dimensions();
-            let tile_dimensions = tilemap.tile_dimensions();
-            let physics_tile_width = tile_dimensions.width as f32 / tilemap.physics_scale();
-            let physics_tile_height = tile_dimensions.height as f32 / tilemap.physics_scale();
-            let chunk = if let Some(chunk) = tilemap.chunks_mut().get_mut(&point) {
-                chunk
-            } else {
-                warn!("Can not get chunk at {}, skipping", &point);
-                continue;
-            };
-            for z_order in 0..layers_len {
-                spawn_collisions(
-                    commands,
-                    &layers,
-                    point,
-                    z_order,
-                    chunk,
-                    chunk_dimensions,
-                    tile_dimensions,
-                    transform,
-                    physics_tile_width,
-                    physics_tile_height,
-                );
+                // The colliders are placed where their chunks were.
+                TilemapChunkEvent::Rehomed { .. } => {
+                    for point in tilemap.spawned_chunks().iter() {
+                        for z_order in 0..layers.len() {
+                            modified_layers.insert((point.into(), z_order), None);
+                        }
+                    }
+                }
+                _ => {}
             }
         }
 
-        let mut spawned_collisions = Vec::new();
-        let mut despawned_collisions = Vec::new();
         let mut reader = tilemap.collision_events().get_reader();
         for event in reader.iter(&tilemap.collision_events()) {
             use crate::event::TilemapCollisionEvent::*;
-            match event {
+            let (chunk_point, tiles) = match event {
                 Spawned {
                     ref chunk_point,
                     ref tiles,
-                } => {
-                    spawned_collisions.push((*chunk_point, tiles.clone()));
-                }
+                } => (chunk_point, tiles),
                 Despawned {
                     ref chunk_point,
                     ref tiles,
-                } => {
-                    despawned_collisions.push((*chunk_point, tiles.clone()));
-                }
+                } => (chunk_point, tiles),
             };
+            for tile in tiles.iter() {
+                let points = modified_layers
+                    .entry((*chunk_point, tile.z_order))
+                    .or_insert_with(|| Some(HashSet::default()));
+                if let Some(points) = points {
+                    points.insert(tile.point);
+                }
+            }
         }
 
-        for (chunk_point, tiles) in spawned_collisions.into_iter() {
-            let layers = tilemap.layers();
-            let chunk_dimensions = tilemap.chunk_dimensions();
-            let tile_dimensions = tilemap.tile_dimensions();
-            let physics_tile_width = tile_dimensions.width as f32 / tilemap.physics_scale();
-            let physics_tile_height = tile_dimensions.height as f32 / tilemap.physics_scale();
-            let chunk = if let Some(chunk) = tilemap.chunks_mut().get_mut(&chunk_point) {
-                chunk
-            } else {
-                warn!("Can not get chunk at {}, skipping", &chunk_point);
-                continue;
-            };
-            for tile in tiles {
-                spawn_collisions(
-                    commands,
-                    &layers,
-                    tile.point,
-                    tile.z_order,
-                    chunk,
-                    chunk_dimensions,
-                    tile_dimensions,
-                    transform,
-                    physics_tile_width,
-                    physics_tile_height,
-                );
+        update_collision_chunks(
+            commands,
+            &*backend,
+            &mut tilemap,
+            transform,
+            &tracker_query,
+            &mut modified_layers,
+        );
+
+        let modified_layers =
+            in_order(modified_layers, tilemap.is_deterministic(), |(key, _)| *key);
+        for ((chunk_point, z_order), changed_points) in modified_layers.into_iter() {
+            spawned += spawn_collisions(
+                commands,
+                &*backend,
+                tilemap_entity,
+                &mut tilemap,
+                transform,
+                &layers,
+                chunk_point,
+                z_order,
+                changed_points.as_ref(),
+            );
+            info!(
+                "Chunk {} on z order {} collisions spawned",
+                chunk_point, z_order
+            );
+        }
+    }
+    metrics.collision = FeatureMetrics::since(start, spawned);
+}
+
+/// The system which sends the trigger events of the sensor tiles.
+///
+/// An event is sent for every collider which started to overlap a sensor tile
+/// since the last frame. The overlaps are read from the narrow phase of rapier
+/// so that its proximity events are left for other systems.
+#[cfg(feature = "bevy_rapier2d")]
+pub(crate) fn tilemap_trigger_events(
+    narrow_phase: Res<NarrowPhase>,
+    collider_query: Query<(Entity, &ColliderHandleComponent)>,
+    mut sensor_query: Query<(&mut TileSensor, &ColliderHandleComponent)>,
+    mut tilemap_query: Query<&mut Tilemap>,
+) {
+    let mut collider_entities: Option<HashMap<ColliderHandle, Entity>> = None;
+    for (mut sensor, sensor_collider) in sensor_query.iter_mut() {
+        let sensor_handle = sensor_collider.handle();
+        let mut overlapping = HashSet::default();
+        if let Some(proximities) = narrow_phase.proximities_with(sensor_handle) {
+            for (handle1, handle2, pair) in proximities {
+                if pair.proximity != Proximity::Intersecting {
+                    continue;
+                }
+                if handle1 == sensor_handle {
+                    overlapping.insert(handle2);
+                } else {
+                    overlapping.insert(handle1);
+                }
             }
         }
 
-        for (chunk_point, tiles) in despawned_collisions.into_iter() {
-            let chunk_dimensions = tilemap.chunk_dimensions();
-            let chunk = if let Some(chunk) = tilemap.chunks_mut().get_mut(&chunk_point) {
-                chunk
+        for handle in overlapping.iter() {
+            if sensor.overlapping.contains(handle) {
+                continue;
+            }
+            let collider_entities = collider_entities.get_or_insert_with(|| {
+                collider_query
+                    .iter()
+                    .map(|(entity, collider)| (collider.handle(), entity))
+                    .collect()
+            });
+            let entity = if let Some(entity) = collider_entities.get(handle) {
+                *entity
             } else {
-                warn!("Can not get chunk at {}, skipping", &chunk_point);
                 continue;
             };
-            for tile in tiles {
-                let index = chunk_dimensions.encode_point_unchecked(tile.point);
-                let collision_entity = chunk.get_collision_entity(index);
-                if let Some(entity) = collision_entity {
-                    commands.despawn(entity);
-                    info!(
-                        "Tile {} on z order {} collision entity despawned",
-                        tile.point, tile.z_order
-                    );
-                }
+            if let Ok(mut tilemap) = tilemap_query.get_mut(sensor.tilemap) {
+                tilemap.send_trigger_event(TileTriggerEvent {
+                    point: sensor.point,
+                    z_order: sensor.z_order,
+                    entity,
+                });
             }
         }
+        sensor.overlapping = overlapping;
     }
 }
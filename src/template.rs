@@ -0,0 +1,92 @@
+//! Chunk templates which are inserted into a tilemap by handle.
+//!
+//! Dungeon generators assemble their levels from rooms which were authored
+//! by hand. A room is authored as a chunk of the tilemap, registered once as
+//! a template with [`Tilemap::add_chunk_template`], and then inserted at any
+//! number of chunk points with [`Tilemap::insert_chunk_from_template`]. The
+//! template keeps every tile of every layer, with their channels, transforms
+//! and heights, but none of the entities, meshes or colliders of the chunk it
+//! was taken from.
+//!
+//! Spawned copies of a template share its meshes until they are changed, so
+//! maps that repeat the same filler chunks, such as oceans, build one mesh
+//! for each layer of the filler, see [`Tilemap::chunk_instance`].
+//!
+//! Templates are kept with the layers of the tilemap, so layers which are
+//! added, moved or removed later are added, moved or removed in the
+//! templates too.
+//!
+//! [`Tilemap::add_chunk_template`]: crate::tilemap::Tilemap::add_chunk_template
+//! [`Tilemap::insert_chunk_from_template`]: crate::tilemap::Tilemap::insert_chunk_from_template
+//! [`Tilemap::chunk_instance`]: crate::tilemap::Tilemap::chunk_instance
+//!
+//! # Assembling rooms
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::prelude::*;
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(8, 8)
+//!     .tile_dimensions(16, 16)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // A room with walls around its floor, authored in the chunk at the origin.
+//! let mut tiles = Vec::new();
+//! for x in -4..4 {
+//!     for y in -4..4 {
+//!         let wall = x == -4 || x == 3 || y == -4 || y == 3;
+//!         let sprite_index = if wall { 2 } else { 1 };
+//!         tiles.push(Tile { point: (x, y), sprite_index, ..Default::default() });
+//!     }
+//! }
+//! tilemap.insert_tiles(tiles).unwrap();
+//! let room = tilemap.add_chunk_template((0, 0)).unwrap();
+//! tilemap.remove_chunk((0, 0)).unwrap();
+//!
+//! // A corridor of rooms.
+//! for x in 0..3 {
+//!     tilemap.insert_chunk_from_template((x, 0), room).unwrap();
+//! }
+//!
+//! assert_eq!(tilemap.get_tile((12, 3), 0).unwrap().index, 2);
+//! assert_eq!(tilemap.get_tile((17, 1), 0).unwrap().index, 1);
+//! assert!(tilemap.get_tile((1, 9), 0).is_none());
+//! ```
+
+use crate::lib::*;
+
+/// The handle of a chunk template of a tilemap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ChunkTemplateHandle(pub(crate) usize);
+
+impl ChunkTemplateHandle {
+    /// Returns the index of the template in the templates of its tilemap,
+    /// from the first template that was added.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// assert_eq!(tilemap.add_chunk_template((0, 0)).unwrap().index(), 0);
+    /// assert_eq!(tilemap.add_chunk_template((0, 0)).unwrap().index(), 1);
+    /// ```
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
@@ -0,0 +1,246 @@
+//! A headless app for integration tests of tilemaps.
+//!
+//! [`tilemap_test_harness`] builds an app with the [`Tilemap2DPlugin`] and
+//! only the resources it needs, so that it runs without a window or a
+//! renderer, with a texture atlas which is kept in memory if the `render`
+//! feature is on. Frames are run one at a time, after which the tilemaps and
+//! their chunks can be read back.
+//! [`tilemap_test_harness_with`] also adds another plugin, such as one of the
+//! plugins of the crate which are not part of the main plugin.
+//!
+//! This module is only built with the `testing` feature.
+//!
+//! [`Tilemap2DPlugin`]: crate::Tilemap2DPlugin
+//!
+//! # Testing a tilemap
+//! ```
+//! use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 3, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//!
+//! harness.tick();
+//!
+//! assert_eq!(harness.spawned_chunks(entity), vec![(0, 0).into()]);
+//! assert_eq!(harness.chunk_entities(entity, (0, 0)).len(), 1);
+//!
+//! let indexes = harness.chunk_tile_indexes(entity, (0, 0), 0).unwrap();
+//! assert_eq!(indexes[2 * 4 + 2], 3);
+//! ```
+
+#[cfg(feature = "render")]
+use crate::chunk::mesh::ChunkMesh;
+use crate::{entity::TilemapBundle, lib::*, Tilemap, Tilemap2DPlugin};
+
+/// The dimensions in pixels of the sprites of the texture atlas of a harness.
+pub const HARNESS_SPRITE_DIMENSIONS: u32 = 32;
+
+/// The number of columns and rows of sprites in the texture atlas of a harness.
+pub const HARNESS_ATLAS_DIMENSIONS: u32 = 4;
+
+/// A headless app with the tilemap plugin, see the [module](self).
+pub struct TilemapTestHarness {
+    /// The app which is updated every tick.
+    app: App,
+    /// The handle of the texture atlas which is kept in memory.
+    #[cfg(feature = "render")]
+    texture_atlas: Handle<TextureAtlas>,
+}
+
+/// Builds a headless app with the tilemap plugin and a texture atlas of white
+/// sprites.
+pub fn tilemap_test_harness() -> TilemapTestHarness {
+    finish_harness(harness_builder())
+}
+
+/// Builds a headless app with the tilemap plugin and another plugin, and a
+/// texture atlas of white sprites.
+///
+/// # Examples
+/// ```
+/// use bevy_tilemap::{debug::TilemapDebugPlugin, testing::tilemap_test_harness_with};
+///
+/// let mut harness = tilemap_test_harness_with(TilemapDebugPlugin);
+/// harness.tick();
+/// ```
+pub fn tilemap_test_harness_with<P: Plugin>(plugin: P) -> TilemapTestHarness {
+    let mut builder = harness_builder();
+    builder.add_plugin(plugin);
+    finish_harness(builder)
+}
+
+/// Builds a headless app with the tilemap plugin and the resources it needs.
+fn harness_builder() -> AppBuilder {
+    let mut builder = App::build();
+    builder
+        .add_plugin(ReflectPlugin)
+        .add_plugin(CorePlugin)
+        .add_plugin(TransformPlugin)
+        .add_plugin(AssetPlugin)
+        .add_event::<WindowResized>();
+    #[cfg(feature = "render")]
+    builder
+        .add_asset::<Mesh>()
+        .add_asset::<Shader>()
+        .add_asset::<PipelineDescriptor>()
+        .add_asset::<Texture>()
+        .add_asset::<TextureAtlas>()
+        .add_resource(RenderGraph::default());
+    builder.add_plugin(Tilemap2DPlugin);
+    builder
+}
+
+/// Adds the texture atlas of white sprites to an app, and builds the harness.
+fn finish_harness(builder: AppBuilder) -> TilemapTestHarness {
+    #[cfg(feature = "render")]
+    let texture_atlas = {
+        let resources = builder.resources();
+        let mut textures = resources
+            .get_mut::<Assets<Texture>>()
+            .expect("`Texture` is missing.");
+        let mut texture_atlases = resources
+            .get_mut::<Assets<TextureAtlas>>()
+            .expect("`TextureAtlas` is missing.");
+        let size = HARNESS_SPRITE_DIMENSIONS * HARNESS_ATLAS_DIMENSIONS;
+        let texture = textures.add(Texture::new_fill(
+            Extent3d::new(size, size, 1),
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        ));
+        texture_atlases.add(TextureAtlas::from_grid(
+            texture,
+            Vec2::new(
+                HARNESS_SPRITE_DIMENSIONS as f32,
+                HARNESS_SPRITE_DIMENSIONS as f32,
+            ),
+            HARNESS_ATLAS_DIMENSIONS as usize,
+            HARNESS_ATLAS_DIMENSIONS as usize,
+        ))
+    };
+
+    TilemapTestHarness {
+        app: builder.app,
+        #[cfg(feature = "render")]
+        texture_atlas,
+    }
+}
+
+impl TilemapTestHarness {
+    /// Returns the handle of the texture atlas, for the tilemaps of the
+    /// harness.
+    #[cfg(feature = "render")]
+    pub fn texture_atlas(&self) -> &Handle<TextureAtlas> {
+        &self.texture_atlas
+    }
+
+    /// Returns the app, to add systems or to read the world and resources.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Spawns a tilemap entity at the origin, returning it.
+    pub fn spawn_tilemap(&mut self, tilemap: Tilemap) -> Entity {
+        self.app.world.spawn(TilemapBundle {
+            tilemap,
+            transform: Default::default(),
+            global_transform: Default::default(),
+        })
+    }
+
+    /// Runs a single frame of the app.
+    pub fn tick(&mut self) {
+        self.app.update();
+    }
+
+    /// Runs a number of frames of the app.
+    pub fn tick_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.tick();
+        }
+    }
+
+    /// Returns the tilemap of an entity.
+    pub fn tilemap(&self, entity: Entity) -> Option<&Tilemap> {
+        self.app.world.get::<Tilemap>(entity).ok()
+    }
+
+    /// Returns the tilemap of an entity to change it between ticks.
+    pub fn tilemap_mut(&mut self, entity: Entity) -> Option<Mut<'_, Tilemap>> {
+        self.app.world.get_mut::<Tilemap>(entity).ok()
+    }
+
+    /// Returns the points of the spawned chunks of a tilemap, in order.
+    pub fn spawned_chunks(&self, entity: Entity) -> Vec<Point2> {
+        let mut points: Vec<Point2> = match self.tilemap(entity) {
+            Some(tilemap) => tilemap
+                .spawned_chunks()
+                .iter()
+                .map(|point| point.into())
+                .collect(),
+            None => Vec::new(),
+        };
+        points.sort();
+        points
+    }
+
+    /// Returns the entities of the spawned layers of a chunk of a tilemap.
+    pub fn chunk_entities<P: Into<Point2>>(&self, entity: Entity, point: P) -> Vec<Entity> {
+        self.tilemap(entity)
+            .and_then(|tilemap| tilemap.get_chunk(&point.into()))
+            .map(|chunk| chunk.get_entities())
+            .unwrap_or_default()
+    }
+
+    /// Returns the sprite index of every tile of a chunk layer as it is in
+    /// its mesh, or `None` if the layer has no mesh.
+    #[cfg(feature = "render")]
+    pub fn chunk_tile_indexes<P: Into<Point2>>(
+        &self,
+        entity: Entity,
+        point: P,
+        z_order: usize,
+    ) -> Option<Vec<usize>> {
+        let chunk = self.tilemap(entity)?.get_chunk(&point.into())?;
+        let meshes = self.app.resources.get::<Assets<Mesh>>()?;
+        let mesh = meshes.get(chunk.get_mesh(z_order)?)?;
+        match mesh.attribute(ChunkMesh::ATTRIBUTE_TILE_INDEX)? {
+            VertexAttributeValues::Uint(indexes) => Some(
+                indexes
+                    .iter()
+                    .step_by(4)
+                    .map(|index| *index as usize)
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns how many quads of a chunk layer are drawn by its mesh, or
+    /// `None` if the layer has no mesh.
+    #[cfg(feature = "render")]
+    pub fn chunk_quads<P: Into<Point2>>(
+        &self,
+        entity: Entity,
+        point: P,
+        z_order: usize,
+    ) -> Option<usize> {
+        let chunk = self.tilemap(entity)?.get_chunk(&point.into())?;
+        let meshes = self.app.resources.get::<Assets<Mesh>>()?;
+        let mesh = meshes.get(chunk.get_mesh(z_order)?)?;
+        match mesh.indices()? {
+            Indices::U16(indices) => Some(indices.len() / 6),
+            Indices::U32(indices) => Some(indices.len() / 6),
+        }
+    }
+}
@@ -14,6 +14,17 @@ pub struct Tile<P: Into<Point2>> {
     pub sprite_index: usize,
     /// The desired tint and alpha of the tile. White means no change.
     pub tint: Color,
+    /// Reserved data for custom shaders, see [`RawTile::extra`].
+    ///
+    /// [`RawTile::extra`]: crate::chunk::RawTile::extra
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra: u16,
+    /// The offset of the tile from its point in pixels, see
+    /// [`RawTile::offset`].
+    ///
+    /// [`RawTile::offset`]: crate::chunk::RawTile::offset
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offset: Vec2,
 }
 
 impl<P: Into<Point2> + Default> Default for Tile<P> {
@@ -23,6 +34,96 @@ impl<P: Into<Point2> + Default> Default for Tile<P> {
             z_order: 0,
             sprite_index: 0,
             tint: Color::WHITE,
+            extra: 0,
+            offset: Vec2::zero(),
         }
     }
 }
+
+/// The rotation and scale of a tile of a decoration layer, around its middle.
+///
+/// The corners of the tile are moved in its chunk mesh as though the sprite
+/// is as big as the tile dimensions.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TileTransform {
+    /// The rotation of the tile counterclockwise, in radians.
+    pub rotation: f32,
+    /// The scale of the tile along its width and height.
+    pub scale: Vec2,
+}
+
+impl Default for TileTransform {
+    fn default() -> TileTransform {
+        TileTransform {
+            rotation: 0.0,
+            scale: Vec2::one(),
+        }
+    }
+}
+
+impl TileTransform {
+    /// Constructs a new transform with a rotation, in radians, and a scale.
+    pub fn new(rotation: f32, scale: Vec2) -> TileTransform {
+        TileTransform { rotation, scale }
+    }
+
+    /// Returns how far each corner of a tile of the dimensions is moved, in
+    /// the order of the vertices of its quad: bottom left, top left, top
+    /// right and bottom right.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_math::Vec2;
+    /// use bevy_tilemap::tile::TileTransform;
+    ///
+    /// // Twice as big, so every corner moves out by half of the tile.
+    /// let transform = TileTransform::new(0.0, Vec2::new(2.0, 2.0));
+    /// let offsets = transform.corner_offsets(Vec2::new(32.0, 16.0));
+    ///
+    /// assert_eq!(offsets[0], Vec2::new(-16.0, -8.0));
+    /// assert_eq!(offsets[2], Vec2::new(16.0, 8.0));
+    /// ```
+    pub fn corner_offsets(&self, dimensions: Vec2) -> [Vec2; 4] {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half = dimensions / 2.0;
+        let corners = [
+            Vec2::new(-half.x, -half.y),
+            Vec2::new(-half.x, half.y),
+            Vec2::new(half.x, half.y),
+            Vec2::new(half.x, -half.y),
+        ];
+        let mut offsets = [Vec2::zero(); 4];
+        for (offset, corner) in offsets.iter_mut().zip(corners.iter()) {
+            let scaled = *corner * self.scale;
+            let rotated = Vec2::new(
+                scaled.x * cos - scaled.y * sin,
+                scaled.x * sin + scaled.y * cos,
+            );
+            *offset = rotated - *corner;
+        }
+        offsets
+    }
+}
+
+/// The hit points of the tiles of a sprite index.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct SpriteHitPoints {
+    /// The damage a tile takes before it is destroyed.
+    pub(crate) hit_points: u32,
+    /// The sprite index that replaces a destroyed tile, or `None` if it is
+    /// removed.
+    pub(crate) rubble: Option<usize>,
+}
+
+/// The damage that a tile took.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct TileDamage {
+    /// The sprite index the tile had when it took the damage, so that a tile
+    /// which was replaced starts with all of its hit points.
+    pub(crate) sprite_index: usize,
+    /// The damage taken.
+    pub(crate) damage: u32,
+}
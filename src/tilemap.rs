@@ -93,16 +93,54 @@
 //! }
 //! ```
 
-#[cfg(feature = "bevy_rapier2d")]
-use crate::event::TilemapCollisionEvent;
+use crate::event::{TileDestroyedEvent, TileTriggerEvent, TilemapCollisionEvent};
+#[cfg(feature = "procgen")]
+use crate::procgen::ChunkGenerator;
 use crate::{
-    chunk::{Chunk, LayerKind, RawTile},
-    event::TilemapChunkEvent,
+    animation::{AnimationClip, Fade, ShaderAnimation, TileAfter, TileAnimation},
+    channel::{ChannelKind, ChannelValue, TileChannel},
+    chunk::{
+        ActivityPolicy, Chunk, ChunkActivity, ChunkSpawnConfig, LayerKind, RawTile, ShedPolicy,
+        SpawnBudget, StoragePolicy,
+    },
+    decal::{Decal, DecalPolicy, DECAL_ROTATION_CHANNEL},
+    delta::TilemapDelta,
+    event::{ChunkDespawnedEvent, TileEntityEvent, TilemapChunkEvent, TilemapShedEvent},
+    geometry, hex,
+    highlight::HighlightLayer,
+    iso,
     lib::*,
+    metrics::{CompactReport, TilemapStats},
+    navmesh::NavMesh,
     prelude::GridTopology,
-    tile::Tile,
+    region::{RegionTile, TileRegion},
+    rule::TileRules,
+    snapshot::TilemapSnapshot,
+    sprite::SpriteRect,
+    template::ChunkTemplateHandle,
+    tile::{SpriteHitPoints, Tile, TileDamage, TileTransform},
+    variant::VariantGroup,
+    wall::WallSprite,
+    wfc::WfcRules,
+    world::WorldPoint,
+};
+#[cfg(feature = "serialize")]
+use crate::{
+    chunk::codec,
+    store::{ChunkEncoding, ChunkStore},
+};
+#[cfg(feature = "render")]
+use crate::{
+    chunk::{mesh::ChunkMesh, TileOcclusion},
+    instance::TilemapData,
+    palette::ChunkPalette,
+    render_layers::RenderLayers,
 };
 
+/// The value of the points without a tile in a grid of sprite indexes, see
+/// [`Tilemap::from_grid`].
+pub const EMPTY_GRID_TILE: u32 = u32::MAX;
+
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 /// The kinds of errors that can occur.
 pub enum ErrorKind {
@@ -120,6 +158,66 @@ pub enum ErrorKind {
     MissingChunk,
     /// The chunk already exists.
     ChunkAlreadyExists(Point2),
+    /// The operation is not supported for the topology.
+    UnsupportedTopology(GridTopology),
+    /// The chunk width and height are not the same.
+    NonSquareChunks,
+    /// The metadata of a texture atlas could not be read.
+    InvalidAtlasMetadata(String),
+    /// The animation clip does not exist.
+    MissingAnimationClip(String),
+    /// The layer does not have the channel.
+    MissingChannel(String),
+    /// The value is of another kind than the channel.
+    MismatchedChannel(String),
+    /// A chunk could not be written to or read from disk.
+    ChunkStorage(String),
+    /// The tilemap could not be exported to an image.
+    ImageExport(String),
+    /// The tilemap has no decal layers.
+    MissingDecalLayers,
+    /// The tilemap has no highlight layer.
+    MissingHighlightLayer,
+    /// The chunk template does not exist.
+    MissingChunkTemplate,
+    /// The tilemap does not keep the entities of its tiles.
+    TileEntitiesDisabled,
+    /// The layer is one of the decal layers, which are only moved together.
+    DecalLayer(usize),
+    /// The layer is not a dense or a sparse layer, or the kind is not dense
+    /// or sparse, so the kind of the layer in a chunk can not be overridden.
+    LayerKindNotOverridable(usize),
+    /// The comma separated values could not be read.
+    InvalidCsv(String),
+    /// The tiles of a chunk or layer are static and can not be changed.
+    StaticTiles(Point2, usize),
+    /// The operation is not supported for tilemaps with dimensions.
+    FixedDimensions,
+    /// The map file could not be read.
+    InvalidMapFile(String),
+    /// The save could not be read or written.
+    InvalidSave(String),
+    /// The save was written with a newer version of the save format.
+    UnsupportedSaveVersion(u32),
+    /// The grid does not have as many sprite indexes as its dimensions need,
+    /// which are the indexes it has and then the indexes it needs.
+    MismatchedGrid(usize, usize),
+    /// The world point is too far from the origin of the tilemap to be one
+    /// of its points.
+    DistantWorldPoint(WorldPoint),
+    /// The variant group does not exist.
+    MissingVariantGroup(String),
+    /// The variant group has no variants with a weight.
+    EmptyVariantGroup(String),
+    /// The layer has no rules.
+    MissingTileRules(usize),
+    /// Wave function collapse could not fit tiles into the region from the
+    /// point.
+    WfcContradiction(Point2),
+    /// The layer is not a decoration layer.
+    NotDecorationLayer(usize),
+    /// There is no tile at the point of the layer.
+    MissingTile(Point2, usize),
 }
 
 impl Display for ErrorKind {
@@ -146,6 +244,113 @@ impl Display for ErrorKind {
                 "the chunk {} already exists, if this was intentional run `remove_chunk` first",
                 p
             ),
+            UnsupportedTopology(topology) => {
+                write!(
+                    f,
+                    "the operation is not supported for {} tilemaps",
+                    topology
+                )
+            }
+            NonSquareChunks => write!(f, "the chunk width and height must be the same"),
+            InvalidAtlasMetadata(reason) => write!(f, "invalid atlas metadata: {}", reason),
+            MissingAnimationClip(name) => write!(
+                f,
+                "the animation clip \"{}\" does not exist, try `add_animation_clip` first",
+                name
+            ),
+            MissingChannel(name) => write!(
+                f,
+                "the layer has no channel \"{}\", try `add_channel` first",
+                name
+            ),
+            MismatchedChannel(name) => write!(
+                f,
+                "the value is of another kind than the channel \"{}\"",
+                name
+            ),
+            ChunkStorage(reason) => write!(f, "the chunk could not be stored: {}", reason),
+            ImageExport(reason) => write!(f, "the image could not be exported: {}", reason),
+            MissingDecalLayers => write!(
+                f,
+                "the tilemap has no decal layers, try `add_decal_layers` first"
+            ),
+            MissingHighlightLayer => write!(
+                f,
+                "the tilemap has no highlight layer, try `add_highlight_layer` first"
+            ),
+            MissingChunkTemplate => write!(
+                f,
+                "the chunk template does not exist, try `add_chunk_template` first"
+            ),
+            TileEntitiesDisabled => write!(
+                f,
+                "the tilemap does not keep tile entities, try `set_tile_entities` first"
+            ),
+            DecalLayer(z) => write!(
+                f,
+                "layer {} is a decal layer, which can not be moved on its own",
+                z
+            ),
+            LayerKindNotOverridable(z) => write!(
+                f,
+                "only dense and sparse layers can be overridden with dense or sparse, not layer {}",
+                z
+            ),
+            InvalidCsv(reason) => write!(f, "invalid comma separated values: {}", reason),
+            StaticTiles(point, z_order) => write!(
+                f,
+                "the tiles of layer {} in chunk {} are static, try `set_chunk_static` first",
+                z_order, point
+            ),
+            FixedDimensions => write!(
+                f,
+                "the operation is not supported for tilemaps with dimensions"
+            ),
+            InvalidMapFile(reason) => write!(f, "invalid map file: {}", reason),
+            InvalidSave(reason) => write!(f, "invalid save: {}", reason),
+            UnsupportedSaveVersion(version) => write!(
+                f,
+                "the save has version {} of the save format, which is newer than this version",
+                version
+            ),
+            MismatchedGrid(found, expected) => write!(
+                f,
+                "the grid has {} sprite indexes but its dimensions need {}",
+                found, expected
+            ),
+            DistantWorldPoint(point) => write!(
+                f,
+                "the world point {} is too far from the origin of the tilemap",
+                point
+            ),
+            MissingVariantGroup(name) => write!(
+                f,
+                "the variant group \"{}\" does not exist, try `add_variant_group` first",
+                name
+            ),
+            EmptyVariantGroup(name) => write!(
+                f,
+                "the variant group \"{}\" has no variants with a weight",
+                name
+            ),
+            MissingTileRules(z_order) => write!(
+                f,
+                "the layer {} has no rules, try `add_tile_rules` first",
+                z_order
+            ),
+            WfcContradiction(point) => write!(
+                f,
+                "no tiles of the rules fit together in the region from {}",
+                point
+            ),
+            NotDecorationLayer(z_order) => write!(
+                f,
+                "the layer {} is not a decoration layer, try `LayerKind::Decoration`",
+                z_order
+            ),
+            MissingTile(point, z_order) => {
+                write!(f, "there is no tile at {} of layer {}", point, z_order)
+            }
         }
     }
 }
@@ -185,11 +390,12 @@ pub type TilemapResult<T> = Result<T, TilemapError>;
 
 bitflags! {
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-    struct AutoFlags: u16 {
+    pub(crate) struct AutoFlags: u16 {
         const NONE = 0b0;
         const AUTO_CONFIGURE = 0b0000_0000_0000_0001;
         const AUTO_CHUNK = 0b0000_0000_0000_0010;
         const AUTO_SPAWN = 0b0000_0000_0000_0100;
+        const TILE_ENTITIES = 0b0000_0000_0000_1000;
     }
 }
 
@@ -206,32 +412,273 @@ impl Default for AutoFlags {
     }
 }
 
+/// The tiles that count as the neighbours of a tile.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Neighborhood {
+    /// The 4 tiles which share an edge with a square tile.
+    Four,
+    /// The 8 tiles which share an edge or a corner with a square tile.
+    Eight,
+    /// The 6 tiles around a hex tile, in the coordinates of the topology of
+    /// the tilemap, see [`hex`].
+    ///
+    /// [`hex`]: crate::hex
+    Hex,
+}
+
+/// The offsets to the 4 neighbours of a square tile, counter clockwise
+/// starting from the positive x axis.
+const FOUR_NEIGHBORS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// The offsets to the 8 neighbours of a square tile, counter clockwise
+/// starting from the positive x axis.
+const EIGHT_NEIGHBORS: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// The opacity of tilemaps which were saved without one.
+#[cfg(feature = "serde")]
+fn opaque() -> f32 {
+    1.0
+}
+
+/// Returns the point of the chunk of chunk dimensions which has a tile.
+pub(crate) fn chunk_point_of(chunk_dimensions: Dimension2, point: Point2) -> Point2 {
+    let width = chunk_dimensions.width as f32;
+    let height = chunk_dimensions.height as f32;
+    let x = ((point.x as f32 + width / 2.0) / width).floor() as i32;
+    let y = ((point.y as f32 + height / 2.0) / height).floor() as i32;
+    Point2::new(x, y)
+}
+
+/// Returns the point of a tile from its point in the chunk of chunk
+/// dimensions at the chunk point.
+fn tile_point_to_point(
+    chunk_dimensions: Dimension2,
+    chunk_point: Point2,
+    tile_point: Point2,
+) -> Point2 {
+    let width = chunk_dimensions.width as i32;
+    let height = chunk_dimensions.height as i32;
+    Point2::new(
+        tile_point.x + (width * chunk_point.x) - (width / 2),
+        tile_point.y + (height * chunk_point.y) - (height / 2),
+    )
+}
+
+/// Collects the items, sorted by their keys if the tilemap is deterministic
+/// so that they are in the same order on every run and platform.
+pub(crate) fn in_order<T, K, I, F>(items: I, deterministic: bool, key: F) -> Vec<T>
+where
+    K: Ord,
+    I: IntoIterator<Item = T>,
+    F: FnMut(&T) -> K,
+{
+    let mut items: Vec<T> = items.into_iter().collect();
+    if deterministic {
+        items.sort_unstable_by_key(key);
+    }
+    items
+}
+
+/// Returns the z order which a z order is at once the layers at two z orders
+/// are swapped.
+fn swapped_z_order(z_order: usize, a: usize, b: usize) -> usize {
+    if z_order == a {
+        b
+    } else if z_order == b {
+        a
+    } else {
+        z_order
+    }
+}
+
+/// Swaps the values of a map by z order at two z orders.
+fn swap_z_orders<V>(map: &mut HashMap<usize, V>, a: usize, b: usize) {
+    let value_a = map.remove(&a);
+    let value_b = map.remove(&b);
+    if let Some(value) = value_a {
+        map.insert(b, value);
+    }
+    if let Some(value) = value_b {
+        map.insert(a, value);
+    }
+}
+
+/// Swaps the values of a map by point and z order at two z orders.
+fn swap_tile_z_orders<V>(map: &mut HashMap<(Point2, usize), V>, a: usize, b: usize) {
+    *map = map
+        .drain()
+        .map(|((point, z_order), value)| ((point, swapped_z_order(z_order, a, b)), value))
+        .collect();
+}
+
 /// A layer configuration for a tilemap.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct TilemapLayer {
     /// The kind of layer to create.
     pub kind: LayerKind,
-    /// The interaction group and its mask.
+    /// If the layer is also drawn in the mask pass, see [`mask`].
+    ///
+    /// [`mask`]: crate::mask
+    pub mask: bool,
+    /// If the layer is also drawn in the picking pass, see [`picking`].
+    ///
+    /// [`picking`]: crate::picking
+    pub picking: bool,
+    /// The interaction group and its mask, which decide what the colliders of
+    /// the layer collide with. The layer has no colliders if it is in no
+    /// group.
     #[cfg_attr(feature = "serde", serde(skip))]
     #[cfg(feature = "bevy_rapier2d")]
     pub interaction_groups: InteractionGroups,
+    /// The solver group and its mask, which decide what the colliders of the
+    /// layer push back against. Bodies that collide with the layer but are
+    /// not in these groups still send contact events.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg(feature = "bevy_rapier2d")]
+    pub solver_groups: InteractionGroups,
+    /// If the tiles of the layer are one-way platforms, which bodies can pass
+    /// through from below. The rapier backend implements these with the
+    /// `platform` module.
+    pub one_way: bool,
+    /// If the tiles of the layer are sensors which send trigger events, see
+    /// [`trigger_events`].
+    ///
+    /// [`trigger_events`]: Tilemap::trigger_events
+    pub sensor: bool,
+    /// If the tiles of the layer are sorted by their rows on a square
+    /// tilemap, so the tiles below are drawn in front of those above them,
+    /// such as for sprites which are taller than a tile. Layers of
+    /// [`LayerKind::YSorted`] are always sorted. See [`iso`].
+    ///
+    /// [`iso`]: crate::iso
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub y_sort: bool,
+    /// If the tiles of the layer whose sprites are walls are extruded on a
+    /// square tilemap, see [`wall`]. The layer is sorted by its rows.
+    ///
+    /// [`wall`]: crate::wall
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub walls: bool,
+    /// The color which every tile of the layer is multiplied by when it is
+    /// drawn, on top of the color of the tilemap and of the tile itself.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color: Color,
+    /// The animation which the shader plays on every tile of the layer, see
+    /// [`animation`].
+    ///
+    /// [`animation`]: crate::animation
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shader_animation: Option<ShaderAnimation>,
+    /// The tile which the empty tiles of a dense layer are filled with, and
+    /// which its tiles are set back to when they are cleared. Without one
+    /// they are [`RawTile::EMPTY`] and there is no tile.
+    ///
+    /// This is for layers such as the water of an ocean map which are never
+    /// without a tile. The empty tile is a tile like any other, so it is
+    /// drawn and returned by [`get_tile`]. Chunks where the layer is stored
+    /// sparsely, see [`set_chunk_layer_kind`], are not filled.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::RawTile, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// // Sprite 0 is sand, and the ocean is sprite 5.
+    /// let ocean = RawTile { index: 5, ..Default::default() };
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { empty_tile: Some(ocean), ..Default::default() }, 0)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 0, ..Default::default() }).unwrap();
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 0);
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 5);
+    ///
+    /// // The island sinks.
+    /// tilemap.clear_tile((0, 0), 0).unwrap();
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 5);
+    /// ```
+    ///
+    /// [`get_tile`]: Tilemap::get_tile
+    /// [`set_chunk_layer_kind`]: Tilemap::set_chunk_layer_kind
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub empty_tile: Option<RawTile>,
+    /// The z translation of the chunk entities of the layer, which decides
+    /// the order it is drawn in among the layers of this and other tilemaps
+    /// and the sprites around them. Layers without one are drawn at their z
+    /// order, see [`Tilemap::set_layer_depth`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depth: Option<f32>,
+    /// If the layer is not drawn. Its tiles still collide, are ruled and are
+    /// kept up to date, see [`Tilemap::set_layer_visible`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hidden: bool,
+    /// The render layers of the layer, which decide the cameras that draw
+    /// it. Layers without them are in the render layers of the tilemap, see
+    /// [`render_layers`].
+    ///
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub render_layers: Option<RenderLayers>,
 }
 
 impl Default for TilemapLayer {
     fn default() -> TilemapLayer {
         TilemapLayer {
             kind: LayerKind::Dense,
+            mask: false,
+            picking: false,
             #[cfg(feature = "bevy_rapier2d")]
             interaction_groups: InteractionGroups::none(),
+            #[cfg(feature = "bevy_rapier2d")]
+            solver_groups: InteractionGroups::all(),
+            one_way: false,
+            sensor: false,
+            y_sort: false,
+            walls: false,
+            color: Color::WHITE,
+            shader_animation: None,
+            empty_tile: None,
+            depth: None,
+            hidden: false,
+            #[cfg(feature = "render")]
+            render_layers: None,
         }
     }
 }
 
+impl TilemapLayer {
+    /// Returns `true` if the tiles of the layer are sorted by their rows.
+    #[cfg(feature = "render")]
+    pub(crate) fn is_y_sorted(&self) -> bool {
+        self.y_sort || self.walls || self.kind == LayerKind::YSorted
+    }
+}
+
 /// A Tilemap which maintains chunks and its tiles within.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
 pub struct Tilemap {
+    /// An optional human-readable name used for debugging and diagnostics.
+    name: Option<String>,
     /// The type of grid to use.
     topology: GridTopology,
     /// An optional field which can contain the tilemaps dimensions in chunks.
@@ -245,31 +692,243 @@ pub struct Tilemap {
     layers: Vec<Option<TilemapLayer>>,
     /// Auto flags used for different automated features.
     auto_flags: AutoFlags,
-    /// Dimensions of chunks to spawn from camera transform.
-    auto_spawn: Option<Dimension2>,
+    /// How chunks are spawned and despawned around the cameras.
+    spawn_config: Option<ChunkSpawnConfig>,
+    /// The radius of chunks around collision trackers which get colliders.
+    collision_radius: Option<Dimension2>,
+    /// The activity of chunks by their distance to the cameras.
+    activity_policy: Option<ActivityPolicy>,
+    /// How many chunks are spawned in a frame.
+    spawn_budget: Option<SpawnBudget>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks waiting to be spawned, if there is a spawn budget.
+    spawn_queue: HashSet<Point2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks of the cameras or streaming anchors, from which the queued
+    /// chunks are spawned.
+    spawn_focus: Vec<Point2>,
+    /// The thresholds past which load is shed.
+    shed_policy: Option<ShedPolicy>,
+    /// The fill ratios at which sparse layers are stored dense and back.
+    #[cfg_attr(feature = "serde", serde(default))]
+    storage_policy: Option<StoragePolicy>,
+    /// The kinds of the layers of chunks which are not those of the layers,
+    /// at their chunk points and z orders.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::pairs"))]
+    layer_kind_overrides: HashMap<(Point2, usize), LayerKind>,
+    /// If chunks and tiles are updated in the order of their points.
+    #[cfg_attr(feature = "serde", serde(default))]
+    deterministic: bool,
+    /// The world point of the chunk at `(0, 0)`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    origin: WorldPoint,
+    /// The chunk which is translated to where the chunk at `(0, 0)` is.
+    #[cfg_attr(feature = "serde", serde(default))]
+    translation_origin: Point2,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks whose colliders were shed, with how many they had.
+    shed_colliders: HashMap<Point2, usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks which were despawned to shed their meshes.
+    shed_meshes: HashSet<Point2>,
+    /// The points of the chunks which were written to the chunk store.
+    #[cfg(feature = "serialize")]
+    unloaded_chunks: HashSet<Point2>,
+    /// The points in the chunk store of the unloaded chunks which were
+    /// written before the origin moved, at their points.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::pairs"))]
+    #[cfg(feature = "serialize")]
+    store_points: HashMap<Point2, Point2>,
+    /// The z orders which the layers of the unloaded chunks were written at
+    /// before layers were swapped, by their z orders now, at their points.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::pairs"))]
+    #[cfg(feature = "serialize")]
+    layer_permutations: HashMap<Point2, Vec<usize>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The store which chunks are written to when they are dropped from
+    /// memory.
+    #[cfg(feature = "serialize")]
+    chunk_store: Option<Arc<dyn ChunkStore>>,
+    /// How chunks are encoded when they are written to the chunk store.
+    #[cfg(feature = "serialize")]
+    chunk_encoding: ChunkEncoding,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The events of the load that was shed.
+    shed_events: Events<TilemapShedEvent>,
+    /// How many frames a chunk whose tiles were all removed is kept before
+    /// it is removed, if empty chunks are collected.
+    #[cfg_attr(feature = "serde", serde(default))]
+    empty_chunk_frames: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks whose tiles were all removed, with how many frames they
+    /// have been empty for.
+    empty_chunks: HashMap<Point2, u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The events of the empty chunks which were removed.
+    despawned_events: Events<ChunkDespawnedEvent>,
     /// Rapier physics scale for colliders and rigid bodies created
     /// for layers with colliders.
     #[cfg(feature = "bevy_rapier2d")]
     physics_scale: f32,
-    /// Custom flags.
-    custom_flags: Vec<u32>,
+    #[cfg(feature = "render")]
     #[cfg_attr(feature = "serde", serde(skip))]
     /// The handle of the texture atlas.
     texture_atlas: Handle<TextureAtlas>,
-    /// A map of all the chunks at points.
-    chunks: HashMap<Point2, Chunk>,
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The handle of the render pipeline of the chunks, if it is not the one
+    /// of the topology.
+    pipeline: Option<Handle<PipelineDescriptor>>,
+    #[cfg(feature = "lighting")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The handle of the normal map which is laid out like the texture atlas.
+    normal_map: Option<Handle<Texture>>,
+    #[cfg(feature = "render")]
     #[cfg_attr(feature = "serde", serde(skip))]
-    /// A map of all currently spawned entities.
-    entities: HashMap<usize, Vec<Entity>>,
+    /// The handle of the palette which the tiles are colored from, if they
+    /// are indexed colors.
+    palette: Option<Handle<Texture>>,
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The handle of the palette which the colors are blended to, and how
+    /// far.
+    palette_blend: Option<(Handle<Texture>, f32)>,
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The render layers of the layers which have none of their own.
+    render_layers: RenderLayers,
+    /// A map of all the chunks at points.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    chunks: HashMap<Point2, Arc<Chunk>>,
     #[cfg_attr(feature = "serde", serde(skip))]
     /// The events of the tilemap.
     chunk_events: Events<TilemapChunkEvent>,
-    #[cfg(feature = "bevy_rapier2d")]
     #[cfg_attr(feature = "serde", serde(skip))]
     /// The collision events of the tilemap.
     collision_events: Events<TilemapCollisionEvent>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The chunks which have colliders, if there is a collision radius.
+    collision_chunks: Option<HashSet<Point2>>,
     /// A set of all spawned chunks.
     spawned: HashSet<(i32, i32)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// A set of all chunks which had tiles modified since they were last
+    /// rendered.
+    modified_chunks: HashSet<Point2>,
+    /// The color which every tile of the tilemap is multiplied by.
+    #[cfg_attr(feature = "serde", serde(default))]
+    color: Color,
+    /// The opacity of every tile of the tilemap, on top of its color.
+    #[cfg_attr(feature = "serde", serde(default = "opaque"))]
+    opacity: f32,
+    /// The fade of the opacity of the tilemap, if it is fading.
+    #[cfg_attr(feature = "serde", serde(default))]
+    fade: Option<Fade>,
+    /// The fades of the opacities of the layers, at their z order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    layer_fades: HashMap<usize, Fade>,
+    /// The z translation which the depths of the layers are added to, which
+    /// orders the tilemap among other tilemaps.
+    #[cfg_attr(feature = "serde", serde(default))]
+    render_order: f32,
+    /// A map of the sprite indexes which are drawn with their own rect in the
+    /// texture atlas.
+    sprite_rects: HashMap<usize, SpriteRect>,
+    /// A map of the sprite indexes whose tiles are extruded into walls.
+    #[cfg_attr(feature = "serde", serde(default))]
+    wall_sprites: HashMap<usize, WallSprite>,
+    /// A set of the sprite indexes which are opaque and hide the tiles below
+    /// them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    opaque_sprites: HashSet<usize>,
+    /// A set of the sprite indexes whose tiles are one-way platforms.
+    one_way_sprites: HashSet<usize>,
+    /// A set of the sprite indexes whose tiles are sensors.
+    sensor_sprites: HashSet<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The trigger events of the sensor tiles.
+    trigger_events: Events<TileTriggerEvent>,
+    /// A map of the animation clips by their names.
+    animation_clips: HashMap<String, AnimationClip>,
+    /// A map of the tiles which are playing an animation clip.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    animated_tiles: HashMap<(Point2, usize), TileAnimation>,
+    /// How fast the animated tiles play, where `1.0` is their own speed.
+    animation_speed: f32,
+    /// If the animated tiles are paused.
+    animations_paused: bool,
+    /// A map of the variant groups by their names.
+    #[cfg_attr(feature = "serde", serde(default))]
+    variant_groups: HashMap<String, VariantGroup>,
+    /// The seed which the variants of tiles are picked with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    variant_seed: u64,
+    /// A map of the sprite indexes whose tiles can be destroyed.
+    sprite_hit_points: HashMap<usize, SpriteHitPoints>,
+    /// A map of the tiles which took damage.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    tile_damage: HashMap<(Point2, usize), TileDamage>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The events of the tiles destroyed by damage.
+    destroyed_events: Events<TileDestroyedEvent>,
+    /// The rules of the layers, at their z order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    tile_rules: HashMap<usize, TileRules>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// If automatic rules are being applied, so that the tiles they change do
+    /// not apply them again.
+    applying_tile_rules: bool,
+    #[cfg(feature = "procgen")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The generator of the chunks which are spawned but do not exist yet.
+    chunk_generator: Option<Box<dyn ChunkGenerator>>,
+    /// The navigation meshes of the layers, at their z order.
+    navmeshes: HashMap<usize, NavMesh>,
+    /// The vertex attribute channels of the layers, at their z order.
+    channels: HashMap<usize, Vec<TileChannel>>,
+    /// The policy of the decal layers, if there are any.
+    decal_policy: Option<DecalPolicy>,
+    /// The decals of the tiles at their points, from oldest to newest.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    decals: HashMap<Point2, Vec<Decal>>,
+    /// The layer which tiles are highlighted in, if there is one.
+    highlight_layer: Option<HighlightLayer>,
+    /// The colors of the highlighted tiles at their points.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    highlights: HashMap<Point2, Color>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The points which were highlighted since the stale highlights were
+    /// last cleared.
+    kept_highlights: HashSet<Point2>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The gameplay entities bound to tiles, at their points and z orders.
+    tile_entities: HashMap<(Point2, usize), Entity>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The events of the gameplay entities which were unbound from tiles.
+    tile_entity_events: Events<TileEntityEvent>,
+    /// The chunk templates, by the indexes of their handles.
+    chunk_templates: Vec<Arc<Chunk>>,
+    /// The templates of the chunks which are still copies of them, at the
+    /// points of the chunks.
+    #[cfg_attr(feature = "serde", serde(with = "crate::pairs"))]
+    chunk_instances: HashMap<Point2, ChunkTemplateHandle>,
+    #[cfg(feature = "render")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The meshes of the layers of the chunk templates, by their z orders,
+    /// which are shared by the spawned copies of the templates.
+    template_meshes: HashMap<(ChunkTemplateHandle, usize), Handle<Mesh>>,
+    /// A set of the chunks whose tiles can no longer be changed.
+    static_chunks: HashSet<Point2>,
+    /// A set of the z orders of the layers whose tiles can no longer be
+    /// changed.
+    static_layers: HashSet<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The changes since they were last drained, if they are tracked.
+    changes: Option<TilemapDelta>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    /// The points and z orders of the tiles which changed since the changes
+    /// were last drained, whose last state is not yet in the changes.
+    changed_tiles: HashSet<(Point2, usize)>,
 }
 
 /// Tilemap factory, which can be used to construct and configure new tilemaps.
@@ -285,8 +944,8 @@ pub struct Tilemap {
 /// Default is 32x, 32y.
 /// - [`tile_dimensions`]: specifies the tile's dimensions in pixels.
 /// Default is 32px, 32px.
-/// - [`z_layers`]: specifies the maximum number of layers that sprites
-/// can exist on. Default is 20.
+/// - [`z_layers`]: specifies the number of z orders that layers can be
+/// added at. Default is 5.
 /// - [`texture_atlas`]: specifies the texture atlas handle
 /// to use for the tilemap.
 ///
@@ -330,6 +989,8 @@ pub struct Tilemap {
 /// [`TilemapResult`]: TilemapResult
 #[derive(Clone, PartialEq, Debug)]
 pub struct TilemapBuilder {
+    /// An optional human-readable name of the tilemap.
+    name: Option<String>,
     /// The type of grid to use.
     topology: GridTopology,
     /// An optional field which can contain the tilemaps dimensions in chunks.
@@ -343,11 +1004,41 @@ pub struct TilemapBuilder {
     /// The layers to be set. If there are more, it will override `z_layers`.
     layers: Option<HashMap<usize, TilemapLayer>>,
     /// If the tilemap currently has a sprite sheet handle on it or not.
+    #[cfg(feature = "render")]
     texture_atlas: Option<Handle<TextureAtlas>>,
+    /// The render pipeline of the chunks, if it is not the one of the
+    /// topology.
+    #[cfg(feature = "render")]
+    pipeline: Option<Handle<PipelineDescriptor>>,
+    /// The normal map which is laid out like the texture atlas.
+    #[cfg(feature = "lighting")]
+    normal_map: Option<Handle<Texture>>,
+    /// The palette which the tiles are colored from.
+    #[cfg(feature = "render")]
+    palette: Option<Handle<Texture>>,
+    /// The render layers of the layers which have none of their own.
+    #[cfg(feature = "render")]
+    render_layers: RenderLayers,
     /// True if this tilemap will automatically configure.
     auto_flags: AutoFlags,
-    /// The radius of chunks to spawn from a camera's transform.
-    auto_spawn: Option<Dimension2>,
+    /// How chunks are spawned and despawned around the cameras.
+    spawn_config: Option<ChunkSpawnConfig>,
+    /// The radius of chunks around collision trackers which get colliders.
+    collision_radius: Option<Dimension2>,
+    /// The activity of chunks by their distance to the cameras.
+    activity_policy: Option<ActivityPolicy>,
+    /// How many chunks are spawned in a frame.
+    spawn_budget: Option<SpawnBudget>,
+    /// The thresholds past which load is shed.
+    shed_policy: Option<ShedPolicy>,
+    /// How many frames empty chunks are kept before they are removed.
+    empty_chunk_frames: Option<u32>,
+    /// The fill ratios at which sparse layers are stored dense and back.
+    storage_policy: Option<StoragePolicy>,
+    /// If chunks and tiles are updated in the order of their points.
+    deterministic: bool,
+    /// The z translation which the depths of the layers are added to.
+    render_order: f32,
     /// Rapier physics scale for colliders and rigid bodies created
     /// for layers with colliders.
     #[cfg(feature = "bevy_rapier2d")]
@@ -357,15 +1048,33 @@ pub struct TilemapBuilder {
 impl Default for TilemapBuilder {
     fn default() -> Self {
         TilemapBuilder {
+            name: None,
             topology: GridTopology::Square,
             dimensions: None,
             chunk_dimensions: DEFAULT_CHUNK_DIMENSIONS,
             tile_dimensions: None,
             z_layers: DEFAULT_Z_LAYERS,
             layers: None,
+            #[cfg(feature = "render")]
             texture_atlas: None,
+            #[cfg(feature = "render")]
+            pipeline: None,
+            #[cfg(feature = "lighting")]
+            normal_map: None,
+            #[cfg(feature = "render")]
+            palette: None,
+            #[cfg(feature = "render")]
+            render_layers: Default::default(),
             auto_flags: AutoFlags::NONE,
-            auto_spawn: None,
+            spawn_config: None,
+            collision_radius: None,
+            activity_policy: None,
+            spawn_budget: None,
+            shed_policy: None,
+            empty_chunk_frames: None,
+            storage_policy: None,
+            deterministic: false,
+            render_order: 0.0,
             #[cfg(feature = "bevy_rapier2d")]
             physics_scale: 1.0,
         }
@@ -400,6 +1109,23 @@ impl TilemapBuilder {
         TilemapBuilder::default()
     }
 
+    /// Sets a human-readable name for the tilemap.
+    ///
+    /// The name has no effect on how the tilemap behaves. It is shown in the
+    /// `Debug` and `Display` output and in log messages which makes it far
+    /// easier to tell multiple tilemaps apart.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let builder = TilemapBuilder::new().name("overworld");
+    /// ```
+    pub fn name<S: Into<String>>(mut self, name: S) -> TilemapBuilder {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Sets the topology of the tilemap.
     ///
     /// The default is a square grid. Use this if you want a hexagonal grid instead.
@@ -462,9 +1188,11 @@ impl TilemapBuilder {
         self
     }
 
-    /// Sets the amount of render layers that sprites can exist on.
+    /// Sets the number of z orders that layers can be added at, which is the
+    /// depth of the tilemap.
     ///
-    /// By default there are 20 if this is not set.
+    /// By default there are 5 if this is not set. It can be changed later
+    /// with [`Tilemap::set_z_layers`].
     ///
     /// # Examples
     /// ```
@@ -520,11 +1248,105 @@ impl TilemapBuilder {
     ///
     /// let builder = TilemapBuilder::new().texture_atlas(texture_atlas_handle);
     /// ```
+    #[cfg(feature = "render")]
     pub fn texture_atlas(mut self, handle: Handle<TextureAtlas>) -> TilemapBuilder {
         self.texture_atlas = Some(handle);
         self
     }
 
+    /// Sets the render pipeline which the chunks are drawn with, instead of
+    /// the one of the topology.
+    ///
+    /// The pipeline gets the same vertex attributes of the tiles as the one of
+    /// the topology, so palette effects and dissolves only need a fragment
+    /// shader of their own, see [`GridTopology::chunk_pipeline`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::pipeline::PipelineDescriptor;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let pipeline_handle = Handle::weak(HandleId::random::<PipelineDescriptor>());
+    ///
+    /// let builder = TilemapBuilder::new().pipeline(pipeline_handle);
+    /// ```
+    ///
+    /// [`GridTopology::chunk_pipeline`]: crate::chunk::render::GridTopology::chunk_pipeline
+    #[cfg(feature = "render")]
+    pub fn pipeline(mut self, handle: Handle<PipelineDescriptor>) -> TilemapBuilder {
+        self.pipeline = Some(handle);
+        self
+    }
+
+    /// Sets the normal map of the tilemap, which is laid out exactly like the
+    /// texture of the texture atlas, and draws its chunks lit by point lights.
+    ///
+    /// This is behind the `lighting` feature, see [`light`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::texture::Texture;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let normal_map_handle = Handle::weak(HandleId::random::<Texture>());
+    ///
+    /// let builder = TilemapBuilder::new().normal_map(normal_map_handle);
+    /// ```
+    ///
+    /// [`light`]: crate::light
+    #[cfg(feature = "lighting")]
+    pub fn normal_map(mut self, handle: Handle<Texture>) -> TilemapBuilder {
+        self.normal_map = Some(handle);
+        self
+    }
+
+    /// Sets the palette of the tilemap, which draws the red channel of the
+    /// texture atlas as indexes into the colors of the palette.
+    ///
+    /// This is behind the `render` feature, see [`palette`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::texture::Texture;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let palette_handle = Handle::weak(HandleId::random::<Texture>());
+    ///
+    /// let builder = TilemapBuilder::new().palette(palette_handle);
+    /// ```
+    ///
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn palette(mut self, handle: Handle<Texture>) -> TilemapBuilder {
+        self.palette = Some(handle);
+        self
+    }
+
+    /// Sets the render layers of the tilemap, which decide the cameras that
+    /// draw the layers without render layers of their own.
+    ///
+    /// This is behind the `render` feature, see [`render_layers`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, render_layers::RenderLayers};
+    ///
+    /// let builder = TilemapBuilder::new().render_layers(RenderLayers::layer(1));
+    /// ```
+    ///
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    pub fn render_layers(mut self, render_layers: RenderLayers) -> TilemapBuilder {
+        self.render_layers = render_layers;
+        self
+    }
+
     /// Sets if you want the tilemap to automatically spawn new chunks.
     ///
     /// This is useful if the tilemap map is meant to be endless or nearly
@@ -545,6 +1367,22 @@ impl TilemapBuilder {
         self
     }
 
+    /// Sets the tilemap to keep the gameplay entities bound to its tiles with
+    /// [`Tilemap::set_tile_entity`].
+    ///
+    /// By default this is not enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let builder = TilemapBuilder::new().tile_entities();
+    /// ```
+    pub fn tile_entities(mut self) -> Self {
+        self.auto_flags.insert(AutoFlags::TILE_ENTITIES);
+        self
+    }
+
     /// Sets the tilemap to automatically spawn new chunks within given
     /// dimensions.
     ///
@@ -552,6 +1390,12 @@ impl TilemapBuilder {
     /// fit the screen. It is possible that it may not be able to catch all
     /// dimensions but typical uses should be completely fine.
     ///
+    /// Chunks are despawned as soon as they leave the radius, use
+    /// [`spawn_config`] for a larger despawn radius which the window does
+    /// not change.
+    ///
+    /// [`spawn_config`]: TilemapBuilder::spawn_config
+    ///
     /// # Examples
     /// ```
     /// use bevy_tilemap::prelude::*;
@@ -559,85 +1403,326 @@ impl TilemapBuilder {
     /// let builder = TilemapBuilder::new().auto_spawn(2, 3);
     /// ```
     pub fn auto_spawn(mut self, width: u32, height: u32) -> Self {
-        self.auto_spawn = Some(Dimension2::new(width, height));
+        let radius = Dimension2::new(width, height);
+        self.spawn_config = Some(ChunkSpawnConfig::new(radius, radius));
+        self.auto_flags.insert(AutoFlags::AUTO_SPAWN);
         self
     }
 
-    /// Sets the Rapier physics scale for colliders and rigid bodies created
-    /// for layers with colliders.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub fn physics_scale(mut self, scale: f32) -> Self {
-        self.physics_scale = scale;
+    /// Sets the tilemap to automatically spawn new chunks with the spawn and
+    /// despawn radii, most loaded chunks and spawn budget of the config.
+    /// Unlike with [`auto_spawn`], the radii are kept when the window is
+    /// resized.
+    ///
+    /// [`auto_spawn`]: TilemapBuilder::auto_spawn
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{
+    ///     chunk::{ChunkSpawnConfig, SpawnBudget},
+    ///     prelude::*,
+    /// };
+    ///
+    /// // A handheld keeps few chunks around and spawns one chunk a frame.
+    /// let config = ChunkSpawnConfig {
+    ///     max_loaded_chunks: Some(16),
+    ///     budget_per_frame: Some(SpawnBudget::Chunks(1)),
+    ///     ..ChunkSpawnConfig::new((1, 1), (2, 2))
+    /// };
+    ///
+    /// let builder = TilemapBuilder::new().spawn_config(config);
+    /// ```
+    pub fn spawn_config(mut self, config: ChunkSpawnConfig) -> Self {
+        self.spawn_config = Some(config);
+        self.auto_flags.remove(AutoFlags::AUTO_SPAWN);
+        if let Some(budget) = config.budget_per_frame {
+            self.spawn_budget = Some(budget);
+        }
         self
     }
 
-    /// Consumes the builder and returns a result.
-    ///
-    /// If successful a [`TilemapResult`] is return with [tilemap] on
-    /// succes or a [`TilemapError`] if there is an issue.
+    /// Sets the radius of chunks around each [`CollisionTracker`] which get
+    /// colliders.
     ///
-    /// # Errors
-    /// If a texture atlas is not set this is the only way that an error can
-    /// occur. If this happens, be sure to use [`texture_atlas`].
+    /// The colliders of every other chunk are dropped, so that the cost of
+    /// physics scales with where the tracked entities are rather than with
+    /// every spawned chunk. Without a radius every spawned chunk has
+    /// colliders.
     ///
     /// # Examples
     /// ```
-    /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_sprite::prelude::*;
     /// use bevy_tilemap::prelude::*;
     ///
-    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// let builder = TilemapBuilder::new().collision_radius(1, 1);
+    /// ```
     ///
-    /// let builder = TilemapBuilder::new().tile_dimensions(32, 32).texture_atlas(texture_atlas_handle);
+    /// [`CollisionTracker`]: crate::physics::CollisionTracker
+    pub fn collision_radius(mut self, width: u32, height: u32) -> Self {
+        self.collision_radius = Some(Dimension2::new(width, height));
+        self
+    }
+
+    /// Sets the activity of the spawned chunks by their distance to the
+    /// cameras, so that far-away chunks are put to sleep.
     ///
-    /// assert!(builder.finish().is_ok());
-    /// assert!(TilemapBuilder::new().finish().is_err());
+    /// # Examples
     /// ```
+    /// use bevy_tilemap::{chunk::ActivityPolicy, prelude::*};
     ///
-    /// [`texture_atlas`]: TilemapBuilder::texture_atlas
-    /// [tilemap]: Tilemap
-    /// [`TilemapError`]: TilemapError
-    /// [`TilemapResult`]: TilemapResult
-    pub fn finish(self) -> TilemapResult<Tilemap> {
-        let texture_atlas = if let Some(atlas) = self.texture_atlas {
-            atlas
-        } else {
-            return Err(ErrorKind::MissingTextureAtlas.into());
-        };
-        let tile_dimensions = if let Some(dimensions) = self.tile_dimensions {
-            dimensions
-        } else {
-            return Err(ErrorKind::MissingTileDimensions.into());
-        };
+    /// let builder = TilemapBuilder::new().activity_policy(ActivityPolicy::new((1, 1), (3, 3)));
+    /// ```
+    pub fn activity_policy(mut self, policy: ActivityPolicy) -> Self {
+        self.activity_policy = Some(policy);
+        self
+    }
 
-        let z_layers = if let Some(layers) = &self.layers {
-            if self.z_layers > layers.len() {
-                self.z_layers
-            } else {
-                layers.len()
-            }
-        } else {
-            self.z_layers
-        };
+    /// Sets how many chunks are spawned in a frame, so that a camera which
+    /// moved far does not spawn every chunk around it at once.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{chunk::SpawnBudget, prelude::*};
+    ///
+    /// let builder = TilemapBuilder::new().spawn_budget(SpawnBudget::Chunks(4));
+    /// ```
+    pub fn spawn_budget(mut self, budget: SpawnBudget) -> Self {
+        self.spawn_budget = Some(budget);
+        self
+    }
 
-        let mut tilemap = Tilemap {
+    /// Sets the thresholds past which load is shed from the chunks farthest
+    /// from the camera.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{chunk::ShedPolicy, prelude::*};
+    ///
+    /// let builder = TilemapBuilder::new().shed_policy(ShedPolicy::new().max_colliders(2_000));
+    /// ```
+    pub fn shed_policy(mut self, policy: ShedPolicy) -> Self {
+        self.shed_policy = Some(policy);
+        self
+    }
+
+    /// Sets the tilemap to remove chunks whose tiles were all removed, once
+    /// they have been empty for a number of frames, see
+    /// [`Tilemap::set_empty_chunk_frames`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let builder = TilemapBuilder::new().collect_empty_chunks(60);
+    /// ```
+    pub fn collect_empty_chunks(mut self, frames: u32) -> Self {
+        self.empty_chunk_frames = Some(frames);
+        self
+    }
+
+    /// Sets the fill ratios at which the sparse layers of each chunk are
+    /// stored as dense layers and back, see [`StoragePolicy`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{chunk::StoragePolicy, prelude::*};
+    ///
+    /// let builder = TilemapBuilder::new().storage_policy(StoragePolicy::default());
+    /// ```
+    ///
+    /// [`StoragePolicy`]: crate::chunk::StoragePolicy
+    pub fn storage_policy(mut self, policy: StoragePolicy) -> Self {
+        self.storage_policy = Some(policy);
+        self
+    }
+
+    /// Sets the tilemap to update its chunks and tiles in the order of their
+    /// points, instead of the order they are stored in which changes between
+    /// runs and platforms. Lockstep simulations need this so that events are
+    /// sent and entities spawned in the same order for every peer.
+    ///
+    /// By default this is not enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let builder = TilemapBuilder::new().deterministic();
+    /// ```
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Sets the render order of the tilemap, which the depths of its layers
+    /// are added to, see [`Tilemap::set_render_order`].
+    ///
+    /// By default this is `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let builder = TilemapBuilder::new().render_order(100.0);
+    /// ```
+    pub fn render_order(mut self, order: f32) -> Self {
+        self.render_order = order;
+        self
+    }
+
+    /// Sets the Rapier physics scale for colliders and rigid bodies created
+    /// for layers with colliders.
+    #[cfg(feature = "bevy_rapier2d")]
+    pub fn physics_scale(mut self, scale: f32) -> Self {
+        self.physics_scale = scale;
+        self
+    }
+
+    /// Consumes the builder and returns a result.
+    ///
+    /// If successful a [`TilemapResult`] is return with [tilemap] on
+    /// succes or a [`TilemapError`] if there is an issue.
+    ///
+    /// # Errors
+    /// If a texture atlas is not set this is the only way that an error can
+    /// occur. If this happens, be sure to use [`texture_atlas`]. Without the
+    /// `render` feature the texture atlas is not needed.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let builder = TilemapBuilder::new().tile_dimensions(32, 32).texture_atlas(texture_atlas_handle);
+    ///
+    /// assert!(builder.finish().is_ok());
+    /// assert!(TilemapBuilder::new().finish().is_err());
+    /// ```
+    ///
+    /// [`texture_atlas`]: TilemapBuilder::texture_atlas
+    /// [tilemap]: Tilemap
+    /// [`TilemapError`]: TilemapError
+    /// [`TilemapResult`]: TilemapResult
+    pub fn finish(self) -> TilemapResult<Tilemap> {
+        #[cfg(feature = "render")]
+        let texture_atlas = if let Some(atlas) = self.texture_atlas {
+            atlas
+        } else {
+            return Err(ErrorKind::MissingTextureAtlas.into());
+        };
+        let tile_dimensions = if let Some(dimensions) = self.tile_dimensions {
+            dimensions
+        } else {
+            return Err(ErrorKind::MissingTileDimensions.into());
+        };
+
+        // The depth is grown to fit the layers of the builder.
+        let z_layers = self
+            .layers
+            .iter()
+            .flat_map(|layers| layers.keys())
+            .map(|z_order| z_order.saturating_add(1))
+            .fold(self.z_layers, usize::max);
+
+        let mut tilemap = Tilemap {
+            name: self.name,
             topology: self.topology,
             dimensions: self.dimensions,
             chunk_dimensions: self.chunk_dimensions,
             tile_dimensions,
             layers: vec![None; z_layers],
             auto_flags: self.auto_flags,
-            auto_spawn: self.auto_spawn,
+            spawn_config: self.spawn_config,
+            collision_radius: self.collision_radius,
+            activity_policy: self.activity_policy,
+            spawn_budget: self.spawn_budget,
+            spawn_queue: Default::default(),
+            spawn_focus: Default::default(),
+            shed_policy: self.shed_policy,
+            empty_chunk_frames: self.empty_chunk_frames,
+            storage_policy: self.storage_policy,
+            layer_kind_overrides: Default::default(),
+            deterministic: self.deterministic,
+            origin: Default::default(),
+            translation_origin: Default::default(),
+            shed_colliders: Default::default(),
+            shed_meshes: Default::default(),
+            #[cfg(feature = "serialize")]
+            unloaded_chunks: Default::default(),
+            #[cfg(feature = "serialize")]
+            store_points: Default::default(),
+            #[cfg(feature = "serialize")]
+            layer_permutations: Default::default(),
+            #[cfg(feature = "serialize")]
+            chunk_store: None,
+            #[cfg(feature = "serialize")]
+            chunk_encoding: ChunkEncoding::default(),
+            shed_events: Default::default(),
+            empty_chunks: Default::default(),
+            despawned_events: Default::default(),
             #[cfg(feature = "bevy_rapier2d")]
             physics_scale: self.physics_scale,
-            custom_flags: Vec::new(),
+            #[cfg(feature = "render")]
             texture_atlas,
+            #[cfg(feature = "render")]
+            pipeline: self.pipeline,
+            #[cfg(feature = "lighting")]
+            normal_map: self.normal_map,
+            #[cfg(feature = "render")]
+            palette: self.palette,
+            #[cfg(feature = "render")]
+            palette_blend: None,
+            #[cfg(feature = "render")]
+            render_layers: self.render_layers,
             chunks: Default::default(),
-            entities: Default::default(),
             chunk_events: Default::default(),
-            #[cfg(feature = "bevy_rapier2d")]
             collision_events: Default::default(),
+            collision_chunks: Default::default(),
             spawned: Default::default(),
+            modified_chunks: Default::default(),
+            color: Color::WHITE,
+            opacity: 1.0,
+            fade: None,
+            layer_fades: Default::default(),
+            render_order: self.render_order,
+            sprite_rects: Default::default(),
+            wall_sprites: Default::default(),
+            opaque_sprites: Default::default(),
+            one_way_sprites: Default::default(),
+            sensor_sprites: Default::default(),
+            trigger_events: Default::default(),
+            animation_clips: Default::default(),
+            animated_tiles: Default::default(),
+            animation_speed: 1.0,
+            animations_paused: false,
+            variant_groups: Default::default(),
+            variant_seed: 0,
+            sprite_hit_points: Default::default(),
+            tile_damage: Default::default(),
+            destroyed_events: Default::default(),
+            tile_rules: Default::default(),
+            applying_tile_rules: false,
+            #[cfg(feature = "procgen")]
+            chunk_generator: None,
+            navmeshes: Default::default(),
+            channels: Default::default(),
+            decal_policy: None,
+            decals: Default::default(),
+            highlight_layer: None,
+            highlights: Default::default(),
+            kept_highlights: Default::default(),
+            tile_entities: Default::default(),
+            tile_entity_events: Default::default(),
+            chunk_templates: Default::default(),
+            chunk_instances: Default::default(),
+            #[cfg(feature = "render")]
+            template_meshes: Default::default(),
+            static_chunks: Default::default(),
+            static_layers: Default::default(),
+            changes: None,
+            changed_tiles: Default::default(),
         };
 
         if let Some(mut layers) = self.layers {
@@ -650,6 +1735,16 @@ impl TilemapBuilder {
     }
 }
 
+impl Display for Tilemap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(name) = &self.name {
+            write!(f, "tilemap \"{}\"", name)
+        } else {
+            write!(f, "unnamed tilemap")
+        }
+    }
+}
+
 impl TypeUuid for Tilemap {
     const TYPE_UUID: Uuid = Uuid::from_u128(109481186966523254410691740507722642628);
 }
@@ -657,23 +1752,102 @@ impl TypeUuid for Tilemap {
 impl Default for Tilemap {
     fn default() -> Self {
         Tilemap {
+            name: None,
             topology: GridTopology::Square,
             dimensions: None,
             chunk_dimensions: DEFAULT_CHUNK_DIMENSIONS,
             tile_dimensions: DEFAULT_TEXTURE_DIMENSIONS,
             layers: vec![None; DEFAULT_Z_LAYERS],
             auto_flags: AutoFlags::NONE,
-            auto_spawn: None,
+            spawn_config: None,
+            collision_radius: None,
+            activity_policy: None,
+            spawn_budget: None,
+            spawn_queue: Default::default(),
+            spawn_focus: Default::default(),
+            shed_policy: None,
+            empty_chunk_frames: None,
+            storage_policy: None,
+            layer_kind_overrides: Default::default(),
+            deterministic: false,
+            origin: Default::default(),
+            translation_origin: Default::default(),
+            shed_colliders: Default::default(),
+            shed_meshes: Default::default(),
+            #[cfg(feature = "serialize")]
+            unloaded_chunks: Default::default(),
+            #[cfg(feature = "serialize")]
+            store_points: Default::default(),
+            #[cfg(feature = "serialize")]
+            layer_permutations: Default::default(),
+            #[cfg(feature = "serialize")]
+            chunk_store: None,
+            #[cfg(feature = "serialize")]
+            chunk_encoding: ChunkEncoding::default(),
+            shed_events: Default::default(),
+            empty_chunks: Default::default(),
+            despawned_events: Default::default(),
             #[cfg(feature = "bevy_rapier2d")]
             physics_scale: 1.0,
-            custom_flags: Vec::new(),
+            #[cfg(feature = "render")]
             texture_atlas: Handle::default(),
+            #[cfg(feature = "render")]
+            pipeline: None,
+            #[cfg(feature = "lighting")]
+            normal_map: None,
+            #[cfg(feature = "render")]
+            palette: None,
+            #[cfg(feature = "render")]
+            palette_blend: None,
+            #[cfg(feature = "render")]
+            render_layers: Default::default(),
             chunks: Default::default(),
-            entities: Default::default(),
             chunk_events: Default::default(),
-            #[cfg(feature = "bevy_rapier2d")]
             collision_events: Default::default(),
+            collision_chunks: Default::default(),
             spawned: Default::default(),
+            modified_chunks: Default::default(),
+            color: Color::WHITE,
+            opacity: 1.0,
+            fade: None,
+            layer_fades: Default::default(),
+            render_order: Default::default(),
+            sprite_rects: Default::default(),
+            wall_sprites: Default::default(),
+            opaque_sprites: Default::default(),
+            one_way_sprites: Default::default(),
+            sensor_sprites: Default::default(),
+            trigger_events: Default::default(),
+            animation_clips: Default::default(),
+            animated_tiles: Default::default(),
+            animation_speed: 1.0,
+            animations_paused: false,
+            variant_groups: Default::default(),
+            variant_seed: 0,
+            sprite_hit_points: Default::default(),
+            tile_damage: Default::default(),
+            destroyed_events: Default::default(),
+            tile_rules: Default::default(),
+            applying_tile_rules: false,
+            #[cfg(feature = "procgen")]
+            chunk_generator: None,
+            navmeshes: Default::default(),
+            channels: Default::default(),
+            decal_policy: None,
+            decals: Default::default(),
+            highlight_layer: None,
+            highlights: Default::default(),
+            kept_highlights: Default::default(),
+            tile_entities: Default::default(),
+            tile_entity_events: Default::default(),
+            chunk_templates: Default::default(),
+            chunk_instances: Default::default(),
+            #[cfg(feature = "render")]
+            template_meshes: Default::default(),
+            static_chunks: Default::default(),
+            static_layers: Default::default(),
+            changes: None,
+            changed_tiles: Default::default(),
         }
     }
 }
@@ -698,6 +1872,7 @@ impl Tilemap {
     /// ```
     ///
     /// [`default`]: Tilemap::default
+    #[cfg(feature = "render")]
     pub fn new(texture_atlas: Handle<TextureAtlas>, tile_width: u32, tile_height: u32) -> Tilemap {
         Tilemap {
             texture_atlas,
@@ -706,6 +1881,20 @@ impl Tilemap {
         }
     }
 
+    /// Constructs a new Tilemap with the tile dimensions and default
+    /// configuration, without a texture atlas as headless builds have none.
+    ///
+    /// This differs from [`default`] in that it requires the tile dimensions.
+    ///
+    /// [`default`]: Tilemap::default
+    #[cfg(not(feature = "render"))]
+    pub fn new(tile_width: u32, tile_height: u32) -> Tilemap {
+        Tilemap {
+            tile_dimensions: Dimension2::new(tile_width, tile_height),
+            ..Default::default()
+        }
+    }
+
     /// Configures the builder with the default settings.
     ///
     /// Is equivalent to [`default`] and [`builder`] method in the
@@ -733,6 +1922,57 @@ impl Tilemap {
         TilemapBuilder::default()
     }
 
+    /// Returns the name of the tilemap, if it has one.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle.clone_weak())
+    ///     .tile_dimensions(32, 32)
+    ///     .name("overworld")
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(tilemap.name(), Some("overworld"));
+    /// assert_eq!(tilemap.to_string(), "tilemap \"overworld\"");
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.name(), None);
+    /// assert_eq!(tilemap.to_string(), "unnamed tilemap");
+    /// ```
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the name of the tilemap, returning the previous name if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.set_name("dungeon"), None);
+    /// assert_eq!(tilemap.set_name("cellar"), Some("dungeon".to_string()));
+    /// assert_eq!(tilemap.name(), Some("cellar"));
+    /// ```
+    pub fn set_name<S: Into<String>>(&mut self, name: S) -> Option<String> {
+        self.name.replace(name.into())
+    }
+
     /// Sets the sprite sheet for use in the tilemap.
     ///
     /// This can be used if the need to swap the sprite sheet for another is
@@ -751,6 +1991,7 @@ impl Tilemap {
     ///
     /// tilemap.set_texture_atlas(texture_atlas_handle);
     /// ```
+    #[cfg(feature = "render")]
     pub fn set_texture_atlas(&mut self, handle: Handle<TextureAtlas>) {
         self.texture_atlas = handle;
     }
@@ -772,68 +2013,63 @@ impl Tilemap {
     /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     /// let texture_atlas: &Handle<TextureAtlas> = tilemap.texture_atlas();
     /// ```
+    #[cfg(feature = "render")]
     pub fn texture_atlas(&self) -> &Handle<TextureAtlas> {
         &self.texture_atlas
     }
 
-    /// Constructs a new chunk and stores it at a coordinate position.
+    /// Sets the color which every tile of the tilemap is multiplied by when it
+    /// is drawn, on top of the colors of its layers and tiles.
     ///
-    /// It requires that you give it either a point. It then automatically sets
-    /// both a sized mesh and chunk for use based on the parameters set in the
-    /// parent tilemap.
+    /// The color is applied by the shader, so tinting the whole map for a day
+    /// and night cycle or to show it underwater rebuilds no chunk meshes.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::color::Color;
     /// use bevy_sprite::prelude::*;
     /// use bevy_tilemap::prelude::*;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .dimensions(3, 3)
-    ///     .tile_dimensions(32, 32)
-    ///     .finish()
-    ///     .unwrap();
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// // Add some chunks.
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.insert_chunk((1, 1)).is_ok());
-    /// assert!(tilemap.insert_chunk((-2, -2)).is_err());
+    /// // Dusk.
+    /// tilemap.set_color(Color::rgb(0.8, 0.6, 0.7));
     ///
-    /// assert!(tilemap.contains_chunk((0, 0)));
-    /// assert!(tilemap.contains_chunk((1, 1)));
-    /// assert!(!tilemap.contains_chunk((-2, -2)));
+    /// assert_eq!(tilemap.color(), Color::rgb(0.8, 0.6, 0.7));
     /// ```
-    /// # Errors
-    ///
-    /// If the point does not exist in the tilemap, an error is returned. This
-    /// can only be returned if you had set the dimensions on the tilemap.
-    ///
-    /// Also will return an error if the chunk already exists. If this happens
-    /// and was intentional, it is best to remove the chunk first. This is
-    /// simply a fail safe without actually returning the chunk as it is meant
-    /// to be kept internal.
-    pub fn insert_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
-        let point: Point2 = point.into();
-        if let Some(dimensions) = &self.dimensions {
-            dimensions.check_point(point)?;
-        }
-        let layer_kinds = self
-            .layers
-            .iter()
-            .map(|x| x.and_then(|y| Some(y.kind)))
-            .collect::<Vec<Option<LayerKind>>>();
-        let chunk = Chunk::new(point, &layer_kinds, self.chunk_dimensions);
-        match self.chunks.insert(point, chunk) {
-            Some(_) => Err(ErrorKind::ChunkAlreadyExists(point).into()),
-            None => Ok(()),
-        }
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Returns the color which every tile of the tilemap is multiplied by.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Sets the opacity of every tile of the tilemap from `0.0` to `1.0`, on
+    /// top of its color, which stops the tilemap fading.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.fade = None;
+    }
+
+    /// Returns the opacity of every tile of the tilemap.
+    pub fn opacity(&self) -> f32 {
+        self.opacity
     }
 
-    /// Returns `true` if the chunk is included in the tilemap.
+    /// Sets the render order of the tilemap, which is the z translation that
+    /// the depths of its layers are added to when its chunks are drawn.
+    ///
+    /// Tilemaps which overlap, such as a ground map and a map of details over
+    /// it, are drawn in their render orders, so every layer of the tilemap
+    /// with the higher order is drawn over every layer of the other, as long
+    /// as the orders are further apart than their depths. Spawned chunks are
+    /// moved to the new order in the tilemap stage.
     ///
     /// # Examples
     /// ```
@@ -844,59 +2080,77 @@ impl Tilemap {
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// let ground = Tilemap::new(texture_atlas_handle.clone(), 32, 32);
+    /// let mut details = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.contains_chunk((0, 0)));
-    /// assert!(!tilemap.contains_chunk((1, 1)));
+    /// // The details are drawn over every layer of the ground.
+    /// details.set_render_order(ground.z_layers() as f32);
+    ///
+    /// assert_eq!(details.render_order(), 5.0);
+    /// assert_eq!(details.layer_depth(0), 0.0);
     /// ```
-    pub fn contains_chunk<P: Into<Point2>>(&mut self, point: P) -> bool {
-        let point: Point2 = point.into();
-        self.chunks.contains_key(&point)
+    pub fn set_render_order(&mut self, order: f32) {
+        self.render_order = order;
     }
 
-    #[deprecated(
-        since = "0.4.0",
-        note = "Please use `add_layer` method instead with the `TilemapLayer` struct"
-    )]
-    #[doc(hidden)]
-    pub fn add_layer_with_kind(&mut self, kind: LayerKind, z_order: usize) -> TilemapResult<()> {
-        let layer = TilemapLayer {
-            kind,
-            #[cfg(feature = "bevy_rapier2d")]
-            interaction_groups: InteractionGroups::default(),
-        };
-        if let Some(some_kind) = self.layers.get_mut(z_order) {
-            if some_kind.is_some() {
-                return Err(ErrorKind::LayerExists(z_order).into());
-            }
-            *some_kind = Some(layer);
-        }
-
-        for chunk in self.chunks.values_mut() {
-            chunk.add_layer(&kind, z_order, self.chunk_dimensions);
-        }
-
-        Ok(())
+    /// Returns the render order of the tilemap.
+    pub fn render_order(&self) -> f32 {
+        self.render_order
     }
 
-    /// Adds a layer to the tilemap.
+    /// Fades the opacity of the whole tilemap to an opacity from `0.0` to
+    /// `1.0` over a duration in seconds, such as for level transitions. The
+    /// fade starts from the opacity the tilemap has and replaces any fade it
+    /// was in.
     ///
-    /// This method creates a layer across all chunks at the specified Z layer.
-    /// For ease of use, it by default makes a layer with a dense
-    /// [`LayerKind`] which is ideal for layers full of sprites.
+    /// The opacity is kept apart from the color of the tilemap, so fades and
+    /// a [`DaylightCycle`] do not undo each other.
     ///
-    /// If you want to use a layer that is more performant and less data heavy,
-    /// use [`add_layer_with_kind`] with [`LayerKind::Sparse`].
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
     ///
-    /// If the layer is already the specified layer's kind, then nothing
-    /// happens.
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A fade which is over at once.
+    /// tilemap.fade_to(0.5, 0.0);
+    /// assert_eq!(tilemap.opacity(), 0.5);
+    ///
+    /// // Fade out for the next level.
+    /// tilemap.fade_to(0.0, 1.0);
+    /// assert!(tilemap.is_fading());
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// // The fade goes on for a second of frames.
+    /// let tilemap = harness.tilemap(entity).unwrap();
+    /// assert!(tilemap.is_fading());
+    /// assert!(tilemap.opacity() <= 0.5);
+    /// ```
+    ///
+    /// [`DaylightCycle`]: crate::daylight::DaylightCycle
+    pub fn fade_to(&mut self, opacity: f32, duration: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if duration > 0.0 {
+            self.fade = Some(Fade::new(self.opacity, opacity, duration));
+        } else {
+            self.set_opacity(opacity);
+        }
+    }
+
+    /// Fades the opacity of a layer, which is the alpha of its color, to an
+    /// opacity from `0.0` to `1.0` over a duration in seconds. The fade
+    /// starts from the opacity the layer has and replaces any fade it was in.
     ///
     /// # Errors
     ///
-    /// If a layer is set and a different layer already exists at that Z layer
-    /// then an error is returned regarding that. This is done to prevent
-    /// accidental overwrites of a layer.
+    /// If the layer does not exist, an error is returned.
     ///
     /// # Examples
     /// ```
@@ -907,855 +2161,8848 @@ impl Tilemap {
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let layer = TilemapLayer {
-    ///    kind: LayerKind::Sparse,
-    ///    ..Default::default()
-    /// };
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert!(tilemap.add_layer(layer, 1).is_ok());
-    /// assert!(tilemap.add_layer(layer, 1).is_err());
-    /// ```
+    /// // The roof fades away as the player walks into the house.
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 2).unwrap();
+    /// tilemap.fade_layer_to(2, 0.0, 0.25).unwrap();
     ///
-    /// [`add_layer_with_kind`]: Tilemap::add_layer_with_kind
-    /// [`LayerKind`]: crate::chunk::LayerKind
-    /// [`LayerKind::Sparse`]: crate::chunk::LayerKind::Sparse
-    pub fn add_layer(&mut self, layer: TilemapLayer, z_order: usize) -> TilemapResult<()> {
-        if let Some(inner_layer) = self.layers.get_mut(z_order) {
-            if inner_layer.is_some() {
-                return Err(ErrorKind::LayerExists(z_order).into());
-            }
-            *inner_layer = Some(layer);
+    /// assert!(tilemap.is_fading());
+    /// assert!(tilemap.fade_layer_to(3, 0.0, 0.25).is_err());
+    /// ```
+    pub fn fade_layer_to(
+        &mut self,
+        z_order: usize,
+        opacity: f32,
+        duration: f32,
+    ) -> TilemapResult<()> {
+        let layer = if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer
+        } else {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        };
+        let opacity = opacity.clamp(0.0, 1.0);
+        if duration > 0.0 {
+            let fade = Fade::new(layer.color.a(), opacity, duration);
+            self.layer_fades.insert(z_order, fade);
+        } else {
+            layer.color.set_a(opacity);
+            self.layer_fades.remove(&z_order);
         }
+        Ok(())
+    }
 
-        for chunk in self.chunks.values_mut() {
-            chunk.add_layer(&layer.kind, z_order, self.chunk_dimensions)
+    /// Returns `true` if the tilemap or any of its layers is fading.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some() || !self.layer_fades.is_empty()
+    }
+
+    /// Moves the fades of the tilemap and its layers on by the time, in
+    /// seconds, and sets their opacities.
+    pub(crate) fn update_fades(&mut self, delta: f32) {
+        if let Some(fade) = &mut self.fade {
+            let (opacity, done) = fade.advance(delta);
+            self.opacity = opacity;
+            if done {
+                self.fade = None;
+            }
         }
+        let layers = &mut self.layers;
+        self.layer_fades.retain(|z_order, fade| {
+            let layer = if let Some(Some(layer)) = layers.get_mut(*z_order) {
+                layer
+            } else {
+                return false;
+            };
+            let (opacity, done) = fade.advance(delta);
+            layer.color.set_a(opacity);
+            !done
+        });
+    }
 
-        Ok(())
+    /// Returns the color which the tiles of a layer are multiplied by in the
+    /// shader, which is the color of the tilemap times that of the layer.
+    #[cfg(feature = "render")]
+    pub(crate) fn layer_tint(&self, z_order: usize) -> Vec4 {
+        let layer_color = match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.color,
+            _ => Color::WHITE,
+        };
+        Vec4::from(self.color) * Vec4::from(layer_color) * Vec4::new(1.0, 1.0, 1.0, self.opacity)
     }
 
-    /// Moves a layer from one Z level to another.
-    ///
-    /// # Errors
-    ///
-    /// If the destination exists, it will throw an error. Likewise, if the
-    /// origin does not exist, it also will throw an error.
+    /// Returns the handle of the render pipeline which the chunks are drawn
+    /// with, if it is not the one of the topology.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::pipeline::PipelineDescriptor;
     /// use bevy_sprite::prelude::*;
     /// use bevy_tilemap::prelude::*;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// let pipeline_handle = Handle::weak(HandleId::random::<PipelineDescriptor>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
+    /// let tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .z_layers(3)
     ///     .tile_dimensions(32, 32)
-    ///     .add_layer(TilemapLayer { kind: LayerKind::Dense, ..Default::default() }, 0)
-    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 3)
+    ///     .pipeline(pipeline_handle.clone())
     ///     .finish()
     ///     .unwrap();
     ///
-    /// // If we moved this to layer 3, it would instead fail.
-    /// assert!(tilemap.move_layer(0, 2).is_ok());
-    /// assert!(tilemap.move_layer(3, 2).is_err());
+    /// assert_eq!(tilemap.pipeline(), Some(&pipeline_handle));
     /// ```
-    pub fn move_layer(&mut self, from_z: usize, to_z: usize) -> TilemapResult<()> {
-        if let Some(layer) = self.layers.get(to_z) {
-            if layer.is_some() {
-                return Err(ErrorKind::LayerExists(to_z).into());
-            }
-        };
-        if let Some(layer) = self.layers.get(from_z) {
-            if Some(layer).is_none() {
-                return Err(ErrorKind::LayerDoesNotExist(from_z).into());
-            }
-        }
-
-        self.layers.swap(from_z, to_z);
-        for chunk in self.chunks.values_mut() {
-            chunk.move_layer(from_z, to_z);
-        }
-
-        Ok(())
+    #[cfg(feature = "render")]
+    pub fn pipeline(&self) -> Option<&Handle<PipelineDescriptor>> {
+        self.pipeline.as_ref()
     }
 
-    /// Removes a layer from the tilemap and inner chunks.
+    /// Returns the handle of the normal map of the tilemap, if it is lit.
     ///
-    /// **Warning**: This is destructive if you have tiles that exist on that
-    /// layer. If you want to add them back in, better to use the [`move_layer`]
-    /// method instead.
+    /// This is behind the `lighting` feature, see [`light`].
     ///
-    /// This method takes in a Z layer which is then flagged for deletion. If
-    /// the layer already does not exist, it does nothing.
+    /// [`light`]: crate::light
+    #[cfg(feature = "lighting")]
+    pub fn normal_map(&self) -> Option<&Handle<Texture>> {
+        self.normal_map.as_ref()
+    }
+
+    /// Sets the palette which the tiles are colored from, which stops any
+    /// blend to another palette. A tilemap which had no palette is drawn in
+    /// indexed colors from then on.
+    ///
+    /// This is behind the `render` feature, see [`palette`].
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::texture::Texture;
     /// use bevy_sprite::prelude::*;
     /// use bevy_tilemap::prelude::*;
     ///
-    /// // In production use a strong handle from an actual source.
+    /// // In production use strong handles from actual sources.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// let summer = Handle::weak(HandleId::random::<Texture>());
+    /// let winter = Handle::weak(HandleId::random::<Texture>());
     ///
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// assert_eq!(tilemap.palette(), None);
     ///
-    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1);
+    /// tilemap.set_palette(summer);
+    /// tilemap.set_palette(winter.clone());
+    /// assert_eq!(tilemap.palette(), Some(&winter));
     ///
-    /// tilemap.remove_layer(1);
+    /// // Back to the colors of the texture atlas.
+    /// tilemap.clear_palette();
+    /// assert_eq!(tilemap.palette(), None);
     /// ```
     ///
-    /// [`move_layer`]: Tilemap::move_layer
-    pub fn remove_layer(&mut self, z: usize) {
-        if let Some(layer) = self.layers.get_mut(z) {
-            *layer = None;
-        } else {
-            return;
-        }
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn set_palette(&mut self, handle: Handle<Texture>) {
+        self.palette = Some(handle);
+        self.palette_blend = None;
+    }
 
-        for chunk in self.chunks.values_mut() {
-            chunk.remove_layer(z);
-        }
+    /// Clears the palette, so the tiles are drawn in the colors of the
+    /// texture atlas again.
+    ///
+    /// This is behind the `render` feature, see [`palette`].
+    ///
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn clear_palette(&mut self) {
+        self.palette = None;
+        self.palette_blend = None;
     }
 
-    /// Spawns a chunk at a given index or coordinate.
+    /// Returns the handle of the palette which the tiles are colored from, if
+    /// they are indexed colors.
     ///
-    /// Does nothing if the chunk does not exist.
+    /// This is behind the `render` feature, see [`palette`].
     ///
-    /// # Errors
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn palette(&self) -> Option<&Handle<Texture>> {
+        self.palette.as_ref()
+    }
+
+    /// Blends the colors of the palette to those of another palette, by an
+    /// amount from `0.0` for the palette to `1.0` for the other one. This does
+    /// nothing if the tilemap has no palette.
     ///
-    /// If the coordinate or index is out of bounds.
+    /// This is behind the `render` feature, see [`palette`].
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::texture::Texture;
     /// use bevy_sprite::prelude::*;
     /// use bevy_tilemap::prelude::*;
     ///
-    /// // In production use a strong handle from an actual source.
+    /// // In production use strong handles from actual sources.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// let day = Handle::weak(HandleId::random::<Texture>());
+    /// let dusk = Handle::weak(HandleId::random::<Texture>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .dimensions(1, 1)
-    ///     .tile_dimensions(32, 32)
-    ///     .finish()
-    ///     .unwrap();
-    ///
-    /// tilemap.insert_chunk((0, 0));
-    ///
-    /// // Ideally you should want to set some tiles here else nothing will
-    /// // display in the render...
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.blend_palette(dusk.clone(), 0.5);
+    /// assert_eq!(tilemap.palette_blend(), None);
     ///
-    /// assert!(tilemap.spawn_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.spawn_chunk((1, 1)).is_err());
-    /// assert!(tilemap.spawn_chunk((-1, -1)).is_err());
+    /// tilemap.set_palette(day);
+    /// tilemap.blend_palette(dusk.clone(), 1.5);
+    /// assert_eq!(tilemap.palette_blend(), Some((&dusk, 1.0)));
     /// ```
-    pub fn spawn_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
-        let point: Point2 = point.into();
-        if let Some(dimensions) = &self.dimensions {
-            dimensions.check_point(point)?;
+    ///
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn blend_palette(&mut self, handle: Handle<Texture>, amount: f32) {
+        if self.palette.is_some() {
+            self.palette_blend = Some((handle, amount.clamp(0.0, 1.0)));
         }
+    }
 
-        if self.spawned.contains(&(point.x, point.y)) {
-            return Ok(());
-        } else {
-            self.chunk_events.send(TilemapChunkEvent::Spawned { point });
-        }
+    /// Returns the handle of the palette which the colors are blended to and
+    /// how far, if they are.
+    ///
+    /// This is behind the `render` feature, see [`palette`].
+    ///
+    /// [`palette`]: crate::palette
+    #[cfg(feature = "render")]
+    pub fn palette_blend(&self) -> Option<(&Handle<Texture>, f32)> {
+        self.palette_blend
+            .as_ref()
+            .map(|(handle, amount)| (handle, *amount))
+    }
 
-        Ok(())
+    /// Sets the render layers of the tilemap, which decide the cameras that
+    /// draw the layers without render layers of their own. Spawned chunks
+    /// are given them in the tilemap stage.
+    ///
+    /// This is behind the `render` feature, see [`render_layers`].
+    ///
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    pub fn set_render_layers(&mut self, render_layers: RenderLayers) {
+        self.render_layers = render_layers;
     }
 
-    /// Spawns a chunk at a given tile point.
+    /// Returns the render layers of the tilemap.
+    ///
+    /// This is behind the `render` feature, see [`render_layers`].
+    ///
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    pub fn render_layers(&self) -> RenderLayers {
+        self.render_layers
+    }
+
+    /// Sets the render layers of a layer, like
+    /// [`TilemapLayer::render_layers`]. With `None` the layer is in the render
+    /// layers of the tilemap.
+    ///
+    /// This is behind the `render` feature, see [`render_layers`].
     ///
     /// # Errors
     ///
-    /// If the coordinate or index is out of bounds or if the chunk does not
-    /// exist, an error will be returned.
+    /// If the layer does not exist, an error is returned.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, render_layers::RenderLayers};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .chunk_dimensions(32, 32)
-    ///     .tile_dimensions(32, 32)
-    ///     .dimensions(1, 1)
-    ///     .finish()
-    ///     .unwrap();
-    ///
-    /// let point = (15, 15);
-    /// let sprite_index = 0;
-    /// let tile = Tile { point, sprite_index, ..Default::default() };
-    ///
-    /// tilemap.insert_tile(tile);
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    /// tilemap.set_render_layers(RenderLayers::layer(2));
     ///
-    /// assert!(tilemap.spawn_chunk_containing_point(point).is_ok());
-    /// assert!(tilemap.spawn_chunk_containing_point((16, 16)).is_err());
-    /// assert!(tilemap.spawn_chunk_containing_point((-18, -18)).is_err());
+    /// tilemap.set_layer_render_layers(1, Some(RenderLayers::layer(3))).unwrap();
+    /// assert_eq!(tilemap.layer_render_layers(0), RenderLayers::layer(2));
+    /// assert_eq!(tilemap.layer_render_layers(1), RenderLayers::layer(3));
+    /// assert!(tilemap.set_layer_render_layers(2, None).is_err());
     /// ```
-    pub fn spawn_chunk_containing_point<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
-        let point = self.point_to_chunk_point(point);
-        self.spawn_chunk(point)
+    ///
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    pub fn set_layer_render_layers(
+        &mut self,
+        z_order: usize,
+        render_layers: Option<RenderLayers>,
+    ) -> TilemapResult<()> {
+        if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer.render_layers = render_layers;
+            Ok(())
+        } else {
+            Err(ErrorKind::LayerDoesNotExist(z_order).into())
+        }
     }
 
-    /// De-spawns a spawned chunk at a given index or coordinate.
+    /// Returns the render layers which the chunks of a layer are drawn in,
+    /// which are those of the tilemap if the layer has none of its own.
     ///
-    /// If the chunk is not spawned this will result in nothing.
+    /// This is behind the `render` feature, see [`render_layers`].
     ///
-    /// # Errors
+    /// [`render_layers`]: crate::render_layers
+    #[cfg(feature = "render")]
+    pub fn layer_render_layers(&self, z_order: usize) -> RenderLayers {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.render_layers.unwrap_or(self.render_layers),
+            _ => self.render_layers,
+        }
+    }
+
+    /// Returns the handle of the render pipeline which the chunks are drawn
+    /// with.
+    #[cfg(feature = "render")]
+    pub(crate) fn chunk_pipeline_handle(&self) -> Handle<PipelineDescriptor> {
+        if let Some(pipeline) = &self.pipeline {
+            return pipeline.clone_weak();
+        }
+        if self.palette.is_some() {
+            return self.topology.to_palette_pipeline_handle().typed();
+        }
+        #[cfg(feature = "lighting")]
+        if self.normal_map.is_some() {
+            return self.topology.to_lit_pipeline_handle().typed();
+        }
+        self.topology.to_pipeline_handle().typed()
+    }
+
+    /// Sets the rect in the texture atlas that a sprite index is drawn with,
+    /// returning the previous one if there was one.
     ///
-    /// If the coordinate or index is out of bounds, an error will be returned.
+    /// Every tile with the sprite index is drawn with the rect instead of the
+    /// one of the texture atlas, which allows trimmed and rotated sprites from
+    /// a texture packer to be used.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, sprite::SpriteRect};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .dimensions(1, 1)
-    ///     .tile_dimensions(32, 32)
-    ///     .finish()
-    ///     .unwrap();
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    /// let sprite_rect = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(30.0, 28.0))
+    ///     .trimmed(Vec2::new(1.0, 0.0), Vec2::new(32.0, 32.0));
+    /// assert_eq!(tilemap.set_sprite_rect(3, sprite_rect), None);
+    /// assert_eq!(tilemap.sprite_rect(3), Some(&sprite_rect));
+    /// ```
+    pub fn set_sprite_rect(&mut self, index: usize, sprite_rect: SpriteRect) -> Option<SpriteRect> {
+        self.modify_all_chunks();
+        self.sprite_rects.insert(index, sprite_rect)
+    }
+
+    /// Removes the rect of a sprite index, so that it is drawn with the rect
+    /// of the texture atlas again. The rect is returned if there was one.
     ///
-    /// // Ideally you should want to set some tiles here else nothing will
-    /// // display in the render...
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, sprite::SpriteRect};
     ///
-    /// assert!(tilemap.spawn_chunk((0, 0)).is_ok());
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// // Later a frame or more on...
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert!(tilemap.despawn_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.despawn_chunk((-1, -1)).is_err());
+    /// let sprite_rect = SpriteRect::new(Vec2::new(0.0, 0.0), Vec2::new(32.0, 32.0));
+    /// tilemap.set_sprite_rect(3, sprite_rect);
+    ///
+    /// assert_eq!(tilemap.remove_sprite_rect(3), Some(sprite_rect));
+    /// assert_eq!(tilemap.sprite_rect(3), None);
     /// ```
-    pub fn despawn_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
-        let point: Point2 = point.into();
-        if let Some(dimensions) = &self.dimensions {
-            dimensions.check_point(point)?;
-        }
-
-        self.spawned.remove(&(point.x, point.y));
-
-        if let Some(chunk) = self.chunks.get_mut(&point) {
-            let entities = chunk.get_entities();
-            self.chunk_events
-                .send(TilemapChunkEvent::Despawned { entities, point })
+    pub fn remove_sprite_rect(&mut self, index: usize) -> Option<SpriteRect> {
+        let sprite_rect = self.sprite_rects.remove(&index);
+        if sprite_rect.is_some() {
+            self.modify_all_chunks();
         }
-
-        Ok(())
+        sprite_rect
     }
 
-    /// Destructively removes a chunk at a coordinate position and despawns them
-    /// if needed.
+    /// Returns a reference to the rect of a sprite index, if it has one.
     ///
-    /// Internally, this sends an event to the tilemap's system flagging which
-    /// chunks must be removed by index and entity. A chunk is not recoverable
-    /// if this action is done.
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
     ///
-    /// Does nothing if the chunk does not exist.
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// # Errors
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// If the coordinate or index is out of bounds, an error will be returned.
+    /// assert_eq!(tilemap.sprite_rect(0), None);
+    /// ```
+    pub fn sprite_rect(&self, index: usize) -> Option<&SpriteRect> {
+        self.sprite_rects.get(&index)
+    }
+
+    /// Sets the wall that the tiles of a sprite index are extruded into on
+    /// layers with walls, returning the previous one if there was one.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, wall::WallSprite};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .dimensions(3, 3)
-    ///     .tile_dimensions(32, 32)
-    ///     .finish()
-    ///     .unwrap();
-    ///
-    /// // Add some chunks.
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.insert_chunk((1, 1)).is_ok());
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert!(tilemap.remove_chunk((0, 0)).is_ok());
-    /// assert!(tilemap.remove_chunk((1, 1)).is_ok());
-    /// assert!(tilemap.remove_chunk((-2, -2)).is_err());
+    /// let wall = WallSprite::new(5, 48.0);
+    /// assert_eq!(tilemap.set_wall_sprite(4, wall), None);
+    /// assert_eq!(tilemap.wall_sprite(4), Some(&wall));
     /// ```
-    pub fn remove_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
-        let point = point.into();
-        self.despawn_chunk(point)?;
-
-        self.chunks.remove(&point);
-
-        Ok(())
+    pub fn set_wall_sprite(&mut self, index: usize, wall: WallSprite) -> Option<WallSprite> {
+        self.modify_all_chunks();
+        self.wall_sprites.insert(index, wall)
     }
 
-    /// Takes a tile point and changes it into a chunk point.
+    /// Removes the wall of a sprite index, so that its tiles are drawn flat
+    /// again. The wall is returned if there was one.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, wall::WallSprite};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// let tile_point = (15, 15);
-    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
-    /// assert_eq!((0, 0), chunk_point);
-    ///
-    /// let tile_point = (16, 16);
-    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
-    /// assert_eq!((1, 1), chunk_point);
-    ///
-    /// let tile_point = (-16, -16);
-    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
-    /// assert_eq!((-0, -0), chunk_point);
+    /// let wall = WallSprite::new(5, 48.0);
+    /// tilemap.set_wall_sprite(4, wall);
     ///
-    /// let tile_point = (-17, -17);
-    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
-    /// assert_eq!((-1, -1), chunk_point);
+    /// assert_eq!(tilemap.remove_wall_sprite(4), Some(wall));
+    /// assert_eq!(tilemap.wall_sprite(4), None);
     /// ```
-    pub fn point_to_chunk_point<P: Into<Point2>>(&self, point: P) -> (i32, i32) {
-        let point: Point2 = point.into();
-        let width = self.chunk_dimensions.width as f32;
-        let height = self.chunk_dimensions.height as f32;
-        let x = ((point.x as f32 + width / 2.0) / width).floor() as i32;
-        let y = ((point.y as f32 + height / 2.0) / height).floor() as i32;
-        (x, y)
+    pub fn remove_wall_sprite(&mut self, index: usize) -> Option<WallSprite> {
+        let wall = self.wall_sprites.remove(&index);
+        if wall.is_some() {
+            self.modify_all_chunks();
+        }
+        wall
     }
 
-    /// Sorts tiles into the chunks they belong to.
-    fn sort_tiles_to_chunks<P, I>(
-        &mut self,
-        tiles: I,
-    ) -> TilemapResult<HashMap<Point2, Vec<Tile<Point2>>>>
-    where
-        P: Into<Point2>,
-        I: IntoIterator<Item = Tile<P>>,
-    {
-        let width = self.chunk_dimensions.width as i32;
-        let height = self.chunk_dimensions.height as i32;
+    /// Returns a reference to the wall of a sprite index, if it has one.
+    pub fn wall_sprite(&self, index: usize) -> Option<&WallSprite> {
+        self.wall_sprites.get(&index)
+    }
 
-        let mut chunk_map: HashMap<Point2, Vec<Tile<Point2>>> = HashMap::default();
-        for tile in tiles.into_iter() {
-            let global_tile_point: Point2 = tile.point.into();
-            let chunk_point: Point2 = self.point_to_chunk_point(global_tile_point).into();
+    /// Sets if the sprite of a sprite index is opaque and covers its whole
+    /// tile, so the tiles below its tiles are not drawn.
+    ///
+    /// A tile is hidden by an opaque tile at the same point on a layer above,
+    /// which leaves it out of the mesh of its layer. On maps with many stacked
+    /// layers this can halve the vertices which are drawn. Only tiles without
+    /// an offset and opaque tiles which are not tinted translucent are
+    /// counted. Layers which are decorations, masks, picked, y sorted or
+    /// animated, as well as sprites with their own rect, neither hide nor are
+    /// hidden. Chunks are built again as their tiles change but not as the
+    /// opacity of the tilemap or a layer does, so layers which fade should
+    /// not be given opaque sprites.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.set_opaque_sprite(2, true);
+    /// assert!(tilemap.is_opaque_sprite(2));
+    ///
+    /// // A stone floor over grass, with a hole in it.
+    /// let grass = (-2..2).flat_map(|x| (-2..2).map(move |y| Tile { point: (x, y), sprite_index: 1, ..Default::default() }));
+    /// let stone = (-2..2).flat_map(|x| (-2..2).map(move |y| Tile { point: (x, y), sprite_index: 2, z_order: 1, ..Default::default() }));
+    /// tilemap.insert_tiles(grass.chain(stone)).unwrap();
+    /// tilemap.clear_tile((0, 0), 1).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// // Only the grass in the hole is drawn.
+    /// assert_eq!(harness.chunk_quads(entity, (0, 0), 0), Some(1));
+    /// assert_eq!(harness.chunk_quads(entity, (0, 0), 1), Some(15));
+    /// ```
+    pub fn set_opaque_sprite(&mut self, index: usize, opaque: bool) {
+        let changed = if opaque {
+            self.opaque_sprites.insert(index)
+        } else {
+            self.opaque_sprites.remove(&index)
+        };
+        if changed {
+            self.modify_all_chunks();
+        }
+    }
 
-            if let Some(layer) = self.layers.get(tile.z_order as usize) {
-                if layer.as_ref().is_none() {
-                    self.add_layer(TilemapLayer::default(), tile.z_order as usize)?;
-                }
-            } else {
-                return Err(ErrorKind::LayerDoesNotExist(tile.z_order).into());
-            }
+    /// Returns if the sprite of a sprite index is opaque and hides the tiles
+    /// below its tiles.
+    pub fn is_opaque_sprite(&self, index: usize) -> bool {
+        self.opaque_sprites.contains(&index)
+    }
 
-            let tile_point = Point2::new(
-                global_tile_point.x - (width * chunk_point.x) + (width / 2),
-                global_tile_point.y - (height * chunk_point.y) + (height / 2),
-            );
+    /// Returns `true` if any sprites are opaque, so the layers of a chunk are
+    /// built again together when any of them changes.
+    #[cfg(feature = "render")]
+    pub(crate) fn has_opaque_sprites(&self) -> bool {
+        !self.opaque_sprites.is_empty()
+    }
 
-            let chunk_tile: Tile<Point2> = Tile {
-                point: tile_point,
-                z_order: tile.z_order,
-                sprite_index: tile.sprite_index,
-                tint: tile.tint,
-            };
-            if let Some(tiles) = chunk_map.get_mut(&chunk_point) {
-                tiles.push(chunk_tile);
-            } else {
-                let tiles = vec![chunk_tile];
-                chunk_map.insert(chunk_point, tiles);
+    /// Returns the layers above a layer whose opaque tiles hide its tiles, or
+    /// `None` if none of its tiles can be hidden.
+    #[cfg(feature = "render")]
+    pub(crate) fn layer_occlusion(&self, z_order: usize) -> Option<TileOcclusion> {
+        let is_flat = |layer: &TilemapLayer| {
+            (layer.kind == LayerKind::Dense || layer.kind == LayerKind::Sparse)
+                && !layer.is_y_sorted()
+                && layer.shader_animation.is_none()
+        };
+        match self.layers.get(z_order) {
+            Some(Some(layer)) if is_flat(layer) && !layer.mask && !layer.picking => {}
+            _ => return None,
+        }
+        let sprites: HashSet<usize> = self
+            .opaque_sprites
+            .iter()
+            .filter(|index| !self.sprite_rects.contains_key(index))
+            .copied()
+            .collect();
+        let layers: Vec<usize> = self
+            .layers
+            .iter()
+            .enumerate()
+            .skip(z_order + 1)
+            .filter(|(_, layer)| {
+                layer
+                    .filter(|layer| {
+                        let alpha = self.color.a() * layer.color.a() * self.opacity;
+                        is_flat(layer) && alpha >= 1.0
+                    })
+                    .is_some()
+            })
+            .map(|(z_order, _)| z_order)
+            .collect();
+        if sprites.is_empty() || layers.is_empty() {
+            return None;
+        }
+        Some(TileOcclusion { layers, sprites })
+    }
+
+    /// Marks every chunk as modified, so that all of their meshes are rebuilt.
+    fn modify_all_chunks(&mut self) {
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (chunk_point, chunk) in chunks.into_iter() {
+            let mut layers = HashMap::default();
+            for z_order in 0..self.layers.len() {
+                if let Some(entity) = chunk.get_entity(z_order) {
+                    layers.insert(z_order, entity);
+                }
             }
+            self.modified_chunks.insert(*chunk_point);
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
         }
-        Ok(chunk_map)
     }
 
-    /// Sets many tiles, creating new chunks if needed.
-    ///
-    /// If setting a single tile is more preferable, then use the [`insert_tile`]
-    /// method instead.
-    ///
-    /// If the chunk does not yet exist, it will create a new one automatically.
-    ///
-    /// # Errors
+    /// Constructs a new chunk and stores it at a coordinate position.
     ///
-    /// Returns an error if the given coordinate or index is out of bounds, the
-    /// layer or chunk does not exist. If either the layer or chunk error occurs
-    /// then creating what is missing will resolve it.
+    /// It requires that you give it either a point. It then automatically sets
+    /// both a sized mesh and chunk for use based on the parameters set in the
+    /// parent tilemap.
     ///
     /// # Examples
-    ///
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_render::prelude::*;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::prelude::*;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .dimensions(1, 1)
+    ///     .dimensions(3, 3)
     ///     .tile_dimensions(32, 32)
     ///     .finish()
     ///     .unwrap();
     ///
-    /// tilemap.insert_chunk((0, 0)).unwrap();
-    ///
-    /// let mut tiles = vec![
-    ///     Tile { point: (1, 1), sprite_index: 0, ..Default::default() },
-    ///     Tile { point: (2, 2), sprite_index: 1, ..Default::default() },
-    ///     Tile { point: (3, 3), sprite_index: 2, ..Default::default() },
-    /// ];
-    ///
-    /// // Set multiple tiles and unwrap the result
-    /// tilemap.insert_tiles(tiles).unwrap();
+    /// // Add some chunks.
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.insert_chunk((1, 1)).is_ok());
+    /// assert!(tilemap.insert_chunk((-2, -2)).is_err());
     ///
-    /// assert_eq!(tilemap.get_tile((1, 1), 0), Some(&RawTile { index: 0, color: Color::WHITE }));
-    /// assert_eq!(tilemap.get_tile((2, 2), 0), Some(&RawTile { index: 1, color: Color::WHITE }));
-    /// assert_eq!(tilemap.get_tile((3, 3), 0), Some(&RawTile { index: 2, color: Color::WHITE }));
-    /// assert_eq!(tilemap.get_tile((4, 4), 0), None);
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert!(tilemap.contains_chunk((1, 1)));
+    /// assert!(!tilemap.contains_chunk((-2, -2)));
     /// ```
+    /// # Errors
     ///
-    /// [`insert_tile`]: Tilemap::insert_tile
-    pub fn insert_tiles<P, I>(&mut self, tiles: I) -> TilemapResult<()>
-    where
-        P: Into<Point2>,
-        I: IntoIterator<Item = Tile<P>>,
-    {
-        let chunk_map = self.sort_tiles_to_chunks(tiles)?;
-        for (chunk_point, tiles) in chunk_map.into_iter() {
-            // Is there a better way to do this? Clippy hates if I don't do it
-            // like this talking about constructing regardless yet, here it is,
-            // copying stuff regardless because it doesn't like self in the
-            // `FnOnce`.
-            let layers = self.layers.clone();
-            let chunk_dimensions = self.chunk_dimensions;
-            let chunk = if self.auto_flags.contains(AutoFlags::AUTO_CHUNK) {
-                self.chunks.entry(chunk_point).or_insert_with(|| {
-                    let layer_kinds = layers
-                        .iter()
-                        .map(|x| x.and_then(|y| Some(y.kind)))
-                        .collect::<Vec<Option<LayerKind>>>();
-                    Chunk::new(chunk_point, &layer_kinds, chunk_dimensions)
-                })
-            } else {
-                match self.chunks.get_mut(&chunk_point) {
-                    Some(c) => c,
-                    None => return Err(ErrorKind::MissingChunk.into()),
-                }
-            };
-
-            let mut layers = HashMap::default();
-            for tile in tiles.iter() {
-                let index = self.chunk_dimensions.encode_point_unchecked(tile.point);
-                // TODO: Tile collider must be added to the chunk.
-                chunk.set_tile(index, *tile);
-                if let Some(entity) = chunk.get_entity(tile.z_order) {
-                    layers.entry(tile.z_order).or_insert(entity);
+    /// If the point does not exist in the tilemap, an error is returned. This
+    /// can only be returned if you had set the dimensions on the tilemap.
+    ///
+    /// Also will return an error if the chunk already exists. If this happens
+    /// and was intentional, it is best to remove the chunk first. This is
+    /// simply a fail safe without actually returning the chunk as it is meant
+    /// to be kept internal.
+    pub fn insert_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if let Some(dimensions) = &self.dimensions {
+            dimensions.check_point(point)?;
+        }
+        #[cfg(feature = "serialize")]
+        if self.unloaded_chunks.contains(&point) {
+            return Err(ErrorKind::ChunkAlreadyExists(point).into());
+        }
+        let layer_kinds = self.chunk_layer_kinds(point);
+        let mut chunk = Chunk::new(point, &layer_kinds, self.chunk_dimensions);
+        let area = self.chunk_dimensions.area() as usize;
+        for (z_order, empty_tile) in self.empty_tiles() {
+            chunk.fill_empty_tiles(z_order, empty_tile, area);
+        }
+        match self.chunks.insert(point, Arc::new(chunk)) {
+            Some(_) => Err(ErrorKind::ChunkAlreadyExists(point).into()),
+            None => {
+                if let Some(changes) = &mut self.changes {
+                    changes.insert_chunk(point);
                 }
+                Ok(())
             }
-
-            self.chunk_events
-                .send(TilemapChunkEvent::Modified { layers });
-            #[cfg(feature = "bevy_rapier2d")]
-            self.collision_events
-                .send(TilemapCollisionEvent::Spawned { chunk_point, tiles });
         }
-
-        Ok(())
     }
 
-    /// Sets a single tile at a coordinate position, creating a chunk if necessary.
-    ///
-    /// If you are setting more than one tile at a time, it is highly
-    /// recommended not to run this method! If that is preferred, do use
-    /// [`insert_tiles`] instead. Every single tile that is created creates a new
-    /// event. With bulk tiles, it creates 1 event for all.
-    ///
-    /// If the chunk does not yet exist, it will create a new one automatically.
-    ///
-    /// [`insert_tiles`]: Tilemap::insert_tiles
+    /// Returns `true` if the chunk is included in the tilemap, even if it was
+    /// written to the chunk store.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_render::prelude::*;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::prelude::*;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// tilemap.insert_chunk((0, 0)).unwrap();
-    ///
-    /// let point = (9, 3);
-    /// let sprite_index = 3;
-    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert!(!tilemap.contains_chunk((1, 1)));
+    /// ```
+    pub fn contains_chunk<P: Into<Point2>>(&self, point: P) -> bool {
+        let point: Point2 = point.into();
+        #[cfg(feature = "serialize")]
+        if self.unloaded_chunks.contains(&point) {
+            return true;
+        }
+        self.chunks.contains_key(&point)
+    }
+
+    /// Returns `true` if the chunk is spawned, not counting chunks which are
+    /// still queued to be spawned.
     ///
-    /// assert!(tilemap.insert_tile(tile).is_ok());
-    /// assert_eq!(tilemap.get_tile((9, 3), 0), Some(&RawTile { index: 3, color: Color::WHITE }))
+    /// # Examples
     /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
     ///
-    /// # Errors
+    /// let mut harness = tilemap_test_harness();
     ///
-    /// Returns an error if the given coordinate or index is out of bounds.
-    pub fn insert_tile<P: Into<Point2>>(&mut self, tile: Tile<P>) -> TilemapResult<()> {
-        let tiles = vec![tile];
-        self.insert_tiles(tiles)
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// assert!(!tilemap.is_chunk_spawned((0, 0)));
+    ///
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// assert!(harness.tilemap(entity).unwrap().is_chunk_spawned((0, 0)));
+    /// ```
+    pub fn is_chunk_spawned<P: Into<Point2>>(&self, point: P) -> bool {
+        let point: Point2 = point.into();
+        self.spawned.contains(&(point.x, point.y))
     }
 
-    /// Clears the tiles at the specified points from the tilemap.
+    /// Returns an iterator of the points of every chunk of the tilemap,
+    /// including those which were written to the chunk store, in no
+    /// particular order.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_render::prelude::*;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap_types::point::Point2;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_chunk((-1, 2)).unwrap();
     ///
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    /// let mut points: Vec<Point2> = tilemap.chunk_points().collect();
+    /// points.sort();
+    /// assert_eq!(points, vec![Point2::new(-1, 2), Point2::new(0, 0)]);
+    /// ```
+    pub fn chunk_points(&self) -> impl Iterator<Item = Point2> + '_ {
+        let points = self.chunks.keys().copied();
+        #[cfg(feature = "serialize")]
+        let points = points.chain(self.unloaded_chunks.iter().copied());
+        points
+    }
+
+    /// Registers the chunk at a point as a template, returning its handle.
     ///
-    /// let mut tiles = vec![
-    ///     Tile { point: (1, 1), ..Default::default() },
-    ///     Tile { point: (2, 2), ..Default::default() },
-    ///     Tile { point: (3, 3), ..Default::default() },
-    /// ];
+    /// The template is a copy of the tiles of every layer of the chunk, so the
+    /// chunk can be changed or removed afterwards. Entities, meshes and
+    /// colliders are not copied.
     ///
-    /// // Set multiple tiles and unwrap the result
-    /// assert!(tilemap.insert_tiles(tiles.clone()).is_ok());
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
     ///
-    /// // Then later on... Do note that if this done in the same frame, the
-    /// // tiles will not even exist at all.
-    /// let mut to_remove = vec![
-    ///     ((1, 1), 0),
-    ///     ((2, 2), 0),
-    /// ];
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// tilemap.clear_tiles(to_remove).unwrap();
-    /// assert_eq!(tilemap.get_tile((1, 1), 0), None);
-    /// assert_eq!(tilemap.get_tile((2, 2), 0), None);
-    /// assert_eq!(tilemap.get_tile((3, 3), 0), Some(&RawTile { index: 0, color: Color::WHITE} ));
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 3, ..Default::default() }).unwrap();
+    ///
+    /// let template = tilemap.add_chunk_template((0, 0)).unwrap();
+    /// tilemap.clear_tile((0, 0), 0).unwrap();
+    /// tilemap.insert_chunk_from_template((1, 0), template).unwrap();
+    ///
+    /// assert!(tilemap.get_tile((0, 0), 0).is_none());
+    /// assert_eq!(tilemap.get_tile((32, 0), 0).unwrap().index, 3);
+    /// assert!(tilemap.add_chunk_template((5, 5)).is_err());
     /// ```
     ///
     /// # Errors
     ///
-    /// An error can occure if the point is outside of the tilemap. This can
-    /// only happen if the tilemap has dimensions.
-    pub fn clear_tiles<P, I>(&mut self, points: I) -> TilemapResult<()>
-    where
-        P: Into<Point2>,
-        I: IntoIterator<Item = (P, usize)>,
-    {
-        let mut tiles = Vec::new();
-        for (point, z_order) in points {
-            tiles.push(Tile {
-                point: point.into(),
-                sprite_index: 0,
-                z_order,
-                tint: Color::rgba(0.0, 0.0, 0.0, 0.0),
-            });
+    /// Returns an error if the chunk does not exist.
+    pub fn add_chunk_template<P: Into<Point2>>(
+        &mut self,
+        point: P,
+    ) -> TilemapResult<ChunkTemplateHandle> {
+        let point: Point2 = point.into();
+        #[cfg(feature = "serialize")]
+        self.load_chunk(point)?;
+        let mut template = match self.chunks.get(&point) {
+            Some(chunk) => Chunk::clone(chunk),
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        template.copy_spawned_state(None);
+        self.chunk_templates.push(Arc::new(template));
+        Ok(ChunkTemplateHandle(self.chunk_templates.len() - 1))
+    }
+
+    /// Inserts a chunk at a point which is a copy of a chunk template.
+    ///
+    /// Until the chunk is changed, its layers are drawn with the meshes of the
+    /// template, which are built once for every copy, see
+    /// [`chunk_instance`].
+    ///
+    /// [`chunk_instance`]: Tilemap::chunk_instance
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 5, ..Default::default() }).unwrap();
+    /// let template = tilemap.add_chunk_template((0, 0)).unwrap();
+    ///
+    /// assert!(tilemap.insert_chunk_from_template((0, 1), template).is_ok());
+    /// assert_eq!(tilemap.get_tile((1, 5), 0).unwrap().index, 5);
+    ///
+    /// // The chunk already exists.
+    /// assert!(tilemap.insert_chunk_from_template((0, 1), template).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template does not exist, if the chunk already
+    /// exists or if the point is outside of the dimensions of the tilemap.
+    pub fn insert_chunk_from_template<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        template: ChunkTemplateHandle,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if let Some(dimensions) = &self.dimensions {
+            dimensions.check_point(point)?;
+        }
+        #[cfg(feature = "serialize")]
+        if self.unloaded_chunks.contains(&point) {
+            return Err(ErrorKind::ChunkAlreadyExists(point).into());
+        }
+        if self.chunks.contains_key(&point) {
+            return Err(ErrorKind::ChunkAlreadyExists(point).into());
+        }
+        let mut chunk = match self.chunk_templates.get(template.0) {
+            Some(template) => Chunk::clone(template),
+            None => return Err(ErrorKind::MissingChunkTemplate.into()),
+        };
+        chunk.set_point(point);
+
+        let chunk_dimensions = self.chunk_dimensions;
+        let tiles = chunk.tiles(chunk_dimensions);
+        self.chunks.insert(point, Arc::new(chunk));
+        if self.changes.is_some() {
+            for tile in tiles.iter() {
+                let tile_point = tile_point_to_point(chunk_dimensions, point, tile.point);
+                self.changed_tiles.insert((tile_point, tile.z_order));
+            }
+        }
+        if let Some(changes) = &mut self.changes {
+            changes.insert_chunk(point);
+        }
+        // Changes to a chunk which was removed from the point before are
+        // not changes to the copy.
+        self.modified_chunks.remove(&point);
+        self.chunk_instances.insert(point, template);
+        for navmesh in self.navmeshes.values_mut() {
+            navmesh.mark_dirty(point);
+        }
+        self.collision_events.send(TilemapCollisionEvent::Spawned {
+            chunk_point: point,
+            tiles,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the template which the chunk at a point is a copy of, if it
+    /// was inserted from one and has not been changed since.
+    ///
+    /// Once spawned, such chunks share the meshes of their template, so
+    /// filler chunks such as oceans are built once however many of them are
+    /// drawn. Only layers whose meshes do not depend on where the chunk is
+    /// are shared, which are those that are not y sorted, masks or picked, on
+    /// tilemaps that are not iso. The chunks of tilemap assets always have
+    /// their own meshes, which every instance of the asset shares.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::prelude::*;
+    /// use bevy_render::mesh::Mesh;
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    /// use bevy_transform::prelude::*;
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { picking: true, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// let water = (-2..2).flat_map(|x| (-2..2).map(move |y| Tile { point: (x, y), sprite_index: 1, ..Default::default() }));
+    /// tilemap.insert_tiles(water).unwrap();
+    /// let ocean = tilemap.add_chunk_template((0, 0)).unwrap();
+    /// tilemap.remove_chunk((0, 0)).unwrap();
+    /// for x in 0..3 {
+    ///     tilemap.insert_chunk_from_template((x, 0), ocean).unwrap();
+    ///     tilemap.spawn_chunk((x, 0)).unwrap();
+    /// }
+    /// assert_eq!(tilemap.chunk_instance((1, 0)), Some(ocean));
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// let mesh_of = |harness: &mut bevy_tilemap::testing::TilemapTestHarness, x: i32| {
+    ///     let layer = harness.chunk_entities(entity, (x, 0))[0];
+    ///     harness.app().world.get::<Handle<Mesh>>(layer).unwrap().clone_weak()
+    /// };
+    /// assert_eq!(mesh_of(&mut harness, 0), mesh_of(&mut harness, 2));
+    ///
+    /// // An island is raised in the middle chunk, which then has its own mesh.
+    /// harness
+    ///     .tilemap_mut(entity)
+    ///     .unwrap()
+    ///     .insert_tile(Tile { point: (4, 0), sprite_index: 2, ..Default::default() })
+    ///     .unwrap();
+    /// harness.tick();
+    ///
+    /// assert_eq!(harness.tilemap(entity).unwrap().chunk_instance((1, 0)), None);
+    /// assert_ne!(mesh_of(&mut harness, 1), mesh_of(&mut harness, 0));
+    /// assert_eq!(harness.chunk_tile_indexes(entity, (1, 0), 0).unwrap()[10], 2);
+    /// assert_eq!(harness.chunk_tile_indexes(entity, (0, 0), 0).unwrap()[10], 1);
+    ///
+    /// // The picking pass of a layer draws the mesh of its layer.
+    /// let layer = harness.chunk_entities(entity, (1, 0))[1];
+    /// let world = &harness.app().world;
+    /// let mesh = world.get::<Handle<Mesh>>(layer).unwrap().clone_weak();
+    /// let passes = world.get::<Children>(layer).unwrap();
+    /// assert!(!passes.is_empty());
+    /// for pass in passes.iter() {
+    ///     assert_eq!(*world.get::<Handle<Mesh>>(*pass).unwrap(), mesh);
+    /// }
+    /// ```
+    pub fn chunk_instance<P: Into<Point2>>(&self, point: P) -> Option<ChunkTemplateHandle> {
+        self.chunk_instances.get(&point.into()).copied()
+    }
+
+    /// Removes the template which the chunk at a point is a copy of, after the
+    /// chunk was changed.
+    pub(crate) fn remove_chunk_instance(&mut self, point: Point2) -> Option<ChunkTemplateHandle> {
+        self.chunk_instances.remove(&point)
+    }
+
+    /// Returns the shared mesh of a layer of a chunk template, if a copy of
+    /// the template was spawned.
+    #[cfg(feature = "render")]
+    pub(crate) fn template_mesh(
+        &self,
+        template: ChunkTemplateHandle,
+        z_order: usize,
+    ) -> Option<&Handle<Mesh>> {
+        self.template_meshes.get(&(template, z_order))
+    }
+
+    /// Sets the shared mesh of a layer of a chunk template.
+    #[cfg(feature = "render")]
+    pub(crate) fn set_template_mesh(
+        &mut self,
+        template: ChunkTemplateHandle,
+        z_order: usize,
+        mesh: Handle<Mesh>,
+    ) {
+        self.template_meshes.insert((template, z_order), mesh);
+    }
+
+    #[deprecated(
+        since = "0.4.0",
+        note = "Please use `add_layer` method instead with the `TilemapLayer` struct"
+    )]
+    #[doc(hidden)]
+    pub fn add_layer_with_kind(&mut self, kind: LayerKind, z_order: usize) -> TilemapResult<()> {
+        let layer = TilemapLayer {
+            kind,
+            mask: false,
+            picking: false,
+            #[cfg(feature = "bevy_rapier2d")]
+            interaction_groups: InteractionGroups::default(),
+            #[cfg(feature = "bevy_rapier2d")]
+            solver_groups: InteractionGroups::all(),
+            one_way: false,
+            sensor: false,
+            y_sort: false,
+            walls: false,
+            color: Color::WHITE,
+            shader_animation: None,
+            empty_tile: None,
+            depth: None,
+            hidden: false,
+            #[cfg(feature = "render")]
+            render_layers: None,
+        };
+        if z_order >= self.layers.len() {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        if let Some(some_kind) = self.layers.get_mut(z_order) {
+            if some_kind.is_some() {
+                return Err(ErrorKind::LayerExists(z_order).into());
+            }
+            *some_kind = Some(layer);
+        }
+
+        let chunks = self
+            .chunks
+            .values_mut()
+            .chain(self.chunk_templates.iter_mut());
+        for chunk in chunks.map(Arc::make_mut) {
+            chunk.add_layer(&kind, z_order, self.chunk_dimensions);
+        }
+        #[cfg(feature = "render")]
+        self.template_meshes.clear();
+
+        Ok(())
+    }
+
+    /// Adds a layer to the tilemap.
+    ///
+    /// This method creates a layer across all chunks at the specified Z layer.
+    /// For ease of use, it by default makes a layer with a dense
+    /// [`LayerKind`] which is ideal for layers full of sprites.
+    ///
+    /// If you want to use a layer that is more performant and less data heavy,
+    /// use [`add_layer_with_kind`] with [`LayerKind::Sparse`].
+    ///
+    /// If the layer is already the specified layer's kind, then nothing
+    /// happens.
+    ///
+    /// # Errors
+    ///
+    /// If a layer is set and a different layer already exists at that Z layer
+    /// then an error is returned regarding that. This is done to prevent
+    /// accidental overwrites of a layer.
+    /// An error is also returned if the z order is not below the depth of the
+    /// tilemap, see [`Tilemap::set_z_layers`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let layer = TilemapLayer {
+    ///    kind: LayerKind::Sparse,
+    ///    ..Default::default()
+    /// };
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert!(tilemap.add_layer(layer, 1).is_ok());
+    /// assert!(tilemap.add_layer(layer, 1).is_err());
+    /// ```
+    ///
+    /// [`add_layer_with_kind`]: Tilemap::add_layer_with_kind
+    /// [`LayerKind`]: crate::chunk::LayerKind
+    /// [`LayerKind::Sparse`]: crate::chunk::LayerKind::Sparse
+    pub fn add_layer(&mut self, layer: TilemapLayer, z_order: usize) -> TilemapResult<()> {
+        if z_order >= self.layers.len() {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        if let Some(inner_layer) = self.layers.get_mut(z_order) {
+            if inner_layer.is_some() {
+                return Err(ErrorKind::LayerExists(z_order).into());
+            }
+            *inner_layer = Some(layer);
+        }
+
+        let chunks = self
+            .chunks
+            .values_mut()
+            .chain(self.chunk_templates.iter_mut());
+        let area = self.chunk_dimensions.area() as usize;
+        for chunk in chunks.map(Arc::make_mut) {
+            chunk.add_layer(&layer.kind, z_order, self.chunk_dimensions);
+            if let Some(empty_tile) = layer.empty_tile {
+                chunk.fill_empty_tiles(z_order, empty_tile, area);
+            }
+        }
+        #[cfg(feature = "render")]
+        self.template_meshes.clear();
+
+        Ok(())
+    }
+
+    /// Moves a layer from one Z level to another.
+    ///
+    /// # Errors
+    ///
+    /// If the destination exists, it will throw an error. Likewise, if the
+    /// origin does not exist, it also will throw an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .z_layers(3)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Dense, ..Default::default() }, 0)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 3)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // If we moved this to layer 3, it would instead fail.
+    /// assert!(tilemap.move_layer(0, 2).is_ok());
+    /// assert!(tilemap.move_layer(3, 2).is_err());
+    /// ```
+    pub fn move_layer(&mut self, from_z: usize, to_z: usize) -> TilemapResult<()> {
+        match self.layers.get(from_z) {
+            Some(Some(_)) => {}
+            _ => return Err(ErrorKind::LayerDoesNotExist(from_z).into()),
+        }
+        match self.layers.get(to_z) {
+            Some(None) => {}
+            Some(Some(_)) => return Err(ErrorKind::LayerExists(to_z).into()),
+            None => return Err(ErrorKind::LayerDoesNotExist(to_z).into()),
+        }
+
+        self.swap_layer_z_orders(from_z, to_z)
+    }
+
+    /// Swaps the layers at two z orders, with their tiles, channels, rules
+    /// and everything else kept by z order, so the layer which was drawn
+    /// below is drawn above.
+    ///
+    /// The spawned chunks are spawned again, so their entities are placed at
+    /// the z orders of their layers. Chunks which were unloaded to the chunk
+    /// store have their layers swapped once they are loaded.
+    ///
+    /// # Errors
+    ///
+    /// If either layer does not exist, or is one of the decal layers, an
+    /// error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Dense, ..Default::default() }, 0)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 2, z_order: 1, ..Default::default() }).unwrap();
+    ///
+    /// // The roofs are drawn below the floors.
+    /// tilemap.swap_layers(0, 1).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 2);
+    /// assert_eq!(tilemap.get_tile((0, 0), 1).unwrap().index, 1);
+    /// assert!(tilemap.swap_layers(0, 2).is_err());
+    /// ```
+    pub fn swap_layers(&mut self, a: usize, b: usize) -> TilemapResult<()> {
+        for z_order in [a, b].iter().copied() {
+            match self.layers.get(z_order) {
+                Some(Some(_)) => {}
+                _ => return Err(ErrorKind::LayerDoesNotExist(z_order).into()),
+            }
+        }
+        if a == b {
+            return Ok(());
+        }
+
+        self.swap_layer_z_orders(a, b)
+    }
+
+    /// Swaps the layers at two z orders, or moves a layer to a z order
+    /// without one, along with everything the tilemap keeps by z order, and
+    /// spawns the spawned chunks again.
+    fn swap_layer_z_orders(&mut self, a: usize, b: usize) -> TilemapResult<()> {
+        if let Some(policy) = &self.decal_policy {
+            let decal_layers = policy.z_orders();
+            if let Some(z_order) = [a, b].iter().copied().find(|z| decal_layers.contains(z)) {
+                return Err(ErrorKind::DecalLayer(z_order).into());
+            }
+        }
+
+        #[cfg(feature = "serialize")]
+        for point in self.unloaded_chunks.iter() {
+            let permutation = self.layer_permutations.entry(*point).or_default();
+            let count = permutation.len().max(a.max(b) + 1);
+            for z_order in permutation.len()..count {
+                permutation.push(z_order);
+            }
+            permutation.swap(a, b);
+        }
+
+        self.layers.swap(a, b);
+        let chunks = self
+            .chunks
+            .values_mut()
+            .chain(self.chunk_templates.iter_mut());
+        for chunk in chunks.map(Arc::make_mut) {
+            chunk.swap_layers(a, b);
+        }
+        #[cfg(feature = "render")]
+        self.template_meshes.clear();
+        swap_z_orders(&mut self.channels, a, b);
+        swap_z_orders(&mut self.layer_fades, a, b);
+        swap_z_orders(&mut self.tile_rules, a, b);
+        swap_z_orders(&mut self.navmeshes, a, b);
+        for (z_order, navmesh) in self.navmeshes.iter_mut() {
+            navmesh.set_z_order(*z_order);
+        }
+        swap_tile_z_orders(&mut self.layer_kind_overrides, a, b);
+        swap_tile_z_orders(&mut self.animated_tiles, a, b);
+        swap_tile_z_orders(&mut self.tile_damage, a, b);
+        swap_tile_z_orders(&mut self.tile_entities, a, b);
+        self.static_layers = self
+            .static_layers
+            .drain()
+            .map(|z_order| swapped_z_order(z_order, a, b))
+            .collect();
+        if let Some(highlight_layer) = &mut self.highlight_layer {
+            highlight_layer.z_order = swapped_z_order(highlight_layer.z_order, a, b);
+        }
+
+        self.respawn_all_chunks()
+    }
+
+    /// Sets the interaction and solver groups of a layer, see
+    /// [`TilemapLayer::interaction_groups`] and
+    /// [`TilemapLayer::solver_groups`]. The collisions of the layer in the
+    /// spawned chunks are spawned again.
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_rapier2d::rapier::geometry::InteractionGroups;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.add_layer(TilemapLayer::default(), 1).unwrap();
+    ///
+    /// // Water is in the second group and only collides with boats in the
+    /// // third group.
+    /// let water = InteractionGroups::new(0b0010, 0b0100);
+    ///
+    /// assert!(tilemap.set_layer_interaction_groups(1, water, InteractionGroups::all()).is_ok());
+    /// assert!(tilemap.set_layer_interaction_groups(2, water, InteractionGroups::all()).is_err());
+    /// ```
+    #[cfg(feature = "bevy_rapier2d")]
+    pub fn set_layer_interaction_groups(
+        &mut self,
+        z_order: usize,
+        interaction_groups: InteractionGroups,
+        solver_groups: InteractionGroups,
+    ) -> TilemapResult<()> {
+        let layer = if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer
+        } else {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        };
+        layer.interaction_groups = interaction_groups;
+        layer.solver_groups = solver_groups;
+
+        self.respawn_collisions(|tile_z_order, _| tile_z_order == z_order);
+
+        Ok(())
+    }
+
+    /// Sets the color which every tile of a layer is multiplied by when it is
+    /// drawn, like [`TilemapLayer::color`].
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::color::Color;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// // Everything on the layer of the sea floor is seen through water.
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    /// tilemap.set_layer_color(1, Color::rgb(0.4, 0.7, 1.0)).unwrap();
+    ///
+    /// assert_eq!(tilemap.layer_color(1), Some(Color::rgb(0.4, 0.7, 1.0)));
+    /// assert!(tilemap.set_layer_color(2, Color::WHITE).is_err());
+    /// ```
+    pub fn set_layer_color(&mut self, z_order: usize, color: Color) -> TilemapResult<()> {
+        if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer.color = color;
+            Ok(())
+        } else {
+            Err(ErrorKind::LayerDoesNotExist(z_order).into())
+        }
+    }
+
+    /// Returns the color which every tile of a layer is multiplied by, or
+    /// `None` if the layer does not exist.
+    pub fn layer_color(&self, z_order: usize) -> Option<Color> {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => Some(layer.color),
+            _ => None,
+        }
+    }
+
+    /// Sets the animation which the shader plays on every tile of a layer,
+    /// like [`TilemapLayer::shader_animation`], or stops it with `None`.
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::ShaderAnimation, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// // Lava flows slowly to the right.
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    /// let flow = ShaderAnimation::Scroll { x: 4.0, y: 0.0 };
+    /// tilemap.set_layer_shader_animation(1, Some(flow)).unwrap();
+    ///
+    /// assert_eq!(tilemap.layer_shader_animation(1), Some(flow));
+    /// assert!(tilemap.set_layer_shader_animation(2, None).is_err());
+    /// ```
+    pub fn set_layer_shader_animation(
+        &mut self,
+        z_order: usize,
+        shader_animation: Option<ShaderAnimation>,
+    ) -> TilemapResult<()> {
+        if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer.shader_animation = shader_animation;
+            Ok(())
+        } else {
+            Err(ErrorKind::LayerDoesNotExist(z_order).into())
+        }
+    }
+
+    /// Returns the animation which the shader plays on every tile of a layer,
+    /// or `None` if there is none or the layer does not exist.
+    pub fn layer_shader_animation(&self, z_order: usize) -> Option<ShaderAnimation> {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.shader_animation,
+            _ => None,
+        }
+    }
+
+    /// Returns the number of z orders that layers can be added at, which is
+    /// the depth of the tilemap.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .z_layers(3)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// assert_eq!(tilemap.z_layers(), 3);
+    ///
+    /// // Tiles can not be inserted above the depth until it is grown.
+    /// let tile = Tile { point: (0, 0), sprite_index: 1, z_order: 7, ..Default::default() };
+    /// assert!(tilemap.insert_tile(tile).is_err());
+    /// tilemap.set_z_layers(8).unwrap();
+    /// tilemap.insert_tile(tile).unwrap();
+    /// assert_eq!(tilemap.get_tile((0, 0), 7).unwrap().index, 1);
+    /// ```
+    pub fn z_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Sets the number of z orders that layers can be added at, in the
+    /// tilemap and in every chunk.
+    ///
+    /// Layers can only be added, and tiles inserted, below the depth.
+    ///
+    /// # Errors
+    ///
+    /// If a layer exists at a z order which would be dropped, an error is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    ///
+    /// assert!(tilemap.set_z_layers(2).is_ok());
+    /// assert!(tilemap.set_z_layers(1).is_err());
+    /// assert_eq!(tilemap.z_layers(), 2);
+    /// ```
+    pub fn set_z_layers(&mut self, count: usize) -> TilemapResult<()> {
+        if let Some(z_order) =
+            (count..self.layers.len()).find(|z| self.layers.get(*z).is_some_and(Option::is_some))
+        {
+            return Err(ErrorKind::LayerExists(z_order).into());
+        }
+        self.resize_z_layers(count);
+
+        Ok(())
+    }
+
+    /// Resizes the z orders of the tilemap and of every chunk in memory.
+    /// Chunks which are unloaded are resized once they are loaded.
+    fn resize_z_layers(&mut self, count: usize) {
+        self.layers.resize(count, None);
+        let chunks = self
+            .chunks
+            .values_mut()
+            .chain(self.chunk_templates.iter_mut());
+        for chunk in chunks.map(Arc::make_mut) {
+            chunk.set_z_layers(count);
+        }
+    }
+
+    /// Sets the z translation of the chunk entities of a layer, which
+    /// decides the order it is drawn in, like [`TilemapLayer::depth`]. With
+    /// `None` the layer is drawn at its z order.
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    /// assert_eq!(tilemap.layer_depth(1), 1.0);
+    ///
+    /// // The canopy is drawn above the sprites of the characters at 10.
+    /// tilemap.set_layer_depth(1, Some(20.0)).unwrap();
+    /// assert_eq!(tilemap.layer_depth(1), 20.0);
+    /// assert!(tilemap.set_layer_depth(2, Some(20.0)).is_err());
+    /// ```
+    pub fn set_layer_depth(&mut self, z_order: usize, depth: Option<f32>) -> TilemapResult<()> {
+        if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer.depth = depth;
+            Ok(())
+        } else {
+            Err(ErrorKind::LayerDoesNotExist(z_order).into())
+        }
+    }
+
+    /// Returns the z translation which the chunk entities of a layer are
+    /// drawn at from the render order of the tilemap, which is its z order
+    /// if it has no depth.
+    pub fn layer_depth(&self, z_order: usize) -> f32 {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.depth.unwrap_or(z_order as f32),
+            _ => z_order as f32,
+        }
+    }
+
+    /// Shows or hides a layer, like [`TilemapLayer::hidden`]. The tiles of a
+    /// hidden layer still collide and are kept up to date, so it is drawn as
+    /// it is once it is shown again.
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1).unwrap();
+    ///
+    /// // The roofs are hidden while the player is inside.
+    /// tilemap.set_layer_visible(1, false).unwrap();
+    /// assert!(!tilemap.is_layer_visible(1));
+    /// assert!(tilemap.is_layer_visible(0));
+    /// ```
+    pub fn set_layer_visible(&mut self, z_order: usize, visible: bool) -> TilemapResult<()> {
+        if let Some(Some(layer)) = self.layers.get_mut(z_order) {
+            layer.hidden = !visible;
+            Ok(())
+        } else {
+            Err(ErrorKind::LayerDoesNotExist(z_order).into())
+        }
+    }
+
+    /// Returns `true` if a layer is drawn, which layers are unless they were
+    /// hidden.
+    pub fn is_layer_visible(&self, z_order: usize) -> bool {
+        match self.layers.get(z_order) {
+            Some(Some(layer)) => !layer.hidden,
+            _ => true,
+        }
+    }
+
+    /// Removes a layer from the tilemap and inner chunks.
+    ///
+    /// **Warning**: This is destructive if you have tiles that exist on that
+    /// layer. If you want to add them back in, better to use the [`move_layer`]
+    /// method instead.
+    ///
+    /// This method takes in a Z layer which is then flagged for deletion. If
+    /// the layer already does not exist, it does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1);
+    ///
+    /// tilemap.remove_layer(1);
+    /// ```
+    ///
+    /// [`move_layer`]: Tilemap::move_layer
+    pub fn remove_layer(&mut self, z: usize) {
+        if let Some(layer) = self.layers.get_mut(z) {
+            *layer = None;
+        } else {
+            return;
+        }
+
+        let chunks = self
+            .chunks
+            .values_mut()
+            .chain(self.chunk_templates.iter_mut());
+        for chunk in chunks.map(Arc::make_mut) {
+            chunk.remove_layer(z);
+        }
+        #[cfg(feature = "render")]
+        self.template_meshes.clear();
+        self.channels.remove(&z);
+        self.layer_kind_overrides
+            .retain(|(_, z_order), _| *z_order != z);
+        self.unbind_tile_entities(|_, z_order| z_order == z, false);
+    }
+
+    /// Spawns a chunk at a given index or coordinate.
+    ///
+    /// Does nothing if the chunk does not exist. With a [`SpawnBudget`] the
+    /// chunk is queued, and spawned once the chunks nearer to the camera are.
+    ///
+    /// [`SpawnBudget`]: crate::chunk::SpawnBudget
+    ///
+    /// # Errors
+    ///
+    /// If the coordinate or index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .dimensions(1, 1)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_chunk((0, 0));
+    ///
+    /// // Ideally you should want to set some tiles here else nothing will
+    /// // display in the render...
+    ///
+    /// assert!(tilemap.spawn_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.spawn_chunk((1, 1)).is_err());
+    /// assert!(tilemap.spawn_chunk((-1, -1)).is_err());
+    /// ```
+    pub fn spawn_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if let Some(dimensions) = &self.dimensions {
+            dimensions.check_point(point)?;
+        }
+
+        #[cfg(feature = "serialize")]
+        self.load_chunk(point)?;
+        #[cfg(feature = "procgen")]
+        if !self.chunks.contains_key(&point) {
+            if let Some(generator) = self.chunk_generator.take() {
+                let generated = self.generate_chunk(&*generator, point);
+                self.chunk_generator = Some(generator);
+                generated?;
+            }
+        }
+        if self.spawned.contains(&(point.x, point.y)) || self.shed_meshes.contains(&point) {
+            return Ok(());
+        } else if self.spawn_budget.is_some() {
+            self.spawn_queue.insert(point);
+        } else {
+            self.chunk_events.send(TilemapChunkEvent::Spawned { point });
+        }
+
+        Ok(())
+    }
+
+    /// Spawns a chunk at a given tile point.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinate or index is out of bounds or if the chunk does not
+    /// exist, an error will be returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(32, 32)
+    ///     .tile_dimensions(32, 32)
+    ///     .dimensions(1, 1)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let point = (15, 15);
+    /// let sprite_index = 0;
+    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    ///
+    /// tilemap.insert_tile(tile);
+    ///
+    /// assert!(tilemap.spawn_chunk_containing_point(point).is_ok());
+    /// assert!(tilemap.spawn_chunk_containing_point((16, 16)).is_err());
+    /// assert!(tilemap.spawn_chunk_containing_point((-18, -18)).is_err());
+    /// ```
+    pub fn spawn_chunk_containing_point<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point = self.point_to_chunk_point(point);
+        self.spawn_chunk(point)
+    }
+
+    /// De-spawns a spawned chunk at a given index or coordinate.
+    ///
+    /// If the chunk is not spawned this will result in nothing.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinate or index is out of bounds, an error will be returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .dimensions(1, 1)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    ///
+    /// // Ideally you should want to set some tiles here else nothing will
+    /// // display in the render...
+    ///
+    /// assert!(tilemap.spawn_chunk((0, 0)).is_ok());
+    ///
+    /// // Later a frame or more on...
+    ///
+    /// assert!(tilemap.despawn_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.despawn_chunk((-1, -1)).is_err());
+    /// ```
+    pub fn despawn_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if let Some(dimensions) = &self.dimensions {
+            dimensions.check_point(point)?;
+        }
+
+        self.shed_meshes.remove(&point);
+        if self.spawn_queue.remove(&point) {
+            return Ok(());
+        }
+        self.spawned.remove(&(point.x, point.y));
+        self.shed_colliders.remove(&point);
+        if let Some(collision_chunks) = &mut self.collision_chunks {
+            collision_chunks.remove(&point);
+        }
+
+        if let Some(chunk) = self.chunks.get_mut(&point).map(Arc::make_mut) {
+            let entities = chunk.get_entities();
+            chunk.take_colliders();
+            self.chunk_events
+                .send(TilemapChunkEvent::Despawned { entities, point })
+        }
+        self.unbind_chunk_tile_entities(point, true);
+
+        Ok(())
+    }
+
+    /// De-spawns every spawned chunk, keeping their tiles.
+    ///
+    /// This tears down the entities and meshes of the tilemap, such as when
+    /// the level is left, while its tiles stay in the tilemap to be spawned
+    /// again later. Chunks which were queued to be spawned are dropped from
+    /// the queue. With [`auto_spawn`] the chunks around the camera are
+    /// spawned again on the next frame.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// for point in vec![(0, 0), (8, 0)] {
+    ///     tilemap.insert_tile(Tile { point, ..Default::default() }).unwrap();
+    ///     tilemap.spawn_chunk_containing_point(point).unwrap();
+    /// }
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    /// assert_eq!(harness.spawned_chunks(entity).len(), 2);
+    ///
+    /// harness.tilemap_mut(entity).unwrap().despawn_all_chunks().unwrap();
+    /// harness.tick();
+    ///
+    /// assert!(harness.spawned_chunks(entity).is_empty());
+    /// assert!(harness.tilemap(entity).unwrap().get_tile((8, 0), 0).is_some());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a chunk is outside of the dimensions of the tilemap, an error is
+    /// returned.
+    ///
+    /// [`auto_spawn`]: TilemapBuilder::auto_spawn
+    pub fn despawn_all_chunks(&mut self) -> TilemapResult<()> {
+        let mut points: Vec<Point2> = self.spawned.iter().map(Point2::from).collect();
+        points.extend(self.spawn_queue.iter().copied());
+        points.extend(self.shed_meshes.iter().copied());
+        for point in in_order(points, self.deterministic, |point| *point) {
+            self.despawn_chunk(point)?;
+        }
+        // The chunks around the cameras are spawned again.
+        self.spawn_focus.clear();
+
+        Ok(())
+    }
+
+    /// De-spawns and spawns again every spawned chunk, building their
+    /// entities and meshes from scratch.
+    ///
+    /// This is for changes which the spawned chunks do not pick up on their
+    /// own, such as graphics settings. Chunks which were queued to be spawned
+    /// stay queued, and chunks whose meshes were shed stay shed.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    /// let entities = harness.chunk_entities(entity, (0, 0));
+    ///
+    /// harness.tilemap_mut(entity).unwrap().respawn_all_chunks().unwrap();
+    /// harness.tick();
+    ///
+    /// assert_eq!(harness.spawned_chunks(entity).len(), 1);
+    /// assert_ne!(harness.chunk_entities(entity, (0, 0)), entities);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a chunk could not be spawned again, an error is returned.
+    pub fn respawn_all_chunks(&mut self) -> TilemapResult<()> {
+        let points: Vec<Point2> = self.spawned.iter().map(Point2::from).collect();
+        let points = in_order(points, self.deterministic, |point| *point);
+        for point in points.iter() {
+            self.despawn_chunk(*point)?;
+        }
+        for point in points.into_iter() {
+            self.spawn_chunk(point)?;
+        }
+
+        Ok(())
+    }
+
+    /// Destructively removes a chunk at a coordinate position and despawns them
+    /// if needed.
+    ///
+    /// Internally, this sends an event to the tilemap's system flagging which
+    /// chunks must be removed by index and entity. A chunk is not recoverable
+    /// if this action is done.
+    ///
+    /// Does nothing if the chunk does not exist.
+    ///
+    /// # Errors
+    ///
+    /// If the coordinate or index is out of bounds, an error will be returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .dimensions(3, 3)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // Add some chunks.
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.insert_chunk((1, 1)).is_ok());
+    ///
+    /// assert!(tilemap.remove_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.remove_chunk((1, 1)).is_ok());
+    /// assert!(tilemap.remove_chunk((-2, -2)).is_err());
+    /// ```
+    pub fn remove_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point = point.into();
+        self.despawn_chunk(point)?;
+
+        #[cfg(feature = "serialize")]
+        if self.unloaded_chunks.remove(&point) {
+            let store_point = self.store_point(point);
+            self.store_points.remove(&point);
+            self.layer_permutations.remove(&point);
+            if let Some(store) = &self.chunk_store {
+                if let Err(e) = store.remove(store_point) {
+                    warn!("{}", e);
+                }
+            }
+        }
+        self.chunks.remove(&point);
+        self.chunk_instances.remove(&point);
+        self.unbind_chunk_tile_entities(point, false);
+        for navmesh in self.navmeshes.values_mut() {
+            navmesh.mark_dirty(point);
+        }
+        if self.changes.is_some() {
+            let chunk_dimensions = self.chunk_dimensions;
+            let chunk_point = |tile_point| chunk_point_of(chunk_dimensions, tile_point);
+            self.changed_tiles
+                .retain(|(tile_point, _)| chunk_point(*tile_point) != point);
+            if let Some(changes) = &mut self.changes {
+                changes.remove_chunk(point, chunk_point);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Frees the memory which heavy editing left behind, returning what was
+    /// freed.
+    ///
+    /// The capacity which sparse and decoration layers kept for removed
+    /// tiles is freed, and chunks which have no tiles are removed, see
+    /// [`remove_chunk`]. Static chunks are kept, and chunks which are shared
+    /// with a chunk template are only removed, never shrunk. Long running
+    /// servers call this from time to time.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let mut tiles = Vec::new();
+    /// for x in -4..4 {
+    ///     tiles.push(Tile { point: (x, 0), sprite_index: 1, z_order: 1, ..Default::default() });
+    /// }
+    /// tilemap.insert_tiles(tiles).unwrap();
+    /// tilemap.insert_chunk((3, 3)).unwrap();
+    /// for x in -4..3 {
+    ///     tilemap.clear_tile((x, 0), 1).unwrap();
+    /// }
+    ///
+    /// let report = tilemap.compact();
+    /// assert_eq!(report.removed_chunks, 1);
+    /// assert!(report.freed_bytes > 0);
+    /// assert!(!tilemap.contains_chunk((3, 3)));
+    /// assert_eq!(tilemap.get_tile((3, 0), 1).unwrap().index, 1);
+    /// ```
+    ///
+    /// [`remove_chunk`]: Tilemap::remove_chunk
+    pub fn compact(&mut self) -> CompactReport {
+        let mut report = CompactReport::default();
+        let empty_chunks: Vec<Point2> = self
+            .chunks
+            .iter()
+            .filter(|(point, chunk)| !self.static_chunks.contains(point) && chunk.is_empty())
+            .map(|(point, _)| *point)
+            .collect();
+        for point in empty_chunks {
+            let heap_size = match self.chunks.get(&point) {
+                Some(chunk) if Arc::strong_count(chunk) == 1 => chunk.heap_size(),
+                Some(_) => 0,
+                None => continue,
+            };
+            if let Err(e) = self.remove_chunk(point) {
+                warn!("{}", e);
+                continue;
+            }
+            report.removed_chunks += 1;
+            report.freed_bytes += heap_size + ::std::mem::size_of::<Chunk>();
+        }
+        for chunk in self.chunks.values_mut() {
+            // Shrinking a shared chunk would copy it.
+            if let Some(chunk) = Arc::get_mut(chunk) {
+                let heap_size = chunk.heap_size();
+                chunk.shrink_to_fit();
+                report.freed_bytes += heap_size.saturating_sub(chunk.heap_size());
+            }
+        }
+        report
+    }
+
+    /// Returns the chunks, tiles and memory of the tilemap.
+    ///
+    /// The bytes are estimates from the capacity of the layers and the
+    /// vertices of the meshes, which help to decide whether a layer should
+    /// be dense or sparse. Chunks which were unloaded to the chunk store are
+    /// not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A few trees over a field of grass.
+    /// let mut tiles = Vec::new();
+    /// for x in -4..4 {
+    ///     for y in -4..4 {
+    ///         tiles.push(Tile { point: (x, y), sprite_index: 1, ..Default::default() });
+    ///     }
+    /// }
+    /// for x in 0..3 {
+    ///     tiles.push(Tile { point: (x, x), sprite_index: 5, z_order: 1, ..Default::default() });
+    /// }
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// let stats = tilemap.stats();
+    /// assert_eq!(stats.chunks, 1);
+    /// assert_eq!(stats.spawned_chunks, 0);
+    /// assert_eq!(stats.layers[&LayerKind::Dense].tiles, 64);
+    /// assert_eq!(stats.layers[&LayerKind::Sparse].tiles, 3);
+    /// // Three trees take less memory sparse than dense.
+    /// assert!(stats.layers[&LayerKind::Sparse].bytes < stats.layers[&LayerKind::Dense].bytes);
+    /// ```
+    pub fn stats(&self) -> TilemapStats {
+        let mut stats = TilemapStats {
+            chunks: self.chunks.len(),
+            spawned_chunks: self.spawned.len(),
+            ..Default::default()
+        };
+        #[cfg(feature = "render")]
+        let area = self.chunk_dimensions.area() as usize;
+        for chunk in self.chunks.values() {
+            #[cfg(feature = "render")]
+            let spawned = self.spawned.contains(&(chunk.point().x, chunk.point().y));
+            for (z_order, layer) in self.layers.iter().enumerate() {
+                let kind = match layer {
+                    Some(layer) => layer.kind,
+                    None => continue,
+                };
+                let bytes = match chunk.layer_heap_size(z_order) {
+                    Some(bytes) => bytes,
+                    None => continue,
+                };
+                let layer_stats = stats.layers.entry(kind).or_default();
+                layer_stats.layers += 1;
+                layer_stats.tiles += chunk.get_tile_indices(z_order).unwrap_or_default().len();
+                layer_stats.bytes += bytes;
+                #[cfg(feature = "render")]
+                if spawned && chunk.get_entity(z_order).is_some() {
+                    stats.mesh_bytes += ChunkMesh::approximate_size(area);
+                }
+            }
+        }
+        stats
+    }
+
+    /// Takes a tile point and changes it into a chunk point.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let tile_point = (15, 15);
+    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
+    /// assert_eq!((0, 0), chunk_point);
+    ///
+    /// let tile_point = (16, 16);
+    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
+    /// assert_eq!((1, 1), chunk_point);
+    ///
+    /// let tile_point = (-16, -16);
+    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
+    /// assert_eq!((-0, -0), chunk_point);
+    ///
+    /// let tile_point = (-17, -17);
+    /// let chunk_point = tilemap.point_to_chunk_point(tile_point);
+    /// assert_eq!((-1, -1), chunk_point);
+    /// ```
+    pub fn point_to_chunk_point<P: Into<Point2>>(&self, point: P) -> (i32, i32) {
+        let chunk_point = chunk_point_of(self.chunk_dimensions, point.into());
+        (chunk_point.x, chunk_point.y)
+    }
+
+    /// Takes a tile point and returns the translation of the center of the
+    /// tile, relative to the tilemap's transform and its translation origin.
+    ///
+    /// This takes the topology of the tilemap into account and is exactly
+    /// where the tile is rendered.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.point_to_translation((0, 0)), Vec2::new(16.0, 16.0));
+    /// assert_eq!(tilemap.point_to_translation((-1, 2)), Vec2::new(-16.0, 80.0));
+    /// ```
+    pub fn point_to_translation<P: Into<Point2>>(&self, point: P) -> Vec2 {
+        geometry::tile_center(
+            self.topology,
+            point.into() - self.translation_offset(),
+            self.chunk_dimensions,
+            self.tile_dimensions,
+        )
+    }
+
+    /// Takes a translation, relative to the tilemap's transform and its
+    /// translation origin, and returns the point of the tile which contains
+    /// it.
+    ///
+    /// This works for every topology, hexes are picked by their hexagon and
+    /// iso tiles by their diamond instead of by their rectangular sprite. A
+    /// translation that lies exactly on a shared edge or corner always
+    /// resolves to the same tile.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .topology(GridTopology::IsoDiamond)
+    ///     .tile_dimensions(64, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let center = tilemap.point_to_translation((3, -2));
+    /// assert_eq!(tilemap.translation_to_point(center), (3, -2).into());
+    ///
+    /// // Just inside of the right corner of the diamond.
+    /// let right = center + Vec2::new(31.0, 0.0);
+    /// assert_eq!(tilemap.translation_to_point(right), (3, -2).into());
+    ///
+    /// // Past the upper right edge is the next diamond along the x axis.
+    /// let upper_right = center + Vec2::new(20.0, 10.0);
+    /// assert_eq!(tilemap.translation_to_point(upper_right), (4, -2).into());
+    /// ```
+    pub fn translation_to_point(&self, translation: Vec2) -> Point2 {
+        geometry::translation_to_point(
+            self.topology,
+            translation,
+            self.chunk_dimensions,
+            self.tile_dimensions,
+        ) + self.translation_offset()
+    }
+
+    /// Takes a global translation, such as that of a camera or of the cursor
+    /// in the world, and returns the point of the tile which contains it
+    /// with the global transform of the tilemap entity.
+    ///
+    /// Unlike [`translation_to_point`], this is right for tilemaps which are
+    /// rotated or scaled, or whose entity is the child of another.
+    ///
+    /// [`translation_to_point`]: Tilemap::translation_to_point
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::{Quat, Vec3};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    /// use bevy_transform::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// // Drawn twice as large and turned a quarter counterclockwise.
+    /// let transform = GlobalTransform {
+    ///     rotation: Quat::from_rotation_z(::std::f32::consts::FRAC_PI_2),
+    ///     scale: Vec3::new(2.0, 2.0, 1.0),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let center = tilemap.point_to_global_translation(&transform, (1, 0));
+    /// assert!((center - Vec3::new(-32.0, 96.0, 0.0)).length() < 0.001);
+    /// assert_eq!(tilemap.global_translation_to_point(&transform, center), (1, 0).into());
+    /// ```
+    pub fn global_translation_to_point(
+        &self,
+        transform: &GlobalTransform,
+        translation: Vec3,
+    ) -> Point2 {
+        let translation = transform
+            .compute_matrix()
+            .inverse()
+            .transform_point3(translation);
+        self.translation_to_point(translation.truncate())
+    }
+
+    /// Returns the global translation of the center of a tile with the global
+    /// transform of the tilemap entity, see
+    /// [`global_translation_to_point`].
+    ///
+    /// [`global_translation_to_point`]: Tilemap::global_translation_to_point
+    pub fn point_to_global_translation<P: Into<Point2>>(
+        &self,
+        transform: &GlobalTransform,
+        point: P,
+    ) -> Vec3 {
+        transform.mul_vec3(self.point_to_translation(point).extend(0.0))
+    }
+
+    /// Takes a translation, relative to the tilemap's transform and its
+    /// translation origin, and returns the depth of something standing there
+    /// on a y sorted layer of a square tilemap, see [`iso::row_depth`].
+    ///
+    /// Sprites such as players and NPCs which are given the z order of a y
+    /// sorted layer plus the depth of where their feet are walk in front of
+    /// the tiles behind them and behind the tiles in front of them.
+    ///
+    /// [`iso::row_depth`]: crate::iso::row_depth
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::YSorted, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A tree in the row at 2 and a player standing just below it.
+    /// tilemap.insert_tile(Tile { point: (0, 2), sprite_index: 9, z_order: 1, ..Default::default() }).unwrap();
+    /// let tree = tilemap.y_sort_depth(tilemap.point_to_translation((0, 2)));
+    /// let feet = tilemap.point_to_translation((0, 2)) - Vec2::new(0.0, 10.0);
+    ///
+    /// // The player is drawn in front of the tree.
+    /// let z = 1.0 + tilemap.y_sort_depth(feet);
+    /// assert!(z > 1.0 + tree);
+    /// ```
+    pub fn y_sort_depth(&self, translation: Vec2) -> f32 {
+        let point = self.translation_to_point(translation);
+        let center = self.point_to_translation(point);
+        let row = (translation.y - center.y) / self.tile_dimensions.height as f32;
+        iso::row_depth(point.y as f32 + 0.5 + row)
+    }
+
+    /// Snaps a translation, relative to the tilemap's transform, to the center
+    /// of the tile which contains it.
+    ///
+    /// Useful for grid overlays and for placing things on tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .topology(GridTopology::HexY)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let center = tilemap.point_to_translation((2, 5));
+    /// let snapped = tilemap.snap_translation(center + Vec2::new(5.0, -6.0));
+    /// assert_eq!(snapped, center);
+    /// ```
+    pub fn snap_translation(&self, translation: Vec2) -> Vec2 {
+        self.point_to_translation(self.translation_to_point(translation))
+    }
+
+    /// Draws the tiles from one point to another, both included, of the
+    /// layers in the z order range into a new texture, see [`export`].
+    ///
+    /// The texture covers every tile of the region, whether it is set or not,
+    /// and has the pixel format of the texture of the texture atlas. Tiles of
+    /// chunks which were unloaded are not drawn.
+    ///
+    /// # Errors
+    ///
+    /// If the texture of the texture atlas is not loaded or not 8 bit RGBA, an
+    /// error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::prelude::*;
+    /// use bevy_render::{prelude::*, texture::Extent3d};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    /// let texture_atlas_handle = harness.texture_atlas().clone();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle.clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    ///
+    /// let resources = &harness.app().resources;
+    /// let texture_atlases = resources.get::<Assets<TextureAtlas>>().unwrap();
+    /// let textures = resources.get::<Assets<Texture>>().unwrap();
+    /// let texture_atlas = texture_atlases.get(&texture_atlas_handle).unwrap();
+    ///
+    /// let texture = tilemap
+    ///     .render_to_texture((0, 0), (1, 0), 0..1, texture_atlas, &textures)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(texture.size, Extent3d::new(64, 32, 1));
+    /// // The tile is white and the point next to it is empty.
+    /// assert_eq!(&texture.data[0..4], &[255, 255, 255, 255]);
+    /// assert_eq!(&texture.data[32 * 4..33 * 4], &[0, 0, 0, 0]);
+    /// ```
+    ///
+    /// [`export`]: crate::export
+    #[cfg(feature = "render")]
+    pub fn render_to_texture<P: Into<Point2>>(
+        &self,
+        from: P,
+        to: P,
+        layers: Range<usize>,
+        texture_atlas: &TextureAtlas,
+        textures: &Assets<Texture>,
+    ) -> TilemapResult<Texture> {
+        crate::export::render_to_texture(
+            self,
+            from.into(),
+            to.into(),
+            layers,
+            texture_atlas,
+            textures,
+        )
+    }
+
+    /// Writes the sprite indexes of the tiles of a layer from one point to
+    /// another, both included, as comma separated values.
+    ///
+    /// Each line is a row of tiles, from the highest row down, and each row
+    /// goes from the lowest column up. Points without a tile are left empty.
+    /// This is what [`set_layer_from_csv`] reads, with the top left point
+    /// `(from.x, to.y)` for a `from` below and left of `to`.
+    ///
+    /// [`set_layer_from_csv`]: Tilemap::set_layer_from_csv
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 1), sprite_index: 3, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (2, 0), sprite_index: 12, ..Default::default() }).unwrap();
+    ///
+    /// assert_eq!(tilemap.layer_to_csv(0, (0, 0), (2, 1)).unwrap(), "3,,\n,,12\n");
+    /// ```
+    pub fn layer_to_csv<P: Into<Point2>>(
+        &self,
+        z_order: usize,
+        from: P,
+        to: P,
+    ) -> TilemapResult<String> {
+        if let None | Some(None) = self.layers.get(z_order) {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        let from: Point2 = from.into();
+        let to: Point2 = to.into();
+        let mut csv = String::new();
+        for y in (from.y.min(to.y)..=from.y.max(to.y)).rev() {
+            let row: Vec<String> = (from.x.min(to.x)..=from.x.max(to.x))
+                .map(|x| match self.get_tile((x, y), z_order) {
+                    Some(tile) => tile.index.to_string(),
+                    None => String::new(),
+                })
+                .collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        Ok(csv)
+    }
+
+    /// Sets the tiles of a layer from the sprite indexes of comma separated
+    /// values, with the first value at the top left point. Each line is a row
+    /// of tiles going down, as [`layer_to_csv`] writes them.
+    ///
+    /// Empty values clear their tile, and blank lines are skipped.
+    ///
+    /// [`layer_to_csv`]: Tilemap::layer_to_csv
+    ///
+    /// # Errors
+    ///
+    /// If a value is not a sprite index, if the layer does not exist or if a
+    /// tile is outside of the dimensions of the tilemap, an error is returned
+    /// and no tiles are set.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.set_layer_from_csv(0, (-1, 1), "1, 2\n , 4\n").unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 1), 0).unwrap().index, 2);
+    /// assert_eq!(tilemap.get_tile((-1, 0), 0), None);
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 4);
+    /// assert!(tilemap.set_layer_from_csv(0, (0, 0), "1,grass").is_err());
+    /// ```
+    pub fn set_layer_from_csv<P: Into<Point2>>(
+        &mut self,
+        z_order: usize,
+        top_left: P,
+        csv: &str,
+    ) -> TilemapResult<()> {
+        let top_left: Point2 = top_left.into();
+        let mut tiles = Vec::new();
+        let mut cleared = Vec::new();
+        let rows = csv.lines().filter(|line| !line.trim().is_empty());
+        for (row, line) in rows.enumerate() {
+            for (column, value) in line.split(',').enumerate() {
+                let point = Point2::new(top_left.x + column as i32, top_left.y - row as i32);
+                let value = value.trim();
+                if value.is_empty() {
+                    cleared.push(point);
+                    continue;
+                }
+                let sprite_index = value.parse::<usize>().map_err(|_| {
+                    ErrorKind::InvalidCsv(::std::format!(
+                        "\"{}\" in row {} column {} is not a sprite index",
+                        value,
+                        row + 1,
+                        column + 1
+                    ))
+                })?;
+                tiles.push(Tile {
+                    point,
+                    z_order,
+                    sprite_index,
+                    ..Default::default()
+                });
+            }
+        }
+        self.insert_tiles(tiles)?;
+        let cleared: Vec<(Point2, usize)> = cleared
+            .into_iter()
+            .filter(|point| self.get_tile(*point, z_order).is_some())
+            .map(|point| (point, z_order))
+            .collect();
+        self.clear_tiles(cleared)
+    }
+
+    /// Builds a tilemap from a grid of sprite indexes on a layer.
+    ///
+    /// The grid is in row-major order, from the top row down, as most map
+    /// data and generators have it. Its bottom left tile is at the point
+    /// `(0, 0)`, so the tile at `column` and `row` is at the point
+    /// `(column, height - 1 - row)`. Points which are [`EMPTY_GRID_TILE`] are
+    /// left without a tile. The chunks which the grid covers are made even if
+    /// the builder does not auto chunk.
+    ///
+    /// # Errors
+    ///
+    /// If the grid does not have `width * height` sprite indexes, if the
+    /// tilemap can not be built or if the grid is outside of its dimensions,
+    /// an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tilemap::EMPTY_GRID_TILE};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let builder = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32);
+    ///
+    /// let grid = [1, 2, EMPTY_GRID_TILE, 3, 4, 5];
+    /// let tilemap = Tilemap::from_grid(builder, 3, 2, &grid, 1).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 1), 1).unwrap().index, 1);
+    /// assert_eq!(tilemap.get_tile((2, 1), 1), None);
+    /// assert_eq!(tilemap.get_tile((2, 0), 1).unwrap().index, 5);
+    /// assert_eq!(tilemap.to_grid(1).unwrap(), (3, 2, grid.to_vec()));
+    /// ```
+    pub fn from_grid(
+        builder: TilemapBuilder,
+        width: u32,
+        height: u32,
+        grid: &[u32],
+        z_order: usize,
+    ) -> TilemapResult<Tilemap> {
+        let expected = width as usize * height as usize;
+        if grid.len() != expected {
+            return Err(ErrorKind::MismatchedGrid(grid.len(), expected).into());
+        }
+        let mut tilemap = builder.finish()?;
+        if expected == 0 {
+            return Ok(tilemap);
+        }
+
+        let low = tilemap.point_to_chunk_point((0, 0));
+        let high = tilemap.point_to_chunk_point((width as i32 - 1, height as i32 - 1));
+        for y in low.1..=high.1 {
+            for x in low.0..=high.0 {
+                if !tilemap.contains_chunk((x, y)) {
+                    tilemap.insert_chunk((x, y))?;
+                }
+            }
+        }
+        let tiles = grid
+            .iter()
+            .enumerate()
+            .filter(|(_, sprite_index)| **sprite_index != EMPTY_GRID_TILE)
+            .map(|(n, sprite_index)| {
+                let column = (n % width as usize) as i32;
+                let row = (n / width as usize) as i32;
+                Tile {
+                    point: Point2::new(column, height as i32 - 1 - row),
+                    z_order,
+                    sprite_index: *sprite_index as usize,
+                    ..Default::default()
+                }
+            });
+        tilemap.insert_tiles(tiles)?;
+
+        Ok(tilemap)
+    }
+
+    /// Returns the sprite indexes of a layer as a grid of its width, its
+    /// height and its indexes, which [`from_grid`] reads.
+    ///
+    /// The grid goes from the point `(0, 0)` to the highest column and row
+    /// with a tile on the layer, so tiles at negative points are left out.
+    /// Points without a tile are [`EMPTY_GRID_TILE`].
+    ///
+    /// [`from_grid`]: Tilemap::from_grid
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tilemap::EMPTY_GRID_TILE};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 7, ..Default::default() }).unwrap();
+    ///
+    /// assert_eq!(tilemap.to_grid(0).unwrap(), (2, 1, vec![EMPTY_GRID_TILE, 7]));
+    /// ```
+    pub fn to_grid(&self, z_order: usize) -> TilemapResult<(u32, u32, Vec<u32>)> {
+        if let None | Some(None) = self.layers.get(z_order) {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        let chunk_width = self.chunk_dimensions.width as i32;
+        let chunk_height = self.chunk_dimensions.height as i32;
+        let mut width = 0;
+        let mut height = 0;
+        for chunk_point in self.chunks.keys() {
+            let low_x = chunk_point.x * chunk_width - chunk_width / 2;
+            let low_y = chunk_point.y * chunk_height - chunk_height / 2;
+            for y in low_y.max(0)..low_y + chunk_height {
+                for x in low_x.max(0)..low_x + chunk_width {
+                    if self.get_tile((x, y), z_order).is_some() {
+                        width = width.max(x + 1);
+                        height = height.max(y + 1);
+                    }
+                }
+            }
+        }
+
+        let mut grid = Vec::with_capacity((width * height) as usize);
+        for y in (0..height).rev() {
+            for x in 0..width {
+                grid.push(match self.get_tile((x, y), z_order) {
+                    Some(tile) => tile.index as u32,
+                    None => EMPTY_GRID_TILE,
+                });
+            }
+        }
+        Ok((width as u32, height as u32, grid))
+    }
+
+    /// Fills the region of the dimensions from its bottom left point with
+    /// tiles on the layer by wave function collapse, picked with the seed,
+    /// see the [`wfc`] module. The tiles on the edges of the region fit with
+    /// the tiles of the layer around it.
+    ///
+    /// [`wfc`]: crate::wfc
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, wfc::WfcRules};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // Stripes which are always 5 and 6 next to each other.
+    /// let rules = WfcRules::new()
+    ///     .with_tile(5, 1)
+    ///     .with_tile(6, 1)
+    ///     .with_horizontal(5, 6)
+    ///     .with_horizontal(6, 5)
+    ///     .with_vertical(5, 5)
+    ///     .with_vertical(6, 6);
+    ///
+    /// tilemap.insert_tile(Tile { point: (-1, 0), sprite_index: 5, ..Default::default() }).unwrap();
+    /// tilemap.fill_wfc(&rules, (0, 0), (4, 4), 0, 3).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 3), 0).unwrap().index, 6);
+    /// assert_eq!(tilemap.get_tile((1, 2), 0).unwrap().index, 5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no tiles fit the region, or if the tiles can not
+    /// be inserted for the same reasons as [`insert_tiles`].
+    ///
+    /// [`insert_tiles`]: Tilemap::insert_tiles
+    pub fn fill_wfc<P: Into<Point2>>(
+        &mut self,
+        rules: &WfcRules,
+        from: P,
+        dimensions: (u32, u32),
+        z_order: usize,
+        seed: u64,
+    ) -> TilemapResult<()> {
+        let tiles = rules.generate(from, dimensions, z_order, seed, |point| {
+            self.get_tile(point, z_order).map(|tile| tile.index)
+        })?;
+        self.insert_tiles(tiles)
+    }
+
+    /// Sets the generator of the chunks which are spawned but do not exist
+    /// yet, see the [`procgen`] module. This is behind the `procgen` feature.
+    ///
+    /// [`procgen`]: crate::procgen
+    #[cfg(feature = "procgen")]
+    pub fn set_chunk_generator<G: ChunkGenerator + 'static>(&mut self, generator: G) {
+        self.chunk_generator = Some(Box::new(generator));
+    }
+
+    /// Removes the generator of the chunks and returns it, if there was one.
+    /// This is behind the `procgen` feature.
+    #[cfg(feature = "procgen")]
+    pub fn remove_chunk_generator(&mut self) -> Option<Box<dyn ChunkGenerator>> {
+        self.chunk_generator.take()
+    }
+
+    /// Returns `true` if the tilemap has a generator of chunks. This is
+    /// behind the `procgen` feature.
+    #[cfg(feature = "procgen")]
+    pub fn has_chunk_generator(&self) -> bool {
+        self.chunk_generator.is_some()
+    }
+
+    /// Generates the tiles of the chunk at the chunk point with the
+    /// generator, inserting the chunk if it does not exist yet. This is
+    /// behind the `procgen` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{
+    ///     prelude::*,
+    ///     procgen::{HeightmapGenerator, Noise},
+    /// };
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // Every height is below the only threshold.
+    /// let generator = HeightmapGenerator::new(Noise::perlin(5)).with_threshold(2.0, 9);
+    /// tilemap.generate_chunk(&generator, (1, 0)).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((2, -2), 0).unwrap().index, 9);
+    /// assert_eq!(tilemap.get_tile((5, 1), 0).unwrap().index, 9);
+    /// assert!(tilemap.get_tile((1, 0), 0).is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the chunk point is out of bounds, or if the tiles can not be
+    /// inserted for the same reasons as [`insert_tiles`], an error is
+    /// returned.
+    ///
+    /// [`insert_tiles`]: Tilemap::insert_tiles
+    #[cfg(feature = "procgen")]
+    pub fn generate_chunk<P: Into<Point2>>(
+        &mut self,
+        generator: &dyn ChunkGenerator,
+        point: P,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if !self.chunks.contains_key(&point) {
+            self.insert_chunk(point)?;
+        }
+        let from = tile_point_to_point(self.chunk_dimensions, point, Point2::new(0, 0));
+        let dimensions = (self.chunk_dimensions.width, self.chunk_dimensions.height);
+        self.insert_tiles(generator.generate(from, dimensions))
+    }
+
+    /// Writes the tiles of every layer as a map of the TMX format of Tiled,
+    /// see the [`tmx`] module.
+    ///
+    /// [`tmx`]: crate::tmx
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap is not square or iso diamond, or if no region is given
+    /// and it has no chunks, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tmx::TmxExport};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .topology(GridTopology::IsoDiamond)
+    ///     .tile_dimensions(64, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 2, ..Default::default() }).unwrap();
+    ///
+    /// let export = TmxExport::new("iso.tsx").first_gid(10).region((0, 0), (1, 2));
+    /// let tmx = tilemap.to_tmx(&export).unwrap();
+    ///
+    /// // The top corner is the highest point, so the tile is in the last column.
+    /// assert!(tmx.contains(r#"orientation="isometric" renderorder="right-down" width="3" height="2""#));
+    /// assert!(tmx.contains("0,0,12,\n0,0,0\n"));
+    /// ```
+    pub fn to_tmx(&self, export: &crate::tmx::TmxExport) -> TilemapResult<String> {
+        crate::tmx::to_tmx(self, export)
+    }
+
+    /// Writes the tilemap as a versioned save, see the [`save`] module.
+    ///
+    /// [`save`]: crate::save
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap can not be serialized, an error is returned.
+    #[cfg(feature = "serialize")]
+    pub fn to_save(&self) -> TilemapResult<Vec<u8>> {
+        crate::save::write("tilemap", self)
+    }
+
+    /// Reads a tilemap from a save of any version, drawn with the texture
+    /// atlas. The chunks which were spawned when it was saved are spawned
+    /// again.
+    ///
+    /// # Errors
+    ///
+    /// If the save is not a valid save of a tilemap or was written with a
+    /// newer version of the save format, an error is returned.
+    #[cfg(all(feature = "serialize", feature = "render"))]
+    pub fn from_save(bytes: &[u8], texture_atlas: Handle<TextureAtlas>) -> TilemapResult<Tilemap> {
+        Tilemap::from_save_with(bytes, texture_atlas, |_, _| Ok(()))
+    }
+
+    /// Reads a tilemap from a save of any version, without a texture atlas
+    /// as headless builds have none. The chunks which were spawned when it
+    /// was saved are spawned again.
+    ///
+    /// # Errors
+    ///
+    /// If the save is not a valid save of a tilemap or was written with a
+    /// newer version of the save format, an error is returned.
+    #[cfg(all(feature = "serialize", not(feature = "render")))]
+    pub fn from_save(bytes: &[u8]) -> TilemapResult<Tilemap> {
+        Tilemap::from_save_with(bytes, |_, _| Ok(()))
+    }
+
+    /// Reads a tilemap from a save of any version like [`from_save`], first
+    /// calling a function with the version the save was written with and its
+    /// JSON, upgraded to the current version, to fix it up.
+    ///
+    /// [`from_save`]: Tilemap::from_save
+    ///
+    /// # Errors
+    ///
+    /// If the save is not a valid save of a tilemap, was written with a newer
+    /// version of the save format or the function returns an error, an error
+    /// is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle.clone(), 32, 32);
+    /// tilemap.set_name("cave");
+    /// let save = tilemap.to_save().unwrap();
+    ///
+    /// let tilemap = Tilemap::from_save_with(&save, texture_atlas_handle, |version, json| {
+    ///     assert_eq!(version, bevy_tilemap::save::SAVE_VERSION);
+    ///     json["name"] = "old cave".into();
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(tilemap.name(), Some("old cave"));
+    /// ```
+    #[cfg(all(feature = "serialize", feature = "render"))]
+    pub fn from_save_with<F>(
+        bytes: &[u8],
+        texture_atlas: Handle<TextureAtlas>,
+        fix_up: F,
+    ) -> TilemapResult<Tilemap>
+    where
+        F: FnOnce(u32, &mut JsonValue) -> TilemapResult<()>,
+    {
+        let mut tilemap = Tilemap::read_save(bytes, fix_up)?;
+        tilemap.texture_atlas = texture_atlas;
+        Ok(tilemap)
+    }
+
+    /// Reads a tilemap from a save of any version like [`from_save`], without
+    /// a texture atlas, first calling a function with the version the save was
+    /// written with and its JSON, upgraded to the current version, to fix it
+    /// up.
+    ///
+    /// [`from_save`]: Tilemap::from_save
+    ///
+    /// # Errors
+    ///
+    /// If the save is not a valid save of a tilemap, was written with a newer
+    /// version of the save format or the function returns an error, an error
+    /// is returned.
+    #[cfg(all(feature = "serialize", not(feature = "render")))]
+    pub fn from_save_with<F>(bytes: &[u8], fix_up: F) -> TilemapResult<Tilemap>
+    where
+        F: FnOnce(u32, &mut JsonValue) -> TilemapResult<()>,
+    {
+        Tilemap::read_save(bytes, fix_up)
+    }
+
+    /// Reads a tilemap from a save, fixed up by the function, and spawns the
+    /// chunks which were spawned when it was saved.
+    #[cfg(feature = "serialize")]
+    fn read_save<F>(bytes: &[u8], fix_up: F) -> TilemapResult<Tilemap>
+    where
+        F: FnOnce(u32, &mut JsonValue) -> TilemapResult<()>,
+    {
+        let (version, mut json) =
+            crate::save::read(bytes, "tilemap", &crate::save::TILEMAP_MIGRATIONS)?;
+        fix_up(version, &mut json)?;
+        let mut tilemap: Tilemap = crate::save::from_json(json)?;
+        // The entities of the save no longer exist.
+        for chunk in tilemap.chunks.values_mut().map(Arc::make_mut) {
+            chunk.take_colliders();
+        }
+        let spawned: Vec<(i32, i32)> = tilemap.spawned.drain().collect();
+        for point in spawned.into_iter() {
+            tilemap.spawn_chunk(point)?;
+        }
+
+        Ok(tilemap)
+    }
+
+    /// Sets if the changes to the chunks and tiles of the tilemap are tracked,
+    /// so that they can be drained with [`drain_changes`]. Changes are not
+    /// tracked unless this is set, and those which were tracked are dropped
+    /// when it is unset.
+    ///
+    /// [`drain_changes`]: Tilemap::drain_changes
+    pub fn set_track_changes(&mut self, track: bool) {
+        if track {
+            self.changes.get_or_insert_with(TilemapDelta::default);
+        } else {
+            self.changes = None;
+            self.changed_tiles.clear();
+        }
+    }
+
+    /// Returns `true` if the changes to the chunks and tiles of the tilemap
+    /// are tracked.
+    pub fn tracks_changes(&self) -> bool {
+        self.changes.is_some()
+    }
+
+    /// Returns the changes to the chunks and tiles of the tilemap since they
+    /// were last drained, see the [`delta`] module. The delta is empty if
+    /// changes are not tracked.
+    ///
+    /// [`delta`]: crate::delta
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.set_track_changes(true);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 3, ..Default::default() }).unwrap();
+    /// tilemap.get_tile_mut((1, 1), 0).unwrap().index = 4;
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.clear_tile((0, 0), 0).unwrap();
+    ///
+    /// let delta = tilemap.drain_changes();
+    /// assert!(delta.inserted_chunks().contains(&(0, 0).into()));
+    /// assert_eq!(delta.tiles()[&((1, 1).into(), 0)].unwrap().index, 4);
+    /// assert_eq!(delta.tiles()[&((0, 0).into(), 0)], None);
+    ///
+    /// tilemap.remove_chunk((0, 0)).unwrap();
+    /// let delta = tilemap.drain_changes();
+    /// assert!(delta.removed_chunks().contains(&(0, 0).into()));
+    /// assert!(delta.tiles().is_empty());
+    /// ```
+    pub fn drain_changes(&mut self) -> TilemapDelta {
+        self.resolve_changed_tiles(None);
+        match &mut self.changes {
+            Some(changes) => ::std::mem::take(changes),
+            None => TilemapDelta::default(),
+        }
+    }
+
+    /// Applies the changes of a delta, which was drained from a tilemap with
+    /// the same layers, to the tilemap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a chunk or a tile of the delta is outside of the
+    /// tilemap, or a tile is in a chunk which the tilemap does not have and
+    /// can not make.
+    pub fn apply_delta(&mut self, delta: &TilemapDelta) -> TilemapResult<()> {
+        crate::delta::apply(self, delta)
+    }
+
+    /// Records the last state of the changed tiles in the changes, or only
+    /// those in a chunk.
+    fn resolve_changed_tiles(&mut self, chunk_point: Option<Point2>) {
+        let changed_tiles: Vec<(Point2, usize)> = match chunk_point {
+            Some(chunk_point) => self
+                .changed_tiles
+                .iter()
+                .filter(|(point, _)| chunk_point_of(self.chunk_dimensions, *point) == chunk_point)
+                .copied()
+                .collect(),
+            None => self.changed_tiles.iter().copied().collect(),
+        };
+        for (point, z_order) in changed_tiles.into_iter() {
+            self.changed_tiles.remove(&(point, z_order));
+            let tile = self.get_tile(point, z_order).copied();
+            if let Some(changes) = &mut self.changes {
+                changes.set_tile(point, z_order, tile);
+            }
+        }
+    }
+
+    /// Takes a snapshot of the chunks of the tilemap and their tiles, which
+    /// it can be put back to with [`restore`], see the [`snapshot`] module.
+    ///
+    /// The snapshot shares the chunks with the tilemap, so taking one does
+    /// not copy any tiles.
+    ///
+    /// [`restore`]: Tilemap::restore
+    /// [`snapshot`]: crate::snapshot
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// let snapshot = tilemap.snapshot();
+    /// assert!(snapshot.contains_chunk((0, 0)));
+    /// assert_eq!(snapshot.chunk_count(), 1);
+    /// ```
+    pub fn snapshot(&self) -> TilemapSnapshot {
+        TilemapSnapshot {
+            chunks: self.chunks.clone(),
+            #[cfg(feature = "serialize")]
+            unloaded_chunks: self.unloaded_chunks.clone(),
+        }
+    }
+
+    /// Takes the tiles of the tilemap and how they are drawn as data, which
+    /// can be added as an asset that instances render, see the [`instance`]
+    /// module.
+    ///
+    /// The data shares the chunks with the tilemap, so taking it does not copy
+    /// any tiles. Chunks which were written to the chunk store are left out.
+    ///
+    /// [`instance`]: crate::instance
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (3, 3), sprite_index: 2, ..Default::default() }).unwrap();
+    ///
+    /// let data = tilemap.to_data();
+    /// assert!(data.contains_chunk((0, 0)));
+    /// assert_eq!(data.get_tile((3, 3), 0).unwrap().index, 2);
+    /// ```
+    #[cfg(feature = "render")]
+    pub fn to_data(&self) -> TilemapData {
+        TilemapData {
+            name: self.name.clone(),
+            topology: self.topology,
+            chunk_dimensions: self.chunk_dimensions,
+            tile_dimensions: self.tile_dimensions,
+            translation_origin: self.translation_origin,
+            texture_atlas: self.texture_atlas.clone(),
+            layers: self.layers.clone(),
+            chunks: self.chunks.clone(),
+            color: self.color,
+            opacity: self.opacity,
+            render_order: self.render_order,
+            render_layers: self.render_layers,
+            pipeline: self.chunk_pipeline_handle(),
+            palette: ChunkPalette::from_tilemap(self),
+            #[cfg(feature = "lighting")]
+            normal_map: self.normal_map.clone(),
+            sprite_rects: self.sprite_rects.clone(),
+            wall_sprites: self.wall_sprites.clone(),
+            channels: self.channels.clone(),
+            occlusions: (0..self.layers.len())
+                .map(|z_order| self.layer_occlusion(z_order))
+                .collect(),
+        }
+    }
+
+    /// Puts the chunks of the tilemap and their tiles back to a snapshot.
+    ///
+    /// Chunks which did not change since the snapshot are left as they are.
+    /// The others are replaced by those of the snapshot, keeping their layer
+    /// entities, and their meshes and colliders are rebuilt when the tilemap
+    /// is next updated. Chunks which the snapshot does not have are removed
+    /// and those which it has are inserted again. With tracked changes, the
+    /// restored chunks and tiles are changes too.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// let snapshot = tilemap.snapshot();
+    ///
+    /// tilemap.insert_tile(Tile { point: (2, 2), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.remove_chunk((0, 0)).unwrap();
+    /// tilemap.insert_chunk((1, 0)).unwrap();
+    ///
+    /// tilemap.restore(&snapshot).unwrap();
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert!(!tilemap.contains_chunk((1, 0)));
+    /// assert_eq!(tilemap.get_tile((2, 2), 0), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a chunk which the snapshot does not have can not be
+    /// removed.
+    pub fn restore(&mut self, snapshot: &TilemapSnapshot) -> TilemapResult<()> {
+        let removed: Vec<Point2> = self
+            .chunks
+            .keys()
+            .filter(|point| !snapshot.chunks.contains_key(point))
+            .copied()
+            .collect();
+        for point in in_order(removed, self.deterministic, |point| *point) {
+            #[cfg(feature = "serialize")]
+            if snapshot.unloaded_chunks.contains(&point) {
+                continue;
+            }
+            self.remove_chunk(point)?;
+        }
+
+        let chunk_dimensions = self.chunk_dimensions;
+        let chunks = in_order(snapshot.chunks.iter(), self.deterministic, |(point, _)| {
+            **point
+        });
+        for (point, chunk) in chunks.into_iter() {
+            let current = self.chunks.get(point);
+            if let Some(current) = current {
+                if Arc::ptr_eq(current, chunk) {
+                    continue;
+                }
+            }
+            let mut restored = chunk.clone();
+            Arc::make_mut(&mut restored).copy_spawned_state(current.map(|chunk| &**chunk));
+            let previous_tiles = current
+                .map(|chunk| chunk.tiles(chunk_dimensions))
+                .unwrap_or_default();
+            let tiles = restored.tiles(chunk_dimensions);
+            let mut layers = HashMap::default();
+            for z_order in 0..self.layers.len() {
+                if let Some(entity) = restored.get_entity(z_order) {
+                    layers.insert(z_order, entity);
+                }
+            }
+            #[cfg(feature = "serialize")]
+            self.unloaded_chunks.remove(point);
+            let inserted = self.chunks.insert(*point, restored).is_none();
+
+            if self.changes.is_some() {
+                for tile in previous_tiles.iter().chain(tiles.iter()) {
+                    let tile_point = tile_point_to_point(chunk_dimensions, *point, tile.point);
+                    self.changed_tiles.insert((tile_point, tile.z_order));
+                }
+            }
+            if let Some(changes) = &mut self.changes {
+                if inserted {
+                    changes.insert_chunk(*point);
+                }
+            }
+            self.modified_chunks.insert(*point);
+            for navmesh in self.navmeshes.values_mut() {
+                navmesh.mark_dirty(*point);
+            }
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+            self.collision_events
+                .send(TilemapCollisionEvent::Despawned {
+                    chunk_point: *point,
+                    tiles: previous_tiles,
+                });
+            self.collision_events.send(TilemapCollisionEvent::Spawned {
+                chunk_point: *point,
+                tiles,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sorts tiles into the chunks they belong to.
+    fn sort_tiles_to_chunks<P, I>(
+        &mut self,
+        tiles: I,
+    ) -> TilemapResult<HashMap<Point2, Vec<Tile<Point2>>>>
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = Tile<P>>,
+    {
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+
+        let mut chunk_map: HashMap<Point2, Vec<Tile<Point2>>> = HashMap::default();
+        for tile in tiles.into_iter() {
+            let global_tile_point: Point2 = tile.point.into();
+            let chunk_point: Point2 = self.point_to_chunk_point(global_tile_point).into();
+            if self.static_chunks.contains(&chunk_point)
+                || self.static_layers.contains(&tile.z_order)
+            {
+                return Err(ErrorKind::StaticTiles(chunk_point, tile.z_order).into());
+            }
+
+            if let Some(layer) = self.layers.get(tile.z_order) {
+                if layer.as_ref().is_none() {
+                    self.add_layer(TilemapLayer::default(), tile.z_order)?;
+                }
+            } else {
+                return Err(ErrorKind::LayerDoesNotExist(tile.z_order).into());
+            }
+
+            let tile_point = Point2::new(
+                global_tile_point.x - (width * chunk_point.x) + (width / 2),
+                global_tile_point.y - (height * chunk_point.y) + (height / 2),
+            );
+
+            let chunk_tile: Tile<Point2> = Tile {
+                point: tile_point,
+                z_order: tile.z_order,
+                sprite_index: tile.sprite_index,
+                tint: tile.tint,
+                extra: tile.extra,
+                offset: tile.offset,
+            };
+            if let Some(tiles) = chunk_map.get_mut(&chunk_point) {
+                tiles.push(chunk_tile);
+            } else {
+                let tiles = vec![chunk_tile];
+                chunk_map.insert(chunk_point, tiles);
+            }
+        }
+        Ok(chunk_map)
+    }
+
+    /// Sets many tiles, creating new chunks if needed.
+    ///
+    /// If setting a single tile is more preferable, then use the [`insert_tile`]
+    /// method instead.
+    ///
+    /// If the chunk does not yet exist, it will create a new one automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given coordinate or index is out of bounds, the
+    /// layer or chunk does not exist. If either the layer or chunk error occurs
+    /// then creating what is missing will resolve it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .dimensions(1, 1)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// let mut tiles = vec![
+    ///     Tile { point: (1, 1), sprite_index: 0, ..Default::default() },
+    ///     Tile { point: (2, 2), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (3, 3), sprite_index: 2, ..Default::default() },
+    /// ];
+    ///
+    /// // Set multiple tiles and unwrap the result
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((1, 1), 0), Some(&RawTile { index: 0, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// assert_eq!(tilemap.get_tile((2, 2), 0), Some(&RawTile { index: 1, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// assert_eq!(tilemap.get_tile((3, 3), 0), Some(&RawTile { index: 2, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// assert_eq!(tilemap.get_tile((4, 4), 0), None);
+    /// ```
+    ///
+    /// [`insert_tile`]: Tilemap::insert_tile
+    pub fn insert_tiles<P, I>(&mut self, tiles: I) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = Tile<P>>,
+    {
+        let chunk_map = self.sort_tiles_to_chunks(tiles)?;
+        let mut ruled_tiles = Vec::new();
+        for (chunk_point, tiles) in in_order(chunk_map, self.deterministic, |(point, _)| *point) {
+            #[cfg(feature = "serialize")]
+            self.load_chunk(chunk_point)?;
+            // Is there a better way to do this? Clippy hates if I don't do it
+            // like this talking about constructing regardless yet, here it is,
+            // copying stuff regardless because it doesn't like self in the
+            // `FnOnce`.
+            let layer_kinds = self.chunk_layer_kinds(chunk_point);
+            let empty_tiles = self.empty_tiles();
+            let chunk_dimensions = self.chunk_dimensions;
+            if !self.chunks.contains_key(&chunk_point) {
+                if let Some(changes) = &mut self.changes {
+                    if self.auto_flags.contains(AutoFlags::AUTO_CHUNK) {
+                        changes.insert_chunk(chunk_point);
+                    }
+                }
+            }
+            let chunk = if self.auto_flags.contains(AutoFlags::AUTO_CHUNK) {
+                Arc::make_mut(self.chunks.entry(chunk_point).or_insert_with(|| {
+                    let mut chunk = Chunk::new(chunk_point, &layer_kinds, chunk_dimensions);
+                    let area = chunk_dimensions.area() as usize;
+                    for (z_order, empty_tile) in empty_tiles {
+                        chunk.fill_empty_tiles(z_order, empty_tile, area);
+                    }
+                    Arc::new(chunk)
+                }))
+            } else {
+                match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+                    Some(c) => c,
+                    None => return Err(ErrorKind::MissingChunk.into()),
+                }
+            };
+
+            let mut layers = HashMap::default();
+            for tile in tiles.iter() {
+                let index = self.chunk_dimensions.encode_point_unchecked(tile.point);
+                chunk.set_tile(index, *tile);
+                if let Some(entity) = chunk.get_entity(tile.z_order) {
+                    layers.entry(tile.z_order).or_insert(entity);
+                }
+                let point = tile_point_to_point(chunk_dimensions, chunk_point, tile.point);
+                if self.changes.is_some() {
+                    self.changed_tiles.insert((point, tile.z_order));
+                }
+                if self
+                    .tile_rules
+                    .get(&tile.z_order)
+                    .is_some_and(|rules| rules.auto)
+                {
+                    ruled_tiles.push((point, tile.z_order));
+                }
+            }
+            self.modified_chunks.insert(chunk_point);
+            for navmesh in self.navmeshes.values_mut() {
+                navmesh.mark_dirty(chunk_point);
+            }
+
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+            self.collision_events
+                .send(TilemapCollisionEvent::Spawned { chunk_point, tiles });
+        }
+
+        self.apply_tile_rules_around(ruled_tiles)
+    }
+
+    /// Sets a single tile at a coordinate position, creating a chunk if necessary.
+    ///
+    /// If you are setting more than one tile at a time, it is highly
+    /// recommended not to run this method! If that is preferred, do use
+    /// [`insert_tiles`] instead. Every single tile that is created creates a new
+    /// event. With bulk tiles, it creates 1 event for all.
+    ///
+    /// If the chunk does not yet exist, it will create a new one automatically.
+    ///
+    /// [`insert_tiles`]: Tilemap::insert_tiles
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// let point = (9, 3);
+    /// let sprite_index = 3;
+    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    ///
+    /// assert!(tilemap.insert_tile(tile).is_ok());
+    /// assert_eq!(tilemap.get_tile((9, 3), 0), Some(&RawTile { index: 3, color: Color::WHITE, extra: 0, ..Default::default() }))
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given coordinate or index is out of bounds.
+    pub fn insert_tile<P: Into<Point2>>(&mut self, tile: Tile<P>) -> TilemapResult<()> {
+        let tiles = vec![tile];
+        self.insert_tiles(tiles)
+    }
+
+    /// Clears the tiles at the specified points from the tilemap.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    ///
+    /// let mut tiles = vec![
+    ///     Tile { point: (1, 1), ..Default::default() },
+    ///     Tile { point: (2, 2), ..Default::default() },
+    ///     Tile { point: (3, 3), ..Default::default() },
+    /// ];
+    ///
+    /// // Set multiple tiles and unwrap the result
+    /// assert!(tilemap.insert_tiles(tiles.clone()).is_ok());
+    ///
+    /// // Then later on... Do note that if this done in the same frame, the
+    /// // tiles will not even exist at all.
+    /// let mut to_remove = vec![
+    ///     ((1, 1), 0),
+    ///     ((2, 2), 0),
+    /// ];
+    ///
+    /// tilemap.clear_tiles(to_remove).unwrap();
+    /// assert_eq!(tilemap.get_tile((1, 1), 0), None);
+    /// assert_eq!(tilemap.get_tile((2, 2), 0), None);
+    /// assert_eq!(tilemap.get_tile((3, 3), 0), Some(&RawTile { index: 0, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An error can occure if the point is outside of the tilemap. This can
+    /// only happen if the tilemap has dimensions.
+    pub fn clear_tiles<P, I>(&mut self, points: I) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = (P, usize)>,
+    {
+        let mut tiles = Vec::new();
+        for (point, z_order) in points {
+            tiles.push(Tile {
+                point: point.into(),
+                sprite_index: RawTile::EMPTY.index,
+                z_order,
+                tint: RawTile::EMPTY.color,
+                extra: 0,
+                offset: Vec2::zero(),
+            });
+        }
+        let chunk_map = self.sort_tiles_to_chunks(tiles)?;
+        let empty_tiles = self.empty_tiles();
+        let mut layers = HashMap::default();
+        let mut ruled_tiles = Vec::new();
+        let mut cleared_tiles = HashSet::default();
+        for (chunk_point, tiles) in in_order(chunk_map, self.deterministic, |(point, _)| *point) {
+            #[cfg(feature = "serialize")]
+            self.load_chunk(chunk_point)?;
+            let chunk = match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+                Some(c) => c,
+                None => return Err(ErrorKind::MissingChunk.into()),
+            };
+            for tile in tiles.iter() {
+                let index = self.chunk_dimensions.encode_point_unchecked(tile.point);
+                chunk.remove_tile(index, tile.z_order);
+                let empty_tile = empty_tiles
+                    .iter()
+                    .find(|(z_order, _)| *z_order == tile.z_order);
+                if let Some((_, empty_tile)) = empty_tile {
+                    chunk.fill_tile(index, tile.z_order, *empty_tile);
+                }
+                if let Some(entity) = chunk.get_entity(tile.z_order) {
+                    layers.entry(tile.z_order).or_insert(entity);
+                }
+                let point = tile_point_to_point(self.chunk_dimensions, chunk_point, tile.point);
+                cleared_tiles.insert((point, tile.z_order));
+                if self.changes.is_some() {
+                    self.changed_tiles.insert((point, tile.z_order));
+                }
+                if self
+                    .tile_rules
+                    .get(&tile.z_order)
+                    .is_some_and(|rules| rules.auto)
+                {
+                    ruled_tiles.push((point, tile.z_order));
+                }
+            }
+            self.modified_chunks.insert(chunk_point);
+            for navmesh in self.navmeshes.values_mut() {
+                navmesh.mark_dirty(chunk_point);
+            }
+
+            self.collision_events
+                .send(TilemapCollisionEvent::Despawned { chunk_point, tiles });
+        }
+
+        self.chunk_events
+            .send(TilemapChunkEvent::Modified { layers });
+        self.unbind_tile_entities(
+            |point, z_order| cleared_tiles.contains(&(point, z_order)),
+            false,
+        );
+
+        self.apply_tile_rules_around(ruled_tiles)
+    }
+
+    /// Takes a global tile point and returns a tile point in a chunk.
+    fn point_to_tile_point(&self, point: Point2) -> Point2 {
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+        Point2::new(
+            point.x - (width * chunk_point.x) + (width / 2),
+            point.y - (height * chunk_point.y) + (height / 2),
+        )
+    }
+
+    /// Clear a single tile at the specified point from the tilemap.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
+    ///
+    /// let point = (3, 1);
+    /// let sprite_index = 1;
+    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    ///
+    /// // Set a single tile and unwrap the result
+    /// assert!(tilemap.insert_tile(tile).is_ok());
+    ///
+    /// // Later on...
+    /// assert!(tilemap.clear_tile(point, 0).is_ok());
+    /// assert_eq!(tilemap.get_tile((3, 1), 0), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An error can occure if the point is outside of the tilemap. This can
+    /// only happen if the tilemap has dimensions.
+    pub fn clear_tile<P>(&mut self, point: P, z_order: usize) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+    {
+        let points = vec![(point, z_order)];
+        self.clear_tiles(points)
+    }
+
+    /// Clears every tile of a layer from every chunk of the tilemap.
+    ///
+    /// The tiles of each chunk are cleared at once instead of one by one,
+    /// and the mesh and colliders of each chunk layer are built again once.
+    /// Chunks which were unloaded to the chunk store are loaded first. Dense
+    /// layers with an [`empty_tile`] are filled with it again.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), z_order: 1, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (20, 20), z_order: 1, ..Default::default() }).unwrap();
+    ///
+    /// tilemap.clear_layer(1).unwrap();
+    /// assert!(tilemap.get_tile((0, 0), 1).is_none());
+    /// assert!(tilemap.get_tile((20, 20), 1).is_none());
+    /// assert!(tilemap.get_tile((0, 0), 0).is_some());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not exist, or a chunk could not be loaded from the
+    /// chunk store, an error is returned.
+    ///
+    /// [`empty_tile`]: TilemapLayer::empty_tile
+    pub fn clear_layer(&mut self, z_order: usize) -> TilemapResult<()> {
+        if self
+            .layers
+            .get(z_order)
+            .filter(|layer| layer.is_some())
+            .is_none()
+        {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        #[cfg(feature = "serialize")]
+        for point in self.unloaded_chunks.clone().into_iter() {
+            self.load_chunk(point)?;
+        }
+        let points: Vec<Point2> = self.chunks.keys().copied().collect();
+        let mut ruled_tiles = Vec::new();
+        for point in in_order(points, self.deterministic, |point| *point) {
+            ruled_tiles.extend(self.clear_chunk_layers(point, &[z_order]));
+        }
+        self.apply_tile_rules_around(ruled_tiles)
+    }
+
+    /// Clears every tile of every layer of a chunk.
+    ///
+    /// The tiles are cleared at once instead of one by one, and the meshes
+    /// and colliders of the chunk are built again once. The chunk itself is
+    /// kept, use [`remove_chunk`] to remove it.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (20, 20), ..Default::default() }).unwrap();
+    ///
+    /// tilemap.clear_chunk((0, 0)).unwrap();
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert!(tilemap.get_tile((0, 0), 0).is_none());
+    /// assert!(tilemap.get_tile((20, 20), 0).is_some());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the chunk does not exist, or could not be loaded from the chunk
+    /// store, an error is returned.
+    ///
+    /// [`remove_chunk`]: Tilemap::remove_chunk
+    pub fn clear_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        #[cfg(feature = "serialize")]
+        self.load_chunk(point)?;
+        if !self.chunks.contains_key(&point) {
+            return Err(ErrorKind::MissingChunk.into());
+        }
+        let z_orders: Vec<usize> = (0..self.layers.len()).collect();
+        let ruled_tiles = self.clear_chunk_layers(point, &z_orders);
+        self.apply_tile_rules_around(ruled_tiles)
+    }
+
+    /// Clears every tile of every layer of every chunk, keeping the chunks
+    /// and layers.
+    ///
+    /// The tiles of each chunk are cleared at once instead of one by one,
+    /// and the meshes and colliders of each chunk are built again once.
+    /// Chunks which were unloaded to the chunk store are loaded first.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (100, 0), ..Default::default() }).unwrap();
+    ///
+    /// tilemap.clear().unwrap();
+    /// assert!(tilemap.get_tile((0, 0), 0).is_none());
+    /// assert!(tilemap.get_tile((100, 0), 0).is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a chunk could not be loaded from the chunk store, an error is
+    /// returned.
+    pub fn clear(&mut self) -> TilemapResult<()> {
+        #[cfg(feature = "serialize")]
+        for point in self.unloaded_chunks.clone().into_iter() {
+            self.load_chunk(point)?;
+        }
+        let points: Vec<Point2> = self.chunks.keys().copied().collect();
+        let z_orders: Vec<usize> = (0..self.layers.len()).collect();
+        let mut ruled_tiles = Vec::new();
+        for point in in_order(points, self.deterministic, |point| *point) {
+            ruled_tiles.extend(self.clear_chunk_layers(point, &z_orders));
+        }
+        self.apply_tile_rules_around(ruled_tiles)
+    }
+
+    /// Clears every tile of the layers of a chunk in memory at once, and
+    /// marks their meshes and colliders to be built again. Returns the points
+    /// of the cleared tiles whose layers have automatic tile rules.
+    fn clear_chunk_layers(
+        &mut self,
+        chunk_point: Point2,
+        z_orders: &[usize],
+    ) -> Vec<(Point2, usize)> {
+        let area = self.chunk_dimensions.area() as usize;
+        let empty_tiles = self.empty_tiles();
+        let chunk = match self.chunks.get_mut(&chunk_point) {
+            Some(chunk) => chunk,
+            None => return Vec::new(),
+        };
+        let cleared = z_orders.iter().any(|z_order| {
+            !chunk
+                .get_tile_indices(*z_order)
+                .unwrap_or_default()
+                .is_empty()
+        });
+        if !cleared {
+            return Vec::new();
+        }
+        let chunk = Arc::make_mut(chunk);
+        let mut layers = HashMap::default();
+        let mut tiles = Vec::new();
+        let mut ruled_tiles = Vec::new();
+        for z_order in z_orders.iter().copied() {
+            let indices = chunk.clear_layer(z_order);
+            if indices.is_empty() {
+                continue;
+            }
+            let empty_tile = empty_tiles.iter().find(|(z, _)| *z == z_order);
+            if let Some((_, empty_tile)) = empty_tile {
+                chunk.fill_empty_tiles(z_order, *empty_tile, area);
+            }
+            if let Some(entity) = chunk.get_entity(z_order) {
+                layers.insert(z_order, entity);
+            }
+            let ruled = self
+                .tile_rules
+                .get(&z_order)
+                .filter(|rules| rules.auto)
+                .is_some();
+            for index in indices {
+                let tile_point = self.chunk_dimensions.decode_point_unchecked(index);
+                let point = tile_point_to_point(self.chunk_dimensions, chunk_point, tile_point);
+                if self.changes.is_some() {
+                    self.changed_tiles.insert((point, z_order));
+                }
+                if ruled {
+                    ruled_tiles.push((point, z_order));
+                }
+                tiles.push(Tile {
+                    point: tile_point,
+                    sprite_index: RawTile::EMPTY.index,
+                    z_order,
+                    tint: RawTile::EMPTY.color,
+                    extra: 0,
+                    offset: Vec2::zero(),
+                });
+            }
+        }
+        self.modified_chunks.insert(chunk_point);
+        for navmesh in self.navmeshes.values_mut() {
+            navmesh.mark_dirty(chunk_point);
+        }
+        self.collision_events
+            .send(TilemapCollisionEvent::Despawned { chunk_point, tiles });
+        self.chunk_events
+            .send(TilemapChunkEvent::Modified { layers });
+        let chunk_dimensions = self.chunk_dimensions;
+        self.unbind_tile_entities(
+            |point, z_order| {
+                z_orders.contains(&z_order)
+                    && chunk_point_of(chunk_dimensions, point) == chunk_point
+            },
+            false,
+        );
+
+        ruled_tiles
+    }
+
+    /// Gets a raw tile from a given point and z order.
+    ///
+    /// This is different thant he usual [`Tile`] struct in that it only
+    /// contains the sprite index and the tint.
+    ///
+    /// [`Tile`]: crate::tile::Tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// let point = (9, 3);
+    /// let sprite_index = 3;
+    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    ///
+    /// assert!(tilemap.insert_tile(tile).is_ok());
+    /// assert_eq!(tilemap.get_tile((9, 3), 0), Some(&RawTile { index: 3, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// assert_eq!(tilemap.get_tile((10, 4), 0), None);
+    /// ```
+    pub fn get_tile<P>(&self, point: P, z_order: usize) -> Option<&RawTile>
+    where
+        P: Into<Point2>,
+    {
+        let point: Point2 = point.into();
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let chunk = self.chunks.get(&chunk_point)?;
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        chunk.get_tile(z_order, index)
+    }
+
+    /// Returns `true` if there is a tile at the point and z order.
+    ///
+    /// Chunks which were written to the chunk store are not read back, so
+    /// their tiles are not found.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (9, 3), ..Default::default() }).unwrap();
+    ///
+    /// assert!(tilemap.contains_tile((9, 3), 0));
+    /// assert!(!tilemap.contains_tile((9, 4), 0));
+    /// assert!(!tilemap.contains_tile((9, 3), 1));
+    /// ```
+    pub fn contains_tile<P: Into<Point2>>(&self, point: P, z_order: usize) -> bool {
+        self.get_tile(point, z_order).is_some()
+    }
+
+    /// Returns an iterator of the neighbours of a tile with their raw tiles at
+    /// the z order, or `None` for the neighbours without a tile.
+    ///
+    /// The neighbours in other chunks are found as well, but those outside of
+    /// the dimensions of the tilemap are left out. Neighbours are returned
+    /// counter clockwise, starting from the positive x axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tilemap::Neighborhood};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .dimensions(1, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // The chunk covers the points from -2 to 1.
+    /// tilemap.insert_tile(Tile { point: (0, 1), sprite_index: 3, ..Default::default() }).unwrap();
+    ///
+    /// let neighbors: Vec<_> = tilemap.neighbors((0, 0), 0, Neighborhood::Four).collect();
+    /// assert_eq!(neighbors.len(), 4);
+    /// assert_eq!(neighbors[1].1.unwrap().index, 3);
+    /// assert!(neighbors[0].1.is_none());
+    ///
+    /// // The corner of the map has only 3 neighbours.
+    /// assert_eq!(tilemap.neighbors((1, 1), 0, Neighborhood::Eight).count(), 3);
+    /// ```
+    pub fn neighbors<P: Into<Point2>>(
+        &self,
+        point: P,
+        z_order: usize,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (Point2, Option<&RawTile>)> {
+        let point: Point2 = point.into();
+        let offset = |(x, y): &(i32, i32)| Point2::new(point.x + x, point.y + y);
+        let points: Vec<Point2> = match neighborhood {
+            Neighborhood::Four => FOUR_NEIGHBORS.iter().map(offset).collect(),
+            Neighborhood::Eight => EIGHT_NEIGHBORS.iter().map(offset).collect(),
+            Neighborhood::Hex => {
+                let axial = hex::offset_to_axial(point, self.topology);
+                hex::hex_neighbours(axial)
+                    .iter()
+                    .map(|neighbor| hex::axial_to_offset(*neighbor, self.topology))
+                    .collect()
+            }
+        };
+        points
+            .into_iter()
+            .filter(move |point| match &self.dimensions {
+                Some(dimensions) => dimensions
+                    .check_point(self.point_to_chunk_point(*point).into())
+                    .is_ok(),
+                None => true,
+            })
+            .map(move |point| (point, self.get_tile(point, z_order)))
+    }
+
+    /// Gets a mutable raw tile from a given point and z order.
+    ///
+    /// This is different thant he usual [`Tile`] struct in that it only
+    /// contains the sprite index and the tint.
+    ///
+    /// [`Tile`]: crate::tile::Tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    ///
+    /// let point = (2, 5);
+    /// let sprite_index = 2;
+    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    ///
+    /// assert!(tilemap.insert_tile(tile).is_ok());
+    /// assert_eq!(tilemap.get_tile_mut((2, 5), 0), Some(&mut RawTile { index: 2, color: Color::WHITE, extra: 0, ..Default::default() }));
+    /// assert_eq!(tilemap.get_tile_mut((1, 4), 0), None);
+    /// ```
+    pub fn get_tile_mut<P>(&mut self, point: P, z_order: usize) -> Option<&mut RawTile>
+    where
+        P: Into<Point2>,
+    {
+        let point: Point2 = point.into();
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        if self.static_chunks.contains(&chunk_point) || self.static_layers.contains(&z_order) {
+            return None;
+        }
+        let tile_point = self.point_to_tile_point(point);
+        let chunk = self.chunks.get_mut(&chunk_point).map(Arc::make_mut)?;
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        self.modified_chunks.insert(chunk_point);
+        if self.changes.is_some() {
+            self.changed_tiles.insert((point, z_order));
+        }
+        for navmesh in self.navmeshes.values_mut() {
+            navmesh.mark_dirty(chunk_point);
+        }
+        let mut layers = HashMap::default();
+        if let Some(entity) = chunk.get_entity(z_order) {
+            layers.insert(z_order, entity);
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+        }
+        chunk.get_tile_mut(z_order, index)
+    }
+
+    /// Adds a vertex attribute channel to the layer at the z order, or
+    /// changes the kind of the channel if the layer already has it.
+    ///
+    /// The values of the tiles are put in the chunk meshes of the layer as the
+    /// vertex attribute with the name, see the [`channel`] module.
+    ///
+    /// [`channel`]: crate::channel
+    ///
+    /// # Errors
+    ///
+    /// If the z order is beyond the layers of the tilemap, an error is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{channel::ChannelKind, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.add_channel(0, "Vertex_Team_Color", ChannelKind::Vec4).is_ok());
+    /// assert!(tilemap.add_channel(100, "Vertex_Team_Color", ChannelKind::Vec4).is_err());
+    /// assert_eq!(tilemap.channels(0)[0].kind, ChannelKind::Vec4);
+    /// ```
+    pub fn add_channel<S: Into<String>>(
+        &mut self,
+        z_order: usize,
+        name: S,
+        kind: ChannelKind,
+    ) -> TilemapResult<()> {
+        if self.layers.get(z_order).is_none() {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        let name = name.into();
+        let channels = self.channels.entry(z_order).or_default();
+        if let Some(channel) = channels.iter_mut().find(|channel| channel.name == name) {
+            channel.kind = kind;
+        } else {
+            channels.push(TileChannel { name, kind });
+        }
+
+        // Every mesh of the layer gets the new attribute.
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (point, chunk) in chunks.into_iter() {
+            if let Some(entity) = chunk.get_entity(z_order) {
+                let mut layers = HashMap::default();
+                layers.insert(z_order, entity);
+                self.chunk_events
+                    .send(TilemapChunkEvent::Modified { layers });
+            }
+            self.modified_chunks.insert(*point);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the vertex attribute channels of the layer at the z order.
+    pub fn channels(&self, z_order: usize) -> &[TileChannel] {
+        match self.channels.get(&z_order) {
+            Some(channels) => channels,
+            None => &[],
+        }
+    }
+
+    /// Sets the value of the tile at a point in a channel of its layer.
+    ///
+    /// # Errors
+    ///
+    /// If the layer does not have the channel, if the value is of another
+    /// kind than the channel or if the chunk does not exist, an error is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{channel::ChannelKind, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.add_channel(0, "Vertex_Growth", ChannelKind::Float).unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    ///
+    /// assert!(tilemap.set_tile_channel((0, 0), 0, "Vertex_Growth", 2.0).is_ok());
+    /// assert!(tilemap.set_tile_channel((0, 0), 0, "Vertex_Growth", [2.0; 4]).is_err());
+    /// assert!(tilemap.set_tile_channel((0, 0), 0, "Vertex_Wetness", 2.0).is_err());
+    /// ```
+    pub fn set_tile_channel<P, V>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        name: &str,
+        value: V,
+    ) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        V: Into<ChannelValue>,
+    {
+        let point: Point2 = point.into();
+        let value: ChannelValue = value.into();
+        let kind = match self
+            .channels(z_order)
+            .iter()
+            .find(|channel| channel.name == name)
+        {
+            Some(channel) => channel.kind,
+            None => return Err(ErrorKind::MissingChannel(name.to_string()).into()),
+        };
+        if value.kind() != kind {
+            return Err(ErrorKind::MismatchedChannel(name.to_string()).into());
+        }
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        if self.static_chunks.contains(&chunk_point) || self.static_layers.contains(&z_order) {
+            return Err(ErrorKind::StaticTiles(chunk_point, z_order).into());
+        }
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let chunk_dimensions = self.chunk_dimensions;
+        let chunk = match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+            Some(chunk) => chunk,
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        chunk.set_channel_value(
+            z_order,
+            name,
+            index,
+            value.to_components(),
+            chunk_dimensions,
+        );
+        self.modified_chunks.insert(chunk_point);
+        if let Some(entity) = chunk.get_entity(z_order) {
+            let mut layers = HashMap::default();
+            layers.insert(z_order, entity);
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the tile at a point in a channel of its layer,
+    /// which is zero if it was never set, or `None` if the layer does not
+    /// have the channel or the chunk does not exist.
+    pub fn tile_channel<P: Into<Point2>>(
+        &self,
+        point: P,
+        z_order: usize,
+        name: &str,
+    ) -> Option<ChannelValue> {
+        let point: Point2 = point.into();
+        let kind = self
+            .channels(z_order)
+            .iter()
+            .find(|channel| channel.name == name)?
+            .kind;
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let chunk = self.chunks.get(&chunk_point)?;
+        let components = chunk
+            .channel_value(z_order, name, index)
+            .unwrap_or([0.0; 4]);
+        Some(ChannelValue::from_components(kind, components))
+    }
+
+    /// Sets the rotation and scale of the tile at a point of a decoration
+    /// layer, which are baked into the corners of its quad in the chunk mesh.
+    ///
+    /// The tile is rotated and scaled around its middle, as though its sprite
+    /// is as big as the tile dimensions. The transform is kept until the tile
+    /// is removed, and setting the default transform clears it.
+    ///
+    /// # Errors
+    ///
+    /// If the layer is not a decoration layer, if the tiles are static, if
+    /// the chunk does not exist or if there is no tile at the point, an
+    /// error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tile::TileTransform};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Decoration, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 7, z_order: 1, ..Default::default() }).unwrap();
+    ///
+    /// // A pebble turned a little and shrunk to half of its size.
+    /// let pebble = TileTransform::new(0.3, Vec2::new(0.5, 0.5));
+    /// assert!(tilemap.set_tile_transform((1, 1), 1, pebble).is_ok());
+    /// assert_eq!(tilemap.tile_transform((1, 1), 1), Some(pebble));
+    ///
+    /// // Only decoration layers have transforms.
+    /// tilemap.insert_tile(Tile { point: (1, 1), ..Default::default() }).unwrap();
+    /// assert!(tilemap.set_tile_transform((1, 1), 0, pebble).is_err());
+    /// ```
+    pub fn set_tile_transform<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        transform: TileTransform,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        match self.layers.get(z_order) {
+            Some(Some(layer)) if layer.kind == LayerKind::Decoration => {}
+            Some(Some(_)) => return Err(ErrorKind::NotDecorationLayer(z_order).into()),
+            _ => return Err(ErrorKind::LayerDoesNotExist(z_order).into()),
+        }
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        if self.static_chunks.contains(&chunk_point) || self.static_layers.contains(&z_order) {
+            return Err(ErrorKind::StaticTiles(chunk_point, z_order).into());
+        }
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let chunk = match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+            Some(chunk) => chunk,
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        if !chunk.set_tile_transform(z_order, index, transform) {
+            return Err(ErrorKind::MissingTile(point, z_order).into());
+        }
+        self.modified_chunks.insert(chunk_point);
+        if let Some(entity) = chunk.get_entity(z_order) {
+            let mut layers = HashMap::default();
+            layers.insert(z_order, entity);
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the rotation and scale of the tile at a point of a decoration
+    /// layer, or `None` if the tile has the default transform, if there is no
+    /// tile or if the layer is not a decoration layer.
+    pub fn tile_transform<P: Into<Point2>>(
+        &self,
+        point: P,
+        z_order: usize,
+    ) -> Option<TileTransform> {
+        let point: Point2 = point.into();
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        self.chunks
+            .get(&chunk_point)?
+            .tile_transform(z_order, index)
+    }
+
+    /// Copies the tiles of every layer from one point to another, both
+    /// included, into a region which can be pasted with [`paste_region`],
+    /// see the [`region`] module.
+    ///
+    /// [`paste_region`]: Tilemap::paste_region
+    /// [`region`]: crate::region
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (3, 4), sprite_index: 2, ..Default::default() }).unwrap();
+    ///
+    /// // The corners can be given in any order.
+    /// let region = tilemap.copy_region((4, 5), (2, 2));
+    /// assert_eq!(region.dimensions(), (3, 4));
+    /// assert_eq!(region.get((1, 2), 0).unwrap().index, 2);
+    /// ```
+    pub fn copy_region<P: Into<Point2>>(&self, from: P, to: P) -> TileRegion {
+        let (from, to): (Point2, Point2) = (from.into(), to.into());
+        let min = Point2::new(from.x.min(to.x), from.y.min(to.y));
+        let max = Point2::new(from.x.max(to.x), from.y.max(to.y));
+        let mut tiles = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let point = Point2::new(x, y);
+                for z_order in 0..self.layers.len() {
+                    if let Some(tile) = self.get_tile(point, z_order) {
+                        tiles.push(RegionTile {
+                            point: point - min,
+                            z_order,
+                            tile: *tile,
+                            transform: self.tile_transform(point, z_order),
+                        });
+                    }
+                }
+            }
+        }
+        TileRegion::new(
+            (max.x - min.x + 1) as u32,
+            (max.y - min.y + 1) as u32,
+            tiles,
+        )
+    }
+
+    /// Edits the tiles of a layer from one point to another, both included,
+    /// with a closure which is given every point with its tile, or `None` if
+    /// it has none.
+    ///
+    /// The closure sets, changes or clears each tile in place, and only the
+    /// tiles which changed are written back, all at once, so the meshes and
+    /// colliders of each chunk are built again once. Points are visited row
+    /// by row from the lowest. Chunks which were unloaded to the chunk store
+    /// are loaded first.
+    ///
+    /// # Errors
+    ///
+    /// If a tile is set where a chunk does not exist and can not be made, or
+    /// on a layer which can not be added, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::RawTile, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A floor of sprite 1 with a checkerboard of sprite 2.
+    /// tilemap.edit_rect((0, 0), (3, 3), 0, |point, tile| {
+    ///     let index = if (point.x + point.y) % 2 == 0 { 1 } else { 2 };
+    ///     *tile = Some(RawTile { index, ..Default::default() });
+    /// }).unwrap();
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 2);
+    ///
+    /// // A splash of damage clears the tiles of sprite 2 near the corner.
+    /// tilemap.edit_rect((0, 0), (1, 1), 0, |_, tile| {
+    ///     if tile.filter(|tile| tile.index == 2).is_some() {
+    ///         *tile = None;
+    ///     }
+    /// }).unwrap();
+    /// assert!(tilemap.get_tile((1, 0), 0).is_none());
+    /// assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 1);
+    /// assert_eq!(tilemap.get_tile((3, 0), 0).unwrap().index, 2);
+    /// ```
+    pub fn edit_rect<P, F>(
+        &mut self,
+        from: P,
+        to: P,
+        z_order: usize,
+        mut edit: F,
+    ) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        F: FnMut(Point2, &mut Option<RawTile>),
+    {
+        let (from, to): (Point2, Point2) = (from.into(), to.into());
+        let min = Point2::new(from.x.min(to.x), from.y.min(to.y));
+        let max = Point2::new(from.x.max(to.x), from.y.max(to.y));
+        #[cfg(feature = "serialize")]
+        {
+            let mut chunk_points = HashSet::default();
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    chunk_points.insert(self.point_to_chunk_point((x, y)));
+                }
+            }
+            for chunk_point in chunk_points.into_iter() {
+                self.load_chunk(chunk_point)?;
+            }
+        }
+        let mut set = Vec::new();
+        let mut cleared = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let point = Point2::new(x, y);
+                let previous = self.get_tile(point, z_order).copied();
+                let mut tile = previous;
+                edit(point, &mut tile);
+                if tile == previous {
+                    continue;
+                }
+                match tile {
+                    Some(tile) => set.push(Tile {
+                        point,
+                        sprite_index: tile.index,
+                        z_order,
+                        tint: tile.color,
+                        extra: tile.extra,
+                        offset: tile.offset,
+                    }),
+                    None => cleared.push((point, z_order)),
+                }
+            }
+        }
+        if !cleared.is_empty() {
+            self.clear_tiles(cleared)?;
+        }
+        if !set.is_empty() {
+            self.insert_tiles(set)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pastes the tiles of a region with the lower left corner of the region
+    /// at a point. The tiles of the tilemap where the region has none are
+    /// kept.
+    ///
+    /// # Errors
+    ///
+    /// If a layer of the region does not exist, if a chunk does not exist
+    /// and can not be made or if the tiles are static, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 2, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 3, ..Default::default() }).unwrap();
+    ///
+    /// // Mirrored from left to right.
+    /// let region = tilemap.copy_region((0, 0), (1, 0)).flipped_x();
+    /// tilemap.paste_region((5, 5), &region).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((5, 5), 0).unwrap().index, 3);
+    /// assert_eq!(tilemap.get_tile((6, 5), 0).unwrap().index, 2);
+    /// ```
+    pub fn paste_region<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        region: &TileRegion,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        let tiles = region.tiles().iter().map(|tile| Tile {
+            point: point + tile.point,
+            z_order: tile.z_order,
+            sprite_index: tile.tile.index,
+            tint: tile.tile.color,
+            extra: tile.tile.extra,
+            offset: tile.tile.offset,
+        });
+        self.insert_tiles(tiles)?;
+        for tile in region.tiles() {
+            if let Some(transform) = tile.transform {
+                self.set_tile_transform(point + tile.point, tile.z_order, transform)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the elevation of the ground at a point, in pixels, which raises
+    /// the tiles of every layer at the point by as much when they are drawn.
+    ///
+    /// Cliffs of iso maps are tiles raised above the tiles in front of them,
+    /// and ramps are sprites which slope up to the elevation of the tiles
+    /// behind them, see [`iso`]. Tiles are still picked and sorted by their
+    /// points as though they were not raised. Setting an elevation of zero
+    /// clears it.
+    ///
+    /// # Errors
+    ///
+    /// If the chunk does not exist, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .topology(GridTopology::IsoDiamond)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(64, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // A plateau one step of 16 pixels above the ground around it.
+    /// for y in 0..2 {
+    ///     for x in 0..2 {
+    ///         tilemap.insert_tile(Tile { point: (x, y), sprite_index: 1, ..Default::default() }).unwrap();
+    ///         tilemap.set_height((x, y), 16.0).unwrap();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(tilemap.height_at((1, 1)), 16.0);
+    /// assert_eq!(tilemap.height_at((2, 1)), 0.0);
+    /// ```
+    ///
+    /// [`iso`]: crate::iso
+    pub fn set_height<P: Into<Point2>>(&mut self, point: P, height: f32) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let chunk = match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+            Some(chunk) => chunk,
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        chunk.set_height(index, height);
+        let mut layers = HashMap::default();
+        for z_order in 0..self.layers.len() {
+            if let Some(entity) = chunk.get_entity(z_order) {
+                layers.insert(z_order, entity);
+            }
+        }
+        self.modified_chunks.insert(chunk_point);
+        self.chunk_events
+            .send(TilemapChunkEvent::Modified { layers });
+
+        Ok(())
+    }
+
+    /// Returns the elevation of the ground at a point, in pixels, which is
+    /// zero if it was never set or the chunk does not exist.
+    pub fn height_at<P: Into<Point2>>(&self, point: P) -> f32 {
+        let point: Point2 = point.into();
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        self.chunks
+            .get(&chunk_point)
+            .map(|chunk| chunk.height(index))
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the vertex attribute channels of every layer, at their z
+    /// order.
+    #[cfg(feature = "render")]
+    pub(crate) fn layer_channels(&self) -> &HashMap<usize, Vec<TileChannel>> {
+        &self.channels
+    }
+
+    /// Adds an animation clip by its name, returning the clip which had the
+    /// name before if there was one.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::AnimationClip, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let clip = AnimationClip::new(vec![0, 1], 0.5);
+    /// assert_eq!(tilemap.add_animation_clip("torch", clip.clone()), None);
+    /// assert_eq!(tilemap.animation_clip("torch"), Some(&clip));
+    /// ```
+    pub fn add_animation_clip<S: Into<String>>(
+        &mut self,
+        name: S,
+        clip: AnimationClip,
+    ) -> Option<AnimationClip> {
+        self.animation_clips.insert(name.into(), clip)
+    }
+
+    /// Removes an animation clip, returning it if it existed. Tiles which
+    /// were playing it keep their current sprite until the clip is added
+    /// again.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::AnimationClip, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.add_animation_clip("torch", AnimationClip::new(vec![0, 1], 0.5));
+    ///
+    /// assert!(tilemap.remove_animation_clip("torch").is_some());
+    /// assert!(tilemap.animation_clip("torch").is_none());
+    /// ```
+    pub fn remove_animation_clip(&mut self, name: &str) -> Option<AnimationClip> {
+        self.animation_clips.remove(name)
+    }
+
+    /// Returns a reference to an animation clip by its name, if it exists.
+    pub fn animation_clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.animation_clips.get(name)
+    }
+
+    /// Plays an animation clip on the tile at a point. The tile is set to the
+    /// frame it starts at right away, which is the first frame unless the clip
+    /// has another [`AnimationPhase`], keeping its tint, extra data and
+    /// offset if there already was a tile.
+    ///
+    /// [`AnimationPhase`]: crate::animation::AnimationPhase
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clip does not exist, or if the tile can not be
+    /// inserted for the same reasons as [`insert_tile`].
+    ///
+    /// [`insert_tile`]: Tilemap::insert_tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::AnimationClip, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.set_animated_tile((0, 0), 0, "water_flow").is_err());
+    ///
+    /// tilemap.add_animation_clip("water_flow", AnimationClip::new(vec![8, 9, 10], 0.1));
+    ///
+    /// assert!(tilemap.set_animated_tile((0, 0), 0, "water_flow").is_ok());
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 8);
+    /// ```
+    pub fn set_animated_tile<P, S>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        name: S,
+    ) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        S: Into<String>,
+    {
+        let point: Point2 = point.into();
+        let name = name.into();
+        let (sprite_index, time) = match self.animation_clips.get(&name) {
+            Some(clip) => {
+                let time = clip.phase.start_time(point, clip.duration());
+                (clip.frame_at(time).map(|frame| frame.sprite_index), time)
+            }
+            None => return Err(ErrorKind::MissingAnimationClip(name).into()),
+        };
+        if let Some(sprite_index) = sprite_index {
+            let (tint, extra, offset) = self
+                .get_tile(point, z_order)
+                .map(|raw_tile| (raw_tile.color, raw_tile.extra, raw_tile.offset))
+                .unwrap_or((Color::WHITE, 0, Vec2::zero()));
+            self.insert_tile(Tile {
+                point,
+                z_order,
+                sprite_index,
+                tint,
+                extra,
+                offset,
+            })?;
+        }
+        self.animated_tiles.insert(
+            (point, z_order),
+            TileAnimation {
+                clip: name,
+                time,
+                then: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Plays an animation clip once on the tile at a point, from its first
+    /// frame, after which the tile changes as it is told to. The tile is set
+    /// to the first frame right away, keeping its tint, extra data and offset
+    /// if there already was a tile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clip does not exist, or if the tile can not be
+    /// inserted for the same reasons as [`insert_tile`].
+    ///
+    /// [`insert_tile`]: Tilemap::insert_tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::{AnimationClip, TileAfter}, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.add_animation_clip("chest_open", AnimationClip::new(vec![20, 21, 22], 0.1));
+    /// tilemap.insert_tile(Tile { point: (2, 1), sprite_index: 19, ..Default::default() }).unwrap();
+    ///
+    /// // The chest stays open when the clip is done.
+    /// assert!(tilemap.play_once((2, 1), 0, "chest_open", TileAfter::Sprite(23)).is_ok());
+    /// assert_eq!(tilemap.get_tile((2, 1), 0).unwrap().index, 20);
+    /// ```
+    pub fn play_once<P, S>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        name: S,
+        then: TileAfter,
+    ) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        S: Into<String>,
+    {
+        let point: Point2 = point.into();
+        let name = name.into();
+        let sprite_index = match self.animation_clips.get(&name) {
+            Some(clip) => clip.frames.first().map(|frame| frame.sprite_index),
+            None => return Err(ErrorKind::MissingAnimationClip(name).into()),
+        };
+        let previous = self.get_tile(point, z_order).copied();
+        let then = match (then, previous) {
+            (TileAfter::Revert, Some(raw_tile)) => TileAfter::Sprite(raw_tile.index),
+            (TileAfter::Revert, None) => TileAfter::Remove,
+            (then, _) => then,
+        };
+        if let Some(sprite_index) = sprite_index {
+            self.insert_tile(Tile {
+                point,
+                z_order,
+                sprite_index,
+                tint: previous
+                    .map(|raw_tile| raw_tile.color)
+                    .unwrap_or(Color::WHITE),
+                extra: previous.map(|raw_tile| raw_tile.extra).unwrap_or(0),
+                offset: previous
+                    .map(|raw_tile| raw_tile.offset)
+                    .unwrap_or_else(Vec2::zero),
+            })?;
+        }
+        self.animated_tiles.insert(
+            (point, z_order),
+            TileAnimation {
+                clip: name,
+                time: 0.0,
+                then: Some(then),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Stops the animation of the tile at a point, which keeps the sprite it
+    /// has. Returns `true` if the tile was animated.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{animation::AnimationClip, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.add_animation_clip("water_flow", AnimationClip::new(vec![8, 9, 10], 0.1));
+    /// tilemap.set_animated_tile((0, 0), 0, "water_flow").unwrap();
+    ///
+    /// assert!(tilemap.clear_animated_tile((0, 0), 0));
+    /// assert!(!tilemap.clear_animated_tile((0, 0), 0));
+    /// ```
+    pub fn clear_animated_tile<P: Into<Point2>>(&mut self, point: P, z_order: usize) -> bool {
+        self.animated_tiles
+            .remove(&(point.into(), z_order))
+            .is_some()
+    }
+
+    /// Sets how fast the animated tiles play, where `1.0` is the speed of
+    /// their clips and `0.5` is half of it. Speeds below zero are set to zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.animation_speed(), 1.0);
+    ///
+    /// tilemap.set_animation_speed(0.25);
+    /// assert_eq!(tilemap.animation_speed(), 0.25);
+    ///
+    /// tilemap.set_animation_speed(-1.0);
+    /// assert_eq!(tilemap.animation_speed(), 0.0);
+    /// ```
+    pub fn set_animation_speed(&mut self, speed: f32) {
+        self.animation_speed = speed.max(0.0);
+    }
+
+    /// Returns a copy of how fast the animated tiles play.
+    pub fn animation_speed(&self) -> f32 {
+        self.animation_speed
+    }
+
+    /// Pauses or resumes all the animated tiles. Paused tiles keep the frame
+    /// they are on and continue from it when resumed.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.set_animations_paused(true);
+    /// assert!(tilemap.animations_paused());
+    ///
+    /// tilemap.set_animations_paused(false);
+    /// assert!(!tilemap.animations_paused());
+    /// ```
+    pub fn set_animations_paused(&mut self, paused: bool) {
+        self.animations_paused = paused;
+    }
+
+    /// Returns `true` if the animated tiles are paused.
+    pub fn animations_paused(&self) -> bool {
+        self.animations_paused
+    }
+
+    /// Adds a variant group by its name, returning the group which had the
+    /// name before if there was one.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, variant::VariantGroup};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let group = VariantGroup::new(vec![4, 5, 6, 7]);
+    /// assert_eq!(tilemap.add_variant_group("grass", group.clone()), None);
+    /// assert_eq!(tilemap.variant_group("grass"), Some(&group));
+    /// ```
+    pub fn add_variant_group<S: Into<String>>(
+        &mut self,
+        name: S,
+        group: VariantGroup,
+    ) -> Option<VariantGroup> {
+        self.variant_groups.insert(name.into(), group)
+    }
+
+    /// Removes a variant group, returning it if it existed. Tiles which were
+    /// set to one of its variants keep their sprite.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, variant::VariantGroup};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.add_variant_group("grass", VariantGroup::new(vec![4, 5]));
+    ///
+    /// assert!(tilemap.remove_variant_group("grass").is_some());
+    /// assert!(tilemap.variant_group("grass").is_none());
+    /// ```
+    pub fn remove_variant_group(&mut self, name: &str) -> Option<VariantGroup> {
+        self.variant_groups.remove(name)
+    }
+
+    /// Returns a reference to a variant group by its name, if it exists.
+    pub fn variant_group(&self, name: &str) -> Option<&VariantGroup> {
+        self.variant_groups.get(name)
+    }
+
+    /// Sets the seed which the variants of tiles are picked with. Tiles which
+    /// were already set keep their sprite.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.variant_seed(), 0);
+    ///
+    /// tilemap.set_variant_seed(1234);
+    /// assert_eq!(tilemap.variant_seed(), 1234);
+    /// ```
+    pub fn set_variant_seed(&mut self, seed: u64) {
+        self.variant_seed = seed;
+    }
+
+    /// Returns the seed which the variants of tiles are picked with.
+    pub fn variant_seed(&self) -> u64 {
+        self.variant_seed
+    }
+
+    /// Sets the tile at a point to a variant of a group, which is picked from
+    /// the point and the variant seed so that it is the same every time,
+    /// keeping its tint, extra data and offset if there already was a tile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group does not exist or has no variants with a
+    /// weight, or if the tile can not be inserted for the same reasons as
+    /// [`insert_tile`].
+    ///
+    /// [`insert_tile`]: Tilemap::insert_tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, variant::VariantGroup};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.set_tile_variant((1, 1), 0, "grass").is_err());
+    ///
+    /// tilemap.add_variant_group("grass", VariantGroup::weighted(vec![(4, 3), (5, 1)]));
+    ///
+    /// assert!(tilemap.set_tile_variant((1, 1), 0, "grass").is_ok());
+    /// let index = tilemap.get_tile((1, 1), 0).unwrap().index;
+    /// assert!(index == 4 || index == 5);
+    /// ```
+    pub fn set_tile_variant<P, S>(&mut self, point: P, z_order: usize, name: S) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        S: Into<String>,
+    {
+        let point: Point2 = point.into();
+        let name = name.into();
+        let sprite_index = match self.variant_groups.get(&name) {
+            Some(group) => match group.pick(self.variant_seed, point) {
+                Some(sprite_index) => sprite_index,
+                None => return Err(ErrorKind::EmptyVariantGroup(name).into()),
+            },
+            None => return Err(ErrorKind::MissingVariantGroup(name).into()),
+        };
+        let (tint, extra, offset) = self
+            .get_tile(point, z_order)
+            .map(|raw_tile| (raw_tile.color, raw_tile.extra, raw_tile.offset))
+            .unwrap_or((Color::WHITE, 0, Vec2::zero()));
+        self.insert_tile(Tile {
+            point,
+            z_order,
+            sprite_index,
+            tint,
+            extra,
+            offset,
+        })
+    }
+
+    /// Advances the animated tiles by the time, in seconds, and sets the
+    /// sprites of those that moved on to another frame. Tiles which were
+    /// cleared stop being animated.
+    ///
+    /// The time is scaled by the animation speed, and nothing advances while
+    /// the animations are paused or in frozen or static chunks and layers. Tiles which played a
+    /// clip once are changed as they were told to and stop being animated.
+    ///
+    /// Returns the amount of animated tiles that were advanced.
+    pub(crate) fn update_animations(&mut self, delta: f32) -> usize {
+        if self.animations_paused {
+            return 0;
+        }
+        let delta = delta * self.animation_speed;
+        let frozen_chunks = self.chunks_with_activity(ChunkActivity::Frozen);
+        let frozen_tiles: HashSet<(Point2, usize)> = if frozen_chunks.is_empty()
+            && self.static_chunks.is_empty()
+            && self.static_layers.is_empty()
+        {
+            HashSet::default()
+        } else {
+            self.animated_tiles
+                .keys()
+                .filter(|(point, z_order)| {
+                    let chunk_point: Point2 = self.point_to_chunk_point(*point).into();
+                    frozen_chunks.contains(&chunk_point)
+                        || self.static_chunks.contains(&chunk_point)
+                        || self.static_layers.contains(z_order)
+                })
+                .cloned()
+                .collect()
+        };
+        let mut advanced = 0;
+        let mut changed = Vec::new();
+        let mut finished = Vec::new();
+        for (key, animation) in self.animated_tiles.iter_mut() {
+            if frozen_tiles.contains(key) {
+                continue;
+            }
+            advanced += 1;
+            let clip = if let Some(clip) = self.animation_clips.get(&animation.clip) {
+                clip
+            } else {
+                continue;
+            };
+            let before = clip
+                .frame_at(animation.time)
+                .map(|frame| frame.sprite_index);
+            let duration = clip.duration();
+            animation.time += delta;
+            match animation.then {
+                Some(then) if animation.time >= duration => {
+                    finished.push((*key, then));
+                    continue;
+                }
+                Some(_) => {}
+                None if duration > 0.0 => animation.time %= duration,
+                None => {}
+            }
+            let after = clip
+                .frame_at(animation.time)
+                .map(|frame| frame.sprite_index);
+            if let (Some(sprite_index), true) = (after, before != after) {
+                changed.push((*key, sprite_index));
+            }
+        }
+
+        let changed = in_order(changed, self.deterministic, |(key, _)| *key);
+        for ((point, z_order), sprite_index) in changed.into_iter() {
+            if let Some(raw_tile) = self.get_tile_mut(point, z_order) {
+                raw_tile.index = sprite_index;
+            } else {
+                self.animated_tiles.remove(&(point, z_order));
+            }
+        }
+
+        let finished = in_order(finished, self.deterministic, |(key, _)| *key);
+        for ((point, z_order), then) in finished.into_iter() {
+            self.animated_tiles.remove(&(point, z_order));
+            match then {
+                TileAfter::Sprite(sprite_index) => {
+                    if let Some(raw_tile) = self.get_tile_mut(point, z_order) {
+                        raw_tile.index = sprite_index;
+                    }
+                }
+                TileAfter::Remove | TileAfter::Revert => {
+                    if let Err(e) = self.clear_tile(point, z_order) {
+                        warn!("{}", e);
+                    }
+                }
+            }
+        }
+
+        advanced
+    }
+
+    /// Adds the sparse decal layers of the policy, each with the decal
+    /// rotation channel, see the [`decal`] module.
+    ///
+    /// [`decal`]: crate::decal
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap already has decal layers, if any of the layers already
+    /// exists or if they go beyond the layers of the tilemap, an error is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{decal::DecalPolicy, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .z_layers(4)
+    ///     .add_layer(TilemapLayer::default(), 0)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.add_decal_layers(DecalPolicy::new(0, 2)).is_err());
+    /// assert!(tilemap.add_decal_layers(DecalPolicy::new(3, 2)).is_err());
+    /// assert!(tilemap.add_decal_layers(DecalPolicy::new(1, 3)).is_ok());
+    /// assert_eq!(tilemap.decal_policy().unwrap().max_per_tile, 3);
+    /// ```
+    pub fn add_decal_layers(&mut self, policy: DecalPolicy) -> TilemapResult<()> {
+        if let Some(existing) = &self.decal_policy {
+            return Err(ErrorKind::LayerExists(existing.z_order).into());
+        }
+        for z_order in policy.z_orders() {
+            match self.layers.get(z_order) {
+                Some(Some(_)) => return Err(ErrorKind::LayerExists(z_order).into()),
+                Some(None) => {}
+                None => return Err(ErrorKind::LayerDoesNotExist(z_order).into()),
+            }
+        }
+        for z_order in policy.z_orders() {
+            let layer = TilemapLayer {
+                kind: LayerKind::Sparse,
+                ..Default::default()
+            };
+            self.add_layer(layer, z_order)?;
+            self.add_channel(z_order, DECAL_ROTATION_CHANNEL, ChannelKind::Float)?;
+        }
+        self.decal_policy = Some(policy);
+
+        Ok(())
+    }
+
+    /// Returns the policy of the decal layers, if there are any.
+    pub fn decal_policy(&self) -> Option<&DecalPolicy> {
+        self.decal_policy.as_ref()
+    }
+
+    /// Adds a decal on top of the decals of the tile at a point. If the tile
+    /// already has as many decals as the policy allows, its oldest decal is
+    /// removed.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap has no decal layers or the chunk of the point does not
+    /// exist and can not be made, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{channel::ChannelValue, decal::{DecalPolicy, DECAL_ROTATION_CHANNEL}, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.add_decal((0, 0), 4, 0.0, Color::WHITE).is_err());
+    ///
+    /// tilemap.add_decal_layers(DecalPolicy::new(1, 1)).unwrap();
+    /// tilemap.add_decal((0, 0), 4, 0.5, Color::WHITE).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 1).unwrap().index, 4);
+    /// assert_eq!(
+    ///     tilemap.tile_channel((0, 0), 1, DECAL_ROTATION_CHANNEL),
+    ///     Some(ChannelValue::Float(0.5))
+    /// );
+    /// ```
+    pub fn add_decal<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        sprite_index: usize,
+        rotation: f32,
+        tint: Color,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        let max_per_tile = match &self.decal_policy {
+            Some(policy) => policy.max_per_tile,
+            None => return Err(ErrorKind::MissingDecalLayers.into()),
+        };
+        let decals = self.decals.entry(point).or_default();
+        decals.push(Decal::new(sprite_index, rotation, tint));
+        if decals.len() > max_per_tile {
+            let excess = decals.len() - max_per_tile;
+            decals.drain(..excess);
+        }
+        self.write_decals(point)
+    }
+
+    /// Returns the decals of the tile at a point, from oldest to newest.
+    pub fn decals<P: Into<Point2>>(&self, point: P) -> &[Decal] {
+        match self.decals.get(&point.into()) {
+            Some(decals) => decals,
+            None => &[],
+        }
+    }
+
+    /// Removes every decal of the tile at a point.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap has no decal layers, an error is returned.
+    pub fn clear_decals<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if self.decal_policy.is_none() {
+            return Err(ErrorKind::MissingDecalLayers.into());
+        }
+        if self.decals.remove(&point).is_some() {
+            self.write_decals(point)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the tiles of the decal layers at a point to the decals of the
+    /// tile, clearing the layers without a decal.
+    fn write_decals(&mut self, point: Point2) -> TilemapResult<()> {
+        let policy = match self.decal_policy {
+            Some(policy) => policy,
+            None => return Err(ErrorKind::MissingDecalLayers.into()),
+        };
+        let decals = self.decals.get(&point).cloned().unwrap_or_default();
+        for (slot, z_order) in policy.z_orders().enumerate() {
+            match decals.get(slot) {
+                Some(decal) => {
+                    self.insert_tile(Tile {
+                        point,
+                        z_order,
+                        sprite_index: decal.sprite_index,
+                        tint: decal.color(&policy),
+                        ..Default::default()
+                    })?;
+                    self.set_tile_channel(point, z_order, DECAL_ROTATION_CHANNEL, decal.rotation)?;
+                }
+                None => {
+                    if self.get_tile(point, z_order).is_some() {
+                        self.clear_tile(point, z_order)?;
+                    }
+                }
+            }
+        }
+        if decals.is_empty() {
+            self.decals.remove(&point);
+        }
+        Ok(())
+    }
+
+    /// Ages the decals by the time, in seconds, fading out and removing those
+    /// at the end of their lifetime. Decals on static tiles do not age.
+    ///
+    /// Returns the amount of decals that were aged.
+    pub(crate) fn update_decals(&mut self, delta: f32) -> usize {
+        let policy = match self.decal_policy {
+            Some(policy) if policy.lifetime.is_some() => policy,
+            _ => return 0,
+        };
+        let static_points: HashSet<Point2> = self
+            .decals
+            .keys()
+            .filter(|point| {
+                self.static_layers.contains(&policy.z_order)
+                    || self
+                        .static_chunks
+                        .contains(&self.point_to_chunk_point(**point).into())
+            })
+            .copied()
+            .collect();
+        let mut aged = 0;
+        let mut changed = Vec::new();
+        for (point, decals) in self.decals.iter_mut() {
+            if static_points.contains(point) {
+                continue;
+            }
+            aged += decals.len();
+            let mut fading = false;
+            for decal in decals.iter_mut() {
+                decal.age += delta;
+                fading = fading || decal.is_fading(&policy);
+            }
+            let before = decals.len();
+            decals.retain(|decal| !decal.is_expired(&policy));
+            if fading || decals.len() != before {
+                changed.push(*point);
+            }
+        }
+        for point in in_order(changed, self.deterministic, |point| *point) {
+            if let Err(e) = self.write_decals(point) {
+                warn!("{}", e);
+            }
+        }
+        aged
+    }
+
+    /// Adds the sparse layer which tiles are highlighted in, see the
+    /// [`highlight`] module.
+    ///
+    /// [`highlight`]: crate::highlight
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap already has a highlight layer, if the layer already
+    /// exists or if it goes beyond the layers of the tilemap, an error is
+    /// returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{highlight::HighlightLayer, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .z_layers(3)
+    ///     .add_layer(TilemapLayer::default(), 0)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.add_highlight_layer(HighlightLayer::new(0, 3)).is_err());
+    /// assert!(tilemap.add_highlight_layer(HighlightLayer::new(3, 3)).is_err());
+    /// assert!(tilemap.add_highlight_layer(HighlightLayer::new(2, 3)).is_ok());
+    /// assert_eq!(tilemap.highlight_layer().unwrap().z_order, 2);
+    /// ```
+    pub fn add_highlight_layer(&mut self, layer: HighlightLayer) -> TilemapResult<()> {
+        if let Some(existing) = &self.highlight_layer {
+            return Err(ErrorKind::LayerExists(existing.z_order).into());
+        }
+        match self.layers.get(layer.z_order) {
+            Some(Some(_)) => return Err(ErrorKind::LayerExists(layer.z_order).into()),
+            Some(None) => {}
+            None => return Err(ErrorKind::LayerDoesNotExist(layer.z_order).into()),
+        }
+        let tilemap_layer = TilemapLayer {
+            kind: LayerKind::Sparse,
+            ..Default::default()
+        };
+        self.add_layer(tilemap_layer, layer.z_order)?;
+        self.highlight_layer = Some(layer);
+
+        Ok(())
+    }
+
+    /// Returns the layer which tiles are highlighted in, if there is one.
+    pub fn highlight_layer(&self) -> Option<&HighlightLayer> {
+        self.highlight_layer.as_ref()
+    }
+
+    /// Highlights the tiles at the points in a color until the tilemap stage
+    /// of the next frame, unless they are highlighted again before then.
+    ///
+    /// Tiles which are already highlighted in the color are not written
+    /// again, so calling this every frame with the same tiles costs nothing
+    /// but the lookups.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap has no highlight layer or the chunk of a point does not
+    /// exist and can not be made, an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_render::prelude::*;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{highlight::HighlightLayer, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.highlight_tiles(vec![(0, 0)], Color::GREEN).is_err());
+    ///
+    /// tilemap.add_highlight_layer(HighlightLayer::new(1, 3)).unwrap();
+    /// tilemap.highlight_tiles(vec![(0, 0), (1, 0)], Color::GREEN).unwrap();
+    ///
+    /// assert_eq!(tilemap.highlight((1, 0)), Some(Color::GREEN));
+    /// assert_eq!(tilemap.get_tile((1, 0), 1).unwrap().color, Color::GREEN);
+    /// ```
+    pub fn highlight_tiles<P, I>(&mut self, points: I, color: Color) -> TilemapResult<()>
+    where
+        P: Into<Point2>,
+        I: IntoIterator<Item = P>,
+    {
+        let layer = match self.highlight_layer {
+            Some(layer) => layer,
+            None => return Err(ErrorKind::MissingHighlightLayer.into()),
+        };
+        for point in points {
+            let point: Point2 = point.into();
+            self.kept_highlights.insert(point);
+            if self.highlights.get(&point) == Some(&color) {
+                continue;
+            }
+            self.insert_tile(Tile {
+                point,
+                z_order: layer.z_order,
+                sprite_index: layer.sprite_index,
+                tint: color,
+                ..Default::default()
+            })?;
+            self.highlights.insert(point, color);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the color of the highlight of the tile at a point, if it is
+    /// highlighted.
+    pub fn highlight<P: Into<Point2>>(&self, point: P) -> Option<Color> {
+        self.highlights.get(&point.into()).copied()
+    }
+
+    /// Returns the points and colors of the highlighted tiles.
+    pub fn highlighted_tiles(&self) -> impl Iterator<Item = (&Point2, &Color)> {
+        self.highlights.iter()
+    }
+
+    /// Clears every highlight at once, without waiting for the next frame.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap has no highlight layer, an error is returned.
+    pub fn clear_highlights(&mut self) -> TilemapResult<()> {
+        if self.highlight_layer.is_none() {
+            return Err(ErrorKind::MissingHighlightLayer.into());
+        }
+        self.kept_highlights.clear();
+        self.clear_stale_highlights()
+    }
+
+    /// Clears the highlights which were not highlighted again since this was
+    /// last called.
+    pub(crate) fn clear_stale_highlights(&mut self) -> TilemapResult<()> {
+        let layer = match self.highlight_layer {
+            Some(layer) => layer,
+            None => return Ok(()),
+        };
+        let kept = ::std::mem::take(&mut self.kept_highlights);
+        let stale: Vec<Point2> = self
+            .highlights
+            .keys()
+            .filter(|point| !kept.contains(point))
+            .copied()
+            .collect();
+        for point in stale {
+            self.highlights.remove(&point);
+            if self.get_tile(point, layer.z_order).is_some() {
+                self.clear_tile(point, layer.z_order)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets if the tilemap keeps the gameplay entities bound to its tiles.
+    /// Turning it off forgets every bound entity, without despawning them.
+    pub fn set_tile_entities(&mut self, enabled: bool) {
+        self.auto_flags.set(AutoFlags::TILE_ENTITIES, enabled);
+        if !enabled {
+            self.tile_entities.clear();
+        }
+    }
+
+    /// Returns `true` if the tilemap keeps the gameplay entities bound to its
+    /// tiles.
+    pub fn has_tile_entities(&self) -> bool {
+        self.auto_flags.contains(AutoFlags::TILE_ENTITIES)
+    }
+
+    /// Binds a gameplay entity, such as a chest, a door or a machine, to the
+    /// tile at a point and z order, returning the entity that was bound to it
+    /// before.
+    ///
+    /// The entity is unbound, but never despawned, once its tile is cleared,
+    /// its chunk or layer is removed or its chunk is despawned, and a
+    /// [`TileEntityEvent`] is sent to the [`tile_entity_events`]. The game
+    /// decides if the entity is despawned, or kept until the chunk is spawned
+    /// again. Entities which the game despawns are forgotten.
+    ///
+    /// [`TileEntityEvent`]: crate::event::TileEntityEvent
+    /// [`tile_entity_events`]: Tilemap::tile_entity_events
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{event::TileEntityEvent, prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .tile_entities()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 4, z_order: 1, ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let chest = harness.app().world.spawn(());
+    /// tilemap.set_tile_entity((1, 1), 1, chest).unwrap();
+    ///
+    /// assert_eq!(tilemap.entity_at((1, 1)), Some(chest));
+    /// assert_eq!(tilemap.tile_entity((1, 1), 0), None);
+    ///
+    /// // The chest is unbound when its chunk streams out, but not despawned.
+    /// tilemap.despawn_chunk((0, 0)).unwrap();
+    /// assert!(tilemap.entity_at((1, 1)).is_none());
+    /// let mut reader = tilemap.tile_entity_events().get_reader();
+    /// assert_eq!(
+    ///     reader.iter(tilemap.tile_entity_events()).next(),
+    ///     Some(&TileEntityEvent::ChunkDespawned { point: (1, 1).into(), z_order: 1, entity: chest })
+    /// );
+    ///
+    /// // Clearing the tile of the chest unbinds it too.
+    /// tilemap.set_tile_entity((1, 1), 1, chest).unwrap();
+    /// tilemap.clear_tile((1, 1), 1).unwrap();
+    /// assert!(tilemap.entity_at((1, 1)).is_none());
+    ///
+    /// // Doors which are despawned by the game are forgotten.
+    /// let door = harness.app().world.spawn(());
+    /// tilemap.set_tile_entity((9, 9), 0, door).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.app().world.despawn(door).unwrap();
+    /// harness.tick();
+    ///
+    /// assert!(harness.app().world.contains(chest));
+    /// assert!(harness.tilemap(entity).unwrap().entity_at((9, 9)).is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tilemap does not keep tile entities, if the
+    /// layer does not exist or if the point is out of the bounds of the
+    /// tilemap.
+    pub fn set_tile_entity<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        entity: Entity,
+    ) -> TilemapResult<Option<Entity>> {
+        let point: Point2 = point.into();
+        if !self.has_tile_entities() {
+            return Err(ErrorKind::TileEntitiesDisabled.into());
+        }
+        if !self.layers.get(z_order).is_some_and(Option::is_some) {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        if let Some(dimensions) = &self.dimensions {
+            dimensions.check_point(point)?;
+        }
+
+        Ok(self.tile_entities.insert((point, z_order), entity))
+    }
+
+    /// Unbinds the gameplay entity of the tile at a point and z order,
+    /// returning it. The entity is not despawned.
+    pub fn remove_tile_entity<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        z_order: usize,
+    ) -> Option<Entity> {
+        self.tile_entities.remove(&(point.into(), z_order))
+    }
+
+    /// Returns the gameplay entity bound to the tile at a point and z order,
+    /// if there is one.
+    pub fn tile_entity<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<Entity> {
+        self.tile_entities.get(&(point.into(), z_order)).copied()
+    }
+
+    /// Returns the gameplay entity bound to a tile at a point, from the
+    /// highest layer which has one, if there is one.
+    pub fn entity_at<P: Into<Point2>>(&self, point: P) -> Option<Entity> {
+        let point: Point2 = point.into();
+        (0..self.layers.len())
+            .rev()
+            .find_map(|z_order| self.tile_entity(point, z_order))
+    }
+
+    /// Returns the events of the gameplay entities which were unbound from
+    /// their tiles, see [`set_tile_entity`].
+    ///
+    /// [`set_tile_entity`]: Tilemap::set_tile_entity
+    pub fn tile_entity_events(&self) -> &Events<TileEntityEvent> {
+        &self.tile_entity_events
+    }
+
+    /// Updates the events of the gameplay entities which were unbound.
+    pub(crate) fn tile_entity_events_update(&mut self) {
+        self.tile_entity_events.update();
+    }
+
+    /// Unbinds the gameplay entities of the tiles which match, in order, and
+    /// sends their events, as despawned with their chunk or as removed.
+    fn unbind_tile_entities<F>(&mut self, mut unbinds: F, chunk_despawned: bool)
+    where
+        F: FnMut(Point2, usize) -> bool,
+    {
+        if self.tile_entities.is_empty() {
+            return;
+        }
+        let tiles = in_order(
+            self.tile_entities
+                .keys()
+                .filter(|(point, z_order)| unbinds(*point, *z_order))
+                .copied(),
+            self.deterministic,
+            |tile| *tile,
+        );
+        for (point, z_order) in tiles.into_iter() {
+            let entity = match self.tile_entities.remove(&(point, z_order)) {
+                Some(entity) => entity,
+                None => continue,
+            };
+            let event = if chunk_despawned {
+                TileEntityEvent::ChunkDespawned {
+                    point,
+                    z_order,
+                    entity,
+                }
+            } else {
+                TileEntityEvent::TileRemoved {
+                    point,
+                    z_order,
+                    entity,
+                }
+            };
+            self.tile_entity_events.send(event);
+        }
+    }
+
+    /// Unbinds the gameplay entities of the tiles of a chunk.
+    fn unbind_chunk_tile_entities(&mut self, chunk_point: Point2, chunk_despawned: bool) {
+        let chunk_dimensions = self.chunk_dimensions;
+        self.unbind_tile_entities(
+            |point, _| chunk_point_of(chunk_dimensions, point) == chunk_point,
+            chunk_despawned,
+        );
+    }
+
+    /// Forgets the gameplay entities which no longer exist.
+    pub(crate) fn retain_tile_entities<F: FnMut(Entity) -> bool>(&mut self, mut exists: F) {
+        self.tile_entities.retain(|_, entity| exists(*entity));
+    }
+
+    /// Sets the hit points of the tiles of a sprite index, so that they can be
+    /// destroyed with [`damage_tile`]. A destroyed tile is replaced with the
+    /// rubble sprite index, or removed if there is none.
+    ///
+    /// [`damage_tile`]: Tilemap::damage_tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // Crates break into rubble in three hits while walls take ten.
+    /// tilemap.set_sprite_hit_points(5, 3, Some(6));
+    /// tilemap.set_sprite_hit_points(8, 10, None);
+    ///
+    /// assert_eq!(tilemap.sprite_hit_points(5), Some(3));
+    /// assert_eq!(tilemap.sprite_hit_points(6), None);
+    /// ```
+    pub fn set_sprite_hit_points(&mut self, index: usize, hit_points: u32, rubble: Option<usize>) {
+        self.sprite_hit_points
+            .insert(index, SpriteHitPoints { hit_points, rubble });
+    }
+
+    /// Removes the hit points of a sprite index, so that its tiles can not be
+    /// destroyed. Returns `true` if it had hit points.
+    pub fn remove_sprite_hit_points(&mut self, index: usize) -> bool {
+        self.sprite_hit_points.remove(&index).is_some()
+    }
+
+    /// Returns the hit points of the tiles of a sprite index, if they can be
+    /// destroyed.
+    pub fn sprite_hit_points(&self, index: usize) -> Option<u32> {
+        self.sprite_hit_points
+            .get(&index)
+            .map(|hit_points| hit_points.hit_points)
+    }
+
+    /// Returns the hit points that the tile at a point has left, or `None` if
+    /// there is no tile or it can not be destroyed.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.set_sprite_hit_points(5, 3, None);
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 5, ..Default::default() }).unwrap();
+    ///
+    /// assert_eq!(tilemap.tile_hit_points((1, 1), 0), Some(3));
+    ///
+    /// tilemap.damage_tile((1, 1), 0, 2).unwrap();
+    /// assert_eq!(tilemap.tile_hit_points((1, 1), 0), Some(1));
+    /// ```
+    pub fn tile_hit_points<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<u32> {
+        let point: Point2 = point.into();
+        let sprite_index = self.get_tile(point, z_order)?.index;
+        let hit_points = self.sprite_hit_points.get(&sprite_index)?.hit_points;
+        let damage = match self.tile_damage.get(&(point, z_order)) {
+            Some(damage) if damage.sprite_index == sprite_index => damage.damage,
+            _ => 0,
+        };
+        Some(hit_points.saturating_sub(damage))
+    }
+
+    /// Deals damage to the tile at a point. Returns `true` if the tile ran out
+    /// of hit points and was destroyed.
+    ///
+    /// A destroyed tile is replaced with the rubble sprite index of its sprite
+    /// or removed, its collisions are spawned again and a
+    /// [`TileDestroyedEvent`] is sent to the [`destroyed_events`]. Tiles whose
+    /// sprite index has no hit points, set with [`set_sprite_hit_points`], can
+    /// not be damaged.
+    ///
+    /// [`destroyed_events`]: Tilemap::destroyed_events
+    /// [`set_sprite_hit_points`]: Tilemap::set_sprite_hit_points
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.set_sprite_hit_points(5, 3, Some(6));
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 5, ..Default::default() }).unwrap();
+    ///
+    /// assert!(!tilemap.damage_tile((1, 1), 0, 2).unwrap());
+    /// assert!(tilemap.damage_tile((1, 1), 0, 2).unwrap());
+    /// assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 6);
+    ///
+    /// // The rubble has no hit points.
+    /// assert!(!tilemap.damage_tile((1, 1), 0, 2).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`insert_tile`] and [`clear_tile`] when the tile
+    /// is destroyed.
+    ///
+    /// [`insert_tile`]: Tilemap::insert_tile
+    /// [`clear_tile`]: Tilemap::clear_tile
+    pub fn damage_tile<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        amount: u32,
+    ) -> TilemapResult<bool> {
+        let point: Point2 = point.into();
+        let raw_tile = match self.get_tile(point, z_order) {
+            Some(raw_tile) => *raw_tile,
+            None => return Ok(false),
+        };
+        let hit_points = match self.sprite_hit_points.get(&raw_tile.index) {
+            Some(hit_points) => *hit_points,
+            None => return Ok(false),
+        };
+        let damage = self
+            .tile_damage
+            .entry((point, z_order))
+            .or_insert(TileDamage {
+                sprite_index: raw_tile.index,
+                damage: 0,
+            });
+        if damage.sprite_index != raw_tile.index {
+            *damage = TileDamage {
+                sprite_index: raw_tile.index,
+                damage: 0,
+            };
+        }
+        damage.damage = damage.damage.saturating_add(amount);
+        if damage.damage < hit_points.hit_points {
+            return Ok(false);
+        }
+
+        self.tile_damage.remove(&(point, z_order));
+        self.animated_tiles.remove(&(point, z_order));
+        match hit_points.rubble {
+            Some(sprite_index) => self.insert_tile(Tile {
+                point,
+                z_order,
+                sprite_index,
+                tint: raw_tile.color,
+                extra: raw_tile.extra,
+                offset: raw_tile.offset,
+            })?,
+            None => self.clear_tile(point, z_order)?,
+        }
+        self.destroyed_events.send(TileDestroyedEvent {
+            point,
+            z_order,
+            sprite_index: raw_tile.index,
+            rubble: hit_points.rubble,
+        });
+
+        Ok(true)
+    }
+
+    /// Returns a reference to the events of the tiles destroyed by
+    /// [`damage_tile`].
+    ///
+    /// [`damage_tile`]: Tilemap::damage_tile
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_app::prelude::*;
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, event::TileDestroyedEvent};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let events: &Events<TileDestroyedEvent> = tilemap.destroyed_events();
+    /// ```
+    pub fn destroyed_events(&self) -> &Events<TileDestroyedEvent> {
+        &self.destroyed_events
+    }
+
+    /// Updates the destroyed events. This should only be done once per frame.
+    pub(crate) fn destroyed_events_update(&mut self) {
+        self.destroyed_events.update()
+    }
+
+    /// Adds rules to a layer, returning the rules it had before if there
+    /// were any, see the [`rule`] module.
+    ///
+    /// The rules are not applied to the tiles the layer already has until
+    /// [`apply_tile_rules`] is called.
+    ///
+    /// [`rule`]: crate::rule
+    /// [`apply_tile_rules`]: Tilemap::apply_tile_rules
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, rule::TileRules};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert_eq!(tilemap.add_tile_rules(0, TileRules::new()), None);
+    /// assert!(tilemap.tile_rules(0).is_some());
+    /// ```
+    pub fn add_tile_rules(&mut self, z_order: usize, rules: TileRules) -> Option<TileRules> {
+        self.tile_rules.insert(z_order, rules)
+    }
+
+    /// Removes the rules of a layer, returning them. The tiles they changed
+    /// keep their sprites.
+    pub fn remove_tile_rules(&mut self, z_order: usize) -> Option<TileRules> {
+        self.tile_rules.remove(&z_order)
+    }
+
+    /// Returns a reference to the rules of a layer, if it has any.
+    pub fn tile_rules(&self, z_order: usize) -> Option<&TileRules> {
+        self.tile_rules.get(&z_order)
+    }
+
+    /// Applies the rules of a layer to every tile of the layer in the chunks
+    /// which are in memory, returning the amount of tiles which changed.
+    ///
+    /// This is needed for the tiles which were there before the rules were
+    /// added, for rules which are not automatic, and for tiles which were
+    /// changed in place with [`get_tile_mut`].
+    ///
+    /// [`get_tile_mut`]: Tilemap::get_tile_mut
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, rule::{TileMatch, TileRule, TileRules}};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// for x in 0..3 {
+    ///     tilemap.insert_tile(Tile { point: (x, 0), sprite_index: 1, ..Default::default() }).unwrap();
+    /// }
+    ///
+    /// let rules = TileRules::new()
+    ///     .with_terrain("wall", vec![1, 2])
+    ///     .with_rule(TileRule::new("wall", 2).with_neighbor((1, 0), TileMatch::Empty));
+    /// tilemap.add_tile_rules(0, rules);
+    ///
+    /// assert_eq!(tilemap.apply_tile_rules(0).unwrap(), 1);
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).unwrap().index, 1);
+    /// assert_eq!(tilemap.get_tile((2, 0), 0).unwrap().index, 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer has no rules, or if the tiles can not
+    /// be changed for the same reasons as [`insert_tiles`].
+    ///
+    /// [`insert_tiles`]: Tilemap::insert_tiles
+    pub fn apply_tile_rules(&mut self, z_order: usize) -> TilemapResult<usize> {
+        if !self.tile_rules.contains_key(&z_order) {
+            return Err(ErrorKind::MissingTileRules(z_order).into());
+        }
+        let mut points = Vec::new();
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (chunk_point, chunk) in chunks.into_iter() {
+            for index in chunk.get_tile_indices(z_order).unwrap_or_default() {
+                let tile_point = self.chunk_dimensions.decode_point_unchecked(index);
+                points.push(tile_point_to_point(
+                    self.chunk_dimensions,
+                    *chunk_point,
+                    tile_point,
+                ));
+            }
+        }
+        let tiles = self.resolve_tile_rules(z_order, points);
+        let changed = tiles.len();
+        self.write_ruled_tiles(tiles)?;
+
+        Ok(changed)
+    }
+
+    /// Applies the automatic rules to the tiles whose rules match the changed
+    /// tiles as a neighbour, unless the changed tiles were set by rules.
+    fn apply_tile_rules_around(&mut self, changed: Vec<(Point2, usize)>) -> TilemapResult<()> {
+        if changed.is_empty() || self.applying_tile_rules {
+            return Ok(());
+        }
+        let mut points: HashMap<usize, HashSet<Point2>> = HashMap::default();
+        for (point, z_order) in changed.into_iter() {
+            if let Some(rules) = self.tile_rules.get(&z_order) {
+                let points = points.entry(z_order).or_default();
+                for offset in rules.dependents().into_iter() {
+                    points.insert(point + offset);
+                }
+            }
+        }
+        let mut tiles = Vec::new();
+        for (z_order, points) in in_order(points, self.deterministic, |(z_order, _)| *z_order) {
+            let points = in_order(points, self.deterministic, |point| *point);
+            tiles.extend(self.resolve_tile_rules(z_order, points));
+        }
+        self.write_ruled_tiles(tiles)
+    }
+
+    /// Returns the tiles of a layer at the points whose sprite their rules
+    /// change, keeping their tint, extra data and offset.
+    fn resolve_tile_rules<I>(&self, z_order: usize, points: I) -> Vec<Tile<Point2>>
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let rules = match self.tile_rules.get(&z_order) {
+            Some(rules) => rules,
+            None => return Vec::new(),
+        };
+        #[cfg(feature = "serialize")]
+        let offsets = rules.offsets();
+        let sprite_at = |point: Point2| self.get_tile(point, z_order).map(|tile| tile.index);
+        let mut tiles = Vec::new();
+        for point in points.into_iter() {
+            let tile = match self.get_tile(point, z_order) {
+                Some(tile) => *tile,
+                None => continue,
+            };
+            let chunk_point = chunk_point_of(self.chunk_dimensions, point);
+            if self.static_chunks.contains(&chunk_point) || self.static_layers.contains(&z_order) {
+                continue;
+            }
+            // Tiles next to an unloaded chunk are ruled again once it loads.
+            #[cfg(feature = "serialize")]
+            if offsets.iter().any(|offset| {
+                self.unloaded_chunks
+                    .contains(&chunk_point_of(self.chunk_dimensions, point + *offset))
+            }) {
+                continue;
+            }
+            match rules.resolve(point, sprite_at) {
+                Some(sprite_index) if sprite_index != tile.index => tiles.push(Tile {
+                    point,
+                    z_order,
+                    sprite_index,
+                    tint: tile.color,
+                    extra: tile.extra,
+                    offset: tile.offset,
+                }),
+                _ => {}
+            }
+        }
+        tiles
+    }
+
+    /// Applies the automatic rules to the tiles along the edges of a chunk
+    /// which was loaded, and to the tiles of the chunks around it whose rules
+    /// match them, which were left as they were while it was unloaded.
+    #[cfg(feature = "serialize")]
+    fn apply_tile_rules_at_edges(&mut self, chunk_point: Point2) -> TilemapResult<()> {
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+        let mut changed = Vec::new();
+        for (z_order, rules) in self.tile_rules.iter().filter(|(_, rules)| rules.auto) {
+            let reach = rules.reach();
+            for y in 0..height {
+                for x in 0..width {
+                    if x < reach || y < reach || x >= width - reach || y >= height - reach {
+                        let tile_point = Point2::new(x, y);
+                        let point =
+                            tile_point_to_point(self.chunk_dimensions, chunk_point, tile_point);
+                        changed.push((point, *z_order));
+                    }
+                }
+            }
+        }
+        self.apply_tile_rules_around(changed)
+    }
+
+    /// Inserts the tiles which rules changed, without applying the rules to
+    /// the tiles around them again.
+    fn write_ruled_tiles(&mut self, tiles: Vec<Tile<Point2>>) -> TilemapResult<()> {
+        if tiles.is_empty() {
+            return Ok(());
+        }
+        self.applying_tile_rules = true;
+        let result = self.insert_tiles(tiles);
+        self.applying_tile_rules = false;
+        result
+    }
+
+    /// Adds a navigation mesh to a layer, replacing the one it had.
+    ///
+    /// Every tile of the layer is walkable, except for the tiles of the
+    /// blocked sprite indexes. The regions of every chunk are merged right
+    /// away, and then again for the chunks whose tiles change.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 0, ..Default::default() }).unwrap();
+    ///
+    /// assert!(tilemap.add_navmesh(0, vec![3]).is_ok());
+    /// assert!(tilemap.navmesh(0).unwrap().region_at((0, 0)).is_some());
+    ///
+    /// assert!(tilemap.add_navmesh(9, vec![]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer does not exist.
+    pub fn add_navmesh<I>(&mut self, z_order: usize, blocked_sprites: I) -> TilemapResult<()>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        match self.layers.get(z_order) {
+            Some(Some(_)) => {}
+            _ => return Err(ErrorKind::LayerDoesNotExist(z_order).into()),
+        }
+        let mut navmesh = NavMesh::new(
+            z_order,
+            self.chunk_dimensions,
+            blocked_sprites.into_iter().collect(),
+        );
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (chunk_point, chunk) in chunks.into_iter() {
+            navmesh.rebuild_chunk(*chunk_point, Some(chunk));
+        }
+        self.navmeshes.insert(z_order, navmesh);
+
+        Ok(())
+    }
+
+    /// Removes the navigation mesh of a layer, returning it.
+    pub fn remove_navmesh(&mut self, z_order: usize) -> Option<NavMesh> {
+        self.navmeshes.remove(&z_order)
+    }
+
+    /// Returns a reference to the navigation mesh of a layer, if it has one.
+    pub fn navmesh(&self, z_order: usize) -> Option<&NavMesh> {
+        self.navmeshes.get(&z_order)
+    }
+
+    /// Merges the walkable tiles of the chunks which changed into the regions
+    /// of every navigation mesh again, returning the amount of chunks that
+    /// were merged.
+    ///
+    /// This is done by the tilemap systems once per frame, but can be done
+    /// sooner if a navigation mesh is needed right after its tiles change.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 0, ..Default::default() }).unwrap();
+    /// tilemap.add_navmesh(0, vec![]).unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (1, 0), sprite_index: 0, ..Default::default() }).unwrap();
+    /// assert!(tilemap.navmesh(0).unwrap().region_at((1, 0)).is_none());
+    ///
+    /// assert_eq!(tilemap.update_navmeshes(), 1);
+    /// assert!(tilemap.navmesh(0).unwrap().region_at((1, 0)).is_some());
+    /// ```
+    pub fn update_navmeshes(&mut self) -> usize {
+        let mut merged = 0;
+        for navmesh in self.navmeshes.values_mut() {
+            let dirty = in_order(navmesh.take_dirty(), self.deterministic, |point| *point);
+            for chunk_point in dirty.into_iter() {
+                navmesh.rebuild_chunk(
+                    chunk_point,
+                    self.chunks.get(&chunk_point).map(|chunk| &**chunk),
+                );
+                merged += 1;
+            }
+        }
+        merged
+    }
+
+    /// Inserts empty rows at the row `at`, moving every tile on and above it
+    /// up by `count` rows.
+    ///
+    /// Chunks are created as the tiles need them. If a tile moves into a new
+    /// chunk from a spawned chunk, the new chunk is spawned as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (0, 5), sprite_index: 2, ..Default::default() },
+    ///     Tile { point: (5, 0), sprite_index: 3, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.insert_rows(3, 2).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((0, 5), 0), None);
+    /// assert_eq!(tilemap.get_tile((0, 7), 0).map(|t| t.index), Some(2));
+    /// ```
+    ///
+    /// The transforms, heights and gameplay entities of the tiles move with
+    /// them.
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_ecs::prelude::*;
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tile::TileTransform};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Decoration, ..Default::default() }, 1)
+    ///     .tile_entities()
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let pebble = TileTransform::new(0.3, Vec2::new(0.5, 0.5));
+    /// let chest = Entity::new(1);
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 7, z_order: 1, ..Default::default() }).unwrap();
+    /// tilemap.set_tile_transform((1, 1), 1, pebble).unwrap();
+    /// tilemap.set_height((1, 1), 16.0).unwrap();
+    /// tilemap.set_tile_entity((1, 1), 1, chest).unwrap();
+    ///
+    /// tilemap.insert_rows(0, 1).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((1, 2), 1).map(|t| t.index), Some(7));
+    /// assert_eq!(tilemap.tile_transform((1, 2), 1), Some(pebble));
+    /// assert_eq!(tilemap.height_at((1, 2)), 16.0);
+    /// assert_eq!(tilemap.height_at((1, 1)), 0.0);
+    /// assert_eq!(tilemap.tile_entity((1, 2), 1), Some(chest));
+    /// assert_eq!(tilemap.tile_entity((1, 1), 1), None);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a tile would be moved outside of the tilemap, an error is returned
+    /// and no tiles are moved. This can only happen if the tilemap has
+    /// dimensions.
+    pub fn insert_rows(&mut self, at: i32, count: u32) -> TilemapResult<()> {
+        let count = count as i32;
+        self.move_tiles(
+            |_, _| {},
+            |point| {
+                if point.y >= at {
+                    Some(Point2::new(point.x, point.y + count))
+                } else {
+                    Some(point)
+                }
+            },
+        )
+    }
+
+    /// Removes `count` rows starting from the row `at`, removing their tiles
+    /// and moving every tile above them down by `count` rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (0, 5), sprite_index: 2, ..Default::default() },
+    ///     Tile { point: (5, 0), sprite_index: 3, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.remove_rows(1, 2).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((0, 3), 0).map(|t| t.index), Some(2));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a tile would be moved outside of the tilemap, an error is returned
+    /// and no tiles are moved. This can only happen if the tilemap has
+    /// dimensions.
+    pub fn remove_rows(&mut self, at: i32, count: u32) -> TilemapResult<()> {
+        let count = count as i32;
+        self.move_tiles(
+            |_, _| {},
+            |point| {
+                if point.y >= at + count {
+                    Some(Point2::new(point.x, point.y - count))
+                } else if point.y >= at {
+                    None
+                } else {
+                    Some(point)
+                }
+            },
+        )
+    }
+
+    /// Inserts empty columns at the column `at`, moving every tile on and to
+    /// the right of it right by `count` columns.
+    ///
+    /// Chunks are created as the tiles need them. If a tile moves into a new
+    /// chunk from a spawned chunk, the new chunk is spawned as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (0, 5), sprite_index: 2, ..Default::default() },
+    ///     Tile { point: (5, 0), sprite_index: 3, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.insert_columns(-1, 1).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((1, 0), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((6, 0), 0).map(|t| t.index), Some(3));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a tile would be moved outside of the tilemap, an error is returned
+    /// and no tiles are moved. This can only happen if the tilemap has
+    /// dimensions.
+    pub fn insert_columns(&mut self, at: i32, count: u32) -> TilemapResult<()> {
+        let count = count as i32;
+        self.move_tiles(
+            |_, _| {},
+            |point| {
+                if point.x >= at {
+                    Some(Point2::new(point.x + count, point.y))
+                } else {
+                    Some(point)
+                }
+            },
+        )
+    }
+
+    /// Removes `count` columns starting from the column `at`, removing their
+    /// tiles and moving every tile to the right of them left by `count`
+    /// columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (0, 5), sprite_index: 2, ..Default::default() },
+    ///     Tile { point: (5, 0), sprite_index: 3, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.remove_columns(0, 1).unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 0), None);
+    /// assert_eq!(tilemap.get_tile((0, 5), 0), None);
+    /// assert_eq!(tilemap.get_tile((4, 0), 0).map(|t| t.index), Some(3));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a tile would be moved outside of the tilemap, an error is returned
+    /// and no tiles are moved. This can only happen if the tilemap has
+    /// dimensions.
+    pub fn remove_columns(&mut self, at: i32, count: u32) -> TilemapResult<()> {
+        let count = count as i32;
+        self.move_tiles(
+            |_, _| {},
+            |point| {
+                if point.x >= at + count {
+                    Some(Point2::new(point.x - count, point.y))
+                } else if point.x >= at {
+                    None
+                } else {
+                    Some(point)
+                }
+            },
+        )
+    }
+
+    /// Mirrors every tile along the x axis, around the center of the chunk at
+    /// `(0, 0)` which is the center of the tilemap.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (1, 2), sprite_index: 2, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.mirror_x().unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, 0), 0), None);
+    /// assert_eq!(tilemap.get_tile((-1, 0), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((-2, 2), 0).map(|t| t.index), Some(2));
+    /// ```
+    ///
+    /// The offsets and transforms of the tiles are mirrored with them, so
+    /// that decorations face the other way.
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, tile::TileTransform};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Decoration, ..Default::default() }, 1)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tile = Tile { point: (1, 1), sprite_index: 7, z_order: 1, offset: Vec2::new(4.0, 2.0), ..Default::default() };
+    /// tilemap.insert_tile(tile).unwrap();
+    /// tilemap.set_tile_transform((1, 1), 1, TileTransform::new(0.3, Vec2::new(0.5, 0.5))).unwrap();
+    ///
+    /// tilemap.mirror_x().unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((-2, 1), 1).map(|t| t.offset), Some(Vec2::new(-4.0, 2.0)));
+    /// assert_eq!(
+    ///     tilemap.tile_transform((-2, 1), 1),
+    ///     Some(TileTransform::new(-0.3, Vec2::new(-0.5, 0.5)))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the topology is not square or iso, an error is returned. If a tile
+    /// would be moved outside of the tilemap, an error is returned and no
+    /// tiles are moved.
+    pub fn mirror_x(&mut self) -> TilemapResult<()> {
+        self.check_grid_topology()?;
+        let offset = self.chunk_dimensions.width as i32 % 2 - 1;
+        self.move_tiles(
+            |offset, transform| {
+                offset.x = -offset.x;
+                if let Some(transform) = transform {
+                    transform.rotation = -transform.rotation;
+                    transform.scale.x = -transform.scale.x;
+                }
+            },
+            |point| Some(Point2::new(offset - point.x, point.y)),
+        )
+    }
+
+    /// Mirrors every tile along the y axis, around the center of the chunk at
+    /// `(0, 0)` which is the center of the tilemap.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (1, 2), sprite_index: 2, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.mirror_y().unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((0, -1), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((1, -3), 0).map(|t| t.index), Some(2));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the topology is not square or iso, an error is returned. If a tile
+    /// would be moved outside of the tilemap, an error is returned and no
+    /// tiles are moved.
+    pub fn mirror_y(&mut self) -> TilemapResult<()> {
+        self.check_grid_topology()?;
+        let offset = self.chunk_dimensions.height as i32 % 2 - 1;
+        self.move_tiles(
+            |offset, transform| {
+                offset.y = -offset.y;
+                if let Some(transform) = transform {
+                    transform.rotation = -transform.rotation;
+                    transform.scale.y = -transform.scale.y;
+                }
+            },
+            |point| Some(Point2::new(point.x, offset - point.y)),
+        )
+    }
+
+    /// Rotates every tile counter clockwise by 90 degrees, around the center
+    /// of the chunk at `(0, 0)` which is the center of the tilemap.
+    ///
+    /// The chunks have to be square. If the tilemap has dimensions which are
+    /// not square, the tiles that are rotated outside of it return an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tiles = vec![
+    ///     Tile { point: (0, 0), sprite_index: 1, ..Default::default() },
+    ///     Tile { point: (1, 2), sprite_index: 2, ..Default::default() },
+    /// ];
+    /// tilemap.insert_tiles(tiles).unwrap();
+    ///
+    /// tilemap.rotate_90().unwrap();
+    ///
+    /// assert_eq!(tilemap.get_tile((-1, 0), 0).map(|t| t.index), Some(1));
+    /// assert_eq!(tilemap.get_tile((-3, 1), 0).map(|t| t.index), Some(2));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the topology is not square or iso or if the chunks are not square,
+    /// an error is returned. If a tile would be moved outside of the tilemap,
+    /// an error is returned and no tiles are moved.
+    pub fn rotate_90(&mut self) -> TilemapResult<()> {
+        self.check_grid_topology()?;
+        if self.chunk_dimensions.width != self.chunk_dimensions.height {
+            return Err(ErrorKind::NonSquareChunks.into());
+        }
+        let offset = self.chunk_dimensions.width as i32 % 2 - 1;
+        self.move_tiles(
+            |offset, transform| {
+                *offset = Vec2::new(-offset.y, offset.x);
+                if let Some(transform) = transform {
+                    transform.rotation += ::std::f32::consts::FRAC_PI_2;
+                }
+            },
+            |point| Some(Point2::new(offset - point.y, point.x)),
+        )
+    }
+
+    /// Returns an error if the topology is not a grid of squares, which are
+    /// the only tiles that can be mirrored and rotated in place.
+    fn check_grid_topology(&self) -> TilemapResult<()> {
+        match self.topology {
+            GridTopology::Square | GridTopology::IsoDiamond => Ok(()),
+            topology => Err(ErrorKind::UnsupportedTopology(topology).into()),
+        }
+    }
+
+    /// Moves every tile to the point that is returned, or removes it if `None`
+    /// is returned. Everything kept for a tile, from its transform, channel
+    /// values and height to its decals, highlight and gameplay entity, moves
+    /// or is removed with it.
+    ///
+    /// The offset and transform of each moved tile are turned by `orient`, so
+    /// that they are mirrored or rotated with the points.
+    ///
+    /// The chunks in the chunk store are loaded first, as any of their tiles
+    /// may move.
+    fn move_tiles<O, F>(&mut self, orient: O, mut f: F) -> TilemapResult<()>
+    where
+        O: Fn(&mut Vec2, Option<&mut TileTransform>),
+        F: FnMut(Point2) -> Option<Point2>,
+    {
+        #[cfg(feature = "serialize")]
+        for point in self.unloaded_chunks.clone().into_iter() {
+            self.load_chunk(point)?;
+        }
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+        let mut cleared = Vec::new();
+        let mut moved = Vec::new();
+        let mut transforms = Vec::new();
+        let mut cleared_channels = Vec::new();
+        let mut channels = Vec::new();
+        let mut cleared_heights = Vec::new();
+        let mut heights = Vec::new();
+        let mut spawned = HashSet::default();
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (chunk_point, chunk) in chunks.into_iter() {
+            let is_spawned = self.spawned.contains(&(chunk_point.x, chunk_point.y));
+            let to_point = |index| -> TilemapResult<Point2> {
+                let tile_point = self.chunk_dimensions.decode_point(index)?;
+                Ok(Point2::new(
+                    tile_point.x + width * chunk_point.x - width / 2,
+                    tile_point.y + height * chunk_point.y - height / 2,
+                ))
+            };
+            let raised = in_order(chunk.heights().iter(), self.deterministic, |(index, _)| {
+                **index
+            });
+            for (index, tile_height) in raised.into_iter() {
+                let point = to_point(*index)?;
+                match f(point) {
+                    Some(new_point) if new_point == point => continue,
+                    Some(new_point) => heights.push((new_point, *tile_height)),
+                    None => {}
+                }
+                cleared_heights.push(point);
+            }
+            for z_order in 0..self.layers.len() {
+                let indices = if let Some(indices) = chunk.get_tile_indices(z_order) {
+                    indices
+                } else {
+                    continue;
+                };
+                for index in indices.into_iter() {
+                    let raw_tile = if let Some(raw_tile) = chunk.get_tile(z_order, index) {
+                        *raw_tile
+                    } else {
+                        continue;
+                    };
+                    let point = to_point(index)?;
+                    let new_point = f(point);
+                    if new_point == Some(point) {
+                        continue;
+                    }
+                    for channel in self.channels(z_order).iter() {
+                        if let Some(value) = chunk.channel_value(z_order, &channel.name, index) {
+                            if let Some(new_point) = new_point {
+                                channels.push((new_point, z_order, channel.name.clone(), value));
+                            }
+                            cleared_channels.push((point, z_order, channel.name.clone()));
+                        }
+                    }
+                    if let Some(new_point) = new_point {
+                        let mut offset = raw_tile.offset;
+                        let mut transform = chunk.tile_transform(z_order, index);
+                        orient(&mut offset, transform.as_mut());
+                        moved.push(Tile {
+                            point: new_point,
+                            z_order,
+                            sprite_index: raw_tile.index,
+                            tint: raw_tile.color,
+                            extra: raw_tile.extra,
+                            offset,
+                        });
+                        if let Some(transform) = transform {
+                            transforms.push((new_point, z_order, transform));
+                        }
+                        if is_spawned {
+                            spawned.insert(self.point_to_chunk_point(new_point));
+                        }
+                    }
+                    cleared.push((point, z_order));
+                }
+            }
+        }
+
+        // Make sure that every tile fits before anything is changed.
+        let mut new_chunks = HashSet::default();
+        let points = moved
+            .iter()
+            .map(|tile| tile.point)
+            .chain(heights.iter().map(|(point, _)| *point));
+        for point in points {
+            let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+            if let Some(dimensions) = &self.dimensions {
+                dimensions.check_point(chunk_point)?;
+            }
+            if !self.chunks.contains_key(&chunk_point) {
+                new_chunks.insert(chunk_point);
+            }
+        }
+        for chunk_point in new_chunks.into_iter() {
+            self.insert_chunk(chunk_point)?;
+        }
+
+        // The entities of moved tiles are taken out so that clearing the
+        // tiles does not unbind them, and those of removed tiles are unbound.
+        let bindings: Vec<((Point2, usize), Entity)> = self
+            .tile_entities
+            .iter()
+            .filter_map(|((point, z_order), entity)| match f(*point) {
+                Some(new_point) if new_point != *point => Some(((new_point, *z_order), *entity)),
+                _ => None,
+            })
+            .collect();
+        self.tile_entities
+            .retain(|(point, _), _| f(*point).is_none_or(|new_point| new_point == *point));
+        self.unbind_tile_entities(|point, _| f(point).is_none(), false);
+
+        self.clear_tiles(cleared)?;
+        self.insert_tiles(moved)?;
+        for chunk_point in spawned.into_iter() {
+            self.spawn_chunk(chunk_point)?;
+        }
+        for (point, z_order, transform) in transforms.into_iter() {
+            self.with_chunk_at(point, |chunk, index| {
+                chunk.set_tile_transform(z_order, index, transform);
+            });
+        }
+        let chunk_dimensions = self.chunk_dimensions;
+        for (point, z_order, name) in cleared_channels.into_iter() {
+            self.with_chunk_at(point, |chunk, index| {
+                chunk.set_channel_value(z_order, &name, index, [0.0; 4], chunk_dimensions);
+            });
+        }
+        for (point, z_order, name, value) in channels.into_iter() {
+            self.with_chunk_at(point, |chunk, index| {
+                chunk.set_channel_value(z_order, &name, index, value, chunk_dimensions);
+            });
+        }
+        for point in cleared_heights.into_iter() {
+            self.with_chunk_at(point, |chunk, index| chunk.set_height(index, 0.0));
+        }
+        for (point, tile_height) in heights.into_iter() {
+            self.with_chunk_at(point, |chunk, index| chunk.set_height(index, tile_height));
+        }
+        self.tile_entities.extend(bindings);
+
+        // The kinds of the layers of a chunk move with the center of it.
+        let mut overrides = Vec::new();
+        let keys = in_order(
+            self.layer_kind_overrides.iter(),
+            self.deterministic,
+            |((point, z_order), _)| (*point, *z_order),
+        );
+        for ((chunk_point, z_order), kind) in keys.into_iter() {
+            let center = Point2::new(width * chunk_point.x, height * chunk_point.y);
+            let new_chunk_point = f(center).map(|point| self.point_to_chunk_point(point).into());
+            if new_chunk_point != Some(*chunk_point) {
+                overrides.push((*chunk_point, *z_order, new_chunk_point, *kind));
+            }
+        }
+        for (chunk_point, z_order, _, _) in overrides.iter() {
+            self.set_chunk_layer_kind(*chunk_point, *z_order, None)?;
+        }
+        for (_, z_order, new_chunk_point, kind) in overrides.into_iter() {
+            if let Some(new_chunk_point) = new_chunk_point {
+                self.set_chunk_layer_kind(new_chunk_point, z_order, Some(kind))?;
+            }
+        }
+
+        // Animations, damage, decals and highlights move along with their
+        // tiles.
+        let animated_tiles = ::std::mem::take(&mut self.animated_tiles);
+        for ((point, z_order), animation) in animated_tiles.into_iter() {
+            if let Some(new_point) = f(point) {
+                self.animated_tiles.insert((new_point, z_order), animation);
+            }
+        }
+        let tile_damage = ::std::mem::take(&mut self.tile_damage);
+        for ((point, z_order), damage) in tile_damage.into_iter() {
+            if let Some(new_point) = f(point) {
+                self.tile_damage.insert((new_point, z_order), damage);
+            }
+        }
+        let decals = ::std::mem::take(&mut self.decals);
+        for (point, decals) in decals.into_iter() {
+            if let Some(new_point) = f(point) {
+                self.decals.insert(new_point, decals);
+            }
+        }
+        let highlights = ::std::mem::take(&mut self.highlights);
+        for (point, color) in highlights.into_iter() {
+            if let Some(new_point) = f(point) {
+                self.highlights.insert(new_point, color);
+            }
+        }
+        let kept_highlights = ::std::mem::take(&mut self.kept_highlights);
+        for point in kept_highlights.into_iter() {
+            if let Some(new_point) = f(point) {
+                self.kept_highlights.insert(new_point);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls a function with the chunk of a point and the index of the point
+    /// in it, if the chunk exists, and marks the layers of the chunk as
+    /// modified.
+    fn with_chunk_at<F>(&mut self, point: Point2, mut f: F)
+    where
+        F: FnMut(&mut Chunk, usize),
+    {
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        let tile_point = self.point_to_tile_point(point);
+        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
+        let chunk = match self.chunks.get_mut(&chunk_point).map(Arc::make_mut) {
+            Some(chunk) => chunk,
+            None => return,
+        };
+        f(chunk, index);
+        let mut layers = HashMap::default();
+        for z_order in 0..self.layers.len() {
+            if let Some(entity) = chunk.get_entity(z_order) {
+                layers.insert(z_order, entity);
+            }
+        }
+        self.modified_chunks.insert(chunk_point);
+        self.chunk_events
+            .send(TilemapChunkEvent::Modified { layers });
+    }
+
+    /// Returns the center tile, if the tilemap has dimensions.
+    ///
+    /// Returns `None` if the tilemap has no constrainted dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle.clone_weak())
+    ///     .dimensions(32, 32)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let center = tilemap.center_tile_coord();
+    ///
+    /// // 32 * 32 / 2 = 512
+    /// assert_eq!(center, Some((512, 512)));
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let center = tilemap.center_tile_coord();
+    ///
+    /// assert_eq!(center, None);
+    /// ```
+    pub fn center_tile_coord(&self) -> Option<(i32, i32)> {
+        self.dimensions.map(|dimensions| {
+            (
+                (dimensions.width / 2 * self.chunk_dimensions.width) as i32,
+                (dimensions.height / 2 * self.chunk_dimensions.height) as i32,
+            )
+        })
+    }
+
+    /// The width of the tilemap in chunks, if it has dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle.clone_weak())
+    ///     .dimensions(32, 64)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let width = tilemap.width();
+    ///
+    /// assert_eq!(width, Some(32));
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let width = tilemap.width();
+    ///
+    /// assert_eq!(width, None);
+    /// ```
+    pub fn width(&self) -> Option<u32> {
+        self.dimensions.map(|dimensions| dimensions.width)
+    }
+
+    /// The height of the tilemap in chunks, if it has dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle.clone_weak())
+    ///     .dimensions(32, 64)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let height = tilemap.height();
+    ///
+    /// assert_eq!(height, Some(64));
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let height = tilemap.height();
+    ///
+    /// assert_eq!(height, None);
+    /// ```
+    pub fn height(&self) -> Option<u32> {
+        self.dimensions.map(|dimensions| dimensions.height)
+    }
+
+    /// The width of all the chunks in tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(32, 64)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let chunk_width: u32 = tilemap.chunk_width();
+    ///
+    /// assert_eq!(chunk_width, 32);
+    /// ```
+    pub fn chunk_width(&self) -> u32 {
+        self.chunk_dimensions.width
+    }
+
+    /// The height of all the chunks in tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(32, 64)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let chunk_height: u32 = tilemap.chunk_height();
+    ///
+    /// assert_eq!(chunk_height, 64);
+    /// ```
+    pub fn chunk_height(&self) -> u32 {
+        self.chunk_dimensions.height
+    }
+
+    /// The width of a tile in pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 64)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tile_width: u32 = tilemap.tile_width();
+    ///
+    /// assert_eq!(tile_width, 32);
+    /// ```
+    pub fn tile_width(&self) -> u32 {
+        self.tile_dimensions.width
+    }
+
+    /// The height of a tile in pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 64)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let tile_height: u32 = tilemap.tile_height();
+    ///
+    /// assert_eq!(tile_height, 64);
+    /// ```
+    pub fn tile_height(&self) -> u32 {
+        self.tile_dimensions.height
+    }
+
+    /// Gets a reference to a chunk.
+    pub(crate) fn get_chunk(&self, point: &Point2) -> Option<&Chunk> {
+        self.chunks.get(point).map(|chunk| &**chunk)
+    }
+
+    /// The topology of the tilemap grid.
+    ///
+    /// Currently there are 8 topologies which are set with [`GridTopology`]. By
+    /// default this is square as it is the most common topology.
+    ///
+    /// Typically, for most situations squares are used for local maps and hex
+    /// is used for war games or world maps. It is easier to define structures
+    /// with walls and floors with square but not impossible with hex. Iso
+    /// diamonds give a 2.5D look to a square grid.
+    ///
+    /// [`GridTopology`]: crate::render::GridTopology
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .topology(GridTopology::HexX)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(tilemap.topology(), GridTopology::HexX);
+    /// ```
+    pub fn topology(&self) -> GridTopology {
+        self.topology
+    }
+
+    /// Returns a reference to the tilemap chunk events.
+    ///
+    /// This is handy if it is needed to know when new chunks are created which
+    /// can then be used to trigger events with other systems. For example,
+    /// if you have a system that adds tiles procedurally to the chunks, upon
+    /// a chunk event this can be used to trigger the creation of those tiles.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_app::prelude::*;
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, event::TilemapChunkEvent};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let events: &Events<TilemapChunkEvent> = tilemap.chunk_events();
+    /// ```
+    pub fn chunk_events(&self) -> &Events<TilemapChunkEvent> {
+        &self.chunk_events
+    }
+
+    /// Updates the chunk events. This should only be done once per frame.
+    pub(crate) fn chunk_events_update(&mut self) {
+        self.chunk_events.update()
+    }
+
+    /// Returns a reference to the tilemap collision events.
+    ///
+    /// This is handy if you need to know which collisions were spawned which
+    /// can then be used to trigger other systems. It should be used in
+    /// conjunction with [`chunk_events_update`] as collisions will spawn
+    /// on a chunk spawn and be despawned with a chunk despawn. Those will not
+    /// trigger events.
+    ///
+    /// [`chunk_events_update`]:
+    ///
+    ///
+    pub fn collision_events(&self) -> &Events<TilemapCollisionEvent> {
+        &self.collision_events
+    }
+
+    /// Returns the point and z order of the tile that a collider entity of the
+    /// tilemap was hit at, or `None` if the entity is not one of its
+    /// colliders.
+    ///
+    /// Colliders cover a rectangle of tiles, so the translation of the hit,
+    /// relative to the tilemap's transform and in pixels, picks the tile of
+    /// the rectangle. A translation outside of the rectangle picks the tile on
+    /// its edge which is closest to it. The [`TileCollider`] component of
+    /// the entity has the whole rectangle.
+    ///
+    /// [`TileCollider`]: crate::physics::TileCollider
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_ecs::Entity;
+    /// use bevy_math::Vec2;
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// assert!(tilemap.collider_to_tile(Entity::new(1), Vec2::new(16.0, 16.0)).is_none());
+    /// ```
+    pub fn collider_to_tile(&self, entity: Entity, translation: Vec2) -> Option<(Point2, usize)> {
+        let width = self.chunk_dimensions.width as i32;
+        let height = self.chunk_dimensions.height as i32;
+        self.chunks.iter().find_map(|(chunk_point, chunk)| {
+            chunk.get_collider(entity).map(|(z_order, collider)| {
+                let min = Point2::new(
+                    collider.point.x + width * chunk_point.x - width / 2,
+                    collider.point.y + height * chunk_point.y - height / 2,
+                );
+                let max = Point2::new(
+                    min.x + collider.dimensions.width as i32 - 1,
+                    min.y + collider.dimensions.height as i32 - 1,
+                );
+                let point = self.translation_to_point(translation);
+                let point =
+                    Point2::new(point.x.max(min.x).min(max.x), point.y.max(min.y).min(max.y));
+                (point, z_order)
+            })
+        })
+    }
+
+    /// Sets if the tiles of a sprite index are one-way platforms, see
+    /// [`TilemapLayer::one_way`]. The collisions of the spawned chunks with the
+    /// sprite index are spawned again.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.set_one_way_sprite(4, true);
+    ///
+    /// assert!(tilemap.is_one_way_sprite(4));
+    /// assert!(!tilemap.is_one_way_sprite(5));
+    /// ```
+    pub fn set_one_way_sprite(&mut self, index: usize, one_way: bool) {
+        let changed = if one_way {
+            self.one_way_sprites.insert(index)
+        } else {
+            self.one_way_sprites.remove(&index)
+        };
+        if changed {
+            self.respawn_sprite_collisions(index);
+        }
+    }
+
+    /// Sets if the tiles of a sprite index are sensors, which send a
+    /// [`TileTriggerEvent`] when a rigid body starts to overlap them. The
+    /// collisions of the spawned chunks with the sprite index are spawned
+    /// again.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// tilemap.set_sensor_sprite(7, true);
+    ///
+    /// assert!(tilemap.is_sensor_sprite(7));
+    /// assert!(!tilemap.is_sensor_sprite(8));
+    /// ```
+    pub fn set_sensor_sprite(&mut self, index: usize, sensor: bool) {
+        let changed = if sensor {
+            self.sensor_sprites.insert(index)
+        } else {
+            self.sensor_sprites.remove(&index)
+        };
+        if changed {
+            self.respawn_sprite_collisions(index);
+        }
+    }
+
+    /// Returns if the tiles of a sprite index are sensors.
+    pub fn is_sensor_sprite(&self, index: usize) -> bool {
+        self.sensor_sprites.contains(&index)
+    }
+
+    /// Returns a reference to the sprite indexes whose tiles are sensors.
+    pub(crate) fn sensor_sprites(&self) -> &HashSet<usize> {
+        &self.sensor_sprites
+    }
+
+    /// Sends a collision spawned event for every tile with the sprite index in
+    /// the spawned chunks.
+    fn respawn_sprite_collisions(&mut self, index: usize) {
+        self.respawn_collisions(|_, raw_tile| raw_tile.index == index)
+    }
+
+    /// Sends a collision spawned event for every tile of the spawned chunks
+    /// that the filter returns `true` for, with its z order.
+    fn respawn_collisions<F>(&mut self, mut filter: F)
+    where
+        F: FnMut(usize, &RawTile) -> bool,
+    {
+        let chunks = in_order(self.chunks.iter(), self.deterministic, |(point, _)| **point);
+        for (chunk_point, chunk) in chunks.into_iter() {
+            if !self.spawned.contains(&(chunk_point.x, chunk_point.y)) {
+                continue;
+            }
+            let mut tiles = Vec::new();
+            for z_order in 0..self.layers.len() {
+                let indices = if let Some(indices) = chunk.get_tile_indices(z_order) {
+                    indices
+                } else {
+                    continue;
+                };
+                for tile_index in indices.into_iter() {
+                    let raw_tile = match chunk.get_tile(z_order, tile_index) {
+                        Some(raw_tile) if filter(z_order, raw_tile) => *raw_tile,
+                        _ => continue,
+                    };
+                    let point = match self.chunk_dimensions.decode_point(tile_index) {
+                        Ok(point) => point,
+                        Err(_) => continue,
+                    };
+                    tiles.push(Tile {
+                        point,
+                        z_order,
+                        sprite_index: raw_tile.index,
+                        tint: raw_tile.color,
+                        extra: raw_tile.extra,
+                        offset: raw_tile.offset,
+                    });
+                }
+            }
+            if !tiles.is_empty() {
+                self.collision_events.send(TilemapCollisionEvent::Spawned {
+                    chunk_point: *chunk_point,
+                    tiles,
+                });
+            }
+        }
+    }
+
+    /// Returns if the tiles of a sprite index are one-way platforms.
+    pub fn is_one_way_sprite(&self, index: usize) -> bool {
+        self.one_way_sprites.contains(&index)
+    }
+
+    /// Returns a reference to the sprite indexes whose tiles are one-way
+    /// platforms.
+    pub(crate) fn one_way_sprites(&self) -> &HashSet<usize> {
+        &self.one_way_sprites
+    }
+
+    /// Updates the collision events. This should only be done once per frame.
+    pub(crate) fn collision_events_update(&mut self) {
+        self.collision_events.update()
+    }
+
+    /// Returns a reference to the trigger events of the sensor tiles.
+    ///
+    /// A [`TileTriggerEvent`] is sent by the physics backend when a body
+    /// starts to overlap a tile of a layer with [`TilemapLayer::sensor`] set,
+    /// or a tile of a sprite index set with [`set_sensor_sprite`]. With the
+    /// rapier backend only dynamic rigid bodies overlap the sensors and the
+    /// `RapierPhysicsPlugin` must be added for them to be detected.
+    ///
+    /// [`set_sensor_sprite`]: Tilemap::set_sensor_sprite
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_app::prelude::*;
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, event::TileTriggerEvent};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    ///
+    /// let events: &Events<TileTriggerEvent> = tilemap.trigger_events();
+    /// ```
+    pub fn trigger_events(&self) -> &Events<TileTriggerEvent> {
+        &self.trigger_events
+    }
+
+    /// Sends a trigger event of a sensor tile. This is done by the physics
+    /// backend, see [`physics`].
+    ///
+    /// [`physics`]: crate::physics
+    pub fn send_trigger_event(&mut self, event: TileTriggerEvent) {
+        self.trigger_events.send(event)
+    }
+
+    /// Updates the trigger events. This should only be done once per frame.
+    pub(crate) fn trigger_events_update(&mut self) {
+        self.trigger_events.update()
+    }
+
+    /// Returns a copy of the physics scale.
+    #[cfg(feature = "bevy_rapier2d")]
+    pub fn physics_scale(&self) -> f32 {
+        self.physics_scale
+    }
+
+    /// Sets the physics scale.
+    #[cfg(feature = "bevy_rapier2d")]
+    pub fn set_physics_scale(&mut self, scale: f32) {
+        self.physics_scale = scale;
+    }
+
+    /// Returns the radius of chunks around each [`CollisionTracker`] which
+    /// get colliders, or `None` if every spawned chunk does.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert!(tilemap.collision_radius().is_none());
+    ///
+    /// tilemap.set_collision_radius(2, 1);
+    /// assert_eq!(tilemap.collision_radius().unwrap().width, 2);
+    ///
+    /// tilemap.remove_collision_radius();
+    /// assert!(tilemap.collision_radius().is_none());
+    /// ```
+    ///
+    /// [`CollisionTracker`]: crate::physics::CollisionTracker
+    pub fn collision_radius(&self) -> Option<Dimension2> {
+        self.collision_radius
+    }
+
+    /// Sets the radius of chunks around each [`CollisionTracker`] which get
+    /// colliders. The colliders of the other chunks are dropped in the next
+    /// frame.
+    ///
+    /// [`CollisionTracker`]: crate::physics::CollisionTracker
+    pub fn set_collision_radius(&mut self, width: u32, height: u32) {
+        self.collision_radius = Some(Dimension2::new(width, height));
+    }
+
+    /// Removes the collision radius, so that every spawned chunk gets its
+    /// colliders again in the next frame.
+    pub fn remove_collision_radius(&mut self) {
+        self.collision_radius = None;
+    }
+
+    /// Sets how much of a chunk is kept up to date.
+    ///
+    /// A chunk which is woken up from [`ChunkActivity::Frozen`] has its meshes
+    /// updated with the tiles that changed while it was frozen. While there is
+    /// an activity policy, it sets the activity of the spawned chunks every
+    /// frame instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::ChunkActivity, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// assert_eq!(tilemap.chunk_activity((0, 0)), Some(ChunkActivity::Active));
+    ///
+    /// assert!(tilemap.set_chunk_activity((0, 0), ChunkActivity::Frozen).is_ok());
+    /// assert_eq!(tilemap.chunk_activity((0, 0)), Some(ChunkActivity::Frozen));
+    ///
+    /// assert!(tilemap.set_chunk_activity((1, 1), ChunkActivity::Frozen).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk does not exist.
+    pub fn set_chunk_activity<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        activity: ChunkActivity,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        let chunk = match self.chunks.get_mut(&point).map(Arc::make_mut) {
+            Some(chunk) => chunk,
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        let previous = chunk.activity();
+        chunk.set_activity(activity);
+        if previous == ChunkActivity::Frozen && activity != ChunkActivity::Frozen {
+            let mut layers = HashMap::default();
+            for z_order in 0..self.layers.len() {
+                if let Some(entity) = chunk.get_entity(z_order) {
+                    layers.insert(z_order, entity);
+                }
+            }
+            self.modified_chunks.insert(point);
+            self.chunk_events
+                .send(TilemapChunkEvent::Modified { layers });
+        }
+
+        Ok(())
+    }
+
+    /// Returns how much of a chunk is kept up to date, or `None` if the chunk
+    /// does not exist.
+    pub fn chunk_activity<P: Into<Point2>>(&self, point: P) -> Option<ChunkActivity> {
+        self.chunks.get(&point.into()).map(|chunk| chunk.activity())
+    }
+
+    /// Returns the activity policy, if there is one.
+    pub fn activity_policy(&self) -> Option<ActivityPolicy> {
+        self.activity_policy
+    }
+
+    /// Sets the activity policy, or removes it with `None`. Without a policy
+    /// the chunks keep the activity they had.
+    pub fn set_activity_policy(&mut self, policy: Option<ActivityPolicy>) {
+        self.activity_policy = policy;
+    }
+
+    /// Marks a chunk as static, or dynamic again with `false`.
+    ///
+    /// The tiles of a static chunk can no longer be inserted, cleared or
+    /// changed, which lets the tilemap skip them: their animated tiles and
+    /// decals are not advanced, their navigation meshes are never dirty and
+    /// their colliders, once built, are kept while the chunk is spawned
+    /// instead of being dropped and rebuilt as collision trackers move.
+    /// Chunks are usually made static once they were generated.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// assert!(tilemap.set_chunk_static((0, 0), true).is_ok());
+    /// assert!(tilemap.is_chunk_static((0, 0)));
+    ///
+    /// assert!(tilemap.insert_tile(Tile { point: (1, 1), ..Default::default() }).is_err());
+    /// assert!(tilemap.clear_tile((0, 0), 0).is_err());
+    /// assert_eq!(tilemap.get_tile_mut((0, 0), 0), None);
+    ///
+    /// // Other chunks can still be changed.
+    /// assert!(tilemap.insert_tile(Tile { point: (4, 4), ..Default::default() }).is_ok());
+    ///
+    /// assert!(tilemap.set_chunk_static((0, 0), false).is_ok());
+    /// assert!(tilemap.clear_tile((0, 0), 0).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk does not exist.
+    pub fn set_chunk_static<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        is_static: bool,
+    ) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if !self.chunks.contains_key(&point) {
+            return Err(ErrorKind::MissingChunk.into());
+        }
+        if is_static {
+            self.static_chunks.insert(point);
+        } else {
+            self.static_chunks.remove(&point);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the chunk was marked as static.
+    pub fn is_chunk_static<P: Into<Point2>>(&self, point: P) -> bool {
+        self.static_chunks.contains(&point.into())
+    }
+
+    /// Marks the layer at the z order as static in every chunk, or dynamic
+    /// again with `false`, see [`set_chunk_static`].
+    ///
+    /// [`set_chunk_static`]: Tilemap::set_chunk_static
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .z_layers(2)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (0, 0), z_order: 0, ..Default::default() }).unwrap();
+    /// assert!(tilemap.set_layer_static(0, true).is_ok());
+    /// assert!(tilemap.is_layer_static(0));
+    /// assert!(tilemap.is_tile_static((9, 9), 0));
+    ///
+    /// assert!(tilemap.clear_tile((0, 0), 0).is_err());
+    /// assert!(tilemap.insert_tile(Tile { point: (0, 0), z_order: 1, ..Default::default() }).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer does not exist.
+    pub fn set_layer_static(&mut self, z_order: usize, is_static: bool) -> TilemapResult<()> {
+        if !self.layers.get(z_order).is_some_and(Option::is_some) {
+            return Err(ErrorKind::LayerDoesNotExist(z_order).into());
+        }
+        if is_static {
+            self.static_layers.insert(z_order);
+        } else {
+            self.static_layers.remove(&z_order);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the layer at the z order was marked as static.
+    pub fn is_layer_static(&self, z_order: usize) -> bool {
+        self.static_layers.contains(&z_order)
+    }
+
+    /// Returns `true` if the tile at the point and z order can not be
+    /// changed, because its chunk or layer is static.
+    pub fn is_tile_static<P: Into<Point2>>(&self, point: P, z_order: usize) -> bool {
+        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
+        self.static_chunks.contains(&chunk_point) || self.static_layers.contains(&z_order)
+    }
+
+    /// Returns how chunks are spawned and despawned around the cameras, with
+    /// the spawn budget of the tilemap, or `None` without auto spawn.
+    pub fn spawn_config(&self) -> Option<ChunkSpawnConfig> {
+        self.spawn_config.map(|config| ChunkSpawnConfig {
+            budget_per_frame: self.spawn_budget,
+            ..config
+        })
+    }
+
+    /// Sets how chunks are spawned and despawned around the cameras, or turns
+    /// auto spawn off with `None`. The spawn budget of the config replaces
+    /// the spawn budget of the tilemap, if it has one, and its radii are no
+    /// longer fit to the window when it is resized.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::ChunkSpawnConfig, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_spawn(2, 2)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// // Chunks are kept until they are three chunks away.
+    /// tilemap.set_spawn_config(Some(ChunkSpawnConfig::new((2, 2), (3, 3))));
+    ///
+    /// assert_eq!(tilemap.spawn_config().unwrap().despawn_radius, (3, 3).into());
+    /// ```
+    ///
+    /// Unlike the radius of [`auto_spawn`], the radii of the config are kept
+    /// when the window is resized.
+    ///
+    /// [`auto_spawn`]: TilemapBuilder::auto_spawn
+    ///
+    /// ```
+    /// use bevy_app::Events;
+    /// use bevy_tilemap::{chunk::ChunkSpawnConfig, prelude::*, testing::tilemap_test_harness};
+    /// use bevy_window::{WindowId, WindowResized};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_spawn(2, 2)
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.set_spawn_config(Some(ChunkSpawnConfig::new((1, 1), (3, 3))));
+    /// let entity = harness.spawn_tilemap(tilemap);
+    ///
+    /// harness
+    ///     .app()
+    ///     .resources
+    ///     .get_mut::<Events<WindowResized>>()
+    ///     .unwrap()
+    ///     .send(WindowResized { id: WindowId::primary(), width: 3840.0, height: 2160.0 });
+    /// harness.tick();
+    ///
+    /// let config = harness.tilemap(entity).unwrap().spawn_config().unwrap();
+    /// assert_eq!(config.spawn_radius, (1, 1).into());
+    /// ```
+    pub fn set_spawn_config(&mut self, config: Option<ChunkSpawnConfig>) {
+        self.spawn_config = config;
+        self.auto_flags.remove(AutoFlags::AUTO_SPAWN);
+        if let Some(budget) = config.and_then(|config| config.budget_per_frame) {
+            self.set_spawn_budget(Some(budget));
+        }
+    }
+
+    /// Returns how many chunks are spawned in a frame, if there is a budget.
+    pub fn spawn_budget(&self) -> Option<SpawnBudget> {
+        self.spawn_budget
+    }
+
+    /// Sets how many chunks are spawned in a frame, or removes the budget with
+    /// `None`, which spawns the queued chunks right away.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{chunk::SpawnBudget, prelude::*};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .spawn_budget(SpawnBudget::Chunks(1))
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    ///
+    /// assert!(tilemap.queued_chunks().contains(&(0, 0).into()));
+    ///
+    /// tilemap.set_spawn_budget(None);
+    ///
+    /// assert!(tilemap.queued_chunks().is_empty());
+    /// ```
+    pub fn set_spawn_budget(&mut self, budget: Option<SpawnBudget>) {
+        self.spawn_budget = budget;
+        if budget.is_none() {
+            let queued = in_order(self.spawn_queue.drain(), self.deterministic, |point| *point);
+            for point in queued.into_iter() {
+                self.chunk_events.send(TilemapChunkEvent::Spawned { point });
+            }
+        }
+    }
+
+    /// Returns the chunks which wait for the spawn budget to be spawned.
+    pub fn queued_chunks(&self) -> &HashSet<Point2> {
+        &self.spawn_queue
+    }
+
+    /// Sets the chunks of the cameras or streaming anchors, from which the
+    /// queued chunks are spawned.
+    pub(crate) fn set_spawn_focus(&mut self, points: Vec<Point2>) {
+        self.spawn_focus = points;
+    }
+
+    /// Returns the chunks of the cameras or streaming anchors, from which the
+    /// queued chunks are spawned.
+    pub(crate) fn spawn_focus(&self) -> &[Point2] {
+        &self.spawn_focus
+    }
+
+    /// Returns the queued chunks ordered from the nearest to the chunk of a
+    /// camera or streaming anchor.
+    pub(crate) fn spawn_queue_by_distance(&self) -> BinaryHeap<Reverse<(i64, Point2)>> {
+        self.spawn_queue
+            .iter()
+            .map(|point| Reverse((self.chunk_distance(*point), *point)))
+            .collect()
+    }
+
+    /// Returns the squared distance in chunks of a chunk to the nearest chunk
+    /// of a camera or streaming anchor, or to the origin without any.
+    pub(crate) fn chunk_distance(&self, point: Point2) -> i64 {
+        let distance = |focus: Point2| {
+            let offset = point - focus;
+            let x = i64::from(offset.x);
+            let y = i64::from(offset.y);
+            x * x + y * y
+        };
+        self.spawn_focus
+            .iter()
+            .map(|focus| distance(*focus))
+            .min()
+            .unwrap_or_else(|| distance(Point2::default()))
+    }
+
+    /// Takes a chunk out of the spawn queue and sends its spawned event,
+    /// returning `false` if it was not queued.
+    pub(crate) fn spawn_queued_chunk(&mut self, point: Point2) -> bool {
+        if !self.spawn_queue.remove(&point) {
+            return false;
+        }
+        self.chunk_events.send(TilemapChunkEvent::Spawned { point });
+        true
+    }
+
+    /// Rebuilds the meshes of every spawned chunk which has tiles from one
+    /// point to another, both included, right away and regardless of budgets.
+    /// For moments where the map must look right in this frame, such as
+    /// before a screenshot or at the start of a cutscene.
+    ///
+    /// The meshes of frozen chunks are rebuilt as well. Chunks of the region
+    /// which wait in the spawn queue or had their meshes shed are spawned in
+    /// the next run of the tilemap stage, regardless of the spawn budget.
+    ///
+    /// Returns the amount of layer meshes that were rebuilt.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::prelude::*;
+    /// use bevy_render::prelude::*;
+    /// use bevy_tilemap::{chunk::ChunkActivity, prelude::*, testing::tilemap_test_harness};
+    ///
+    /// let mut harness = tilemap_test_harness();
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// // Frozen chunks do not update their meshes.
+    /// {
+    ///     let app = harness.app();
+    ///     let mut tilemap = app.world.get_mut::<Tilemap>(entity).unwrap();
+    ///     tilemap.set_chunk_activity((0, 0), ChunkActivity::Frozen).unwrap();
+    ///     tilemap.get_tile_mut((0, 0), 0).unwrap().index = 3;
+    ///
+    ///     let mut meshes = app.resources.get_mut::<Assets<Mesh>>().unwrap();
+    ///     assert_eq!(tilemap.force_rebuild((-2, -2), (1, 1), &mut meshes), 1);
+    /// }
+    ///
+    /// let indexes = harness.chunk_tile_indexes(entity, (0, 0), 0).unwrap();
+    /// assert_eq!(indexes[2 * 4 + 2], 3);
+    /// ```
+    #[cfg(feature = "render")]
+    pub fn force_rebuild<P: Into<Point2>>(
+        &mut self,
+        from: P,
+        to: P,
+        meshes: &mut Assets<Mesh>,
+    ) -> usize {
+        let from: Point2 = self.point_to_chunk_point(from).into();
+        let to: Point2 = self.point_to_chunk_point(to).into();
+        let occlusions: Vec<Option<TileOcclusion>> = (0..self.layers.len())
+            .map(|z_order| self.layer_occlusion(z_order))
+            .collect();
+        let mut rebuilt = 0;
+        for y in from.y.min(to.y)..=from.y.max(to.y) {
+            for x in from.x.min(to.x)..=from.x.max(to.x) {
+                let point = Point2::new(x, y);
+                if self.spawn_queued_chunk(point) {
+                    continue;
+                }
+                if self.shed_meshes.remove(&point) {
+                    if let Err(e) = self.spawn_chunk(point) {
+                        warn!("{}", e);
+                    }
+                    continue;
+                }
+                if !self.spawned.contains(&(point.x, point.y)) {
+                    continue;
+                }
+                let chunk = match self.chunks.get_mut(&point).map(Arc::make_mut) {
+                    Some(chunk) => chunk,
+                    None => continue,
+                };
+                for z_order in 0..self.layers.len() {
+                    let has_mesh = chunk
+                        .get_mesh(z_order)
+                        .and_then(|handle| meshes.get(handle))
+                        .is_some();
+                    if !has_mesh {
+                        continue;
+                    }
+                    let y_sort = if let Some(Some(layer)) = self.layers.get(z_order) {
+                        layer.is_y_sorted()
+                    } else {
+                        false
+                    };
+                    crate::system::build_layer_mesh(
+                        meshes,
+                        chunk,
+                        z_order,
+                        self.chunk_dimensions,
+                        self.tile_dimensions,
+                        self.topology,
+                        y_sort,
+                        crate::system::layer_walls(
+                            &self.layers,
+                            z_order,
+                            self.topology,
+                            &self.wall_sprites,
+                        ),
+                        occlusions.get(z_order).and_then(Option::as_ref),
+                        &self.sprite_rects,
+                        &self.channels,
+                    );
+                    rebuilt += 1;
+                }
+            }
+        }
+        rebuilt
+    }
+
+    /// Moves the origin of the tilemap to a chunk, so that the chunk which was
+    /// at the offset is at `(0, 0)` afterwards, for floating origin games
+    /// which keep the camera near the origin to avoid the imprecision of `f32`
+    /// translations far away from it.
+    ///
+    /// Every chunk is given its new point and the tiles their new points,
+    /// along with everything kept about them such as animations, damage,
+    /// decals and navigation meshes, without their tiles being changed.
+    /// Chunks which were unloaded to the chunk store stay there, and are read
+    /// back from where they were written once they are loaded. The spawned
+    /// chunks move to their new translations in the next run of the tilemap
+    /// stage, where their colliders are spawned again, so the rest of the
+    /// world should be moved by the same amount at the same time.
+    ///
+    /// Iso tilemaps and y sorted layers are only sorted by depth within about
+    /// 4095 cells of the origin, see [`iso`], so endless iso maps should move
+    /// the origin before the camera gets that far.
+    ///
+    /// [`iso`]: crate::iso
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.insert_tile(Tile { point: (400, -80), sprite_index: 3, ..Default::default() }).unwrap();
+    /// assert!(tilemap.contains_chunk((100, -20)));
+    ///
+    /// assert!(tilemap.shift_origin((100, -20)).is_ok());
+    ///
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert!(!tilemap.contains_chunk((100, -20)));
+    /// assert_eq!(tilemap.get_tile((0, 0), 0).unwrap().index, 3);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tilemap has dimensions, or if the tiles of a
+    /// hex tilemap with offset rows or columns would move by an odd amount
+    /// across them, which would change which of them are offset. The tiles
+    /// can also not move by more than the points of a tilemap reach.
+    pub fn shift_origin<P: Into<Point2>>(&mut self, delta_chunks: P) -> TilemapResult<()> {
+        let delta: Point2 = delta_chunks.into();
+        if self.dimensions.is_some() {
+            return Err(ErrorKind::FixedDimensions.into());
+        }
+        let offset = match (
+            delta.x.checked_mul(self.chunk_dimensions.width as i32),
+            delta.y.checked_mul(self.chunk_dimensions.height as i32),
+        ) {
+            (Some(x), Some(y)) => Point2::new(x, y),
+            _ => {
+                let origin = WorldPoint::new(
+                    self.origin.x + i64::from(delta.x),
+                    self.origin.y + i64::from(delta.y),
+                );
+                return Err(ErrorKind::DistantWorldPoint(origin).into());
+            }
+        };
+        let changes_offset = match self.topology {
+            GridTopology::HexEvenRows | GridTopology::HexOddRows => offset.y % 2 != 0,
+            GridTopology::HexEvenCols | GridTopology::HexOddCols => offset.x % 2 != 0,
+            _ => false,
+        };
+        if changes_offset {
+            return Err(ErrorKind::UnsupportedTopology(self.topology).into());
         }
-        let chunk_map = self.sort_tiles_to_chunks(tiles)?;
-        let mut layers = HashMap::default();
-        for (chunk_point, tiles) in chunk_map.into_iter() {
-            let chunk = match self.chunks.get_mut(&chunk_point) {
-                Some(c) => c,
-                None => return Err(ErrorKind::MissingChunk.into()),
-            };
-            for tile in tiles.iter() {
-                let index = self.chunk_dimensions.encode_point_unchecked(tile.point);
-                chunk.remove_tile(index, tile.z_order);
-                if let Some(entity) = chunk.get_entity(tile.z_order) {
-                    layers.entry(tile.z_order).or_insert(entity);
+        if delta == Point2::new(0, 0) {
+            return Ok(());
+        }
+        // The chunk store keeps chunks at the points they were written at,
+        // which the unloaded chunks remember until they are loaded.
+        #[cfg(feature = "serialize")]
+        {
+            let mut store_points = HashMap::default();
+            for point in self.unloaded_chunks.iter() {
+                let store_point = self.store_point(*point);
+                if *point - delta != store_point {
+                    store_points.insert(*point - delta, store_point);
                 }
             }
+            self.store_points = store_points;
+            self.layer_permutations = self
+                .layer_permutations
+                .drain()
+                .map(|(point, permutation)| (point - delta, permutation))
+                .collect();
+            self.unloaded_chunks = self
+                .unloaded_chunks
+                .drain()
+                .map(|point| point - delta)
+                .collect();
+        }
 
-            #[cfg(feature = "bevy_rapier2d")]
-            self.collision_events
-                .send(TilemapCollisionEvent::Despawned { chunk_point, tiles });
+        self.chunks = self
+            .chunks
+            .drain()
+            .map(|(point, mut chunk)| {
+                Arc::make_mut(&mut chunk).set_point(point - delta);
+                (point - delta, chunk)
+            })
+            .collect();
+        self.spawned = self
+            .spawned
+            .drain()
+            .map(|(x, y)| (x - delta.x, y - delta.y))
+            .collect();
+        for points in vec![
+            &mut self.spawn_queue,
+            &mut self.shed_meshes,
+            &mut self.modified_chunks,
+            &mut self.static_chunks,
+        ]
+        .into_iter()
+        {
+            *points = points.drain().map(|point| point - delta).collect();
+        }
+        if let Some(points) = self.collision_chunks.as_mut() {
+            *points = points.drain().map(|point| point - delta).collect();
+        }
+        for focus in self.spawn_focus.iter_mut() {
+            *focus -= delta;
+        }
+        self.changed_tiles = self
+            .changed_tiles
+            .drain()
+            .map(|(point, z_order)| (point - offset, z_order))
+            .collect();
+        if let Some(changes) = &mut self.changes {
+            changes.shift(delta, offset);
+        }
+        self.shed_colliders = self
+            .shed_colliders
+            .drain()
+            .map(|(point, colliders)| (point - delta, colliders))
+            .collect();
+        self.animated_tiles = self
+            .animated_tiles
+            .drain()
+            .map(|((point, z_order), animation)| ((point - offset, z_order), animation))
+            .collect();
+        self.tile_damage = self
+            .tile_damage
+            .drain()
+            .map(|((point, z_order), damage)| ((point - offset, z_order), damage))
+            .collect();
+        self.decals = self
+            .decals
+            .drain()
+            .map(|(point, decals)| (point - offset, decals))
+            .collect();
+        self.tile_entities = self
+            .tile_entities
+            .drain()
+            .map(|((point, z_order), entity)| ((point - offset, z_order), entity))
+            .collect();
+        for navmesh in self.navmeshes.values_mut() {
+            navmesh.clear();
+            for point in self.chunks.keys() {
+                navmesh.mark_dirty(*point);
+            }
         }
 
-        self.chunk_events
-            .send(TilemapChunkEvent::Modified { layers });
+        self.origin.x += i64::from(delta.x);
+        self.origin.y += i64::from(delta.y);
 
-        Ok(())
-    }
+        self.chunk_events.send(TilemapChunkEvent::Rehomed { delta });
 
-    /// Takes a global tile point and returns a tile point in a chunk.
-    fn point_to_tile_point(&self, point: Point2) -> Point2 {
-        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
-        let width = self.chunk_dimensions.width as i32;
-        let height = self.chunk_dimensions.height as i32;
-        Point2::new(
-            point.x - (width * chunk_point.x) + (width / 2),
-            point.y - (height * chunk_point.y) + (height / 2),
-        )
+        Ok(())
     }
 
-    /// Clear a single tile at the specified point from the tilemap.
+    /// Moves the origin of the tilemap to a chunk of the world, see
+    /// [`shift_origin`] and the [`world`] module.
+    ///
+    /// [`shift_origin`]: Tilemap::shift_origin
+    /// [`world`]: crate::world
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::{prelude::*, world::WorldPoint};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
-    ///
-    /// assert!(tilemap.insert_chunk((0, 0)).is_ok());
-    ///
-    /// let point = (3, 1);
-    /// let sprite_index = 1;
-    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
     ///
-    /// // Set a single tile and unwrap the result
-    /// assert!(tilemap.insert_tile(tile).is_ok());
+    /// tilemap.shift_origin_to((50_000_000, 0)).unwrap();
+    /// tilemap.shift_origin_to((100_000_000, 0)).unwrap();
     ///
-    /// // Later on...
-    /// assert!(tilemap.clear_tile(point, 0).is_ok());
-    /// assert_eq!(tilemap.get_tile((3, 1), 0), None);
+    /// assert_eq!(tilemap.origin(), WorldPoint::new(100_000_000, 0));
+    /// assert!(tilemap.shift_origin_to((0, 0)).is_err());
     /// ```
     ///
     /// # Errors
     ///
-    /// An error can occure if the point is outside of the tilemap. This can
-    /// only happen if the tilemap has dimensions.
-    pub fn clear_tile<P>(&mut self, point: P, z_order: usize) -> TilemapResult<()>
-    where
-        P: Into<Point2>,
-    {
-        let points = vec![(point, z_order)];
-        self.clear_tiles(points)
+    /// Returns an error if the chunk is too far from the current origin to
+    /// move to at once, or for the same reasons as [`shift_origin`].
+    pub fn shift_origin_to<W: Into<WorldPoint>>(&mut self, chunk: W) -> TilemapResult<()> {
+        let chunk: WorldPoint = chunk.into();
+        let delta = match (
+            i32::try_from(chunk.x - self.origin.x),
+            i32::try_from(chunk.y - self.origin.y),
+        ) {
+            (Ok(x), Ok(y)) => Point2::new(x, y),
+            _ => return Err(ErrorKind::DistantWorldPoint(chunk).into()),
+        };
+        self.shift_origin(delta)
     }
 
-    /// Gets a raw tile from a given point and z order.
+    /// Returns the world point of the chunk at `(0, 0)`, which moves along
+    /// with the origin.
+    pub fn origin(&self) -> WorldPoint {
+        self.origin
+    }
+
+    /// Returns the world point of a tile.
     ///
-    /// This is different thant he usual [`Tile`] struct in that it only
-    /// contains the sprite index and the tint.
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, world::WorldPoint};
     ///
-    /// [`Tile`]: crate::tile::Tile
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// tilemap.shift_origin((3, -1)).unwrap();
+    ///
+    /// assert_eq!(tilemap.point_to_world((1, 1)), WorldPoint::new(13, -3));
+    /// ```
+    pub fn point_to_world<P: Into<Point2>>(&self, point: P) -> WorldPoint {
+        let point: Point2 = point.into();
+        WorldPoint::new(
+            i64::from(point.x) + self.origin.x * i64::from(self.chunk_dimensions.width),
+            i64::from(point.y) + self.origin.y * i64::from(self.chunk_dimensions.height),
+        )
+    }
+
+    /// Returns the point of a tile of the world.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_render::prelude::*;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::{prelude::*, world::WorldPoint};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
     ///
-    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// tilemap.shift_origin((3, -1)).unwrap();
     ///
-    /// let point = (9, 3);
-    /// let sprite_index = 3;
-    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    /// assert_eq!(tilemap.world_to_point((13, -3)).unwrap(), (1, 1).into());
+    /// assert!(tilemap.world_to_point((1 << 40, 0)).is_err());
+    /// ```
     ///
-    /// assert!(tilemap.insert_tile(tile).is_ok());
-    /// assert_eq!(tilemap.get_tile((9, 3), 0), Some(&RawTile { index: 3, color: Color::WHITE }));
-    /// assert_eq!(tilemap.get_tile((10, 4), 0), None);
+    /// # Errors
+    ///
+    /// Returns an error if the world point is too far from the origin to be a
+    /// point of the tilemap.
+    pub fn world_to_point<W: Into<WorldPoint>>(&self, point: W) -> TilemapResult<Point2> {
+        let point: WorldPoint = point.into();
+        let x = point.x - self.origin.x * i64::from(self.chunk_dimensions.width);
+        let y = point.y - self.origin.y * i64::from(self.chunk_dimensions.height);
+        match (i32::try_from(x), i32::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Point2::new(x, y)),
+            _ => Err(ErrorKind::DistantWorldPoint(point).into()),
+        }
+    }
+
+    /// Returns the world point of the chunk which has a tile of the world.
+    ///
+    /// # Examples
     /// ```
-    pub fn get_tile<P>(&mut self, point: P, z_order: usize) -> Option<&RawTile>
-    where
-        P: Into<Point2>,
-    {
-        let point: Point2 = point.into();
-        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
-        let tile_point = self.point_to_tile_point(point);
-        let chunk = self.chunks.get(&chunk_point)?;
-        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
-        chunk.get_tile(z_order, index)
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::{prelude::*, world::WorldPoint};
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    ///
+    /// let tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// let chunk = tilemap.world_point_to_chunk_point((40_000_000_001, -3));
+    /// assert_eq!(chunk, WorldPoint::new(10_000_000_000, -1));
+    /// ```
+    pub fn world_point_to_chunk_point<W: Into<WorldPoint>>(&self, point: W) -> WorldPoint {
+        let point: WorldPoint = point.into();
+        let width = i64::from(self.chunk_dimensions.width);
+        let height = i64::from(self.chunk_dimensions.height);
+        WorldPoint::new(
+            (point.x + width / 2).div_euclid(width),
+            (point.y + height / 2).div_euclid(height),
+        )
     }
 
-    /// Gets a mutable raw tile from a given point and z order.
+    /// Sets the chunk which is translated to where the chunk at `(0, 0)` is,
+    /// moving every chunk along with it without changing their points.
     ///
-    /// This is different thant he usual [`Tile`] struct in that it only
-    /// contains the sprite index and the tint.
+    /// Chunks are drawn at `f32` translations which lose precision far away
+    /// from `(0, 0)`, so the vertices of chunks which are millions of tiles
+    /// away jitter. Rebasing the translation origin to the chunk of the
+    /// camera every so often keeps the chunks near it at small translations.
+    /// The spawned chunks move to their new translations in the next run of
+    /// the tilemap stage, like with [`shift_origin`], so the camera and the
+    /// rest of the world should be moved by the same amount at the same time.
+    /// Unlike [`shift_origin`], the points of the tiles stay the same.
     ///
-    /// [`Tile`]: crate::tile::Tile
+    /// [`shift_origin`]: Tilemap::shift_origin
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_render::prelude::*;
+    /// use bevy_math::Vec2;
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, chunk::RawTile};
+    /// use bevy_tilemap::prelude::*;
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
-    ///
-    /// tilemap.insert_chunk((0, 0)).unwrap();
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
     ///
-    /// let point = (2, 5);
-    /// let sprite_index = 2;
-    /// let tile = Tile { point, sprite_index, ..Default::default() };
+    /// let far = (4_000_000, -2_000_000);
+    /// tilemap.set_translation_origin(tilemap.point_to_chunk_point(far)).unwrap();
     ///
-    /// assert!(tilemap.insert_tile(tile).is_ok());
-    /// assert_eq!(tilemap.get_tile_mut((2, 5), 0), Some(&mut RawTile { index: 2, color: Color::WHITE }));
-    /// assert_eq!(tilemap.get_tile_mut((1, 4), 0), None);
+    /// assert_eq!(tilemap.translation_origin(), (1_000_000, -500_000).into());
+    /// assert_eq!(tilemap.point_to_translation(far), Vec2::new(16.0, 16.0));
+    /// assert_eq!(tilemap.translation_to_point(Vec2::new(16.0, 16.0)), far.into());
     /// ```
-    pub fn get_tile_mut<P>(&mut self, point: P, z_order: usize) -> Option<&mut RawTile>
-    where
-        P: Into<Point2>,
-    {
-        let point: Point2 = point.into();
-        let chunk_point: Point2 = self.point_to_chunk_point(point).into();
-        let tile_point = self.point_to_tile_point(point);
-        let chunk = self.chunks.get_mut(&chunk_point)?;
-        let index = self.chunk_dimensions.encode_point_unchecked(tile_point);
-        let mut layers = HashMap::default();
-        if let Some(entity) = chunk.get_entity(z_order) {
-            layers.insert(z_order, entity);
-            self.chunk_events
-                .send(TilemapChunkEvent::Modified { layers });
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tiles of the chunk are beyond what the points
+    /// of a tilemap reach.
+    pub fn set_translation_origin<P: Into<Point2>>(&mut self, chunk: P) -> TilemapResult<()> {
+        let chunk: Point2 = chunk.into();
+        let in_reach = chunk
+            .x
+            .checked_mul(self.chunk_dimensions.width as i32)
+            .and(chunk.y.checked_mul(self.chunk_dimensions.height as i32))
+            .is_some();
+        if !in_reach {
+            let chunk = WorldPoint::new(
+                self.origin.x + i64::from(chunk.x),
+                self.origin.y + i64::from(chunk.y),
+            );
+            return Err(ErrorKind::DistantWorldPoint(chunk).into());
         }
-        chunk.get_tile_mut(z_order, index)
+        let delta = chunk - self.translation_origin;
+        if delta == Point2::new(0, 0) {
+            return Ok(());
+        }
+        self.translation_origin = chunk;
+        self.chunk_events.send(TilemapChunkEvent::Rehomed { delta });
+
+        Ok(())
     }
 
-    /// Returns the center tile, if the tilemap has dimensions.
-    ///
-    /// Returns `None` if the tilemap has no constrainted dimensions.
+    /// Returns the chunk which is translated to where the chunk at `(0, 0)`
+    /// is, which is `(0, 0)` unless it was rebased.
+    pub fn translation_origin(&self) -> Point2 {
+        self.translation_origin
+    }
+
+    /// Returns the translation of a chunk, relative to the tilemap's
+    /// transform and its translation origin.
+    pub(crate) fn chunk_translation(&self, topology: GridTopology, chunk_point: Point2) -> Vec2 {
+        geometry::chunk_translation(
+            topology,
+            chunk_point - self.translation_origin,
+            self.chunk_dimensions,
+            self.tile_dimensions,
+        )
+    }
+
+    /// Returns the point of the first tile of the translation origin, less
+    /// the point of the first tile of the chunk at `(0, 0)`.
+    fn translation_offset(&self) -> Point2 {
+        Point2::new(
+            self.translation_origin.x * self.chunk_dimensions.width as i32,
+            self.translation_origin.y * self.chunk_dimensions.height as i32,
+        )
+    }
+
+    /// Returns the thresholds past which load is shed, if there is a policy.
+    pub fn shed_policy(&self) -> Option<&ShedPolicy> {
+        self.shed_policy.as_ref()
+    }
+
+    /// Sets the thresholds past which load is shed, or removes the policy with
+    /// `None`, which brings back the colliders and meshes that were shed.
+    /// Chunks which were unloaded stay on disk until they are loaded.
     ///
     /// # Examples
-    ///
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{chunk::ShedPolicy, prelude::*};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle.clone_weak())
-    ///     .dimensions(32, 32)
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
     ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
     ///
-    /// let center = tilemap.center_tile_coord();
+    /// tilemap.set_shed_policy(Some(ShedPolicy::new().max_layer_entities(100)));
     ///
-    /// // 32 * 32 / 2 = 512
-    /// assert_eq!(center, Some((512, 512)));
+    /// assert_eq!(tilemap.shed_policy().unwrap().layer_entities(), Some(100));
+    /// ```
+    pub fn set_shed_policy(&mut self, policy: Option<ShedPolicy>) {
+        self.shed_policy = policy;
+        if self.shed_policy.is_none() {
+            self.shed_colliders.clear();
+            let shed_meshes: Vec<Point2> = self.shed_meshes.drain().collect();
+            for point in shed_meshes.into_iter() {
+                if let Err(e) = self.spawn_chunk(point) {
+                    warn!("{}", e);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the chunks and tiles are updated in the order of
+    /// their points.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Sets if the chunks and tiles are updated in the order of their points,
+    /// see [`TilemapBuilder::deterministic`].
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_asset::{prelude::*, HandleId};
+    /// use bevy_sprite::prelude::*;
+    /// use bevy_tilemap::prelude::*;
+    ///
+    /// // In production use a strong handle from an actual source.
+    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// let center = tilemap.center_tile_coord();
+    /// tilemap.set_deterministic(true);
     ///
-    /// assert_eq!(center, None);
+    /// assert!(tilemap.is_deterministic());
     /// ```
-    pub fn center_tile_coord(&self) -> Option<(i32, i32)> {
-        self.dimensions.map(|dimensions| {
-            (
-                (dimensions.width / 2 * self.chunk_dimensions.width) as i32,
-                (dimensions.height / 2 * self.chunk_dimensions.height) as i32,
-            )
-        })
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
     }
 
-    /// The width of the tilemap in chunks, if it has dimensions.
+    /// Returns a reference to the events of the load that was shed.
     ///
     /// # Examples
     /// ```
+    /// use bevy_app::Events;
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{event::TilemapShedEvent, prelude::*};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
     /// let tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle.clone_weak())
-    ///     .dimensions(32, 64)
+    ///     .texture_atlas(texture_atlas_handle)
+    ///     .chunk_dimensions(4, 4)
     ///     .tile_dimensions(32, 32)
     ///     .finish()
     ///     .unwrap();
     ///
-    /// let width = tilemap.width();
-    ///
-    /// assert_eq!(width, Some(32));
+    /// let events: &Events<TilemapShedEvent> = tilemap.shed_events();
+    /// ```
+    pub fn shed_events(&self) -> &Events<TilemapShedEvent> {
+        &self.shed_events
+    }
+
+    /// Updates the events of the load that was shed.
+    pub(crate) fn shed_events_update(&mut self) {
+        self.shed_events.update();
+    }
+
+    /// Returns how many frames a chunk whose tiles were all removed is kept
+    /// before it is removed, or `None` if empty chunks are kept.
+    pub fn empty_chunk_frames(&self) -> Option<u32> {
+        self.empty_chunk_frames
+    }
+
+    /// Sets how many frames a chunk whose tiles were all removed is kept
+    /// before it is removed, or keeps empty chunks with `None`.
+    ///
+    /// Once a chunk has been empty for the frames, it is despawned and
+    /// removed like with [`remove_chunk`], its copy in the chunk store is
+    /// removed too and a [`ChunkDespawnedEvent`] is sent to the
+    /// [`despawned_events`]. Chunks which get a tile again in the meantime
+    /// are kept. Only chunks which are emptied are removed, so chunks which
+    /// were inserted without tiles are kept until they have had some, and
+    /// static chunks are always kept. Without the auto chunk flag, tiles can
+    /// not be inserted into a chunk which was removed until it is inserted
+    /// again.
     ///
-    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{prelude::*, testing::tilemap_test_harness};
     ///
-    /// let width = tilemap.width();
+    /// let mut harness = tilemap_test_harness();
     ///
-    /// assert_eq!(width, None);
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
+    ///     .finish()
+    ///     .unwrap();
+    /// tilemap.set_empty_chunk_frames(Some(2));
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 1, ..Default::default() }).unwrap();
+    /// tilemap.spawn_chunk((0, 0)).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// harness.tilemap_mut(entity).unwrap().clear_tile((0, 0), 0).unwrap();
+    /// harness.tick();
+    /// assert!(harness.tilemap_mut(entity).unwrap().contains_chunk((0, 0)));
+    ///
+    /// harness.tick();
+    /// assert!(!harness.tilemap_mut(entity).unwrap().contains_chunk((0, 0)));
+    /// let tilemap = harness.tilemap(entity).unwrap();
+    /// let mut reader = tilemap.despawned_events().get_reader();
+    /// assert_eq!(reader.iter(tilemap.despawned_events()).count(), 1);
     /// ```
-    pub fn width(&self) -> Option<u32> {
-        self.dimensions.map(|dimensions| dimensions.width)
+    ///
+    /// [`remove_chunk`]: Tilemap::remove_chunk
+    /// [`ChunkDespawnedEvent`]: crate::event::ChunkDespawnedEvent
+    /// [`despawned_events`]: Tilemap::despawned_events
+    pub fn set_empty_chunk_frames(&mut self, frames: Option<u32>) {
+        self.empty_chunk_frames = frames;
+        if frames.is_none() {
+            self.empty_chunks.clear();
+        }
     }
 
-    /// The height of the tilemap in chunks, if it has dimensions.
+    /// Returns the fill ratios at which sparse layers are stored dense and
+    /// back, if their storage is converted.
+    pub fn storage_policy(&self) -> Option<&StoragePolicy> {
+        self.storage_policy.as_ref()
+    }
+
+    /// Sets the fill ratios at which the sparse layers of each chunk are
+    /// stored as dense layers and back, or keeps their storage with `None`,
+    /// see [`StoragePolicy`].
+    ///
+    /// The layers of the chunks whose tiles changed are checked at the end
+    /// of the frame.
     ///
     /// # Examples
     /// ```
-    /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{chunk::StoragePolicy, prelude::*, testing::tilemap_test_harness};
     ///
-    /// // In production use a strong handle from an actual source.
-    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// let mut harness = tilemap_test_harness();
     ///
-    /// let tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle.clone_weak())
-    ///     .dimensions(32, 64)
+    /// let mut tilemap = TilemapBuilder::new()
+    ///     .texture_atlas(harness.texture_atlas().clone())
+    ///     .chunk_dimensions(4, 4)
     ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
+    /// tilemap.set_storage_policy(Some(StoragePolicy::new(0.75, 0.5)));
+    ///
+    /// // Forest fills the whole chunk.
+    /// let mut forest = Vec::new();
+    /// for x in -2..2 {
+    ///     for y in -2..2 {
+    ///         forest.push(Tile { point: (x, y), sprite_index: 4, z_order: 1, ..Default::default() });
+    ///     }
+    /// }
+    /// tilemap.insert_tiles(forest).unwrap();
+    /// let entity = harness.spawn_tilemap(tilemap);
+    /// harness.tick();
+    ///
+    /// let tilemap = harness.tilemap(entity).unwrap();
+    /// assert_eq!(tilemap.layer_storage((0, 0), 1), Some(LayerKind::Dense));
+    /// assert_eq!(tilemap.get_tile((1, 1), 1).unwrap().index, 4);
+    ///
+    /// // A clearing is cut into most of it.
+    /// let clearing: Vec<(i32, i32)> = (-2..2).flat_map(|x| (-2..1).map(move |y| (x, y))).collect();
+    /// harness.tilemap_mut(entity).unwrap().clear_tiles(clearing.into_iter().map(|point| (point, 1))).unwrap();
+    /// harness.tick();
+    ///
+    /// let tilemap = harness.tilemap(entity).unwrap();
+    /// assert_eq!(tilemap.layer_storage((0, 0), 1), Some(LayerKind::Sparse));
+    /// assert_eq!(tilemap.get_tile((1, 1), 1).unwrap().index, 4);
+    /// assert!(tilemap.get_tile((1, 0), 1).is_none());
+    /// ```
     ///
-    /// let height = tilemap.height();
-    ///
-    /// assert_eq!(height, Some(64));
+    /// [`StoragePolicy`]: crate::chunk::StoragePolicy
+    pub fn set_storage_policy(&mut self, policy: Option<StoragePolicy>) {
+        self.storage_policy = policy;
+    }
+
+    /// Returns the kind of storage of a layer in the chunk at the chunk
+    /// point, which for sparse layers depends on the [`StoragePolicy`] and
+    /// for y sorted layers is sparse, or `None` if the chunk or the layer
+    /// does not exist.
+    ///
+    /// [`StoragePolicy`]: crate::chunk::StoragePolicy
+    pub fn layer_storage<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<LayerKind> {
+        self.chunks.get(&point.into())?.layer_storage(z_order)
+    }
+
+    /// Overrides the kind of a dense or sparse layer in the chunk at the
+    /// chunk point, or goes back to the kind of the layer with `None`.
     ///
-    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// A mostly sparse overworld can have dense layers in the chunks of its
+    /// cities, so each chunk is stored as densely as it is filled. The tiles
+    /// of the chunk are kept if it exists, and it is made with the kind if it
+    /// is inserted later. A layer keeps behaving as it did, only its storage
+    /// changes, and the [`StoragePolicy`] leaves it be.
     ///
-    /// let height = tilemap.height();
+    /// # Errors
     ///
-    /// assert_eq!(height, None);
-    /// ```
-    pub fn height(&self) -> Option<u32> {
-        self.dimensions.map(|dimensions| dimensions.height)
-    }
-
-    /// The width of all the chunks in tiles.
+    /// If the layer does not exist, or either it or the kind is not dense or
+    /// sparse, an error is returned.
     ///
     /// # Examples
     /// ```
@@ -1766,208 +11013,616 @@ impl Tilemap {
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let tilemap = TilemapBuilder::new()
+    /// let mut tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .chunk_dimensions(32, 64)
-    ///     .tile_dimensions(32, 32)
+    ///     .chunk_dimensions(8, 8)
+    ///     .tile_dimensions(16, 16)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
     ///
-    /// let chunk_width: u32 = tilemap.chunk_width();
+    /// // The city is built on the chunk to the east, which is not made yet.
+    /// tilemap.set_chunk_layer_kind((1, 0), 1, Some(LayerKind::Dense)).unwrap();
+    /// tilemap.insert_tile(Tile { point: (8, 0), sprite_index: 7, z_order: 1, ..Default::default() }).unwrap();
+    /// tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 3, z_order: 1, ..Default::default() }).unwrap();
     ///
-    /// assert_eq!(chunk_width, 32);
+    /// assert_eq!(tilemap.layer_storage((1, 0), 1), Some(LayerKind::Dense));
+    /// assert_eq!(tilemap.layer_storage((0, 0), 1), Some(LayerKind::Sparse));
+    /// assert_eq!(tilemap.chunk_layer_kind((1, 0), 1), Some(LayerKind::Dense));
+    ///
+    /// // The city is razed.
+    /// tilemap.set_chunk_layer_kind((1, 0), 1, None).unwrap();
+    /// assert_eq!(tilemap.layer_storage((1, 0), 1), Some(LayerKind::Sparse));
+    /// assert_eq!(tilemap.get_tile((8, 0), 1).unwrap().index, 7);
     /// ```
-    pub fn chunk_width(&self) -> u32 {
-        self.chunk_dimensions.width
+    ///
+    /// [`StoragePolicy`]: crate::chunk::StoragePolicy
+    pub fn set_chunk_layer_kind<P: Into<Point2>>(
+        &mut self,
+        point: P,
+        z_order: usize,
+        kind: Option<LayerKind>,
+    ) -> TilemapResult<()> {
+        let point = point.into();
+        let layer_kind = match self.layers.get(z_order) {
+            Some(Some(layer)) => layer.kind,
+            _ => return Err(ErrorKind::LayerDoesNotExist(z_order).into()),
+        };
+        let overridable = |kind| kind == LayerKind::Dense || kind == LayerKind::Sparse;
+        if !overridable(layer_kind) || kind.filter(|kind| !overridable(*kind)).is_some() {
+            return Err(ErrorKind::LayerKindNotOverridable(z_order).into());
+        }
+        match kind {
+            Some(kind) => self.layer_kind_overrides.insert((point, z_order), kind),
+            None => self.layer_kind_overrides.remove(&(point, z_order)),
+        };
+        let storage = kind.unwrap_or(layer_kind);
+        let area = self.chunk_dimensions.area() as usize;
+        let empty_tile = self.empty_tiles().into_iter().find(|(z, _)| *z == z_order);
+        if let Some(chunk) = self.chunks.get_mut(&point) {
+            if chunk.layer_storage(z_order) != Some(storage) {
+                let chunk = Arc::make_mut(chunk);
+                chunk.set_layer_storage(z_order, storage, area);
+                if let Some((_, empty_tile)) = empty_tile {
+                    chunk.fill_empty_tiles(z_order, empty_tile, area);
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// The height of all the chunks in tiles.
+    /// Returns the kind which overrides that of a layer in the chunk at the
+    /// chunk point, if there is one.
+    pub fn chunk_layer_kind<P: Into<Point2>>(&self, point: P, z_order: usize) -> Option<LayerKind> {
+        self.layer_kind_overrides
+            .get(&(point.into(), z_order))
+            .copied()
+    }
+
+    /// Returns the z orders and empty tiles of the dense layers which have
+    /// one.
+    fn empty_tiles(&self) -> Vec<(usize, RawTile)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .filter_map(|(z_order, layer)| {
+                let layer = layer
+                    .as_ref()
+                    .filter(|layer| layer.kind == LayerKind::Dense)?;
+                Some((z_order, layer.empty_tile?))
+            })
+            .collect()
+    }
+
+    /// Returns the kinds of the layers of a chunk at the chunk point, with
+    /// the kinds which override them.
+    fn chunk_layer_kinds(&self, point: Point2) -> Vec<Option<LayerKind>> {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(z_order, layer)| {
+                let layer = layer.as_ref()?;
+                Some(self.chunk_layer_kind(point, z_order).unwrap_or(layer.kind))
+            })
+            .collect()
+    }
+
+    /// Stores the sparse layers of the modified chunks as dense layers or
+    /// back, if their fill ratios crossed those of the storage policy.
+    pub(crate) fn adapt_layer_storage(&mut self, modified: &[Point2]) {
+        let policy = match self.storage_policy {
+            Some(policy) => policy,
+            None => return,
+        };
+        let area = self.chunk_dimensions.area() as usize;
+        let sparse_layers: Vec<usize> = self
+            .layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| {
+                layer
+                    .as_ref()
+                    .filter(|layer| layer.kind == LayerKind::Sparse)
+                    .is_some()
+            })
+            .map(|(z_order, _)| z_order)
+            .collect();
+        for point in modified.iter() {
+            for z_order in sparse_layers.iter() {
+                if self.layer_kind_overrides.contains_key(&(*point, *z_order)) {
+                    continue;
+                }
+                let storage = match self.chunks.get(point) {
+                    Some(chunk) => chunk.storage_change(*z_order, area, &policy),
+                    None => continue,
+                };
+                if let (Some(storage), Some(chunk)) = (storage, self.chunks.get_mut(point)) {
+                    Arc::make_mut(chunk).set_layer_storage(*z_order, storage, area);
+                }
+            }
+        }
+    }
+
+    /// Returns a reference to the events of the empty chunks which were
+    /// removed, see [`set_empty_chunk_frames`].
+    ///
+    /// [`set_empty_chunk_frames`]: Tilemap::set_empty_chunk_frames
+    pub fn despawned_events(&self) -> &Events<ChunkDespawnedEvent> {
+        &self.despawned_events
+    }
+
+    /// Updates the events of the empty chunks which were removed.
+    pub(crate) fn despawned_events_update(&mut self) {
+        self.despawned_events.update();
+    }
+
+    /// Counts another frame for the chunks whose tiles were all removed,
+    /// starting with the modified chunks which are empty, and removes those
+    /// which have been empty for long enough.
+    pub(crate) fn collect_empty_chunks(&mut self, modified: &[Point2]) {
+        let frames = match self.empty_chunk_frames {
+            Some(frames) => frames,
+            None => return,
+        };
+        for point in modified.iter() {
+            let empty = match self.chunks.get(point) {
+                Some(chunk) => chunk.is_empty() && !self.static_chunks.contains(point),
+                None => false,
+            };
+            if empty {
+                self.empty_chunks.entry(*point).or_insert(0);
+            } else {
+                self.empty_chunks.remove(point);
+            }
+        }
+        let mut collected = Vec::new();
+        for (point, age) in self.empty_chunks.iter_mut() {
+            *age += 1;
+            if *age >= frames {
+                collected.push(*point);
+            }
+        }
+        collected.sort_unstable_by_key(|point| (point.y, point.x));
+        for point in collected {
+            self.empty_chunks.remove(&point);
+            if !self.chunks.contains_key(&point) {
+                continue;
+            }
+            if let Err(e) = self.remove_chunk(point) {
+                warn!("{}", e);
+                continue;
+            }
+            #[cfg(feature = "serialize")]
+            if let Some(store) = self
+                .chunk_store
+                .as_ref()
+                .filter(|_| !self.is_store_point_taken(point))
+            {
+                if let Err(e) = store.remove(point) {
+                    warn!("{}", e);
+                }
+            }
+            self.despawned_events.send(ChunkDespawnedEvent { point });
+        }
+    }
+
+    /// Returns the chunks whose colliders were shed, with how many they had.
+    pub(crate) fn shed_colliders(&self) -> &HashMap<Point2, usize> {
+        &self.shed_colliders
+    }
+
+    /// Sheds the colliders of a spawned chunk, which the collision system
+    /// then despawns.
+    pub(crate) fn shed_chunk_colliders(&mut self, point: Point2, colliders: usize) {
+        self.shed_colliders.insert(point, colliders);
+        if colliders > 0 {
+            self.shed_events.send(TilemapShedEvent::Colliders {
+                chunk_point: point,
+                colliders,
+            });
+        }
+    }
+
+    /// Brings back the colliders of a chunk, which the collision system then
+    /// spawns again.
+    pub(crate) fn readmit_chunk_colliders(&mut self, point: Point2) {
+        self.shed_colliders.remove(&point);
+    }
+
+    /// Forgets the colliders that were shed of the chunks which are no longer
+    /// spawned.
+    pub(crate) fn retain_shed_colliders(&mut self) {
+        let spawned = &self.spawned;
+        self.shed_colliders
+            .retain(|point, _| spawned.contains(&(point.x, point.y)));
+    }
+
+    /// Returns the chunks which were despawned to shed their meshes.
+    pub(crate) fn shed_meshes(&self) -> &HashSet<Point2> {
+        &self.shed_meshes
+    }
+
+    /// Despawns a spawned chunk and drops its meshes, until it is readmitted.
+    pub(crate) fn shed_chunk_meshes(&mut self, point: Point2) {
+        if let Err(e) = self.despawn_chunk(point) {
+            warn!("{}", e);
+            return;
+        }
+        #[cfg(feature = "render")]
+        if let Some(chunk) = self.chunks.get_mut(&point).map(Arc::make_mut) {
+            chunk.clear_meshes();
+        }
+        self.shed_meshes.insert(point);
+        self.shed_events
+            .send(TilemapShedEvent::Meshes { chunk_point: point });
+    }
+
+    /// Spawns a chunk whose meshes were shed again.
+    pub(crate) fn readmit_chunk_meshes(&mut self, point: Point2) {
+        if self.shed_meshes.remove(&point) {
+            if let Err(e) = self.spawn_chunk(point) {
+                warn!("{}", e);
+            }
+        }
+    }
+
+    /// Sets the store which chunks are written to when they are dropped from
+    /// memory, see the [`store`] module. Chunks which leave the radius of the
+    /// auto spawn are then written to it, as are the chunks unloaded to shed
+    /// load.
+    ///
+    /// [`store`]: crate::store
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, store::FileChunkStore};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let tilemap = TilemapBuilder::new()
-    ///     .texture_atlas(texture_atlas_handle)
-    ///     .chunk_dimensions(32, 64)
-    ///     .tile_dimensions(32, 32)
-    ///     .finish()
-    ///     .unwrap();
-    ///
-    /// let chunk_height: u32 = tilemap.chunk_height();
+    /// let mut tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
     ///
-    /// assert_eq!(chunk_height, 64);
+    /// assert!(tilemap.chunk_store().is_none());
+    /// tilemap.set_chunk_store(FileChunkStore::new("saves/chunks"));
+    /// assert!(tilemap.chunk_store().is_some());
     /// ```
-    pub fn chunk_height(&self) -> u32 {
-        self.chunk_dimensions.height
+    #[cfg(feature = "serialize")]
+    pub fn set_chunk_store<S: ChunkStore>(&mut self, store: S) {
+        self.chunk_store = Some(Arc::new(store));
     }
 
-    /// The width of a tile in pixels.
+    /// Returns the store which chunks are written to, if there is one.
+    #[cfg(feature = "serialize")]
+    pub fn chunk_store(&self) -> Option<&dyn ChunkStore> {
+        self.chunk_store.as_deref()
+    }
+
+    /// Sets how chunks are encoded when they are written to the chunk store.
+    /// Chunks which were already written are still read back.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{
+    ///     prelude::*,
+    ///     store::{ChunkEncoding, FileChunkStore},
+    /// };
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let tilemap = TilemapBuilder::new()
+    /// let mut tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .tile_dimensions(32, 64)
-    ///     .tile_dimensions(32, 32)
+    ///     .chunk_dimensions(32, 32)
+    ///     .tile_dimensions(16, 16)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
     ///
-    /// let tile_width: u32 = tilemap.tile_width();
+    /// let directory = std::env::temp_dir().join("set_chunk_encoding");
+    /// tilemap.set_chunk_store(FileChunkStore::new(&directory));
+    /// tilemap.set_chunk_encoding(ChunkEncoding::Compressed(3));
     ///
-    /// assert_eq!(tile_width, 32);
+    /// let tiles = (0..16).map(|x| Tile { point: (x, 0), sprite_index: 4, ..Default::default() });
+    /// tilemap.insert_tiles(tiles).unwrap();
+    /// tilemap.unload_chunk((0, 0)).unwrap();
+    /// assert!(std::fs::read(directory.join("chunk_0_0.chunk")).unwrap().len() < 1_000);
+    ///
+    /// tilemap.load_chunk((0, 0)).unwrap();
+    /// assert_eq!(tilemap.get_tile((15, 0), 0).unwrap().index, 4);
     /// ```
-    pub fn tile_width(&self) -> u32 {
-        self.tile_dimensions.width
+    #[cfg(feature = "compress")]
+    pub fn set_chunk_encoding(&mut self, encoding: ChunkEncoding) {
+        self.chunk_encoding = encoding;
     }
 
-    /// The height of a tile in pixels.
+    /// Returns how chunks are encoded when they are written to the chunk
+    /// store.
+    #[cfg(feature = "compress")]
+    pub fn chunk_encoding(&self) -> ChunkEncoding {
+        self.chunk_encoding
+    }
+
+    /// Writes a chunk to the chunk store and drops it from memory, until it is
+    /// loaded again. The chunk should be despawned first.
+    ///
+    /// # Errors
+    ///
+    /// If the tilemap has no chunk store, the chunk is not in memory or it
+    /// can not be written, an error is returned.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, store::FileChunkStore};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let tilemap = TilemapBuilder::new()
+    /// let mut tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .tile_dimensions(32, 64)
+    ///     .chunk_dimensions(4, 4)
+    ///     .tile_dimensions(32, 32)
+    ///     .add_layer(TilemapLayer { kind: LayerKind::Sparse, ..Default::default() }, 1)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
     ///
-    /// let tile_height: u32 = tilemap.tile_height();
+    /// tilemap.set_chunk_store(FileChunkStore::new(std::env::temp_dir().join("unload_chunk")));
+    /// tilemap.insert_tile(Tile { point: (1, 1), sprite_index: 3, ..Default::default() }).unwrap();
     ///
-    /// assert_eq!(tile_height, 64);
+    /// tilemap.unload_chunk((0, 0)).unwrap();
+    /// assert!(tilemap.is_chunk_unloaded((0, 0)));
+    /// assert!(tilemap.contains_chunk((0, 0)));
+    /// assert_eq!(tilemap.get_tile((1, 1), 0), None);
+    ///
+    /// tilemap.load_chunk((0, 0)).unwrap();
+    /// assert_eq!(tilemap.get_tile((1, 1), 0).unwrap().index, 3);
+    ///
+    /// // Layers which are swapped while the chunk is unloaded are swapped in
+    /// // it once it is loaded.
+    /// tilemap.unload_chunk((0, 0)).unwrap();
+    /// tilemap.swap_layers(0, 1).unwrap();
+    /// assert!(tilemap.is_chunk_unloaded((0, 0)));
+    ///
+    /// tilemap.load_chunk((0, 0)).unwrap();
+    /// assert_eq!(tilemap.get_tile((1, 1), 1).unwrap().index, 3);
+    /// assert_eq!(tilemap.get_tile((1, 1), 0), None);
     /// ```
-    pub fn tile_height(&self) -> u32 {
-        self.tile_dimensions.height
+    #[cfg(feature = "serialize")]
+    pub fn unload_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if !self.chunks.contains_key(&point) {
+            return Err(ErrorKind::MissingChunk.into());
+        }
+        // A chunk which was written at the point before the origin moved is
+        // read back before it is overwritten.
+        let taken_by = self
+            .store_points
+            .iter()
+            .find(|(_, store_point)| **store_point == point)
+            .map(|(chunk_point, _)| *chunk_point);
+        if let Some(chunk_point) = taken_by {
+            self.load_chunk(chunk_point)?;
+        }
+        let store = match &self.chunk_store {
+            Some(store) => store,
+            None => {
+                return Err(
+                    ErrorKind::ChunkStorage("the tilemap has no chunk store".to_string()).into(),
+                )
+            }
+        };
+        let chunk = match self.chunks.get(&point) {
+            Some(chunk) => chunk,
+            None => return Err(ErrorKind::MissingChunk.into()),
+        };
+        let bytes = codec::encode(chunk, self.chunk_encoding)?;
+        store.save(point, &bytes)?;
+        self.resolve_changed_tiles(Some(point));
+        self.chunks.remove(&point);
+        self.unloaded_chunks.insert(point);
+        Ok(())
     }
 
-    /// Gets a reference to a chunk.
-    pub(crate) fn get_chunk(&self, point: &Point2) -> Option<&Chunk> {
-        self.chunks.get(point)
+    /// Unloads a chunk which is not spawned to shed load.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn shed_chunk(&mut self, point: Point2) -> TilemapResult<()> {
+        self.unload_chunk(point)?;
+        self.shed_events
+            .send(TilemapShedEvent::Unloaded { chunk_point: point });
+        Ok(())
     }
 
-    /// The topology of the tilemap grid.
+    /// Reads a chunk which was written to the chunk store back into memory.
+    /// It is loaded when it is inserted, spawned or tiles are inserted into
+    /// it, but needs to be loaded before its tiles are read.
     ///
-    /// Currently there are 7 topologies which are set with [`GridTopology`]. By
-    /// default this is square as it is the most common topology.
+    /// Does nothing if the chunk was not unloaded.
     ///
-    /// Typically, for most situations squares are used for local maps and hex
-    /// is used for war games or world maps. It is easier to define structures
-    /// with walls and floors with square but not impossible with hex.
+    /// # Errors
     ///
-    /// [`GridTopology`]: crate::render::GridTopology
+    /// If the chunk can not be read from the store, an error is returned.
     ///
     /// # Examples
     /// ```
     /// use bevy_asset::{prelude::*, HandleId};
     /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::prelude::*;
+    /// use bevy_tilemap::{prelude::*, store::FileChunkStore};
     ///
     /// // In production use a strong handle from an actual source.
     /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
     ///
-    /// let tilemap = TilemapBuilder::new()
+    /// let mut tilemap = TilemapBuilder::new()
     ///     .texture_atlas(texture_atlas_handle)
-    ///     .topology(GridTopology::HexX)
+    ///     .chunk_dimensions(4, 4)
     ///     .tile_dimensions(32, 32)
+    ///     .auto_chunk()
     ///     .finish()
     ///     .unwrap();
     ///
-    /// assert_eq!(tilemap.topology(), GridTopology::HexX);
-    /// ```
-    pub fn topology(&self) -> GridTopology {
-        self.topology
-    }
-
-    /// Returns a reference to the tilemap chunk events.
+    /// tilemap.insert_tile(Tile { point: (1, 1), ..Default::default() }).unwrap();
     ///
-    /// This is handy if it is needed to know when new chunks are created which
-    /// can then be used to trigger events with other systems. For example,
-    /// if you have a system that adds tiles procedurally to the chunks, upon
-    /// a chunk event this can be used to trigger the creation of those tiles.
+    /// assert!(!tilemap.is_chunk_unloaded((0, 0)));
+    /// assert!(tilemap.load_chunk((0, 0)).is_ok());
+    /// assert!(tilemap.get_tile((1, 1), 0).is_some());
     ///
-    /// # Examples
-    /// ```
-    /// use bevy_app::prelude::*;
-    /// use bevy_asset::{prelude::*, HandleId};
-    /// use bevy_sprite::prelude::*;
-    /// use bevy_tilemap::{prelude::*, event::TilemapChunkEvent};
+    /// // Chunks which were unloaded before the origin moved are loaded at
+    /// // their new points.
+    /// tilemap.set_chunk_store(FileChunkStore::new(std::env::temp_dir().join("load_chunk")));
+    /// tilemap.unload_chunk((0, 0)).unwrap();
+    /// tilemap.shift_origin((1, 0)).unwrap();
     ///
-    /// // In production use a strong handle from an actual source.
-    /// let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+    /// assert!(tilemap.is_chunk_unloaded((-1, 0)));
+    /// tilemap.load_chunk((-1, 0)).unwrap();
+    /// assert!(tilemap.get_tile((-3, 1), 0).is_some());
     ///
-    /// let tilemap = Tilemap::new(texture_atlas_handle, 32, 32);
+    /// // Moving the tiles loads the stored chunks to move theirs too.
+    /// tilemap.unload_chunk((-1, 0)).unwrap();
+    /// tilemap.insert_rows(0, 1).unwrap();
     ///
-    /// let events: &Events<TilemapChunkEvent> = tilemap.chunk_events();
+    /// assert!(!tilemap.is_chunk_unloaded((-1, 0)));
+    /// assert!(tilemap.get_tile((-3, 2), 0).is_some());
     /// ```
-    pub fn chunk_events(&self) -> &Events<TilemapChunkEvent> {
-        &self.chunk_events
+    #[cfg(feature = "serialize")]
+    pub fn load_chunk<P: Into<Point2>>(&mut self, point: P) -> TilemapResult<()> {
+        let point: Point2 = point.into();
+        if !self.unloaded_chunks.contains(&point) {
+            return Ok(());
+        }
+        let store_point = self.store_point(point);
+        let bytes = match &self.chunk_store {
+            Some(store) => store.load(store_point)?,
+            None => None,
+        };
+        let bytes = bytes.ok_or_else(|| {
+            ErrorKind::ChunkStorage(::std::format!("the chunk {} is not in the store", point))
+        })?;
+        let mut chunk = codec::decode(&bytes)?;
+        self.unloaded_chunks.remove(&point);
+        self.store_points.remove(&point);
+        // The origin may have moved since the chunk was written.
+        chunk.set_point(point);
+        // Layers may have been swapped since the chunk was written.
+        let z_layers = chunk.z_layers();
+        let permutation = self.layer_permutations.remove(&point).unwrap_or_default();
+        chunk.permute_layers(&permutation);
+        // Layers may have been added above the z orders the chunk was written
+        // with.
+        let written =
+            |z_order: usize| permutation.get(z_order).copied().unwrap_or(z_order) < z_layers;
+        if chunk.z_layers() < self.layers.len() || !permutation.is_empty() {
+            chunk.set_z_layers(chunk.z_layers().max(self.layers.len()));
+            for (z_order, kind) in self.chunk_layer_kinds(point).into_iter().enumerate() {
+                if let Some(kind) = kind.filter(|_| !written(z_order)) {
+                    chunk.add_layer(&kind, z_order, self.chunk_dimensions);
+                }
+            }
+            let area = self.chunk_dimensions.area() as usize;
+            for (z_order, empty_tile) in self.empty_tiles() {
+                if !written(z_order) {
+                    chunk.fill_empty_tiles(z_order, empty_tile, area);
+                }
+            }
+        }
+        self.chunks.insert(point, Arc::new(chunk));
+        self.apply_tile_rules_at_edges(point)
     }
 
-    /// Updates the chunk events. This should only be done once per frame.
-    pub(crate) fn chunk_events_update(&mut self) {
-        self.chunk_events.update()
+    /// Returns the point in the chunk store of an unloaded chunk, which is
+    /// where it was written before the origin moved.
+    #[cfg(feature = "serialize")]
+    fn store_point(&self, point: Point2) -> Point2 {
+        self.store_points.get(&point).copied().unwrap_or(point)
     }
 
-    /// Returns a reference to the tilemap collision events.
-    ///
-    /// This is handy if you need to know which collisions were spawned which
-    /// can then be used to trigger other systems. It should be used in
-    /// conjunction with [`chunk_events_update`] as collisions will spawn
-    /// on a chunk spawn and be despawned with a chunk despawn. Those will not
-    /// trigger events.
-    ///
-    /// [`chunk_events_update`]:
-    ///
-    ///
-    #[cfg(feature = "bevy_rapier2d")]
-    pub fn collision_events(&self) -> &Events<TilemapCollisionEvent> {
-        &self.collision_events
+    /// Returns `true` if an unloaded chunk at another point was written at
+    /// the point in the chunk store.
+    #[cfg(feature = "serialize")]
+    fn is_store_point_taken(&self, point: Point2) -> bool {
+        self.store_points
+            .values()
+            .any(|store_point| *store_point == point)
     }
 
-    /// Updates the collision events. This should only be done once per frame.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub(crate) fn collision_events_update(&mut self) {
-        self.collision_events.update()
+    /// Returns how many chunks are in memory.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn chunk_count(&self) -> usize {
+        self.chunks.len()
     }
 
-    /// Returns a copy of the physics scale.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub fn physics_scale(&self) -> f32 {
-        self.physics_scale
+    /// Returns the chunks in memory which can be unloaded, which are those
+    /// that are not spawned, queued or shed and have no animated tiles.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn unloadable_chunks(&self) -> Vec<Point2> {
+        let animated: HashSet<Point2> = self
+            .animated_tiles
+            .keys()
+            .map(|(point, _)| self.point_to_chunk_point(*point).into())
+            .collect();
+        self.chunks
+            .keys()
+            .filter(|point| {
+                !self.spawned.contains(&(point.x, point.y))
+                    && !self.spawn_queue.contains(point)
+                    && !self.shed_meshes.contains(point)
+                    && !animated.contains(point)
+            })
+            .copied()
+            .collect()
     }
 
-    /// Sets the physics scale.
-    #[cfg(feature = "bevy_rapier2d")]
-    pub fn set_physics_scale(&mut self, scale: f32) {
-        self.physics_scale = scale;
+    /// Returns `true` if the chunk was written to the chunk store and dropped
+    /// from memory.
+    #[cfg(feature = "serialize")]
+    pub fn is_chunk_unloaded<P: Into<Point2>>(&self, point: P) -> bool {
+        self.unloaded_chunks.contains(&point.into())
+    }
+
+    /// Returns the points of the chunks with the activity.
+    pub(crate) fn chunks_with_activity(&self, activity: ChunkActivity) -> HashSet<Point2> {
+        self.chunks
+            .iter()
+            .filter(|(_, chunk)| chunk.activity() == activity)
+            .map(|(point, _)| *point)
+            .collect()
+    }
+
+    /// Returns the points of the static chunks.
+    pub(crate) fn static_chunks(&self) -> &HashSet<Point2> {
+        &self.static_chunks
     }
 
-    /// Returns an option containing a Dimension2.
-    pub(crate) fn auto_spawn(&self) -> Option<Dimension2> {
-        self.auto_spawn
+    /// Returns a mutable reference to the chunks which have colliders, or
+    /// `None` if every spawned chunk does.
+    pub(crate) fn collision_chunks_mut(&mut self) -> &mut Option<HashSet<Point2>> {
+        &mut self.collision_chunks
     }
 
-    /// Sets the auto spawn radius.
+    /// Returns `true` if the auto spawn radius is fit to the window, which it
+    /// is unless a spawn config was set.
+    pub(crate) fn fits_spawn_radius_to_window(&self) -> bool {
+        self.auto_flags.contains(AutoFlags::AUTO_SPAWN)
+    }
+
+    /// Sets the auto spawn radius, keeping the margin between the spawn and
+    /// despawn radii.
     pub(crate) fn set_auto_spawn(&mut self, dimension: Dimension2) {
-        self.auto_spawn = Some(dimension);
+        let mut config = self
+            .spawn_config
+            .unwrap_or_else(|| ChunkSpawnConfig::new(dimension, dimension));
+        let margin = |spawn: u32, despawn: u32| despawn.saturating_sub(spawn);
+        config.despawn_radius = Dimension2::new(
+            dimension.width + margin(config.spawn_radius.width, config.despawn_radius.width),
+            dimension.height + margin(config.spawn_radius.height, config.despawn_radius.height),
+        );
+        config.spawn_radius = dimension;
+        self.spawn_config = Some(config);
     }
 
     /// Returns a copy of the chunk's dimensions.
@@ -1995,9 +11650,28 @@ impl Tilemap {
         self.layers.clone()
     }
 
-    /// Returns a mutable reference to the inner chunks.
-    pub(crate) fn chunks_mut(&mut self) -> &mut HashMap<Point2, Chunk> {
-        &mut self.chunks
+    /// Returns a mutable reference to a chunk, which is copied first if a
+    /// snapshot shares it.
+    pub(crate) fn chunk_mut(&mut self, point: &Point2) -> Option<&mut Chunk> {
+        self.chunks.get_mut(point).map(Arc::make_mut)
+    }
+
+    /// Returns a reference to the sprite rects of the sprite indexes.
+    #[cfg(feature = "render")]
+    pub(crate) fn sprite_rects(&self) -> &HashMap<usize, SpriteRect> {
+        &self.sprite_rects
+    }
+
+    /// Returns the walls of the sprite indexes which have one.
+    #[cfg(feature = "render")]
+    pub(crate) fn wall_sprites(&self) -> &HashMap<usize, WallSprite> {
+        &self.wall_sprites
+    }
+
+    /// Takes the points of all chunks which had tiles modified since this was
+    /// last called.
+    pub(crate) fn take_modified_chunks(&mut self) -> Vec<Point2> {
+        self.modified_chunks.drain().collect()
     }
 }
 
@@ -0,0 +1,239 @@
+//! Exporting tilemaps to the TMX format of the Tiled map editor.
+//!
+//! [`Tilemap::to_tmx`] writes the tiles of every layer of a tilemap as a TMX
+//! map which refers to an external tileset, so that maps which were made or
+//! changed at runtime can be opened in Tiled and touched up there. Each sprite
+//! index is written as its global tile ID, which is the first global ID of
+//! the tileset plus the index, with the flip flags of a [`TmxExport::flip`]
+//! function.
+//!
+//! Square and iso diamond tilemaps are exported as orthogonal and isometric
+//! maps. Tiled counts rows from the top, so the rows are written from the
+//! highest point down. The colors of tiles can not be exported.
+//!
+//! [`Tilemap::to_tmx`]: crate::tilemap::Tilemap::to_tmx
+//!
+//! # Exporting a map
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{prelude::*, tmx::TmxExport};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(2, 2)
+//!     .tile_dimensions(16, 16)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // The chunk covers the points from -1 to 0.
+//! tilemap.insert_tile(Tile { point: (-1, 0), sprite_index: 4, ..Default::default() }).unwrap();
+//!
+//! let tmx = tilemap.to_tmx(&TmxExport::new("terrain.tsx")).unwrap();
+//!
+//! assert!(tmx.contains(r#"<tileset firstgid="1" source="terrain.tsx"/>"#));
+//! assert!(tmx.contains("5,0,\n0,0\n"));
+//! ```
+
+use crate::{
+    chunk::{render::GridTopology, RawTile},
+    lib::*,
+    tilemap::{ErrorKind, Tilemap, TilemapResult},
+};
+
+bitflags! {
+    /// The flip flags of a global tile ID in Tiled.
+    pub struct TmxFlip: u32 {
+        /// The tile is not flipped.
+        const NONE = 0;
+        /// The tile is flipped horizontally.
+        const HORIZONTAL = 0x8000_0000;
+        /// The tile is flipped vertically.
+        const VERTICAL = 0x4000_0000;
+        /// The tile is flipped along its diagonal, which with the other
+        /// flags rotates it.
+        const DIAGONAL = 0x2000_0000;
+    }
+}
+
+/// The options of a TMX export.
+#[derive(Clone, Debug)]
+pub struct TmxExport {
+    /// The path of the external tileset, relative to the map.
+    tileset_source: String,
+    /// The global tile ID of the first sprite of the tileset.
+    first_gid: u32,
+    /// The points of two opposite corners of the exported tiles, or `None`
+    /// for every chunk.
+    region: Option<(Point2, Point2)>,
+    /// Returns the flip flags of a tile.
+    flip: Option<fn(&RawTile) -> TmxFlip>,
+}
+
+impl TmxExport {
+    /// Constructs the options of an export of every chunk which refers to the
+    /// tileset, whose first global tile ID is 1.
+    pub fn new<S: Into<String>>(tileset_source: S) -> TmxExport {
+        TmxExport {
+            tileset_source: tileset_source.into(),
+            first_gid: 1,
+            region: None,
+            flip: None,
+        }
+    }
+
+    /// Sets the global tile ID of the first sprite of the tileset.
+    pub fn first_gid(mut self, first_gid: u32) -> TmxExport {
+        self.first_gid = first_gid;
+        self
+    }
+
+    /// Only exports the tiles from one point to another, both included.
+    pub fn region<P: Into<Point2>>(mut self, from: P, to: P) -> TmxExport {
+        self.region = Some((from.into(), to.into()));
+        self
+    }
+
+    /// Sets the function which returns the flip flags of each tile, such as
+    /// from flips that a custom shader reads from [`RawTile::extra`].
+    ///
+    /// [`RawTile::extra`]: crate::chunk::RawTile::extra
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::{chunk::RawTile, tmx::{TmxExport, TmxFlip}};
+    ///
+    /// fn flip(tile: &RawTile) -> TmxFlip {
+    ///     TmxFlip::from_bits_truncate(u32::from(tile.extra) << 16)
+    /// }
+    ///
+    /// let export = TmxExport::new("tiles.tsx").flip(flip);
+    /// ```
+    pub fn flip(mut self, flip: fn(&RawTile) -> TmxFlip) -> TmxExport {
+        self.flip = Some(flip);
+        self
+    }
+}
+
+/// Writes the tiles of the layers of the tilemap as a TMX map, see
+/// [`Tilemap::to_tmx`].
+///
+/// [`Tilemap::to_tmx`]: crate::tilemap::Tilemap::to_tmx
+pub(crate) fn to_tmx(tilemap: &Tilemap, export: &TmxExport) -> TilemapResult<String> {
+    let orientation = match tilemap.topology() {
+        GridTopology::Square => "orthogonal",
+        GridTopology::IsoDiamond => "isometric",
+        topology => return Err(ErrorKind::UnsupportedTopology(topology).into()),
+    };
+    let (min, max) = match export.region {
+        Some((from, to)) => (
+            Point2::new(from.x.min(to.x), from.y.min(to.y)),
+            Point2::new(from.x.max(to.x), from.y.max(to.y)),
+        ),
+        None => chunk_bounds(tilemap).ok_or(ErrorKind::MissingChunk)?,
+    };
+    let x_count = (max.x - min.x + 1) as u32;
+    let y_count = (max.y - min.y + 1) as u32;
+    // Iso rows run along the x axis, from the top corner down.
+    let (width, height) = match tilemap.topology() {
+        GridTopology::IsoDiamond => (y_count, x_count),
+        _ => (x_count, y_count),
+    };
+    let point_at = |column: u32, row: u32| -> Point2 {
+        match tilemap.topology() {
+            GridTopology::IsoDiamond => Point2::new(max.x - row as i32, max.y - column as i32),
+            _ => Point2::new(min.x + column as i32, max.y - row as i32),
+        }
+    };
+
+    let layers: Vec<usize> = tilemap
+        .layers()
+        .iter()
+        .enumerate()
+        .filter_map(|(z_order, layer)| layer.map(|_| z_order))
+        .collect();
+    let mut tmx = String::new();
+    tmx.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    tmx.push_str(&::std::format!(
+        "<map version=\"1.5\" orientation=\"{}\" renderorder=\"right-down\" width=\"{}\" \
+         height=\"{}\" tilewidth=\"{}\" tileheight=\"{}\" infinite=\"0\" nextlayerid=\"{}\" \
+         nextobjectid=\"1\">\n",
+        orientation,
+        width,
+        height,
+        tilemap.tile_width(),
+        tilemap.tile_height(),
+        layers.len() + 1,
+    ));
+    tmx.push_str(&::std::format!(
+        " <tileset firstgid=\"{}\" source=\"{}\"/>\n",
+        export.first_gid,
+        escape(&export.tileset_source),
+    ));
+    for (id, z_order) in layers.into_iter().enumerate() {
+        tmx.push_str(&::std::format!(
+            " <layer id=\"{}\" name=\"layer {}\" width=\"{}\" height=\"{}\">\n",
+            id + 1,
+            z_order,
+            width,
+            height,
+        ));
+        tmx.push_str("  <data encoding=\"csv\">\n");
+        for row in 0..height {
+            let gids: Vec<String> = (0..width)
+                .map(|column| {
+                    let tile = match tilemap.get_tile(point_at(column, row), z_order) {
+                        Some(tile) => tile,
+                        None => return "0".to_string(),
+                    };
+                    let flip = export.flip.map_or(TmxFlip::NONE, |flip| flip(tile));
+                    let gid = (export.first_gid + tile.index as u32) | flip.bits();
+                    gid.to_string()
+                })
+                .collect();
+            tmx.push_str(&gids.join(","));
+            if row + 1 < height {
+                tmx.push(',');
+            }
+            tmx.push('\n');
+        }
+        tmx.push_str("  </data>\n");
+        tmx.push_str(" </layer>\n");
+    }
+    tmx.push_str("</map>\n");
+
+    Ok(tmx)
+}
+
+/// Returns the lowest and highest points of the chunks of a tilemap, or
+/// `None` if it has none.
+fn chunk_bounds(tilemap: &Tilemap) -> Option<(Point2, Point2)> {
+    let width = tilemap.chunk_width() as i32;
+    let height = tilemap.chunk_height() as i32;
+    let mut bounds: Option<(Point2, Point2)> = None;
+    for point in tilemap.chunk_points() {
+        let low = Point2::new(point.x * width - width / 2, point.y * height - height / 2);
+        let high = Point2::new(low.x + width - 1, low.y + height - 1);
+        bounds = Some(match bounds {
+            Some((min, max)) => (
+                Point2::new(min.x.min(low.x), min.y.min(low.y)),
+                Point2::new(max.x.max(high.x), max.y.max(high.y)),
+            ),
+            None => (low, high),
+        });
+    }
+    bounds
+}
+
+/// Escapes the characters of an XML attribute value.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,167 @@
+//! Weighted random variants of tiles.
+//!
+//! A [`VariantGroup`] is a set of sprite indexes which are all the same kind
+//! of tile, such as a few sprites of grass, each with its own weight. Groups
+//! are added to a tilemap by name with [`Tilemap::add_variant_group`] and any
+//! tile can then be set to one of its variants with
+//! [`Tilemap::set_tile_variant`].
+//!
+//! The variant is picked from a hash of the point of the tile and the variant
+//! seed of the tilemap, set with [`Tilemap::set_variant_seed`], so a map looks
+//! varied but is the same every time it is built from the same seed.
+//!
+//! [`Tilemap::add_variant_group`]: crate::tilemap::Tilemap::add_variant_group
+//! [`Tilemap::set_tile_variant`]: crate::tilemap::Tilemap::set_tile_variant
+//! [`Tilemap::set_variant_seed`]: crate::tilemap::Tilemap::set_variant_seed
+//!
+//! # Varied grass
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{prelude::*, variant::VariantGroup};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(8, 8)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Plain grass is far more common than grass with flowers.
+//! tilemap.add_variant_group("grass", VariantGroup::weighted(vec![(4, 10), (5, 10), (6, 1), (7, 1)]));
+//! tilemap.set_variant_seed(42);
+//!
+//! for y in 0..8 {
+//!     for x in 0..8 {
+//!         tilemap.set_tile_variant((x, y), 0, "grass").unwrap();
+//!     }
+//! }
+//!
+//! let index = tilemap.get_tile((3, 5), 0).unwrap().index;
+//! assert!((4..8).contains(&index));
+//! assert_eq!(tilemap.variant_group("grass").unwrap().pick(42, (3, 5)), Some(index));
+//! ```
+
+use crate::lib::*;
+
+/// A sprite index of a variant group, with how often it is picked.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TileVariant {
+    /// The sprite index of the variant.
+    pub sprite_index: usize,
+    /// How often the variant is picked, relative to the weights of the other
+    /// variants of its group.
+    pub weight: u32,
+}
+
+/// A set of sprite indexes which are all the same kind of tile.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct VariantGroup {
+    /// The variants of the group.
+    pub variants: Vec<TileVariant>,
+}
+
+impl VariantGroup {
+    /// Constructs a new group of sprite indexes which are all picked as often.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::variant::VariantGroup;
+    ///
+    /// let group = VariantGroup::new(vec![4, 5, 6, 7]);
+    ///
+    /// assert_eq!(group.variants.len(), 4);
+    /// assert_eq!(group.total_weight(), 4);
+    /// ```
+    pub fn new(sprite_indexes: Vec<usize>) -> VariantGroup {
+        VariantGroup::weighted(
+            sprite_indexes
+                .into_iter()
+                .map(|sprite_index| (sprite_index, 1))
+                .collect(),
+        )
+    }
+
+    /// Constructs a new group of sprite indexes with their weights.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::variant::VariantGroup;
+    ///
+    /// let group = VariantGroup::weighted(vec![(4, 8), (5, 2)]);
+    ///
+    /// assert_eq!(group.variants[1].weight, 2);
+    /// assert_eq!(group.total_weight(), 10);
+    /// ```
+    pub fn weighted(variants: Vec<(usize, u32)>) -> VariantGroup {
+        VariantGroup {
+            variants: variants
+                .into_iter()
+                .map(|(sprite_index, weight)| TileVariant {
+                    sprite_index,
+                    weight,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the sum of the weights of the variants.
+    pub fn total_weight(&self) -> u64 {
+        self.variants
+            .iter()
+            .map(|variant| u64::from(variant.weight))
+            .sum()
+    }
+
+    /// Returns the sprite index of the variant which is picked for the tile
+    /// at the point with the seed, or `None` if the group has no variants with
+    /// a weight.
+    ///
+    /// The same seed and point always pick the same variant, on every
+    /// platform.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::variant::VariantGroup;
+    ///
+    /// let group = VariantGroup::weighted(vec![(4, 1), (5, 0), (6, 1)]);
+    ///
+    /// let index = group.pick(7, (3, -2)).unwrap();
+    /// assert!(index == 4 || index == 6);
+    /// assert_eq!(group.pick(7, (3, -2)), Some(index));
+    /// assert_eq!(VariantGroup::new(vec![]).pick(7, (3, -2)), None);
+    /// ```
+    pub fn pick<P: Into<Point2>>(&self, seed: u64, point: P) -> Option<usize> {
+        let total_weight = self.total_weight();
+        if total_weight == 0 {
+            return None;
+        }
+        let mut roll = position_hash(seed, point.into()) % total_weight;
+        for variant in self.variants.iter() {
+            let weight = u64::from(variant.weight);
+            if roll < weight {
+                return Some(variant.sprite_index);
+            }
+            roll -= weight;
+        }
+        None
+    }
+}
+
+/// Returns a hash of a point with a seed, which is the same on every
+/// platform unlike the hashers of the standard library.
+pub(crate) fn position_hash(seed: u64, point: Point2) -> u64 {
+    // The finalizer of SplitMix64, which spreads neighbouring points far
+    // apart.
+    let mut hash = seed ^ (u64::from(point.x as u32) | u64::from(point.y as u32) << 32);
+    hash = hash.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
@@ -0,0 +1,90 @@
+//! Walls which are extruded into a cheap 2.5D look.
+//!
+//! Dungeon crawlers seen from above at an angle show the tops of their walls
+//! raised above the floor, with the side of each wall which faces the camera
+//! below its top. A [`WallSprite`] is set for the sprite index of a wall with
+//! [`Tilemap::set_wall_sprite`], and every tile of it on a layer with
+//! [`walls`] is then drawn twice in the chunk mesh: its top face raised by the
+//! height of the wall, and its side face stretched from the floor up to the
+//! top face with the side sprite.
+//!
+//! Seen from above, only the south side of a wall faces the camera, so that
+//! is the side which is drawn. Layers with walls are sorted by their rows like
+//! [`y_sort`]ed layers, so the top of a wall is drawn in front of the walls
+//! and floor behind it and the side of the wall in front of it hides it where
+//! they overlap. Walls are only extruded on square tilemaps.
+//!
+//! [`Tilemap::set_wall_sprite`]: crate::tilemap::Tilemap::set_wall_sprite
+//! [`walls`]: crate::tilemap::TilemapLayer::walls
+//! [`y_sort`]: crate::tilemap::TilemapLayer::y_sort
+//!
+//! # A dungeon
+//! ```
+//! use bevy_asset::prelude::*;
+//! use bevy_render::prelude::*;
+//! use bevy_tilemap::{prelude::*, testing::tilemap_test_harness, wall::WallSprite};
+//!
+//! let mut harness = tilemap_test_harness();
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(harness.texture_atlas().clone())
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(16, 16)
+//!     .add_layer(TilemapLayer { kind: LayerKind::Sparse, walls: true, ..Default::default() }, 1)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Sprite 4 is the top of a stone wall and sprite 5 is its side, which is
+//! // 24 pixels tall.
+//! tilemap.set_wall_sprite(4, WallSprite::new(5, 24.0));
+//! tilemap.insert_tile(Tile { point: (0, 0), sprite_index: 4, z_order: 1, ..Default::default() }).unwrap();
+//! tilemap.spawn_chunk((0, 0)).unwrap();
+//! let entity = harness.spawn_tilemap(tilemap);
+//! harness.tick();
+//!
+//! // The side faces of the 16 tiles of the chunk come after their tops.
+//! let indexes = harness.chunk_tile_indexes(entity, (0, 0), 1).unwrap();
+//! assert_eq!(indexes.len(), 32);
+//! assert_eq!(indexes[2 * 4 + 2], 4);
+//! assert_eq!(indexes[16 + 2 * 4 + 2], 5);
+//! ```
+
+use crate::lib::*;
+
+/// How the tiles of a sprite index are extruded into a wall.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WallSprite {
+    /// The sprite index of the side of the wall.
+    pub side_index: usize,
+    /// How far the top of the wall is raised above the floor, in pixels.
+    pub height: f32,
+}
+
+impl WallSprite {
+    /// Constructs a new wall with the sprite index of its side and its height
+    /// in pixels.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::wall::WallSprite;
+    ///
+    /// let wall = WallSprite::new(5, 24.0);
+    ///
+    /// assert_eq!(wall.side_index, 5);
+    /// assert_eq!(wall.height, 24.0);
+    /// ```
+    pub fn new(side_index: usize, height: f32) -> WallSprite {
+        WallSprite { side_index, height }
+    }
+
+    /// Returns how far each corner of the top face and then of the side face
+    /// of a tile of the height is moved, in the order of the vertices of its
+    /// quad: bottom left, top left, top right and bottom right.
+    #[cfg(feature = "render")]
+    pub(crate) fn corner_offsets(&self, tile_height: f32) -> ([f32; 4], [f32; 4]) {
+        let side_top = self.height - tile_height;
+        ([self.height; 4], [0.0, side_top, side_top, 0.0])
+    }
+}
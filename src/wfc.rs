@@ -0,0 +1,534 @@
+//! Wave function collapse, which fills regions with tiles that fit together.
+//!
+//! [`WfcRules`] are the sprite indexes which can be placed, with how often,
+//! and which of them can be next to each other horizontally and vertically.
+//! They are either written by hand or learned from an example grid with
+//! [`WfcRules::from_example`]. [`WfcRules::generate`] then fills a region
+//! with tiles where every pair of neighbours is allowed, one tile at a time
+//! from the most constrained point, such as for the rooms of a roguelike or
+//! the chunks of an overworld.
+//!
+//! The tiles are picked from a seed, so the same seed always fills a region
+//! the same way. The tiles just outside of the region are given to the
+//! generator, so that a chunk which is generated next to another fits with
+//! it. The generator does not need the tilemap, so it can run on another
+//! thread, such as in a task of the `AsyncComputeTaskPool`, and its tiles be
+//! inserted once it is done. [`Tilemap::fill_wfc`] does both at once.
+//!
+//! [`Tilemap::fill_wfc`]: crate::tilemap::Tilemap::fill_wfc
+//!
+//! # Filling a chunk
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{prelude::*, wfc::WfcRules};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(8, 8)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! // Water (1) is never next to land (3) without sand (2) between them.
+//! let example = [
+//!     1, 1, 2, 3,
+//!     1, 2, 3, 3,
+//!     2, 3, 3, 3,
+//! ];
+//! let rules = WfcRules::from_example(4, 3, &example).unwrap();
+//!
+//! tilemap.fill_wfc(&rules, (-4, -4), (8, 8), 0, 7).unwrap();
+//!
+//! for y in -4..4 {
+//!     for x in -4..3 {
+//!         let left = tilemap.get_tile((x, y), 0).unwrap().index;
+//!         let right = tilemap.get_tile((x + 1, y), 0).unwrap().index;
+//!         assert!(!(left == 1 && right == 3) && !(left == 3 && right == 1));
+//!     }
+//! }
+//! ```
+
+use crate::{
+    lib::*,
+    tile::Tile,
+    tilemap::{ErrorKind, TilemapResult, EMPTY_GRID_TILE},
+};
+
+/// How many times a region is tried again from the start when it runs into
+/// a point where no tile fits.
+const ATTEMPTS: u32 = 10;
+
+/// The offsets of the neighbours of a point. The opposite of each direction
+/// is the one next to it, at its index with the lowest bit flipped.
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// The sprite indexes which can be placed by wave function collapse and
+/// which of them can be next to each other.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct WfcRules {
+    /// The sprite indexes which can be placed, with how often they are
+    /// picked relative to each other. Those with a weight of 0 are never
+    /// placed.
+    pub weights: HashMap<usize, u32>,
+    /// The pairs of sprite indexes which can be next to each other, the
+    /// first on the left of the second.
+    pub horizontal: HashSet<(usize, usize)>,
+    /// The pairs of sprite indexes which can be next to each other, the
+    /// first below the second.
+    pub vertical: HashSet<(usize, usize)>,
+}
+
+impl WfcRules {
+    /// Constructs new rules without any tiles.
+    pub fn new() -> WfcRules {
+        WfcRules::default()
+    }
+
+    /// Learns rules from an example grid of sprite indexes, where each
+    /// sprite index is as likely as it is common and the pairs of neighbours
+    /// of the example are allowed.
+    ///
+    /// The grid is in the same row-major order from the top row down as in
+    /// [`Tilemap::from_grid`], and its points which are [`EMPTY_GRID_TILE`]
+    /// are skipped.
+    ///
+    /// [`Tilemap::from_grid`]: crate::tilemap::Tilemap::from_grid
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::wfc::WfcRules;
+    ///
+    /// let rules = WfcRules::from_example(3, 2, &[1, 1, 2, 2, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(rules.weights[&2], 3);
+    /// assert!(rules.horizontal.contains(&(1, 2)));
+    /// assert!(!rules.horizontal.contains(&(2, 1)));
+    /// assert!(rules.vertical.contains(&(3, 2)));
+    ///
+    /// assert!(WfcRules::from_example(3, 2, &[1, 2]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid does not have `width * height` sprite
+    /// indexes.
+    pub fn from_example(width: u32, height: u32, grid: &[u32]) -> TilemapResult<WfcRules> {
+        let expected = width as usize * height as usize;
+        if grid.len() != expected {
+            return Err(ErrorKind::MismatchedGrid(grid.len(), expected).into());
+        }
+        let at = |column: usize, row: usize| {
+            if column >= width as usize {
+                return None;
+            }
+            grid.get(row * width as usize + column)
+                .filter(|index| **index != EMPTY_GRID_TILE)
+                .map(|index| *index as usize)
+        };
+        let mut rules = WfcRules::new();
+        for row in 0..height as usize {
+            for column in 0..width as usize {
+                let index = match at(column, row) {
+                    Some(index) => index,
+                    None => continue,
+                };
+                *rules.weights.entry(index).or_insert(0) += 1;
+                if let Some(right) = at(column + 1, row) {
+                    rules.horizontal.insert((index, right));
+                }
+                // The rows go from the top down, so the next row is below.
+                if let Some(below) = at(column, row + 1) {
+                    rules.vertical.insert((below, index));
+                }
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Adds a sprite index which can be placed with its weight.
+    pub fn with_tile(mut self, sprite_index: usize, weight: u32) -> WfcRules {
+        self.weights.insert(sprite_index, weight);
+        self
+    }
+
+    /// Allows a sprite index to be on the left of another.
+    pub fn with_horizontal(mut self, left: usize, right: usize) -> WfcRules {
+        self.horizontal.insert((left, right));
+        self
+    }
+
+    /// Allows a sprite index to be below another.
+    pub fn with_vertical(mut self, below: usize, above: usize) -> WfcRules {
+        self.vertical.insert((below, above));
+        self
+    }
+
+    /// Fills the region of the dimensions from its bottom left point with
+    /// tiles on the layer, picked with the seed, and returns them.
+    ///
+    /// The sprite index of the tile at a point just outside of the region is
+    /// given by the border closure, or `None` if there is no tile there. The
+    /// tiles on the edges of the region fit with those which are of the
+    /// rules.
+    ///
+    /// # Examples
+    /// ```
+    /// use bevy_tilemap::wfc::WfcRules;
+    ///
+    /// // A checkerboard of 1 and 2.
+    /// let rules = WfcRules::new()
+    ///     .with_tile(1, 1)
+    ///     .with_tile(2, 1)
+    ///     .with_horizontal(1, 2)
+    ///     .with_horizontal(2, 1)
+    ///     .with_vertical(1, 2)
+    ///     .with_vertical(2, 1);
+    ///
+    /// // The tile on the left of the region is a 2.
+    /// let border = |point: (i32, i32)| if point == (-1, 0) { Some(2) } else { None };
+    /// let tiles = rules.generate((0, 0), (3, 3), 0, 99, |point| border((point.x, point.y))).unwrap();
+    ///
+    /// assert_eq!(tiles.len(), 9);
+    /// for tile in tiles.iter() {
+    ///     let expected = if (tile.point.x + tile.point.y) % 2 == 0 { 1 } else { 2 };
+    ///     assert_eq!(tile.sprite_index, expected);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no tiles fit the region, or if they could not be
+    /// fitted after a few attempts.
+    pub fn generate<P, F>(
+        &self,
+        from: P,
+        dimensions: (u32, u32),
+        z_order: usize,
+        seed: u64,
+        border: F,
+    ) -> TilemapResult<Vec<Tile<Point2>>>
+    where
+        P: Into<Point2>,
+        F: Fn(Point2) -> Option<usize>,
+    {
+        let from: Point2 = from.into();
+        let (width, height) = (dimensions.0 as i32, dimensions.1 as i32);
+        if width == 0 || height == 0 {
+            return Ok(Vec::new());
+        }
+        let tileset = Tileset::new(self);
+        let mut rng = Rng(seed);
+        for _ in 0..ATTEMPTS {
+            let mut wave = Wave::new(width, height, tileset.len());
+            if !wave.constrain_border(&tileset, from, &border) {
+                break;
+            }
+            if !wave.collapse(&tileset, &mut rng) {
+                continue;
+            }
+            let tiles = wave
+                .collapsed()
+                .map(|(x, y, tile)| Tile {
+                    point: Point2::new(from.x + x, from.y + y),
+                    sprite_index: tileset.sprite_index(tile),
+                    z_order,
+                    ..Default::default()
+                })
+                .collect();
+            return Ok(tiles);
+        }
+        Err(ErrorKind::WfcContradiction(from).into())
+    }
+}
+
+/// The sprite indexes of rules which can be placed, in order, and which of
+/// them can be next to each other in each direction.
+struct Tileset {
+    /// The sprite indexes and their weights.
+    tiles: Vec<(usize, u32)>,
+    /// If a tile can be at a direction from another, at
+    /// `(direction * len + tile) * len + other`.
+    allowed: Vec<bool>,
+}
+
+impl Tileset {
+    /// Constructs the tileset of the rules.
+    fn new(rules: &WfcRules) -> Tileset {
+        let mut tiles: Vec<(usize, u32)> = rules
+            .weights
+            .iter()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(sprite_index, weight)| (*sprite_index, *weight))
+            .collect();
+        tiles.sort_unstable();
+        let len = tiles.len();
+        let mut allowed = vec![false; DIRECTIONS.len() * len * len];
+        let position = |sprite_index: usize| tiles.iter().position(|(s, _)| *s == sprite_index);
+        let mut allow = |direction: usize, tile: usize, other: usize| {
+            if let Some(allowed) = allowed.get_mut((direction * len + tile) * len + other) {
+                *allowed = true;
+            }
+        };
+        for (left, right) in rules.horizontal.iter() {
+            if let (Some(left), Some(right)) = (position(*left), position(*right)) {
+                allow(0, left, right);
+                allow(1, right, left);
+            }
+        }
+        for (below, above) in rules.vertical.iter() {
+            if let (Some(below), Some(above)) = (position(*below), position(*above)) {
+                allow(2, below, above);
+                allow(3, above, below);
+            }
+        }
+        Tileset { tiles, allowed }
+    }
+
+    /// Returns how many tiles there are.
+    fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Returns the sprite index of a tile.
+    fn sprite_index(&self, tile: usize) -> usize {
+        self.tiles
+            .get(tile)
+            .map_or(0, |(sprite_index, _)| *sprite_index)
+    }
+
+    /// Returns the weight of a tile.
+    fn weight(&self, tile: usize) -> u64 {
+        self.tiles
+            .get(tile)
+            .map_or(0, |(_, weight)| u64::from(*weight))
+    }
+
+    /// Returns the tile of a sprite index, if it can be placed.
+    fn tile_of(&self, sprite_index: usize) -> Option<usize> {
+        self.tiles.iter().position(|(s, _)| *s == sprite_index)
+    }
+
+    /// Returns `true` if the other tile can be at the direction from the
+    /// tile.
+    fn allows(&self, direction: usize, tile: usize, other: usize) -> bool {
+        let len = self.len();
+        self.allowed
+            .get((direction * len + tile) * len + other)
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// The tiles which can still be placed at each point of a region.
+struct Wave {
+    /// The width of the region.
+    width: i32,
+    /// The height of the region.
+    height: i32,
+    /// How many tiles there are.
+    len: usize,
+    /// If a tile can still be placed at a point, at `cell * len + tile`
+    /// where the cell of a point is `y * width + x`.
+    possible: Vec<bool>,
+    /// How many tiles can still be placed at each cell.
+    remaining: Vec<usize>,
+}
+
+impl Wave {
+    /// Constructs a wave where every tile can be placed everywhere.
+    fn new(width: i32, height: i32, len: usize) -> Wave {
+        let cells = (width * height) as usize;
+        Wave {
+            width,
+            height,
+            len,
+            possible: vec![true; cells * len],
+            remaining: vec![len; cells],
+        }
+    }
+
+    /// Returns the cell of a point, if it is in the region.
+    fn cell(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((y * self.width + x) as usize)
+    }
+
+    /// Returns `true` if the tile can still be placed at the cell.
+    fn is_possible(&self, cell: usize, tile: usize) -> bool {
+        self.possible
+            .get(cell * self.len + tile)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Returns how many tiles can still be placed at the cell.
+    fn remaining(&self, cell: usize) -> usize {
+        self.remaining.get(cell).copied().unwrap_or(0)
+    }
+
+    /// Stops the tile from being placed at the cell.
+    fn ban(&mut self, cell: usize, tile: usize) {
+        if let Some(possible) = self.possible.get_mut(cell * self.len + tile) {
+            if *possible {
+                *possible = false;
+                if let Some(remaining) = self.remaining.get_mut(cell) {
+                    *remaining -= 1;
+                }
+            }
+        }
+    }
+
+    /// Bans the tiles on the edges of the region which do not fit with the
+    /// tiles just outside of it, returning `false` if a point is left
+    /// without any.
+    fn constrain_border<F>(&mut self, tileset: &Tileset, from: Point2, border: &F) -> bool
+    where
+        F: Fn(Point2) -> Option<usize>,
+    {
+        let mut changed = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+                    if self.cell(x + dx, y + dy).is_some() {
+                        continue;
+                    }
+                    let outside = Point2::new(from.x + x + dx, from.y + y + dy);
+                    let tile = match border(outside).and_then(|s| tileset.tile_of(s)) {
+                        Some(tile) => tile,
+                        None => continue,
+                    };
+                    let cell = match self.cell(x, y) {
+                        Some(cell) => cell,
+                        None => continue,
+                    };
+                    // The point is at the opposite direction from the tile.
+                    for other in 0..self.len {
+                        if !tileset.allows(direction ^ 1, tile, other) {
+                            self.ban(cell, other);
+                        }
+                    }
+                    changed.push(cell);
+                }
+            }
+        }
+        if changed.iter().any(|cell| self.remaining(*cell) == 0) {
+            return false;
+        }
+        self.propagate(tileset, changed)
+    }
+
+    /// Bans the tiles which no longer fit next to the cells which changed,
+    /// and then next to those, returning `false` if a point is left without
+    /// any.
+    fn propagate(&mut self, tileset: &Tileset, mut changed: Vec<usize>) -> bool {
+        while let Some(cell) = changed.pop() {
+            let (x, y) = (cell as i32 % self.width, cell as i32 / self.width);
+            for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+                let neighbor = match self.cell(x + dx, y + dy) {
+                    Some(neighbor) => neighbor,
+                    None => continue,
+                };
+                let mut banned = false;
+                for other in 0..self.len {
+                    if !self.is_possible(neighbor, other) {
+                        continue;
+                    }
+                    let supported = (0..self.len).any(|tile| {
+                        self.is_possible(cell, tile) && tileset.allows(direction, tile, other)
+                    });
+                    if !supported {
+                        self.ban(neighbor, other);
+                        banned = true;
+                    }
+                }
+                if banned {
+                    if self.remaining(neighbor) == 0 {
+                        return false;
+                    }
+                    changed.push(neighbor);
+                }
+            }
+        }
+        true
+    }
+
+    /// Places a tile at the most constrained point, one at a time, until
+    /// every point has one, returning `false` if a point is left without
+    /// any.
+    fn collapse(&mut self, tileset: &Tileset, rng: &mut Rng) -> bool {
+        loop {
+            let fewest = match self.remaining.iter().filter(|r| **r > 1).min() {
+                Some(fewest) => *fewest,
+                None => return self.remaining.iter().all(|r| *r == 1),
+            };
+            let candidates: Vec<usize> = (0..self.remaining.len())
+                .filter(|cell| self.remaining(*cell) == fewest)
+                .collect();
+            let cell = match candidates.get(rng.below(candidates.len() as u64) as usize) {
+                Some(cell) => *cell,
+                None => return false,
+            };
+            let total: u64 = (0..self.len)
+                .filter(|tile| self.is_possible(cell, *tile))
+                .map(|tile| tileset.weight(tile))
+                .sum();
+            let mut roll = rng.below(total);
+            let mut picked = None;
+            for tile in (0..self.len).filter(|tile| self.is_possible(cell, *tile)) {
+                let weight = tileset.weight(tile);
+                if picked.is_none() && roll < weight {
+                    picked = Some(tile);
+                }
+                roll = roll.saturating_sub(weight);
+            }
+            for tile in 0..self.len {
+                if Some(tile) != picked {
+                    self.ban(cell, tile);
+                }
+            }
+            if !self.propagate(tileset, vec![cell]) {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the points of the region with the tile placed at each.
+    fn collapsed(&self) -> impl Iterator<Item = (i32, i32, usize)> + '_ {
+        (0..self.remaining.len()).filter_map(move |cell| {
+            let tile = (0..self.len).find(|tile| self.is_possible(cell, *tile))?;
+            let (x, y) = (cell as i32 % self.width, cell as i32 / self.width);
+            Some((x, y, tile))
+        })
+    }
+}
+
+/// A small random number generator, SplitMix64, which gives the same numbers
+/// for a seed on every platform.
+struct Rng(u64);
+
+impl Rng {
+    /// Returns the next random number.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut hash = self.0;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^ (hash >> 31)
+    }
+
+    /// Returns a random number below the bound, or 0 if it is 0.
+    fn below(&mut self, bound: u64) -> u64 {
+        match bound {
+            0 => 0,
+            bound => self.next() % bound,
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Points of worlds which are larger than the points of a tilemap.
+//!
+//! The points of tiles and chunks are `i32`, and the translations they are
+//! drawn at are `f32` which lose precision far away from the origin. Endless
+//! procedural worlds instead keep their tilemap near the origin with
+//! [`Tilemap::shift_origin`], and address their tiles with a [`WorldPoint`]
+//! which is `i64`. The tilemap keeps the world point of its origin, so its
+//! points are turned into world points with [`Tilemap::point_to_world`] and
+//! back with [`Tilemap::world_to_point`] for as long as they are near enough
+//! to the origin.
+//!
+//! Worlds whose tile points should stay the same instead keep the chunks near
+//! the camera at small translations with [`Tilemap::set_translation_origin`].
+//!
+//! [`Tilemap::shift_origin`]: crate::tilemap::Tilemap::shift_origin
+//! [`Tilemap::set_translation_origin`]: crate::tilemap::Tilemap::set_translation_origin
+//! [`Tilemap::point_to_world`]: crate::tilemap::Tilemap::point_to_world
+//! [`Tilemap::world_to_point`]: crate::tilemap::Tilemap::world_to_point
+//!
+//! # Travelling far away
+//! ```
+//! use bevy_asset::{prelude::*, HandleId};
+//! use bevy_sprite::prelude::*;
+//! use bevy_tilemap::{prelude::*, world::WorldPoint};
+//!
+//! // In production use a strong handle from an actual source.
+//! let texture_atlas_handle = Handle::weak(HandleId::random::<TextureAtlas>());
+//!
+//! let mut tilemap = TilemapBuilder::new()
+//!     .texture_atlas(texture_atlas_handle)
+//!     .chunk_dimensions(4, 4)
+//!     .tile_dimensions(32, 32)
+//!     .auto_chunk()
+//!     .finish()
+//!     .unwrap();
+//!
+//! let far = WorldPoint::new(4_000_000_001, -1);
+//! assert!(tilemap.world_to_point(far).is_err());
+//!
+//! // The origin is shifted along the way as the camera travels.
+//! tilemap.shift_origin_to((500_000_000, 0)).unwrap();
+//! tilemap.shift_origin_to((1_000_000_000, 0)).unwrap();
+//! assert_eq!(tilemap.world_point_to_chunk_point(far), tilemap.origin());
+//!
+//! let point = tilemap.world_to_point(far).unwrap();
+//! tilemap.insert_tile(Tile { point, sprite_index: 3, ..Default::default() }).unwrap();
+//!
+//! assert_eq!(point, (1, -1).into());
+//! assert_eq!(tilemap.point_to_world(point), far);
+//! ```
+
+use crate::lib::*;
+
+/// A point of a tile or chunk in a world, which is `i64` unlike the points of
+/// a tilemap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct WorldPoint {
+    /// X value of the point.
+    pub x: i64,
+    /// Y value of the point.
+    pub y: i64,
+}
+
+impl WorldPoint {
+    /// Constructs a new world point with a X,Y coordinate.
+    pub fn new(x: i64, y: i64) -> WorldPoint {
+        WorldPoint { x, y }
+    }
+}
+
+impl Display for WorldPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for WorldPoint {
+    fn from((x, y): (i64, i64)) -> WorldPoint {
+        WorldPoint { x, y }
+    }
+}
+
+impl From<Point2> for WorldPoint {
+    fn from(point: Point2) -> WorldPoint {
+        WorldPoint {
+            x: i64::from(point.x),
+            y: i64::from(point.y),
+        }
+    }
+}